@@ -0,0 +1,84 @@
+//! Benchmarking of the parsing of function bodies.
+
+use arbitrary::{Arbitrary as _, Unstructured};
+use criterion::{black_box, BenchmarkId};
+use rand::{RngCore as _, SeedableRng as _};
+use wasmiter::{component::CodeSection, sections::id};
+
+/// Generates random modules, only keeping the contents of their code sections.
+fn code_sections(unstructured_size: usize, count: usize) -> Vec<Vec<u8>> {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(0x5EED);
+    let mut unstructured = vec![0u8; unstructured_size];
+    let mut sections = Vec::with_capacity(count);
+    while sections.len() < count {
+        rng.fill_bytes(&mut unstructured);
+        let wasm = wasm_smith::Module::arbitrary_take_rest(Unstructured::new(&unstructured))
+            .unwrap()
+            .to_bytes();
+
+        for result in wasmiter::parse_module_sections(wasm.as_slice()).unwrap() {
+            let section = result.unwrap();
+            if section.id() == id::CODE {
+                let contents = section.contents();
+                let start = usize::try_from(contents.base()).unwrap();
+                let end = start + usize::try_from(contents.length()).unwrap();
+                sections.push(wasm[start..end].to_vec());
+            }
+        }
+    }
+
+    sections
+}
+
+fn parse_instructions(code_section: &[u8]) -> usize {
+    let mut count = 0;
+    for result in CodeSection::new(0, code_section).unwrap() {
+        result
+            .unwrap()
+            .read(
+                |_| wasmiter::parser::Parsed::Ok(()),
+                |(), code| {
+                    while code
+                        .next(|instruction| {
+                            black_box(instruction);
+                            wasmiter::parser::Parsed::Ok(())
+                        })
+                        .transpose()?
+                        .is_some()
+                    {
+                        count += 1;
+                    }
+                    wasmiter::parser::Parsed::Ok(())
+                },
+            )
+            .unwrap();
+    }
+    count
+}
+
+fn instructions(c: &mut criterion::Criterion) {
+    let mut group = c.benchmark_group("instructions");
+
+    for size in [0x1000usize, 0x10000] {
+        let sections = code_sections(size, 16);
+        let total_length = sections.iter().map(Vec::len).sum::<usize>();
+        group.throughput(criterion::Throughput::Bytes(total_length as u64));
+        group.bench_with_input(
+            BenchmarkId::new("wasmiter", size),
+            &sections,
+            |b, sections| {
+                b.iter(|| {
+                    sections
+                        .iter()
+                        .map(|section| parse_instructions(section))
+                        .sum::<usize>()
+                })
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion::criterion_group!(benchmarks, instructions);
+criterion::criterion_main!(benchmarks);
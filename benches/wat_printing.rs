@@ -37,7 +37,7 @@ fn printing(c: &mut criterion::Criterion) {
                     wasmiter::parse_module_sections(wasm.as_slice())
                         .unwrap()
                         .display_module()
-                );
+                )
             }),
         );
         group.bench_with_input(
@@ -50,5 +50,41 @@ fn printing(c: &mut criterion::Criterion) {
     group.finish();
 }
 
-criterion::criterion_group!(benchmarks, printing);
+fn custom_sections(c: &mut criterion::Criterion) {
+    // Resembles a debug build, with a name for every function and DWARF sections
+    let mut text = String::from("(module");
+    for i in 0..50000 {
+        text.push_str(&format!(" (func $function_number_{i})"));
+    }
+    text.push(')');
+
+    let mut wasm = wat::parse_str(&text).unwrap();
+    for name in [".debug_info", ".debug_line"] {
+        let contents = vec![0xAAu8; 0x40000];
+        let length = name.len() + 1 + contents.len();
+        leb128::write::unsigned(&mut wasm, 0).unwrap();
+        leb128::write::unsigned(&mut wasm, length as u64).unwrap();
+        leb128::write::unsigned(&mut wasm, name.len() as u64).unwrap();
+        wasm.extend_from_slice(name.as_bytes());
+        wasm.extend_from_slice(&contents);
+    }
+
+    let sections = wasmiter::parse_module_sections(wasm.as_slice()).unwrap();
+    let mut group = c.benchmark_group("custom_sections");
+    group.throughput(criterion::Throughput::Bytes(wasm.len() as u64));
+    group.bench_function("decoded", |b| {
+        b.iter(|| sections.display_module().to_string())
+    });
+    group.bench_function("opaque", |b| {
+        b.iter(|| {
+            sections
+                .display_module()
+                .without_custom_sections()
+                .to_string()
+        })
+    });
+    group.finish();
+}
+
+criterion::criterion_group!(benchmarks, printing, custom_sections);
 criterion::criterion_main!(benchmarks);
@@ -0,0 +1,4 @@
+fn main() {
+    // Set by docs.rs, see `package.metadata.docs.rs` in the manifest
+    println!("cargo:rustc-check-cfg=cfg(doc_cfg)");
+}
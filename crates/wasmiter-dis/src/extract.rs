@@ -0,0 +1,73 @@
+//! Implementation of the `--extract-section` flag.
+
+use wasmiter::{
+    custom::CustomSection,
+    input::{BorrowInput as _, Input},
+    sections::SectionSequence,
+};
+
+/// Describes a section to copy, and where to copy its contents to.
+#[derive(Clone, Debug)]
+pub(crate) struct Extraction {
+    /// The name of a custom section, or the *id* of any other section.
+    section: String,
+    destination: std::path::PathBuf,
+}
+
+impl std::str::FromStr for Extraction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s.split_once('=') {
+            Some((section, destination)) if !section.is_empty() && !destination.is_empty() => {
+                Ok(Self {
+                    section: section.to_string(),
+                    destination: destination.into(),
+                })
+            }
+            _ => Err(format!("expected NAME=PATH, but got {s:?}")),
+        }
+    }
+}
+
+/// Copies the contents of the first section matching each [`Extraction`] to a file.
+pub(crate) fn extract_sections<I: Input>(
+    sections: &SectionSequence<I>,
+    extractions: &[Extraction],
+) -> Result<(), Box<dyn std::error::Error>> {
+    for extraction in extractions {
+        let id = extraction.section.parse::<u8>().ok();
+        let mut found = false;
+        for result in sections.borrow_input() {
+            let section = result?;
+            let contents = if Some(section.id()) == id {
+                *section.contents()
+            } else {
+                match CustomSection::try_from_section(section) {
+                    Ok(custom) => {
+                        let custom = custom?;
+                        if !custom.name().try_eq_str(&extraction.section)? {
+                            continue;
+                        }
+                        *custom.contents()
+                    }
+                    Err(_) => continue,
+                }
+            };
+
+            let mut file = std::io::BufWriter::new(std::fs::File::create(&extraction.destination)?);
+            contents.copy_to(&mut file)?;
+            std::io::Write::flush(&mut file)?;
+            found = true;
+            break;
+        }
+
+        if !found {
+            return Err(
+                format!("module does not contain a {:?} section", extraction.section).into(),
+            );
+        }
+    }
+
+    Ok(())
+}
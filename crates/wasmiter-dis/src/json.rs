@@ -0,0 +1,200 @@
+//! Implementation of the `--format json` option.
+
+use std::io::Write;
+use wasmiter::{
+    input::Input,
+    manifest::{self, Manifest, Signature},
+    sections::{id as section_id, SectionSequence},
+    types::{FuncType, GlobalMutability, IdxType, Sharing, ValType},
+};
+
+/// Writes a JSON string literal, escaping characters as needed.
+fn write_string(output: &mut dyn Write, s: &str) -> std::io::Result<()> {
+    output.write_all(b"\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => output.write_all(b"\\\"")?,
+            '\\' => output.write_all(b"\\\\")?,
+            '\n' => output.write_all(b"\\n")?,
+            '\r' => output.write_all(b"\\r")?,
+            '\t' => output.write_all(b"\\t")?,
+            c if c.is_control() => write!(output, "\\u{:04x}", u32::from(c))?,
+            c => write!(output, "{c}")?,
+        }
+    }
+    output.write_all(b"\"")
+}
+
+/// Writes a JSON array, using the closure to write each element.
+fn write_array<T>(
+    output: &mut dyn Write,
+    items: impl IntoIterator<Item = T>,
+    mut f: impl FnMut(&mut dyn Write, T) -> std::io::Result<()>,
+) -> std::io::Result<()> {
+    output.write_all(b"[")?;
+    for (i, item) in items.into_iter().enumerate() {
+        if i > 0 {
+            output.write_all(b",")?;
+        }
+        f(output, item)?;
+    }
+    output.write_all(b"]")
+}
+
+fn write_optional_number(output: &mut dyn Write, n: Option<u64>) -> std::io::Result<()> {
+    match n {
+        Some(n) => write!(output, "{n}"),
+        None => output.write_all(b"null"),
+    }
+}
+
+fn write_types(output: &mut dyn Write, types: &[ValType]) -> std::io::Result<()> {
+    write_array(output, types, |output, ty| {
+        write_string(output, &ty.to_string())
+    })
+}
+
+fn write_func_type(output: &mut dyn Write, signature: &FuncType) -> std::io::Result<()> {
+    output.write_all(b"\"params\":")?;
+    write_types(output, signature.parameters())?;
+    output.write_all(b",\"results\":")?;
+    write_types(output, signature.results())
+}
+
+/// Writes the fields describing the kind and type of an import or export.
+fn write_signature(output: &mut dyn Write, signature: &Signature) -> std::io::Result<()> {
+    match signature {
+        Signature::Function(signature) => {
+            output.write_all(b"\"kind\":\"func\",")?;
+            write_func_type(output, signature)
+        }
+        Signature::Table(table) => {
+            output.write_all(b"\"kind\":\"table\",\"element_type\":")?;
+            write_string(output, &table.element_type().to_string())?;
+            write!(
+                output,
+                ",\"minimum\":{},\"maximum\":",
+                table.limits().minimum()
+            )?;
+            write_optional_number(output, table.limits().maximum())?;
+            write!(
+                output,
+                ",\"table64\":{}",
+                table.limits().index_type() == IdxType::I64
+            )
+        }
+        Signature::Memory(memory) => {
+            write!(
+                output,
+                "\"kind\":\"memory\",\"minimum\":{},\"maximum\":",
+                memory.minimum()
+            )?;
+            write_optional_number(output, memory.maximum())?;
+            write!(
+                output,
+                ",\"shared\":{},\"memory64\":{}",
+                memory.share() == Sharing::Shared,
+                memory.index_type() == IdxType::I64
+            )
+        }
+        Signature::Global(global) => {
+            output.write_all(b"\"kind\":\"global\",\"type\":")?;
+            write_string(output, &global.value_type().to_string())?;
+            write!(
+                output,
+                ",\"mutable\":{}",
+                global.mutability() == GlobalMutability::Variable
+            )
+        }
+        Signature::Tag(signature) => {
+            output.write_all(b"\"kind\":\"tag\",")?;
+            write_func_type(output, signature)
+        }
+        _ => output.write_all(b"\"kind\":\"unknown\""),
+    }
+}
+
+fn write_manifest(output: &mut dyn Write, manifest: &Manifest) -> std::io::Result<()> {
+    output.write_all(b"{\"name\":")?;
+    match manifest.name() {
+        Some(name) => write_string(output, name)?,
+        None => output.write_all(b"null")?,
+    }
+
+    output.write_all(b",\"sections\":")?;
+    write_array(output, manifest.sections(), |output, section| {
+        write!(output, "{{\"id\":{},\"name\":", section.id())?;
+        let name = section_id::name(section.id()).unwrap_or("Unknown");
+        write_string(output, &name.to_ascii_lowercase())?;
+        if let Some(custom_name) = section.custom_name() {
+            output.write_all(b",\"custom_name\":")?;
+            write_string(output, custom_name)?;
+        }
+        write!(
+            output,
+            ",\"offset\":{},\"size\":{}}}",
+            section.offset(),
+            section.length()
+        )
+    })?;
+
+    output.write_all(b",\"custom_sections\":")?;
+    write_array(
+        output,
+        manifest
+            .sections()
+            .iter()
+            .filter_map(manifest::SectionSummary::custom_name),
+        write_string,
+    )?;
+
+    output.write_all(b",\"imports\":")?;
+    write_array(output, manifest.imports(), |output, import| {
+        output.write_all(b"{\"module\":")?;
+        write_string(output, import.module())?;
+        output.write_all(b",\"name\":")?;
+        write_string(output, import.name())?;
+        output.write_all(b",")?;
+        write_signature(output, import.signature())?;
+        output.write_all(b"}")
+    })?;
+
+    output.write_all(b",\"exports\":")?;
+    write_array(output, manifest.exports(), |output, export| {
+        output.write_all(b"{\"name\":")?;
+        write_string(output, export.name())?;
+        output.write_all(b",")?;
+        write_signature(output, export.signature())?;
+        output.write_all(b"}")
+    })?;
+
+    output.write_all(b",\"functions\":")?;
+    write_array(
+        output,
+        manifest.functions().iter().enumerate(),
+        |output, (index, signature)| {
+            write!(output, "{{\"index\":{index},")?;
+            write_func_type(output, signature)?;
+            output.write_all(b"}")
+        },
+    )?;
+
+    output.write_all(b",\"features\":")?;
+    write_array(output, manifest.features(), |output, feature| {
+        write_string(output, feature.name())
+    })?;
+
+    output.write_all(b"}")
+}
+
+/// Writes a JSON object summarizing the sections, imports, exports, and function signatures of
+/// the module.
+pub(crate) fn write_summary<I: Input>(
+    sections: &SectionSequence<I>,
+    output: &mut dyn Write,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let manifest = manifest::manifest_sections(sections)?;
+    write_manifest(output, &manifest)?;
+    writeln!(output)?;
+    Ok(())
+}
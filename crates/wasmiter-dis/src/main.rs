@@ -6,10 +6,30 @@
 
 use clap::Parser;
 use std::io::Write;
+use wasmiter::{
+    component::{FuncsComponent, ImportKind, KnownSection},
+    input::{BorrowInput as _, Input},
+    sections::SectionSequence,
+};
+
+mod extract;
+mod json;
+mod parallel;
+mod verify;
+mod wast;
 
 // TODO: How compatible with [`wasm2wat`] should this be?
 // [`wasm2wat`]: https://webassembly.github.io/wabt/doc/wasm2wat.1.html
 
+/// The kind of output to generate.
+#[derive(Clone, Copy, Eq, PartialEq, clap::ValueEnum)]
+enum Format {
+    /// WebAssembly Text
+    Wat,
+    /// A JSON object summarizing the module's sections, imports, exports, and function signatures
+    Json,
+}
+
 #[derive(Parser)]
 #[command(version, about)]
 struct Cli {
@@ -18,6 +38,146 @@ struct Cli {
     /// Where to write the generated WebAssembly Text, defaults to stdout
     #[arg(short, long)]
     output: Option<std::path::PathBuf>,
+    /// Writes the module skeleton to `module.wat` and each function to a separate `func_N.wat`
+    /// file in the given directory, instead of a single output
+    #[arg(long, value_name = "DIR", conflicts_with = "output")]
+    split_output: Option<std::path::PathBuf>,
+    /// Parses and validates the entire module before printing, reporting the first error
+    /// encountered along with its offset and function index
+    #[arg(long)]
+    verify: bool,
+    /// Annotates instructions with the relocations that apply to them, if the module contains a
+    /// relocation section for the code section
+    #[arg(long, conflicts_with = "split_output")]
+    relocations: bool,
+    /// The number of threads used to render function bodies, which are then written in order
+    #[arg(
+        short,
+        long,
+        default_value = "1",
+        conflicts_with_all = ["split_output", "relocations"]
+    )]
+    jobs: std::num::NonZeroUsize,
+    /// Copies the contents of the custom section with the given NAME, or the section with the
+    /// given numeric id, to a file at PATH instead of generating WebAssembly Text
+    #[arg(long, value_name = "NAME=PATH")]
+    extract_section: Vec<extract::Extraction>,
+    /// Writes a WebAssembly script (`.wast`) containing the module followed by an `assert_return`
+    /// command for each exported function, with placeholder arguments and results to fill in
+    #[arg(long, conflicts_with = "split_output")]
+    wast: bool,
+    /// The format of the output
+    #[arg(
+        long,
+        value_enum,
+        default_value = "wat",
+        conflicts_with_all = ["split_output", "relocations", "wast"]
+    )]
+    format: Format,
+    /// Prints the offset, size, and number of entries of each section, similar to
+    /// `wasm-objdump -h`, instead of generating WebAssembly Text
+    #[arg(long, conflicts_with_all = ["split_output", "relocations", "wast", "format"])]
+    headers: bool,
+    /// Prints the entries within each section, similar to `wasm-objdump -x`, instead of generating
+    /// WebAssembly Text
+    #[arg(
+        short = 'x',
+        long,
+        conflicts_with_all = ["split_output", "relocations", "wast", "format"]
+    )]
+    details: bool,
+    /// Prints the size of each section and function body, sorted from largest to smallest, instead
+    /// of generating WebAssembly Text. Function names are looked up in the module's name section
+    #[arg(
+        long,
+        conflicts_with_all = ["split_output", "relocations", "wast", "format", "headers", "details"]
+    )]
+    size: bool,
+    /// Only writes the function with the given index or name, skipping the bodies of all other
+    /// functions. Names are looked up in the module's name section
+    #[arg(
+        long,
+        value_name = "INDEX|NAME",
+        conflicts_with_all = ["split_output", "relocations", "wast", "format", "headers", "details", "size"]
+    )]
+    function: Option<String>,
+}
+
+fn write_file(
+    path: &std::path::Path,
+    contents: impl std::fmt::Display,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut buffered = std::io::BufWriter::new(std::fs::File::create(path)?);
+    writeln!(&mut buffered, "{contents}")?;
+    buffered.flush()?;
+    Ok(())
+}
+
+fn write_split_output<I: Input>(
+    sections: &SectionSequence<I>,
+    directory: &std::path::Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    std::fs::create_dir_all(directory)?;
+    write_file(
+        &directory.join("module.wat"),
+        sections.display_module().without_function_bodies(),
+    )?;
+
+    let mut imported_function_count = 0u32;
+    let mut function_types = None;
+    for result in sections.borrow_input() {
+        let known = match KnownSection::interpret(result?) {
+            Ok(known) => known?,
+            Err(_) => continue,
+        };
+
+        match known {
+            KnownSection::Import(mut imports) => {
+                while let Some(import) = imports.parse()? {
+                    if let ImportKind::Function(_) = import.kind() {
+                        imported_function_count += 1;
+                    }
+                }
+            }
+            KnownSection::Function(types) => function_types = Some(types),
+            KnownSection::Code(code) => {
+                if let Some(types) = function_types.take() {
+                    let funcs = FuncsComponent::new(types, code)?;
+                    for (index, result) in (imported_function_count..).zip(funcs) {
+                        write_file(&directory.join(format!("func_{index}.wat")), result?)?;
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+
+    Ok(())
+}
+
+/// Resolves the `--function` argument, which is either a function index or a name in the name
+/// section.
+fn resolve_function<I: Input>(
+    sections: &SectionSequence<I>,
+    function: &str,
+) -> Result<wasmiter::index::FuncIdx, Box<dyn std::error::Error>> {
+    let index = match function.parse::<u32>() {
+        Ok(index) => index.into(),
+        Err(_) => sections
+            .find_function_by_name(function)?
+            .ok_or_else(|| format!("no function named {function:?} was found"))?,
+    };
+
+    let count = sections.function_count()?;
+    if index.to_u32() >= count {
+        return Err(format!(
+            "function index {} is out of bounds, the module contains {count} functions",
+            index.to_u32()
+        )
+        .into());
+    }
+
+    Ok(index)
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -25,13 +185,28 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     cfg_if::cfg_if! {
         if #[cfg(any(unix, windows))] {
-            let sections = wasmiter::parse_module_sections_from_mmap_file(cli.file)?;
+            let sections = wasmiter::parse_module_sections_from_mmap_file(&cli.file)?;
         } else {
-            let bytes = std::fs::read(cli.file)?;
+            let bytes = std::fs::read(&cli.file)?;
             let sections = wasmiter::parse_module_sections(bytes.as_slice())?;
         }
     };
 
+    if cli.verify {
+        if let Err(diagnostic) = verify::verify(&sections, &cli.file) {
+            eprintln!("{diagnostic}");
+            std::process::exit(1);
+        }
+    }
+
+    if !cli.extract_section.is_empty() {
+        return extract::extract_sections(&sections, &cli.extract_section);
+    }
+
+    if let Some(directory) = cli.split_output.as_ref() {
+        return write_split_output(&sections, directory);
+    }
+
     let mut file;
     let mut stdout;
 
@@ -44,7 +219,37 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     };
 
     let mut buffered = std::io::BufWriter::new(output);
-    writeln!(&mut buffered, "{}", sections.display_module())?;
+    if let Some(function) = cli.function.as_deref() {
+        let index = resolve_function(&sections, function)?;
+        writeln!(&mut buffered, "{}", sections.display_function(index))?;
+    } else if cli.size {
+        let profile = wasmiter::analysis::size_profile(&sections, true)?;
+        write!(&mut buffered, "{profile}")?;
+    } else if cli.headers || cli.details {
+        writeln!(
+            &mut buffered,
+            "\n{}:\tfile format wasm 0x1\n",
+            cli.file.display()
+        )?;
+        if cli.headers {
+            let headers = wasmiter::dump::headers(&sections);
+            writeln!(&mut buffered, "Sections:\n\n{headers}")?;
+        }
+        if cli.details {
+            let details = wasmiter::dump::details(&sections);
+            writeln!(&mut buffered, "Section Details:\n\n{details}")?;
+        }
+    } else if cli.format == Format::Json {
+        json::write_summary(&sections, &mut buffered)?;
+    } else if cli.relocations {
+        let display = sections.display_module().with_relocation_annotations();
+        writeln!(&mut buffered, "{display}")?;
+    } else {
+        parallel::write_module(&sections, cli.jobs, &mut buffered)?;
+    }
+    if cli.wast {
+        wast::write_assertions(&sections, &mut buffered)?;
+    }
     buffered.flush()?;
 
     Ok(())
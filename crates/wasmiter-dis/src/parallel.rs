@@ -0,0 +1,114 @@
+//! Implementation of the `--jobs` flag, which renders function bodies on multiple threads.
+
+use std::{io::Write, num::NonZeroUsize};
+use wasmiter::{
+    component::KnownSection,
+    input::{BorrowInput as _, Input},
+    sections::SectionSequence,
+};
+
+/// Finds the position of the *code section* in the module, along with the number of functions it
+/// contains.
+///
+/// Returns `None` if the module does not contain a *code section*, or if it could not be parsed.
+fn find_code_section<I: Input>(sections: &SectionSequence<I>) -> Option<(usize, u32)> {
+    for (index, result) in sections.borrow_input().enumerate() {
+        if let Ok(Ok(KnownSection::Code(code))) = KnownSection::interpret(result.ok()?) {
+            return Some((index, code.remaining_count()));
+        }
+    }
+
+    None
+}
+
+/// Renders the functions in the *code section* at `code_index`, splitting the `count` functions
+/// into `jobs` contiguous chunks that are each rendered on their own thread.
+///
+/// Each chunk is rendered along with the rest of the module, so that functions are written with
+/// the same names and signatures as when the entire module is printed at once.
+fn render_functions<I: Input + Sync>(
+    sections: &SectionSequence<I>,
+    code_index: usize,
+    count: u32,
+    jobs: NonZeroUsize,
+) -> Vec<String> {
+    let jobs = u32::try_from(jobs.get()).unwrap_or(u32::MAX);
+    let chunk_size = (count / jobs + u32::from(count % jobs != 0)).max(1);
+    let chunks = (0..count)
+        .step_by(usize::try_from(chunk_size).unwrap_or(usize::MAX))
+        .map(|start| start..count.min(start + chunk_size))
+        .collect::<Vec<_>>();
+
+    let mut rendered = vec![String::new(); chunks.len()];
+    std::thread::scope(|scope| {
+        for (range, buffer) in chunks.into_iter().zip(&mut rendered) {
+            scope.spawn(move || {
+                *buffer = sections
+                    .display_module()
+                    .with_section_range(code_index..code_index + 1)
+                    .with_function_range(range)
+                    .to_string();
+            });
+        }
+    });
+
+    rendered
+}
+
+/// Writes the module to the `output`, rendering the function bodies on up to `jobs` threads.
+///
+/// The sections before and after the *code section* are printed separately, and the rendered
+/// functions are written in order between them. If a function body is malformed, the error is
+/// written in place of the remaining functions in its chunk, and the other chunks are still
+/// printed.
+///
+/// Falls back to printing the module on the current thread if the *code section* could not be
+/// found.
+pub(crate) fn write_module<I: Input + Sync>(
+    sections: &SectionSequence<I>,
+    jobs: NonZeroUsize,
+    output: &mut dyn Write,
+) -> std::io::Result<()> {
+    if jobs.get() == 1 {
+        return writeln!(output, "{}", sections.display_module());
+    }
+
+    let (code_index, count) = match find_code_section(sections) {
+        Some(found) => found,
+        None => return writeln!(output, "{}", sections.display_module()),
+    };
+
+    // Each piece is written as a separate module, so the module header and closing parenthesis
+    // are removed from the pieces in between
+    let empty = sections
+        .display_module()
+        .with_section_range(0..0)
+        .to_string();
+    let header = empty.strip_suffix(')').unwrap_or(&empty);
+
+    let before = sections
+        .display_module()
+        .with_section_range(0..code_index)
+        .to_string();
+    let after = sections
+        .display_module()
+        .with_section_range(code_index + 1..usize::MAX)
+        .to_string();
+
+    let (before, after) = match (before.strip_suffix(')'), after.strip_prefix(header)) {
+        (Some(before), Some(after)) => (before, after),
+        _ => return writeln!(output, "{}", sections.display_module()),
+    };
+
+    writeln!(output, "{before}")?;
+    for chunk in render_functions(sections, code_index, count, jobs) {
+        // The newline preceding the code section was already written
+        let functions = chunk
+            .strip_prefix(header)
+            .and_then(|functions| functions.strip_prefix('\n'))
+            .and_then(|functions| functions.strip_suffix(')'))
+            .unwrap_or(&chunk);
+        write!(output, "{functions}")?;
+    }
+    writeln!(output, "{after}")
+}
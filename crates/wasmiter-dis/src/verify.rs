@@ -0,0 +1,69 @@
+//! Implementation of the `--verify` flag.
+
+use wasmiter::{
+    component::{Code, Import, ImportKind},
+    input::Input,
+    parser::Parsed,
+    sections::{Section, SectionSequence},
+    validate,
+    visit::{self, ModuleVisitor},
+};
+
+/// Tracks the location of the entry currently being parsed, so that errors can be reported with
+/// the offset and function index they occured in.
+#[derive(Default)]
+struct Verifier {
+    offset: u64,
+    imported_functions: u32,
+    defined_functions: u32,
+    function: Option<u32>,
+}
+
+impl ModuleVisitor for Verifier {
+    fn visit_section<I: Input>(&mut self, section: &Section<I>) -> Parsed<()> {
+        self.offset = section.contents().base();
+        self.function = None;
+        Ok(())
+    }
+
+    fn visit_import<I: Input>(&mut self, import: &Import<I>) -> Parsed<()> {
+        if let ImportKind::Function(_) = import.kind() {
+            self.imported_functions += 1;
+        }
+        Ok(())
+    }
+
+    fn visit_code<I: Input>(&mut self, code: &Code<I>) -> Parsed<()> {
+        self.offset = code.content().base();
+        self.function = Some(self.imported_functions + self.defined_functions);
+        self.defined_functions += 1;
+        Ok(())
+    }
+}
+
+/// Parses the entire module, including every function body, then
+/// [validates](https://webassembly.github.io/spec/core/valid/index.html) it, returning a diagnostic
+/// describing the first error that was encountered.
+pub(crate) fn verify<I: Input>(
+    sections: &SectionSequence<I>,
+    file: &std::path::Path,
+) -> Result<(), String> {
+    let mut verifier = Verifier::default();
+    visit::visit_module_sections(sections, &mut verifier).map_err(|error| {
+        let location = match verifier.function {
+            Some(index) => format!(" in function {index}"),
+            None => String::new(),
+        };
+
+        format!(
+            "{}:{:08x}: error{location}: {error}",
+            file.display(),
+            verifier.offset
+        )
+    })?;
+
+    validate::validate_module_sections(sections).map_err(|error| match error.offset() {
+        Some(offset) => format!("{}:{offset:08x}: error: {error}", file.display()),
+        None => format!("{}: error: {error}", file.display()),
+    })
+}
@@ -0,0 +1,102 @@
+//! Implementation of the `--wast` flag.
+
+use std::io::Write;
+use wasmiter::{
+    component::{Export, ExportKind, Import, ImportKind, ResultType},
+    index::{FuncIdx, TypeIdx},
+    input::{BorrowInput as _, Input},
+    parser::Parsed,
+    sections::SectionSequence,
+    types::ValType,
+    visit::{self, ModuleVisitor},
+};
+
+/// Collects the signatures of every function, and the names of the exported functions.
+#[derive(Default)]
+struct Manifest {
+    types: Vec<(Vec<ValType>, Vec<ValType>)>,
+    functions: Vec<TypeIdx>,
+    /// The escaped name of each function export, in the order they are exported.
+    exports: Vec<(String, FuncIdx)>,
+}
+
+impl ModuleVisitor for Manifest {
+    fn visit_type<I: Input>(
+        &mut self,
+        parameters: ResultType<u64, I>,
+        results: ResultType<u64, I>,
+    ) -> Parsed<()> {
+        self.types.push((
+            parameters.collect::<Parsed<_>>()?,
+            results.collect::<Parsed<_>>()?,
+        ));
+        Ok(())
+    }
+
+    fn visit_import<I: Input>(&mut self, import: &Import<I>) -> Parsed<()> {
+        if let ImportKind::Function(signature) = import.kind() {
+            self.functions.push(*signature);
+        }
+        Ok(())
+    }
+
+    fn visit_function(&mut self, signature: TypeIdx) -> Parsed<()> {
+        self.functions.push(signature);
+        Ok(())
+    }
+
+    fn visit_export<I: Input>(&mut self, export: &Export<I>) -> Parsed<()> {
+        if let ExportKind::Function(index) = export.kind() {
+            let name = export.name().borrow_input().escaped().to_string();
+            self.exports.push((name, *index));
+        }
+        Ok(())
+    }
+}
+
+/// Writes a placeholder constant of the given type, to be replaced with an actual value.
+fn write_placeholder(output: &mut dyn Write, value_type: ValType) -> std::io::Result<()> {
+    match value_type {
+        ValType::I32 | ValType::I64 | ValType::F32 | ValType::F64 => {
+            write!(output, " ({value_type}.const 0 (; TODO ;))")
+        }
+        ValType::V128 => write!(output, " (v128.const i32x4 0 0 0 0 (; TODO ;))"),
+        ValType::FuncRef => write!(output, " (ref.null func (; TODO ;))"),
+        ValType::ExternRef => write!(output, " (ref.null extern (; TODO ;))"),
+        ValType::ExnRef => write!(output, " (ref.null exn (; TODO ;))"),
+        ValType::TypedRef { heap_type, .. } => {
+            write!(output, " (ref.null {heap_type} (; TODO ;))")
+        }
+    }
+}
+
+/// Writes an `assert_return` command invoking each exported function, with placeholders for the
+/// arguments and expected results.
+pub(crate) fn write_assertions<I: Input>(
+    sections: &SectionSequence<I>,
+    output: &mut dyn Write,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut manifest = Manifest::default();
+    visit::visit_module_sections(sections, &mut manifest)?;
+
+    for (name, index) in manifest.exports.iter() {
+        let (parameters, results) = manifest
+            .functions
+            .get(index.to_usize())
+            .and_then(|signature| manifest.types.get(signature.to_usize()))
+            .ok_or_else(|| format!("could not resolve the signature of function export {name}"))?;
+
+        writeln!(output)?;
+        write!(output, "(assert_return (invoke {name}")?;
+        for parameter in parameters {
+            write_placeholder(output, *parameter)?;
+        }
+        write!(output, ")")?;
+        for result in results {
+            write_placeholder(output, *result)?;
+        }
+        writeln!(output, ")")?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use std::process::Command;
+
+fn disassemble(file: &std::path::Path, jobs: u32) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_wasmiter-dis"))
+        .arg(file)
+        .arg("--jobs")
+        .arg(jobs.to_string())
+        .output()
+        .unwrap();
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn parallel_output_matches_sequential() {
+    let directory = std::env::temp_dir().join("wasmiter-dis-parallel");
+    std::fs::create_dir_all(&directory).unwrap();
+
+    let modules = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tests/modules");
+    for entry in std::fs::read_dir(modules).unwrap() {
+        let path = entry.unwrap().path();
+        let wasm = wat::parse_file(&path).unwrap();
+        let file = directory
+            .join(path.file_stem().unwrap())
+            .with_extension("wasm");
+        std::fs::write(&file, wasm).unwrap();
+
+        let sequential = disassemble(&file, 1);
+        for jobs in [2, 3, 8] {
+            assert_eq!(
+                disassemble(&file, jobs),
+                sequential,
+                "{} with {jobs} jobs",
+                path.display()
+            );
+        }
+    }
+}
@@ -0,0 +1,7 @@
+//! Checks that no public entry point panics when given random input bytes.
+
+#![no_main]
+
+libfuzzer_sys::fuzz_target!(|wasm: &[u8]| {
+    wasmiter_fuzz::check_no_panic(wasm);
+});
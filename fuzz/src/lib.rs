@@ -1,5 +1,16 @@
 //! Helper structs and functions for fuzzing targets.
 
+use wasmiter::{
+    component::{Export, Import, KnownSection, ResultType},
+    custom::{
+        name::{IndirectNameMap, NameMap},
+        CustomSection, KnownCustomSection,
+    },
+    index::Index,
+    input::{BorrowInput as _, Input},
+    parser::Parsed,
+};
+
 mod wasm;
 
 pub use wasm::Wasm;
@@ -11,148 +22,222 @@ pub fn print_reference_wat(wasm: &[u8]) -> String {
     }
 }
 
-pub fn process_sections(wasm: &[u8]) -> wasmiter::parser::Parsed<()> {
-    use wasmiter::{component::KnownSection, custom::KnownCustomSection};
-
-    for result in wasmiter::parse_module_sections(wasm)? {
-        match KnownSection::interpret(result?) {
-            Ok(known) => match known? {
-                KnownSection::Type(mut types) => loop {
-                    let result = types.parse(
-                        |params| {
-                            for item in params {
-                                let _ = item?;
+fn name_map_strings<N: Index, I: Clone + Input>(name_map: NameMap<N, u64, I>) -> Parsed<()> {
+    for result in name_map {
+        let _ = result?.name().borrow_input().try_into_string()?;
+    }
+    Ok(())
+}
+
+fn indirect_name_map_strings<K: Index, V: Index, I: Input>(
+    mut indirect_name_map: IndirectNameMap<K, V, u64, I>,
+) -> Parsed<()> {
+    while indirect_name_map
+        .parse(|_, name_map| {
+            for result in name_map {
+                let _ = result?.name().borrow_input().try_into_string()?;
+            }
+            Ok(())
+        })?
+        .is_some()
+    {}
+    Ok(())
+}
+
+/// Checks that every section, entry, instruction, and name in a module can be parsed.
+struct SectionChecker;
+
+impl wasmiter::visit::ModuleVisitor for SectionChecker {
+    fn visit_import<I: Input>(&mut self, import: &Import<I>) -> Parsed<()> {
+        let _ = import.module().borrow_input().try_into_string()?;
+        let _ = import.name().borrow_input().try_into_string()?;
+        Ok(())
+    }
+
+    fn visit_export<I: Input>(&mut self, export: &Export<I>) -> Parsed<()> {
+        let _ = export.name().borrow_input().try_into_string()?;
+        Ok(())
+    }
+
+    fn visit_type<I: Input>(
+        &mut self,
+        parameters: ResultType<u64, I>,
+        results: ResultType<u64, I>,
+    ) -> Parsed<()> {
+        for item in parameters.into_iter().chain(results) {
+            let _ = item?;
+        }
+        Ok(())
+    }
+
+    fn visit_known_section<I: Input>(&mut self, section: &KnownSection<I>) -> Parsed<()> {
+        #[allow(unreachable_patterns)]
+        match section {
+            KnownSection::Type(_)
+            | KnownSection::Import(_)
+            | KnownSection::Function(_)
+            | KnownSection::Table(_)
+            | KnownSection::Memory(_)
+            | KnownSection::Global(_)
+            | KnownSection::Export(_)
+            | KnownSection::Start(_)
+            | KnownSection::Element(_)
+            | KnownSection::Code(_)
+            | KnownSection::Data(_)
+            | KnownSection::DataCount(_)
+            | KnownSection::Tag(_) => Ok(()),
+            bad => panic!("unsupported section {:?}", bad.id()),
+        }
+    }
+
+    fn visit_custom_section<I: Input>(&mut self, section: &CustomSection<I>) -> Parsed<()> {
+        use wasmiter::custom::name::NameSubsection;
+
+        let known = match KnownCustomSection::interpret(section.borrow_input()) {
+            Ok(known) => known,
+            Err(_) => return Ok(()),
+        };
+
+        match known {
+            KnownCustomSection::Name(name) => {
+                for result in name {
+                    if let Ok(result) = result {
+                        match result? {
+                            NameSubsection::ModuleName(name) => {
+                                let _ = name.try_into_string()?;
                             }
-                            Ok(())
-                        },
-                        |(), results| {
-                            for item in results {
-                                let _ = item?;
+                            NameSubsection::FunctionName(name_map) => name_map_strings(name_map)?,
+                            NameSubsection::TypeName(name_map) => name_map_strings(name_map)?,
+                            NameSubsection::TableName(name_map) => name_map_strings(name_map)?,
+                            NameSubsection::MemoryName(name_map) => name_map_strings(name_map)?,
+                            NameSubsection::GlobalName(name_map) => name_map_strings(name_map)?,
+                            NameSubsection::ElementName(name_map) => name_map_strings(name_map)?,
+                            NameSubsection::DataName(name_map) => name_map_strings(name_map)?,
+                            NameSubsection::TagName(name_map) => name_map_strings(name_map)?,
+                            NameSubsection::LocalName(indirect_name_map) => {
+                                indirect_name_map_strings(indirect_name_map)?
                             }
-                            Ok(())
-                        },
-                    )?;
-
-                    if result.is_none() {
-                        break;
-                    }
-                },
-                KnownSection::Import(imports) => {
-                    for result in imports {
-                        let entry = result?;
-                        let _ = entry.module().try_into_string()?;
-                        let _ = entry.name().try_into_string()?;
-                    }
-                }
-                KnownSection::Function(functions) => {
-                    for result in functions {
-                        let _ = result?;
-                    }
-                }
-                KnownSection::Table(tables) => {
-                    for result in tables {
-                        let _ = result?;
-                    }
-                }
-                KnownSection::Memory(mems) => {
-                    for result in mems {
-                        let _ = result?;
-                    }
-                }
-                KnownSection::Global(mut globals) => loop {
-                    if globals.parse(|_, _| Ok(()))?.is_none() {
-                        break;
-                    }
-                },
-                KnownSection::Export(exports) => {
-                    for result in exports {
-                        let _ = result?.name().try_into_string()?;
-                    }
-                }
-                KnownSection::Start(_) => (),
-                KnownSection::Element(mut elements) => loop {
-                    if elements.parse(|_| Ok(()), |(), _| Ok(()))?.is_none() {
-                        break;
-                    }
-                },
-                KnownSection::Code(code) => {
-                    for result in code {
-                        let entry = result?;
-                        entry.read(|_| wasmiter::parser::Parsed::Ok(()), |(), _| Ok(()))?;
-                    }
-                }
-                KnownSection::Data(mut data) => loop {
-                    if data.parse(|_| Ok(()), |(), _| Ok(()))?.is_none() {
-                        break;
-                    }
-                },
-                KnownSection::DataCount(_) => (),
-                KnownSection::Tag(tag) => {
-                    for result in tag {
-                        let _ = result?;
+                            NameSubsection::LabelName(indirect_name_map) => {
+                                indirect_name_map_strings(indirect_name_map)?
+                            }
+                            NameSubsection::FieldName(indirect_name_map) => {
+                                indirect_name_map_strings(indirect_name_map)?
+                            }
+                            bad => panic!("unsupported name subsection {:?}", bad.id()),
+                        }
                     }
                 }
-                bad => panic!("unsupported section {:?}", bad.id()),
-            },
-            Err(possibly_custom) => {
-                if let Ok(result) =
-                    wasmiter::custom::CustomSection::try_from_section(possibly_custom)
-                {
-                    if let Ok(known) = KnownCustomSection::interpret(result?) {
-                        match known {
-                            KnownCustomSection::Name(name) => {
-                                for result in name {
-                                    use wasmiter::custom::name::NameSubsection;
-
-                                    if let Ok(result) = result {
-                                        match result? {
-                                            NameSubsection::ModuleName(name) => {
-                                                let _ = name.try_into_string()?;
-                                            }
-                                            NameSubsection::FunctionName(name_map) => {
-                                                for result in name_map {
-                                                    let name_assoc = result?;
-                                                    let _ = name_assoc.name().try_into_string()?;
-                                                }
-                                            }
-                                            NameSubsection::LocalName(mut indirect_name_map) => {
-                                                loop {
-                                                    let result = indirect_name_map.parse(
-                                                        |_, name_map| {
-                                                            for result in name_map {
-                                                                let name_assoc = result?;
-                                                                let _ = name_assoc
-                                                                    .name()
-                                                                    .try_into_string()?;
-                                                            }
-                                                            Ok(())
-                                                        },
-                                                    )?;
-
-                                                    if result.is_some() {
-                                                        break;
-                                                    }
-                                                }
-                                            }
-                                            NameSubsection::TagName(name_map) => {
-                                                for result in name_map {
-                                                    let name_assoc = result?;
-                                                    let _ = name_assoc.name().try_into_string()?;
-                                                }
-                                            }
-                                            bad => {
-                                                panic!("unsupported name subsection {:?}", bad.id())
-                                            }
-                                        }
+            }
+            KnownCustomSection::Linking(linking) => {
+                use wasmiter::custom::linking::LinkingSubsection;
+
+                for result in linking {
+                    if let Ok(result) = result {
+                        match result? {
+                            LinkingSubsection::SegmentInfo(segments) => {
+                                for segment in segments {
+                                    let _ = segment?.name().try_into_string()?;
+                                }
+                            }
+                            LinkingSubsection::InitFuncs(functions) => {
+                                for function in functions {
+                                    function?;
+                                }
+                            }
+                            LinkingSubsection::ComdatInfo(comdats) => {
+                                for comdat in comdats {
+                                    for symbol in comdat?.symbols() {
+                                        symbol?;
+                                    }
+                                }
+                            }
+                            LinkingSubsection::SymbolTable(symbols) => {
+                                for symbol in symbols {
+                                    if let Some(name) = symbol?.name() {
+                                        let _ = name.try_into_string()?;
                                     }
                                 }
                             }
-                            bad => panic!("unsupported custom section {:?}", bad.name()),
+                            bad => panic!("unsupported linking subsection {:?}", bad.id()),
                         }
                     }
                 }
             }
+            #[allow(unreachable_patterns)]
+            bad => panic!("unsupported custom section {:?}", bad.name()),
+        }
+
+        Ok(())
+    }
+}
+
+pub fn process_sections(wasm: &[u8]) -> Parsed<()> {
+    wasmiter::visit::visit_module(wasm, &mut SectionChecker)
+}
+
+/// Passes the `wasm` bytes to each of the public entry points that accept untrusted input,
+/// discarding any errors or output.
+///
+/// Any panic that occurs is a bug, since `wasmiter` guarantees that no panics occur when parsing
+/// malformed input.
+pub fn check_no_panic(wasm: &[u8]) {
+    use std::fmt::Write as _;
+
+    let _ = process_sections(wasm);
+
+    let sections = match wasmiter::parse_module_sections(wasm) {
+        Ok(sections) => sections,
+        // Also check sequences of sections without the preamble
+        Err(_) => wasmiter::sections::SectionSequence::new(0, wasm),
+    };
+
+    let mut output = String::new();
+    let _ = write!(output, "{:?}", sections.debug_module());
+    output.clear();
+    let _ = write!(output, "{}", sections.display_module());
+    output.clear();
+    let _ = write!(
+        output,
+        "{}",
+        sections.display_module().with_relocation_annotations()
+    );
+
+    let _ = wasmiter::validate::validate_module(wasm);
+    let _ = wasmiter::edit::merge(&[wasm, wasm]);
+    let renames = wasmiter::edit::RenameMap::new().with_export_prefix("fuzz_");
+    let _ = wasmiter::edit::rename(wasm, &renames);
+    let _ = wasmiter::addrmap::AddressMap::new(&sections);
+
+    if let Ok(text) = std::str::from_utf8(wasm) {
+        let _ = wasmiter::text::parse_str(text);
+    }
+
+    if let Ok(signatures) = wasmiter::component::SignatureResolver::new(&sections) {
+        for index in 0..signatures.function_count() {
+            let _ = signatures.function_signature(index.into());
+        }
+    }
+
+    let _ = wasmiter::analysis::hash_canonical(&sections);
+    let _ = wasmiter::analysis::constant_globals(&sections);
+    let _ = wasmiter::analysis::stack_depth(&sections);
+    let _ = wasmiter::analysis::startup_cost(&sections);
+
+    for result in sections.borrow_input() {
+        let section = match result {
+            Ok(section) => section,
+            Err(_) => continue,
+        };
+        output.clear();
+        let _ = write!(output, "{section:?}");
+        let _ = wasmiter::analysis::classify_data(section.contents());
+
+        if let Ok(Ok(KnownSection::Code(code))) = KnownSection::interpret(section) {
+            if let Ok(index) = wasmiter::component::CodeSectionIndex::new(code, 0) {
+                for function in 0..index.len() {
+                    let _ = index.get(function.into());
+                }
+            }
         }
     }
-    Ok(())
 }
@@ -0,0 +1,200 @@
+//! Provides the [`AddressMap`] struct, which translates offsets into a WebAssembly module into
+//! positions within its sections and function bodies.
+
+use crate::{
+    component::{ImportKind, KnownSection},
+    index::FuncIdx,
+    input::{BorrowInput as _, Input},
+    parser::Parsed,
+    sections::SectionSequence,
+};
+use alloc::vec::Vec;
+
+/// A position within the contents of a section, returned by [`AddressMap::section()`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct SectionLocation {
+    index: usize,
+    id: u8,
+    offset: u64,
+}
+
+impl SectionLocation {
+    /// Gets the position of the section within the module, where `0` refers to the first section
+    /// after the preamble.
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Gets the [*id*](crate::sections::id) of the section.
+    #[inline]
+    pub fn id(&self) -> u8 {
+        self.id
+    }
+
+    /// Gets the offset from the start of the section's contents, after its *id* and size.
+    #[inline]
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+}
+
+/// A position within the body of a function, returned by [`AddressMap::function()`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct FunctionLocation {
+    index: FuncIdx,
+    offset: u64,
+}
+
+impl FunctionLocation {
+    /// Gets the index of the function, which includes any imported functions.
+    #[inline]
+    pub fn index(&self) -> FuncIdx {
+        self.index
+    }
+
+    /// Gets the offset from the start of the function's entry in the *code section*, after its
+    /// size. Offset `0` refers to the start of the function's local declarations.
+    #[inline]
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Range<T> {
+    start: u64,
+    end: u64,
+    item: T,
+}
+
+/// Finds the entry in the sorted `ranges` that contains the `offset`.
+fn find<T: Copy>(ranges: &[Range<T>], offset: u64) -> Option<(usize, u64, T)> {
+    let index = ranges.partition_point(|range| range.end <= offset);
+    let range = ranges.get(index)?;
+    if range.start <= offset {
+        Some((index, offset - range.start, range.item))
+    } else {
+        None
+    }
+}
+
+/// Maps absolute offsets into a WebAssembly module to the section and function body that contain
+/// them, which is useful when symbolicating stack traces that refer to offsets into the module.
+///
+/// # Example
+///
+/// ```
+/// use wasmiter::{addrmap::AddressMap, index::FuncIdx, sections::id};
+///
+/// let wasm = wat::parse_str(r#"(module
+///     (import "env" "f" (func))
+///     (func i32.const 1 drop)
+///     (func nop))"#).unwrap();
+///
+/// let sections = wasmiter::parse_module_sections(wasm.as_slice())?;
+/// let map = AddressMap::new(&sections)?;
+///
+/// // Find the offset of the nop instruction
+/// let nop = wasm.iter().rposition(|b| *b == 0x01).unwrap() as u64;
+///
+/// let section = map.section(nop).unwrap();
+/// assert_eq!(section.id(), id::CODE);
+///
+/// let function = map.function(nop).unwrap();
+/// assert_eq!(function.index(), FuncIdx::from(2u32));
+/// assert_eq!(function.offset(), 1); // after the local declarations
+/// # Ok::<(), wasmiter::parser::Error>(())
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct AddressMap {
+    sections: Vec<Range<u8>>,
+    functions: Vec<Range<FuncIdx>>,
+}
+
+impl AddressMap {
+    /// Reads the `sections` of a module once to build an [`AddressMap`], parsing only the
+    /// *import section* and the sizes of each function in the *code section*.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a section, import, or *code section* entry could not be parsed.
+    pub fn new<I: Input>(sections: &SectionSequence<I>) -> Parsed<Self> {
+        let mut map = Self::default();
+        let mut imported_function_count = 0u32;
+
+        for result in sections.borrow_input() {
+            let section = result?;
+            let start = section.contents().base();
+            map.sections.push(Range {
+                start,
+                end: start.saturating_add(section.length()),
+                item: section.id(),
+            });
+
+            match KnownSection::interpret(section) {
+                Ok(Ok(KnownSection::Import(mut imports))) => {
+                    while let Some(import) = imports.parse()? {
+                        if let ImportKind::Function(_) = import.kind() {
+                            imported_function_count += 1;
+                        }
+                    }
+                }
+                Ok(Ok(KnownSection::Code(code))) => {
+                    for entry in code.with_function_indices(imported_function_count) {
+                        let (index, code) = entry?;
+                        let body = code.content();
+                        map.functions.push(Range {
+                            start: body.base(),
+                            end: body.base().saturating_add(body.length()),
+                            item: index,
+                        });
+                    }
+                }
+                Ok(Err(error)) => return Err(error),
+                _ => (),
+            }
+        }
+
+        Ok(map)
+    }
+
+    /// Gets the section whose contents contain the byte at the given `offset`.
+    ///
+    /// Returns `None` if the `offset` refers to the preamble, the *id* or size of a section, or
+    /// is past the end of the module.
+    pub fn section(&self, offset: u64) -> Option<SectionLocation> {
+        let (index, offset, id) = find(&self.sections, offset)?;
+        Some(SectionLocation { index, id, offset })
+    }
+
+    /// Gets the function whose body contains the byte at the given `offset`.
+    ///
+    /// Returns `None` if the `offset` is not within the *code section*, or refers to the size of
+    /// an entry.
+    pub fn function(&self, offset: u64) -> Option<FunctionLocation> {
+        let (_, offset, index) = find(&self.functions, offset)?;
+        Some(FunctionLocation { index, offset })
+    }
+
+    /// Gets the number of sections in the module.
+    #[inline]
+    pub fn section_count(&self) -> usize {
+        self.sections.len()
+    }
+
+    /// Gets the number of functions defined in the *code section*.
+    #[inline]
+    pub fn function_count(&self) -> usize {
+        self.functions.len()
+    }
+}
+
+#[cfg(feature = "mem-usage")]
+impl crate::mem_usage::MemUsage for AddressMap {
+    #[inline]
+    fn mem_usage(&self) -> usize {
+        crate::mem_usage::vec_capacity(&self.sections)
+            + crate::mem_usage::vec_capacity(&self.functions)
+    }
+}
@@ -0,0 +1,58 @@
+//! Types and functions for analyzing the contents of a WebAssembly module.
+
+mod canonical_hash;
+#[cfg(feature = "alloc")]
+mod constant_globals;
+mod contains_opcode;
+#[cfg(feature = "alloc")]
+mod custom_section_lint;
+mod data_contents;
+#[cfg(feature = "alloc")]
+mod export_index;
+#[cfg(feature = "alloc")]
+mod feature_detector;
+#[cfg(feature = "alloc")]
+mod host_interface;
+#[cfg(feature = "alloc")]
+mod size_profile;
+#[cfg(feature = "alloc")]
+mod stack_depth;
+mod startup_cost;
+
+pub use canonical_hash::{hash_canonical, hash_canonical_with};
+pub use contains_opcode::contains_opcode;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub use constant_globals::{constant_globals, ConstantValue};
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub use custom_section_lint::{lint_custom_sections, CustomSectionBudget, CustomSectionLint};
+
+pub use data_contents::{classify_data, decode_utf8, DataContents, Utf8Chunk};
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub use export_index::{ExportIndex, ImportCounts};
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub use feature_detector::FeatureDetector;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub use host_interface::{
+    check_imports, type_differences, ExternType, HostImports, HostInterface, ImportMismatch,
+    TypeDifference,
+};
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub use size_profile::{size_profile, FunctionSize, SectionSize, SizeProfile};
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub use stack_depth::{stack_depth, StackDepth};
+
+pub use startup_cost::{startup_cost, StartupCost};
@@ -0,0 +1,89 @@
+use crate::{
+    input::{BorrowInput as _, Input},
+    parser::Parsed,
+    sections::{id as section_id, SectionSequence},
+};
+
+const FNV_OFFSET_BASIS: u64 = 0xCBF2_9CE4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01B3;
+
+/// Provides the bytes of every non-custom section in a module, in order, to the closure `f`.
+///
+/// Each section is provided as its *id* byte, followed by its size as a little-endian 64-bit
+/// integer, followed by its contents. This allows modules to be hashed with any digest algorithm,
+/// such that modules differing only in their custom sections (such as debugging information or
+/// `producers` metadata) produce the same digest.
+///
+/// See [`hash_canonical`] for a convenience function that computes a 64-bit hash.
+///
+/// # Errors
+///
+/// Returns an error if a section could not be parsed or read.
+pub fn hash_canonical_with<I, F>(sections: &SectionSequence<I>, mut f: F) -> Parsed<()>
+where
+    I: Input,
+    F: FnMut(&[u8]),
+{
+    let mut buffer = [0u8; 256];
+    for result in sections.borrow_input() {
+        let section = result?;
+        if section.id() == section_id::CUSTOM {
+            continue;
+        }
+
+        f(&[section.id()]);
+        f(&section.length().to_le_bytes());
+
+        let contents = section.contents();
+        let mut offset = contents.base();
+        let end = contents.base().saturating_add(contents.length());
+        while offset < end {
+            let size = usize::try_from(end - offset)
+                .unwrap_or(buffer.len())
+                .min(buffer.len());
+            let chunk = &mut buffer[..size];
+            contents.read_exact_at(offset, chunk)?;
+            f(chunk);
+            offset += size as u64;
+        }
+    }
+
+    Ok(())
+}
+
+/// Computes a 64-bit hash over the non-custom sections of a module, so that modules differing
+/// only in their custom sections produce the same hash.
+///
+/// The hash is computed with the
+/// [FNV-1a](https://en.wikipedia.org/wiki/Fowler%E2%80%93Noll%E2%80%93Vo_hash_function) algorithm,
+/// and is stable across versions of this crate. It is not suitable for cryptographic purposes;
+/// use [`hash_canonical_with`] to compute a digest with another algorithm.
+///
+/// # Errors
+///
+/// Returns an error if a section could not be parsed or read.
+///
+/// # Example
+///
+/// ```
+/// use wasmiter::analysis;
+///
+/// let module = b"\0asm\x01\0\0\0\x01\x04\x01\x60\0\0";
+/// let mut with_custom = module.to_vec();
+/// with_custom.extend_from_slice(b"\0\x05\x04name");
+///
+/// let a = wasmiter::parse_module_sections(module.as_slice())?;
+/// let b = wasmiter::parse_module_sections(with_custom.as_slice())?;
+/// assert_eq!(analysis::hash_canonical(&a)?, analysis::hash_canonical(&b)?);
+/// # Ok::<(), wasmiter::parser::Error>(())
+/// ```
+pub fn hash_canonical<I: Input>(sections: &SectionSequence<I>) -> Parsed<u64> {
+    let mut hash = FNV_OFFSET_BASIS;
+    hash_canonical_with(sections, |bytes| {
+        for byte in bytes {
+            hash ^= u64::from(*byte);
+            hash = hash.wrapping_mul(FNV_PRIME);
+        }
+    })?;
+    Ok(hash)
+}
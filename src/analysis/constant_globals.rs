@@ -0,0 +1,135 @@
+use crate::{
+    component::{ImportKind, KnownSection},
+    index::{FuncIdx, GlobalIdx},
+    input::{BorrowInput as _, Input},
+    instruction_set::Instruction,
+    parser::Parsed,
+    sections::SectionSequence,
+    types::{GlobalMutability, RefType},
+};
+use alloc::{collections::BTreeMap, vec::Vec};
+
+/// The statically-known value of an immutable
+/// [global](https://webassembly.github.io/spec/core/syntax/modules.html#globals).
+///
+/// Returned by [`constant_globals`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum ConstantValue {
+    /// A 32-bit integer.
+    I32(i32),
+    /// A 64-bit integer.
+    I64(i64),
+    /// A 32-bit floating-point value.
+    F32(f32),
+    /// A 64-bit floating-point value.
+    F64(f64),
+    /// A 128-bit vector.
+    V128(u128),
+    /// A null reference of the given type.
+    RefNull(RefType),
+    /// A reference to a function.
+    RefFunc(FuncIdx),
+    /// The value of an immutable imported global, which is only known at instantiation.
+    Imported(GlobalIdx),
+}
+
+fn constant<I: Input>(
+    instruction: &Instruction<'_, I>,
+    values: &[Option<ConstantValue>],
+) -> Option<ConstantValue> {
+    Some(match instruction {
+        Instruction::I32Const(value) => ConstantValue::I32(*value),
+        Instruction::I64Const(value) => ConstantValue::I64(*value),
+        Instruction::F32Const(value) => ConstantValue::F32(*value),
+        Instruction::F64Const(value) => ConstantValue::F64(*value),
+        #[cfg(feature = "simd")]
+        Instruction::V128Const(value) => ConstantValue::V128(*value),
+        Instruction::RefNull(ref_type) => ConstantValue::RefNull(*ref_type),
+        Instruction::RefFunc(function) => ConstantValue::RefFunc(*function),
+        Instruction::GlobalGet(global) => return values.get(usize::from(*global)).copied()?,
+        _ => return None,
+    })
+}
+
+/// Lists the globals defined in a module that are immutable and are initialized with a
+/// statically-known constant value.
+///
+/// Initializers that read another global with **global.get** are resolved to the value of that
+/// global if it is also a constant, or to an [`Imported`](ConstantValue::Imported) value if it
+/// is an immutable imported global. Initializers consisting of more than one instruction, such as
+/// those using the [extended constant expressions proposal](https://github.com/WebAssembly/extended-const),
+/// are not evaluated.
+///
+/// Imported globals are not included in the returned map.
+///
+/// # Errors
+///
+/// Returns an error if a section could not be parsed.
+pub fn constant_globals<I: Input>(
+    sections: &SectionSequence<I>,
+) -> Parsed<BTreeMap<GlobalIdx, ConstantValue>> {
+    let mut values = Vec::<Option<ConstantValue>>::new();
+    let mut imported_global_count = 0usize;
+
+    for result in sections.borrow_input() {
+        let known = match KnownSection::interpret(result?) {
+            Ok(known) => known?,
+            Err(_) => continue,
+        };
+
+        match known {
+            KnownSection::Import(mut imports) => {
+                while let Some(import) = imports.parse()? {
+                    if let ImportKind::Global(global_type) = import.kind() {
+                        let index = GlobalIdx::try_from(values.len())?;
+                        values.push(match global_type.mutability() {
+                            GlobalMutability::Constant => Some(ConstantValue::Imported(index)),
+                            GlobalMutability::Variable => None,
+                        });
+                        imported_global_count += 1;
+                    }
+                }
+            }
+            KnownSection::Global(mut globals) => loop {
+                let result = globals.parse(|global_type, initializer| {
+                    let mut first = true;
+                    let mut value = None;
+                    while let Some(result) = initializer.next(|instruction| {
+                        if !matches!(instruction, Instruction::End) {
+                            value = if first {
+                                constant(instruction, &values)
+                            } else {
+                                None
+                            };
+                            first = false;
+                        }
+                        Parsed::Ok(())
+                    }) {
+                        result?;
+                    }
+
+                    Ok(match global_type.mutability() {
+                        GlobalMutability::Constant => value,
+                        GlobalMutability::Variable => None,
+                    })
+                })?;
+
+                match result {
+                    Some(value) => values.push(value),
+                    None => break,
+                }
+            },
+            _ => (),
+        }
+    }
+
+    let mut constants = BTreeMap::new();
+    for (index, value) in values.into_iter().enumerate().skip(imported_global_count) {
+        if let Some(value) = value {
+            constants.insert(GlobalIdx::try_from(index)?, value);
+        }
+    }
+
+    Ok(constants)
+}
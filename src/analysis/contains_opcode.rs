@@ -0,0 +1,78 @@
+use crate::{
+    component::{IndexedCodeSection, Locals},
+    index::FuncIdx,
+    input::Input,
+    instruction_set::{InstructionOpcode, InstructionSequence},
+    parser::Parsed,
+};
+
+/// Searches the function bodies in the *code section* for the first instruction whose
+/// [`InstructionOpcode`] satisfies the `predicate`, returning the index of the function containing
+/// it along with the offset to the instruction.
+///
+/// Only the opcode and immediate arguments of each instruction are read, without building an
+/// [`Instruction`](crate::instruction_set::Instruction). Scanning stops at the first match, so
+/// remaining instructions and *code section* entries are not parsed. This allows cheap policy checks, such as rejecting modules that contain
+/// [atomic instructions](InstructionOpcode::FE).
+///
+/// # Errors
+///
+/// Returns an error if a *code section* entry or an instruction preceding the match could not be
+/// parsed.
+///
+/// # Example
+///
+/// ```
+/// use wasmiter::{analysis, component::KnownSection, index::FuncIdx, instruction_set::InstructionOpcode};
+///
+/// # #[cfg(feature = "threads")]
+/// # fn main() -> Result<(), wasmiter::parser::Error> {
+/// let wasm = wat::parse_str(r#"(module
+///     (memory 1 1 shared)
+///     (func)
+///     (func (result i32) i32.const 0 i32.atomic.load))"#).unwrap();
+///
+/// let sections = wasmiter::parse_module_sections(wasm.as_slice())?;
+/// for result in sections {
+///     if let Ok(Ok(KnownSection::Code(code))) = KnownSection::interpret(result?) {
+///         let atomic = analysis::contains_opcode(code.with_function_indices(0), |opcode| {
+///             matches!(opcode, InstructionOpcode::FE(_))
+///         })?;
+///
+///         let (index, _) = atomic.unwrap();
+///         assert_eq!(index, FuncIdx::from(1u32));
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// # #[cfg(not(feature = "threads"))]
+/// # fn main() {}
+/// ```
+pub fn contains_opcode<I, P>(
+    mut code: IndexedCodeSection<I>,
+    mut predicate: P,
+) -> Parsed<Option<(FuncIdx, u64)>>
+where
+    I: Input,
+    P: FnMut(InstructionOpcode) -> bool,
+{
+    while let Some((index, entry)) = code.parse()? {
+        let contents = entry.content();
+        let offset = Locals::new(contents.base(), contents, entry.max_locals())?.finish()?;
+        let mut instructions = InstructionSequence::new(offset, contents);
+
+        loop {
+            let instruction_offset = instructions.offset();
+            match instructions.skip_next() {
+                Some(Ok(opcode)) if predicate(opcode) => {
+                    return Ok(Some((index, instruction_offset)))
+                }
+                Some(Ok(_)) => (),
+                Some(Err(e)) => return Err(e),
+                None => break,
+            }
+        }
+    }
+
+    Ok(None)
+}
@@ -0,0 +1,187 @@
+use crate::{
+    custom::CustomSection,
+    input::{BorrowInput as _, Input},
+    parser::{Parsed, ResultExt as _},
+    sections::SectionSequence,
+};
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use core::fmt::{Display, Formatter};
+
+/// Thresholds used by [`lint_custom_sections`] to decide when custom sections are too large.
+///
+/// By default, no size limits are enforced, and only duplicate names are reported.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct CustomSectionBudget {
+    max_section_size: Option<u64>,
+    max_total_size: Option<u64>,
+}
+
+impl CustomSectionBudget {
+    /// Creates a new [`CustomSectionBudget`] with no size limits.
+    pub const fn new() -> Self {
+        Self {
+            max_section_size: None,
+            max_total_size: None,
+        }
+    }
+
+    /// Sets the maximum size, in bytes, of a single custom section.
+    pub const fn with_max_section_size(mut self, size: u64) -> Self {
+        self.max_section_size = Some(size);
+        self
+    }
+
+    /// Sets the maximum combined size, in bytes, of all custom sections in a module.
+    pub const fn with_max_total_size(mut self, size: u64) -> Self {
+        self.max_total_size = Some(size);
+        self
+    }
+
+    /// The maximum size, in bytes, of a single custom section.
+    #[inline]
+    pub const fn max_section_size(&self) -> Option<u64> {
+        self.max_section_size
+    }
+
+    /// The maximum combined size, in bytes, of all custom sections in a module.
+    #[inline]
+    pub const fn max_total_size(&self) -> Option<u64> {
+        self.max_total_size
+    }
+}
+
+/// Describes a problem with the custom sections of a module.
+///
+/// Section names that are not valid UTF-8 are converted lossily. Offsets refer to the start of
+/// each section's contents, and sizes include the section name.
+///
+/// Returned by [`lint_custom_sections`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum CustomSectionLint {
+    /// A custom section is larger than the
+    /// [maximum section size](CustomSectionBudget::max_section_size).
+    SectionTooLarge {
+        /// The name of the custom section.
+        name: String,
+        /// The offset of the custom section.
+        offset: u64,
+        /// The size of the custom section, in bytes.
+        size: u64,
+        /// The maximum size of a custom section, in bytes.
+        limit: u64,
+    },
+    /// The combined size of all custom sections is larger than the
+    /// [maximum total size](CustomSectionBudget::max_total_size).
+    TotalTooLarge {
+        /// The combined size of all custom sections, in bytes.
+        size: u64,
+        /// The maximum combined size of all custom sections, in bytes.
+        limit: u64,
+    },
+    /// More than one custom section has the same name.
+    DuplicateName {
+        /// The name of the custom section.
+        name: String,
+        /// The offset of the duplicate custom section.
+        offset: u64,
+        /// The offset of the first custom section with the same name.
+        first_offset: u64,
+    },
+}
+
+impl Display for CustomSectionLint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::SectionTooLarge {
+                name,
+                offset,
+                size,
+                limit,
+            } => write!(
+                f,
+                "custom section {name:?} at {offset:#X} has a size of {size} bytes, exceeding the limit of {limit} bytes"
+            ),
+            Self::TotalTooLarge { size, limit } => write!(
+                f,
+                "custom sections have a combined size of {size} bytes, exceeding the limit of {limit} bytes"
+            ),
+            Self::DuplicateName {
+                name,
+                offset,
+                first_offset,
+            } => write!(
+                f,
+                "custom section {name:?} at {offset:#X} has the same name as the section at {first_offset:#X}"
+            ),
+        }
+    }
+}
+
+/// Checks the custom sections of a module against the given `budget`, and reports custom
+/// sections that share the same name.
+///
+/// Large custom sections, such as those containing debugging information, often increase the
+/// size of modules deployed to production.
+///
+/// # Errors
+///
+/// Returns an error if a section or the name of a custom section could not be parsed.
+pub fn lint_custom_sections<I: Input>(
+    sections: &SectionSequence<I>,
+    budget: &CustomSectionBudget,
+) -> Parsed<Vec<CustomSectionLint>> {
+    let mut lints = Vec::new();
+    let mut first_offsets = BTreeMap::<Vec<u8>, u64>::new();
+    let mut total_size = 0u64;
+
+    for result in sections.borrow_input() {
+        let section = result?;
+        let offset = section.contents().base();
+        let size = section.length();
+
+        let custom = match CustomSection::try_from_section(section) {
+            Ok(custom) => custom.context("custom section name")?,
+            Err(_) => continue,
+        };
+
+        total_size = total_size.saturating_add(size);
+
+        let name_bytes = custom.name().borrow_input().into_bytes()?;
+        let name = || {
+            custom
+                .name()
+                .borrow_input()
+                .chars_lossy()
+                .collect::<String>()
+        };
+
+        if let Some(limit) = budget.max_section_size.filter(|limit| size > *limit) {
+            lints.push(CustomSectionLint::SectionTooLarge {
+                name: name(),
+                offset,
+                size,
+                limit,
+            });
+        }
+
+        if let Some(first_offset) = first_offsets.get(&name_bytes) {
+            lints.push(CustomSectionLint::DuplicateName {
+                name: name(),
+                offset,
+                first_offset: *first_offset,
+            });
+        } else {
+            first_offsets.insert(name_bytes, offset);
+        }
+    }
+
+    if let Some(limit) = budget.max_total_size.filter(|limit| total_size > *limit) {
+        lints.push(CustomSectionLint::TotalTooLarge {
+            size: total_size,
+            limit,
+        });
+    }
+
+    Ok(lints)
+}
@@ -0,0 +1,180 @@
+use crate::{
+    input::{Input, Window},
+    parser::Parsed,
+};
+
+const CHUNK_SIZE: usize = 256;
+
+/// Maximum length of a UTF-8 sequence that is carried over to the next chunk.
+const MAX_INCOMPLETE_CHAR: usize = 3;
+
+/// Describes the likely contents of a
+/// [data segment](https://webassembly.github.io/spec/core/syntax/modules.html#data-segments).
+///
+/// Returned by [`classify_data`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum DataContents {
+    /// The data segment contains no bytes.
+    Empty,
+    /// Every byte in the data segment is zero. Since memories are zero-initialized, such data
+    /// segments are often redundant.
+    ZeroFilled,
+    /// The data segment is valid UTF-8, and contains no control characters other than whitespace
+    /// and the `NUL` characters used to terminate C strings.
+    Text,
+    /// Byte values are distributed nearly uniformly, which is typical of compressed or encrypted
+    /// data.
+    HighEntropy,
+    /// None of the other classifications apply.
+    Binary,
+}
+
+/// A chunk of bytes provided by [`decode_utf8`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Utf8Chunk<'a> {
+    /// A sequence of valid UTF-8.
+    Valid(&'a str),
+    /// A sequence of bytes that is not valid UTF-8.
+    Invalid(&'a [u8]),
+}
+
+fn chunk_size(offset: u64, end: u64) -> usize {
+    usize::try_from(end - offset)
+        .unwrap_or(CHUNK_SIZE)
+        .min(CHUNK_SIZE)
+}
+
+/// Decodes the bytes in the given `contents` as UTF-8, providing each valid or invalid sequence
+/// to the closure `f`.
+///
+/// Bytes are read in small chunks, so large data segments can be decoded without allocating.
+/// Characters that span the boundary between chunks are correctly decoded. To decode a range of
+/// bytes within a data segment, use [`Window::with_offset_and_length()`].
+///
+/// # Errors
+///
+/// Returns an error if the bytes could not be read, or if the closure returned an error.
+pub fn decode_utf8<I, F>(contents: &Window<I>, mut f: F) -> Parsed<()>
+where
+    I: Input,
+    F: FnMut(Utf8Chunk<'_>) -> Parsed<()>,
+{
+    let mut buffer = [0u8; CHUNK_SIZE + MAX_INCOMPLETE_CHAR];
+    let mut carried = 0usize;
+    let mut offset = contents.base();
+    let end = contents.base().saturating_add(contents.length());
+
+    while offset < end {
+        let size = chunk_size(offset, end);
+        contents.read_exact_at(offset, &mut buffer[carried..carried + size])?;
+        offset += size as u64;
+
+        let length = carried + size;
+        let mut start = 0usize;
+        carried = 0;
+
+        while start < length {
+            let bytes = &buffer[start..length];
+            let (valid_length, invalid_length) = match core::str::from_utf8(bytes) {
+                Ok(_) => (bytes.len(), None),
+                Err(e) => (e.valid_up_to(), Some(e.error_len())),
+            };
+
+            if let Ok(valid) = core::str::from_utf8(&bytes[..valid_length]) {
+                if !valid.is_empty() {
+                    f(Utf8Chunk::Valid(valid))?;
+                }
+            }
+
+            start += valid_length;
+            match invalid_length {
+                None => break,
+                Some(Some(invalid_length)) => {
+                    f(Utf8Chunk::Invalid(&buffer[start..start + invalid_length]))?;
+                    start += invalid_length;
+                }
+                // Incomplete character at the end of the chunk
+                Some(None) if offset < end => {
+                    buffer.copy_within(start..length, 0);
+                    carried = length - start;
+                    break;
+                }
+                Some(None) => {
+                    f(Utf8Chunk::Invalid(&buffer[start..length]))?;
+                    break;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Uses heuristics to guess what kind of data the given `contents` of a data segment contain.
+///
+/// # Errors
+///
+/// Returns an error if the bytes could not be read.
+pub fn classify_data<I: Input>(contents: &Window<I>) -> Parsed<DataContents> {
+    let length = contents.length();
+    if length == 0 {
+        return Ok(DataContents::Empty);
+    }
+
+    let mut histogram = [0u64; 256];
+    let mut buffer = [0u8; CHUNK_SIZE];
+    let mut offset = contents.base();
+    let end = contents.base().saturating_add(length);
+    while offset < end {
+        let chunk = &mut buffer[..chunk_size(offset, end)];
+        contents.read_exact_at(offset, chunk)?;
+        offset += chunk.len() as u64;
+
+        for byte in chunk.iter() {
+            histogram[usize::from(*byte)] += 1;
+        }
+    }
+
+    if histogram[0] == length {
+        return Ok(DataContents::ZeroFilled);
+    }
+
+    let has_control_characters = (1u8..0x20)
+        .filter(|b| !matches!(b, b'\t' | b'\n' | b'\r'))
+        .chain(core::iter::once(0x7F))
+        .any(|b| histogram[usize::from(b)] != 0);
+
+    if !has_control_characters {
+        let mut is_utf8 = true;
+        decode_utf8(contents, |chunk| {
+            if let Utf8Chunk::Invalid(_) = chunk {
+                is_utf8 = false;
+            }
+            Ok(())
+        })?;
+
+        if is_utf8 {
+            return Ok(DataContents::Text);
+        }
+    }
+
+    // Pearson's chi-squared test against a uniform distribution of byte values, which has 255
+    // degrees of freedom
+    if length >= CHUNK_SIZE as u64 {
+        let expected = length as f64 / 256.0;
+        let chi_squared = histogram
+            .iter()
+            .map(|count| {
+                let difference = *count as f64 - expected;
+                difference * difference / expected
+            })
+            .sum::<f64>();
+
+        if chi_squared < 512.0 {
+            return Ok(DataContents::HighEntropy);
+        }
+    }
+
+    Ok(DataContents::Binary)
+}
@@ -0,0 +1,198 @@
+use crate::{
+    component::{ExportKind, ImportKind, KnownSection},
+    input::{BorrowInput as _, Input},
+    parser::{Parsed, ResultExt as _},
+    sections::SectionSequence,
+};
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+
+/// The number of imported definitions of each kind, which precede the definitions in the module
+/// within each index space.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct ImportCounts {
+    functions: u32,
+    tables: u32,
+    memories: u32,
+    globals: u32,
+    tags: u32,
+}
+
+impl ImportCounts {
+    /// Gets the number of imported functions.
+    #[inline]
+    pub fn functions(&self) -> u32 {
+        self.functions
+    }
+
+    /// Gets the number of imported tables.
+    #[inline]
+    pub fn tables(&self) -> u32 {
+        self.tables
+    }
+
+    /// Gets the number of imported memories.
+    #[inline]
+    pub fn memories(&self) -> u32 {
+        self.memories
+    }
+
+    /// Gets the number of imported globals.
+    #[inline]
+    pub fn globals(&self) -> u32 {
+        self.globals
+    }
+
+    /// Gets the number of imported tags.
+    #[inline]
+    pub fn tags(&self) -> u32 {
+        self.tags
+    }
+
+    /// Returns `true` if the index in the given `kind` refers to an imported definition, or
+    /// `false` if it refers to a definition in the module.
+    pub fn is_imported(&self, kind: ExportKind) -> bool {
+        match kind {
+            ExportKind::Function(index) => index.to_u32() < self.functions,
+            ExportKind::Table(index) => index.to_u32() < self.tables,
+            ExportKind::Memory(index) => index.to_u32() < self.memories,
+            ExportKind::Global(index) => index.to_u32() < self.globals,
+            ExportKind::Tag(index) => index.to_u32() < self.tags,
+        }
+    }
+
+    fn count(&mut self, kind: &ImportKind) {
+        let count = match kind {
+            ImportKind::Function(_) => &mut self.functions,
+            ImportKind::Table(_) => &mut self.tables,
+            ImportKind::Memory(_) => &mut self.memories,
+            ImportKind::Global(_) => &mut self.globals,
+            ImportKind::Tag(_) => &mut self.tags,
+        };
+
+        *count = count.saturating_add(1);
+    }
+}
+
+/// Maps the names of a module's exports to the definitions they refer to, and each exported
+/// definition to its names, along with the number of [imports](ImportCounts) of each kind.
+///
+/// # Example
+///
+/// ```
+/// use wasmiter::{analysis::ExportIndex, component::ExportKind, index::FuncIdx};
+///
+/// let wasm = wat::parse_str(r#"(module
+///     (import "env" "f" (func $f))
+///     (func $g)
+///     (export "f" (func $f))
+///     (export "g" (func $g))
+///     (export "also_g" (func $g)))"#).unwrap();
+///
+/// let sections = wasmiter::parse_module_sections(wasm.as_slice())?;
+/// let exports = ExportIndex::new(&sections)?;
+///
+/// let g = ExportKind::Function(FuncIdx::from(1u32));
+/// assert_eq!(exports.get("g"), Some(g));
+/// assert_eq!(exports.names_of(g), &["g", "also_g"]);
+/// assert_eq!(exports.imports().functions(), 1);
+/// assert!(exports.imports().is_imported(exports.get("f").unwrap()));
+/// # Ok::<(), wasmiter::parser::Error>(())
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ExportIndex {
+    by_name: BTreeMap<String, ExportKind>,
+    by_definition: BTreeMap<ExportKind, Vec<String>>,
+    imports: ImportCounts,
+}
+
+impl ExportIndex {
+    /// Reads the *import* and *export* sections of a module in a single pass over its `sections`.
+    ///
+    /// If more than one export has the same name, only the first is returned by
+    /// [`ExportIndex::get()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a section, an import, or an export could not be parsed, or if the name
+    /// of an export is not valid UTF-8.
+    pub fn new<I: Input>(sections: &SectionSequence<I>) -> Parsed<Self> {
+        let mut index = Self::default();
+        for result in sections.borrow_input() {
+            match KnownSection::interpret(result?) {
+                Ok(Ok(KnownSection::Import(mut imports))) => {
+                    while let Some(import) = imports.parse()? {
+                        index.imports.count(import.kind());
+                    }
+                }
+                Ok(Ok(KnownSection::Export(mut exports))) => {
+                    while let Some(export) = exports.parse()? {
+                        let kind = *export.kind();
+                        let name = export.name().try_into_string().context("export name")?;
+                        index.by_name.entry(name.clone()).or_insert(kind);
+                        index.by_definition.entry(kind).or_default().push(name);
+                    }
+                }
+                Ok(Err(error)) => return Err(error),
+                _ => (),
+            }
+        }
+
+        Ok(index)
+    }
+
+    /// Gets the definition exported with the given `name`.
+    #[inline]
+    pub fn get(&self, name: &str) -> Option<ExportKind> {
+        self.by_name.get(name).copied()
+    }
+
+    /// Gets the names that the given definition is exported with, in the order they appear in the
+    /// *export section*.
+    pub fn names_of(&self, definition: ExportKind) -> &[String] {
+        self.by_definition
+            .get(&definition)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Returns an iterator over the name and definition of each export, sorted by name.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (&str, ExportKind)> + '_ {
+        self.by_name
+            .iter()
+            .map(|(name, kind)| (name.as_str(), *kind))
+    }
+
+    /// Gets the number of imports of each kind.
+    #[inline]
+    pub fn imports(&self) -> &ImportCounts {
+        &self.imports
+    }
+
+    /// Gets the number of distinct export names.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.by_name.len()
+    }
+
+    /// Returns `true` if the module has no exports.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.by_name.is_empty()
+    }
+}
+
+#[cfg(feature = "mem-usage")]
+impl crate::mem_usage::MemUsage for ExportIndex {
+    fn mem_usage(&self) -> usize {
+        let by_name = self.by_name.keys().map(String::mem_usage).sum::<usize>();
+        let by_definition = self
+            .by_definition
+            .values()
+            .map(Vec::mem_usage)
+            .sum::<usize>();
+        by_name
+            + by_definition
+            + crate::mem_usage::btree_map_entries(&self.by_name)
+            + crate::mem_usage::btree_map_entries(&self.by_definition)
+    }
+}
@@ -0,0 +1,328 @@
+use crate::{
+    component::{ExportKind, Import, ImportKind, ResultType, Tag},
+    features::Feature,
+    input::Input,
+    instruction_set::Instruction,
+    parser::Parsed,
+    types::{
+        BlockType, GlobalMutability, GlobalType, HeapType, IdxType, MemType, RefType, Sharing,
+        TableType, ValType,
+    },
+    visit::ModuleVisitor,
+};
+use alloc::{collections::BTreeSet, vec::Vec};
+
+/// A [`ModuleVisitor`] that records which [`Feature`]s a module makes use of, in both the types
+/// of its definitions and the instructions within its function bodies.
+///
+/// This allows embedders to reject modules relying on unsupported proposals before compiling
+/// them.
+///
+/// # Example
+///
+/// ```
+/// use wasmiter::{analysis::FeatureDetector, features::Feature};
+///
+/// let wasm = wat::parse_str(r#"(module
+///     (memory 1)
+///     (memory 1)
+///     (func (param i32) (result i32)
+///         local.get 0
+///         i32.load 1))"#).unwrap();
+///
+/// let mut detector = FeatureDetector::new();
+/// wasmiter::visit::visit_module(wasm.as_slice(), &mut detector)?;
+/// assert!(detector.uses(Feature::MultiMemory));
+/// assert!(!detector.uses(Feature::Simd));
+/// assert_eq!(detector.features().collect::<Vec<_>>(), [Feature::MultiMemory]);
+/// # Ok::<(), wasmiter::parser::Error>(())
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct FeatureDetector {
+    features: BTreeSet<Feature>,
+    tables: u32,
+    memories: u32,
+    /// The mutability of each global, used to check exported globals.
+    globals: Vec<GlobalMutability>,
+}
+
+impl FeatureDetector {
+    /// Creates a new [`FeatureDetector`] that has not yet detected any features.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            features: BTreeSet::new(),
+            tables: 0,
+            memories: 0,
+            globals: Vec::new(),
+        }
+    }
+
+    /// Returns `true` if the given [`Feature`] was detected.
+    #[inline]
+    pub fn uses(&self, feature: Feature) -> bool {
+        self.features.contains(&feature)
+    }
+
+    /// Gets the [`Feature`]s that were detected, in sorted order.
+    #[inline]
+    pub fn features(&self) -> impl Iterator<Item = Feature> + '_ {
+        self.features.iter().copied()
+    }
+
+    /// Returns the set of [`Feature`]s that were detected.
+    #[inline]
+    pub fn into_detected(self) -> BTreeSet<Feature> {
+        self.features
+    }
+
+    #[inline]
+    pub(crate) fn insert(&mut self, feature: Feature) {
+        self.features.insert(feature);
+    }
+
+    pub(crate) fn val_type(&mut self, value_type: ValType) {
+        let feature = match value_type {
+            ValType::V128 => Feature::Simd,
+            ValType::ExternRef => Feature::ReferenceTypes,
+            ValType::ExnRef => Feature::Exceptions,
+            ValType::TypedRef {
+                heap_type: HeapType::Exn,
+                ..
+            } => Feature::Exceptions,
+            ValType::TypedRef { .. } => Feature::FunctionReferences,
+            _ => return,
+        };
+
+        self.insert(feature);
+    }
+
+    pub(crate) fn func_type(&mut self, parameters: &[ValType], results: &[ValType]) {
+        if results.len() > 1 {
+            self.insert(Feature::MultiValue);
+        }
+
+        for value_type in parameters.iter().chain(results) {
+            self.val_type(*value_type);
+        }
+    }
+
+    pub(crate) fn table(&mut self, table: &TableType) {
+        if table.element_type() != RefType::Func {
+            self.val_type(table.element_type().into());
+        }
+
+        if table.limits().index_type() == IdxType::I64 {
+            self.insert(Feature::Memory64);
+        }
+
+        self.tables += 1;
+        if self.tables > 1 {
+            self.insert(Feature::ReferenceTypes);
+        }
+    }
+
+    pub(crate) fn memory(&mut self, memory: &MemType) {
+        if memory.share() == Sharing::Shared {
+            self.insert(Feature::Threads);
+        }
+
+        if memory.index_type() == IdxType::I64 {
+            self.insert(Feature::Memory64);
+        }
+
+        self.memories += 1;
+        if self.memories > 1 {
+            self.insert(Feature::MultiMemory);
+        }
+    }
+
+    /// Records the features required by an imported or exported global.
+    pub(crate) fn external_global(&mut self, global: GlobalType) {
+        if global.mutability() == GlobalMutability::Variable {
+            self.insert(Feature::MutableGlobal);
+        }
+    }
+
+    fn memory_index<N: Into<u32>>(&mut self, index: N) {
+        if index.into() != 0 {
+            self.insert(Feature::MultiMemory);
+        }
+    }
+}
+
+impl ModuleVisitor for FeatureDetector {
+    fn visit_type<I: Input>(
+        &mut self,
+        parameters: ResultType<u64, I>,
+        results: ResultType<u64, I>,
+    ) -> Parsed<()> {
+        for result in parameters {
+            self.val_type(result?);
+        }
+
+        if results.remaining_count() > 1 {
+            self.insert(Feature::MultiValue);
+        }
+
+        for result in results {
+            self.val_type(result?);
+        }
+
+        Ok(())
+    }
+
+    fn visit_import<I: Input>(&mut self, import: &Import<I>) -> Parsed<()> {
+        match import.kind() {
+            ImportKind::Function(_) => (),
+            ImportKind::Table(table) => self.table(table),
+            ImportKind::Memory(memory) => self.memory(memory),
+            ImportKind::Global(global) => {
+                self.external_global(*global);
+                self.val_type(global.value_type());
+                self.globals.push(global.mutability());
+            }
+            ImportKind::Tag(_) => self.insert(Feature::Exceptions),
+        }
+
+        Ok(())
+    }
+
+    fn visit_table(&mut self, table: TableType) -> Parsed<()> {
+        self.table(&table);
+        Ok(())
+    }
+
+    fn visit_memory(&mut self, memory: MemType) -> Parsed<()> {
+        self.memory(&memory);
+        Ok(())
+    }
+
+    fn visit_global(&mut self, global: GlobalType) -> Parsed<()> {
+        self.val_type(global.value_type());
+        self.globals.push(global.mutability());
+        Ok(())
+    }
+
+    fn visit_export<I: Input>(&mut self, export: &crate::component::Export<I>) -> Parsed<()> {
+        match export.kind() {
+            ExportKind::Global(index)
+                if self.globals.get(usize::from(*index)) == Some(&GlobalMutability::Variable) =>
+            {
+                self.insert(Feature::MutableGlobal)
+            }
+            ExportKind::Tag(_) => self.insert(Feature::Exceptions),
+            _ => (),
+        }
+
+        Ok(())
+    }
+
+    fn visit_local_group(&mut self, _: u32, local_type: ValType) -> Parsed<()> {
+        self.val_type(local_type);
+        Ok(())
+    }
+
+    fn visit_data_count(&mut self, _: u32) -> Parsed<()> {
+        self.insert(Feature::BulkMemory);
+        Ok(())
+    }
+
+    fn visit_tag(&mut self, Tag::Exception(_): Tag) -> Parsed<()> {
+        self.insert(Feature::Exceptions);
+        Ok(())
+    }
+
+    fn visit_instruction<I: Input>(&mut self, instruction: &mut Instruction<'_, I>) -> Parsed<()> {
+        let proposals = [
+            (
+                instruction.is_from_non_trapping_float_to_int_conversions(),
+                Feature::NonTrappingFloatToInt,
+            ),
+            (
+                instruction.is_from_sign_extension_operators(),
+                Feature::SignExtension,
+            ),
+            (
+                instruction.is_from_reference_types(),
+                Feature::ReferenceTypes,
+            ),
+            (
+                instruction.is_from_bulk_memory_operations(),
+                Feature::BulkMemory,
+            ),
+            (instruction.is_from_fixed_width_simd(), Feature::Simd),
+            (instruction.is_from_tail_call(), Feature::TailCall),
+            (
+                instruction.is_from_function_references(),
+                Feature::FunctionReferences,
+            ),
+            (instruction.is_from_threads(), Feature::Threads),
+            (instruction.is_exception_handling(), Feature::Exceptions),
+        ];
+
+        for (used, feature) in proposals {
+            if used {
+                self.insert(feature);
+            }
+        }
+
+        if let Some(memarg) = instruction.memarg() {
+            self.memory_index(memarg.memory());
+        }
+
+        match instruction {
+            Instruction::Block(block_type)
+            | Instruction::Loop(block_type)
+            | Instruction::If(block_type) => self.block_type(*block_type),
+            #[cfg(feature = "exceptions")]
+            Instruction::Try(block_type) | Instruction::TryTable(block_type, _) => {
+                self.block_type(*block_type)
+            }
+            // Typed select and indirect calls through tables other than 0
+            Instruction::Select(types) if types.remaining_count() > 0 => {
+                self.insert(Feature::ReferenceTypes)
+            }
+            Instruction::CallIndirect(_, table) if u32::from(*table) != 0 => {
+                self.insert(Feature::ReferenceTypes)
+            }
+            #[cfg(feature = "tail-call")]
+            Instruction::ReturnCallIndirect(_, table) if u32::from(*table) != 0 => {
+                self.insert(Feature::ReferenceTypes)
+            }
+            Instruction::MemorySize(memory)
+            | Instruction::MemoryGrow(memory)
+            | Instruction::MemoryFill(memory)
+            | Instruction::MemoryInit(_, memory) => self.memory_index(*memory),
+            Instruction::MemoryCopy {
+                destination,
+                source,
+            } => {
+                self.memory_index(*destination);
+                self.memory_index(*source);
+            }
+            _ => (),
+        }
+
+        Ok(())
+    }
+}
+
+impl FeatureDetector {
+    fn block_type(&mut self, block_type: BlockType) {
+        match block_type {
+            BlockType::Index(_) => self.insert(Feature::MultiValue),
+            BlockType::Inline(value_type) => self.val_type(value_type),
+            BlockType::Empty => (),
+        }
+    }
+}
+
+#[cfg(feature = "mem-usage")]
+impl crate::mem_usage::MemUsage for FeatureDetector {
+    #[inline]
+    fn mem_usage(&self) -> usize {
+        self.features.len() * core::mem::size_of::<Feature>()
+            + crate::mem_usage::vec_capacity(&self.globals)
+    }
+}
@@ -0,0 +1,579 @@
+use crate::{
+    component::{ImportKind, KnownSection, Tag},
+    index::TypeIdx,
+    input::{BorrowInput as _, Input},
+    parser::{Context, Error, ErrorKind, Parsed},
+    sections::SectionSequence,
+    types::{FuncType, GlobalType, Limits, MemType, RefType, TableType, ValType},
+};
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use core::fmt::{Display, Formatter};
+
+/// The type of a definition that is provided to a module through an import, also known as an
+/// [external type](https://webassembly.github.io/spec/core/syntax/types.html#external-types).
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ExternType {
+    /// A function with the given signature.
+    Func(FuncType),
+    /// A table with the given element type and limits.
+    Table(TableType),
+    /// A memory with the given limits.
+    Memory(MemType),
+    /// A global with the given type.
+    Global(GlobalType),
+    /// An exception tag with the given parameter types, introduced as part of the
+    /// [exception handling proposal](https://github.com/WebAssembly/exception-handling).
+    Tag(FuncType),
+}
+
+impl ExternType {
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::Func(_) => "func",
+            Self::Table(_) => "table",
+            Self::Memory(_) => "memory",
+            Self::Global(_) => "global",
+            Self::Tag(_) => "tag",
+        }
+    }
+}
+
+fn write_value_types(keyword: &str, types: &[ValType], f: &mut Formatter<'_>) -> core::fmt::Result {
+    if !types.is_empty() {
+        write!(f, " ({keyword}")?;
+        for ty in types {
+            write!(f, " {ty}")?;
+        }
+        f.write_str(")")?;
+    }
+    Ok(())
+}
+
+impl Display for ExternType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "({}", self.kind())?;
+        match self {
+            Self::Func(signature) | Self::Tag(signature) => {
+                write_value_types("param", signature.parameters(), f)?;
+                write_value_types("result", signature.results(), f)?;
+            }
+            Self::Table(table) => {
+                f.write_str(" ")?;
+                crate::text::write(table, f)?;
+            }
+            Self::Memory(memory) => {
+                f.write_str(" ")?;
+                crate::text::write(memory, f)?;
+            }
+            Self::Global(global) => {
+                f.write_str(" ")?;
+                crate::text::write(*global, f)?;
+            }
+        }
+        f.write_str(")")
+    }
+}
+
+/// Describes the definitions that a host provides to the modules it instantiates.
+///
+/// See [`check_imports`] for more information.
+pub trait HostInterface {
+    /// Gets the type of the definition provided for the import with the given `module` and
+    /// `name`, or `None` if the host does not provide it.
+    fn resolve(&self, module: &str, name: &str) -> Option<&ExternType>;
+
+    /// Calls `f` with the module name, name, and type of every definition provided by the host.
+    fn for_each_provided(&self, f: &mut dyn FnMut(&str, &str, &ExternType));
+}
+
+/// A [`HostInterface`] that stores the type of each provided definition in a map.
+///
+/// # Example
+///
+/// ```
+/// use wasmiter::{analysis::{ExternType, HostImports}, types::{FuncType, ValType}};
+///
+/// let mut host = HostImports::new();
+/// host.insert("env", "log", ExternType::Func(FuncType::new([ValType::I32], [])));
+/// assert_eq!(host.len(), 1);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct HostImports {
+    definitions: BTreeMap<(String, String), ExternType>,
+}
+
+impl HostImports {
+    /// Creates an empty [`HostImports`].
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Provides a definition with the given `module` and `name`, returning the type of the
+    /// definition it replaced.
+    pub fn insert(
+        &mut self,
+        module: impl Into<String>,
+        name: impl Into<String>,
+        ty: ExternType,
+    ) -> Option<ExternType> {
+        self.definitions.insert((module.into(), name.into()), ty)
+    }
+
+    /// Gets the number of definitions that are provided.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.definitions.len()
+    }
+
+    /// Returns `true` if no definitions are provided.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.definitions.is_empty()
+    }
+}
+
+impl HostInterface for HostImports {
+    fn resolve(&self, module: &str, name: &str) -> Option<&ExternType> {
+        // Tuples of Strings can't be borrowed as tuples of strs, so a linear search of the module
+        // is used instead
+        self.definitions
+            .range((String::from(module), String::new())..)
+            .take_while(|((m, _), _)| m == module)
+            .find_map(|((_, n), ty)| Some(ty).filter(|_| n == name))
+    }
+
+    fn for_each_provided(&self, f: &mut dyn FnMut(&str, &str, &ExternType)) {
+        for ((module, name), ty) in self.definitions.iter() {
+            f(module, name, ty);
+        }
+    }
+}
+
+/// Describes a single difference between the type required by an import and the type provided
+/// by the host.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum TypeDifference {
+    /// The host provides a different kind of definition, such as a memory instead of a function.
+    Kind,
+    /// The number of parameters differ.
+    ParameterCount {
+        /// The number of parameters the module requires.
+        required: usize,
+        /// The number of parameters the host provides.
+        provided: usize,
+    },
+    /// The type of a parameter differs.
+    Parameter {
+        /// The index of the parameter.
+        index: usize,
+        /// The type of the parameter the module requires.
+        required: ValType,
+        /// The type of the parameter the host provides.
+        provided: ValType,
+    },
+    /// The number of results differ.
+    ResultCount {
+        /// The number of results the module requires.
+        required: usize,
+        /// The number of results the host provides.
+        provided: usize,
+    },
+    /// The type of a result differs.
+    Result {
+        /// The index of the result.
+        index: usize,
+        /// The type of the result the module requires.
+        required: ValType,
+        /// The type of the result the host provides.
+        provided: ValType,
+    },
+    /// The element type of a table differs.
+    ElementType {
+        /// The element type the module requires.
+        required: RefType,
+        /// The element type the host provides.
+        provided: RefType,
+    },
+    /// The limits of a table or memory provided by the host do not
+    /// [match](https://webassembly.github.io/spec/core/valid/types.html#limits) the limits
+    /// required by the module.
+    Limits {
+        /// The limits the module requires.
+        required: Limits,
+        /// The limits the host provides.
+        provided: Limits,
+    },
+    /// The type or mutability of a global differs.
+    Global {
+        /// The type of the global the module requires.
+        required: GlobalType,
+        /// The type of the global the host provides.
+        provided: GlobalType,
+    },
+}
+
+impl Display for TypeDifference {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Kind => f.write_str("kind of definition differs"),
+            Self::ParameterCount { required, provided } => {
+                write!(f, "expected {required} parameters, but got {provided}")
+            }
+            Self::Parameter {
+                index,
+                required,
+                provided,
+            } => write!(
+                f,
+                "expected parameter {index} to be {required}, but got {provided}"
+            ),
+            Self::ResultCount { required, provided } => {
+                write!(f, "expected {required} results, but got {provided}")
+            }
+            Self::Result {
+                index,
+                required,
+                provided,
+            } => write!(
+                f,
+                "expected result {index} to be {required}, but got {provided}"
+            ),
+            Self::ElementType { required, provided } => {
+                write!(f, "expected element type {required}, but got {provided}")
+            }
+            Self::Limits { required, provided } => {
+                f.write_str("expected limits ")?;
+                crate::text::write(required, f)?;
+                f.write_str(", but got ")?;
+                crate::text::write(provided, f)
+            }
+            Self::Global { required, provided } => {
+                f.write_str("expected global ")?;
+                crate::text::write(*required, f)?;
+                f.write_str(", but got ")?;
+                crate::text::write(*provided, f)
+            }
+        }
+    }
+}
+
+/// Describes a difference between the imports of a module and a [`HostInterface`].
+///
+/// Names that are not valid UTF-8 are converted lossily.
+///
+/// Returned by [`check_imports`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ImportMismatch {
+    /// The module imports a definition that the host does not provide.
+    Missing {
+        /// The module name of the import.
+        module: String,
+        /// The name of the import.
+        name: String,
+        /// The type the module requires.
+        required: ExternType,
+    },
+    /// The host provides a definition whose type does not match the type required by the module.
+    Mismatched {
+        /// The module name of the import.
+        module: String,
+        /// The name of the import.
+        name: String,
+        /// The type the module requires.
+        required: ExternType,
+        /// The type the host provides.
+        provided: ExternType,
+        /// Each difference between the `required` and `provided` types.
+        differences: Vec<TypeDifference>,
+    },
+    /// The host provides a definition that the module does not import.
+    Extra {
+        /// The module name of the definition.
+        module: String,
+        /// The name of the definition.
+        name: String,
+        /// The type the host provides.
+        provided: ExternType,
+    },
+}
+
+impl Display for ImportMismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Missing {
+                module,
+                name,
+                required,
+            } => write!(f, "missing import {module:?} {name:?} {required}"),
+            Self::Mismatched {
+                module,
+                name,
+                required,
+                provided,
+                differences,
+            } => {
+                write!(
+                    f,
+                    "import {module:?} {name:?} requires {required}, but host provides {provided}"
+                )?;
+                for difference in differences {
+                    write!(f, "; {difference}")?;
+                }
+                Ok(())
+            }
+            Self::Extra {
+                module,
+                name,
+                provided,
+            } => write!(f, "unused host definition {module:?} {name:?} {provided}"),
+        }
+    }
+}
+
+fn signature_differences(
+    required: &FuncType,
+    provided: &FuncType,
+    differences: &mut Vec<TypeDifference>,
+) {
+    if required.parameters().len() != provided.parameters().len() {
+        differences.push(TypeDifference::ParameterCount {
+            required: required.parameters().len(),
+            provided: provided.parameters().len(),
+        });
+    }
+
+    let parameters = required.parameters().iter().zip(provided.parameters());
+    for (index, (required, provided)) in parameters.enumerate() {
+        if required != provided {
+            differences.push(TypeDifference::Parameter {
+                index,
+                required: *required,
+                provided: *provided,
+            });
+        }
+    }
+
+    if required.results().len() != provided.results().len() {
+        differences.push(TypeDifference::ResultCount {
+            required: required.results().len(),
+            provided: provided.results().len(),
+        });
+    }
+
+    let results = required.results().iter().zip(provided.results());
+    for (index, (required, provided)) in results.enumerate() {
+        if required != provided {
+            differences.push(TypeDifference::Result {
+                index,
+                required: *required,
+                provided: *provided,
+            });
+        }
+    }
+}
+
+/// Compares the `required` and `provided` types, returning every difference between them.
+///
+/// An empty result means the `provided` definition can be used to satisfy the import.
+pub fn type_differences(required: &ExternType, provided: &ExternType) -> Vec<TypeDifference> {
+    let mut differences = Vec::new();
+    match (required, provided) {
+        (ExternType::Func(required), ExternType::Func(provided))
+        | (ExternType::Tag(required), ExternType::Tag(provided)) => {
+            signature_differences(required, provided, &mut differences)
+        }
+        (ExternType::Table(required), ExternType::Table(provided)) => {
+            if required.element_type() != provided.element_type() {
+                differences.push(TypeDifference::ElementType {
+                    required: required.element_type(),
+                    provided: provided.element_type(),
+                });
+            }
+
+            if !provided.limits().matches(required.limits()) {
+                differences.push(TypeDifference::Limits {
+                    required: *required.limits(),
+                    provided: *provided.limits(),
+                });
+            }
+        }
+        (ExternType::Memory(required), ExternType::Memory(provided)) => {
+            if !provided.matches(required) {
+                differences.push(TypeDifference::Limits {
+                    required: *required,
+                    provided: *provided,
+                });
+            }
+        }
+        (ExternType::Global(required), ExternType::Global(provided)) => {
+            if required != provided {
+                differences.push(TypeDifference::Global {
+                    required: *required,
+                    provided: *provided,
+                });
+            }
+        }
+        _ => differences.push(TypeDifference::Kind),
+    }
+    differences
+}
+
+#[inline(never)]
+#[cold]
+fn unknown_type(index: TypeIdx) -> Error {
+    Error::new(ErrorKind::InvalidFormat).with_context(Context::from_closure(move |f| {
+        write!(
+            f,
+            "import refers to type {}, which is not defined",
+            index.to_u32()
+        )
+    }))
+}
+
+/// Compares the imports of a module against the definitions provided by a `host`, reporting
+/// imports that are missing or have mismatched types, and definitions that are never imported.
+///
+/// Mismatches are returned in the order of the module's imports, followed by any
+/// [`Extra`](ImportMismatch::Extra) definitions in the order given by
+/// [`HostInterface::for_each_provided`].
+///
+/// # Errors
+///
+/// Returns an error if the *type* or *import* section could not be parsed, or if an import refers
+/// to a type that is not defined.
+///
+/// # Example
+///
+/// ```
+/// use wasmiter::{analysis::{self, ExternType, HostImports, ImportMismatch}, types::*};
+///
+/// let wasm = wat::parse_str(r#"(module
+///     (import "env" "log" (func (param i64)))
+///     (import "env" "memory" (memory 1)))"#).unwrap();
+///
+/// let mut host = HostImports::new();
+/// host.insert("env", "log", ExternType::Func(FuncType::new([ValType::I32], [])));
+/// host.insert("env", "exit", ExternType::Func(FuncType::new([ValType::I32], [])));
+///
+/// let sections = wasmiter::parse_module_sections(wasm.as_slice())?;
+/// let mismatches = analysis::check_imports(&sections, &host)?;
+/// assert_eq!(mismatches.len(), 3);
+/// assert!(matches!(mismatches[0], ImportMismatch::Mismatched { .. }));
+/// assert!(matches!(mismatches[1], ImportMismatch::Missing { .. }));
+/// assert!(matches!(mismatches[2], ImportMismatch::Extra { .. }));
+/// # Ok::<(), wasmiter::parser::Error>(())
+/// ```
+pub fn check_imports<I, H>(sections: &SectionSequence<I>, host: &H) -> Parsed<Vec<ImportMismatch>>
+where
+    I: Input,
+    H: HostInterface + ?Sized,
+{
+    let mut types = Vec::<FuncType>::new();
+    let mut mismatches = Vec::new();
+    let mut imported = Vec::<(String, String)>::new();
+
+    for result in sections.borrow_input() {
+        let known = match KnownSection::interpret(result?) {
+            Ok(known) => known?,
+            Err(_) => continue,
+        };
+
+        match known {
+            KnownSection::Type(mut section) => {
+                while let Some(ty) = section.parse(
+                    |parameters| parameters.collect::<Parsed<Vec<_>>>(),
+                    |parameters, results| {
+                        Ok(FuncType::new(
+                            parameters,
+                            results.collect::<Parsed<Vec<_>>>()?,
+                        ))
+                    },
+                )? {
+                    types.push(ty);
+                }
+            }
+            KnownSection::Import(mut section) => {
+                while let Some(import) = section.parse()? {
+                    let signature = |index: TypeIdx| {
+                        types
+                            .get(usize::from(index))
+                            .cloned()
+                            .ok_or_else(|| unknown_type(index))
+                    };
+
+                    let required = match *import.kind() {
+                        ImportKind::Function(index) => ExternType::Func(signature(index)?),
+                        ImportKind::Table(table) => ExternType::Table(table),
+                        ImportKind::Memory(memory) => ExternType::Memory(memory),
+                        ImportKind::Global(global) => ExternType::Global(global),
+                        ImportKind::Tag(Tag::Exception(index)) => {
+                            ExternType::Tag(signature(index)?)
+                        }
+                    };
+
+                    let module = import
+                        .module()
+                        .borrow_input()
+                        .chars_lossy()
+                        .collect::<String>();
+                    let name = import
+                        .name()
+                        .borrow_input()
+                        .chars_lossy()
+                        .collect::<String>();
+
+                    match host.resolve(&module, &name) {
+                        None => mismatches.push(ImportMismatch::Missing {
+                            module: module.clone(),
+                            name: name.clone(),
+                            required,
+                        }),
+                        Some(provided) => {
+                            let differences = type_differences(&required, provided);
+                            if !differences.is_empty() {
+                                mismatches.push(ImportMismatch::Mismatched {
+                                    module: module.clone(),
+                                    name: name.clone(),
+                                    required,
+                                    provided: provided.clone(),
+                                    differences,
+                                });
+                            }
+                        }
+                    }
+
+                    imported.push((module, name));
+                }
+            }
+            _ => (),
+        }
+    }
+
+    host.for_each_provided(&mut |module, name, provided| {
+        let is_imported = imported.iter().any(|(m, n)| m == module && n == name);
+        if !is_imported {
+            mismatches.push(ImportMismatch::Extra {
+                module: module.into(),
+                name: name.into(),
+                provided: provided.clone(),
+            });
+        }
+    });
+
+    Ok(mismatches)
+}
+
+#[cfg(feature = "mem-usage")]
+impl crate::mem_usage::MemUsage for HostImports {
+    fn mem_usage(&self) -> usize {
+        self.definitions
+            .keys()
+            .map(|(module, name)| module.mem_usage() + name.mem_usage())
+            .sum::<usize>()
+            + crate::mem_usage::btree_map_entries(&self.definitions)
+    }
+}
@@ -0,0 +1,293 @@
+use crate::{
+    component::{ImportKind, KnownSection},
+    custom::{name::NameSubsection, CustomSection, KnownCustomSection},
+    index::FuncIdx,
+    input::{BorrowInput as _, Input},
+    parser::{Parsed, ResultExt as _},
+    sections::SectionSequence,
+};
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use core::fmt::{Display, Formatter};
+
+/// The size of a section, returned by [`SizeProfile::sections()`].
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct SectionSize {
+    id: u8,
+    name: Option<String>,
+    size: u64,
+}
+
+impl SectionSize {
+    /// Gets the [*id*](crate::sections::id) of the section.
+    #[inline]
+    pub fn id(&self) -> u8 {
+        self.id
+    }
+
+    /// Gets the name of the custom section, or `None` if this is not a custom section.
+    #[inline]
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Gets the size of the section, in bytes, including its *id* and size.
+    #[inline]
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+}
+
+/// The size of a function's entry in the *code section*, returned by
+/// [`SizeProfile::functions()`].
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct FunctionSize {
+    index: FuncIdx,
+    name: Option<String>,
+    size: u64,
+}
+
+impl FunctionSize {
+    /// Gets the index of the function, which includes any imported functions.
+    #[inline]
+    pub fn index(&self) -> FuncIdx {
+        self.index
+    }
+
+    /// Gets the name of the function from the *name section*, if names were requested and the
+    /// function has one.
+    #[inline]
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Gets the size of the function's local declarations and body, in bytes, excluding the size
+    /// of the entry.
+    #[inline]
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+}
+
+/// Describes how the bytes of a WebAssembly module are distributed among its sections and
+/// functions, similar to the output of [`twiggy`](https://github.com/rustwasm/twiggy).
+///
+/// Returned by [`size_profile`]. The [`Display`] implementation writes a table of each section
+/// and function, along with its share of the module's size.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SizeProfile {
+    total_size: u64,
+    sections: Vec<SectionSize>,
+    functions: Vec<FunctionSize>,
+}
+
+impl SizeProfile {
+    /// Gets the size of the entire module, in bytes, including the preamble.
+    #[inline]
+    pub fn total_size(&self) -> u64 {
+        self.total_size
+    }
+
+    /// Gets the size of each section, sorted from largest to smallest.
+    ///
+    /// Sections with the same size are kept in the order they appear in the module. Along with the
+    /// 8-byte preamble, the sizes of all sections add up to the [`total_size`](Self::total_size).
+    #[inline]
+    pub fn sections(&self) -> &[SectionSize] {
+        &self.sections
+    }
+
+    /// Gets the size of each function defined in the *code section*, sorted from largest to
+    /// smallest.
+    ///
+    /// Functions with the same size are sorted by their index.
+    #[inline]
+    pub fn functions(&self) -> &[FunctionSize] {
+        &self.functions
+    }
+}
+
+struct Percent {
+    size: u64,
+    total: u64,
+}
+
+impl Display for Percent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let hundredths = u128::from(self.size) * 10000 / u128::from(self.total.max(1));
+        write!(f, "{:>3}.{:02}%", hundredths / 100, hundredths % 100)
+    }
+}
+
+impl Display for SizeProfile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let percent = |size| Percent {
+            size,
+            total: self.total_size,
+        };
+
+        writeln!(f, "{:>10} {:>7}  Section", "Bytes", "%")?;
+        for section in self.sections.iter() {
+            write!(
+                f,
+                "{:>10} {}  {}",
+                section.size,
+                percent(section.size),
+                crate::sections::id::name(section.id).unwrap_or("Unknown")
+            )?;
+            if let Some(name) = section.name() {
+                write!(f, " {name:?}")?;
+            }
+            writeln!(f)?;
+        }
+
+        writeln!(
+            f,
+            "{:>10} {}  Total",
+            self.total_size,
+            percent(self.total_size)
+        )?;
+
+        if !self.functions.is_empty() {
+            writeln!(f, "\n{:>10} {:>7}  Function", "Bytes", "%")?;
+            for function in self.functions.iter() {
+                write!(
+                    f,
+                    "{:>10} {}  func[{}]",
+                    function.size,
+                    percent(function.size),
+                    function.index.to_u32()
+                )?;
+                if let Some(name) = function.name() {
+                    write!(f, " ${name}")?;
+                }
+                writeln!(f)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Reads the size of each section and each function body in a WebAssembly module, optionally
+/// using the *name section* to look up the `names` of functions.
+///
+/// # Example
+///
+/// ```
+/// use wasmiter::{analysis, sections::id};
+///
+/// let wasm = wat::parse_str(r#"(module
+///     (func $small)
+///     (func $large (result i64) i64.const 1 i64.const 2 i64.add))"#).unwrap();
+///
+/// let sections = wasmiter::parse_module_sections(wasm.as_slice())?;
+/// let profile = analysis::size_profile(&sections, true)?;
+///
+/// assert_eq!(profile.total_size(), wasm.len() as u64);
+/// assert_eq!(profile.functions()[0].name(), Some("large"));
+/// assert_eq!(profile.functions()[1].size(), 2);
+/// assert!(profile.sections().iter().any(|section| section.id() == id::CODE));
+/// # Ok::<(), wasmiter::parser::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if a section, an import, the name of a custom section, or an entry in the
+/// *code section* could not be parsed, or if a name was not valid UTF-8.
+pub fn size_profile<I: Input>(sections: &SectionSequence<I>, names: bool) -> Parsed<SizeProfile> {
+    let mut profile = SizeProfile {
+        total_size: 8,
+        ..SizeProfile::default()
+    };
+    let mut imported_function_count = 0u32;
+    let mut function_names = None;
+
+    for result in sections.borrow_input() {
+        let section = result?;
+        let id = section.id();
+
+        // Sections are contiguous, so each one starts where the previous one ended
+        let start = profile.total_size;
+        profile.total_size = section.contents().base().saturating_add(section.length());
+        let size = profile.total_size.saturating_sub(start);
+
+        let section = match CustomSection::try_from_section(section) {
+            Ok(custom) => {
+                let custom = custom?;
+                let name = custom
+                    .name()
+                    .try_into_string()
+                    .context("custom section name")?;
+                profile.sections.push(SectionSize {
+                    id,
+                    name: Some(name),
+                    size,
+                });
+
+                if names && function_names.is_none() {
+                    if let Ok(KnownCustomSection::Name(mut name_section)) =
+                        KnownCustomSection::interpret(custom)
+                    {
+                        let mut map = BTreeMap::new();
+                        while let Some(subsection) = name_section.parse() {
+                            // Unrecognized subsections are skipped
+                            if let Ok(subsection) = subsection {
+                                if let NameSubsection::FunctionName(mut names) = subsection? {
+                                    while let Some(assoc) = names.parse()? {
+                                        map.insert(assoc.index(), assoc.name().try_into_string()?);
+                                    }
+                                }
+                            }
+                        }
+                        function_names = Some(map);
+                    }
+                }
+
+                continue;
+            }
+            Err(section) => section,
+        };
+
+        profile.sections.push(SectionSize {
+            id,
+            name: None,
+            size,
+        });
+
+        match KnownSection::interpret(section) {
+            Ok(Ok(KnownSection::Import(mut imports))) => {
+                while let Some(import) = imports.parse()? {
+                    if let ImportKind::Function(_) = import.kind() {
+                        imported_function_count = imported_function_count.saturating_add(1);
+                    }
+                }
+            }
+            Ok(Ok(KnownSection::Code(code))) => {
+                for entry in code.with_function_indices(imported_function_count) {
+                    let (index, code) = entry?;
+                    profile.functions.push(FunctionSize {
+                        index,
+                        name: None,
+                        size: code.content().length(),
+                    });
+                }
+            }
+            Ok(Err(error)) => return Err(error),
+            _ => (),
+        }
+    }
+
+    if let Some(mut function_names) = function_names {
+        for function in profile.functions.iter_mut() {
+            function.name = function_names.remove(&function.index);
+        }
+    }
+
+    profile
+        .sections
+        .sort_by_key(|section| core::cmp::Reverse(section.size));
+    profile
+        .functions
+        .sort_by_key(|function| (core::cmp::Reverse(function.size), function.index));
+    Ok(profile)
+}
@@ -0,0 +1,339 @@
+use crate::{
+    component::{ImportKind, KnownSection, Tag},
+    index::{FuncIdx, TypeIdx},
+    input::{BorrowInput as _, Input},
+    instruction_set::Instruction,
+    parser::Parsed,
+    sections::SectionSequence,
+    types::BlockType,
+};
+use alloc::vec::Vec;
+
+/// Describes the maximum depth of the value stack and the maximum nesting of
+/// [structured control instructions](https://webassembly.github.io/spec/core/syntax/instructions.html#control-instructions)
+/// within a function body.
+///
+/// Returned by [`stack_depth`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct StackDepth {
+    function: FuncIdx,
+    max_value_stack: u32,
+    max_nesting: u32,
+}
+
+impl StackDepth {
+    /// The index of the function.
+    #[inline]
+    pub fn function(&self) -> FuncIdx {
+        self.function
+    }
+
+    /// The maximum number of operands on the value stack at any point during execution of the
+    /// function body.
+    ///
+    /// Parameters and local variables are not included.
+    #[inline]
+    pub fn max_value_stack(&self) -> u32 {
+        self.max_value_stack
+    }
+
+    /// The maximum number of nested **block**, **loop**, **if**, and **try** instructions.
+    #[inline]
+    pub fn max_nesting(&self) -> u32 {
+        self.max_nesting
+    }
+}
+
+/// The number of parameters and results of a function type.
+type Signature = (u32, u32);
+
+#[derive(Default)]
+struct Signatures {
+    types: Vec<Signature>,
+    functions: Vec<TypeIdx>,
+    tags: Vec<TypeIdx>,
+}
+
+impl Signatures {
+    fn of_type(&self, index: TypeIdx) -> Signature {
+        self.types
+            .get(usize::from(index))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn of_block(&self, block_type: BlockType) -> Signature {
+        match block_type {
+            BlockType::Empty => (0, 0),
+            BlockType::Inline(_) => (0, 1),
+            BlockType::Index(index) => self.of_type(index),
+        }
+    }
+
+    fn of_function(&self, index: FuncIdx) -> Signature {
+        self.functions
+            .get(usize::from(index))
+            .map_or((0, 0), |ty| self.of_type(*ty))
+    }
+
+    #[cfg(feature = "exceptions")]
+    fn tag_parameters(&self, index: crate::index::TagIdx) -> u32 {
+        self.tags
+            .get(usize::from(index))
+            .map_or(0, |ty| self.of_type(*ty).0)
+    }
+}
+
+struct Frame {
+    height: u32,
+    parameters: u32,
+    results: u32,
+}
+
+/// Tracks the height of the value stack, without checking operand types.
+struct Tracker {
+    frames: Vec<Frame>,
+    height: u32,
+    max_height: u32,
+    max_nesting: u32,
+}
+
+impl Tracker {
+    fn new(results: u32) -> Self {
+        Self {
+            frames: alloc::vec![Frame {
+                height: 0,
+                parameters: 0,
+                results,
+            }],
+            height: 0,
+            max_height: 0,
+            max_nesting: 0,
+        }
+    }
+
+    fn floor(&self) -> u32 {
+        self.frames.last().map_or(0, |frame| frame.height)
+    }
+
+    fn pop(&mut self, count: u32) {
+        // Operands are never popped past the start of the current block, which also handles
+        // polymorphic stacks following unconditional branches
+        self.height = self.height.saturating_sub(count).max(self.floor());
+    }
+
+    fn push(&mut self, count: u32) {
+        self.height = self.height.saturating_add(count);
+        self.max_height = self.max_height.max(self.height);
+    }
+
+    fn enter(&mut self, (parameters, results): Signature) {
+        self.pop(parameters);
+        self.frames.push(Frame {
+            height: self.height,
+            parameters,
+            results,
+        });
+        self.push(parameters);
+
+        // Function body is not counted as a nested block
+        let nesting = u32::try_from(self.frames.len() - 1).unwrap_or(u32::MAX);
+        self.max_nesting = self.max_nesting.max(nesting);
+    }
+
+    fn restart(&mut self, pushed: u32) {
+        self.height = self.floor();
+        self.push(pushed);
+    }
+
+    fn exit(&mut self) {
+        if let Some(frame) = self.frames.pop() {
+            self.height = frame.height;
+            self.push(frame.results);
+        }
+    }
+
+    /// Called after an unconditional branch, which makes the stack polymorphic.
+    fn set_unreachable(&mut self) {
+        self.height = self.floor();
+    }
+
+    fn instruction<I: Input>(&mut self, signatures: &Signatures, instruction: &Instruction<I>) {
+        if let Some(effect) = instruction.stack_effect() {
+            self.pop(effect.pops().into());
+            self.push(effect.pushes().into());
+            return;
+        }
+
+        match instruction {
+            Instruction::Block(block_type) | Instruction::Loop(block_type) => {
+                self.enter(signatures.of_block(*block_type))
+            }
+            #[cfg(feature = "exceptions")]
+            Instruction::Try(block_type) | Instruction::TryTable(block_type, _) => {
+                self.enter(signatures.of_block(*block_type))
+            }
+            Instruction::If(block_type) => {
+                self.pop(1);
+                self.enter(signatures.of_block(*block_type));
+            }
+            Instruction::Else => {
+                let parameters = self.frames.last().map_or(0, |frame| frame.parameters);
+                self.restart(parameters);
+            }
+            #[cfg(feature = "exceptions")]
+            Instruction::Catch(tag) => self.restart(signatures.tag_parameters(*tag)),
+            #[cfg(feature = "exceptions")]
+            Instruction::CatchAll => self.restart(0),
+            Instruction::End => self.exit(),
+            #[cfg(feature = "exceptions")]
+            Instruction::Delegate(_) => self.exit(),
+            Instruction::BrIf(_) => self.pop(1),
+            // The non-null reference remains on the stack when the branch is not taken
+            #[cfg(feature = "function-references")]
+            Instruction::BrOnNull(_) => (),
+            #[cfg(feature = "function-references")]
+            Instruction::BrOnNonNull(_) => self.pop(1),
+            Instruction::BrTable(_) => {
+                self.pop(1);
+                self.set_unreachable();
+            }
+            Instruction::Br(_) | Instruction::Return | Instruction::Unreachable => {
+                self.set_unreachable()
+            }
+            #[cfg(feature = "exceptions")]
+            Instruction::Rethrow(_) => self.set_unreachable(),
+            #[cfg(feature = "exceptions")]
+            Instruction::ThrowRef => {
+                self.pop(1);
+                self.set_unreachable();
+            }
+            #[cfg(feature = "exceptions")]
+            Instruction::Throw(tag) => {
+                self.pop(signatures.tag_parameters(*tag));
+                self.set_unreachable();
+            }
+            Instruction::Call(callee) => {
+                let (parameters, results) = signatures.of_function(*callee);
+                self.pop(parameters);
+                self.push(results);
+            }
+            Instruction::CallIndirect(ty, _) => {
+                let (parameters, results) = signatures.of_type(*ty);
+                self.pop(parameters.saturating_add(1));
+                self.push(results);
+            }
+            #[cfg(feature = "tail-call")]
+            Instruction::ReturnCall(callee) => {
+                self.pop(signatures.of_function(*callee).0);
+                self.set_unreachable();
+            }
+            #[cfg(feature = "tail-call")]
+            Instruction::ReturnCallIndirect(ty, _) => {
+                self.pop(signatures.of_type(*ty).0.saturating_add(1));
+                self.set_unreachable();
+            }
+            #[cfg(feature = "function-references")]
+            Instruction::CallRef(ty) => {
+                let (parameters, results) = signatures.of_type(*ty);
+                self.pop(parameters.saturating_add(1));
+                self.push(results);
+            }
+            #[cfg(feature = "function-references")]
+            Instruction::ReturnCallRef(ty) => {
+                self.pop(signatures.of_type(*ty).0.saturating_add(1));
+                self.set_unreachable();
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Estimates the maximum value stack depth and maximum control nesting depth of each function
+/// defined in a module, in the order they are defined.
+///
+/// The effect of each instruction on the value stack is determined using its
+/// [`StackEffect`](crate::instruction_set::StackEffect), along with the types of any referenced
+/// functions, blocks, and tags. Function bodies are not validated, so results for invalid code
+/// are only approximations.
+///
+/// # Errors
+///
+/// Returns an error if a section could not be parsed.
+pub fn stack_depth<I: Input>(sections: &SectionSequence<I>) -> Parsed<Vec<StackDepth>> {
+    let mut signatures = Signatures::default();
+    let mut depths = Vec::new();
+    let mut imported_function_count = 0u32;
+
+    for result in sections.borrow_input() {
+        let known = match KnownSection::interpret(result?) {
+            Ok(known) => known?,
+            Err(_) => continue,
+        };
+
+        match known {
+            KnownSection::Type(mut types) => {
+                while let Some(signature) = types.parse(
+                    |parameters| Ok(parameters.remaining_count()),
+                    |parameters, results| Ok((parameters, results.remaining_count())),
+                )? {
+                    signatures.types.push(signature);
+                }
+            }
+            KnownSection::Import(mut imports) => {
+                while let Some(import) = imports.parse()? {
+                    match import.kind() {
+                        ImportKind::Function(ty) => {
+                            signatures.functions.push(*ty);
+                            imported_function_count += 1;
+                        }
+                        ImportKind::Tag(Tag::Exception(ty)) => signatures.tags.push(*ty),
+                        _ => (),
+                    }
+                }
+            }
+            KnownSection::Function(functions) => {
+                for result in functions {
+                    signatures.functions.push(result?);
+                }
+            }
+            KnownSection::Tag(tags) => {
+                for result in tags {
+                    let Tag::Exception(ty) = result?;
+                    signatures.tags.push(ty);
+                }
+            }
+            KnownSection::Code(mut code) => {
+                let mut index = imported_function_count;
+                while let Some(entry) = code.parse()? {
+                    let function = FuncIdx::from(index);
+                    let mut tracker = Tracker::new(signatures.of_function(function).1);
+
+                    entry.read(
+                        |_| Ok(()),
+                        |(), body| {
+                            while let Some(result) = body.next(|instruction| {
+                                tracker.instruction(&signatures, instruction);
+                                Parsed::Ok(())
+                            }) {
+                                result?;
+                            }
+                            Parsed::Ok(())
+                        },
+                    )?;
+
+                    depths.push(StackDepth {
+                        function,
+                        max_value_stack: tracker.max_height,
+                        max_nesting: tracker.max_nesting,
+                    });
+
+                    index = index.saturating_add(1);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    Ok(depths)
+}
@@ -0,0 +1,206 @@
+use crate::{
+    component::{DataMode, ElementInit, ElementMode, KnownSection},
+    index::FuncIdx,
+    input::{BorrowInput as _, Input},
+    parser::Parsed,
+    sections::SectionSequence,
+};
+
+/// Estimates the amount of work performed during
+/// [instantiation](https://webassembly.github.io/spec/core/exec/modules.html#exec-instantiation)
+/// of a WebAssembly module.
+///
+/// Only entities defined within the module are taken into account, since imported memories,
+/// tables, and globals are allocated by the host.
+///
+/// Returned by [`startup_cost`].
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct StartupCost {
+    active_data_segments: u32,
+    active_data_bytes: u64,
+    active_element_segments: u32,
+    active_element_entries: u64,
+    global_initializers: u32,
+    memory_pages: u64,
+    memory_bytes: u64,
+    table_elements: u64,
+    start_function: Option<FuncIdx>,
+}
+
+impl StartupCost {
+    /// The number of
+    /// [**active** data segments](https://webassembly.github.io/spec/core/syntax/modules.html#data-segments)
+    /// that are copied into memory.
+    #[inline]
+    pub fn active_data_segments(&self) -> u32 {
+        self.active_data_segments
+    }
+
+    /// The total number of bytes copied into memory by **active** data segments.
+    #[inline]
+    pub fn active_data_bytes(&self) -> u64 {
+        self.active_data_bytes
+    }
+
+    /// The number of
+    /// [**active** element segments](https://webassembly.github.io/spec/core/syntax/modules.html#element-segments)
+    /// that are copied into tables.
+    #[inline]
+    pub fn active_element_segments(&self) -> u32 {
+        self.active_element_segments
+    }
+
+    /// The total number of elements written into tables by **active** element segments.
+    #[inline]
+    pub fn active_element_entries(&self) -> u64 {
+        self.active_element_entries
+    }
+
+    /// The number of
+    /// [global](https://webassembly.github.io/spec/core/syntax/modules.html#globals) initializer
+    /// expressions that are evaluated.
+    #[inline]
+    pub fn global_initializers(&self) -> u32 {
+        self.global_initializers
+    }
+
+    /// The total minimum number of pages allocated for memories defined in the module.
+    #[inline]
+    pub fn memory_pages(&self) -> u64 {
+        self.memory_pages
+    }
+
+    /// The total minimum number of bytes allocated for memories defined in the module, taking
+    /// into account the [page size](crate::types::Limits::page_size) of each memory.
+    #[inline]
+    pub fn memory_bytes(&self) -> u64 {
+        self.memory_bytes
+    }
+
+    /// The total minimum number of elements allocated for tables defined in the module.
+    #[inline]
+    pub fn table_elements(&self) -> u64 {
+        self.table_elements
+    }
+
+    /// The
+    /// [**start** function](https://webassembly.github.io/spec/core/syntax/modules.html#start-function)
+    /// that is invoked at the end of instantiation, if one is present.
+    #[inline]
+    pub fn start_function(&self) -> Option<FuncIdx> {
+        self.start_function
+    }
+}
+
+/// Calculates a [`StartupCost`] estimate from the given sequence of module `sections`.
+///
+/// Only the *memory*, *table*, *global*, *start*, *element*, and *data* sections are parsed; the
+/// contents of all other sections are skipped.
+///
+/// # Errors
+///
+/// Returns an error if a section could not be parsed.
+pub fn startup_cost<I: Input>(sections: &SectionSequence<I>) -> Parsed<StartupCost> {
+    let mut cost = StartupCost::default();
+
+    for result in sections.borrow_input() {
+        let known = match KnownSection::interpret(result?) {
+            Ok(known) => known?,
+            Err(_) => continue,
+        };
+
+        match known {
+            KnownSection::Memory(memories) => {
+                for result in memories {
+                    let memory = result?;
+                    cost.memory_pages = cost.memory_pages.saturating_add(memory.minimum());
+                    cost.memory_bytes = cost
+                        .memory_bytes
+                        .saturating_add(memory.minimum().saturating_mul(memory.page_size()));
+                }
+            }
+            KnownSection::Table(tables) => {
+                for result in tables {
+                    let table = result?;
+                    cost.table_elements =
+                        cost.table_elements.saturating_add(table.limits().minimum());
+                }
+            }
+            KnownSection::Global(mut globals) => {
+                while globals.parse(|_, _| Ok(()))?.is_some() {
+                    cost.global_initializers += 1;
+                }
+            }
+            KnownSection::Start(start) => cost.start_function = Some(start),
+            KnownSection::Element(mut elements) => loop {
+                let result = elements.parse(
+                    |mode| Ok(matches!(mode, ElementMode::Active(_, _))),
+                    |active, init| {
+                        if active {
+                            let count = match init {
+                                ElementInit::Functions(functions) => functions.remaining_count(),
+                                ElementInit::Expressions(_, expressions) => {
+                                    expressions.remaining_count()
+                                }
+                            };
+
+                            cost.active_element_segments += 1;
+                            cost.active_element_entries += u64::from(count);
+                        }
+                        Ok(())
+                    },
+                )?;
+
+                if result.is_none() {
+                    break;
+                }
+            },
+            KnownSection::Data(mut datas) => loop {
+                let result = datas.parse(
+                    |mode| Ok(matches!(mode, DataMode::Active(_, _))),
+                    |active, data| {
+                        if active {
+                            cost.active_data_segments += 1;
+                            cost.active_data_bytes =
+                                cost.active_data_bytes.saturating_add(data.length());
+                        }
+                        Ok(())
+                    },
+                )?;
+
+                if result.is_none() {
+                    break;
+                }
+            },
+            _ => (),
+        }
+    }
+
+    Ok(cost)
+}
+
+impl core::fmt::Display for StartupCost {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        writeln!(
+            f,
+            "active data: {} bytes in {} segments",
+            self.active_data_bytes, self.active_data_segments
+        )?;
+        writeln!(
+            f,
+            "active elements: {} entries in {} segments",
+            self.active_element_entries, self.active_element_segments
+        )?;
+        writeln!(f, "global initializers: {}", self.global_initializers)?;
+        writeln!(
+            f,
+            "memory: {} pages ({} bytes)",
+            self.memory_pages, self.memory_bytes
+        )?;
+        writeln!(f, "table elements: {}", self.table_elements)?;
+        match self.start_function {
+            Some(start) => write!(f, "start function: {}", start.to_u32()),
+            None => f.write_str("start function: none"),
+        }
+    }
+}
@@ -0,0 +1,210 @@
+//! Functions for processing many WebAssembly modules in parallel.
+//!
+//! Each module is opened as a memory-mapped file with
+//! [`parse_module_sections_from_mmap_file`](crate::parse_module_sections_from_mmap_file), and
+//! processed on one of a fixed number of worker threads. An error in one module does not prevent
+//! the remaining modules from being processed.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use wasmiter::{batch, component::KnownSection, input::BorrowInput as _};
+//!
+//! let paths = batch::wasm_files_in("modules")?;
+//! let report = batch::process(paths, |sections| {
+//!     let mut function_count = 0;
+//!     for result in sections.borrow_input() {
+//!         if let Ok(known) = KnownSection::interpret(result?) {
+//!             if let KnownSection::Function(functions) = known? {
+//!                 function_count += functions.remaining_count();
+//!             }
+//!         }
+//!     }
+//!     Ok(function_count)
+//! });
+//!
+//! for (path, count) in report.successes() {
+//!     println!("{}: {count} functions", path.display());
+//! }
+//!
+//! for (path, error) in report.failures() {
+//!     eprintln!("{}: {error}", path.display());
+//! }
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use crate::{parser::Parsed, sections::SectionSequence};
+use memmap2::Mmap;
+use std::{
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// The outcome of processing a single module with [`process`].
+#[derive(Debug)]
+pub struct ModuleResult<T> {
+    path: PathBuf,
+    result: Parsed<T>,
+}
+
+impl<T> ModuleResult<T> {
+    /// Gets the path to the module.
+    #[inline]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Gets the value returned when processing the module, or the error that occured when opening
+    /// or processing it.
+    #[inline]
+    pub fn result(&self) -> &Parsed<T> {
+        &self.result
+    }
+
+    /// Returns the path to the module and the outcome of processing it.
+    #[inline]
+    pub fn into_parts(self) -> (PathBuf, Parsed<T>) {
+        (self.path, self.result)
+    }
+}
+
+/// Contains the outcome of processing every module given to [`process`], in the same order as the
+/// paths that were provided.
+#[derive(Debug)]
+pub struct BatchReport<T> {
+    results: Vec<ModuleResult<T>>,
+}
+
+impl<T> BatchReport<T> {
+    /// Gets the outcome of processing each module.
+    #[inline]
+    pub fn results(&self) -> &[ModuleResult<T>] {
+        &self.results
+    }
+
+    /// Returns the outcome of processing each module.
+    #[inline]
+    pub fn into_results(self) -> Vec<ModuleResult<T>> {
+        self.results
+    }
+
+    /// Returns an iterator over the modules that were successfully processed.
+    pub fn successes(&self) -> impl Iterator<Item = (&Path, &T)> {
+        self.results
+            .iter()
+            .filter_map(|module| Some((module.path(), module.result.as_ref().ok()?)))
+    }
+
+    /// Returns an iterator over the modules that could not be opened or processed.
+    pub fn failures(&self) -> impl Iterator<Item = (&Path, &crate::parser::Error)> {
+        self.results
+            .iter()
+            .filter_map(|module| Some((module.path(), module.result.as_ref().err()?)))
+    }
+
+    /// Gets the number of modules that were successfully processed.
+    pub fn success_count(&self) -> usize {
+        self.successes().count()
+    }
+
+    /// Gets the number of modules that could not be opened or processed.
+    pub fn failure_count(&self) -> usize {
+        self.failures().count()
+    }
+}
+
+/// Returns the paths to all files with the `.wasm` extension in the given directory, sorted by
+/// their names.
+///
+/// Subdirectories are not searched.
+///
+/// # Errors
+///
+/// Returns an error if the contents of the directory could not be read.
+pub fn wasm_files_in<P: AsRef<Path>>(directory: P) -> std::io::Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    for entry in std::fs::read_dir(directory)? {
+        let entry = entry?;
+        let path = entry.path();
+        if entry.file_type()?.is_file() && path.extension().map_or(false, |ext| ext == "wasm") {
+            paths.push(path);
+        }
+    }
+
+    paths.sort();
+    Ok(paths)
+}
+
+/// Opens and processes each module in parallel, using one thread per available CPU.
+///
+/// See [`process_with_jobs`] for more information.
+pub fn process<P, T, F>(paths: P, f: F) -> BatchReport<T>
+where
+    P: IntoIterator,
+    P::Item: Into<PathBuf>,
+    T: Send,
+    F: Fn(SectionSequence<Mmap>) -> Parsed<T> + Sync,
+{
+    let jobs = std::thread::available_parallelism().map_or(1, NonZeroUsize::get);
+    process_with_jobs(paths, jobs, f)
+}
+
+/// Opens and processes each module in parallel, using up to `jobs` threads. If `jobs` is `0`,
+/// a single thread is used.
+///
+/// Each module is opened as a memory-mapped file and passed to `f`. Modules are handed out to
+/// threads one at a time, so a few large modules do not prevent the other threads from making
+/// progress. Errors that occur when opening or processing a module are recorded in the returned
+/// [`BatchReport`], and do not affect the processing of other modules.
+///
+/// # Panics
+///
+/// If `f` panics, the panic is propagated once all threads have finished.
+pub fn process_with_jobs<P, T, F>(paths: P, jobs: usize, f: F) -> BatchReport<T>
+where
+    P: IntoIterator,
+    P::Item: Into<PathBuf>,
+    T: Send,
+    F: Fn(SectionSequence<Mmap>) -> Parsed<T> + Sync,
+{
+    let paths = paths.into_iter().map(Into::into).collect::<Vec<PathBuf>>();
+    let next = AtomicUsize::new(0);
+    let worker = || {
+        let mut processed = Vec::new();
+        loop {
+            let index = next.fetch_add(1, Ordering::Relaxed);
+            let path = match paths.get(index) {
+                Some(path) => path,
+                None => return processed,
+            };
+
+            let result = crate::parse_module_sections_from_mmap_file(path).and_then(&f);
+            processed.push((index, result));
+        }
+    };
+
+    let mut results = std::thread::scope(|scope| {
+        let workers = (0..jobs.clamp(1, paths.len().max(1)))
+            .map(|_| scope.spawn(worker))
+            .collect::<Vec<_>>();
+
+        workers
+            .into_iter()
+            .flat_map(|handle| match handle.join() {
+                Ok(processed) => processed,
+                Err(payload) => std::panic::resume_unwind(payload),
+            })
+            .collect::<Vec<_>>()
+    });
+
+    results.sort_unstable_by_key(|(index, _)| *index);
+
+    BatchReport {
+        results: paths
+            .into_iter()
+            .zip(results)
+            .map(|(path, (_, result))| ModuleResult { path, result })
+            .collect(),
+    }
+}
@@ -4,45 +4,82 @@
 //! [sections in the binary format](https://webassembly.github.io/spec/core/binary/modules.html#sections).
 
 mod code_section;
+#[cfg(feature = "alloc")]
+mod code_section_index;
+#[cfg(feature = "alloc")]
+mod const_expr;
 mod datas_component;
 mod elems_component;
 mod exports_component;
 mod funcs_component;
 mod function_section;
+#[cfg(feature = "alloc")]
+mod function_signatures;
 mod globals_component;
+#[cfg(feature = "alloc")]
+mod import_modules;
 mod imports_component;
 mod index_vector;
 mod known_section;
+#[cfg(feature = "alloc")]
+mod local_types;
 mod locals;
 mod mems_component;
 mod result_type;
+mod segment_limits;
+#[cfg(feature = "alloc")]
+mod signature_resolver;
 mod tables_component;
 mod tags_component;
 mod type_parser;
 mod types_component;
 
 pub use type_parser::{
-    block_type, func_type, global_mutability, global_type, limits, mem_type, ref_type, table_type,
-    val_type,
+    block_type, func_type, global_mutability, global_type, heap_type, limits, mem_type, ref_type,
+    table_type, val_type,
 };
 
-pub use code_section::{Code, CodeSection};
-pub use datas_component::{DataMode, DatasComponent};
+pub use code_section::{Code, CodeSection, IndexedCodeSection};
+pub use datas_component::{DataMode, DataSegment, DatasComponent};
 pub use elems_component::{ElementExpressions, ElementInit, ElementMode, ElemsComponent};
 pub use exports_component::{Export, ExportKind, ExportsComponent};
 pub use funcs_component::{Func, FuncsComponent};
 pub use function_section::FunctionSection;
 pub use globals_component::GlobalsComponent;
+#[cfg(feature = "alloc")]
+pub use import_modules::{ImportModule, ImportModules};
 pub use imports_component::{Import, ImportKind, ImportsComponent};
 pub use index_vector::IndexVector;
 pub use known_section::KnownSection;
 pub use locals::Locals;
+
 pub use mems_component::MemsComponent;
 pub use result_type::ResultType;
+pub use segment_limits::{DisplaySegments, SegmentLimits};
 pub use tables_component::TablesComponent;
 pub use tags_component::{parse as tag, Tag, TagsComponent};
 pub use types_component::TypesComponent;
 
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub use code_section_index::CodeSectionIndex;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub use const_expr::{eval_const_expr, ConstValue};
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub use function_signatures::FunctionSignatures;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub use local_types::LocalTypes;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub use signature_resolver::SignatureResolver;
+
 /// Parses a
 /// [WebAssembly index](https://webassembly.github.io/spec/core/binary/modules.html#indices).
 #[inline]
@@ -1,8 +1,10 @@
 use crate::{
     component,
+    index::FuncIdx,
     input::{BorrowInput, CloneInput, HasInput, Input, Window},
     instruction_set::InstructionSequence,
     parser::{self, ResultExt as _, Vector},
+    trace,
 };
 use core::fmt::{Debug, Formatter};
 
@@ -21,6 +23,7 @@ use core::fmt::{Debug, Formatter};
 pub struct Code<I: Input> {
     index: u32,
     content: Window<I>,
+    max_locals: u32,
 }
 
 impl<I: Input> Code<I> {
@@ -36,6 +39,14 @@ impl<I: Input> Code<I> {
         &self.content
     }
 
+    /// Gets the maximum number of local variables that this entry can declare.
+    ///
+    /// See [`CodeSection::with_max_locals()`] for more information.
+    #[inline]
+    pub fn max_locals(&self) -> u32 {
+        self.max_locals
+    }
+
     /// Reads the contents of this code entry.
     ///
     /// The first closure is given a [`Locals`](component::Locals) used to read the compressed
@@ -49,8 +60,15 @@ impl<I: Input> Code<I> {
         L: FnOnce(&mut component::Locals<&mut u64, &Window<I>>) -> Result<Y, E>,
         C: FnOnce(Y, &mut InstructionSequence<&mut u64, &Window<I>>) -> Result<Z, E>,
     {
+        let _span = trace::span!(
+            "read_code_entry",
+            index = self.index,
+            offset = self.content.base(),
+            length = self.content.length()
+        );
+
         let mut offset = self.content.base();
-        let mut locals = component::Locals::new(&mut offset, &self.content)?;
+        let mut locals = component::Locals::new(&mut offset, &self.content, self.max_locals)?;
         let code_arg = locals_f(&mut locals)?;
         locals.finish()?;
 
@@ -102,6 +120,7 @@ impl<'a, I: Input + 'a> BorrowInput<'a, I> for Code<I> {
         Code {
             index: self.index,
             content: self.content.borrow_input(),
+            max_locals: self.max_locals,
         }
     }
 }
@@ -114,6 +133,7 @@ impl<'a, I: Clone + Input + 'a> CloneInput<'a, I> for Code<&'a I> {
         Code {
             index: self.index,
             content: self.content.clone_input(),
+            max_locals: self.max_locals,
         }
     }
 }
@@ -147,12 +167,16 @@ impl<I: Input> Debug for Code<I> {
 #[derive(Clone, Copy)]
 pub struct CodeSection<I: Input> {
     entries: Vector<u64, I>,
+    max_locals: u32,
 }
 
 impl<I: Input> From<Vector<u64, I>> for CodeSection<I> {
     #[inline]
     fn from(entries: Vector<u64, I>) -> Self {
-        Self { entries }
+        Self {
+            entries,
+            max_locals: u32::MAX,
+        }
     }
 }
 
@@ -166,6 +190,42 @@ impl<I: Input> CodeSection<I> {
             .map(Self::from)
     }
 
+    /// Sets the maximum number of local variables that each entry in the *code section* can
+    /// declare, not including the function's parameters.
+    ///
+    /// By default, the only limit is that the total number of locals must fit in a [`u32`].
+    /// Runtimes typically impose a much smaller limit, such as `50000`, and setting one allows
+    /// functions declaring an excessive number of locals to be rejected before they are used.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wasmiter::component::KnownSection;
+    ///
+    /// let wasm = wat::parse_str("(module (func (local i32 i64 f32)))").unwrap();
+    /// let sections = wasmiter::parse_module_sections(wasm.as_slice())?;
+    /// for result in sections {
+    ///     if let Ok(Ok(KnownSection::Code(code))) = KnownSection::interpret(result?) {
+    ///         let mut code = code.with_max_locals(2);
+    ///         let entry = code.parse()?.unwrap();
+    ///         let result = entry.read(|_| Ok(()), |_, _| wasmiter::parser::Parsed::Ok(()));
+    ///         assert!(result.is_err());
+    ///     }
+    /// }
+    /// # Ok::<(), wasmiter::parser::Error>(())
+    /// ```
+    #[inline]
+    pub fn with_max_locals(mut self, limit: u32) -> Self {
+        self.max_locals = limit;
+        self
+    }
+
+    /// Gets the maximum number of local variables that each entry can declare.
+    #[inline]
+    pub fn max_locals(&self) -> u32 {
+        self.max_locals
+    }
+
     /// Gets the expected remaining number of entries in the *code section* that have yet to be
     /// parsed.
     #[inline]
@@ -173,21 +233,182 @@ impl<I: Input> CodeSection<I> {
         self.entries.remaining_count()
     }
 
+    /// Gets the number of entries in the *code section* that have been parsed so far.
+    #[inline]
+    pub fn consumed_count(&self) -> u32 {
+        self.entries.consumed_count()
+    }
+
+    /// Gets the number of entries that were declared in the *code section*.
+    #[inline]
+    pub fn declared_count(&self) -> u32 {
+        self.entries.declared_count()
+    }
+
     /// Parses the next entry in the *code section*.
     pub fn parse(&mut self) -> parser::Parsed<Option<Code<&I>>> {
+        let max_locals = self.max_locals;
         self.entries
             .advance_with_index(|index, offset, bytes| {
                 let size = parser::leb128::u64(offset, bytes).context("code entry size")?;
+                trace::event!(index, offset = *offset, size, "parsed code section entry");
                 let content = Window::with_offset_and_length(bytes, *offset, size);
 
                 crate::input::increment_offset(offset, size)
                     .context("unable to advance offset to read next code section entry")?;
 
-                parser::Parsed::Ok(Code { index, content })
+                parser::Parsed::Ok(Code {
+                    index,
+                    content,
+                    max_locals,
+                })
             })
             .transpose()
             .context("within code section")
     }
+
+    /// Gets the offset to the size of the next entry in the *code section*.
+    #[cfg(feature = "alloc")]
+    pub(super) fn next_entry_offset(&self) -> u64 {
+        self.entries.borrow_input().into_offset()
+    }
+
+    /// Parses the entry with the given `index`, whose size is encoded at the given `offset`,
+    /// without advancing the *code section*.
+    #[cfg(feature = "alloc")]
+    pub(super) fn parse_at(&self, index: u32, mut offset: u64) -> parser::Parsed<Code<&I>> {
+        let bytes = self.entries.input();
+        let size = parser::leb128::u64(&mut offset, bytes).context("code entry size")?;
+        Ok(Code {
+            index,
+            content: Window::with_offset_and_length(bytes, offset, size),
+            max_locals: self.max_locals,
+        })
+    }
+
+    /// Pairs each entry in the *code section* with the [`FuncIdx`] of its corresponding
+    /// function.
+    ///
+    /// Since imported functions come first in the function index space, the index of each
+    /// function is the number of imported functions plus the [index](Code::index) of its entry.
+    #[inline]
+    pub fn with_function_indices(self, imported_function_count: u32) -> IndexedCodeSection<I> {
+        IndexedCodeSection {
+            imported_function_count,
+            code: self,
+        }
+    }
+}
+
+/// Pairs each entry in a [`CodeSection`] with the [`FuncIdx`] of its corresponding function.
+///
+/// Returned by [`CodeSection::with_function_indices()`].
+#[derive(Clone, Copy)]
+pub struct IndexedCodeSection<I: Input> {
+    imported_function_count: u32,
+    code: CodeSection<I>,
+}
+
+impl<I: Input> IndexedCodeSection<I> {
+    /// Gets the number of imported functions, which is the index of the function corresponding
+    /// to the first *code section* entry.
+    #[inline]
+    pub fn imported_function_count(&self) -> u32 {
+        self.imported_function_count
+    }
+
+    /// Gets the expected remaining number of entries in the *code section* that have yet to be
+    /// parsed.
+    #[inline]
+    pub fn remaining_count(&self) -> u32 {
+        self.code.remaining_count()
+    }
+
+    /// Gets the number of entries in the *code section* that have been parsed so far.
+    #[inline]
+    pub fn consumed_count(&self) -> u32 {
+        self.code.consumed_count()
+    }
+
+    /// Gets the number of entries that were declared in the *code section*.
+    #[inline]
+    pub fn declared_count(&self) -> u32 {
+        self.code.declared_count()
+    }
+
+    /// Parses the next entry in the *code section*, along with the index of its function.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the entry could not be parsed, or if the function index is too large.
+    pub fn parse(&mut self) -> parser::Parsed<Option<(FuncIdx, Code<&I>)>> {
+        let imported_function_count = self.imported_function_count;
+        match self.code.parse()? {
+            Some(code) => {
+                let index = FuncIdx::try_from(
+                    u64::from(imported_function_count) + u64::from(code.index()),
+                )?;
+                Ok(Some((index, code)))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+impl<I: Input> HasInput<I> for IndexedCodeSection<I> {
+    #[inline]
+    fn input(&self) -> &I {
+        self.code.input()
+    }
+}
+
+impl<'a, I: Input + 'a> BorrowInput<'a, I> for IndexedCodeSection<I> {
+    type Borrowed = IndexedCodeSection<&'a I>;
+
+    #[inline]
+    fn borrow_input(&'a self) -> Self::Borrowed {
+        IndexedCodeSection {
+            imported_function_count: self.imported_function_count,
+            code: self.code.borrow_input(),
+        }
+    }
+}
+
+impl<'a, I: Clone + Input + 'a> CloneInput<'a, I> for IndexedCodeSection<&'a I> {
+    type Cloned = IndexedCodeSection<I>;
+
+    #[inline]
+    fn clone_input(&self) -> Self::Cloned {
+        IndexedCodeSection {
+            imported_function_count: self.imported_function_count,
+            code: self.code.clone_input(),
+        }
+    }
+}
+
+impl<I: Clone + Input> Iterator for IndexedCodeSection<I> {
+    type Item = parser::Parsed<(FuncIdx, Code<I>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.parse() {
+            Ok(None) => None,
+            Err(e) => Some(Err(e)),
+            Ok(Some((index, code))) => Some(Ok((index, code.clone_input()))),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.code.size_hint()
+    }
+}
+
+impl<I: Clone + Input> core::iter::FusedIterator for IndexedCodeSection<I> {}
+
+impl<I: Input> Debug for IndexedCodeSection<I> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.borrow_input()).finish()
+    }
 }
 
 impl<I: Input> HasInput<I> for CodeSection<I> {
@@ -202,7 +423,10 @@ impl<'a, I: Input + 'a> BorrowInput<'a, I> for CodeSection<I> {
 
     #[inline]
     fn borrow_input(&'a self) -> Self::Borrowed {
-        self.entries.borrow_input().into()
+        CodeSection {
+            entries: self.entries.borrow_input(),
+            max_locals: self.max_locals,
+        }
     }
 }
 
@@ -211,7 +435,10 @@ impl<'a, I: Clone + Input + 'a> CloneInput<'a, I> for CodeSection<&'a I> {
 
     #[inline]
     fn clone_input(&self) -> Self::Cloned {
-        self.entries.clone_input().into()
+        CodeSection {
+            entries: self.entries.clone_input(),
+            max_locals: self.max_locals,
+        }
     }
 }
 
@@ -234,6 +461,13 @@ impl<I: Clone + Input> Iterator for CodeSection<I> {
 
 impl<I: Clone + Input> core::iter::FusedIterator for CodeSection<I> {}
 
+impl<I: Clone + Input> parser::SpannedItems for CodeSection<I> {
+    #[inline]
+    fn next_item_offset(&self) -> u64 {
+        self.entries.next_offset()
+    }
+}
+
 impl<I: Input> Debug for CodeSection<I> {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         f.debug_list().entries(self.borrow_input()).finish()
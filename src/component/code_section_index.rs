@@ -0,0 +1,154 @@
+use crate::{
+    component::{Code, CodeSection},
+    index::FuncIdx,
+    input::{BorrowInput as _, HasInput, Input},
+    parser::{Parsed, ResultExt as _},
+};
+use alloc::vec::Vec;
+use core::fmt::{Debug, Formatter};
+
+/// Stores the offset of each entry in a [`CodeSection`], allowing the body of any function to be
+/// read without parsing the entries before it.
+///
+/// The table of offsets can be built by reading the *code section* once with
+/// [`CodeSectionIndex::new()`], or provided directly with [`CodeSectionIndex::from_offsets()`]
+/// when it was previously saved with [`CodeSectionIndex::offsets()`].
+///
+/// # Example
+///
+/// ```
+/// use wasmiter::{component::{CodeSectionIndex, KnownSection}, index::FuncIdx};
+///
+/// let wasm = wat::parse_str(r#"(module
+///     (import "env" "f" (func))
+///     (func i32.const 1 drop)
+///     (func (local i64) nop))"#).unwrap();
+///
+/// for result in wasmiter::parse_module_sections(wasm.as_slice())? {
+///     if let Ok(Ok(KnownSection::Code(code))) = KnownSection::interpret(result?) {
+///         let index = CodeSectionIndex::new(code, 1)?;
+///         assert_eq!(index.len(), 2);
+///         assert!(index.get(FuncIdx::from(0u32))?.is_none()); // imported
+///         assert_eq!(index.get(FuncIdx::from(2u32))?.unwrap().index(), 1);
+///     }
+/// }
+/// # Ok::<(), wasmiter::parser::Error>(())
+/// ```
+#[derive(Clone)]
+pub struct CodeSectionIndex<I: Input> {
+    code: CodeSection<I>,
+    imported_function_count: u32,
+    offsets: Vec<u64>,
+}
+
+impl<I: Input> CodeSectionIndex<I> {
+    /// Reads the remaining entries of the `code` section to build a table of their offsets.
+    ///
+    /// The `imported_function_count` is the index of the function corresponding to the first
+    /// remaining entry, see [`CodeSection::with_function_indices()`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the size of an entry could not be parsed.
+    pub fn new(code: CodeSection<I>, imported_function_count: u32) -> Parsed<Self> {
+        let mut offsets = Vec::new();
+        let mut entries = code.borrow_input();
+        loop {
+            let offset = entries.next_entry_offset();
+            match entries
+                .parse()
+                .context("while building code section index")?
+            {
+                Some(_) => offsets.push(offset),
+                None => break,
+            }
+        }
+
+        Ok(Self::from_offsets(code, imported_function_count, offsets))
+    }
+
+    /// Uses a precomputed table containing the offset to the size of each entry in the `code`
+    /// section.
+    ///
+    /// The `offsets` are not checked until an entry is read with [`CodeSectionIndex::get()`].
+    #[inline]
+    pub fn from_offsets(
+        code: CodeSection<I>,
+        imported_function_count: u32,
+        offsets: Vec<u64>,
+    ) -> Self {
+        Self {
+            code,
+            imported_function_count,
+            offsets,
+        }
+    }
+
+    /// Gets the offset to the size of each entry in the *code section*.
+    #[inline]
+    pub fn offsets(&self) -> &[u64] {
+        &self.offsets
+    }
+
+    /// Gets the number of imported functions, which is the index of the function corresponding
+    /// to the first entry.
+    #[inline]
+    pub fn imported_function_count(&self) -> u32 {
+        self.imported_function_count
+    }
+
+    /// Gets the number of entries in the *code section*.
+    #[inline]
+    pub fn len(&self) -> u32 {
+        u32::try_from(self.offsets.len()).unwrap_or(u32::MAX)
+    }
+
+    /// Returns `true` if the *code section* has no entries.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+
+    /// Reads the *code section* entry for the function with the given `index`.
+    ///
+    /// Returns `Ok(None)` if the function is imported, or if there is no corresponding entry.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the size of the entry could not be parsed.
+    pub fn get(&self, index: FuncIdx) -> Parsed<Option<Code<&I>>> {
+        let entry = match index.to_u32().checked_sub(self.imported_function_count) {
+            Some(entry) => entry,
+            None => return Ok(None),
+        };
+
+        match self.offsets.get(crate::int::u32_to_usize(entry)) {
+            Some(offset) => self.code.parse_at(entry, *offset).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+impl<I: Input> HasInput<I> for CodeSectionIndex<I> {
+    #[inline]
+    fn input(&self) -> &I {
+        self.code.input()
+    }
+}
+
+impl<I: Input> Debug for CodeSectionIndex<I> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("CodeSectionIndex")
+            .field("imported_function_count", &self.imported_function_count)
+            .field("offsets", &self.offsets)
+            .finish()
+    }
+}
+
+#[cfg(feature = "mem-usage")]
+impl<I: Input> crate::mem_usage::MemUsage for CodeSectionIndex<I> {
+    #[inline]
+    fn mem_usage(&self) -> usize {
+        crate::mem_usage::vec_capacity(&self.offsets)
+    }
+}
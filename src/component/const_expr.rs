@@ -0,0 +1,239 @@
+use crate::{
+    index::{FuncIdx, GlobalIdx},
+    input::Input,
+    instruction_set::{Instruction, InstructionSequence},
+    parser::{Context, Error, ErrorKind, Offset, Parsed},
+    types::RefType,
+};
+use alloc::vec::Vec;
+
+/// The value produced by a
+/// [constant expression](https://webassembly.github.io/spec/core/valid/instructions.html#constant-expressions).
+///
+/// Returned by [`eval_const_expr`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum ConstValue {
+    /// A 32-bit integer.
+    I32(i32),
+    /// A 64-bit integer.
+    I64(i64),
+    /// A 32-bit floating-point value.
+    F32(f32),
+    /// A 64-bit floating-point value.
+    F64(f64),
+    /// A 128-bit vector.
+    V128(u128),
+    /// A null reference of the given type.
+    RefNull(RefType),
+    /// A reference to a function.
+    RefFunc(FuncIdx),
+}
+
+impl ConstValue {
+    fn type_name(&self) -> &'static str {
+        match self {
+            Self::I32(_) => "i32",
+            Self::I64(_) => "i64",
+            Self::F32(_) => "f32",
+            Self::F64(_) => "f64",
+            Self::V128(_) => "v128",
+            Self::RefNull(_) | Self::RefFunc(_) => "reference",
+        }
+    }
+}
+
+#[inline(never)]
+#[cold]
+fn not_constant(name: &'static str, offset: u64) -> Error {
+    Error::new(ErrorKind::InvalidFormat)
+        .with_context(Context::from_closure(move |f| {
+            write!(f, "{name} is not allowed in a constant expression")
+        }))
+        .with_location_context("constant expression", offset)
+}
+
+#[inline(never)]
+#[cold]
+fn unknown_global(global: GlobalIdx, offset: u64) -> Error {
+    Error::new(ErrorKind::InvalidFormat)
+        .with_context(Context::from_closure(move |f| {
+            write!(
+                f,
+                "global {} cannot be read in a constant expression",
+                global.to_u32()
+            )
+        }))
+        .with_location_context("constant expression", offset)
+}
+
+#[inline(never)]
+#[cold]
+fn operand_mismatch(name: &'static str, operand: Option<ConstValue>, offset: u64) -> Error {
+    let operand = operand.map(|value| value.type_name());
+    Error::new(ErrorKind::InvalidFormat)
+        .with_context(Context::from_closure(move |f| match operand {
+            Some(operand) => write!(
+                f,
+                "{name} cannot be applied to an operand of type {operand}"
+            ),
+            None => write!(f, "{name} expected an operand, but the stack was empty"),
+        }))
+        .with_location_context("constant expression", offset)
+}
+
+#[inline(never)]
+#[cold]
+fn bad_result_count(count: usize, offset: u64) -> Error {
+    Error::new(ErrorKind::InvalidFormat)
+        .with_context(Context::from_closure(move |f| {
+            write!(f, "expected 1 result, but {count} values were produced")
+        }))
+        .with_location_context("constant expression", offset)
+}
+
+fn binary<T>(
+    stack: &mut Vec<ConstValue>,
+    name: &'static str,
+    offset: u64,
+    mut unwrap: impl FnMut(ConstValue) -> Option<T>,
+    op: impl FnOnce(T, T) -> ConstValue,
+) -> Parsed<ConstValue> {
+    let mut operand = || {
+        let value = stack.pop();
+        value
+            .and_then(&mut unwrap)
+            .ok_or_else(|| operand_mismatch(name, value, offset))
+    };
+
+    let c2 = operand()?;
+    let c1 = operand()?;
+    Ok(op(c1, c2))
+}
+
+/// Evaluates a
+/// [constant expression](https://webassembly.github.io/spec/core/valid/instructions.html#constant-expressions),
+/// such as a global initializer or the offset of an active element or data segment.
+///
+/// The `*.const`, **ref.null**, **ref.func**, and **global.get** instructions are always allowed.
+/// The value of each global read by **global.get** is obtained by calling `global`, which should
+/// return `None` if the global cannot be used, such as if it is not an immutable imported global.
+///
+/// If `extended` is `true`, the **i32.add**, **i32.sub**, **i32.mul**, **i64.add**, **i64.sub**,
+/// and **i64.mul** instructions introduced in the
+/// [extended constant expressions proposal](https://github.com/WebAssembly/extended-const)
+/// are also allowed.
+///
+/// # Errors
+///
+/// Returns an error if an instruction could not be parsed, if an instruction is not allowed in a
+/// constant expression, if `global` returns `None`, or if the expression does not produce
+/// exactly one value.
+///
+/// # Example
+///
+/// ```
+/// use wasmiter::{
+///     component::{self, ConstValue, KnownSection},
+///     index::GlobalIdx,
+/// };
+///
+/// let wasm = wat::parse_str(r#"(module
+///     (import "env" "base" (global i32))
+///     (global i32 (i32.add (global.get 0) (i32.const 16))))"#).unwrap();
+///
+/// let sections = wasmiter::parse_module_sections(wasm.as_slice())?;
+/// let mut globals = sections
+///     .into_iter()
+///     .find_map(|section| match KnownSection::interpret(section.ok()?) {
+///         Ok(Ok(KnownSection::Global(globals))) => Some(globals),
+///         _ => None,
+///     })
+///     .unwrap();
+///
+/// let imported = |global: GlobalIdx| (global.to_u32() == 0).then_some(ConstValue::I32(1024));
+/// let value = globals.parse(|_, initializer| {
+///     component::eval_const_expr(initializer, true, imported)
+/// })?;
+/// assert_eq!(value, Some(ConstValue::I32(1040)));
+/// # Ok::<(), wasmiter::parser::Error>(())
+/// ```
+pub fn eval_const_expr<O, I, G>(
+    expression: &mut InstructionSequence<O, I>,
+    extended: bool,
+    mut global: G,
+) -> Parsed<ConstValue>
+where
+    O: Offset,
+    I: Input,
+    G: FnMut(GlobalIdx) -> Option<ConstValue>,
+{
+    let mut stack = Vec::<ConstValue>::with_capacity(1);
+
+    loop {
+        let offset = expression.offset();
+        let result = expression.next(|instruction| {
+            let name = instruction.name();
+            let value = match instruction {
+                Instruction::End => return Ok(()),
+                Instruction::I32Const(value) => ConstValue::I32(*value),
+                Instruction::I64Const(value) => ConstValue::I64(*value),
+                Instruction::F32Const(value) => ConstValue::F32(*value),
+                Instruction::F64Const(value) => ConstValue::F64(*value),
+                #[cfg(feature = "simd")]
+                Instruction::V128Const(value) => ConstValue::V128(*value),
+                Instruction::RefNull(ref_type) => ConstValue::RefNull(*ref_type),
+                Instruction::RefFunc(function) => ConstValue::RefFunc(*function),
+                Instruction::GlobalGet(index) => {
+                    global(*index).ok_or_else(|| unknown_global(*index, offset))?
+                }
+                Instruction::I32Add | Instruction::I32Sub | Instruction::I32Mul if extended => {
+                    let op = match instruction {
+                        Instruction::I32Add => i32::wrapping_add,
+                        Instruction::I32Sub => i32::wrapping_sub,
+                        _ => i32::wrapping_mul,
+                    };
+
+                    let unwrap = |value| match value {
+                        ConstValue::I32(value) => Some(value),
+                        _ => None,
+                    };
+
+                    binary(&mut stack, name, offset, unwrap, |c1, c2| {
+                        ConstValue::I32(op(c1, c2))
+                    })?
+                }
+                Instruction::I64Add | Instruction::I64Sub | Instruction::I64Mul if extended => {
+                    let op = match instruction {
+                        Instruction::I64Add => i64::wrapping_add,
+                        Instruction::I64Sub => i64::wrapping_sub,
+                        _ => i64::wrapping_mul,
+                    };
+
+                    let unwrap = |value| match value {
+                        ConstValue::I64(value) => Some(value),
+                        _ => None,
+                    };
+
+                    binary(&mut stack, name, offset, unwrap, |c1, c2| {
+                        ConstValue::I64(op(c1, c2))
+                    })?
+                }
+                _ => return Err(not_constant(name, offset)),
+            };
+
+            stack.push(value);
+            Parsed::Ok(())
+        });
+
+        match result {
+            Some(result) => result?,
+            None => break,
+        }
+    }
+
+    match stack.as_slice() {
+        [value] => Ok(*value),
+        _ => Err(bad_result_count(stack.len(), expression.offset())),
+    }
+}
@@ -1,5 +1,6 @@
 use crate::{
-    index::MemIdx,
+    component::{DisplaySegments, SegmentLimits},
+    index::{DataIdx, MemIdx},
     input::{BorrowInput, CloneInput, HasInput, Input, Window},
     instruction_set::InstructionSequence,
     parser::{self, Offset, Parsed, ResultExt as _, Vector},
@@ -45,6 +46,47 @@ impl<O: Offset, I: Input> Debug for DataMode<O, I> {
     }
 }
 
+/// A [data segment](https://webassembly.github.io/spec/core/syntax/modules.html#data-segments)
+/// whose contents have not been read, returned by [`DatasComponent::parse_segment()`] and
+/// [`DatasComponent::find_segment()`].
+///
+/// The [`Window`] into the segment's contents allows large segments to be read in pieces or
+/// copied directly from the underlying [`Input`], such as a memory-mapped file.
+#[derive(Clone, Copy)]
+pub struct DataSegment<I: Input> {
+    mode: DataMode<u64, I>,
+    data: Window<I>,
+}
+
+impl<I: Input> DataSegment<I> {
+    /// Gets the mode of the data segment.
+    #[inline]
+    pub fn mode(&self) -> &DataMode<u64, I> {
+        &self.mode
+    }
+
+    /// Gets a [`Window`] into the contents of the data segment.
+    #[inline]
+    pub fn data(&self) -> &Window<I> {
+        &self.data
+    }
+
+    /// Consumes the data segment, returning its mode and a [`Window`] into its contents.
+    #[inline]
+    pub fn into_parts(self) -> (DataMode<u64, I>, Window<I>) {
+        (self.mode, self.data)
+    }
+}
+
+impl<I: Input> Debug for DataSegment<I> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("DataSegment")
+            .field("mode", &self.mode)
+            .field("data", &self.data)
+            .finish()
+    }
+}
+
 /// Represents the
 /// [**datas** component](https://webassembly.github.io/spec/core/syntax/modules.html#data-segments)
 /// of a WebAssembly module, stored in and parsed from the
@@ -71,10 +113,10 @@ impl<I: Input> DatasComponent<I> {
     }
 
     /// Parses the next data segment in the section.
-    pub fn parse<Y, Z, M, D>(&mut self, mode_f: M, data_f: D) -> Parsed<Option<Z>>
+    pub fn parse<'a, Y, Z, M, D>(&'a mut self, mode_f: M, data_f: D) -> Parsed<Option<Z>>
     where
-        M: FnOnce(&mut DataMode<&mut u64, &I>) -> Parsed<Y>,
-        D: FnOnce(Y, Window<&I>) -> Parsed<Z>,
+        M: FnOnce(&mut DataMode<&mut u64, &'a I>) -> Parsed<Y>,
+        D: FnOnce(Y, Window<&'a I>) -> Parsed<Z>,
     {
         self.entries.advance(|offset, input| {
             let mode_offset = *offset;
@@ -121,11 +163,116 @@ impl<I: Input> DatasComponent<I> {
         }).transpose().context("within data section")
     }
 
+    /// Parses the next data segment in the section, without reading its contents.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wasmiter::component::{DataMode, KnownSection};
+    /// use wasmiter::input::Input as _;
+    ///
+    /// let wasm = wat::parse_str(r#"(module
+    ///     (memory 1)
+    ///     (data "passive")
+    ///     (data (i32.const 16) "active"))"#).unwrap();
+    ///
+    /// let sections = wasmiter::parse_module_sections(wasm.as_slice())?;
+    /// let mut datas = sections
+    ///     .into_iter()
+    ///     .find_map(|section| match KnownSection::interpret(section.ok()?) {
+    ///         Ok(Ok(KnownSection::Data(datas))) => Some(datas),
+    ///         _ => None,
+    ///     })
+    ///     .unwrap();
+    ///
+    /// let passive = datas.parse_segment()?.unwrap();
+    /// assert!(matches!(passive.mode(), DataMode::Passive));
+    /// assert_eq!(passive.data().length(), 7);
+    ///
+    /// let active = datas.parse_segment()?.unwrap();
+    /// let data = active.data();
+    /// let mut contents = [0u8; 6];
+    /// data.read_exact_at(data.base(), &mut contents)?;
+    /// assert_eq!(&contents, b"active");
+    /// assert!(datas.parse_segment()?.is_none());
+    /// # Ok::<(), wasmiter::parser::Error>(())
+    /// ```
+    pub fn parse_segment(&mut self) -> Parsed<Option<DataSegment<&I>>> {
+        self.parse(
+            |mode| {
+                Ok(match mode {
+                    DataMode::Passive => DataMode::Passive,
+                    DataMode::Active(memory, offset) => DataMode::Active(
+                        *memory,
+                        InstructionSequence::new(offset.offset(), *offset.input()),
+                    ),
+                })
+            },
+            |mode, data| Ok(DataSegment { mode, data }),
+        )
+    }
+
+    /// Finds the data segment with the given `index`, skipping over the contents of all of the
+    /// preceding segments.
+    ///
+    /// The search starts from the first segment that has not yet been parsed, so this should
+    /// usually be called before any segments are parsed. Returns `Ok(None)` if there is no
+    /// data segment with the given `index`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a data segment could not be parsed.
+    pub fn find_segment(&self, index: DataIdx) -> Parsed<Option<DataSegment<&I>>> {
+        let mut datas = self.borrow_input();
+        for _ in 0..index.to_u32() {
+            if datas.parse(|_| Ok(()), |(), _| Ok(()))?.is_none() {
+                return Ok(None);
+            }
+        }
+
+        let segment = match datas.parse_segment()? {
+            Some(segment) => segment,
+            None => return Ok(None),
+        };
+
+        let (mode, data) = segment.into_parts();
+        Ok(Some(DataSegment {
+            mode: match mode {
+                DataMode::Passive => DataMode::Passive,
+                DataMode::Active(memory, offset) => DataMode::Active(
+                    memory,
+                    InstructionSequence::new(offset.offset(), **offset.input()),
+                ),
+            },
+            data: Window::with_offset_and_length(*data.into_inner(), data.base(), data.length()),
+        }))
+    }
+
     /// Gets the expected remaining number of entires in the *data section* that have yet to be parsed.
     #[inline]
     pub fn remaining_count(&self) -> u32 {
         self.entries.remaining_count()
     }
+
+    /// Gets the number of entries in the *data section* that have been parsed so far.
+    #[inline]
+    pub fn consumed_count(&self) -> u32 {
+        self.entries.consumed_count()
+    }
+
+    /// Gets the number of entries that were declared in the *data section*.
+    #[inline]
+    pub fn declared_count(&self) -> u32 {
+        self.entries.declared_count()
+    }
+
+    /// Returns a [`Display`](core::fmt::Display) implementation that writes the data segments in
+    /// the [WebAssembly text format](https://webassembly.github.io/spec/core/text/index.html),
+    /// omitting any segments or bytes past the given `limits`.
+    #[inline]
+    pub fn display_with_limits(&self, limits: SegmentLimits) -> DisplaySegments<'_, Self> {
+        DisplaySegments::new(self, limits)
+    }
 }
 
 impl<I: Input> HasInput<I> for DatasComponent<I> {
@@ -155,24 +302,29 @@ impl<'a, I: Clone + Input + 'a> CloneInput<'a, I> for DatasComponent<&'a I> {
 
 impl<I: Input> Debug for DatasComponent<I> {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        // Each segment is formatted as it is parsed, and the limits ensure that printing a large
+        // section does not take an unbounded amount of time
+        let limits = SegmentLimits::default();
         let mut datas = self.borrow_input();
 
-        struct DataSegment<'a, I: Input> {
-            mode: DataMode<u64, I>,
+        struct DataSegment<'a, I: Input, J: Input> {
+            mode: DataMode<u64, J>,
+            length: u64,
             data: Window<&'a I>,
         }
 
-        impl<I: Input> Debug for DataSegment<'_, I> {
+        impl<I: Input, J: Input> Debug for DataSegment<'_, I, J> {
             fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
                 f.debug_struct("DataSegment")
                     .field("mode", &self.mode)
+                    .field("length", &self.length)
                     .field("data", &self.data)
                     .finish()
             }
         }
 
         let mut list = f.debug_list();
-        loop {
+        for _ in 0..limits.max_segments() {
             let result = datas.parse(
                 |mode| {
                     Ok(match mode {
@@ -183,7 +335,11 @@ impl<I: Input> Debug for DatasComponent<I> {
                     })
                 },
                 |mode, data| {
-                    list.entry(&Parsed::Ok(DataSegment { mode, data }));
+                    let length = data.length();
+                    let shown = core::cmp::min(length, limits.max_data_bytes());
+                    let data =
+                        Window::with_offset_and_length(data.into_inner(), data.base(), shown);
+                    list.entry(&Parsed::Ok(DataSegment { mode, length, data }));
                     Ok(())
                 },
             );
@@ -197,6 +353,11 @@ impl<I: Input> Debug for DatasComponent<I> {
                 }
             }
         }
+
+        if datas.remaining_count() > 0 {
+            list.entry(&format_args!("... {} more", datas.remaining_count()));
+        }
+
         list.finish()
     }
 }
@@ -1,11 +1,14 @@
 use crate::{
-    component::{self, IndexVector},
+    component::{self, DisplaySegments, IndexVector, SegmentLimits},
     index::{self, TableIdx},
     input::{BorrowInput, CloneInput, HasInput, Input},
     instruction_set::InstructionSequence,
     parser::{self, Offset, Parsed, ResultExt as _, Vector},
 };
-use core::fmt::{Debug, Formatter};
+use core::{
+    cell::RefCell,
+    fmt::{Debug, Formatter},
+};
 
 /// Represents a vector of expressions that evaluate to references in an
 /// [element segment](https://webassembly.github.io/spec/core/syntax/modules.html#element-segments).
@@ -27,6 +30,24 @@ impl<O: Offset, I: Input> ElementExpressions<O, I> {
             .map(Self::from)
     }
 
+    /// Gets the expected remaining number of expressions that have yet to be parsed.
+    #[inline]
+    pub fn remaining_count(&self) -> u32 {
+        self.expressions.remaining_count()
+    }
+
+    /// Gets the number of expressions that have been parsed so far.
+    #[inline]
+    pub fn consumed_count(&self) -> u32 {
+        self.expressions.consumed_count()
+    }
+
+    /// Gets the number of expressions that were declared.
+    #[inline]
+    pub fn declared_count(&self) -> u32 {
+        self.expressions.declared_count()
+    }
+
     /// Parses the next expression.
     pub fn next<T, F>(&mut self, f: F) -> Parsed<Option<T>>
     where
@@ -393,6 +414,47 @@ impl<I: Input> ElemsComponent<I> {
     pub fn remaining_count(&self) -> u32 {
         self.elements.remaining_count()
     }
+
+    /// Gets the number of entries in the *element section* that have been parsed so far.
+    #[inline]
+    pub fn consumed_count(&self) -> u32 {
+        self.elements.consumed_count()
+    }
+
+    /// Gets the number of entries that were declared in the *element section*.
+    #[inline]
+    pub fn declared_count(&self) -> u32 {
+        self.elements.declared_count()
+    }
+
+    /// Returns a [`Display`](core::fmt::Display) implementation that writes the element segments
+    /// in the [WebAssembly text format](https://webassembly.github.io/spec/core/text/index.html),
+    /// omitting any segments or elements past the given `limits`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wasmiter::component::{KnownSection, SegmentLimits};
+    ///
+    /// let wasm = wat::parse_str(r#"(module
+    ///     (table 4 funcref)
+    ///     (func $f)
+    ///     (elem (i32.const 0) $f $f $f $f))"#).unwrap();
+    ///
+    /// let sections = wasmiter::parse_module_sections(wasm.as_slice())?;
+    /// for result in sections {
+    ///     if let Ok(Ok(KnownSection::Element(elems))) = KnownSection::interpret(result?) {
+    ///         let limits = SegmentLimits::new().with_max_elements(2);
+    ///         let text = elems.display_with_limits(limits).to_string();
+    ///         assert!(text.ends_with("func 0 0 (; 2 more ;))\n"));
+    ///     }
+    /// }
+    /// # Ok::<(), wasmiter::parser::Error>(())
+    /// ```
+    #[inline]
+    pub fn display_with_limits(&self, limits: SegmentLimits) -> DisplaySegments<'_, Self> {
+        DisplaySegments::new(self, limits)
+    }
 }
 
 impl<I: Input> HasInput<I> for ElemsComponent<I> {
@@ -422,15 +484,87 @@ impl<'a, I: Clone + Input + 'a> CloneInput<'a, I> for ElemsComponent<&'a I> {
 
 impl<I: Input> Debug for ElemsComponent<I> {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        // Each segment is formatted as it is parsed, and the limits ensure that printing a large
+        // section does not take an unbounded amount of time
+        let limits = SegmentLimits::default();
         let mut elems = self.borrow_input();
         let mut list = f.debug_list();
 
-        struct Elem<'a, I: Input> {
-            mode: ElementMode<u64, &'a I>,
-            elements: ElementInit<u64, &'a I>,
+        struct Elements<'b, O: Offset, I: Input> {
+            init: RefCell<&'b mut ElementInit<O, I>>,
+            max_elements: u32,
+        }
+
+        /// Formats the elements as a list, skipping those past the limit.
+        struct ElementList<'c, 'b, O: Offset, I: Input>(&'c Elements<'b, O, I>);
+
+        impl<O: Offset, I: Input> Debug for ElementList<'_, '_, O, I> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+                let max_elements = self.0.max_elements;
+                let mut init = self.0.init.borrow_mut();
+                let mut list = f.debug_list();
+                let remaining = match &mut **init {
+                    ElementInit::Functions(functions) => {
+                        while functions.consumed_count() < max_elements {
+                            match functions.next() {
+                                Some(Ok(index)) => list.entry(&index),
+                                Some(Err(e)) => return list.entry(&Parsed::<()>::Err(e)).finish(),
+                                None => break,
+                            };
+                        }
+                        functions.remaining_count()
+                    }
+                    ElementInit::Expressions(_, expressions) => {
+                        while expressions.consumed_count() < max_elements {
+                            let result = expressions.next(|expression| {
+                                list.entry(expression);
+                                Ok(())
+                            });
+
+                            match result {
+                                Ok(Some(())) => (),
+                                Ok(None) => break,
+                                Err(e) => return list.entry(&Parsed::<()>::Err(e)).finish(),
+                            }
+                        }
+                        expressions.remaining_count()
+                    }
+                };
+
+                if remaining > 0 {
+                    list.entry(&format_args!("... {remaining} more"));
+                }
+                list.finish()
+            }
+        }
+
+        impl<O: Offset, I: Input> Debug for Elements<'_, O, I> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+                let ref_type = match &**self.init.borrow() {
+                    ElementInit::Functions(_) => None,
+                    ElementInit::Expressions(ref_type, _) => Some(*ref_type),
+                };
+
+                match ref_type {
+                    None => f
+                        .debug_tuple("Functions")
+                        .field(&ElementList(self))
+                        .finish(),
+                    Some(ref_type) => f
+                        .debug_struct("Expressions")
+                        .field("type", &ref_type)
+                        .field("values", &ElementList(self))
+                        .finish(),
+                }
+            }
+        }
+
+        struct Elem<'a, 'b, O: Offset, I: Input, J: Input> {
+            mode: ElementMode<u64, &'a J>,
+            elements: Elements<'b, O, I>,
         }
 
-        impl<I: Input> Debug for Elem<'_, I> {
+        impl<O: Offset, I: Input, J: Input> Debug for Elem<'_, '_, O, I, J> {
             fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
                 f.debug_struct("ElementSegment")
                     .field("mode", &self.mode)
@@ -439,7 +573,7 @@ impl<I: Input> Debug for ElemsComponent<I> {
             }
         }
 
-        while elems.remaining_count() > 0 {
+        for _ in 0..limits.max_segments() {
             let result = elems.parse(
                 |mode| {
                     Ok(match mode {
@@ -450,20 +584,33 @@ impl<I: Input> Debug for ElemsComponent<I> {
                         }
                     })
                 },
-                |mode, elements| {
+                |mode, init| {
                     list.entry(&Elem {
                         mode,
-                        elements: elements.clone_input(),
+                        elements: Elements {
+                            init: RefCell::new(init),
+                            max_elements: limits.max_elements(),
+                        },
                     });
 
                     Parsed::Ok(())
                 },
             );
 
-            if let Err(e) = result {
-                list.entry(&Parsed::<()>::Err(e));
+            match result {
+                Ok(Some(())) => (),
+                Ok(None) => break,
+                Err(e) => {
+                    list.entry(&Parsed::<()>::Err(e));
+                    break;
+                }
             }
         }
+
+        if elems.remaining_count() > 0 {
+            list.entry(&format_args!("... {} more", elems.remaining_count()));
+        }
+
         list.finish()
     }
 }
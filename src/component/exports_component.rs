@@ -6,7 +6,7 @@ use crate::{
 use core::fmt::{Debug, Formatter};
 
 /// Describes what kind of entity is specified by an [`Export`].
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 #[allow(missing_docs)]
 #[non_exhaustive]
 pub enum ExportKind {
@@ -158,6 +158,18 @@ impl<I: Input> ExportsComponent<I> {
     pub fn remaining_count(&self) -> u32 {
         self.exports.remaining_count()
     }
+
+    /// Gets the number of entries in the *export section* that have been parsed so far.
+    #[inline]
+    pub fn consumed_count(&self) -> u32 {
+        self.exports.consumed_count()
+    }
+
+    /// Gets the number of entries that were declared in the *export section*.
+    #[inline]
+    pub fn declared_count(&self) -> u32 {
+        self.exports.declared_count()
+    }
 }
 
 impl<I: Input> HasInput<I> for ExportsComponent<I> {
@@ -204,6 +216,13 @@ impl<I: Clone + Input> Iterator for ExportsComponent<I> {
 
 impl<I: Clone + Input> core::iter::FusedIterator for ExportsComponent<I> {}
 
+impl<I: Clone + Input> parser::SpannedItems for ExportsComponent<I> {
+    #[inline]
+    fn next_item_offset(&self) -> u64 {
+        self.exports.next_offset()
+    }
+}
+
 impl<I: Input> core::fmt::Debug for ExportsComponent<I> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_list().entries(self.borrow_input()).finish()
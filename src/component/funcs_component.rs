@@ -15,6 +15,18 @@ pub struct Func<C: Input> {
 }
 
 impl<C: Input> Func<C> {
+    /// Creates a [`Func`] from its type and *code section* entry.
+    ///
+    /// This allows functions to be constructed when the *function* and *code* sections are not
+    /// read together, such as with a [`FunctionSignatures`](crate::component::FunctionSignatures).
+    #[inline]
+    pub fn new(signature: TypeIdx, code: Code<C>) -> Self {
+        Self {
+            r#type: signature,
+            code,
+        }
+    }
+
     /// Gets an index into the *type section* that specifies the type of this function.
     #[inline]
     pub fn signature(&self) -> TypeIdx {
@@ -112,15 +124,31 @@ impl<T: Input, C: Input> FuncsComponent<T, C> {
         }
     }
 
+    /// Gets the expected remaining number of functions that have yet to be parsed.
+    #[inline]
+    pub fn remaining_count(&self) -> u32 {
+        self.types.remaining_count()
+    }
+
     /// Parses the *function* and *code* sections to read the next function.
     pub fn parse(&mut self) -> Parsed<Option<Func<&C>>> {
-        // Constructor ensures both sections have the same count
+        #[cold]
+        #[inline(never)]
+        fn missing_code_entry() -> parser::Error {
+            parser::Error::new(parser::ErrorKind::InvalidFormat)
+                .with_context("missing code section entry for function".into())
+        }
+
         match self.types.next() {
             None => Ok(None),
             Some(Err(e)) => Err(e),
             Some(Ok(r#type)) => {
-                let code = self.code.parse()?.unwrap();
-                Ok(Some(Func { r#type, code }))
+                // Constructor ensures both sections have the same count, but an error in the
+                // code section causes its remaining entries to be skipped
+                match self.code.parse()? {
+                    Some(code) => Ok(Some(Func { r#type, code })),
+                    None => Err(missing_code_entry()),
+                }
             }
         }
     }
@@ -40,6 +40,18 @@ impl<I: Input> FunctionSection<I> {
     pub fn remaining_count(&self) -> u32 {
         self.indices.remaining_count()
     }
+
+    /// Gets the number of entries in the *function section* that have been parsed so far.
+    #[inline]
+    pub fn consumed_count(&self) -> u32 {
+        self.indices.consumed_count()
+    }
+
+    /// Gets the number of entries that were declared in the *function section*.
+    #[inline]
+    pub fn declared_count(&self) -> u32 {
+        self.indices.declared_count()
+    }
 }
 
 impl<I: Input> HasInput<I> for FunctionSection<I> {
@@ -85,6 +97,13 @@ impl<I: Input> Iterator for FunctionSection<I> {
 
 impl<I: Clone + Input> core::iter::FusedIterator for FunctionSection<I> {}
 
+impl<I: Input> crate::parser::SpannedItems for FunctionSection<I> {
+    #[inline]
+    fn next_item_offset(&self) -> u64 {
+        self.indices.next_item_offset()
+    }
+}
+
 impl<I: Input> core::fmt::Debug for FunctionSection<I> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         core::fmt::Debug::fmt(&self.indices, f)
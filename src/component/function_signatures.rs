@@ -0,0 +1,87 @@
+use crate::{
+    component::{Code, Func, FunctionSection},
+    index::TypeIdx,
+    input::Input,
+    parser::{Context, Error, ErrorKind, Parsed, ResultExt as _},
+};
+use alloc::vec::Vec;
+
+/// Stores the type of each function defined in the
+/// [*function section*](https://webassembly.github.io/spec/core/binary/modules.html#function-section),
+/// allowing entries in the *code section* to be associated with their types in any order.
+///
+/// Unlike the [`FuncsComponent`](crate::component::FuncsComponent), which reads the *function*
+/// and *code* sections in lockstep, this allows a [`CodeSection`](crate::component::CodeSection)
+/// to be read before the *function section* is available, with each [`Code`] entry bound to its
+/// type later with [`FunctionSignatures::bind()`].
+#[derive(Clone, Debug, Default)]
+pub struct FunctionSignatures {
+    types: Vec<TypeIdx>,
+}
+
+impl FunctionSignatures {
+    /// Reads the remaining entries of the given *function section*.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an entry in the *function section* could not be parsed.
+    pub fn new<I: Input>(section: FunctionSection<I>) -> Parsed<Self> {
+        let mut types = Vec::new();
+        for result in section {
+            types.push(result.context("function signature")?);
+        }
+
+        Ok(Self { types })
+    }
+
+    /// Gets the number of functions defined in the *function section*.
+    #[inline]
+    pub fn len(&self) -> u32 {
+        u32::try_from(self.types.len()).unwrap_or(u32::MAX)
+    }
+
+    /// Returns `true` if the *function section* was empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.types.is_empty()
+    }
+
+    /// Gets the type of the function corresponding to the *code section* entry with the given
+    /// `index`.
+    #[inline]
+    pub fn get(&self, index: u32) -> Option<TypeIdx> {
+        self.types.get(crate::int::u32_to_usize(index)).copied()
+    }
+
+    /// Associates the given *code section* entry with its type, using its
+    /// [index](Code::index).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the *function section* has no entry corresponding to the `code`.
+    pub fn bind<C: Input>(&self, code: Code<C>) -> Parsed<Func<C>> {
+        #[inline(never)]
+        #[cold]
+        fn missing_signature(index: u32, count: u32) -> Error {
+            Error::new(ErrorKind::InvalidFormat).with_context(Context::from_closure(move |f| {
+                write!(
+                    f,
+                    "code section entry {index} has no corresponding function section entry, function section has {count} entries"
+                )
+            }))
+        }
+
+        match self.get(code.index()) {
+            Some(signature) => Ok(Func::new(signature, code)),
+            None => Err(missing_signature(code.index(), self.len())),
+        }
+    }
+}
+
+#[cfg(feature = "mem-usage")]
+impl crate::mem_usage::MemUsage for FunctionSignatures {
+    #[inline]
+    fn mem_usage(&self) -> usize {
+        crate::mem_usage::vec_capacity(&self.types)
+    }
+}
@@ -53,6 +53,18 @@ impl<I: Input> GlobalsComponent<I> {
     pub fn remaining_count(&self) -> u32 {
         self.globals.remaining_count()
     }
+
+    /// Gets the number of entries in the *global section* that have been parsed so far.
+    #[inline]
+    pub fn consumed_count(&self) -> u32 {
+        self.globals.consumed_count()
+    }
+
+    /// Gets the number of entries that were declared in the *global section*.
+    #[inline]
+    pub fn declared_count(&self) -> u32 {
+        self.globals.declared_count()
+    }
 }
 
 impl<I: Input> HasInput<I> for GlobalsComponent<I> {
@@ -0,0 +1,161 @@
+use crate::{
+    component::{Import, ImportsComponent},
+    input::Input,
+    parser::{name::Name, Parsed},
+};
+use alloc::{
+    collections::{btree_map::Entry, BTreeMap},
+    vec::Vec,
+};
+use core::fmt::{Debug, Formatter};
+
+/// Groups the imports in an [`ImportsComponent`] by their [module name](Import::module).
+///
+/// Each module name is yielded once, in the order that it first appears in the *import section*,
+/// along with an [`ImportModule`] iterator over the imports from that module.
+///
+/// Returned by [`ImportsComponent::group_by_module`], which parses all of the imports in a single
+/// pass to find the range of imports from each module.
+#[derive(Clone)]
+#[must_use]
+pub struct ImportModules<I: Clone + Input> {
+    modules: alloc::vec::IntoIter<ImportModule<I>>,
+}
+
+impl<I: Clone + Input> ImportModules<I> {
+    pub(super) fn new(mut imports: ImportsComponent<I>) -> Parsed<Self> {
+        let mut indices = BTreeMap::<Vec<u8>, usize>::new();
+        let mut modules = Vec::<ImportModule<I>>::new();
+
+        loop {
+            let position = imports.clone();
+            let import = match imports.next().transpose()? {
+                Some(import) => import,
+                None => break,
+            };
+
+            let end = imports.consumed_count();
+            let module = import.module();
+            match indices.entry(module.clone().into_bytes()?) {
+                Entry::Occupied(index) => modules[*index.get()].end = end,
+                Entry::Vacant(index) => {
+                    index.insert(modules.len());
+                    modules.push(ImportModule {
+                        module: module.clone(),
+                        imports: position,
+                        end,
+                    });
+                }
+            }
+        }
+
+        Ok(Self {
+            modules: modules.into_iter(),
+        })
+    }
+}
+
+impl<I: Clone + Input> Iterator for ImportModules<I> {
+    type Item = ImportModule<I>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.modules.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.modules.size_hint()
+    }
+}
+
+impl<I: Clone + Input> ExactSizeIterator for ImportModules<I> {}
+
+impl<I: Clone + Input> core::iter::FusedIterator for ImportModules<I> {}
+
+impl<I: Clone + Input> Debug for ImportModules<I> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+/// An iterator over the imports from a single module, returned by [`ImportModules`].
+#[derive(Clone)]
+#[must_use]
+pub struct ImportModule<I: Clone + Input> {
+    module: Name<I>,
+    /// Positioned at the first import from the module.
+    imports: ImportsComponent<I>,
+    /// The number of imports consumed after parsing the last import from the module.
+    end: u32,
+}
+
+impl<I: Clone + Input> ImportModule<I> {
+    /// Gets the name of the module that the imports originate from.
+    #[inline]
+    pub fn module(&self) -> &Name<I> {
+        &self.module
+    }
+
+    /// Parses the next import from the module.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an import could not be parsed.
+    pub fn parse(&mut self) -> Parsed<Option<Import<I>>> {
+        while self.imports.consumed_count() < self.end {
+            match self.imports.next().transpose()? {
+                Some(import) if import.module().try_eq_name(&self.module)? => {
+                    return Ok(Some(import))
+                }
+                Some(_) => (),
+                None => break,
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+impl<I: Clone + Input> Iterator for ImportModule<I> {
+    type Item = Parsed<Import<I>>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.parse().transpose()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.end.saturating_sub(self.imports.consumed_count());
+        (0, Some(crate::int::u32_to_usize(remaining)))
+    }
+}
+
+impl<I: Clone + Input> core::iter::FusedIterator for ImportModule<I> {}
+
+impl<I: Clone + Input> Debug for ImportModule<I> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        struct Imports<'a, I: Clone + Input>(&'a ImportModule<I>);
+
+        impl<I: Clone + Input> Debug for Imports<'_, I> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+                f.debug_list().entries(self.0.clone()).finish()
+            }
+        }
+
+        f.debug_struct("ImportModule")
+            .field("module", &self.module)
+            .field("imports", &Imports(self))
+            .finish()
+    }
+}
+
+#[cfg(feature = "mem-usage")]
+impl<I: Clone + Input> crate::mem_usage::MemUsage for ImportModules<I> {
+    #[inline]
+    fn mem_usage(&self) -> usize {
+        // The capacity of the original vector is not available
+        self.modules.len() * core::mem::size_of::<ImportModule<I>>()
+    }
+}
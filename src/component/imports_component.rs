@@ -175,6 +175,18 @@ impl<I: Input> ImportsComponent<I> {
         self.imports.remaining_count()
     }
 
+    /// Gets the number of imports that have been parsed so far.
+    #[inline]
+    pub fn consumed_count(&self) -> u32 {
+        self.imports.consumed_count()
+    }
+
+    /// Gets the number of imports that were declared.
+    #[inline]
+    pub fn declared_count(&self) -> u32 {
+        self.imports.declared_count()
+    }
+
     /// Parses the next import in the section.
     pub fn parse(&mut self) -> Parsed<Option<Import<&I>>> {
         self.imports
@@ -184,6 +196,52 @@ impl<I: Input> ImportsComponent<I> {
     }
 }
 
+impl<I: Clone + Input> ImportsComponent<I> {
+    /// Groups the imports by their [module name](Import::module), which is useful when the
+    /// imports from each module are processed separately, such as when generating host bindings.
+    ///
+    /// All of the imports are parsed in a single pass to find the imports from each module.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an import could not be parsed, or if a module name could not be read.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wasmiter::component::{ImportsComponent, KnownSection};
+    ///
+    /// let wasm = wat::parse_str(r#"(module
+    ///     (import "env" "a" (func))
+    ///     (import "wasi" "b" (func))
+    ///     (import "env" "c" (memory 1)))"#).unwrap();
+    ///
+    /// let sections = wasmiter::parse_module_sections(wasm.as_slice())?;
+    /// for result in sections {
+    ///     if let Ok(Ok(KnownSection::Import(imports))) = KnownSection::interpret(result?) {
+    ///         let mut modules = imports.group_by_module()?;
+    ///
+    ///         let env = modules.next().unwrap();
+    ///         assert!(env.module().try_eq_str("env")?);
+    ///         let names = env
+    ///             .map(|import| Ok(import?.name().to_string()))
+    ///             .collect::<wasmiter::parser::Parsed<Vec<_>>>()?;
+    ///         assert_eq!(names, ["a", "c"]);
+    ///
+    ///         assert!(modules.next().unwrap().module().try_eq_str("wasi")?);
+    ///         assert!(modules.next().is_none());
+    ///     }
+    /// }
+    /// # Ok::<(), wasmiter::parser::Error>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    #[inline]
+    pub fn group_by_module(self) -> Parsed<component::ImportModules<I>> {
+        component::ImportModules::new(self)
+    }
+}
+
 impl<I: Input> HasInput<I> for ImportsComponent<I> {
     #[inline]
     fn input(&self) -> &I {
@@ -228,6 +286,13 @@ impl<I: Clone + Input> Iterator for ImportsComponent<I> {
 
 impl<I: Clone + Input> core::iter::FusedIterator for ImportsComponent<I> {}
 
+impl<I: Clone + Input> parser::SpannedItems for ImportsComponent<I> {
+    #[inline]
+    fn next_item_offset(&self) -> u64 {
+        self.imports.next_offset()
+    }
+}
+
 impl<I: Input> Debug for ImportsComponent<I> {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         f.debug_list().entries(self.borrow_input()).finish()
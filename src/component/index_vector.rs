@@ -32,6 +32,18 @@ impl<N: Index, O: Offset, I: Input> IndexVector<N, O, I> {
         self.indices.remaining_count()
     }
 
+    /// Gets the number of indices that have been parsed so far.
+    #[inline]
+    pub fn consumed_count(&self) -> u32 {
+        self.indices.consumed_count()
+    }
+
+    /// Gets the number of indices that were declared.
+    #[inline]
+    pub fn declared_count(&self) -> u32 {
+        self.indices.declared_count()
+    }
+
     /// Parses the remaining indices.
     pub fn finish(mut self) -> Parsed<O> {
         for result in &mut self {
@@ -95,6 +107,13 @@ impl<N: Index, O: Offset, I: Input> Iterator for IndexVector<N, O, I> {
 
 impl<N: Index, O: Offset, I: Input> core::iter::FusedIterator for IndexVector<N, O, I> {}
 
+impl<N: Index, O: Offset, I: Input> crate::parser::SpannedItems for IndexVector<N, O, I> {
+    #[inline]
+    fn next_item_offset(&self) -> u64 {
+        self.indices.next_offset()
+    }
+}
+
 impl<N: Index, O: Offset, I: Input> core::fmt::Debug for IndexVector<N, O, I> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_list().entries(self.borrow_input()).finish()
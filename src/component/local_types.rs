@@ -0,0 +1,109 @@
+use crate::{
+    component::Locals,
+    index::LocalIdx,
+    input::Input,
+    parser::{Error, ErrorKind, Offset, Parsed, ResultExt as _},
+    types::ValType,
+};
+use alloc::vec::Vec;
+
+/// Maps each [`LocalIdx`] within a function to its [`ValType`], including the function's
+/// parameters.
+///
+/// Consecutive locals of the same type are stored as a single run, so the amount of memory
+/// used depends on the number of distinct groups of locals rather than the total number of
+/// locals.
+#[derive(Clone, Debug, Default)]
+pub struct LocalTypes {
+    parameter_count: u32,
+    /// Contains the exclusive end index of each run of locals of the same type.
+    runs: Vec<(u32, ValType)>,
+}
+
+impl LocalTypes {
+    fn push_run(&mut self, count: u32, local_type: ValType) -> Parsed<()> {
+        #[inline(never)]
+        #[cold]
+        fn too_many_locals() -> Error {
+            Error::new(ErrorKind::LocalCountOverflow)
+        }
+
+        let start = self.len();
+        let end = start.checked_add(count).ok_or_else(too_many_locals)?;
+
+        match self.runs.last_mut() {
+            Some((last_end, last_type)) if *last_type == local_type => *last_end = end,
+            _ => self.runs.push((end, local_type)),
+        }
+
+        Ok(())
+    }
+
+    /// Creates a mapping from the types of a function's `parameters`, followed by the
+    /// declarations of its `locals`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a parameter type or local declaration could not be parsed, or if the
+    /// total number of parameters and locals exceeds [`u32::MAX`].
+    pub fn new<P, O, I>(parameters: P, locals: &mut Locals<O, I>) -> Parsed<Self>
+    where
+        P: IntoIterator<Item = Parsed<ValType>>,
+        O: Offset,
+        I: Input,
+    {
+        let mut types = Self::default();
+
+        for result in parameters {
+            types.push_run(1, result.context("parameter type")?)?;
+        }
+
+        types.parameter_count = types.len();
+
+        while let Some((count, local_type)) = locals.next_group()? {
+            types.push_run(count.get(), local_type)?;
+        }
+
+        Ok(types)
+    }
+
+    /// Gets the number of parameters.
+    #[inline]
+    pub fn parameter_count(&self) -> u32 {
+        self.parameter_count
+    }
+
+    /// Gets the total number of parameters and locals.
+    #[inline]
+    pub fn len(&self) -> u32 {
+        self.runs.last().map_or(0, |(end, _)| *end)
+    }
+
+    /// Returns `true` if there are no parameters or locals.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Gets the type of the parameter or local with the given `index`, or `None` if the `index`
+    /// is out of bounds.
+    pub fn get(&self, index: LocalIdx) -> Option<ValType> {
+        let index = u32::from(index);
+        let run = self.runs.partition_point(|(end, _)| *end <= index);
+        self.runs.get(run).map(|(_, local_type)| *local_type)
+    }
+
+    /// Returns `true` if the given `index` refers to a parameter rather than a local variable.
+    #[inline]
+    pub fn is_parameter(&self, index: LocalIdx) -> bool {
+        u32::from(index) < self.parameter_count
+    }
+}
+
+#[cfg(feature = "mem-usage")]
+impl crate::mem_usage::MemUsage for LocalTypes {
+    #[inline]
+    fn mem_usage(&self) -> usize {
+        crate::mem_usage::vec_capacity(&self.runs)
+    }
+}
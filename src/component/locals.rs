@@ -1,7 +1,7 @@
 use crate::{
     component,
     input::{BorrowInput, CloneInput, HasInput, Input},
-    parser::{self, Offset, ResultExt as _},
+    parser::{self, Error, ErrorKind, Offset, ResultExt as _},
     types::ValType,
 };
 use core::{
@@ -15,25 +15,60 @@ use core::{
 /// each function in the
 /// [**funcs** component](https://webassembly.github.io/spec/core/syntax/modules.html#syntax-func)
 /// of a WebAssembly module.
+///
+/// The total number of local variables is checked as each group is parsed, and an error is
+/// returned if it exceeds the [limit](Locals::limit).
 pub struct Locals<O: Offset, I: Input> {
     offset: O,
     input: I,
     /// The number number of groups that remain to be parsed.
     count: u32,
     current: Option<(NonZeroU32, ValType)>,
+    /// The total number of locals in the groups that have been parsed so far.
+    total: u32,
+    limit: u32,
+}
+
+#[inline(never)]
+#[cold]
+fn local_count_overflow(limit: u32, offset: u64) -> Error {
+    let kind = if limit == u32::MAX {
+        ErrorKind::LocalCountOverflow
+    } else {
+        ErrorKind::LocalLimitExceeded(limit)
+    };
+
+    Error::new(kind).with_location_context("local group", offset)
 }
 
 impl<O: Offset, I: Input> Locals<O, I> {
-    pub(super) fn new(mut offset: O, input: I) -> parser::Parsed<Self> {
+    pub(crate) fn new(mut offset: O, input: I, limit: u32) -> parser::Parsed<Self> {
         Ok(Self {
             count: parser::leb128::u32(offset.offset_mut(), &input)
                 .context("locals declaration count")?,
             input,
             offset,
             current: None,
+            total: 0,
+            limit,
         })
     }
 
+    /// Gets the total number of local variables in the groups that have been parsed so far.
+    #[inline]
+    pub fn total_count(&self) -> u32 {
+        self.total
+    }
+
+    /// Gets the maximum number of local variables that can be declared.
+    ///
+    /// See [`CodeSection::with_max_locals()`](component::CodeSection::with_max_locals) for more
+    /// information.
+    #[inline]
+    pub fn limit(&self) -> u32 {
+        self.limit
+    }
+
     fn load_next_group(&mut self) -> parser::Parsed<Option<(NonZeroU32, ValType)>> {
         if let Some(existing) = self.current {
             Ok(Some(existing))
@@ -50,6 +85,12 @@ impl<O: Offset, I: Input> Locals<O, I> {
                     .context("local group count")?;
 
                 if let Some(variable_count) = NonZeroU32::new(count) {
+                    self.total = self
+                        .total
+                        .checked_add(count)
+                        .filter(|total| *total <= self.limit)
+                        .ok_or_else(|| local_count_overflow(self.limit, self.offset.offset()))?;
+
                     let variable_type = component::val_type(self.offset.offset_mut(), &self.input)
                         .context("local group type")?;
 
@@ -81,7 +122,7 @@ impl<O: Offset, I: Input> Locals<O, I> {
 
     /// Parses all local variable declarations.
     pub fn finish(mut self) -> parser::Parsed<O> {
-        while self.next_group().transpose().is_some() {}
+        while self.next_group()?.is_some() {}
         Ok(self.offset)
     }
 }
@@ -122,6 +163,8 @@ impl<'a, O: Offset, I: Input + 'a> BorrowInput<'a, I> for Locals<O, I> {
             input: &self.input,
             count: self.count,
             current: self.current,
+            total: self.total,
+            limit: self.limit,
         }
     }
 }
@@ -136,6 +179,8 @@ impl<'a, O: Offset, I: Clone + Input + 'a> CloneInput<'a, I> for Locals<O, &'a I
             input: self.input.clone(),
             count: self.count,
             current: self.current,
+            total: self.total,
+            limit: self.limit,
         }
     }
 }
@@ -38,6 +38,18 @@ impl<I: Input> MemsComponent<I> {
     pub fn remaining_count(&self) -> u32 {
         self.types.remaining_count()
     }
+
+    /// Gets the number of entries in the *memory section* that have been parsed so far.
+    #[inline]
+    pub fn consumed_count(&self) -> u32 {
+        self.types.consumed_count()
+    }
+
+    /// Gets the number of entries that were declared in the *memory section*.
+    #[inline]
+    pub fn declared_count(&self) -> u32 {
+        self.types.declared_count()
+    }
 }
 
 impl<I: Input> HasInput<I> for MemsComponent<I> {
@@ -83,6 +95,13 @@ impl<I: Input> core::iter::Iterator for MemsComponent<I> {
 
 impl<I: Clone + Input> core::iter::FusedIterator for MemsComponent<I> {}
 
+impl<I: Input> crate::parser::SpannedItems for MemsComponent<I> {
+    #[inline]
+    fn next_item_offset(&self) -> u64 {
+        self.types.next_offset()
+    }
+}
+
 impl<I: Input> core::fmt::Debug for MemsComponent<I> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         core::fmt::Debug::fmt(&self.types, f)
@@ -35,6 +35,18 @@ impl<O: Offset, I: Input> ResultType<O, I> {
         self.types.remaining_count()
     }
 
+    /// Gets the number of types that have been parsed so far.
+    #[inline]
+    pub fn consumed_count(&self) -> u32 {
+        self.types.consumed_count()
+    }
+
+    /// Gets the number of types that were declared.
+    #[inline]
+    pub fn declared_count(&self) -> u32 {
+        self.types.declared_count()
+    }
+
     /// Parses the remaining types.
     pub fn finish(mut self) -> Parsed<O> {
         for result in &mut self {
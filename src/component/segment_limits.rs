@@ -0,0 +1,107 @@
+/// Limits on how much of the [`ElemsComponent`](crate::component::ElemsComponent) or
+/// [`DatasComponent`](crate::component::DatasComponent) is printed, so that printing the segments
+/// of a large module does not take an unbounded amount of time.
+///
+/// Segments, elements, and bytes past the limits are skipped over without being formatted, and a
+/// comment indicating how many were omitted is written instead.
+///
+/// The [`Debug`](core::fmt::Debug) implementations of the element and data sections use the
+/// [`SegmentLimits::default()`] limits.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct SegmentLimits {
+    max_segments: u32,
+    max_elements: u32,
+    max_data_bytes: u64,
+}
+
+impl SegmentLimits {
+    /// Limits that allow every segment to be printed in its entirety.
+    pub const UNLIMITED: Self = Self {
+        max_segments: u32::MAX,
+        max_elements: u32::MAX,
+        max_data_bytes: u64::MAX,
+    };
+
+    /// Creates new [`SegmentLimits`] with the default limits, allowing up to `64` segments,
+    /// `64` elements per element segment, and `256` bytes per data segment to be printed.
+    pub const fn new() -> Self {
+        Self {
+            max_segments: 64,
+            max_elements: 64,
+            max_data_bytes: 256,
+        }
+    }
+
+    /// Sets the maximum number of segments that are printed.
+    pub const fn with_max_segments(mut self, count: u32) -> Self {
+        self.max_segments = count;
+        self
+    }
+
+    /// Sets the maximum number of elements that are printed for each element segment.
+    pub const fn with_max_elements(mut self, count: u32) -> Self {
+        self.max_elements = count;
+        self
+    }
+
+    /// Sets the maximum number of bytes that are printed for each data segment.
+    pub const fn with_max_data_bytes(mut self, count: u64) -> Self {
+        self.max_data_bytes = count;
+        self
+    }
+
+    /// The maximum number of segments that are printed.
+    #[inline]
+    pub const fn max_segments(&self) -> u32 {
+        self.max_segments
+    }
+
+    /// The maximum number of elements that are printed for each element segment.
+    #[inline]
+    pub const fn max_elements(&self) -> u32 {
+        self.max_elements
+    }
+
+    /// The maximum number of bytes that are printed for each data segment.
+    #[inline]
+    pub const fn max_data_bytes(&self) -> u64 {
+        self.max_data_bytes
+    }
+}
+
+impl Default for SegmentLimits {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Helper struct that writes the segments of an [`ElemsComponent`](crate::component::ElemsComponent)
+/// or [`DatasComponent`](crate::component::DatasComponent) in the
+/// [WebAssembly text format](https://webassembly.github.io/spec/core/text/index.html), stopping
+/// once the [`SegmentLimits`] are reached.
+///
+/// Returned by [`ElemsComponent::display_with_limits`](crate::component::ElemsComponent::display_with_limits)
+/// and [`DatasComponent::display_with_limits`](crate::component::DatasComponent::display_with_limits).
+#[derive(Clone, Copy, Debug)]
+pub struct DisplaySegments<'a, S> {
+    segments: &'a S,
+    limits: SegmentLimits,
+}
+
+impl<'a, S> DisplaySegments<'a, S> {
+    pub(super) fn new(segments: &'a S, limits: SegmentLimits) -> Self {
+        Self { segments, limits }
+    }
+
+    #[inline]
+    pub(crate) fn segments(&self) -> &'a S {
+        self.segments
+    }
+
+    /// Gets the limits used when printing the segments.
+    #[inline]
+    pub fn limits(&self) -> &SegmentLimits {
+        &self.limits
+    }
+}
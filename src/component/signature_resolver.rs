@@ -0,0 +1,200 @@
+use crate::{
+    component::{FunctionSection, ImportKind, ImportsComponent, KnownSection, TypesComponent},
+    index::{FuncIdx, TypeIdx},
+    input::{BorrowInput as _, Input},
+    parser::{Error, ErrorKind, Parsed, ResultExt as _},
+    sections::SectionSequence,
+    types::FuncType,
+};
+use alloc::vec::Vec;
+
+#[inline(never)]
+#[cold]
+fn duplicate_section(id: u8, offset: u64) -> Error {
+    Error::new(ErrorKind::DuplicateSection(id)).with_location_context("section", offset)
+}
+
+/// Resolves the signature of any function in a module, combining the types of imported
+/// functions from the *import section* with the types of defined functions from the
+/// *function section*, and looking them up in the *type section*.
+///
+/// This is useful when the signature of the callee of each `call` instruction is needed, since
+/// each lookup only requires indexing into a [`Vec`].
+///
+/// # Example
+///
+/// ```
+/// use wasmiter::{component::SignatureResolver, index::FuncIdx, types::ValType};
+///
+/// let wasm = wat::parse_str(r#"(module
+///     (import "env" "log" (func (param i32)))
+///     (func (result f32) f32.const 1))"#).unwrap();
+///
+/// let sections = wasmiter::parse_module_sections(wasm.as_slice())?;
+/// let signatures = SignatureResolver::new(&sections)?;
+///
+/// assert_eq!(signatures.imported_function_count(), 1);
+/// let log = signatures.function_signature(FuncIdx::from(0u32)).unwrap();
+/// assert_eq!(log.parameters(), &[ValType::I32]);
+/// let defined = signatures.function_signature(FuncIdx::from(1u32)).unwrap();
+/// assert_eq!(defined.results(), &[ValType::F32]);
+/// assert!(signatures.function_signature(FuncIdx::from(2u32)).is_none());
+/// # Ok::<(), wasmiter::parser::Error>(())
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct SignatureResolver {
+    types: Vec<FuncType>,
+    imported: Vec<TypeIdx>,
+    defined: Vec<TypeIdx>,
+}
+
+impl SignatureResolver {
+    /// Reads the *type*, *import*, and *function* sections of a module in a single pass over its
+    /// `sections`.
+    ///
+    /// As with [`Module::new()`](crate::Module::new), modules containing more than one of these
+    /// sections are rejected.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a section, a type, an import, or an entry in the *function section*
+    /// could not be parsed, or if one of these sections appeared more than once.
+    pub fn new<I: Input>(sections: &SectionSequence<I>) -> Parsed<Self> {
+        let mut resolver = Self::default();
+        let mut seen = [false; 3];
+        for result in sections.borrow_input() {
+            let section = result?;
+            let (id, offset) = (section.id(), section.contents().base());
+            let mut first = |index: usize| {
+                if core::mem::replace(&mut seen[index], true) {
+                    Err(duplicate_section(id, offset))
+                } else {
+                    Ok(())
+                }
+            };
+
+            match KnownSection::interpret(section) {
+                Ok(Ok(KnownSection::Type(types))) => {
+                    first(0)?;
+                    resolver.read_types(types)?;
+                }
+                Ok(Ok(KnownSection::Import(imports))) => {
+                    first(1)?;
+                    resolver.read_imports(imports)?;
+                }
+                Ok(Ok(KnownSection::Function(functions))) => {
+                    first(2)?;
+                    resolver.read_functions(functions)?;
+                }
+                Ok(Err(error)) => return Err(error),
+                _ => (),
+            }
+        }
+
+        Ok(resolver)
+    }
+
+    /// Reads the remaining entries of each of the given sections, any of which may be omitted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a type, an import, or an entry in the *function section* could not be
+    /// parsed.
+    pub fn from_components<T: Input, M: Input, F: Input>(
+        types: Option<TypesComponent<T>>,
+        imports: Option<ImportsComponent<M>>,
+        functions: Option<FunctionSection<F>>,
+    ) -> Parsed<Self> {
+        let mut resolver = Self::default();
+        if let Some(types) = types {
+            resolver.read_types(types)?;
+        }
+
+        if let Some(imports) = imports {
+            resolver.read_imports(imports)?;
+        }
+
+        if let Some(functions) = functions {
+            resolver.read_functions(functions)?;
+        }
+
+        Ok(resolver)
+    }
+
+    fn read_types<I: Input>(&mut self, mut types: TypesComponent<I>) -> Parsed<()> {
+        while let Some(func_type) = types.parse(
+            |parameters| parameters.collect::<Parsed<Vec<_>>>(),
+            |parameters, results| {
+                Ok(FuncType::new(
+                    parameters,
+                    results.collect::<Parsed<Vec<_>>>()?,
+                ))
+            },
+        )? {
+            self.types.push(func_type);
+        }
+
+        Ok(())
+    }
+
+    fn read_imports<I: Input>(&mut self, mut imports: ImportsComponent<I>) -> Parsed<()> {
+        while let Some(import) = imports.parse()? {
+            if let ImportKind::Function(ty) = import.kind() {
+                self.imported.push(*ty);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn read_functions<I: Input>(&mut self, functions: FunctionSection<I>) -> Parsed<()> {
+        for result in functions {
+            self.defined.push(result.context("function section entry")?);
+        }
+
+        Ok(())
+    }
+
+    /// Gets the number of imported functions.
+    #[inline]
+    pub fn imported_function_count(&self) -> u32 {
+        u32::try_from(self.imported.len()).unwrap_or(u32::MAX)
+    }
+
+    /// Gets the total number of functions, including imported functions.
+    #[inline]
+    pub fn function_count(&self) -> u32 {
+        u32::try_from(self.imported.len() + self.defined.len()).unwrap_or(u32::MAX)
+    }
+
+    /// Gets the index of the type of the given `function`, or `None` if the `function` does not
+    /// exist.
+    pub fn func_type_index(&self, function: FuncIdx) -> Option<TypeIdx> {
+        let index = crate::int::u32_to_usize(function.to_u32());
+        match index.checked_sub(self.imported.len()) {
+            Some(defined) => self.defined.get(defined).copied(),
+            None => self.imported.get(index).copied(),
+        }
+    }
+
+    /// Gets the type at the given `index` in the *type section*.
+    #[inline]
+    pub fn func_type(&self, index: TypeIdx) -> Option<&FuncType> {
+        self.types.get(crate::int::u32_to_usize(index.to_u32()))
+    }
+
+    /// Gets the signature of the given `function`, or `None` if the `function` or its type does
+    /// not exist.
+    pub fn function_signature(&self, function: FuncIdx) -> Option<&FuncType> {
+        self.func_type(self.func_type_index(function)?)
+    }
+}
+
+#[cfg(feature = "mem-usage")]
+impl crate::mem_usage::MemUsage for SignatureResolver {
+    fn mem_usage(&self) -> usize {
+        self.types.mem_usage()
+            + crate::mem_usage::vec_capacity(&self.imported)
+            + crate::mem_usage::vec_capacity(&self.defined)
+    }
+}
@@ -35,6 +35,18 @@ impl<I: Input> TablesComponent<I> {
     pub fn remaining_count(&self) -> u32 {
         self.types.remaining_count()
     }
+
+    /// Gets the number of entries in the *table section* that have been parsed so far.
+    #[inline]
+    pub fn consumed_count(&self) -> u32 {
+        self.types.consumed_count()
+    }
+
+    /// Gets the number of entries that were declared in the *table section*.
+    #[inline]
+    pub fn declared_count(&self) -> u32 {
+        self.types.declared_count()
+    }
 }
 
 impl<I: Input> HasInput<I> for TablesComponent<I> {
@@ -80,6 +92,13 @@ impl<I: Input> core::iter::Iterator for TablesComponent<I> {
 
 impl<I: Clone + Input> core::iter::FusedIterator for TablesComponent<I> {}
 
+impl<I: Input> crate::parser::SpannedItems for TablesComponent<I> {
+    #[inline]
+    fn next_item_offset(&self) -> u64 {
+        self.types.next_offset()
+    }
+}
+
 impl<I: Input> core::fmt::Debug for TablesComponent<I> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         core::fmt::Debug::fmt(&self.types, f)
@@ -61,6 +61,18 @@ impl<I: Input> TagsComponent<I> {
     pub fn remaining_count(&self) -> u32 {
         self.tags.remaining_count()
     }
+
+    /// Gets the number of tags that have been parsed so far.
+    #[inline]
+    pub fn consumed_count(&self) -> u32 {
+        self.tags.consumed_count()
+    }
+
+    /// Gets the number of tags that were declared.
+    #[inline]
+    pub fn declared_count(&self) -> u32 {
+        self.tags.declared_count()
+    }
 }
 
 impl<I: Input> HasInput<I> for TagsComponent<I> {
@@ -105,6 +117,13 @@ impl<I: Input> Iterator for TagsComponent<I> {
 
 impl<I: Input> core::iter::FusedIterator for TagsComponent<I> {}
 
+impl<I: Input> parser::SpannedItems for TagsComponent<I> {
+    #[inline]
+    fn next_item_offset(&self) -> u64 {
+        self.tags.next_offset()
+    }
+}
+
 impl<I: Input> core::fmt::Debug for TagsComponent<I> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_list().entries(self.borrow_input()).finish()
@@ -15,19 +15,45 @@ pub fn block_type<I: Input>(offset: &mut u64, input: I) -> Parsed<BlockType> {
         }))
     }
 
-    let value = leb128::s64(offset, input).context("block type tag or index")?;
-    Ok(match value {
-        -64 => BlockType::Empty,
-        -1 => BlockType::from(ValType::I32),
-        -2 => BlockType::from(ValType::I64),
-        -3 => BlockType::from(ValType::F32),
-        -4 => BlockType::from(ValType::F64),
-        -5 => BlockType::from(ValType::V128),
-        -16 => BlockType::from(ValType::FuncRef),
-        -17 => BlockType::from(ValType::ExternRef),
-        _ if value < 0 => return Err(not_a_valid_type(value)),
-        _ => BlockType::from(crate::index::TypeIdx::try_from(value as u64)?),
-    })
+    let value = leb128::s33(offset, &input).context("block type tag or index")?;
+    match value.to_block_type() {
+        Some(block_type) => Ok(block_type),
+        #[cfg(feature = "function-references")]
+        None if matches!(value.get(), -29 | -28) => {
+            let heap_type = heap_type(offset, input).context("reference type")?;
+            Ok(BlockType::Inline(
+                types::RefType::new(value.get() == -29, heap_type).into(),
+            ))
+        }
+        None => Err(not_a_valid_type(value.get())),
+    }
+}
+
+/// Parses a [`HeapType`](types::HeapType).
+///
+/// Type indices are only accepted when the `function-references` feature is enabled.
+pub fn heap_type<I: Input>(offset: &mut u64, input: I) -> Parsed<types::HeapType> {
+    #[cold]
+    #[inline(never)]
+    fn not_a_valid_heap_type(value: i64) -> Error {
+        Error::new(ErrorKind::InvalidFormat).with_context(Context::from_closure(move |f| {
+            write!(f, "{value} is not a valid heap type")
+        }))
+    }
+
+    let value = leb128::s33(offset, input).context("heap type")?;
+    match value.get() {
+        -16 => Ok(types::HeapType::Func),
+        -17 => Ok(types::HeapType::Extern),
+        -23 => Ok(types::HeapType::Exn),
+        #[cfg(feature = "function-references")]
+        _ if value.get() >= 0 => Ok(types::HeapType::Index(
+            value
+                .to_type_index()
+                .ok_or_else(|| not_a_valid_heap_type(value.get()))?,
+        )),
+        _ => Err(not_a_valid_heap_type(value.get())),
+    }
 }
 
 /// Parses a [`ValType`].
@@ -60,7 +86,9 @@ pub fn ref_type<I: Input>(offset: &mut u64, input: I) -> Parsed<types::RefType>
     #[inline(never)]
     #[cold]
     fn not_a_ref_type(actual: ValType) -> Error {
-        Error::new(ErrorKind::ExpectedRefType(actual))
+        Error::new(ErrorKind::InvalidFormat).with_context(Context::from_closure(move |f| {
+            write!(f, "expected reference type but got {actual}")
+        }))
     }
 
     let value_type = val_type(offset, input)?;
@@ -102,16 +130,30 @@ pub fn global_type<I: Input>(offset: &mut u64, input: &I) -> Parsed<types::Globa
 }
 
 /// Parses a [`MemType`](types::MemType).
+///
+/// Unlike [`limits`], this accepts a [custom page size](Limits::page_size) if the
+/// `custom-page-sizes` feature is enabled.
 #[inline]
 pub fn mem_type<I: Input>(offset: &mut u64, input: &I) -> Parsed<types::MemType> {
-    limits(offset, input)
+    limits_inner(offset, input, cfg!(feature = "custom-page-sizes"))
 }
 
-/// Parses [`Limits`].
+/// Parses [`Limits`], such as those of a [`TableType`].
+///
+/// Use [`mem_type`] to parse the limits of a linear memory, which may specify a
+/// [custom page size](Limits::page_size).
+#[inline]
 pub fn limits<I: Input>(offset: &mut u64, input: &I) -> Parsed<Limits> {
+    limits_inner(offset, input, false)
+}
+
+fn limits_inner<I: Input>(offset: &mut u64, input: &I, page_size: bool) -> Parsed<Limits> {
     let flag = parser::one_byte_exact(offset, input).context("parsing limit flag")?;
 
-    if flag & (!0b111) != 0 {
+    const HAS_PAGE_SIZE: u8 = 0b1000;
+
+    let allowed = if page_size { 0b1111 } else { 0b0111 };
+    if flag & !allowed != 0 {
         #[inline(never)]
         #[cold]
         fn bad_limit_flags(flags: u8) -> Error {
@@ -168,11 +210,32 @@ pub fn limits<I: Input>(offset: &mut u64, input: &I) -> Parsed<Limits> {
         }))
     }
 
-    if let Some(limits) = Limits::new(minimum, maximum, share, index_type) {
-        Ok(limits)
+    let limits = if let Some(limits) = Limits::new(minimum, maximum, share, index_type) {
+        limits
     } else {
-        Err(limit_maximum_greater_than_minimum(minimum, maximum))
+        return Err(limit_maximum_greater_than_minimum(minimum, maximum));
+    };
+
+    if flag & HAS_PAGE_SIZE == 0 {
+        return Ok(limits);
+    }
+
+    #[inline(never)]
+    #[cold]
+    fn unsupported_page_size(log2: u32) -> Error {
+        Error::new(ErrorKind::InvalidFormat).with_context(Context::from_closure(move |f| {
+            write!(
+                f,
+                "the page size 2^{log2} is not supported, expected 2^0 or 2^16"
+            )
+        }))
     }
+
+    let log2 = leb128::u32(offset, input).context("page size")?;
+    u8::try_from(log2)
+        .ok()
+        .and_then(|log2| limits.with_page_size_log2(log2))
+        .ok_or_else(|| unsupported_page_size(log2))
 }
 
 /// Parses a
@@ -35,6 +35,18 @@ impl<I: Input> TypesComponent<I> {
         self.types.remaining_count()
     }
 
+    /// Gets the number of types that have been parsed so far.
+    #[inline]
+    pub fn consumed_count(&self) -> u32 {
+        self.types.consumed_count()
+    }
+
+    /// Gets the number of types that were declared.
+    #[inline]
+    pub fn declared_count(&self) -> u32 {
+        self.types.declared_count()
+    }
+
     /// Parses the next function type in the section.
     #[inline]
     pub fn parse<Y, Z, P, R>(&mut self, parameter_types: P, result_types: R) -> Parsed<Option<Z>>
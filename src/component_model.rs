@@ -0,0 +1,215 @@
+//! Types and functions for recognizing the
+//! [binary format of WebAssembly components](https://github.com/WebAssembly/component-model/blob/main/design/mvp/Binary.md).
+//!
+//! Components share their preamble and section structure with core WebAssembly modules, but set
+//! the *layer* field of the preamble to `1`. Only the structure of a component is parsed; the
+//! contents of component-level sections other than nested modules and components are left
+//! uninterpreted.
+
+use crate::{
+    input::{BorrowInput, CloneInput, HasInput, Input, Window},
+    parser::{self, Parsed, ResultExt as _},
+    sections::{Section, SectionSequence},
+};
+use core::fmt::{Debug, Formatter};
+
+pub mod id;
+
+/// The *version* field of the preamble of a component supported by this module.
+pub const VERSION: u16 = 0xD;
+
+/// The *layer* field of the preamble of a component.
+pub const LAYER: u16 = 1;
+
+/// Indicates whether a WebAssembly binary is a core module or a component, as specified by the
+/// *layer* field of its preamble.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum BinaryKind {
+    /// A core WebAssembly module, with the given *version*.
+    Module(u16),
+    /// A component, with the given *version*.
+    Component(u16),
+}
+
+/// Reads the preamble of a WebAssembly binary to determine if it is a core module or a component.
+///
+/// The *version* field is returned without being checked.
+///
+/// # Errors
+///
+/// Returns an error if the preamble could not be read, if the magic number is incorrect, or if the
+/// *layer* field is not recognized.
+///
+/// # Example
+///
+/// ```
+/// use wasmiter::component_model::{self, BinaryKind};
+///
+/// let module = wat::parse_str("(module)").unwrap();
+/// assert_eq!(component_model::parse_binary_kind(module.as_slice())?, BinaryKind::Module(1));
+///
+/// let component = b"\0asm\x0D\0\x01\0";
+/// assert_eq!(
+///     component_model::parse_binary_kind(component.as_slice())?,
+///     BinaryKind::Component(0xD),
+/// );
+/// # Ok::<(), wasmiter::parser::Error>(())
+/// ```
+pub fn parse_binary_kind<I: Input>(binary: I) -> Parsed<BinaryKind> {
+    let fields = crate::parse_preamble_version(0, &binary)?;
+    let version = u16::from_le_bytes([fields[0], fields[1]]);
+    match u16::from_le_bytes([fields[2], fields[3]]) {
+        0 => Ok(BinaryKind::Module(version)),
+        LAYER => Ok(BinaryKind::Component(version)),
+        _ => {
+            #[inline(never)]
+            #[cold]
+            fn unknown_layer(fields: [u8; 4]) -> parser::Error {
+                parser::Error::new(parser::ErrorKind::UnsupportedWasmVersion(
+                    u32::from_le_bytes(fields),
+                ))
+                .with_context("unknown preamble layer".into())
+            }
+
+            Err(unknown_layer(fields))
+        }
+    }
+}
+
+const PREAMBLE_VERSION: [u8; 4] = {
+    let version = VERSION.to_le_bytes();
+    let layer = LAYER.to_le_bytes();
+    [version[0], version[1], layer[0], layer[1]]
+};
+
+/// Reads a [WebAssembly component binary](https://github.com/WebAssembly/component-model/blob/main/design/mvp/Binary.md),
+/// returning the sequence of component-level sections.
+///
+/// To interpret the nested modules and components within the sections, use
+/// [`KnownComponentSection::interpret`]. Other sections can be identified by comparing their
+/// [`id`](Section::id) to the constants in the [`id`] module.
+///
+/// # Errors
+///
+/// Returns an error if the preamble is not that of a component with the supported [`VERSION`].
+///
+/// # Example
+///
+/// ```
+/// use wasmiter::component_model::{self, id, KnownComponentSection};
+///
+/// let module = wat::parse_str("(module (func))").unwrap();
+/// let mut component = b"\0asm\x0D\0\x01\0".to_vec();
+/// component.push(id::CORE_MODULE);
+/// component.push(module.len() as u8);
+/// component.extend_from_slice(&module);
+///
+/// for result in component_model::parse_component_sections(component.as_slice())? {
+///     let section = result?;
+///     assert_eq!(section.id(), id::CORE_MODULE);
+///
+///     let sections = match KnownComponentSection::interpret(section)? {
+///         Ok(KnownComponentSection::CoreModule(sections)) => sections,
+///         _ => unreachable!(),
+///     };
+///
+///     assert_eq!(sections.count(), 3); // type, function, and code sections
+/// }
+/// # Ok::<(), wasmiter::parser::Error>(())
+/// ```
+pub fn parse_component_sections<I: Input>(binary: I) -> Parsed<SectionSequence<I>> {
+    crate::parse_preamble_with_version(0, &binary, PREAMBLE_VERSION)
+        .context("expected component preamble")?;
+    Ok(SectionSequence::new(
+        u64::from(crate::PREAMBLE_LENGTH),
+        binary,
+    ))
+}
+
+/// Represents a component-level section containing a nested module or component.
+#[derive(Clone, Copy)]
+#[non_exhaustive]
+pub enum KnownComponentSection<I: Input> {
+    /// A [core module section](id::CORE_MODULE), containing the sections of a core module.
+    CoreModule(SectionSequence<I>),
+    /// A [nested component section](id::COMPONENT), containing component-level sections.
+    Component(SectionSequence<I>),
+}
+
+impl<I: Input> KnownComponentSection<Window<I>> {
+    /// Attempts to interpret the contents of the given component-level section.
+    ///
+    /// Returns `Ok(Err(section))` if the section is not a core module or nested component section.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the preamble of the nested module or component is not supported.
+    pub fn interpret(section: Section<I>) -> Parsed<Result<Self, Section<I>>> {
+        let (id, contents) = match section.id() {
+            id::CORE_MODULE | id::COMPONENT => (section.id(), section.into_contents()),
+            _ => return Ok(Err(section)),
+        };
+
+        let base = contents.base();
+        let start = base + u64::from(crate::PREAMBLE_LENGTH);
+        Ok(Ok(if id == id::CORE_MODULE {
+            crate::parse_module_preamble(base, &contents).context("within core module section")?;
+            Self::CoreModule(SectionSequence::new(start, contents))
+        } else {
+            crate::parse_preamble_with_version(base, &contents, PREAMBLE_VERSION)
+                .context("within nested component section")?;
+            Self::Component(SectionSequence::new(start, contents))
+        }))
+    }
+}
+
+impl<I: Input> KnownComponentSection<I> {
+    /// Gets the sequence of sections within the nested module or component.
+    #[inline]
+    pub fn sections(&self) -> &SectionSequence<I> {
+        match self {
+            Self::CoreModule(sections) | Self::Component(sections) => sections,
+        }
+    }
+}
+
+impl<I: Input> HasInput<I> for KnownComponentSection<I> {
+    #[inline]
+    fn input(&self) -> &I {
+        self.sections().input()
+    }
+}
+
+impl<'a, I: Input + 'a> BorrowInput<'a, I> for KnownComponentSection<I> {
+    type Borrowed = KnownComponentSection<&'a I>;
+
+    fn borrow_input(&'a self) -> Self::Borrowed {
+        match self {
+            Self::CoreModule(sections) => {
+                KnownComponentSection::CoreModule(sections.borrow_input())
+            }
+            Self::Component(sections) => KnownComponentSection::Component(sections.borrow_input()),
+        }
+    }
+}
+
+impl<'a, I: Clone + Input + 'a> CloneInput<'a, I> for KnownComponentSection<&'a I> {
+    type Cloned = KnownComponentSection<I>;
+
+    fn clone_input(&self) -> Self::Cloned {
+        match self {
+            Self::CoreModule(sections) => KnownComponentSection::CoreModule(sections.clone_input()),
+            Self::Component(sections) => KnownComponentSection::Component(sections.clone_input()),
+        }
+    }
+}
+
+impl<I: Input> Debug for KnownComponentSection<I> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::CoreModule(sections) => f.debug_tuple("CoreModule").field(sections).finish(),
+            Self::Component(sections) => f.debug_tuple("Component").field(sections).finish(),
+        }
+    }
+}
@@ -0,0 +1,53 @@
+//! Contains well-known constants representing the section *id*s of a
+//! [WebAssembly component](https://github.com/WebAssembly/component-model/blob/main/design/mvp/Binary.md#component-definitions).
+
+macro_rules! known_ids {
+    ($(
+        $(#[$meta:meta])*
+        $name:ident = $value:literal;
+    )*) => {
+        $(
+            $(#[$meta])*
+            pub const $name: u8 = $value;
+        )*
+
+        /// Gets the name of the component section with the given `id`, or `None` if it is not
+        /// recognized.
+        pub fn name(id: u8) -> Option<&'static str> {
+            match id {
+                $($value => Some(stringify!($name)),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+known_ids! {
+    /// A custom section, which has the same format as a
+    /// [custom section in a module](crate::sections::id::CUSTOM).
+    CUSTOM = 0;
+    /// A section containing an entire core WebAssembly module, including its preamble.
+    CORE_MODULE = 1;
+    /// A section defining core module instances.
+    CORE_INSTANCE = 2;
+    /// A section defining core types.
+    CORE_TYPE = 3;
+    /// A section containing an entire nested component, including its preamble.
+    COMPONENT = 4;
+    /// A section defining component instances.
+    INSTANCE = 5;
+    /// A section defining aliases of exports and outer definitions.
+    ALIAS = 6;
+    /// A section defining component types.
+    TYPE = 7;
+    /// A section defining canonical functions, which lift and lower core functions.
+    CANONICAL = 8;
+    /// A section specifying a start function.
+    START = 9;
+    /// A section defining component imports.
+    IMPORT = 10;
+    /// A section defining component exports.
+    EXPORT = 11;
+    /// A section defining values.
+    VALUE = 12;
+}
@@ -0,0 +1,148 @@
+//! Small recipes showing how the APIs in this crate are combined to answer common questions about
+//! a WebAssembly module.
+//!
+//! Each function is intentionally short, so that it can be copied and adapted as needed.
+
+use crate::{
+    component::{ExportKind, ImportKind, KnownSection},
+    custom::{name::NameSubsection, CustomSection, KnownCustomSection},
+    index::FuncIdx,
+    input::{BorrowInput as _, Input},
+    parser::Parsed,
+    sections::SectionSequence,
+};
+use alloc::{string::String, vec::Vec};
+
+/// Lists the name and [`ExportKind`] of every export in the module.
+///
+/// # Example
+///
+/// ```
+/// use wasmiter::{component::ExportKind, index::FuncIdx};
+///
+/// let wasm = wat::parse_str(r#"(module (func (export "a")) (memory (export "mem") 1))"#).unwrap();
+/// let sections = wasmiter::parse_module_sections(wasm.as_slice())?;
+/// let exports = wasmiter::cookbook::list_exports(&sections)?;
+/// assert_eq!(exports[0], ("a".to_string(), ExportKind::Function(FuncIdx::from(0u32))));
+/// assert_eq!(exports[1].0, "mem");
+/// # Ok::<(), wasmiter::parser::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if a section could not be parsed, or if an export name was not valid UTF-8.
+pub fn list_exports<I: Input>(sections: &SectionSequence<I>) -> Parsed<Vec<(String, ExportKind)>> {
+    let mut exports = Vec::new();
+    for result in sections.borrow_input() {
+        if let Ok(known) = KnownSection::interpret(result?) {
+            if let KnownSection::Export(mut section) = known? {
+                while let Some(export) = section.parse()? {
+                    exports.push((export.name().try_into_string()?, *export.kind()));
+                }
+            }
+        }
+    }
+
+    Ok(exports)
+}
+
+/// Reads the names assigned to functions in the
+/// [*name section*](https://webassembly.github.io/spec/core/appendix/custom.html#name-section).
+///
+/// # Example
+///
+/// ```
+/// let wasm = wat::parse_str("(module (func $first) (func) (func $third))").unwrap();
+/// let sections = wasmiter::parse_module_sections(wasm.as_slice())?;
+/// let names = wasmiter::cookbook::function_names(&sections)?;
+/// assert_eq!(names.len(), 2);
+/// assert_eq!(names[1].0.to_u32(), 2);
+/// assert_eq!(names[1].1, "third");
+/// # Ok::<(), wasmiter::parser::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if a section could not be parsed, or if a name was not valid UTF-8.
+pub fn function_names<I: Input>(sections: &SectionSequence<I>) -> Parsed<Vec<(FuncIdx, String)>> {
+    let mut names = Vec::new();
+    for result in sections.borrow_input() {
+        let custom = match CustomSection::try_from_section(result?) {
+            Ok(custom) => custom?,
+            Err(_) => continue,
+        };
+
+        if let Ok(KnownCustomSection::Name(mut name_section)) =
+            KnownCustomSection::interpret(custom)
+        {
+            while let Some(subsection) = name_section.parse() {
+                // Unrecognized subsections are skipped
+                if let Ok(subsection) = subsection {
+                    if let NameSubsection::FunctionName(mut map) = subsection? {
+                        while let Some(assoc) = map.parse()? {
+                            names.push((assoc.index(), assoc.name().try_into_string()?));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(names)
+}
+
+/// Counts the number of instructions in the body of each function defined in the module,
+/// including the final [**end**](crate::instruction_set::Instruction::End) instruction.
+///
+/// # Example
+///
+/// ```
+/// let wasm = wat::parse_str(r#"
+///     (module
+///         (import "env" "f" (func))
+///         (func (result i32) i32.const 1 i32.const 2 i32.add))
+/// "#).unwrap();
+/// let sections = wasmiter::parse_module_sections(wasm.as_slice())?;
+/// let counts = wasmiter::cookbook::count_instructions(&sections)?;
+/// assert_eq!(counts, [(1u32.into(), 4)]);
+/// # Ok::<(), wasmiter::parser::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if a section or function body could not be parsed.
+pub fn count_instructions<I: Input>(sections: &SectionSequence<I>) -> Parsed<Vec<(FuncIdx, u32)>> {
+    let mut imported_function_count = 0u32;
+    let mut counts = Vec::new();
+    for result in sections.borrow_input() {
+        match KnownSection::interpret(result?) {
+            Ok(Ok(KnownSection::Import(mut imports))) => {
+                while let Some(import) = imports.parse()? {
+                    if let ImportKind::Function(_) = import.kind() {
+                        imported_function_count += 1;
+                    }
+                }
+            }
+            Ok(Ok(KnownSection::Code(code))) => {
+                for entry in code.with_function_indices(imported_function_count) {
+                    let (index, code) = entry?;
+                    let count = code.read(
+                        |_| Parsed::Ok(()),
+                        |(), body| {
+                            let mut count = 0u32;
+                            while body.next(|_| Parsed::Ok(())).transpose()?.is_some() {
+                                count += 1;
+                            }
+                            Parsed::Ok(count)
+                        },
+                    )?;
+                    counts.push((index, count));
+                }
+            }
+            Ok(Err(error)) => return Err(error),
+            _ => (),
+        }
+    }
+
+    Ok(counts)
+}
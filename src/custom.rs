@@ -2,17 +2,33 @@
 //! [WebAssembly custom sections](https://webassembly.github.io/spec/core/appendix/custom.html).
 
 use crate::{
-    input::{BorrowInput, Input, Window},
+    input::{BorrowInput, CloneInput, HasInput, Input, Window},
     sections::{id as section_id, SectionSequence},
 };
 use core::fmt::Debug;
 
 mod custom_section;
 
+pub mod dwarf;
+pub mod linking;
 pub mod name;
+pub mod producers;
+pub mod reloc;
 
 pub use custom_section::CustomSection;
 
+/// Specifies how bytes left over after parsing the contents of a custom section, such as those
+/// at the end of a [name subsection](name::NameSubsection), are handled.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Strictness {
+    /// Trailing bytes are ignored. The number of trailing bytes can be obtained with methods
+    /// such as [`NameSubsection::remnant_length()`](name::NameSubsection::remnant_length).
+    #[default]
+    Lenient,
+    /// Trailing bytes result in an error.
+    Strict,
+}
+
 /// Represents a well-known
 /// [custom section](https://webassembly.github.io/spec/core/appendix/custom.html) in a WebAssembly
 /// module.
@@ -21,36 +37,114 @@ pub use custom_section::CustomSection;
 #[allow(missing_docs)]
 pub enum KnownCustomSection<I: Input> {
     Name(name::NameSection<I>),
+    Dwarf(dwarf::DwarfSection<I>),
+    ExternalDebugInfo(dwarf::ExternalDebugInfo<I>),
+    Linking(linking::LinkingSection<I>),
 }
 
 impl<I: Input> KnownCustomSection<Window<I>> {
     /// Attempts to interpret the contents of the given [`CustomSection`].
     ///
+    /// A [`linking`](section_id::LINKING) section whose version could not be parsed or is not
+    /// supported is not recognized, [`LinkingSection::interpret()`](linking::LinkingSection::interpret)
+    /// can be used to obtain the error instead.
+    ///
     /// # Errors
     ///
     /// Returns `section` if it was not recognized.
+    #[inline]
     pub fn interpret(section: CustomSection<I>) -> Result<Self, CustomSection<I>> {
+        Self::interpret_with(section, Strictness::Lenient)
+    }
+
+    /// Attempts to interpret the contents of the given [`CustomSection`], using the given
+    /// [`Strictness`] to handle trailing bytes within its contents.
+    ///
+    /// # Errors
+    ///
+    /// Returns `section` if it was not recognized.
+    pub fn interpret_with(
+        section: CustomSection<I>,
+        strictness: Strictness,
+    ) -> Result<Self, CustomSection<I>> {
         if let Some(static_name) =
             section_id::is_custom_name_recognized(section.name().borrow_input())
         {
             match static_name {
                 section_id::NAME => {
                     let contents = section.into_contents();
-                    Ok(Self::Name(name::NameSection::new(SectionSequence::new(
-                        contents.base(),
-                        contents,
-                    ))))
+                    Ok(Self::Name(
+                        name::NameSection::new(SectionSequence::new(contents.base(), contents))
+                            .with_strictness(strictness),
+                    ))
+                }
+                section_id::EXTERNAL_DEBUG_INFO => {
+                    let contents = section.into_contents();
+                    Ok(Self::ExternalDebugInfo(
+                        dwarf::ExternalDebugInfo::new(contents.base(), contents)
+                            .with_strictness(strictness),
+                    ))
+                }
+                section_id::LINKING => {
+                    linking::LinkingSection::interpret_supported(section, strictness)
+                        .map(Self::Linking)
                 }
                 _ => Err(section),
             }
         } else {
-            Err(section)
+            dwarf::DwarfSection::interpret(section).map(Self::Dwarf)
         }
     }
 
     /// Gets the name of the custom section.
     pub fn name(&self) -> &str {
-        section_id::NAME
+        match self {
+            Self::Name(_) => section_id::NAME,
+            Self::Dwarf(dwarf) => dwarf.name(),
+            Self::ExternalDebugInfo(_) => section_id::EXTERNAL_DEBUG_INFO,
+            Self::Linking(_) => section_id::LINKING,
+        }
+    }
+}
+
+impl<I: Input> HasInput<I> for KnownCustomSection<I> {
+    fn input(&self) -> &I {
+        match self {
+            Self::Name(names) => names.input(),
+            Self::Dwarf(dwarf) => dwarf.input(),
+            Self::ExternalDebugInfo(info) => info.input(),
+            Self::Linking(linking) => linking.input(),
+        }
+    }
+}
+
+impl<'a, I: Input + 'a> BorrowInput<'a, I> for KnownCustomSection<I> {
+    type Borrowed = KnownCustomSection<&'a I>;
+
+    fn borrow_input(&'a self) -> Self::Borrowed {
+        match self {
+            Self::Name(names) => KnownCustomSection::Name(names.borrow_input()),
+            Self::Dwarf(dwarf) => KnownCustomSection::Dwarf(dwarf.borrow_input()),
+            Self::ExternalDebugInfo(info) => {
+                KnownCustomSection::ExternalDebugInfo(info.borrow_input())
+            }
+            Self::Linking(linking) => KnownCustomSection::Linking(linking.borrow_input()),
+        }
+    }
+}
+
+impl<'a, I: Clone + Input + 'a> CloneInput<'a, I> for KnownCustomSection<&'a I> {
+    type Cloned = KnownCustomSection<I>;
+
+    fn clone_input(&self) -> Self::Cloned {
+        match self {
+            Self::Name(names) => KnownCustomSection::Name(names.clone_input()),
+            Self::Dwarf(dwarf) => KnownCustomSection::Dwarf(dwarf.clone_input()),
+            Self::ExternalDebugInfo(info) => {
+                KnownCustomSection::ExternalDebugInfo(info.clone_input())
+            }
+            Self::Linking(linking) => KnownCustomSection::Linking(linking.clone_input()),
+        }
     }
 }
 
@@ -58,6 +152,9 @@ impl<I: Input> Debug for KnownCustomSection<I> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Self::Name(names) => Debug::fmt(names, f),
+            Self::Dwarf(dwarf) => Debug::fmt(dwarf, f),
+            Self::ExternalDebugInfo(info) => Debug::fmt(info, f),
+            Self::Linking(linking) => Debug::fmt(linking, f),
         }
     }
 }
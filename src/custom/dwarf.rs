@@ -0,0 +1,326 @@
+//! Types to recognize the custom sections containing
+//! [DWARF debugging information](https://yurydelendik.github.io/webassembly-dwarf/), as described
+//! in the [WebAssembly tool conventions](https://github.com/WebAssembly/tool-conventions/blob/main/Debugging.md).
+
+use crate::{
+    custom::{CustomSection, Strictness},
+    input::{BorrowInput, CloneInput, HasInput, Input, Window},
+    parser::{self, name::Name, ResultExt as _},
+    sections::id as section_id,
+};
+use core::fmt::{Debug, Formatter};
+
+/// The prefix used in the names of custom sections containing DWARF debugging information, such
+/// as `.debug_info`.
+pub const NAME_PREFIX: &str = ".debug_";
+
+macro_rules! dwarf_sections {
+    ($(
+        $(#[$meta:meta])*
+        $name:ident = $value:literal;
+    )*) => {
+        /// Indicates which DWARF section is embedded in a [`DwarfSection`].
+        #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+        #[non_exhaustive]
+        pub enum DwarfSectionKind {
+            $(
+                $(#[$meta])*
+                $name,
+            )*
+        }
+
+        impl DwarfSectionKind {
+            /// All of the recognized DWARF sections.
+            pub const ALL: &'static [Self] = &[$(Self::$name,)*];
+
+            /// Gets the name of the custom section containing this DWARF section.
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(Self::$name => $value,)*
+                }
+            }
+
+            /// Gets the DWARF section corresponding to the given custom section name.
+            pub fn from_name(name: &str) -> Option<Self> {
+                match name {
+                    $($value => Some(Self::$name),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+dwarf_sections! {
+    /// The `.debug_abbrev` section, containing abbreviations used by the `.debug_info` section.
+    Abbrev = ".debug_abbrev";
+    /// The `.debug_addr` section, containing addresses referred to by indices.
+    Addr = ".debug_addr";
+    /// The `.debug_aranges` section, mapping address ranges to compilation units.
+    Aranges = ".debug_aranges";
+    /// The `.debug_frame` section, describing call frames.
+    Frame = ".debug_frame";
+    /// The `.debug_info` section, containing debugging information entries.
+    Info = ".debug_info";
+    /// The `.debug_line` section, mapping code offsets to source lines.
+    Line = ".debug_line";
+    /// The `.debug_line_str` section, containing strings used by the `.debug_line` section.
+    LineStr = ".debug_line_str";
+    /// The `.debug_loc` section, containing location lists.
+    Loc = ".debug_loc";
+    /// The `.debug_loclists` section, containing location lists introduced in DWARF 5.
+    Loclists = ".debug_loclists";
+    /// The `.debug_macinfo` section, describing macros.
+    Macinfo = ".debug_macinfo";
+    /// The `.debug_macro` section, describing macros in the format introduced in DWARF 5.
+    Macro = ".debug_macro";
+    /// The `.debug_names` section, an index of names introduced in DWARF 5.
+    Names = ".debug_names";
+    /// The `.debug_pubnames` section, an index of global objects and functions.
+    Pubnames = ".debug_pubnames";
+    /// The `.debug_pubtypes` section, an index of global types.
+    Pubtypes = ".debug_pubtypes";
+    /// The `.debug_ranges` section, containing address ranges.
+    Ranges = ".debug_ranges";
+    /// The `.debug_rnglists` section, containing range lists introduced in DWARF 5.
+    Rnglists = ".debug_rnglists";
+    /// The `.debug_str` section, containing strings used by the `.debug_info` section.
+    Str = ".debug_str";
+    /// The `.debug_str_offsets` section, containing offsets into the `.debug_str` section.
+    StrOffsets = ".debug_str_offsets";
+    /// The `.debug_types` section, containing type units from DWARF 4.
+    Types = ".debug_types";
+}
+
+impl DwarfSectionKind {
+    /// Gets the DWARF section corresponding to the name of the given [`CustomSection`].
+    pub(super) fn recognize<I: Input>(name: &Name<I>) -> Option<Self> {
+        let mut buffer = [0u8; 24]; // Enough to fit the longest DWARF section name
+        if name.length() > buffer.len() as u64 {
+            return None;
+        }
+
+        let name = name.copy_to_slice(&mut buffer).ok()?;
+        Self::from_name(core::str::from_utf8(name).ok()?)
+    }
+}
+
+impl core::fmt::Display for DwarfSectionKind {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Represents a custom section containing a DWARF section, whose contents are not parsed.
+///
+/// The [`contents`](DwarfSection::contents) can be passed to a DWARF parser. Addresses within
+/// the DWARF sections are offsets from the start of the contents of the *code* section.
+#[derive(Clone, Copy)]
+pub struct DwarfSection<I: Input> {
+    kind: DwarfSectionKind,
+    contents: I,
+}
+
+impl<I: Input> DwarfSection<I> {
+    /// Creates a new [`DwarfSection`] with the given `contents`.
+    #[inline]
+    pub fn new(kind: DwarfSectionKind, contents: I) -> Self {
+        Self { kind, contents }
+    }
+
+    /// Gets which DWARF section this is.
+    #[inline]
+    pub fn kind(&self) -> DwarfSectionKind {
+        self.kind
+    }
+
+    /// Gets the name of the custom section.
+    #[inline]
+    pub fn name(&self) -> &'static str {
+        self.kind.name()
+    }
+
+    /// Gets the raw contents of the DWARF section.
+    #[inline]
+    pub fn contents(&self) -> &I {
+        &self.contents
+    }
+
+    /// Gets the raw contents of the DWARF section.
+    #[inline]
+    pub fn into_contents(self) -> I {
+        self.contents
+    }
+}
+
+impl<I: Input> DwarfSection<Window<I>> {
+    /// Attempts to interpret the given [`CustomSection`] as a DWARF section.
+    ///
+    /// # Errors
+    ///
+    /// Returns `section` if its name is not a recognized DWARF section name.
+    pub fn interpret(section: CustomSection<I>) -> Result<Self, CustomSection<I>> {
+        match DwarfSectionKind::recognize(section.name()) {
+            Some(kind) => Ok(Self::new(kind, section.into_contents())),
+            None => Err(section),
+        }
+    }
+}
+
+impl<I: Input> HasInput<I> for DwarfSection<I> {
+    #[inline]
+    fn input(&self) -> &I {
+        &self.contents
+    }
+}
+
+impl<'a, I: Input + 'a> BorrowInput<'a, I> for DwarfSection<I> {
+    type Borrowed = DwarfSection<&'a I>;
+
+    #[inline]
+    fn borrow_input(&'a self) -> Self::Borrowed {
+        DwarfSection::new(self.kind, &self.contents)
+    }
+}
+
+impl<'a, I: Clone + Input + 'a> CloneInput<'a, I> for DwarfSection<&'a I> {
+    type Cloned = DwarfSection<I>;
+
+    #[inline]
+    fn clone_input(&self) -> Self::Cloned {
+        DwarfSection::new(self.kind, self.contents.clone())
+    }
+}
+
+impl<I: Input> Debug for DwarfSection<I> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("DwarfSection")
+            .field("kind", &self.kind)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Represents the
+/// [`external_debug_info` custom section](https://github.com/WebAssembly/tool-conventions/blob/main/Debugging.md#external-dwarf),
+/// which contains the URL of a file containing the debugging information for a module.
+#[derive(Clone, Copy)]
+pub struct ExternalDebugInfo<I: Input> {
+    offset: u64,
+    strictness: Strictness,
+    contents: I,
+}
+
+impl<I: Input> ExternalDebugInfo<I> {
+    /// Uses the given [`Input`] to read the contents of an `external_debug_info` section,
+    /// starting at the given `offset`.
+    #[inline]
+    pub fn new(offset: u64, contents: I) -> Self {
+        Self {
+            offset,
+            strictness: Strictness::Lenient,
+            contents,
+        }
+    }
+
+    /// Sets how bytes left over after the URL are handled.
+    ///
+    /// Defaults to [`Strictness::Lenient`].
+    #[inline]
+    pub fn with_strictness(mut self, strictness: Strictness) -> Self {
+        self.strictness = strictness;
+        self
+    }
+
+    /// Parses the URL of the file containing the debugging information.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the URL could not be parsed, or if the [`Strictness`] is
+    /// [`Strict`](Strictness::Strict) and bytes were left over after the URL.
+    pub fn url(&self) -> parser::Parsed<Name<&I>> {
+        let mut offset = self.offset;
+        let url = parser::name::parse(&mut offset, &self.contents)
+            .context("within external_debug_info section")?;
+
+        if self.strictness == Strictness::Strict {
+            let remnant = self.contents.length_at(offset)?;
+            if remnant > 0 {
+                #[inline(never)]
+                #[cold]
+                fn trailing_bytes(remnant: u64) -> parser::Error {
+                    parser::Error::new(parser::ErrorKind::TrailingBytes(
+                        u32::try_from(remnant).unwrap_or(u32::MAX),
+                    ))
+                }
+
+                return Err(trailing_bytes(remnant)).context("external_debug_info section");
+            }
+        }
+
+        Ok(url)
+    }
+}
+
+impl<I: Input> ExternalDebugInfo<Window<I>> {
+    /// Attempts to interpret the given [`CustomSection`] as an `external_debug_info` section.
+    ///
+    /// # Errors
+    ///
+    /// Returns `section` if its name is not
+    /// [`external_debug_info`](section_id::EXTERNAL_DEBUG_INFO).
+    pub fn interpret(section: CustomSection<I>) -> Result<Self, CustomSection<I>> {
+        match section_id::is_custom_name_recognized(section.name().borrow_input()) {
+            Some(section_id::EXTERNAL_DEBUG_INFO) => {
+                let contents = section.into_contents();
+                Ok(Self::new(contents.base(), contents))
+            }
+            _ => Err(section),
+        }
+    }
+}
+
+impl<I: Input> HasInput<I> for ExternalDebugInfo<I> {
+    #[inline]
+    fn input(&self) -> &I {
+        &self.contents
+    }
+}
+
+impl<'a, I: Input + 'a> BorrowInput<'a, I> for ExternalDebugInfo<I> {
+    type Borrowed = ExternalDebugInfo<&'a I>;
+
+    #[inline]
+    fn borrow_input(&'a self) -> Self::Borrowed {
+        ExternalDebugInfo {
+            offset: self.offset,
+            strictness: self.strictness,
+            contents: &self.contents,
+        }
+    }
+}
+
+impl<'a, I: Clone + Input + 'a> CloneInput<'a, I> for ExternalDebugInfo<&'a I> {
+    type Cloned = ExternalDebugInfo<I>;
+
+    #[inline]
+    fn clone_input(&self) -> Self::Cloned {
+        ExternalDebugInfo {
+            offset: self.offset,
+            strictness: self.strictness,
+            contents: self.contents.clone(),
+        }
+    }
+}
+
+impl<I: Input> Debug for ExternalDebugInfo<I> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let url = self.url();
+        let mut s = f.debug_struct("ExternalDebugInfo");
+        match &url {
+            Ok(url) => s.field("url", url),
+            Err(e) => s.field("url", e),
+        };
+        s.finish()
+    }
+}
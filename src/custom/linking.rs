@@ -0,0 +1,1156 @@
+//! Types to parse the
+//! [`linking` custom section](https://github.com/WebAssembly/tool-conventions/blob/main/Linking.md#linking-metadata-section)
+//! described in the [WebAssembly tool conventions](https://github.com/WebAssembly/tool-conventions)
+//! for static linking.
+//!
+//! The relocations that refer to the symbols in the [`SymbolTable`] are parsed by the
+//! [`reloc`](crate::custom::reloc) module.
+
+use crate::{
+    custom::{CustomSection, Strictness},
+    index,
+    input::{BorrowInput, CloneInput, HasInput, Input, Window},
+    parser::{self, name::Name, Parsed, ResultExt as _, Vector},
+    sections::{id as section_id, Section, SectionSequence},
+};
+use core::fmt::{Debug, Formatter};
+
+/// The version of the `linking` section supported by this module.
+pub const VERSION: u32 = 2;
+
+pub(crate) const SEGMENT_INFO_ID: u8 = 5;
+pub(crate) const INIT_FUNCS_ID: u8 = 6;
+pub(crate) const COMDAT_INFO_ID: u8 = 7;
+pub(crate) const SYMBOL_TABLE_ID: u8 = 8;
+
+macro_rules! flags {
+    ($(#[$outer:meta])* $name:ident {$(
+        $(#[$meta:meta])*
+        $flag:ident = $value:literal;
+    )*}) => {
+        $(#[$outer])*
+        #[derive(Clone, Copy, Default, Eq, Hash, PartialEq)]
+        pub struct $name(u32);
+
+        impl $name {
+            $(
+                $(#[$meta])*
+                pub const $flag: Self = Self($value);
+            )*
+
+            /// Creates flags from the given bits, which may include unknown flags.
+            #[inline]
+            pub const fn from_bits(bits: u32) -> Self {
+                Self(bits)
+            }
+
+            /// Gets the underlying bits, which may include unknown flags.
+            #[inline]
+            pub const fn bits(self) -> u32 {
+                self.0
+            }
+
+            /// Returns `true` if all of the `other` flags are set.
+            #[inline]
+            pub const fn contains(self, other: Self) -> bool {
+                self.0 & other.0 == other.0
+            }
+        }
+
+        impl Debug for $name {
+            fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+                let mut set = f.debug_set();
+                $(
+                    if self.contains(Self::$flag) {
+                        set.entry(&format_args!(stringify!($flag)));
+                    }
+                )*
+
+                let unknown = self.0 & !(0 $(| $value)*);
+                if unknown != 0 {
+                    set.entry(&format_args!("{unknown:#X}"));
+                }
+
+                set.finish()
+            }
+        }
+    };
+}
+
+flags! {
+    /// Flags describing a data segment, stored in a [`SegmentInfo`].
+    SegmentFlags {
+        /// The segment contains only null-terminated strings, allowing them to be merged.
+        STRINGS = 0x1;
+        /// The segment contains thread-local data.
+        TLS = 0x2;
+        /// The segment must not be removed by the linker, even if it is not referenced.
+        RETAIN = 0x4;
+    }
+}
+
+flags! {
+    /// Flags describing a symbol, stored in a [`SymbolInfo`].
+    SymbolFlags {
+        /// The symbol is weak, allowing it to be overridden by a non-weak symbol.
+        BINDING_WEAK = 0x1;
+        /// The symbol is local, and is not visible outside of the object file.
+        BINDING_LOCAL = 0x2;
+        /// The symbol is hidden, and is not exported from the linked module.
+        VISIBILITY_HIDDEN = 0x4;
+        /// The symbol is not defined in the object file, and refers to an import.
+        UNDEFINED = 0x10;
+        /// The symbol is exported from the linked module.
+        EXPORTED = 0x20;
+        /// The symbol has a name that differs from the name of the import it refers to.
+        EXPLICIT_NAME = 0x40;
+        /// The symbol must not be removed by the linker, even if it is not referenced.
+        NO_STRIP = 0x80;
+        /// The symbol refers to thread-local data.
+        TLS = 0x100;
+        /// The address of the symbol is absolute, rather than relative to a data segment.
+        ABSOLUTE = 0x200;
+    }
+}
+
+/// Implements methods to obtain the number of entries in a subsection, along with the traits
+/// used to borrow and clone its [`Input`].
+macro_rules! subsection_vector {
+    ($name:ident, $entries:ident, $what:literal) => {
+        impl<I: Input> $name<I> {
+            /// Uses the given [`Input`] to read the contents of the subsection, starting at the
+            /// given `offset`.
+            pub fn new(offset: u64, input: I) -> Parsed<Self> {
+                Ok(Self {
+                    $entries: Vector::parse(offset, input)
+                        .context(concat!("at start of ", $what))?,
+                })
+            }
+
+            /// Gets the expected remaining number of entries that have yet to be parsed.
+            #[inline]
+            pub fn remaining_count(&self) -> u32 {
+                self.$entries.remaining_count()
+            }
+
+            /// Gets the number of entries that have been parsed so far.
+            #[inline]
+            pub fn consumed_count(&self) -> u32 {
+                self.$entries.consumed_count()
+            }
+
+            /// Gets the number of entries that were declared.
+            #[inline]
+            pub fn declared_count(&self) -> u32 {
+                self.$entries.declared_count()
+            }
+
+            /// Parses all remaining entries, returning the offset to the first byte after the last
+            /// entry.
+            pub fn finish(mut self) -> Parsed<u64> {
+                while self.parse()?.is_some() {}
+                Ok(self.$entries.into_offset())
+            }
+        }
+
+        impl<I: Input> HasInput<I> for $name<I> {
+            #[inline]
+            fn input(&self) -> &I {
+                self.$entries.input()
+            }
+        }
+
+        impl<'a, I: Input + 'a> BorrowInput<'a, I> for $name<I> {
+            type Borrowed = $name<&'a I>;
+
+            #[inline]
+            fn borrow_input(&'a self) -> Self::Borrowed {
+                $name {
+                    $entries: self.$entries.borrow_input(),
+                }
+            }
+        }
+
+        impl<'a, I: Clone + Input + 'a> CloneInput<'a, I> for $name<&'a I> {
+            type Cloned = $name<I>;
+
+            #[inline]
+            fn clone_input(&self) -> Self::Cloned {
+                $name {
+                    $entries: self.$entries.clone_input(),
+                }
+            }
+        }
+
+        impl<I: Input> Debug for $name<I> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+                f.debug_list().entries(self.borrow_input()).finish()
+            }
+        }
+    };
+}
+
+/// Describes a data segment, providing its name, alignment, and [`SegmentFlags`].
+#[derive(Clone, Copy)]
+pub struct SegmentInfo<I: Input> {
+    name: Name<I>,
+    alignment: u32,
+    flags: SegmentFlags,
+}
+
+impl<'a, I: Input> SegmentInfo<&'a I> {
+    fn parse(offset: &mut u64, input: &'a I) -> Parsed<Self> {
+        let name = parser::name::parse(offset, input).context("segment name")?;
+        let alignment = parser::leb128::u32(offset, input).context("segment alignment")?;
+        let flags = parser::leb128::u32(offset, input).context("segment flags")?;
+        Ok(Self {
+            name,
+            alignment,
+            flags: SegmentFlags::from_bits(flags),
+        })
+    }
+}
+
+impl<I: Input> SegmentInfo<I> {
+    /// Gets the name of the data segment.
+    #[inline]
+    pub fn name(&self) -> &Name<I> {
+        &self.name
+    }
+
+    /// Gets the alignment of the data segment, encoded as a power of two.
+    #[inline]
+    pub fn alignment_power(&self) -> u32 {
+        self.alignment
+    }
+
+    /// Gets the flags describing the data segment.
+    #[inline]
+    pub fn flags(&self) -> SegmentFlags {
+        self.flags
+    }
+}
+
+impl<I: Input> HasInput<I> for SegmentInfo<I> {
+    #[inline]
+    fn input(&self) -> &I {
+        self.name.input()
+    }
+}
+
+impl<'a, I: Input + 'a> BorrowInput<'a, I> for SegmentInfo<I> {
+    type Borrowed = SegmentInfo<&'a I>;
+
+    #[inline]
+    fn borrow_input(&'a self) -> Self::Borrowed {
+        SegmentInfo {
+            name: self.name.borrow_input(),
+            alignment: self.alignment,
+            flags: self.flags,
+        }
+    }
+}
+
+impl<'a, I: Clone + Input + 'a> CloneInput<'a, I> for SegmentInfo<&'a I> {
+    type Cloned = SegmentInfo<I>;
+
+    #[inline]
+    fn clone_input(&self) -> Self::Cloned {
+        SegmentInfo {
+            name: self.name.clone_input(),
+            alignment: self.alignment,
+            flags: self.flags,
+        }
+    }
+}
+
+impl<I: Input> Debug for SegmentInfo<I> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SegmentInfo")
+            .field("name", &self.name)
+            .field("alignment_power", &self.alignment)
+            .field("flags", &self.flags)
+            .finish()
+    }
+}
+
+/// Represents the `WASM_SEGMENT_INFO` subsection, which describes each data segment in the
+/// module.
+#[derive(Clone, Copy)]
+pub struct SegmentInfos<I: Input> {
+    segments: Vector<u64, I>,
+}
+
+subsection_vector!(SegmentInfos, segments, "segment info subsection");
+
+impl<I: Input> SegmentInfos<I> {
+    /// Parses the next [`SegmentInfo`].
+    pub fn parse(&mut self) -> Parsed<Option<SegmentInfo<&I>>> {
+        self.segments
+            .advance(SegmentInfo::parse)
+            .transpose()
+            .context("within segment info subsection")
+    }
+}
+
+impl<I: Clone + Input> Iterator for SegmentInfos<I> {
+    type Item = Parsed<SegmentInfo<I>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.parse()
+            .map(|segment| segment.map(|segment| segment.clone_input()))
+            .transpose()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.segments.size_hint()
+    }
+}
+
+impl<I: Clone + Input> core::iter::FusedIterator for SegmentInfos<I> {}
+
+/// An initialization function, which is called by the linked module's start function.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct InitFunc {
+    priority: u32,
+    symbol_index: u32,
+}
+
+impl InitFunc {
+    fn parse<I: Input>(offset: &mut u64, input: &I) -> Parsed<Self> {
+        Ok(Self {
+            priority: parser::leb128::u32(offset, input).context("init function priority")?,
+            symbol_index: parser::leb128::u32(offset, input).context("init function symbol")?,
+        })
+    }
+
+    /// Gets the priority of the function. Functions with lower priorities are called first.
+    #[inline]
+    pub fn priority(&self) -> u32 {
+        self.priority
+    }
+
+    /// Gets the index of the function's symbol in the [`SymbolTable`].
+    #[inline]
+    pub fn symbol_index(&self) -> u32 {
+        self.symbol_index
+    }
+}
+
+/// Represents the `WASM_INIT_FUNCS` subsection, which lists the [`InitFunc`]s of the module.
+#[derive(Clone, Copy)]
+pub struct InitFuncs<I: Input> {
+    functions: Vector<u64, I>,
+}
+
+subsection_vector!(InitFuncs, functions, "init functions subsection");
+
+impl<I: Input> InitFuncs<I> {
+    /// Parses the next [`InitFunc`].
+    pub fn parse(&mut self) -> Parsed<Option<InitFunc>> {
+        self.functions
+            .advance(|offset, input| InitFunc::parse(offset, input))
+            .transpose()
+            .context("within init functions subsection")
+    }
+}
+
+impl<I: Input> Iterator for InitFuncs<I> {
+    type Item = Parsed<InitFunc>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.parse().transpose()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.functions.size_hint()
+    }
+}
+
+impl<I: Input> core::iter::FusedIterator for InitFuncs<I> {}
+
+/// Indicates what kind of definition a [`ComdatSymbol`] refers to.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum ComdatSymbolKind {
+    /// A data segment.
+    Data,
+    /// A function.
+    Function,
+    /// A global.
+    Global,
+    /// A tag, introduced as part of the
+    /// [exception handling proposal](https://github.com/WebAssembly/exception-handling).
+    Tag,
+    /// A table.
+    Table,
+    /// A custom section.
+    Section,
+}
+
+/// A definition that is part of a [`Comdat`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ComdatSymbol {
+    kind: ComdatSymbolKind,
+    index: u32,
+}
+
+impl ComdatSymbol {
+    fn parse<I: Input>(offset: &mut u64, input: &I) -> Parsed<Self> {
+        let kind_offset = *offset;
+        let kind = match parser::one_byte_exact(offset, input).context("COMDAT symbol kind")? {
+            0 => ComdatSymbolKind::Data,
+            1 => ComdatSymbolKind::Function,
+            2 => ComdatSymbolKind::Global,
+            3 => ComdatSymbolKind::Tag,
+            4 => ComdatSymbolKind::Table,
+            5 => ComdatSymbolKind::Section,
+            bad => {
+                #[inline(never)]
+                #[cold]
+                fn bad_kind(offset: u64, kind: u8) -> parser::Error {
+                    parser::Error::new(parser::ErrorKind::BadComdatSymbolKind(kind))
+                        .with_location_context("COMDAT symbol", offset)
+                }
+
+                return Err(bad_kind(kind_offset, bad));
+            }
+        };
+
+        let index = parser::leb128::u32(offset, input).context("COMDAT symbol index")?;
+        Ok(Self { kind, index })
+    }
+
+    /// Gets the kind of definition the symbol refers to.
+    #[inline]
+    pub fn kind(&self) -> ComdatSymbolKind {
+        self.kind
+    }
+
+    /// Gets the index of the definition, which is a data segment index, a function, global, tag,
+    /// or table index, or a section index depending on the [`ComdatSymbolKind`].
+    #[inline]
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+}
+
+/// The [`ComdatSymbol`]s within a [`Comdat`].
+#[derive(Clone, Copy)]
+pub struct ComdatSymbols<I: Input> {
+    symbols: Vector<u64, I>,
+}
+
+impl<I: Input> ComdatSymbols<I> {
+    /// Parses the next [`ComdatSymbol`].
+    pub fn parse(&mut self) -> Parsed<Option<ComdatSymbol>> {
+        self.symbols
+            .advance(|offset, input| ComdatSymbol::parse(offset, input))
+            .transpose()
+            .context("within COMDAT")
+    }
+
+    /// Gets the expected remaining number of symbols that have yet to be parsed.
+    #[inline]
+    pub fn remaining_count(&self) -> u32 {
+        self.symbols.remaining_count()
+    }
+}
+
+impl<I: Input> Iterator for ComdatSymbols<I> {
+    type Item = Parsed<ComdatSymbol>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.parse().transpose()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.symbols.size_hint()
+    }
+}
+
+impl<I: Input> core::iter::FusedIterator for ComdatSymbols<I> {}
+
+impl<I: Input> Debug for ComdatSymbols<I> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_list()
+            .entries(ComdatSymbols {
+                symbols: self.symbols.borrow_input(),
+            })
+            .finish()
+    }
+}
+
+/// A COMDAT, which groups definitions that are either all included or all discarded by the linker
+/// when multiple object files define a COMDAT with the same name.
+#[derive(Clone, Copy)]
+pub struct Comdat<I: Input> {
+    name: Name<I>,
+    flags: u32,
+    symbols: Vector<u64, I>,
+}
+
+impl<'a, I: Input> Comdat<&'a I> {
+    fn parse(offset: &mut u64, input: &'a I) -> Parsed<Self> {
+        let name = parser::name::parse(offset, input).context("COMDAT name")?;
+        let flags = parser::leb128::u32(offset, input).context("COMDAT flags")?;
+        let symbols = Vector::parse(*offset, input).context("COMDAT symbol count")?;
+
+        // Skip over the symbols to get to the next COMDAT
+        let mut remaining = symbols;
+        while let Some(result) = remaining.advance(ComdatSymbol::parse) {
+            result?;
+        }
+
+        *offset = remaining.into_offset();
+        Ok(Self {
+            name,
+            flags,
+            symbols,
+        })
+    }
+}
+
+impl<I: Input> Comdat<I> {
+    /// Gets the name of the COMDAT.
+    #[inline]
+    pub fn name(&self) -> &Name<I> {
+        &self.name
+    }
+
+    /// Gets the flags of the COMDAT, which are currently always `0`.
+    #[inline]
+    pub fn flags(&self) -> u32 {
+        self.flags
+    }
+
+    /// Gets the definitions that are part of the COMDAT.
+    #[inline]
+    pub fn symbols(&self) -> ComdatSymbols<&I> {
+        ComdatSymbols {
+            symbols: self.symbols.borrow_input(),
+        }
+    }
+}
+
+impl<I: Input> HasInput<I> for Comdat<I> {
+    #[inline]
+    fn input(&self) -> &I {
+        self.name.input()
+    }
+}
+
+impl<'a, I: Input + 'a> BorrowInput<'a, I> for Comdat<I> {
+    type Borrowed = Comdat<&'a I>;
+
+    #[inline]
+    fn borrow_input(&'a self) -> Self::Borrowed {
+        Comdat {
+            name: self.name.borrow_input(),
+            flags: self.flags,
+            symbols: self.symbols.borrow_input(),
+        }
+    }
+}
+
+impl<'a, I: Clone + Input + 'a> CloneInput<'a, I> for Comdat<&'a I> {
+    type Cloned = Comdat<I>;
+
+    #[inline]
+    fn clone_input(&self) -> Self::Cloned {
+        Comdat {
+            name: self.name.clone_input(),
+            flags: self.flags,
+            symbols: self.symbols.clone_input(),
+        }
+    }
+}
+
+impl<I: Input> Debug for Comdat<I> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Comdat")
+            .field("name", &self.name)
+            .field("flags", &self.flags)
+            .field("symbols", &self.symbols())
+            .finish()
+    }
+}
+
+/// Represents the `WASM_COMDAT_INFO` subsection, which lists the [`Comdat`]s of the module.
+#[derive(Clone, Copy)]
+pub struct ComdatInfo<I: Input> {
+    comdats: Vector<u64, I>,
+}
+
+subsection_vector!(ComdatInfo, comdats, "COMDAT info subsection");
+
+impl<I: Input> ComdatInfo<I> {
+    /// Parses the next [`Comdat`].
+    pub fn parse(&mut self) -> Parsed<Option<Comdat<&I>>> {
+        self.comdats
+            .advance(Comdat::parse)
+            .transpose()
+            .context("within COMDAT info subsection")
+    }
+}
+
+impl<I: Clone + Input> Iterator for ComdatInfo<I> {
+    type Item = Parsed<Comdat<I>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.parse()
+            .map(|comdat| comdat.map(|comdat| comdat.clone_input()))
+            .transpose()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.comdats.size_hint()
+    }
+}
+
+impl<I: Clone + Input> core::iter::FusedIterator for ComdatInfo<I> {}
+
+/// The location of a defined data symbol, within a data segment.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct DataSymbolDefinition {
+    segment: index::DataIdx,
+    offset: u64,
+    size: u64,
+}
+
+impl DataSymbolDefinition {
+    /// Gets the index of the data segment containing the symbol.
+    #[inline]
+    pub fn segment(&self) -> index::DataIdx {
+        self.segment
+    }
+
+    /// Gets the offset of the symbol within the data segment.
+    #[inline]
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Gets the size of the symbol, in bytes.
+    #[inline]
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+}
+
+/// Indicates what kind of definition a [`SymbolInfo`] refers to.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum SymbolKind {
+    /// A function.
+    Function(index::FuncIdx),
+    /// Data within a data segment, which is `None` if the symbol is
+    /// [undefined](SymbolFlags::UNDEFINED).
+    Data(Option<DataSymbolDefinition>),
+    /// A global.
+    Global(index::GlobalIdx),
+    /// A section, typically a custom section containing debugging information.
+    Section(u32),
+    /// A tag, introduced as part of the
+    /// [exception handling proposal](https://github.com/WebAssembly/exception-handling).
+    Tag(index::TagIdx),
+    /// A table.
+    Table(index::TableIdx),
+}
+
+/// An entry in the [`SymbolTable`].
+#[derive(Clone, Copy)]
+pub struct SymbolInfo<I: Input> {
+    kind: SymbolKind,
+    flags: SymbolFlags,
+    name: Option<Name<I>>,
+}
+
+impl<'a, I: Input> SymbolInfo<&'a I> {
+    fn parse(offset: &mut u64, input: &'a I) -> Parsed<Self> {
+        let kind_offset = *offset;
+        let kind_byte = parser::one_byte_exact(offset, input).context("symbol kind")?;
+        let flags =
+            SymbolFlags::from_bits(parser::leb128::u32(offset, input).context("symbol flags")?);
+        let undefined = flags.contains(SymbolFlags::UNDEFINED);
+
+        let mut name = None;
+        let kind = match kind_byte {
+            0 | 2 | 4 | 5 => {
+                let index = parser::leb128::u32(offset, input).context("symbol index")?;
+                if !undefined || flags.contains(SymbolFlags::EXPLICIT_NAME) {
+                    name = Some(parser::name::parse(offset, input).context("symbol name")?);
+                }
+
+                match kind_byte {
+                    0 => SymbolKind::Function(index.into()),
+                    2 => SymbolKind::Global(index.into()),
+                    4 => SymbolKind::Tag(index.into()),
+                    _ => SymbolKind::Table(index.into()),
+                }
+            }
+            1 => {
+                name = Some(parser::name::parse(offset, input).context("data symbol name")?);
+                SymbolKind::Data(if undefined {
+                    None
+                } else {
+                    Some(DataSymbolDefinition {
+                        segment: crate::component::index(offset, input)
+                            .context("data symbol segment")?,
+                        offset: parser::leb128::u64(offset, input).context("data symbol offset")?,
+                        size: parser::leb128::u64(offset, input).context("data symbol size")?,
+                    })
+                })
+            }
+            3 => SymbolKind::Section(
+                parser::leb128::u32(offset, input).context("section symbol index")?,
+            ),
+            bad => {
+                #[inline(never)]
+                #[cold]
+                fn bad_kind(offset: u64, kind: u8) -> parser::Error {
+                    parser::Error::new(parser::ErrorKind::BadSymbolKind(kind))
+                        .with_location_context("symbol table entry", offset)
+                }
+
+                return Err(bad_kind(kind_offset, bad));
+            }
+        };
+
+        Ok(Self { kind, flags, name })
+    }
+}
+
+impl<I: Input> SymbolInfo<I> {
+    /// Gets the kind of definition the symbol refers to.
+    #[inline]
+    pub fn kind(&self) -> &SymbolKind {
+        &self.kind
+    }
+
+    /// Gets the flags describing the symbol.
+    #[inline]
+    pub fn flags(&self) -> SymbolFlags {
+        self.flags
+    }
+
+    /// Gets the name of the symbol.
+    ///
+    /// Returns `None` for section symbols, and for undefined symbols that take their name from
+    /// the import they refer to.
+    #[inline]
+    pub fn name(&self) -> Option<&Name<I>> {
+        self.name.as_ref()
+    }
+}
+
+impl<I: Input> Debug for SymbolInfo<I> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SymbolInfo")
+            .field("kind", &self.kind)
+            .field("flags", &self.flags)
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+/// Represents the `WASM_SYMBOL_TABLE` subsection, which lists the symbols referred to by
+/// relocations.
+#[derive(Clone, Copy)]
+pub struct SymbolTable<I: Input> {
+    symbols: Vector<u64, I>,
+}
+
+subsection_vector!(SymbolTable, symbols, "symbol table subsection");
+
+impl<I: Input> SymbolTable<I> {
+    /// Parses the next [`SymbolInfo`].
+    pub fn parse(&mut self) -> Parsed<Option<SymbolInfo<&I>>> {
+        self.symbols
+            .advance(SymbolInfo::parse)
+            .transpose()
+            .context("within symbol table subsection")
+    }
+}
+
+impl<I: Clone + Input> Iterator for SymbolTable<I> {
+    type Item = Parsed<SymbolInfo<I>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.parse()
+            .map(|symbol| {
+                symbol.map(|symbol| SymbolInfo {
+                    kind: symbol.kind,
+                    flags: symbol.flags,
+                    name: symbol.name.map(|name| name.clone_input()),
+                })
+            })
+            .transpose()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.symbols.size_hint()
+    }
+}
+
+impl<I: Clone + Input> core::iter::FusedIterator for SymbolTable<I> {}
+
+/// Represents a subsection within the [`LinkingSection`].
+#[derive(Clone, Copy)]
+#[non_exhaustive]
+pub enum LinkingSubsection<I: Input> {
+    /// The `WASM_SEGMENT_INFO` subsection, describing each data segment.
+    SegmentInfo(SegmentInfos<I>),
+    /// The `WASM_INIT_FUNCS` subsection, listing the initialization functions.
+    InitFuncs(InitFuncs<I>),
+    /// The `WASM_COMDAT_INFO` subsection, listing the COMDATs.
+    ComdatInfo(ComdatInfo<I>),
+    /// The `WASM_SYMBOL_TABLE` subsection, listing the symbols referred to by relocations.
+    SymbolTable(SymbolTable<I>),
+}
+
+/// Result type used when interpreting the contents of a [`LinkingSubsection`].
+pub type InterpretedLinkingSubsection<I> = Result<Parsed<LinkingSubsection<Window<I>>>, Section<I>>;
+
+impl<I: Input> LinkingSubsection<Window<I>> {
+    /// Attempts to interpret the contents of the given linking subsection.
+    ///
+    /// Returns `Err(_)` if the subsection's type is not recognized.
+    ///
+    /// Returns `Ok(Err(_))` if the subsection **was** recognized, but the start of its contents
+    /// could not be parsed.
+    pub fn interpret(section: Section<I>) -> InterpretedLinkingSubsection<I> {
+        match section.id() {
+            SEGMENT_INFO_ID => {
+                let contents = section.into_contents();
+                Ok(SegmentInfos::new(contents.base(), contents).map(Self::SegmentInfo))
+            }
+            INIT_FUNCS_ID => {
+                let contents = section.into_contents();
+                Ok(InitFuncs::new(contents.base(), contents).map(Self::InitFuncs))
+            }
+            COMDAT_INFO_ID => {
+                let contents = section.into_contents();
+                Ok(ComdatInfo::new(contents.base(), contents).map(Self::ComdatInfo))
+            }
+            SYMBOL_TABLE_ID => {
+                let contents = section.into_contents();
+                Ok(SymbolTable::new(contents.base(), contents).map(Self::SymbolTable))
+            }
+            _ => Err(section),
+        }
+    }
+
+    /// Attempts to interpret the contents of the given linking subsection, using the given
+    /// [`Strictness`] to handle bytes left over after the end of the subsection's contents.
+    ///
+    /// With [`Strictness::Strict`], the subsection is parsed in its entirety to check for
+    /// trailing bytes.
+    ///
+    /// See the documentation for [`LinkingSubsection::interpret()`] for more information.
+    pub fn interpret_with(
+        section: Section<I>,
+        strictness: Strictness,
+    ) -> InterpretedLinkingSubsection<I> {
+        let subsection = Self::interpret(section)?;
+        Ok(subsection.and_then(|subsection| match strictness {
+            Strictness::Lenient => Ok(subsection),
+            Strictness::Strict => match subsection.remnant_length()? {
+                0 => Ok(subsection),
+                remnant => {
+                    #[inline(never)]
+                    #[cold]
+                    fn trailing_bytes(remnant: u64) -> parser::Error {
+                        parser::Error::new(parser::ErrorKind::TrailingBytes(
+                            u32::try_from(remnant).unwrap_or(u32::MAX),
+                        ))
+                    }
+
+                    Err(trailing_bytes(remnant)).context("linking subsection")
+                }
+            },
+        }))
+    }
+
+    /// Gets the number of bytes left over after the end of the subsection's contents, which are
+    /// ignored when parsing.
+    ///
+    /// Any remaining entries in the subsection are parsed to determine where its contents end.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the remaining entries could not be parsed.
+    pub fn remnant_length(&self) -> Parsed<u64> {
+        let (window, end) = match self {
+            Self::SegmentInfo(segments) => (segments.input(), segments.borrow_input().finish()?),
+            Self::InitFuncs(functions) => (functions.input(), functions.borrow_input().finish()?),
+            Self::ComdatInfo(comdats) => (comdats.input(), comdats.borrow_input().finish()?),
+            Self::SymbolTable(symbols) => (symbols.input(), symbols.borrow_input().finish()?),
+        };
+
+        Ok((window.base() + window.length()).saturating_sub(end))
+    }
+}
+
+impl<I: Input> LinkingSubsection<I> {
+    /// Gets the byte used to identify the type of the subsection.
+    pub fn id(&self) -> u8 {
+        match self {
+            Self::SegmentInfo(_) => SEGMENT_INFO_ID,
+            Self::InitFuncs(_) => INIT_FUNCS_ID,
+            Self::ComdatInfo(_) => COMDAT_INFO_ID,
+            Self::SymbolTable(_) => SYMBOL_TABLE_ID,
+        }
+    }
+}
+
+impl<I: Input> HasInput<I> for LinkingSubsection<I> {
+    fn input(&self) -> &I {
+        match self {
+            Self::SegmentInfo(segments) => segments.input(),
+            Self::InitFuncs(functions) => functions.input(),
+            Self::ComdatInfo(comdats) => comdats.input(),
+            Self::SymbolTable(symbols) => symbols.input(),
+        }
+    }
+}
+
+impl<'a, I: Input + 'a> BorrowInput<'a, I> for LinkingSubsection<I> {
+    type Borrowed = LinkingSubsection<&'a I>;
+
+    fn borrow_input(&'a self) -> Self::Borrowed {
+        match self {
+            Self::SegmentInfo(segments) => LinkingSubsection::SegmentInfo(segments.borrow_input()),
+            Self::InitFuncs(functions) => LinkingSubsection::InitFuncs(functions.borrow_input()),
+            Self::ComdatInfo(comdats) => LinkingSubsection::ComdatInfo(comdats.borrow_input()),
+            Self::SymbolTable(symbols) => LinkingSubsection::SymbolTable(symbols.borrow_input()),
+        }
+    }
+}
+
+impl<'a, I: Clone + Input + 'a> CloneInput<'a, I> for LinkingSubsection<&'a I> {
+    type Cloned = LinkingSubsection<I>;
+
+    fn clone_input(&self) -> Self::Cloned {
+        match self {
+            Self::SegmentInfo(segments) => LinkingSubsection::SegmentInfo(segments.clone_input()),
+            Self::InitFuncs(functions) => LinkingSubsection::InitFuncs(functions.clone_input()),
+            Self::ComdatInfo(comdats) => LinkingSubsection::ComdatInfo(comdats.clone_input()),
+            Self::SymbolTable(symbols) => LinkingSubsection::SymbolTable(symbols.clone_input()),
+        }
+    }
+}
+
+impl<I: Input> Debug for LinkingSubsection<I> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::SegmentInfo(segments) => f.debug_tuple("SegmentInfo").field(segments).finish(),
+            Self::InitFuncs(functions) => f.debug_tuple("InitFuncs").field(functions).finish(),
+            Self::ComdatInfo(comdats) => f.debug_tuple("ComdatInfo").field(comdats).finish(),
+            Self::SymbolTable(symbols) => f.debug_tuple("SymbolTable").field(symbols).finish(),
+        }
+    }
+}
+
+/// Represents the
+/// [`linking` custom section](https://github.com/WebAssembly/tool-conventions/blob/main/Linking.md#linking-metadata-section),
+/// which contains the sequence of [`LinkingSubsection`]s describing the symbols and data
+/// segments of an object file.
+#[derive(Clone, Copy)]
+pub struct LinkingSection<I: Input> {
+    version: u32,
+    strictness: Strictness,
+    subsections: SectionSequence<I>,
+}
+
+fn parse_version<I: Input>(offset: &mut u64, input: &I) -> Parsed<u32> {
+    let version_offset = *offset;
+    let version = parser::leb128::u32(offset, input).context("linking section version")?;
+
+    if version != VERSION {
+        #[inline(never)]
+        #[cold]
+        fn unsupported_version(offset: u64, version: u32) -> parser::Error {
+            parser::Error::new(parser::ErrorKind::UnsupportedLinkingVersion(version))
+                .with_location_context("linking section", offset)
+        }
+
+        return Err(unsupported_version(version_offset, version));
+    }
+
+    Ok(version)
+}
+
+impl<I: Input> LinkingSection<I> {
+    /// Uses the given [`Input`] to read the contents of a `linking` section, starting at the
+    /// given `offset`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the version could not be parsed, or if it is not [`VERSION`].
+    pub fn new(mut offset: u64, input: I) -> Parsed<Self> {
+        let version = parse_version(&mut offset, &input)?;
+        Ok(Self {
+            version,
+            strictness: Strictness::Lenient,
+            subsections: SectionSequence::new(offset, input),
+        })
+    }
+
+    /// Sets how bytes left over after the end of each subsection's contents are handled.
+    ///
+    /// Defaults to [`Strictness::Lenient`].
+    #[inline]
+    pub fn with_strictness(mut self, strictness: Strictness) -> Self {
+        self.strictness = strictness;
+        self
+    }
+
+    /// Gets how bytes left over after the end of each subsection's contents are handled.
+    #[inline]
+    pub fn strictness(&self) -> Strictness {
+        self.strictness
+    }
+
+    /// Gets the version of the `linking` section.
+    #[inline]
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Consumes the [`LinkingSection`], returning the remaining subsections.
+    #[inline]
+    pub fn into_subsections(self) -> SectionSequence<I> {
+        self.subsections
+    }
+
+    /// Attempts to parse the next subsection, returning `None` if no more remain.
+    ///
+    /// If a subsection is not recognized, `Some(Err(_))` is returned.
+    ///
+    /// # Errors
+    ///
+    /// `Some(Ok(Err(_)))` is returned if a subsection could not be parsed.
+    pub fn parse(&mut self) -> Option<InterpretedLinkingSubsection<&I>> {
+        match self.subsections.parse().context("linking subsection") {
+            Ok(Some(section)) => Some(LinkingSubsection::interpret_with(section, self.strictness)),
+            Ok(None) => None,
+            Err(e) => Some(Ok(Err(e))),
+        }
+    }
+}
+
+impl<I: Input> LinkingSection<Window<I>> {
+    /// Attempts to interpret the contents of the given [`CustomSection`] as a `linking` section.
+    ///
+    /// Returns `Err(_)` if the name of the `section` is not
+    /// [`linking`](section_id::LINKING).
+    ///
+    /// Returns `Ok(Err(_))` if the version of the `linking` section could not be parsed or is
+    /// not supported.
+    pub fn interpret(section: CustomSection<I>) -> Result<Parsed<Self>, CustomSection<I>> {
+        match section_id::is_custom_name_recognized(section.name().borrow_input()) {
+            Some(section_id::LINKING) => {
+                let contents = section.into_contents();
+                Ok(Self::new(contents.base(), contents))
+            }
+            _ => Err(section),
+        }
+    }
+
+    /// Interprets the contents of a custom section named [`linking`](section_id::LINKING),
+    /// returning the `section` if its version could not be parsed or is not supported.
+    pub(crate) fn interpret_supported(
+        section: CustomSection<I>,
+        strictness: Strictness,
+    ) -> Result<Self, CustomSection<I>> {
+        let mut offset = section.contents().base();
+        match parse_version(&mut offset, section.contents()) {
+            Ok(version) => Ok(Self {
+                version,
+                strictness,
+                subsections: SectionSequence::new(offset, section.into_contents()),
+            }),
+            Err(_) => Err(section),
+        }
+    }
+}
+
+impl<I: Input> HasInput<I> for LinkingSection<I> {
+    #[inline]
+    fn input(&self) -> &I {
+        self.subsections.input()
+    }
+}
+
+impl<'a, I: Input + 'a> BorrowInput<'a, I> for LinkingSection<I> {
+    type Borrowed = LinkingSection<&'a I>;
+
+    #[inline]
+    fn borrow_input(&'a self) -> Self::Borrowed {
+        LinkingSection {
+            version: self.version,
+            strictness: self.strictness,
+            subsections: self.subsections.borrow_input(),
+        }
+    }
+}
+
+impl<'a, I: Clone + Input + 'a> CloneInput<'a, I> for LinkingSection<&'a I> {
+    type Cloned = LinkingSection<I>;
+
+    #[inline]
+    fn clone_input(&self) -> Self::Cloned {
+        LinkingSection {
+            version: self.version,
+            strictness: self.strictness,
+            subsections: self.subsections.clone_input(),
+        }
+    }
+}
+
+impl<I: Clone + Input> Iterator for LinkingSection<I> {
+    type Item = InterpretedLinkingSubsection<I>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.subsections.next()?.context("linking subsection") {
+            Ok(section) => Some(LinkingSubsection::interpret_with(section, self.strictness)),
+            Err(e) => Some(Ok(Err(e))),
+        }
+    }
+}
+
+impl<I: Clone + Input> core::iter::FusedIterator for LinkingSection<I> {}
+
+impl<I: Input> Debug for LinkingSection<I> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        struct Subsections<'a, I: Input>(LinkingSection<&'a I>);
+
+        impl<I: Input> Debug for Subsections<'_, I> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+                let mut list = f.debug_list();
+                for result in self.0 {
+                    match result {
+                        Ok(Ok(known)) => list.entry(&known),
+                        Ok(Err(e)) => list.entry(&e),
+                        Err(unknown) => list.entry(&unknown),
+                    };
+                }
+                list.finish()
+            }
+        }
+
+        f.debug_struct("LinkingSection")
+            .field("version", &self.version)
+            .field("strictness", &self.strictness)
+            .field("subsections", &Subsections(self.borrow_input()))
+            .finish()
+    }
+}
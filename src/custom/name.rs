@@ -3,6 +3,7 @@
 //! which associates UTF-8 [`Name`](parser::name::Name)s with definitions in a module.
 
 use crate::{
+    custom::Strictness,
     index,
     input::{BorrowInput, CloneInput, HasInput, Input, Window},
     parser::{self, AscendingOrder, ResultExt as _},
@@ -14,14 +15,26 @@ mod indirect_name_map;
 mod name_assoc;
 mod name_map;
 
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[doc(no_inline)]
+pub use crate::edit::NameSectionBuilder;
 pub use indirect_name_map::IndirectNameMap;
 pub use name_assoc::NameAssoc;
 pub use name_map::NameMap;
 
-const MODULE_NAME_ID: u8 = 0;
-const FUNCTION_NAME_ID: u8 = 1;
-const LOCAL_NAME_ID: u8 = 2;
-const TAG_NAME_ID: u8 = 11;
+pub(crate) const MODULE_NAME_ID: u8 = 0;
+pub(crate) const FUNCTION_NAME_ID: u8 = 1;
+pub(crate) const LOCAL_NAME_ID: u8 = 2;
+const LABEL_NAME_ID: u8 = 3;
+const TYPE_NAME_ID: u8 = 4;
+const TABLE_NAME_ID: u8 = 5;
+const MEMORY_NAME_ID: u8 = 6;
+const GLOBAL_NAME_ID: u8 = 7;
+const ELEM_NAME_ID: u8 = 8;
+const DATA_NAME_ID: u8 = 9;
+const FIELD_NAME_ID: u8 = 10;
+pub(crate) const TAG_NAME_ID: u8 = 11;
 
 /// Represents a
 /// [name subsection](https://webassembly.github.io/spec/core/appendix/custom.html#subsections)
@@ -43,6 +56,55 @@ pub enum NameSubsection<I: Input> {
     /// [*local name subsection*](https://webassembly.github.io/spec/core/appendix/custom.html#local-names)
     /// assigns a [`NameMap`] of local variable names for the functions within a WebAssembly module.
     LocalName(IndirectNameMap<index::FuncIdx, index::LocalIdx, u64, I>),
+    /// The *label name subsection* assigns a [`NameMap`] of label names for the functions within
+    /// a WebAssembly module.
+    ///
+    /// Labels are numbered in the order that the
+    /// [structured control instructions](https://webassembly.github.io/spec/core/syntax/instructions.html#syntax-instr-control)
+    /// introducing them appear within the function's code, rather than by their relative depth.
+    ///
+    /// Introduced as part of the [extended name section proposal](https://github.com/WebAssembly/extended-name-section/blob/main/proposals/extended-name-section/Overview.md).
+    LabelName(IndirectNameMap<index::FuncIdx, index::LabelIdx, u64, I>),
+    /// The *type name subsection* assigns names to the types in the
+    /// [*type section*](https://webassembly.github.io/spec/core/binary/modules.html#type-section).
+    ///
+    /// Introduced as part of the [extended name section proposal](https://github.com/WebAssembly/extended-name-section/blob/main/proposals/extended-name-section/Overview.md).
+    TypeName(NameMap<index::TypeIdx, u64, I>),
+    /// The *table name subsection* assigns names to the
+    /// [tables](https://webassembly.github.io/spec/core/syntax/modules.html#tables) of a
+    /// WebAssembly module.
+    ///
+    /// Introduced as part of the [extended name section proposal](https://github.com/WebAssembly/extended-name-section/blob/main/proposals/extended-name-section/Overview.md).
+    TableName(NameMap<index::TableIdx, u64, I>),
+    /// The *memory name subsection* assigns names to the
+    /// [memories](https://webassembly.github.io/spec/core/syntax/modules.html#memories) of a
+    /// WebAssembly module.
+    ///
+    /// Introduced as part of the [extended name section proposal](https://github.com/WebAssembly/extended-name-section/blob/main/proposals/extended-name-section/Overview.md).
+    MemoryName(NameMap<index::MemIdx, u64, I>),
+    /// The *global name subsection* assigns names to the
+    /// [globals](https://webassembly.github.io/spec/core/syntax/modules.html#globals) of a
+    /// WebAssembly module.
+    ///
+    /// Introduced as part of the [extended name section proposal](https://github.com/WebAssembly/extended-name-section/blob/main/proposals/extended-name-section/Overview.md).
+    GlobalName(NameMap<index::GlobalIdx, u64, I>),
+    /// The *element segment name subsection* assigns names to the
+    /// [element segments](https://webassembly.github.io/spec/core/syntax/modules.html#element-segments)
+    /// of a WebAssembly module.
+    ///
+    /// Introduced as part of the [extended name section proposal](https://github.com/WebAssembly/extended-name-section/blob/main/proposals/extended-name-section/Overview.md).
+    ElementName(NameMap<index::ElemIdx, u64, I>),
+    /// The *data segment name subsection* assigns names to the
+    /// [data segments](https://webassembly.github.io/spec/core/syntax/modules.html#data-segments)
+    /// of a WebAssembly module.
+    ///
+    /// Introduced as part of the [extended name section proposal](https://github.com/WebAssembly/extended-name-section/blob/main/proposals/extended-name-section/Overview.md).
+    DataName(NameMap<index::DataIdx, u64, I>),
+    /// The *field name subsection* assigns a [`NameMap`] of field names for the structure types
+    /// within a WebAssembly module.
+    ///
+    /// Introduced as part of the [garbage collection proposal](https://github.com/WebAssembly/gc).
+    FieldName(IndirectNameMap<index::TypeIdx, index::FieldIdx, u64, I>),
     /// The
     /// [*tag name subsection*](https://webassembly.github.io/exception-handling/core/appendix/custom.html#tag-names)
     /// assignes names to the
@@ -82,6 +144,38 @@ impl<I: Input> NameSubsection<Window<I>> {
                 let contents = section.into_contents();
                 Ok(IndirectNameMap::new(contents.base(), contents).map(Self::LocalName))
             }
+            LABEL_NAME_ID => {
+                let contents = section.into_contents();
+                Ok(IndirectNameMap::new(contents.base(), contents).map(Self::LabelName))
+            }
+            TYPE_NAME_ID => {
+                let contents = section.into_contents();
+                Ok(NameMap::new(contents.base(), contents).map(Self::TypeName))
+            }
+            TABLE_NAME_ID => {
+                let contents = section.into_contents();
+                Ok(NameMap::new(contents.base(), contents).map(Self::TableName))
+            }
+            MEMORY_NAME_ID => {
+                let contents = section.into_contents();
+                Ok(NameMap::new(contents.base(), contents).map(Self::MemoryName))
+            }
+            GLOBAL_NAME_ID => {
+                let contents = section.into_contents();
+                Ok(NameMap::new(contents.base(), contents).map(Self::GlobalName))
+            }
+            ELEM_NAME_ID => {
+                let contents = section.into_contents();
+                Ok(NameMap::new(contents.base(), contents).map(Self::ElementName))
+            }
+            DATA_NAME_ID => {
+                let contents = section.into_contents();
+                Ok(NameMap::new(contents.base(), contents).map(Self::DataName))
+            }
+            FIELD_NAME_ID => {
+                let contents = section.into_contents();
+                Ok(IndirectNameMap::new(contents.base(), contents).map(Self::FieldName))
+            }
             TAG_NAME_ID => {
                 let contents = section.into_contents();
                 Ok(NameMap::new(contents.base(), contents).map(Self::TagName))
@@ -90,6 +184,64 @@ impl<I: Input> NameSubsection<Window<I>> {
         }
     }
 
+    /// Attempts to interpret the contents of the given name subsection, using the given
+    /// [`Strictness`] to handle bytes left over after the end of the subsection's contents.
+    ///
+    /// With [`Strictness::Strict`], the subsection is parsed in its entirety to check for
+    /// trailing bytes.
+    ///
+    /// See the documentation for [`NameSubsection::interpret()`] for more information.
+    pub fn interpret_with(
+        section: Section<I>,
+        strictness: Strictness,
+    ) -> InterpretedNameSubsection<I> {
+        let subsection = Self::interpret(section)?;
+        Ok(subsection.and_then(|subsection| match strictness {
+            Strictness::Lenient => Ok(subsection),
+            Strictness::Strict => match subsection.remnant_length()? {
+                0 => Ok(subsection),
+                remnant => {
+                    #[inline(never)]
+                    #[cold]
+                    fn trailing_bytes(remnant: u64) -> parser::Error {
+                        parser::Error::new(parser::ErrorKind::TrailingBytes(
+                            u32::try_from(remnant).unwrap_or(u32::MAX),
+                        ))
+                    }
+
+                    Err(trailing_bytes(remnant)).context("name subsection")
+                }
+            },
+        }))
+    }
+
+    /// Gets the number of bytes left over after the end of the subsection's contents, which are
+    /// ignored when parsing.
+    ///
+    /// Any remaining entries in the subsection are parsed to determine where its contents end.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the remaining entries could not be parsed.
+    pub fn remnant_length(&self) -> parser::Parsed<u64> {
+        let (window, end) = match self {
+            Self::ModuleName(name) => (name.input(), name.offset() + name.length()),
+            Self::FunctionName(names) => (names.input(), names.borrow_input().finish()?),
+            Self::TagName(names) => (names.input(), names.borrow_input().finish()?),
+            Self::LocalName(names) => (names.input(), names.borrow_input().finish()?),
+            Self::LabelName(names) => (names.input(), names.borrow_input().finish()?),
+            Self::TypeName(names) => (names.input(), names.borrow_input().finish()?),
+            Self::TableName(names) => (names.input(), names.borrow_input().finish()?),
+            Self::MemoryName(names) => (names.input(), names.borrow_input().finish()?),
+            Self::GlobalName(names) => (names.input(), names.borrow_input().finish()?),
+            Self::ElementName(names) => (names.input(), names.borrow_input().finish()?),
+            Self::DataName(names) => (names.input(), names.borrow_input().finish()?),
+            Self::FieldName(names) => (names.input(), names.borrow_input().finish()?),
+        };
+
+        Ok((window.base() + window.length()).saturating_sub(end))
+    }
+
     /// Gets the byte
     /// [*id*](https://webassembly.github.io/spec/core/appendix/custom.html#subsections) associated
     /// with the name subsection.
@@ -98,17 +250,94 @@ impl<I: Input> NameSubsection<Window<I>> {
             Self::ModuleName(_) => MODULE_NAME_ID,
             Self::FunctionName(_) => FUNCTION_NAME_ID,
             Self::LocalName(_) => LOCAL_NAME_ID,
+            Self::LabelName(_) => LABEL_NAME_ID,
+            Self::TypeName(_) => TYPE_NAME_ID,
+            Self::TableName(_) => TABLE_NAME_ID,
+            Self::MemoryName(_) => MEMORY_NAME_ID,
+            Self::GlobalName(_) => GLOBAL_NAME_ID,
+            Self::ElementName(_) => ELEM_NAME_ID,
+            Self::DataName(_) => DATA_NAME_ID,
+            Self::FieldName(_) => FIELD_NAME_ID,
             Self::TagName(_) => TAG_NAME_ID,
         }
     }
 }
 
+impl<I: Input> HasInput<I> for NameSubsection<I> {
+    fn input(&self) -> &I {
+        match self {
+            Self::ModuleName(name) => name.input(),
+            Self::FunctionName(names) => names.input(),
+            Self::TagName(names) => names.input(),
+            Self::LocalName(names) => names.input(),
+            Self::LabelName(names) => names.input(),
+            Self::TypeName(names) => names.input(),
+            Self::TableName(names) => names.input(),
+            Self::MemoryName(names) => names.input(),
+            Self::GlobalName(names) => names.input(),
+            Self::ElementName(names) => names.input(),
+            Self::DataName(names) => names.input(),
+            Self::FieldName(names) => names.input(),
+        }
+    }
+}
+
+impl<'a, I: Input + 'a> BorrowInput<'a, I> for NameSubsection<I> {
+    type Borrowed = NameSubsection<&'a I>;
+
+    fn borrow_input(&'a self) -> Self::Borrowed {
+        match self {
+            Self::ModuleName(name) => NameSubsection::ModuleName(name.borrow_input()),
+            Self::FunctionName(names) => NameSubsection::FunctionName(names.borrow_input()),
+            Self::LocalName(names) => NameSubsection::LocalName(names.borrow_input()),
+            Self::LabelName(names) => NameSubsection::LabelName(names.borrow_input()),
+            Self::TypeName(names) => NameSubsection::TypeName(names.borrow_input()),
+            Self::TableName(names) => NameSubsection::TableName(names.borrow_input()),
+            Self::MemoryName(names) => NameSubsection::MemoryName(names.borrow_input()),
+            Self::GlobalName(names) => NameSubsection::GlobalName(names.borrow_input()),
+            Self::ElementName(names) => NameSubsection::ElementName(names.borrow_input()),
+            Self::DataName(names) => NameSubsection::DataName(names.borrow_input()),
+            Self::FieldName(names) => NameSubsection::FieldName(names.borrow_input()),
+            Self::TagName(names) => NameSubsection::TagName(names.borrow_input()),
+        }
+    }
+}
+
+impl<'a, I: Clone + Input + 'a> CloneInput<'a, I> for NameSubsection<&'a I> {
+    type Cloned = NameSubsection<I>;
+
+    fn clone_input(&self) -> Self::Cloned {
+        match self {
+            Self::ModuleName(name) => NameSubsection::ModuleName(name.clone_input()),
+            Self::FunctionName(names) => NameSubsection::FunctionName(names.clone_input()),
+            Self::LocalName(names) => NameSubsection::LocalName(names.clone_input()),
+            Self::LabelName(names) => NameSubsection::LabelName(names.clone_input()),
+            Self::TypeName(names) => NameSubsection::TypeName(names.clone_input()),
+            Self::TableName(names) => NameSubsection::TableName(names.clone_input()),
+            Self::MemoryName(names) => NameSubsection::MemoryName(names.clone_input()),
+            Self::GlobalName(names) => NameSubsection::GlobalName(names.clone_input()),
+            Self::ElementName(names) => NameSubsection::ElementName(names.clone_input()),
+            Self::DataName(names) => NameSubsection::DataName(names.clone_input()),
+            Self::FieldName(names) => NameSubsection::FieldName(names.clone_input()),
+            Self::TagName(names) => NameSubsection::TagName(names.clone_input()),
+        }
+    }
+}
+
 impl<I: Input> Debug for NameSubsection<I> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Self::ModuleName(name) => f.debug_tuple("ModuleName").field(name).finish(),
             Self::FunctionName(names) => f.debug_tuple("FunctionName").field(names).finish(),
             Self::LocalName(names) => f.debug_tuple("LocalName").field(names).finish(),
+            Self::LabelName(names) => f.debug_tuple("LabelName").field(names).finish(),
+            Self::TypeName(names) => f.debug_tuple("TypeName").field(names).finish(),
+            Self::TableName(names) => f.debug_tuple("TableName").field(names).finish(),
+            Self::MemoryName(names) => f.debug_tuple("MemoryName").field(names).finish(),
+            Self::GlobalName(names) => f.debug_tuple("GlobalName").field(names).finish(),
+            Self::ElementName(names) => f.debug_tuple("ElementName").field(names).finish(),
+            Self::DataName(names) => f.debug_tuple("DataName").field(names).finish(),
+            Self::FieldName(names) => f.debug_tuple("FieldName").field(names).finish(),
             Self::TagName(names) => f.debug_tuple("TagName").field(names).finish(),
         }
     }
@@ -122,6 +351,7 @@ impl<I: Input> Debug for NameSubsection<I> {
 pub struct NameSection<I: Input> {
     order: AscendingOrder<u8>,
     first: bool,
+    strictness: Strictness,
     sections: SectionSequence<I>,
 }
 
@@ -131,18 +361,34 @@ impl<I: Input> NameSection<I> {
         Self {
             order: AscendingOrder::new(),
             first: true,
+            strictness: Strictness::Lenient,
             sections,
         }
     }
 
+    /// Sets how bytes left over after the end of each subsection's contents are handled.
+    ///
+    /// Defaults to [`Strictness::Lenient`].
+    #[inline]
+    pub fn with_strictness(mut self, strictness: Strictness) -> Self {
+        self.strictness = strictness;
+        self
+    }
+
+    /// Gets how bytes left over after the end of each subsection's contents are handled.
+    #[inline]
+    pub fn strictness(&self) -> Strictness {
+        self.strictness
+    }
+
     /// Consumes the [`NameSection`], returning the remaining subsections.
     pub fn into_sections(self) -> SectionSequence<I> {
         self.sections
     }
 
-    fn parse_inner<'a, U: Input, F>(&'a mut self, f: F) -> Option<InterpretedNameSubsection<U>>
+    fn parse_inner<'a, U, F>(&'a mut self, f: F) -> Option<InterpretedNameSubsection<U>>
     where
-        U: 'a,
+        U: Input + 'a,
         F: FnOnce(Section<&'a I>) -> Section<U>,
     {
         match self.sections.parse().context("name subsection") {
@@ -153,7 +399,7 @@ impl<I: Input> NameSection<I> {
             {
                 Ok(_) => {
                     self.first = false;
-                    Some(NameSubsection::interpret(f(section)))
+                    Some(NameSubsection::interpret_with(f(section), self.strictness))
                 }
                 Err(e) => Some(Ok(Err(e))),
             },
@@ -190,6 +436,7 @@ impl<'a, I: Input + 'a> BorrowInput<'a, I> for NameSection<I> {
         NameSection {
             order: self.order,
             first: self.first,
+            strictness: self.strictness,
             sections: self.sections.borrow_input(),
         }
     }
@@ -203,6 +450,7 @@ impl<'a, I: Clone + Input + 'a> CloneInput<'a, I> for NameSection<&'a I> {
         NameSection {
             order: self.order,
             first: self.first,
+            strictness: self.strictness,
             sections: self.sections.clone_input(),
         }
     }
@@ -39,6 +39,16 @@ impl<K: Index, V: Index, O: Offset, I: Input> IndirectNameMap<K, V, O, I> {
         self.entries.remaining_count()
     }
 
+    /// Gets the number of pairs in the [`IndirectNameMap`] that have been parsed so far.
+    pub fn consumed_count(&self) -> u32 {
+        self.entries.consumed_count()
+    }
+
+    /// Gets the number of pairs that were declared in the [`IndirectNameMap`].
+    pub fn declared_count(&self) -> u32 {
+        self.entries.declared_count()
+    }
+
     /// Parses the next primary index and [`NameMap`] pair.
     pub fn parse<T, F>(&mut self, f: F) -> Parsed<Option<T>>
     where
@@ -63,6 +73,12 @@ impl<K: Index, V: Index, O: Offset, I: Input> IndirectNameMap<K, V, O, I> {
             .transpose()
             .context("could not parse entry in indirect name map")
     }
+
+    /// Parses all remaining entries in the [`IndirectNameMap`].
+    pub fn finish(mut self) -> Parsed<O> {
+        while self.parse(|_, _| Ok(()))?.is_some() {}
+        Ok(self.entries.into_offset())
+    }
 }
 
 impl<K: Index, V: Index, O: Offset, I: Input> HasInput<I> for IndirectNameMap<K, V, O, I> {
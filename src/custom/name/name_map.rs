@@ -36,6 +36,18 @@ impl<N: Index, O: Offset, I: Input> NameMap<N, O, I> {
         self.entries.remaining_count()
     }
 
+    /// Gets the number of entries in the [`NameMap`] that have been parsed so far.
+    #[inline]
+    pub fn consumed_count(&self) -> u32 {
+        self.entries.consumed_count()
+    }
+
+    /// Gets the number of entries that were declared in the [`NameMap`].
+    #[inline]
+    pub fn declared_count(&self) -> u32 {
+        self.entries.declared_count()
+    }
+
     /// Parses the next entry in the [`NameMap`].
     pub fn parse(&mut self) -> Parsed<Option<NameAssoc<N, &I>>> {
         self.entries
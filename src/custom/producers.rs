@@ -0,0 +1,373 @@
+//! Types to parse the
+//! [`producers` custom section](https://github.com/WebAssembly/tool-conventions/blob/main/ProducersSection.md)
+//! described in the [WebAssembly tool conventions](https://github.com/WebAssembly/tool-conventions),
+//! which records the languages, tools, and SDKs used to produce a module.
+
+use crate::{
+    custom::{CustomSection, Strictness},
+    input::{BorrowInput, CloneInput, HasInput, Input, Window},
+    parser::{self, name::Name, Parsed, ResultExt as _, Vector},
+    sections::id as section_id,
+};
+use core::fmt::{Debug, Formatter};
+
+/// The name of the field listing the source languages of a module.
+pub const LANGUAGE: &str = "language";
+
+/// The name of the field listing the tools that processed a module, such as compilers and
+/// optimizers.
+pub const PROCESSED_BY: &str = "processed-by";
+
+/// The name of the field listing the SDKs used to produce a module.
+pub const SDK: &str = "sdk";
+
+/// A name and version pair, describing a language, tool, or SDK in a [`ProducerField`].
+#[derive(Clone, Copy)]
+pub struct VersionedName<I: Input> {
+    name: Name<I>,
+    version: Name<I>,
+}
+
+impl<'a, I: Input> VersionedName<&'a I> {
+    fn parse(offset: &mut u64, input: &'a I) -> Parsed<Self> {
+        let name = parser::name::parse(offset, input).context("producer name")?;
+        let version = parser::name::parse(offset, input).context("producer version")?;
+        Ok(Self { name, version })
+    }
+}
+
+impl<I: Input> VersionedName<I> {
+    /// Gets the name of the language, tool, or SDK.
+    #[inline]
+    pub fn name(&self) -> &Name<I> {
+        &self.name
+    }
+
+    /// Gets the version string, which may be empty.
+    #[inline]
+    pub fn version(&self) -> &Name<I> {
+        &self.version
+    }
+}
+
+impl<I: Input> HasInput<I> for VersionedName<I> {
+    #[inline]
+    fn input(&self) -> &I {
+        self.name.input()
+    }
+}
+
+impl<'a, I: Input + 'a> BorrowInput<'a, I> for VersionedName<I> {
+    type Borrowed = VersionedName<&'a I>;
+
+    #[inline]
+    fn borrow_input(&'a self) -> Self::Borrowed {
+        VersionedName {
+            name: self.name.borrow_input(),
+            version: self.version.borrow_input(),
+        }
+    }
+}
+
+impl<'a, I: Clone + Input + 'a> CloneInput<'a, I> for VersionedName<&'a I> {
+    type Cloned = VersionedName<I>;
+
+    #[inline]
+    fn clone_input(&self) -> Self::Cloned {
+        VersionedName {
+            name: self.name.clone_input(),
+            version: self.version.clone_input(),
+        }
+    }
+}
+
+impl<I: Input> Debug for VersionedName<I> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("VersionedName")
+            .field("name", &self.name)
+            .field("version", &self.version)
+            .finish()
+    }
+}
+
+/// The [`VersionedName`]s within a [`ProducerField`].
+#[derive(Clone, Copy)]
+pub struct ProducerValues<I: Input> {
+    values: Vector<u64, I>,
+}
+
+impl<I: Input> ProducerValues<I> {
+    /// Parses the next [`VersionedName`].
+    pub fn parse(&mut self) -> Parsed<Option<VersionedName<&I>>> {
+        self.values
+            .advance(VersionedName::parse)
+            .transpose()
+            .context("within producers field")
+    }
+
+    /// Gets the expected remaining number of values that have yet to be parsed.
+    #[inline]
+    pub fn remaining_count(&self) -> u32 {
+        self.values.remaining_count()
+    }
+}
+
+impl<I: Clone + Input> Iterator for ProducerValues<I> {
+    type Item = Parsed<VersionedName<I>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.parse()
+            .map(|value| value.map(|value| value.clone_input()))
+            .transpose()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.values.size_hint()
+    }
+}
+
+impl<I: Clone + Input> core::iter::FusedIterator for ProducerValues<I> {}
+
+impl<I: Input> Debug for ProducerValues<I> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_list()
+            .entries(ProducerValues {
+                values: self.values.borrow_input(),
+            })
+            .finish()
+    }
+}
+
+/// A field of the `producers` section, such as [`LANGUAGE`], [`PROCESSED_BY`], or [`SDK`],
+/// along with its [`VersionedName`]s.
+#[derive(Clone, Copy)]
+pub struct ProducerField<I: Input> {
+    name: Name<I>,
+    values: Vector<u64, I>,
+}
+
+impl<'a, I: Input> ProducerField<&'a I> {
+    fn parse(offset: &mut u64, input: &'a I) -> Parsed<Self> {
+        let name = parser::name::parse(offset, input).context("producers field name")?;
+        let values = Vector::parse(*offset, input).context("producers field value count")?;
+
+        // Skip over the values to get to the next field
+        let mut remaining = values;
+        while let Some(result) = remaining.advance(VersionedName::parse) {
+            result?;
+        }
+
+        *offset = remaining.into_offset();
+        Ok(Self { name, values })
+    }
+}
+
+impl<I: Input> ProducerField<I> {
+    /// Gets the name of the field.
+    #[inline]
+    pub fn name(&self) -> &Name<I> {
+        &self.name
+    }
+
+    /// Gets the names and versions listed in the field.
+    #[inline]
+    pub fn values(&self) -> ProducerValues<&I> {
+        ProducerValues {
+            values: self.values.borrow_input(),
+        }
+    }
+}
+
+impl<I: Input> HasInput<I> for ProducerField<I> {
+    #[inline]
+    fn input(&self) -> &I {
+        self.name.input()
+    }
+}
+
+impl<'a, I: Input + 'a> BorrowInput<'a, I> for ProducerField<I> {
+    type Borrowed = ProducerField<&'a I>;
+
+    #[inline]
+    fn borrow_input(&'a self) -> Self::Borrowed {
+        ProducerField {
+            name: self.name.borrow_input(),
+            values: self.values.borrow_input(),
+        }
+    }
+}
+
+impl<'a, I: Clone + Input + 'a> CloneInput<'a, I> for ProducerField<&'a I> {
+    type Cloned = ProducerField<I>;
+
+    #[inline]
+    fn clone_input(&self) -> Self::Cloned {
+        ProducerField {
+            name: self.name.clone_input(),
+            values: self.values.clone_input(),
+        }
+    }
+}
+
+impl<I: Input> Debug for ProducerField<I> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ProducerField")
+            .field("name", &self.name)
+            .field("values", &self.values())
+            .finish()
+    }
+}
+
+/// Represents the
+/// [`producers` custom section](https://github.com/WebAssembly/tool-conventions/blob/main/ProducersSection.md),
+/// which contains a [`ProducerField`] for each kind of tool used to produce the module.
+#[derive(Clone, Copy)]
+pub struct ProducersSection<I: Input> {
+    fields: Vector<u64, I>,
+}
+
+impl<I: Input> ProducersSection<I> {
+    /// Uses the given [`Input`] to read the contents of a `producers` section, starting at the
+    /// given `offset`.
+    pub fn new(offset: u64, input: I) -> Parsed<Self> {
+        Ok(Self {
+            fields: Vector::parse(offset, input).context("producers field count")?,
+        })
+    }
+
+    /// Gets the expected remaining number of fields that have yet to be parsed.
+    #[inline]
+    pub fn remaining_count(&self) -> u32 {
+        self.fields.remaining_count()
+    }
+
+    /// Parses the next [`ProducerField`].
+    pub fn parse(&mut self) -> Parsed<Option<ProducerField<&I>>> {
+        self.fields
+            .advance(ProducerField::parse)
+            .transpose()
+            .context("within producers section")
+    }
+
+    /// Parses all remaining fields, returning the offset to the first byte after the last field.
+    pub fn finish(mut self) -> Parsed<u64> {
+        while self.parse()?.is_some() {}
+        Ok(self.fields.into_offset())
+    }
+}
+
+impl<I: Input> ProducersSection<Window<I>> {
+    /// Attempts to interpret the contents of the given [`CustomSection`] as a `producers`
+    /// section.
+    ///
+    /// Returns `Err(_)` if the name of the `section` is not
+    /// [`producers`](section_id::PRODUCERS).
+    ///
+    /// Returns `Ok(Err(_))` if the number of fields could not be parsed.
+    pub fn interpret(section: CustomSection<I>) -> Result<Parsed<Self>, CustomSection<I>> {
+        match section_id::is_custom_name_recognized(section.name().borrow_input()) {
+            Some(section_id::PRODUCERS) => {
+                let contents = section.into_contents();
+                Ok(Self::new(contents.base(), contents))
+            }
+            _ => Err(section),
+        }
+    }
+
+    /// Attempts to interpret the contents of the given [`CustomSection`] as a `producers`
+    /// section, using the given [`Strictness`] to handle bytes left over after the last field.
+    ///
+    /// With [`Strictness::Strict`], the section is parsed in its entirety to check for trailing
+    /// bytes.
+    ///
+    /// See the documentation for [`ProducersSection::interpret()`] for more information.
+    pub fn interpret_with(
+        section: CustomSection<I>,
+        strictness: Strictness,
+    ) -> Result<Parsed<Self>, CustomSection<I>> {
+        let producers = Self::interpret(section)?;
+        Ok(producers.and_then(|producers| match strictness {
+            Strictness::Lenient => Ok(producers),
+            Strictness::Strict => match producers.remnant_length()? {
+                0 => Ok(producers),
+                remnant => {
+                    #[inline(never)]
+                    #[cold]
+                    fn trailing_bytes(remnant: u64) -> parser::Error {
+                        parser::Error::new(parser::ErrorKind::TrailingBytes(
+                            u32::try_from(remnant).unwrap_or(u32::MAX),
+                        ))
+                    }
+
+                    Err(trailing_bytes(remnant)).context("producers section")
+                }
+            },
+        }))
+    }
+
+    /// Gets the number of bytes left over after the last field, which are ignored when parsing.
+    ///
+    /// Any remaining fields are parsed to determine where the contents of the section end.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the remaining fields could not be parsed.
+    pub fn remnant_length(&self) -> Parsed<u64> {
+        let window = self.input();
+        let end = self.borrow_input().finish()?;
+        Ok((window.base() + window.length()).saturating_sub(end))
+    }
+}
+
+impl<I: Input> HasInput<I> for ProducersSection<I> {
+    #[inline]
+    fn input(&self) -> &I {
+        self.fields.input()
+    }
+}
+
+impl<'a, I: Input + 'a> BorrowInput<'a, I> for ProducersSection<I> {
+    type Borrowed = ProducersSection<&'a I>;
+
+    #[inline]
+    fn borrow_input(&'a self) -> Self::Borrowed {
+        ProducersSection {
+            fields: self.fields.borrow_input(),
+        }
+    }
+}
+
+impl<'a, I: Clone + Input + 'a> CloneInput<'a, I> for ProducersSection<&'a I> {
+    type Cloned = ProducersSection<I>;
+
+    #[inline]
+    fn clone_input(&self) -> Self::Cloned {
+        ProducersSection {
+            fields: self.fields.clone_input(),
+        }
+    }
+}
+
+impl<I: Clone + Input> Iterator for ProducersSection<I> {
+    type Item = Parsed<ProducerField<I>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.parse()
+            .map(|field| field.map(|field| field.clone_input()))
+            .transpose()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.fields.size_hint()
+    }
+}
+
+impl<I: Clone + Input> core::iter::FusedIterator for ProducersSection<I> {}
+
+impl<I: Input> Debug for ProducersSection<I> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.borrow_input()).finish()
+    }
+}
@@ -0,0 +1,394 @@
+//! Types to parse the
+//! [relocation sections](https://github.com/WebAssembly/tool-conventions/blob/main/Linking.md#relocation-sections)
+//! described in the [WebAssembly tool conventions](https://github.com/WebAssembly/tool-conventions)
+//! for static linking.
+
+use crate::{
+    custom::{CustomSection, Strictness},
+    input::{BorrowInput, CloneInput, HasInput, Input, Window},
+    parser::{self, Parsed, ResultExt as _, Vector},
+};
+use core::fmt::{Debug, Formatter};
+
+/// The prefix used in the names of relocation sections, such as `reloc.CODE`.
+pub const NAME_PREFIX: &str = "reloc.";
+
+macro_rules! relocation_types {
+    ($(
+        $(#[$meta:meta])*
+        $name:ident = $value:literal $(with $addend:ident)?;
+    )*) => {
+        /// Describes how the value at the location of a [`RelocationEntry`] is encoded and what
+        /// kind of symbol it refers to.
+        #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+        #[allow(non_camel_case_types)]
+        #[non_exhaustive]
+        #[repr(u8)]
+        pub enum RelocationType {
+            $(
+                $(#[$meta])*
+                $name = $value,
+            )*
+        }
+
+        impl RelocationType {
+            /// Gets the name of the relocation type, as used in the tool conventions.
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(Self::$name => stringify!($name),)*
+                }
+            }
+
+            /// Returns `true` if relocation entries of this type contain an addend.
+            pub fn has_addend(self) -> bool {
+                match self {
+                    $(Self::$name => relocation_types!(@addend $($addend)?),)*
+                }
+            }
+        }
+
+        impl TryFrom<u8> for RelocationType {
+            type Error = u8;
+
+            fn try_from(value: u8) -> Result<Self, u8> {
+                match value {
+                    $($value => Ok(Self::$name),)*
+                    _ => Err(value),
+                }
+            }
+        }
+    };
+    (@addend addend) => { true };
+    (@addend) => { false };
+}
+
+relocation_types! {
+    /// A function index encoded as a 5-byte *LEB128*, used in **call** instructions.
+    R_WASM_FUNCTION_INDEX_LEB = 0;
+    /// A function table index encoded as a 5-byte signed *LEB128*.
+    R_WASM_TABLE_INDEX_SLEB = 1;
+    /// A function table index encoded as a 4-byte little-endian integer.
+    R_WASM_TABLE_INDEX_I32 = 2;
+    /// A linear memory address encoded as a 5-byte *LEB128*, used in load and store instructions.
+    R_WASM_MEMORY_ADDR_LEB = 3 with addend;
+    /// A linear memory address encoded as a 5-byte signed *LEB128*.
+    R_WASM_MEMORY_ADDR_SLEB = 4 with addend;
+    /// A linear memory address encoded as a 4-byte little-endian integer.
+    R_WASM_MEMORY_ADDR_I32 = 5 with addend;
+    /// A type index encoded as a 5-byte *LEB128*.
+    R_WASM_TYPE_INDEX_LEB = 6;
+    /// A global index encoded as a 5-byte *LEB128*.
+    R_WASM_GLOBAL_INDEX_LEB = 7;
+    /// A byte offset within a function body encoded as a 4-byte little-endian integer.
+    R_WASM_FUNCTION_OFFSET_I32 = 8 with addend;
+    /// A byte offset from the start of a section encoded as a 4-byte little-endian integer.
+    R_WASM_SECTION_OFFSET_I32 = 9 with addend;
+    /// A tag index encoded as a 5-byte *LEB128*.
+    R_WASM_TAG_INDEX_LEB = 10;
+    /// A memory address relative to the `__memory_base` global, encoded as a 5-byte signed
+    /// *LEB128*.
+    R_WASM_MEMORY_ADDR_REL_SLEB = 11 with addend;
+    /// A function table index relative to the `__table_base` global, encoded as a 5-byte signed
+    /// *LEB128*.
+    R_WASM_TABLE_INDEX_REL_SLEB = 12;
+    /// A global index encoded as a 4-byte little-endian integer.
+    R_WASM_GLOBAL_INDEX_I32 = 13;
+    /// A 64-bit linear memory address encoded as a 10-byte *LEB128*.
+    R_WASM_MEMORY_ADDR_LEB64 = 14 with addend;
+    /// A 64-bit linear memory address encoded as a 10-byte signed *LEB128*.
+    R_WASM_MEMORY_ADDR_SLEB64 = 15 with addend;
+    /// A 64-bit linear memory address encoded as an 8-byte little-endian integer.
+    R_WASM_MEMORY_ADDR_I64 = 16 with addend;
+    /// A 64-bit memory address relative to the `__memory_base` global, encoded as a 10-byte
+    /// signed *LEB128*.
+    R_WASM_MEMORY_ADDR_REL_SLEB64 = 17 with addend;
+    /// A 64-bit function table index encoded as a 10-byte signed *LEB128*.
+    R_WASM_TABLE_INDEX_SLEB64 = 18;
+    /// A 64-bit function table index encoded as an 8-byte little-endian integer.
+    R_WASM_TABLE_INDEX_I64 = 19;
+    /// A table number encoded as a 5-byte *LEB128*.
+    R_WASM_TABLE_NUMBER_LEB = 20;
+    /// A memory address relative to the `__tls_base` global, encoded as a 5-byte signed *LEB128*.
+    R_WASM_MEMORY_ADDR_TLS_SLEB = 21 with addend;
+    /// A byte offset within a function body encoded as an 8-byte little-endian integer.
+    R_WASM_FUNCTION_OFFSET_I64 = 22 with addend;
+    /// A memory address relative to the relocation site, encoded as a 4-byte little-endian
+    /// integer.
+    R_WASM_MEMORY_ADDR_LOCREL_I32 = 23 with addend;
+    /// A 64-bit function table index relative to the `__table_base` global, encoded as a 10-byte
+    /// signed *LEB128*.
+    R_WASM_TABLE_INDEX_REL_SLEB64 = 24;
+    /// A 64-bit memory address relative to the `__tls_base` global, encoded as a 10-byte signed
+    /// *LEB128*.
+    R_WASM_MEMORY_ADDR_TLS_SLEB64 = 25 with addend;
+    /// A function index encoded as a 4-byte little-endian integer.
+    R_WASM_FUNCTION_INDEX_I32 = 26;
+}
+
+impl core::fmt::Display for RelocationType {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A
+/// [relocation entry](https://github.com/WebAssembly/tool-conventions/blob/main/Linking.md#relocation-sections),
+/// describing a location within a section that refers to a symbol.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct RelocationEntry {
+    ty: RelocationType,
+    offset: u32,
+    index: u32,
+    addend: i64,
+}
+
+impl RelocationEntry {
+    fn parse<I: Input>(offset: &mut u64, input: &I) -> Parsed<Self> {
+        let type_offset = *offset;
+        let ty = parser::one_byte_exact(offset, input).context("relocation type")?;
+        let ty = RelocationType::try_from(ty).map_err(|bad| {
+            #[inline(never)]
+            #[cold]
+            fn bad_type(offset: u64, ty: u8) -> parser::Error {
+                parser::Error::new(parser::ErrorKind::BadRelocationType(ty))
+                    .with_location_context("relocation entry", offset)
+            }
+
+            bad_type(type_offset, bad)
+        })?;
+
+        let location = parser::leb128::u32(offset, input).context("relocation offset")?;
+        let index = parser::leb128::u32(offset, input).context("relocation index")?;
+        let addend = if ty.has_addend() {
+            parser::leb128::s64(offset, input).context("relocation addend")?
+        } else {
+            0
+        };
+
+        Ok(Self {
+            ty,
+            offset: location,
+            index,
+            addend,
+        })
+    }
+
+    /// Gets the type of the relocation.
+    #[inline]
+    pub fn relocation_type(&self) -> RelocationType {
+        self.ty
+    }
+
+    /// Gets the offset of the value to relocate, relative to the start of the contents of the
+    /// target section.
+    #[inline]
+    pub fn offset(&self) -> u32 {
+        self.offset
+    }
+
+    /// Gets the index of the symbol, or in the case of [`RelocationType::R_WASM_TYPE_INDEX_LEB`],
+    /// the type index.
+    #[inline]
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// Gets the addend to add to the address, or `0` if the [`RelocationType`] does not have one.
+    #[inline]
+    pub fn addend(&self) -> i64 {
+        self.addend
+    }
+}
+
+/// Represents a
+/// [relocation section](https://github.com/WebAssembly/tool-conventions/blob/main/Linking.md#relocation-sections),
+/// which lists the [`RelocationEntry`]s applied to another section in the module.
+///
+/// Relocation sections are custom sections whose names start with [`NAME_PREFIX`].
+#[derive(Clone, Copy)]
+pub struct RelocationSection<I: Input> {
+    section_index: u32,
+    entries: Vector<u64, I>,
+}
+
+impl<I: Input> RelocationSection<I> {
+    /// Uses the given [`Input`] to read the contents of a relocation section, starting at the
+    /// given `offset`.
+    pub fn new(mut offset: u64, input: I) -> Parsed<Self> {
+        let section_index =
+            parser::leb128::u32(&mut offset, &input).context("relocation target section index")?;
+
+        Ok(Self {
+            section_index,
+            entries: Vector::parse(offset, input).context("at start of relocation section")?,
+        })
+    }
+
+    /// Gets the index of the section that the relocations apply to.
+    ///
+    /// Note that section indices include custom sections.
+    #[inline]
+    pub fn section_index(&self) -> u32 {
+        self.section_index
+    }
+
+    /// Parses the next [`RelocationEntry`].
+    pub fn parse(&mut self) -> Parsed<Option<RelocationEntry>> {
+        self.entries
+            .advance(|offset, input| RelocationEntry::parse(offset, input))
+            .transpose()
+            .context("within relocation section")
+    }
+
+    /// Gets the expected remaining number of entries in the relocation section.
+    #[inline]
+    pub fn remaining_count(&self) -> u32 {
+        self.entries.remaining_count()
+    }
+
+    /// Gets the number of entries in the relocation section that have been parsed so far.
+    #[inline]
+    pub fn consumed_count(&self) -> u32 {
+        self.entries.consumed_count()
+    }
+
+    /// Gets the number of entries that were declared in the relocation section.
+    #[inline]
+    pub fn declared_count(&self) -> u32 {
+        self.entries.declared_count()
+    }
+
+    /// Parses all remaining entries, returning the offset to the first byte after the last
+    /// entry.
+    pub fn finish(mut self) -> Parsed<u64> {
+        while self.parse()?.is_some() {}
+        Ok(self.entries.into_offset())
+    }
+}
+
+impl<I: Input> RelocationSection<Window<I>> {
+    /// Attempts to interpret the contents of the given [`CustomSection`] as a relocation section.
+    ///
+    /// Returns `Err(_)` if the name of the `section` does not start with [`NAME_PREFIX`].
+    ///
+    /// Returns `Ok(Err(_))` if the start of the relocation section could not be parsed.
+    pub fn interpret(section: CustomSection<I>) -> Result<Parsed<Self>, CustomSection<I>> {
+        let mut buffer = [0u8; NAME_PREFIX.len()];
+        match section.name().copy_to_slice(&mut buffer) {
+            Ok(prefix) if prefix == NAME_PREFIX.as_bytes() => {
+                let contents = section.into_contents();
+                Ok(Self::new(contents.base(), contents))
+            }
+            _ => Err(section),
+        }
+    }
+
+    /// Attempts to interpret the contents of the given [`CustomSection`] as a relocation section,
+    /// using the given [`Strictness`] to handle bytes left over after the last entry.
+    ///
+    /// With [`Strictness::Strict`], the section is parsed in its entirety to check for trailing
+    /// bytes.
+    ///
+    /// See the documentation for [`RelocationSection::interpret()`] for more information.
+    pub fn interpret_with(
+        section: CustomSection<I>,
+        strictness: Strictness,
+    ) -> Result<Parsed<Self>, CustomSection<I>> {
+        let relocations = Self::interpret(section)?;
+        Ok(relocations.and_then(|relocations| match strictness {
+            Strictness::Lenient => Ok(relocations),
+            Strictness::Strict => match relocations.remnant_length()? {
+                0 => Ok(relocations),
+                remnant => {
+                    #[inline(never)]
+                    #[cold]
+                    fn trailing_bytes(remnant: u64) -> parser::Error {
+                        parser::Error::new(parser::ErrorKind::TrailingBytes(
+                            u32::try_from(remnant).unwrap_or(u32::MAX),
+                        ))
+                    }
+
+                    Err(trailing_bytes(remnant)).context("relocation section")
+                }
+            },
+        }))
+    }
+
+    /// Gets the number of bytes left over after the last entry, which are ignored when parsing.
+    ///
+    /// Any remaining entries are parsed to determine where the contents of the section end.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the remaining entries could not be parsed.
+    pub fn remnant_length(&self) -> Parsed<u64> {
+        let window = self.input();
+        let end = self.borrow_input().finish()?;
+        Ok((window.base() + window.length()).saturating_sub(end))
+    }
+}
+
+impl<I: Input> HasInput<I> for RelocationSection<I> {
+    #[inline]
+    fn input(&self) -> &I {
+        self.entries.input()
+    }
+}
+
+impl<'a, I: Input + 'a> BorrowInput<'a, I> for RelocationSection<I> {
+    type Borrowed = RelocationSection<&'a I>;
+
+    #[inline]
+    fn borrow_input(&'a self) -> Self::Borrowed {
+        RelocationSection {
+            section_index: self.section_index,
+            entries: self.entries.borrow_input(),
+        }
+    }
+}
+
+impl<'a, I: Clone + Input + 'a> CloneInput<'a, I> for RelocationSection<&'a I> {
+    type Cloned = RelocationSection<I>;
+
+    #[inline]
+    fn clone_input(&self) -> Self::Cloned {
+        RelocationSection {
+            section_index: self.section_index,
+            entries: self.entries.clone_input(),
+        }
+    }
+}
+
+impl<I: Input> Iterator for RelocationSection<I> {
+    type Item = Parsed<RelocationEntry>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.parse().transpose()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl<I: Input> core::iter::FusedIterator for RelocationSection<I> {}
+
+impl<I: Input> Debug for RelocationSection<I> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        struct Entries<'a, I: Input>(RelocationSection<&'a I>);
+
+        impl<I: Input> Debug for Entries<'_, I> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+                f.debug_list().entries(self.0).finish()
+            }
+        }
+
+        f.debug_struct("RelocationSection")
+            .field("section_index", &self.section_index)
+            .field("entries", &Entries(self.borrow_input()))
+            .finish()
+    }
+}
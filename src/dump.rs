@@ -0,0 +1,486 @@
+//! Formatters that describe the sections of a WebAssembly module in a plain text format, similar
+//! to the output of [`wasm-objdump`](https://webassembly.github.io/wabt/doc/wasm-objdump.1.html).
+//!
+//! Unlike the [WebAssembly text format](crate::text), these describe the layout of the module's
+//! binary, such as the offsets and sizes of its sections, rather than its contents.
+//!
+//! # Example
+//!
+//! ```
+//! let wasm = wat::parse_str(r#"(module
+//!     (func (export "f") (param i32) (result i32)
+//!         local.get 0))"#).unwrap();
+//!
+//! let sections = wasmiter::parse_module_sections(wasm.as_slice())?;
+//! let headers = wasmiter::dump::headers(&sections).to_string();
+//! assert!(headers.starts_with("     Type start=0x0000000a end=0x00000010 (size=0x00000006) count: 1\n"));
+//!
+//! let details = wasmiter::dump::details(&sections).to_string();
+//! assert!(details.contains("Export[1]:\n - func[0] -> \"f\"\n"));
+//! assert!(details.contains("Code[1]:\n - func[0] size=4\n"));
+//! # Ok::<(), wasmiter::parser::Error>(())
+//! ```
+
+use crate::{
+    component::{DataMode, ElementInit, ElementMode, ExportKind, ImportKind, KnownSection, Tag},
+    custom::CustomSection,
+    input::{BorrowInput as _, Input},
+    parser::Parsed,
+    sections::{id as section_id, Section, SectionSequence},
+    types,
+    wat::Writer,
+};
+
+fn write_dump<I: Input>(
+    sections: &SectionSequence<I>,
+    f: &mut core::fmt::Formatter<'_>,
+    write_section: fn(Section<&I>, &mut DefinitionCounts, &mut Writer) -> Parsed<()>,
+) -> core::fmt::Result {
+    let mut w = Writer::new(f);
+    let mut counts = DefinitionCounts::default();
+    for result in sections.borrow_input() {
+        if let Err(e) = result.and_then(|section| write_section(section, &mut counts, &mut w)) {
+            write!(w, "\nerror: {e}\n");
+            break;
+        }
+    }
+    w.finish()
+}
+
+/// Describes the location and number of entries of each section in a WebAssembly module, with
+/// one line per section.
+///
+/// Returned by the [`headers`] function.
+pub struct Headers<'a, I: Input> {
+    sections: &'a SectionSequence<I>,
+}
+
+/// Returns a [`Display`](core::fmt::Display) implementation that writes the offset, size, and
+/// number of entries of each section.
+///
+/// Custom sections are described by their name instead of an entry count.
+#[inline]
+pub fn headers<I: Input>(sections: &SectionSequence<I>) -> Headers<'_, I> {
+    Headers { sections }
+}
+
+fn write_header<I: Input>(
+    section: Section<&I>,
+    _: &mut DefinitionCounts,
+    w: &mut Writer,
+) -> Parsed<()> {
+    let start = section.contents().base();
+    let length = section.length();
+    write!(
+        w,
+        "{:>9} start={start:#010x} end={:#010x} (size={length:#010x})",
+        section_id::name(section.id()).unwrap_or("Unknown"),
+        start + length,
+    );
+
+    let section = match CustomSection::try_from_section(section) {
+        Ok(custom) => {
+            writeln!(w, " {:?}", custom?.name());
+            return Ok(());
+        }
+        Err(section) => section,
+    };
+
+    let id = section.id();
+    let known = match KnownSection::interpret(section) {
+        Ok(known) => known?,
+        Err(_) => {
+            writeln!(w, " id: {id}");
+            return Ok(());
+        }
+    };
+
+    let count = match known {
+        KnownSection::Type(types) => types.declared_count(),
+        KnownSection::Import(imports) => imports.declared_count(),
+        KnownSection::Function(functions) => functions.declared_count(),
+        KnownSection::Table(tables) => tables.declared_count(),
+        KnownSection::Memory(memories) => memories.declared_count(),
+        KnownSection::Global(globals) => globals.declared_count(),
+        KnownSection::Export(exports) => exports.declared_count(),
+        KnownSection::Start(start) => {
+            writeln!(w, " start: {}", u32::from(start));
+            return Ok(());
+        }
+        KnownSection::Element(elements) => elements.declared_count(),
+        KnownSection::Code(code) => code.declared_count(),
+        KnownSection::Data(data) => data.declared_count(),
+        KnownSection::DataCount(count) => count,
+        KnownSection::Tag(tags) => tags.declared_count(),
+    };
+
+    writeln!(w, " count: {count}");
+    Ok(())
+}
+
+impl<I: Input> core::fmt::Display for Headers<'_, I> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write_dump(self.sections, f, write_header)
+    }
+}
+
+impl<I: Input> core::fmt::Debug for Headers<'_, I> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Headers")
+            .field("sections", self.sections)
+            .finish()
+    }
+}
+
+/// Describes the entries within each section of a WebAssembly module, such as the signature of
+/// each function and the size of each function body.
+///
+/// Returned by the [`details`] function.
+pub struct Details<'a, I: Input> {
+    sections: &'a SectionSequence<I>,
+}
+
+/// Returns a [`Display`](core::fmt::Display) implementation that lists the entries of each
+/// section.
+///
+/// Definitions are listed with their index within their index space, taking into account the
+/// definitions introduced by imports. Instructions, such as those in function bodies or in the
+/// offset expressions of active segments, are not written.
+#[inline]
+pub fn details<I: Input>(sections: &SectionSequence<I>) -> Details<'_, I> {
+    Details { sections }
+}
+
+/// Keeps track of the number of definitions in each index space, so that entries are listed with
+/// the correct indices.
+#[derive(Default)]
+struct DefinitionCounts {
+    functions: u32,
+    bodies: u32,
+    tables: u32,
+    memories: u32,
+    globals: u32,
+    tags: u32,
+}
+
+impl DefinitionCounts {
+    #[inline]
+    fn next(count: &mut u32) -> u32 {
+        let index = *count;
+        *count = count.saturating_add(1);
+        index
+    }
+}
+
+fn write_val_types(
+    types: impl IntoIterator<Item = Parsed<types::ValType>>,
+    w: &mut Writer,
+) -> Parsed<()> {
+    for (i, result) in types.into_iter().enumerate() {
+        if i > 0 {
+            w.write_str(", ");
+        }
+        write!(w, "{}", result?);
+    }
+    Ok(())
+}
+
+fn write_limits(limits: &types::Limits, w: &mut Writer) {
+    write!(w, "initial={}", limits.minimum());
+    if let Some(maximum) = limits.maximum() {
+        write!(w, " max={maximum}");
+    }
+    if limits.index_type() == types::IdxType::I64 {
+        w.write_str(" i64");
+    }
+}
+
+fn write_table_type(table: &types::TableType, w: &mut Writer) {
+    write!(w, "type={} ", table.element_type());
+    write_limits(table.limits(), w);
+}
+
+fn write_mem_type(memory: &types::MemType, w: &mut Writer) {
+    w.write_str("pages: ");
+    write_limits(memory, w);
+    if memory.share() == types::Sharing::Shared {
+        w.write_str(" shared");
+    }
+}
+
+fn write_global_type(global: types::GlobalType, w: &mut Writer) {
+    write!(
+        w,
+        "{} mutable={}",
+        global.value_type(),
+        u8::from(global.mutability() == types::GlobalMutability::Variable)
+    );
+}
+
+fn write_details<I: Input>(
+    section: Section<&I>,
+    counts: &mut DefinitionCounts,
+    w: &mut Writer,
+) -> Parsed<()> {
+    let section = match CustomSection::try_from_section(section) {
+        Ok(custom) => {
+            writeln!(w, "Custom:\n - name: {:?}", custom?.name());
+            return Ok(());
+        }
+        Err(section) => section,
+    };
+
+    let id = section.id();
+    let length = section.length();
+    let known = match KnownSection::interpret(section) {
+        Ok(known) => known?,
+        Err(_) => {
+            writeln!(w, "Unknown:\n - id={id} size={length}");
+            return Ok(());
+        }
+    };
+
+    match known {
+        KnownSection::Type(mut types) => {
+            writeln!(w, "Type[{}]:", types.declared_count());
+            let mut w = w;
+            for index in 0u32.. {
+                let result = types.parse(
+                    move |parameters| {
+                        write!(w, " - type[{index}] (");
+                        write_val_types(parameters, w)?;
+                        w.write_str(") -> ");
+                        Ok(w)
+                    },
+                    |w, results| {
+                        match results.remaining_count() {
+                            0 => w.write_str("nil"),
+                            1 => write_val_types(results, w)?,
+                            _ => {
+                                w.write_char('(');
+                                write_val_types(results, w)?;
+                                w.write_char(')');
+                            }
+                        }
+                        writeln!(w);
+                        Ok(w)
+                    },
+                )?;
+
+                match result {
+                    Some(next) => w = next,
+                    None => break,
+                }
+            }
+        }
+        KnownSection::Import(mut imports) => {
+            writeln!(w, "Import[{}]:", imports.declared_count());
+            while let Some(import) = imports.parse()? {
+                w.write_str(" - ");
+                match import.kind() {
+                    ImportKind::Function(signature) => write!(
+                        w,
+                        "func[{}] sig={}",
+                        DefinitionCounts::next(&mut counts.functions),
+                        u32::from(*signature)
+                    ),
+                    ImportKind::Table(table) => {
+                        write!(w, "table[{}] ", DefinitionCounts::next(&mut counts.tables));
+                        write_table_type(table, w);
+                    }
+                    ImportKind::Memory(memory) => {
+                        write!(
+                            w,
+                            "memory[{}] ",
+                            DefinitionCounts::next(&mut counts.memories)
+                        );
+                        write_mem_type(memory, w);
+                    }
+                    ImportKind::Global(global) => {
+                        write!(
+                            w,
+                            "global[{}] ",
+                            DefinitionCounts::next(&mut counts.globals)
+                        );
+                        write_global_type(*global, w);
+                    }
+                    ImportKind::Tag(Tag::Exception(signature)) => write!(
+                        w,
+                        "tag[{}] sig={}",
+                        DefinitionCounts::next(&mut counts.tags),
+                        u32::from(*signature)
+                    ),
+                }
+                writeln!(w, " <- {}.{}", import.module(), import.name());
+            }
+        }
+        KnownSection::Function(functions) => {
+            writeln!(w, "Function[{}]:", functions.declared_count());
+            counts.bodies = counts.functions;
+            for result in functions {
+                writeln!(
+                    w,
+                    " - func[{}] sig={}",
+                    DefinitionCounts::next(&mut counts.functions),
+                    u32::from(result?)
+                );
+            }
+        }
+        KnownSection::Table(tables) => {
+            writeln!(w, "Table[{}]:", tables.declared_count());
+            for result in tables {
+                write!(
+                    w,
+                    " - table[{}] ",
+                    DefinitionCounts::next(&mut counts.tables)
+                );
+                write_table_type(&result?, w);
+                writeln!(w);
+            }
+        }
+        KnownSection::Memory(memories) => {
+            writeln!(w, "Memory[{}]:", memories.declared_count());
+            for result in memories {
+                write!(
+                    w,
+                    " - memory[{}] ",
+                    DefinitionCounts::next(&mut counts.memories)
+                );
+                write_mem_type(&result?, w);
+                writeln!(w);
+            }
+        }
+        KnownSection::Global(mut globals) => {
+            writeln!(w, "Global[{}]:", globals.declared_count());
+            while let Some(global) = globals.parse(|global, _| Ok(global))? {
+                write!(
+                    w,
+                    " - global[{}] ",
+                    DefinitionCounts::next(&mut counts.globals)
+                );
+                write_global_type(global, w);
+                writeln!(w);
+            }
+        }
+        KnownSection::Export(mut exports) => {
+            writeln!(w, "Export[{}]:", exports.declared_count());
+            while let Some(export) = exports.parse()? {
+                let (kind, index) = match export.kind() {
+                    ExportKind::Function(index) => ("func", u32::from(*index)),
+                    ExportKind::Table(index) => ("table", u32::from(*index)),
+                    ExportKind::Memory(index) => ("memory", u32::from(*index)),
+                    ExportKind::Global(index) => ("global", u32::from(*index)),
+                    ExportKind::Tag(index) => ("tag", u32::from(*index)),
+                };
+                writeln!(w, " - {kind}[{index}] -> {:?}", export.name());
+            }
+        }
+        KnownSection::Start(start) => {
+            writeln!(w, "Start:\n - start function: {}", u32::from(start));
+        }
+        KnownSection::Element(mut elements) => {
+            writeln!(w, "Elem[{}]:", elements.declared_count());
+            for index in 0u32.. {
+                let result = elements.parse(
+                    |mode| {
+                        Ok(match mode {
+                            ElementMode::Passive => None,
+                            ElementMode::Declarative => Some(None),
+                            ElementMode::Active(table, _) => Some(Some(u32::from(*table))),
+                        })
+                    },
+                    |mode, init| {
+                        let count = match init {
+                            ElementInit::Functions(functions) => functions.remaining_count(),
+                            ElementInit::Expressions(_, expressions) => {
+                                expressions.remaining_count()
+                            }
+                        };
+                        Ok((mode, count))
+                    },
+                )?;
+
+                let (mode, count) = match result {
+                    Some((mode, count)) => (mode, count),
+                    None => break,
+                };
+
+                write!(w, " - segment[{index}] ");
+                match mode {
+                    None => w.write_str("passive"),
+                    Some(None) => w.write_str("declarative"),
+                    Some(Some(table)) => write!(w, "active table={table}"),
+                }
+                writeln!(w, " count={count}");
+            }
+        }
+        KnownSection::Code(mut code) => {
+            writeln!(w, "Code[{}]:", code.declared_count());
+            while let Some(body) = code.parse()? {
+                writeln!(
+                    w,
+                    " - func[{}] size={}",
+                    DefinitionCounts::next(&mut counts.bodies),
+                    body.content().length()
+                );
+            }
+        }
+        KnownSection::Data(mut data) => {
+            writeln!(w, "Data[{}]:", data.declared_count());
+            for index in 0u32.. {
+                let result = data.parse(
+                    |mode| {
+                        Ok(match mode {
+                            DataMode::Passive => None,
+                            DataMode::Active(memory, _) => Some(u32::from(*memory)),
+                        })
+                    },
+                    |memory, contents| Ok((memory, contents.length())),
+                )?;
+
+                let (memory, size) = match result {
+                    Some((memory, size)) => (memory, size),
+                    None => break,
+                };
+
+                write!(w, " - segment[{index}] ");
+                match memory {
+                    None => w.write_str("passive"),
+                    Some(memory) => write!(w, "active memory={memory}"),
+                }
+                writeln!(w, " size={size}");
+            }
+        }
+        KnownSection::DataCount(count) => {
+            writeln!(w, "DataCount:\n - data count: {count}");
+        }
+        KnownSection::Tag(tags) => {
+            writeln!(w, "Tag[{}]:", tags.declared_count());
+            for result in tags {
+                let Tag::Exception(signature) = result?;
+                writeln!(
+                    w,
+                    " - tag[{}] sig={}",
+                    DefinitionCounts::next(&mut counts.tags),
+                    u32::from(signature)
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+impl<I: Input> core::fmt::Display for Details<'_, I> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write_dump(self.sections, f, write_details)
+    }
+}
+
+impl<I: Input> core::fmt::Debug for Details<'_, I> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Details")
+            .field("sections", self.sections)
+            .finish()
+    }
+}
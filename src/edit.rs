@@ -0,0 +1,17 @@
+//! Functions for modifying and combining WebAssembly modules.
+
+mod custom_section;
+mod merge;
+mod name_section;
+mod rename;
+#[cfg(feature = "std")]
+mod transform;
+
+pub use custom_section::{custom_section_size, write_custom_section};
+pub use merge::merge;
+pub use name_section::NameSectionBuilder;
+pub use rename::{rename, RenameMap};
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+pub use transform::{insert_custom_section, strip_custom_sections, transform, SectionAction};
@@ -0,0 +1,50 @@
+use crate::{encode, sections::id as section_id};
+use alloc::vec::Vec;
+
+fn contents_size(name: &str, payload_length: u64) -> u64 {
+    let name_length = name.len() as u64;
+    encode::u64_length(name_length)
+        .saturating_add(name_length)
+        .saturating_add(payload_length)
+}
+
+/// Calculates the size, in bytes, of a
+/// [custom section](https://webassembly.github.io/spec/core/binary/modules.html#custom-section)
+/// with the given `name` and a payload of `payload_length` bytes, including the section *id* and
+/// size.
+///
+/// This can be used to reserve space before calling [`write_custom_section`].
+pub fn custom_section_size(name: &str, payload_length: u64) -> u64 {
+    let contents = contents_size(name, payload_length);
+    encode::u64_length(contents)
+        .saturating_add(contents)
+        .saturating_add(1)
+}
+
+/// Appends a
+/// [custom section](https://webassembly.github.io/spec/core/binary/modules.html#custom-section)
+/// with the given `name` and `payload` to the end of a module.
+///
+/// # Example
+///
+/// ```
+/// use wasmiter::edit;
+///
+/// let mut module = b"\0asm\x01\0\0\0".to_vec();
+/// edit::write_custom_section(&mut module, "hello", &[1, 2, 3]);
+/// assert_eq!(module.len() as u64, 8 + edit::custom_section_size("hello", 3));
+///
+/// let sections = wasmiter::parse_module_sections(module.as_slice())?;
+/// let section = sections.into_iter().next().unwrap()?;
+/// let custom = wasmiter::custom::CustomSection::try_from_section(section).unwrap()?;
+/// assert!(custom.name().try_eq_str("hello")?);
+/// # Ok::<(), wasmiter::parser::Error>(())
+/// ```
+pub fn write_custom_section(out: &mut Vec<u8>, name: &str, payload: &[u8]) {
+    let contents = contents_size(name, payload.len() as u64);
+    out.reserve(usize::try_from(custom_section_size(name, payload.len() as u64)).unwrap_or(0));
+    out.push(section_id::CUSTOM);
+    encode::u64(out, contents);
+    encode::bytes(out, name.as_bytes());
+    out.extend_from_slice(payload);
+}
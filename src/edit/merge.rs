@@ -0,0 +1,1153 @@
+#[cfg(feature = "exceptions")]
+use crate::instruction_set::CatchClause;
+use crate::{
+    component::{
+        self, DataMode, ElementInit, ElementMode, ExportKind, ImportKind, KnownSection, Tag,
+    },
+    encode,
+    index::{DataIdx, ElemIdx, TypeIdx},
+    input::{BorrowInput as _, HasInput as _, Input, Window},
+    instruction_set::{Instruction, InstructionSequence, MemArg},
+    parser::{self, leb128, Context, Error, ErrorKind, Offset, Parsed},
+    sections::{id as section_id, SectionSequence},
+    types::{BlockType, GlobalType, HeapType, Limits, RefType, TableType, ValType},
+};
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+
+#[inline(never)]
+#[cold]
+pub(super) fn conflict(reason: &'static str) -> Error {
+    Error::new(ErrorKind::MergeConflict(reason))
+}
+
+#[inline(never)]
+#[cold]
+fn index_out_of_bounds(space: &'static str, index: u32) -> Error {
+    Error::new(ErrorKind::InvalidFormat).with_context(Context::from_closure(move |f| {
+        write!(f, "{space} index {index} is out of bounds")
+    }))
+}
+
+/// Adds to the size of an index space or the number of entries in a section, which must not
+/// exceed [`u32::MAX`] in the merged module.
+fn add_count(total: u32, count: u32) -> Parsed<u32> {
+    total
+        .checked_add(count)
+        .ok_or_else(|| conflict("merged module has too many definitions"))
+}
+
+fn lookup(indices: &[u32], space: &'static str, index: u32) -> Parsed<u32> {
+    indices
+        .get(crate::int::u32_to_usize(index))
+        .copied()
+        .ok_or_else(|| index_out_of_bounds(space, index))
+}
+
+const SPACE_COUNT: usize = 5;
+
+/// An index space that is shared between imports and definitions.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(super) enum Space {
+    Function,
+    Table,
+    Memory,
+    Global,
+    Tag,
+}
+
+impl Space {
+    const ALL: [Self; SPACE_COUNT] = [
+        Self::Function,
+        Self::Table,
+        Self::Memory,
+        Self::Global,
+        Self::Tag,
+    ];
+
+    pub(super) fn of_import(kind: &ImportKind) -> Self {
+        match kind {
+            ImportKind::Function(_) => Self::Function,
+            ImportKind::Table(_) => Self::Table,
+            ImportKind::Memory(_) => Self::Memory,
+            ImportKind::Global(_) => Self::Global,
+            ImportKind::Tag(_) => Self::Tag,
+        }
+    }
+
+    pub(super) fn of_export(kind: &ExportKind) -> (Self, u32) {
+        match kind {
+            ExportKind::Function(index) => (Self::Function, index.to_u32()),
+            ExportKind::Table(index) => (Self::Table, index.to_u32()),
+            ExportKind::Memory(index) => (Self::Memory, index.to_u32()),
+            ExportKind::Global(index) => (Self::Global, index.to_u32()),
+            ExportKind::Tag(index) => (Self::Tag, index.to_u32()),
+        }
+    }
+
+    /// The reason given when an import is resolved to a definition of a different type.
+    fn type_mismatch(self) -> &'static str {
+        match self {
+            Self::Function => "function import resolved to function of different type",
+            Self::Table => "table import resolved to table of different type",
+            Self::Memory => "memory import resolved to memory of different type",
+            Self::Global => "global import resolved to global of different type",
+            Self::Tag => "tag import resolved to tag of different type",
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Function => "function",
+            Self::Table => "table",
+            Self::Memory => "memory",
+            Self::Global => "global",
+            Self::Tag => "tag",
+        }
+    }
+
+    /// The byte used to indicate the kind of an import or export.
+    pub(super) fn kind_byte(self) -> u8 {
+        self as u8
+    }
+}
+
+enum Import {
+    /// The import is kept in the merged module, at the given index.
+    Unresolved(u32),
+    /// The import refers to a definition exported by another module.
+    Resolved { module: usize, index: u32 },
+}
+
+/// Deduplicates function types, using their encoded form as the key.
+#[derive(Default)]
+struct TypeTable {
+    indices: BTreeMap<Vec<u8>, u32>,
+    types: Vec<Vec<u8>>,
+}
+
+impl TypeTable {
+    fn insert(&mut self, encoded: Vec<u8>) -> u32 {
+        if let Some(index) = self.indices.get(&encoded) {
+            return *index;
+        }
+
+        let index = u32::try_from(self.types.len()).unwrap_or(u32::MAX);
+        self.types.push(encoded.clone());
+        self.indices.insert(encoded, index);
+        index
+    }
+}
+
+#[derive(Default)]
+struct ModuleInfo {
+    /// Maps the module's type indices to indices in the merged *type section*.
+    types: Vec<u32>,
+    /// The merged type index of every function in the module, including imports.
+    function_types: Vec<u32>,
+    /// The types of every table, memory, and global in the module, including imports.
+    table_types: Vec<TableType>,
+    memory_types: Vec<Limits>,
+    global_types: Vec<GlobalType>,
+    /// The merged type index of every tag in the module, including imports.
+    tag_types: Vec<u32>,
+    imports: [Vec<Import>; SPACE_COUNT],
+    /// The names of imports, along with their position in their index space.
+    import_names: Vec<(Vec<u8>, Space, usize)>,
+    definitions: [u32; SPACE_COUNT],
+    exports: Vec<(Vec<u8>, Space, u32)>,
+    start: Option<u32>,
+    element_count: u32,
+    data_count: u32,
+    has_data_count: bool,
+}
+
+impl ModuleInfo {
+    fn type_index(&self, index: TypeIdx) -> Parsed<u32> {
+        lookup(&self.types, "type", index.to_u32())
+    }
+
+    fn define(&mut self, space: Space) -> Parsed<()> {
+        let count = &mut self.definitions[space as usize];
+        *count = add_count(*count, 1)?;
+        Ok(())
+    }
+}
+
+fn encode_result_type<O: Offset, I: Input>(
+    out: &mut Vec<u8>,
+    types: &mut component::ResultType<O, I>,
+) -> Parsed<()> {
+    encode::u32(out, types.remaining_count());
+    for result in types {
+        let value_type = result?;
+        if let Some(HeapType::Index(_)) = value_type.try_to_ref_type().map(RefType::heap_type) {
+            // Function types are deduplicated by their encoding, which would require remapping
+            return Err(conflict(
+                "function types cannot contain typed references to types",
+            ));
+        }
+
+        encode::val_type(out, value_type);
+    }
+    Ok(())
+}
+
+fn collect<I: Input>(
+    sections: &SectionSequence<I>,
+    type_table: &mut TypeTable,
+) -> Parsed<ModuleInfo> {
+    let mut info = ModuleInfo::default();
+
+    for result in sections.borrow_input() {
+        let known = match KnownSection::interpret(result?) {
+            Ok(known) => known?,
+            Err(_) => continue,
+        };
+
+        match known {
+            KnownSection::Type(mut types) => loop {
+                let mut encoded = alloc::vec![0x60];
+                let buffer = &mut encoded;
+                let result = types.parse(
+                    move |parameters| {
+                        encode_result_type(buffer, parameters)?;
+                        Ok(buffer)
+                    },
+                    |buffer, results| {
+                        encode_result_type(buffer, results)?;
+                        Ok(())
+                    },
+                )?;
+
+                if result.is_none() {
+                    break;
+                }
+
+                info.types.push(type_table.insert(encoded));
+            },
+            KnownSection::Import(mut imports) => {
+                while let Some(import) = imports.parse()? {
+                    let space = Space::of_import(import.kind());
+                    match import.kind() {
+                        ImportKind::Function(ty) => {
+                            let merged = info.type_index(*ty)?;
+                            info.function_types.push(merged);
+                        }
+                        ImportKind::Table(table) => info.table_types.push(*table),
+                        ImportKind::Memory(memory) => info.memory_types.push(*memory),
+                        ImportKind::Global(global) => info.global_types.push(*global),
+                        ImportKind::Tag(Tag::Exception(ty)) => {
+                            let merged = info.type_index(*ty)?;
+                            info.tag_types.push(merged);
+                        }
+                    }
+
+                    let imports = &mut info.imports[space as usize];
+                    info.import_names
+                        .push((import.name().into_bytes()?, space, imports.len()));
+                    imports.push(Import::Unresolved(0));
+                }
+            }
+            KnownSection::Function(functions) => {
+                for result in functions {
+                    let merged = info.type_index(result?)?;
+                    info.function_types.push(merged);
+                    info.define(Space::Function)?;
+                }
+            }
+            // Definitions are counted as they are parsed, since the declared counts are untrusted
+            KnownSection::Table(tables) => {
+                for result in tables {
+                    info.table_types.push(result?);
+                    info.define(Space::Table)?;
+                }
+            }
+            KnownSection::Memory(memories) => {
+                for result in memories {
+                    info.memory_types.push(result?);
+                    info.define(Space::Memory)?;
+                }
+            }
+            KnownSection::Global(mut globals) => {
+                while let Some(global_type) = globals.parse(|global_type, _| Ok(global_type))? {
+                    info.global_types.push(global_type);
+                    info.define(Space::Global)?;
+                }
+            }
+            KnownSection::Tag(tags) => {
+                for result in tags {
+                    let Tag::Exception(ty) = result?;
+                    let merged = info.type_index(ty)?;
+                    info.tag_types.push(merged);
+                    info.define(Space::Tag)?;
+                }
+            }
+            KnownSection::Export(mut exports) => {
+                while let Some(export) = exports.parse()? {
+                    let (space, index) = Space::of_export(export.kind());
+                    info.exports
+                        .push((export.name().into_bytes()?, space, index));
+                }
+            }
+            KnownSection::Start(start) => info.start = Some(start.to_u32()),
+            KnownSection::Element(elements) => {
+                info.element_count = add_count(info.element_count, elements.remaining_count())?
+            }
+            KnownSection::Data(datas) => {
+                info.data_count = add_count(info.data_count, datas.remaining_count())?
+            }
+            KnownSection::DataCount(_) => info.has_data_count = true,
+            KnownSection::Code(_) => (),
+        }
+    }
+
+    Ok(info)
+}
+
+/// Maps the indices of a module to indices in the merged module.
+struct Remap<'a> {
+    types: &'a [u32],
+    spaces: [Vec<u32>; SPACE_COUNT],
+    element_base: u32,
+    data_base: u32,
+}
+
+impl Remap<'_> {
+    fn type_index(&self, index: TypeIdx) -> Parsed<u32> {
+        lookup(self.types, "type", index.to_u32())
+    }
+
+    fn index<N: Into<u32>>(&self, space: Space, index: N) -> Parsed<u32> {
+        lookup(&self.spaces[space as usize], space.name(), index.into())
+    }
+
+    fn element(&self, index: ElemIdx) -> Parsed<u32> {
+        add_count(self.element_base, index.to_u32())
+    }
+
+    fn data(&self, index: DataIdx) -> Parsed<u32> {
+        add_count(self.data_base, index.to_u32())
+    }
+
+    fn ref_type(&self, ref_type: RefType) -> Parsed<RefType> {
+        Ok(match ref_type.heap_type() {
+            HeapType::Index(index) => RefType::new(
+                ref_type.is_nullable(),
+                HeapType::Index(TypeIdx::from(self.type_index(index)?)),
+            ),
+            _ => ref_type,
+        })
+    }
+
+    fn val_type(&self, value_type: ValType) -> Parsed<ValType> {
+        match value_type.try_to_ref_type() {
+            Some(ref_type) => self.ref_type(ref_type).map(ValType::from),
+            None => Ok(value_type),
+        }
+    }
+
+    fn table_type(&self, table_type: &TableType) -> Parsed<TableType> {
+        Ok(TableType::new(
+            self.ref_type(table_type.element_type())?,
+            *table_type.limits(),
+        ))
+    }
+
+    fn global_type(&self, global_type: GlobalType) -> Parsed<GlobalType> {
+        Ok(GlobalType::new(
+            global_type.mutability(),
+            self.val_type(global_type.value_type())?,
+        ))
+    }
+
+    /// Writes the immediates of an `instruction` that refers to an index, returning `false` if
+    /// the instruction does not refer to any indices.
+    fn instruction<I: Input>(
+        &self,
+        instruction: &Instruction<'_, I>,
+        opcode: &[u8],
+        out: &mut Vec<u8>,
+    ) -> Parsed<bool> {
+        let start = out.len();
+        out.extend_from_slice(opcode);
+
+        match instruction {
+            Instruction::Block(BlockType::Index(ty))
+            | Instruction::Loop(BlockType::Index(ty))
+            | Instruction::If(BlockType::Index(ty)) => {
+                encode::block_type(out, BlockType::Index(TypeIdx::from(self.type_index(*ty)?)))
+            }
+            #[cfg(feature = "exceptions")]
+            Instruction::Try(BlockType::Index(ty)) => {
+                encode::block_type(out, BlockType::Index(TypeIdx::from(self.type_index(*ty)?)))
+            }
+            #[cfg(feature = "function-references")]
+            Instruction::Block(BlockType::Inline(value_type @ ValType::TypedRef { .. }))
+            | Instruction::Loop(BlockType::Inline(value_type @ ValType::TypedRef { .. }))
+            | Instruction::If(BlockType::Inline(value_type @ ValType::TypedRef { .. })) => {
+                encode::block_type(out, BlockType::Inline(self.val_type(*value_type)?))
+            }
+            #[cfg(all(feature = "function-references", feature = "exceptions"))]
+            Instruction::Try(BlockType::Inline(value_type @ ValType::TypedRef { .. })) => {
+                encode::block_type(out, BlockType::Inline(self.val_type(*value_type)?))
+            }
+            #[cfg(feature = "function-references")]
+            Instruction::RefNull(ref_type @ RefType::Typed { .. }) => {
+                encode::heap_type(out, self.ref_type(*ref_type)?.heap_type())
+            }
+            Instruction::Call(callee) | Instruction::RefFunc(callee) => {
+                encode::u32(out, self.index(Space::Function, *callee)?)
+            }
+            #[cfg(feature = "tail-call")]
+            Instruction::ReturnCall(callee) => {
+                encode::u32(out, self.index(Space::Function, *callee)?)
+            }
+            Instruction::CallIndirect(ty, table) => {
+                encode::u32(out, self.type_index(*ty)?);
+                encode::u32(out, self.index(Space::Table, *table)?);
+            }
+            #[cfg(feature = "tail-call")]
+            Instruction::ReturnCallIndirect(ty, table) => {
+                encode::u32(out, self.type_index(*ty)?);
+                encode::u32(out, self.index(Space::Table, *table)?);
+            }
+            #[cfg(feature = "function-references")]
+            Instruction::CallRef(ty) | Instruction::ReturnCallRef(ty) => {
+                encode::u32(out, self.type_index(*ty)?)
+            }
+            // Only the typed form of select has immediates
+            Instruction::Select(types) if opcode == [0x1C] => {
+                let types = types.borrow_input();
+                encode::u32(out, types.remaining_count());
+                for result in types {
+                    encode::val_type(out, self.val_type(result?)?);
+                }
+            }
+            Instruction::GlobalGet(global) | Instruction::GlobalSet(global) => {
+                encode::u32(out, self.index(Space::Global, *global)?)
+            }
+            Instruction::TableGet(table)
+            | Instruction::TableSet(table)
+            | Instruction::TableSize(table)
+            | Instruction::TableGrow(table)
+            | Instruction::TableFill(table) => encode::u32(out, self.index(Space::Table, *table)?),
+            Instruction::TableCopy {
+                destination,
+                source,
+            } => {
+                encode::u32(out, self.index(Space::Table, *destination)?);
+                encode::u32(out, self.index(Space::Table, *source)?);
+            }
+            Instruction::TableInit(element, table) => {
+                encode::u32(out, self.element(*element)?);
+                encode::u32(out, self.index(Space::Table, *table)?);
+            }
+            Instruction::ElemDrop(element) => encode::u32(out, self.element(*element)?),
+            Instruction::MemorySize(memory)
+            | Instruction::MemoryGrow(memory)
+            | Instruction::MemoryFill(memory) => {
+                encode::u32(out, self.index(Space::Memory, *memory)?)
+            }
+            Instruction::MemoryCopy {
+                destination,
+                source,
+            } => {
+                encode::u32(out, self.index(Space::Memory, *destination)?);
+                encode::u32(out, self.index(Space::Memory, *source)?);
+            }
+            Instruction::MemoryInit(data, memory) => {
+                encode::u32(out, self.data(*data)?);
+                encode::u32(out, self.index(Space::Memory, *memory)?);
+            }
+            Instruction::DataDrop(data) => encode::u32(out, self.data(*data)?),
+            #[cfg(feature = "exceptions")]
+            Instruction::Throw(tag) | Instruction::Catch(tag) => {
+                encode::u32(out, self.index(Space::Tag, *tag)?)
+            }
+            #[cfg(feature = "exceptions")]
+            Instruction::TryTable(block_type, clauses) => {
+                encode::block_type(
+                    out,
+                    match block_type {
+                        BlockType::Index(ty) => BlockType::Index(self.type_index(*ty)?.into()),
+                        BlockType::Inline(value_type) => {
+                            BlockType::Inline(self.val_type(*value_type)?)
+                        }
+                        BlockType::Empty => BlockType::Empty,
+                    },
+                );
+
+                let clauses = clauses.iter()?;
+                encode::u32(out, clauses.remaining_count());
+                for result in clauses {
+                    let clause = result?;
+                    let kind = match clause {
+                        CatchClause::Catch { .. } => 0,
+                        CatchClause::CatchRef { .. } => 1,
+                        CatchClause::CatchAll(_) => 2,
+                        CatchClause::CatchAllRef(_) => 3,
+                    };
+
+                    out.push(kind);
+                    if let Some(tag) = clause.tag() {
+                        encode::u32(out, self.index(Space::Tag, tag)?);
+                    }
+                    encode::u32(out, clause.label().to_u32());
+                }
+            }
+            _ => {
+                let memarg = match instruction.memarg() {
+                    Some(memarg) => memarg,
+                    None => {
+                        out.truncate(start);
+                        return Ok(false);
+                    }
+                };
+
+                let memory = self.index(Space::Memory, memarg.memory())?;
+                encode::memarg(
+                    out,
+                    &MemArg::new(memarg.offset(), memarg.align(), memory.into()),
+                );
+
+                #[cfg(feature = "simd")]
+                if let Instruction::V128Load8Lane(_, lane)
+                | Instruction::V128Load16Lane(_, lane)
+                | Instruction::V128Load32Lane(_, lane)
+                | Instruction::V128Load64Lane(_, lane)
+                | Instruction::V128Store8Lane(_, lane)
+                | Instruction::V128Store16Lane(_, lane)
+                | Instruction::V128Store32Lane(_, lane)
+                | Instruction::V128Store64Lane(_, lane) = instruction
+                {
+                    out.push(*lane);
+                }
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+pub(super) fn copy_bytes<I: Input>(
+    out: &mut Vec<u8>,
+    input: &I,
+    mut start: u64,
+    end: u64,
+) -> Parsed<()> {
+    let mut buffer = [0u8; 64];
+    while start < end {
+        let length = usize::try_from(end - start)
+            .unwrap_or(usize::MAX)
+            .min(buffer.len());
+
+        let chunk = &mut buffer[..length];
+        input.read_exact_at(start, chunk)?;
+        out.extend_from_slice(chunk);
+        start += length as u64;
+    }
+    Ok(())
+}
+
+/// Copies an expression, rewriting any indices it refers to.
+fn expression<O: Offset, I: Input>(
+    expression: &mut InstructionSequence<O, I>,
+    remap: &Remap,
+    out: &mut Vec<u8>,
+) -> Parsed<()> {
+    while !expression.is_finished() {
+        let start = expression.offset();
+
+        // Prefixed opcodes are copied as is, since they may not be in their shortest encoding
+        let mut opcode_end = start;
+        let prefix = parser::one_byte_exact(&mut opcode_end, expression.input())?;
+        if matches!(prefix, 0xFC..=0xFE) {
+            leb128::u32(&mut opcode_end, expression.input())?;
+        }
+
+        let mut opcode = Vec::new();
+        copy_bytes(&mut opcode, expression.input(), start, opcode_end)?;
+
+        let rewritten =
+            match expression.next(|instruction| remap.instruction(instruction, &opcode, out)) {
+                Some(result) => result?,
+                None => break,
+            };
+
+        if !rewritten {
+            copy_bytes(out, expression.input(), start, expression.offset())?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Calls `f` with every section in a module with the given `id`.
+fn for_each_section<I, F>(sections: &SectionSequence<I>, id: u8, mut f: F) -> Parsed<()>
+where
+    I: Input,
+    F: FnMut(KnownSection<Window<&I>>) -> Parsed<()>,
+{
+    for result in sections.borrow_input() {
+        let section = result?;
+        if section.id() != id {
+            continue;
+        }
+
+        if let Ok(known) = KnownSection::interpret(section) {
+            f(known?)?;
+        }
+    }
+    Ok(())
+}
+
+/// Writes a section containing a vector of entries, omitting the section if it is empty.
+pub(super) fn write_vector_section(out: &mut Vec<u8>, id: u8, count: u32, entries: &[u8]) {
+    if count > 0 {
+        let mut contents = Vec::with_capacity(entries.len() + 5);
+        encode::u32(&mut contents, count);
+        contents.extend_from_slice(entries);
+        encode::section(out, id, &contents);
+    }
+}
+
+enum SegmentTarget {
+    Passive,
+    Declarative,
+    Active(u32),
+}
+
+fn resolve(
+    infos: &[&ModuleInfo],
+    bases: &[[u32; SPACE_COUNT]],
+    mut module: usize,
+    space: Space,
+    mut index: u32,
+) -> Parsed<u32> {
+    // A chain of imports cannot be longer than the number of modules
+    for _ in 0..=infos.len() {
+        let imports = &infos[module].imports[space as usize];
+        match imports.get(crate::int::u32_to_usize(index)) {
+            Some(Import::Unresolved(merged)) => return Ok(*merged),
+            Some(Import::Resolved {
+                module: exporter,
+                index: exported,
+            }) => {
+                module = *exporter;
+                index = *exported;
+            }
+            None => {
+                let defined = index - u32::try_from(imports.len()).unwrap_or(u32::MAX);
+                return if defined < infos[module].definitions[space as usize] {
+                    Ok(bases[module][space as usize] + defined)
+                } else {
+                    Err(index_out_of_bounds(space.name(), index))
+                };
+            }
+        }
+    }
+
+    Err(conflict("imports and exports form a cycle"))
+}
+
+/// Combines the given WebAssembly `modules` into a single module, returning its binary contents.
+///
+/// The index spaces of each module are concatenated in order, and all indices within the
+/// *code*, *element*, *data*, *global*, and *export* sections are rewritten to refer to the new
+/// indices. Identical function types are unified into a single entry in the *type section*.
+///
+/// An import is resolved if another module exports a definition of the same kind with the exact
+/// same name, in which case the import is removed and all references to it refer to the
+/// exported definition instead. The module names of imports are ignored. All remaining imports
+/// are placed before any definitions in the merged module. Exports of every module are kept.
+///
+/// Custom sections are **not** included in the merged module, since indices within them cannot
+/// be rewritten.
+///
+/// # Errors
+///
+/// Returns an error if any module could not be parsed, if more than one module exports the same
+/// name, if more than one module has a *start* function, if an import would be resolved to a
+/// definition with a different type, if imports are resolved in a cycle, or if an index space
+/// or section of the merged module would contain more than [`u32::MAX`] entries.
+pub fn merge<I: Input>(modules: &[I]) -> Parsed<Vec<u8>> {
+    let mut type_table = TypeTable::default();
+    let mut modules = modules
+        .iter()
+        .map(|binary| {
+            let sections = crate::parse_module_sections(binary)?;
+            let info = collect(&sections, &mut type_table)?;
+            Ok((sections, info))
+        })
+        .collect::<Parsed<Vec<_>>>()?;
+
+    // Resolve imports
+    let mut exported = BTreeMap::<Vec<u8>, (usize, Space, u32)>::new();
+    for (module, (_, info)) in modules.iter().enumerate() {
+        for (name, space, index) in info.exports.iter() {
+            if exported
+                .insert(name.clone(), (module, *space, *index))
+                .is_some()
+            {
+                let name = String::from_utf8_lossy(name).into_owned();
+                return Err(conflict("duplicate export name")
+                    .with_context(Context::from_closure(move |f| write!(f, "export {name:?}"))));
+            }
+        }
+    }
+
+    let mut unresolved_counts = [0u32; SPACE_COUNT];
+    for (module, (_, info)) in modules.iter_mut().enumerate() {
+        for (name, space, position) in info.import_names.iter() {
+            match exported.get(name) {
+                Some((exporter, export_space, index))
+                    if *exporter != module && export_space == space =>
+                {
+                    info.imports[*space as usize][*position] = Import::Resolved {
+                        module: *exporter,
+                        index: *index,
+                    };
+                }
+                _ => (),
+            }
+        }
+
+        for (import, count) in info.imports.iter_mut().zip(unresolved_counts.iter_mut()) {
+            for import in import.iter_mut() {
+                if let Import::Unresolved(merged) = import {
+                    *merged = *count;
+                    *count = add_count(*count, 1)?;
+                }
+            }
+        }
+    }
+
+    let mut bases = Vec::with_capacity(modules.len());
+    let mut next_definitions = unresolved_counts;
+    for (_, info) in modules.iter() {
+        bases.push(next_definitions);
+        for (next, count) in next_definitions.iter_mut().zip(info.definitions) {
+            *next = add_count(*next, count)?;
+        }
+    }
+
+    let infos = modules.iter().map(|(_, info)| info).collect::<Vec<_>>();
+    let mut remaps = Vec::with_capacity(modules.len());
+    let mut element_base = 0u32;
+    let mut data_base = 0u32;
+    for (module, (_, info)) in modules.iter().enumerate() {
+        let mut spaces: [Vec<u32>; SPACE_COUNT] = Default::default();
+        for (space, indices) in Space::ALL.into_iter().zip(spaces.iter_mut()) {
+            let imported = u32::try_from(info.imports[space as usize].len()).unwrap_or(u32::MAX);
+            let count = add_count(imported, info.definitions[space as usize])?;
+            for index in 0..count {
+                indices.push(resolve(&infos, &bases, module, space, index)?);
+            }
+        }
+
+        remaps.push(Remap {
+            types: &info.types,
+            spaces,
+            element_base,
+            data_base,
+        });
+
+        element_base = add_count(element_base, info.element_count)?;
+        data_base = add_count(data_base, info.data_count)?;
+    }
+
+    // Check that resolved imports have the same types as the definitions they refer to
+    for (module, (_, info)) in modules.iter().enumerate() {
+        for space in Space::ALL {
+            for (index, import) in info.imports[space as usize].iter().enumerate() {
+                let (exporter, exported) = match import {
+                    Import::Resolved { module, index } => {
+                        (*module, crate::int::u32_to_usize(*index))
+                    }
+                    Import::Unresolved(_) => continue,
+                };
+
+                let provided = &modules[exporter].1;
+                let (importer_remap, exporter_remap) = (&remaps[module], &remaps[exporter]);
+                let matches = match space {
+                    Space::Function => {
+                        info.function_types.get(index) == provided.function_types.get(exported)
+                    }
+                    Space::Table => match (
+                        info.table_types.get(index),
+                        provided.table_types.get(exported),
+                    ) {
+                        (Some(required), Some(actual)) => {
+                            let required = importer_remap.table_type(required)?;
+                            let actual = exporter_remap.table_type(actual)?;
+                            required.element_type() == actual.element_type()
+                                && actual.limits().matches(required.limits())
+                        }
+                        _ => false,
+                    },
+                    Space::Memory => match (
+                        info.memory_types.get(index),
+                        provided.memory_types.get(exported),
+                    ) {
+                        (Some(required), Some(actual)) => actual.matches(required),
+                        _ => false,
+                    },
+                    Space::Global => match (
+                        info.global_types.get(index),
+                        provided.global_types.get(exported),
+                    ) {
+                        (Some(required), Some(actual)) => {
+                            importer_remap.global_type(*required)?
+                                == exporter_remap.global_type(*actual)?
+                        }
+                        _ => false,
+                    },
+                    Space::Tag => info.tag_types.get(index) == provided.tag_types.get(exported),
+                };
+
+                if !matches {
+                    return Err(conflict(space.type_mismatch()));
+                }
+            }
+        }
+    }
+
+    let mut out = Vec::new();
+    out.extend_from_slice(b"\0asm");
+    out.extend_from_slice(&1u32.to_le_bytes());
+
+    // Type section
+    let mut entries = Vec::new();
+    for encoded in type_table.types.iter() {
+        entries.extend_from_slice(encoded);
+    }
+    write_vector_section(
+        &mut out,
+        section_id::TYPE,
+        u32::try_from(type_table.types.len()).unwrap_or(u32::MAX),
+        &entries,
+    );
+
+    // Import section
+    entries.clear();
+    let mut count = 0u32;
+    for ((sections, info), remap) in modules.iter().zip(remaps.iter()) {
+        for_each_section(sections, section_id::IMPORT, |known| {
+            if let KnownSection::Import(mut imports) = known {
+                let mut positions = [0usize; SPACE_COUNT];
+                while let Some(import) = imports.parse()? {
+                    let space = Space::of_import(import.kind());
+                    let position = positions[space as usize];
+                    positions[space as usize] += 1;
+
+                    if let Some(Import::Resolved { .. }) =
+                        info.imports[space as usize].get(position)
+                    {
+                        continue;
+                    }
+
+                    encode::bytes(&mut entries, &import.module().into_bytes()?);
+                    encode::bytes(&mut entries, &import.name().into_bytes()?);
+                    entries.push(space.kind_byte());
+                    match import.kind() {
+                        ImportKind::Function(ty) => {
+                            encode::u32(&mut entries, remap.type_index(*ty)?)
+                        }
+                        ImportKind::Table(table) => {
+                            encode::table_type(&mut entries, &remap.table_type(table)?)
+                        }
+                        ImportKind::Memory(memory) => encode::limits(&mut entries, memory),
+                        ImportKind::Global(global) => {
+                            encode::global_type(&mut entries, remap.global_type(*global)?)
+                        }
+                        ImportKind::Tag(Tag::Exception(ty)) => {
+                            entries.push(0);
+                            encode::u32(&mut entries, remap.type_index(*ty)?);
+                        }
+                    }
+                    count = add_count(count, 1)?;
+                }
+            }
+            Ok(())
+        })?;
+    }
+    write_vector_section(&mut out, section_id::IMPORT, count, &entries);
+
+    // Function section
+    entries.clear();
+    count = 0;
+    for (_, info) in modules.iter() {
+        let imported = info.imports[Space::Function as usize].len();
+        for ty in info.function_types[imported..].iter() {
+            encode::u32(&mut entries, *ty);
+            count = add_count(count, 1)?;
+        }
+    }
+    write_vector_section(&mut out, section_id::FUNC, count, &entries);
+
+    // Table, memory, and tag sections
+    for id in [section_id::TABLE, section_id::MEMORY, section_id::TAG] {
+        entries.clear();
+        count = 0;
+        for ((sections, _), remap) in modules.iter().zip(remaps.iter()) {
+            for_each_section(sections, id, |known| {
+                match known {
+                    KnownSection::Table(tables) => {
+                        for result in tables {
+                            encode::table_type(&mut entries, &remap.table_type(&result?)?);
+                            count = add_count(count, 1)?;
+                        }
+                    }
+                    KnownSection::Memory(memories) => {
+                        for result in memories {
+                            encode::limits(&mut entries, &result?);
+                            count = add_count(count, 1)?;
+                        }
+                    }
+                    KnownSection::Tag(tags) => {
+                        for result in tags {
+                            let Tag::Exception(ty) = result?;
+                            entries.push(0);
+                            encode::u32(&mut entries, remap.type_index(ty)?);
+                            count = add_count(count, 1)?;
+                        }
+                    }
+                    _ => (),
+                }
+                Ok(())
+            })?;
+        }
+        write_vector_section(&mut out, id, count, &entries);
+    }
+
+    // Global section
+    entries.clear();
+    count = 0;
+    for ((sections, _), remap) in modules.iter().zip(remaps.iter()) {
+        for_each_section(sections, section_id::GLOBAL, |known| {
+            if let KnownSection::Global(mut globals) = known {
+                while globals
+                    .parse(|global_type, initializer| {
+                        encode::global_type(&mut entries, remap.global_type(global_type)?);
+                        expression(initializer, remap, &mut entries)
+                    })?
+                    .is_some()
+                {
+                    count = add_count(count, 1)?;
+                }
+            }
+            Ok(())
+        })?;
+    }
+    write_vector_section(&mut out, section_id::GLOBAL, count, &entries);
+
+    // Export section
+    entries.clear();
+    count = 0;
+    for ((_, info), remap) in modules.iter().zip(remaps.iter()) {
+        for (name, space, index) in info.exports.iter() {
+            encode::bytes(&mut entries, name);
+            entries.push(space.kind_byte());
+            encode::u32(&mut entries, remap.index(*space, *index)?);
+            count = add_count(count, 1)?;
+        }
+    }
+    write_vector_section(&mut out, section_id::EXPORT, count, &entries);
+
+    // Start section
+    let mut start = None;
+    for ((_, info), remap) in modules.iter().zip(remaps.iter()) {
+        if let Some(function) = info.start {
+            if start.is_some() {
+                return Err(conflict("more than one module has a start function"));
+            }
+            start = Some(remap.index(Space::Function, function)?);
+        }
+    }
+    if let Some(function) = start {
+        entries.clear();
+        encode::u32(&mut entries, function);
+        encode::section(&mut out, section_id::START, &entries);
+    }
+
+    // Element section
+    entries.clear();
+    count = 0;
+    for ((sections, _), remap) in modules.iter().zip(remaps.iter()) {
+        for_each_section(sections, section_id::ELEMENT, |known| {
+            if let KnownSection::Element(mut elements) = known {
+                loop {
+                    let result = elements.parse(
+                        |mode| {
+                            let mut offset = Vec::new();
+                            let target = match mode {
+                                ElementMode::Passive => SegmentTarget::Passive,
+                                ElementMode::Declarative => SegmentTarget::Declarative,
+                                ElementMode::Active(table, initializer) => {
+                                    expression(initializer, remap, &mut offset)?;
+                                    SegmentTarget::Active(remap.index(Space::Table, *table)?)
+                                }
+                            };
+                            Ok((target, offset))
+                        },
+                        |(target, offset), init| {
+                            let (ref_type, flags) = match init {
+                                ElementInit::Functions(_) => (None, 0),
+                                ElementInit::Expressions(ref_type, _) => {
+                                    (Some(remap.ref_type(*ref_type)?), 4)
+                                }
+                            };
+
+                            let flags = flags
+                                | match target {
+                                    SegmentTarget::Active(0)
+                                        if matches!(ref_type, None | Some(RefType::Func)) =>
+                                    {
+                                        0
+                                    }
+                                    SegmentTarget::Passive => 1,
+                                    SegmentTarget::Active(_) => 2,
+                                    SegmentTarget::Declarative => 3,
+                                };
+
+                            encode::u32(&mut entries, flags);
+                            if let SegmentTarget::Active(table) = target {
+                                if flags & 2 != 0 {
+                                    encode::u32(&mut entries, table);
+                                }
+                                entries.extend_from_slice(&offset);
+                            }
+
+                            if flags & 3 != 0 {
+                                match ref_type {
+                                    None => entries.push(0),
+                                    Some(ref_type) => encode::ref_type(&mut entries, ref_type),
+                                }
+                            }
+
+                            match init {
+                                ElementInit::Functions(functions) => {
+                                    encode::u32(&mut entries, functions.remaining_count());
+                                    for result in functions {
+                                        let function = remap.index(Space::Function, result?)?;
+                                        encode::u32(&mut entries, function);
+                                    }
+                                }
+                                ElementInit::Expressions(_, expressions) => {
+                                    encode::u32(&mut entries, expressions.remaining_count());
+                                    while expressions
+                                        .next(|item| expression(item, remap, &mut entries))?
+                                        .is_some()
+                                    {}
+                                }
+                            }
+                            Ok(())
+                        },
+                    )?;
+
+                    if result.is_none() {
+                        break;
+                    }
+                    count = add_count(count, 1)?;
+                }
+            }
+            Ok(())
+        })?;
+    }
+    write_vector_section(&mut out, section_id::ELEMENT, count, &entries);
+
+    // Data count section
+    if modules.iter().any(|(_, info)| info.has_data_count) {
+        entries.clear();
+        encode::u32(&mut entries, data_base);
+        encode::section(&mut out, section_id::DATA_COUNT, &entries);
+    }
+
+    // Code section
+    entries.clear();
+    count = 0;
+    let mut body = Vec::new();
+    for ((sections, _), remap) in modules.iter().zip(remaps.iter()) {
+        for_each_section(sections, section_id::CODE, |known| {
+            if let KnownSection::Code(mut code) = known {
+                while let Some(entry) = code.parse()? {
+                    body.clear();
+                    entry.read(
+                        |locals| {
+                            let mut groups = Vec::new();
+                            while let Some((count, local_type)) = locals.next_group()? {
+                                groups.push((count.get(), local_type));
+                            }
+
+                            encode::u64(&mut body, groups.len() as u64);
+                            for (count, local_type) in groups {
+                                encode::u32(&mut body, count);
+                                encode::val_type(&mut body, remap.val_type(local_type)?);
+                            }
+                            Ok(&mut body)
+                        },
+                        |body, instructions| expression(instructions, remap, body),
+                    )?;
+
+                    encode::bytes(&mut entries, &body);
+                    count = add_count(count, 1)?;
+                }
+            }
+            Ok(())
+        })?;
+    }
+    write_vector_section(&mut out, section_id::CODE, count, &entries);
+
+    // Data section
+    entries.clear();
+    count = 0;
+    for ((sections, _), remap) in modules.iter().zip(remaps.iter()) {
+        for_each_section(sections, section_id::DATA, |known| {
+            if let KnownSection::Data(mut datas) = known {
+                loop {
+                    let result = datas.parse(
+                        |mode| {
+                            let mut offset = Vec::new();
+                            let target = match mode {
+                                DataMode::Passive => SegmentTarget::Passive,
+                                DataMode::Active(memory, initializer) => {
+                                    expression(initializer, remap, &mut offset)?;
+                                    SegmentTarget::Active(remap.index(Space::Memory, *memory)?)
+                                }
+                            };
+                            Ok((target, offset))
+                        },
+                        |(target, offset), data| {
+                            match target {
+                                SegmentTarget::Active(0) => entries.push(0),
+                                SegmentTarget::Active(memory) => {
+                                    entries.push(2);
+                                    encode::u32(&mut entries, memory);
+                                }
+                                _ => entries.push(1),
+                            }
+                            entries.extend_from_slice(&offset);
+
+                            encode::u64(&mut entries, data.length());
+                            copy_bytes(
+                                &mut entries,
+                                &data,
+                                data.base(),
+                                data.base() + data.length(),
+                            )
+                        },
+                    )?;
+
+                    if result.is_none() {
+                        break;
+                    }
+                    count = add_count(count, 1)?;
+                }
+            }
+            Ok(())
+        })?;
+    }
+    write_vector_section(&mut out, section_id::DATA, count, &entries);
+
+    Ok(out)
+}
@@ -0,0 +1,266 @@
+use crate::{
+    custom::name::{
+        NameMap, NameSection, NameSubsection, FUNCTION_NAME_ID, LOCAL_NAME_ID, MODULE_NAME_ID,
+        TAG_NAME_ID,
+    },
+    encode,
+    index::{FuncIdx, Index, LocalIdx, TagIdx},
+    input::Input,
+    parser::{Offset, Parsed, ResultExt as _},
+    sections::id as section_id,
+};
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+
+fn name_map<N: crate::index::Index>(out: &mut Vec<u8>, names: &BTreeMap<N, String>) {
+    encode::u64(out, names.len() as u64);
+    for (index, name) in names {
+        encode::u32(out, (*index).into());
+        encode::bytes(out, name.as_bytes());
+    }
+}
+
+fn read_name_map<N: Index, O: Offset, I: Input>(
+    names: &mut BTreeMap<N, String>,
+    map: &mut NameMap<N, O, I>,
+) -> Parsed<()> {
+    while let Some(assoc) = map.parse()? {
+        names.insert(assoc.index(), assoc.name().try_into_string()?);
+    }
+    Ok(())
+}
+
+fn subsection(out: &mut Vec<u8>, buffer: &mut Vec<u8>, id: u8) {
+    encode::section(out, id, buffer);
+    buffer.clear();
+}
+
+/// Builds the contents of a
+/// [`name` custom section](https://webassembly.github.io/spec/core/appendix/custom.html#name-section).
+///
+/// Names can be inserted in any order, the subsections and the indices within them are sorted in
+/// ascending order as required by the specification when the section is written. Existing names
+/// can be read with [`NameSectionBuilder::from_section()`], allowing tools that add or remove
+/// definitions to write the names again.
+///
+/// # Example
+///
+/// ```
+/// use wasmiter::{
+///     custom::{name::NameSubsection, CustomSection, KnownCustomSection},
+///     edit::NameSectionBuilder,
+/// };
+///
+/// let mut names = NameSectionBuilder::new();
+/// names.set_module_name("example");
+/// names.insert_function_name(1u32.into(), "second");
+/// names.insert_function_name(0u32.into(), "first");
+///
+/// let mut module = b"\0asm\x01\0\0\0".to_vec();
+/// names.write(&mut module);
+///
+/// let sections = wasmiter::parse_module_sections(module.as_slice())?;
+/// let section = sections.into_iter().next().unwrap()?;
+/// let custom = CustomSection::try_from_section(section).unwrap()?;
+/// let mut subsections = match KnownCustomSection::interpret(custom) {
+///     Ok(KnownCustomSection::Name(subsections)) => subsections,
+///     _ => unreachable!(),
+/// };
+///
+/// match subsections.next().unwrap() {
+///     Ok(subsection) => match subsection? {
+///         NameSubsection::ModuleName(name) => assert!(name.try_eq_str("example")?),
+///         _ => unreachable!(),
+///     },
+///     Err(_) => unreachable!(),
+/// }
+///
+/// match subsections.next().unwrap() {
+///     Ok(subsection) => match subsection? {
+///         NameSubsection::FunctionName(names) => {
+///             let first = names.into_iter().next().unwrap()?;
+///             assert_eq!(u32::from(first.index()), 0);
+///             assert!(first.name().try_eq_str("first")?);
+///         }
+///         _ => unreachable!(),
+///     },
+///     Err(_) => unreachable!(),
+/// }
+/// # Ok::<(), wasmiter::parser::Error>(())
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct NameSectionBuilder {
+    module: Option<String>,
+    functions: BTreeMap<FuncIdx, String>,
+    locals: BTreeMap<FuncIdx, BTreeMap<LocalIdx, String>>,
+    tags: BTreeMap<TagIdx, String>,
+}
+
+impl NameSectionBuilder {
+    /// Creates a new [`NameSectionBuilder`] that does not contain any names.
+    pub fn new() -> Self {
+        Self {
+            module: None,
+            functions: BTreeMap::new(),
+            locals: BTreeMap::new(),
+            tags: BTreeMap::new(),
+        }
+    }
+
+    /// Reads the module, function, local, and tag names contained in an existing `name` section.
+    ///
+    /// Other subsections, such as those introduced by the
+    /// [extended name section proposal](https://github.com/WebAssembly/extended-name-section), are
+    /// skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a subsection could not be parsed, or if a name is not valid UTF-8.
+    pub fn from_section<I: Input>(mut section: NameSection<I>) -> Parsed<Self> {
+        let mut builder = Self::new();
+        while let Some(subsection) = section.parse() {
+            let subsection = match subsection {
+                Ok(subsection) => subsection,
+                Err(_) => continue,
+            };
+
+            match subsection? {
+                NameSubsection::ModuleName(name) => {
+                    builder.module = Some(name.try_into_string().context("module name")?);
+                }
+                NameSubsection::FunctionName(mut names) => {
+                    read_name_map(&mut builder.functions, &mut names).context("function names")?;
+                }
+                NameSubsection::LocalName(mut functions) => {
+                    while functions
+                        .parse(|function, names| {
+                            read_name_map(builder.locals.entry(function).or_default(), names)
+                        })
+                        .context("local names")?
+                        .is_some()
+                    {}
+                }
+                NameSubsection::TagName(mut names) => {
+                    read_name_map(&mut builder.tags, &mut names).context("tag names")?;
+                }
+                _ => (),
+            }
+        }
+
+        Ok(builder)
+    }
+
+    /// Returns `true` if no names were inserted.
+    pub fn is_empty(&self) -> bool {
+        self.module.is_none()
+            && self.functions.is_empty()
+            && self.locals.values().all(BTreeMap::is_empty)
+            && self.tags.is_empty()
+    }
+
+    /// Sets the name of the module, returning the previous name.
+    pub fn set_module_name(&mut self, name: impl Into<String>) -> Option<String> {
+        self.module.replace(name.into())
+    }
+
+    /// Assigns a name to a function, returning the previous name.
+    pub fn insert_function_name(
+        &mut self,
+        function: FuncIdx,
+        name: impl Into<String>,
+    ) -> Option<String> {
+        self.functions.insert(function, name.into())
+    }
+
+    /// Assigns a name to a local variable within a function, returning the previous name.
+    pub fn insert_local_name(
+        &mut self,
+        function: FuncIdx,
+        local: LocalIdx,
+        name: impl Into<String>,
+    ) -> Option<String> {
+        self.locals
+            .entry(function)
+            .or_default()
+            .insert(local, name.into())
+    }
+
+    /// Assigns a name to a
+    /// [**tag**](https://webassembly.github.io/exception-handling/core/syntax/modules.html#tags),
+    /// returning the previous name.
+    pub fn insert_tag_name(&mut self, tag: TagIdx, name: impl Into<String>) -> Option<String> {
+        self.tags.insert(tag, name.into())
+    }
+
+    /// Assigns names to functions from the given pairs of indices and names, replacing any previous
+    /// names.
+    pub fn extend_function_names<N: Into<String>>(
+        &mut self,
+        names: impl IntoIterator<Item = (FuncIdx, N)>,
+    ) {
+        self.functions
+            .extend(names.into_iter().map(|(index, name)| (index, name.into())));
+    }
+
+    /// Assigns names to the local variables within a function from the given pairs of indices and
+    /// names, replacing any previous names.
+    pub fn extend_local_names<N: Into<String>>(
+        &mut self,
+        function: FuncIdx,
+        names: impl IntoIterator<Item = (LocalIdx, N)>,
+    ) {
+        self.locals
+            .entry(function)
+            .or_default()
+            .extend(names.into_iter().map(|(index, name)| (index, name.into())));
+    }
+
+    /// Assigns names to tags from the given pairs of indices and names, replacing any previous
+    /// names.
+    pub fn extend_tag_names<N: Into<String>>(
+        &mut self,
+        names: impl IntoIterator<Item = (TagIdx, N)>,
+    ) {
+        self.tags
+            .extend(names.into_iter().map(|(index, name)| (index, name.into())));
+    }
+
+    /// Writes the contents of the `name` section, without the custom section *id*, size, or name.
+    ///
+    /// Subsections that would be empty are omitted.
+    pub fn write_contents(&self, out: &mut Vec<u8>) {
+        let mut buffer = Vec::new();
+
+        if let Some(module) = &self.module {
+            encode::bytes(&mut buffer, module.as_bytes());
+            subsection(out, &mut buffer, MODULE_NAME_ID);
+        }
+
+        if !self.functions.is_empty() {
+            name_map(&mut buffer, &self.functions);
+            subsection(out, &mut buffer, FUNCTION_NAME_ID);
+        }
+
+        let locals = self.locals.iter().filter(|(_, names)| !names.is_empty());
+        let local_count = locals.clone().count();
+        if local_count > 0 {
+            encode::u64(&mut buffer, local_count as u64);
+            for (function, names) in locals {
+                encode::u32(&mut buffer, (*function).into());
+                name_map(&mut buffer, names);
+            }
+            subsection(out, &mut buffer, LOCAL_NAME_ID);
+        }
+
+        if !self.tags.is_empty() {
+            name_map(&mut buffer, &self.tags);
+            subsection(out, &mut buffer, TAG_NAME_ID);
+        }
+    }
+
+    /// Appends a `name` custom section containing the names to the end of a module.
+    pub fn write(&self, out: &mut Vec<u8>) {
+        let mut contents = Vec::new();
+        self.write_contents(&mut contents);
+        super::write_custom_section(out, section_id::NAME, &contents);
+    }
+}
@@ -0,0 +1,186 @@
+use crate::{
+    component::{ExportsComponent, ImportKind, ImportsComponent, Tag},
+    edit::merge::{copy_bytes, write_vector_section, Space},
+    encode,
+    input::Input,
+    parser::{Context, Error, ErrorKind, Parsed},
+    sections::id as section_id,
+};
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    string::String,
+    vec::Vec,
+};
+
+#[inline(never)]
+#[cold]
+fn duplicate_export(name: &[u8]) -> Error {
+    let name = String::from_utf8_lossy(name).into_owned();
+    Error::new(ErrorKind::RenameConflict("duplicate export name"))
+        .with_context(Context::from_closure(move |f| write!(f, "export {name:?}")))
+}
+
+/// Describes how [`rename`] changes the names of a module's exports and the module names of its
+/// imports.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RenameMap {
+    exports: BTreeMap<Vec<u8>, Vec<u8>>,
+    export_prefix: Vec<u8>,
+    import_modules: BTreeMap<Vec<u8>, Vec<u8>>,
+}
+
+impl RenameMap {
+    /// Creates a new [`RenameMap`] that does not rename anything.
+    pub fn new() -> Self {
+        Self {
+            exports: BTreeMap::new(),
+            export_prefix: Vec::new(),
+            import_modules: BTreeMap::new(),
+        }
+    }
+
+    /// Renames the export named `from` to `to`, returning the previous new name for `from`.
+    pub fn insert_export(&mut self, from: &str, to: &str) -> Option<String> {
+        self.exports
+            .insert(from.as_bytes().to_vec(), to.as_bytes().to_vec())
+            .map(|previous| String::from_utf8_lossy(&previous).into_owned())
+    }
+
+    /// Renames the module name of every import from the module `from` to `to`, returning the
+    /// previous new name for `from`.
+    pub fn insert_import_module(&mut self, from: &str, to: &str) -> Option<String> {
+        self.import_modules
+            .insert(from.as_bytes().to_vec(), to.as_bytes().to_vec())
+            .map(|previous| String::from_utf8_lossy(&previous).into_owned())
+    }
+
+    /// Sets a `prefix` that is added to the name of every export, after any renames are applied.
+    ///
+    /// This can be used to place the exports of a module into a namespace.
+    pub fn with_export_prefix(mut self, prefix: &str) -> Self {
+        self.export_prefix = prefix.as_bytes().to_vec();
+        self
+    }
+
+    fn export_name(&self, name: Vec<u8>) -> Vec<u8> {
+        let renamed = self.exports.get(&name).cloned().unwrap_or(name);
+        if self.export_prefix.is_empty() {
+            renamed
+        } else {
+            let mut prefixed = self.export_prefix.clone();
+            prefixed.extend_from_slice(&renamed);
+            prefixed
+        }
+    }
+
+    fn import_module(&self, module: Vec<u8>) -> Vec<u8> {
+        self.import_modules.get(&module).cloned().unwrap_or(module)
+    }
+}
+
+/// Copies a WebAssembly `module`, changing the names of its exports and the module names of its
+/// imports according to the given [`RenameMap`], returning the binary contents of the new module.
+///
+/// All other sections, including custom sections, are copied unchanged and in the same order.
+///
+/// # Errors
+///
+/// Returns an error if the `module` could not be parsed, or if more than one export would have
+/// the same name after renaming.
+///
+/// # Example
+///
+/// ```
+/// use wasmiter::edit::{self, RenameMap};
+///
+/// let module = wat::parse_str(r#"(module
+///     (func (export "run"))
+///     (memory (export "memory") 1))"#).unwrap();
+///
+/// let mut renames = RenameMap::new().with_export_prefix("guest.");
+/// renames.insert_export("run", "start");
+/// let renamed = edit::rename(module.as_slice(), &renames)?;
+///
+/// let sections = wasmiter::parse_module_sections(renamed.as_slice())?;
+/// let exports = sections.into_iter().find_map(|result| {
+///     match wasmiter::component::KnownSection::interpret(result.ok()?) {
+///         Ok(Ok(wasmiter::component::KnownSection::Export(exports))) => Some(exports),
+///         _ => None,
+///     }
+/// });
+///
+/// let mut names = Vec::new();
+/// for result in exports.unwrap() {
+///     names.push(result?.name().try_into_string()?);
+/// }
+/// assert_eq!(names, ["guest.start", "guest.memory"]);
+///
+/// // Renaming an export to the name of another export is an error
+/// let mut renames = RenameMap::new();
+/// renames.insert_export("run", "memory");
+/// assert!(edit::rename(module.as_slice(), &renames).is_err());
+/// # Ok::<(), wasmiter::parser::Error>(())
+/// ```
+pub fn rename<I: Input>(module: I, renames: &RenameMap) -> Parsed<Vec<u8>> {
+    let sections = crate::parse_module_sections(module)?;
+
+    let mut out = Vec::new();
+    out.extend_from_slice(b"\0asm");
+    out.extend_from_slice(&1u32.to_le_bytes());
+
+    let mut entries = Vec::new();
+    for result in crate::input::BorrowInput::borrow_input(&sections) {
+        let section = result?;
+        let id = section.id();
+        let contents = section.into_contents();
+        let start = contents.base();
+
+        entries.clear();
+        match id {
+            section_id::IMPORT => {
+                let mut imports = ImportsComponent::new(start, contents)?;
+                let count = imports.remaining_count();
+                while let Some(import) = imports.parse()? {
+                    let module_name = renames.import_module(import.module().into_bytes()?);
+                    encode::bytes(&mut entries, &module_name);
+                    encode::bytes(&mut entries, &import.name().into_bytes()?);
+                    entries.push(Space::of_import(import.kind()).kind_byte());
+                    match import.kind() {
+                        ImportKind::Function(ty) => encode::u32(&mut entries, ty.to_u32()),
+                        ImportKind::Table(table) => encode::table_type(&mut entries, table),
+                        ImportKind::Memory(memory) => encode::limits(&mut entries, memory),
+                        ImportKind::Global(global) => encode::global_type(&mut entries, *global),
+                        ImportKind::Tag(Tag::Exception(ty)) => {
+                            entries.push(0);
+                            encode::u32(&mut entries, ty.to_u32());
+                        }
+                    }
+                }
+                write_vector_section(&mut out, id, count, &entries);
+            }
+            section_id::EXPORT => {
+                let mut exports = ExportsComponent::new(start, contents)?;
+                let count = exports.remaining_count();
+                let mut names = BTreeSet::new();
+                while let Some(export) = exports.parse()? {
+                    let name = renames.export_name(export.name().into_bytes()?);
+                    if !names.insert(name.clone()) {
+                        return Err(duplicate_export(&name));
+                    }
+
+                    let (space, index) = Space::of_export(export.kind());
+                    encode::bytes(&mut entries, &name);
+                    entries.push(space.kind_byte());
+                    encode::u32(&mut entries, index);
+                }
+                write_vector_section(&mut out, id, count, &entries);
+            }
+            _ => {
+                copy_bytes(&mut entries, &contents, start, start + contents.length())?;
+                encode::section(&mut out, id, &entries);
+            }
+        }
+    }
+
+    Ok(out)
+}
@@ -0,0 +1,208 @@
+use crate::{
+    custom::CustomSection,
+    input::{Input, Window},
+    parser::Parsed,
+    sections::Section,
+};
+use alloc::vec::Vec;
+
+/// Describes what [`transform`] does with a section of a module.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum SectionAction {
+    /// Copies the section unchanged, including its *id* and size.
+    #[default]
+    Keep,
+    /// Omits the section from the output.
+    Remove,
+    /// Writes the given bytes in place of the section.
+    ///
+    /// The bytes must include the section *id* and size, such as those written by
+    /// [`write_custom_section`](crate::edit::write_custom_section) or
+    /// [`NameSectionBuilder::write`](crate::edit::NameSectionBuilder::write).
+    Replace(Vec<u8>),
+    /// Writes the given bytes, then copies the section unchanged.
+    ///
+    /// As with [`SectionAction::Replace`], the bytes must include the *id* and size of each
+    /// section that is inserted.
+    InsertBefore(Vec<u8>),
+    /// Copies the section unchanged, then writes the given bytes.
+    ///
+    /// As with [`SectionAction::Replace`], the bytes must include the *id* and size of each
+    /// section that is inserted.
+    InsertAfter(Vec<u8>),
+}
+
+/// Copies a WebAssembly `module` to the given writer, calling `f` to determine whether each
+/// section is kept, removed, or replaced, or whether new sections are inserted next to it,
+/// returning the number of bytes written.
+///
+/// The preamble and every kept section are copied byte-for-byte, so the encoding of section
+/// sizes is preserved.
+///
+/// # Errors
+///
+/// Returns an error if the `module` could not be parsed, if `f` returns an error, or if the
+/// output could not be written.
+///
+/// # Example
+///
+/// ```
+/// use wasmiter::{custom::CustomSection, edit::{self, SectionAction, NameSectionBuilder}};
+///
+/// let module = wat::parse_str("(module (func $f) (@custom \"extra\" \"data\"))").unwrap();
+///
+/// let mut names = NameSectionBuilder::new();
+/// names.insert_function_name(0u32.into(), "renamed");
+/// let mut name_section = Vec::new();
+/// names.write(&mut name_section);
+///
+/// let mut out = Vec::new();
+/// edit::transform(module.as_slice(), &mut out, |section| {
+///     let custom = match CustomSection::try_from_section(section) {
+///         Ok(custom) => custom,
+///         Err(_) => return Ok(SectionAction::Keep),
+///     };
+///
+///     Ok(if custom?.name().try_eq_str("name")? {
+///         SectionAction::Replace(name_section.clone())
+///     } else {
+///         SectionAction::Remove
+///     })
+/// })?;
+///
+/// let sections = wasmiter::parse_module_sections(out.as_slice())?;
+/// assert_eq!(sections.find_function_by_name("renamed")?, Some(0u32.into()));
+/// # Ok::<(), wasmiter::parser::Error>(())
+/// ```
+pub fn transform<I, W, F>(module: I, out: &mut W, mut f: F) -> Parsed<u64>
+where
+    I: Input,
+    W: std::io::Write + ?Sized,
+    F: FnMut(Section<&I>) -> Parsed<SectionAction>,
+{
+    let sections = crate::parse_module_sections(&module)?;
+    let mut written = Window::with_offset_and_length(&module, 0, 8).copy_to(out)?;
+    let write_bytes = |out: &mut W, bytes: &[u8]| -> Parsed<u64> {
+        out.write_all(bytes)?;
+        Ok(bytes.len() as u64)
+    };
+
+    // Sections are contiguous, so each one starts where the previous one ended
+    let mut start = 8u64;
+    for result in sections {
+        let section = result?;
+        let end = section.contents().base().saturating_add(section.length());
+        let original = Window::with_offset_and_length(&module, start, end - start);
+
+        written += match f(section)? {
+            SectionAction::Keep => original.copy_to(out)?,
+            SectionAction::Remove => 0,
+            SectionAction::Replace(bytes) => write_bytes(out, &bytes)?,
+            SectionAction::InsertBefore(bytes) => {
+                write_bytes(out, &bytes)? + original.copy_to(out)?
+            }
+            SectionAction::InsertAfter(bytes) => original.copy_to(out)? + write_bytes(out, &bytes)?,
+        };
+
+        start = end;
+    }
+
+    Ok(written)
+}
+
+/// Copies a WebAssembly `module` to the given writer without any of its custom sections,
+/// returning the number of bytes written.
+///
+/// See [`transform`] for more information.
+///
+/// # Errors
+///
+/// Returns an error if the `module` could not be parsed, or if the output could not be written.
+///
+/// # Example
+///
+/// ```
+/// let module = wat::parse_str("(module $m (func $f) (@custom \"extra\" \"data\"))").unwrap();
+///
+/// let mut stripped = Vec::new();
+/// wasmiter::edit::strip_custom_sections(module.as_slice(), &mut stripped)?;
+/// assert_eq!(stripped, wat::parse_str("(module (func))").unwrap());
+/// # Ok::<(), wasmiter::parser::Error>(())
+/// ```
+pub fn strip_custom_sections<I, W>(module: I, out: &mut W) -> Parsed<u64>
+where
+    I: Input,
+    W: std::io::Write + ?Sized,
+{
+    transform(module, out, |section| {
+        Ok(if CustomSection::try_from_section(section).is_ok() {
+            SectionAction::Remove
+        } else {
+            SectionAction::Keep
+        })
+    })
+}
+
+/// Copies a WebAssembly `module` to the given writer, inserting a
+/// [custom section](https://webassembly.github.io/spec/core/binary/modules.html#custom-section)
+/// with the given `name` and `payload` so that it is preceded by `position` sections, returning
+/// the number of bytes written.
+///
+/// If the `module` contains fewer than `position` sections, the custom section is appended to the
+/// end. See [`transform`] for more information.
+///
+/// # Errors
+///
+/// Returns an error if the `module` could not be parsed, or if the output could not be written.
+///
+/// # Example
+///
+/// ```
+/// use wasmiter::custom::CustomSection;
+///
+/// let module = wat::parse_str("(module (func))").unwrap();
+///
+/// let mut out = Vec::new();
+/// wasmiter::edit::insert_custom_section(module.as_slice(), &mut out, 0, "build_id", &[0xAB; 16])?;
+///
+/// let sections = wasmiter::parse_module_sections(out.as_slice())?;
+/// let first = sections.into_iter().next().unwrap()?;
+/// let custom = CustomSection::try_from_section(first).unwrap()?;
+/// assert!(custom.name().try_eq_str("build_id")?);
+/// assert_eq!(custom.contents().length(), 16);
+/// # Ok::<(), wasmiter::parser::Error>(())
+/// ```
+pub fn insert_custom_section<I, W>(
+    module: I,
+    out: &mut W,
+    position: usize,
+    name: &str,
+    payload: &[u8],
+) -> Parsed<u64>
+where
+    I: Input,
+    W: std::io::Write + ?Sized,
+{
+    let mut section = Vec::new();
+    crate::edit::write_custom_section(&mut section, name, payload);
+
+    let mut index = 0usize;
+    let mut written = transform(module, out, |_| {
+        let action = if index == position {
+            SectionAction::InsertBefore(core::mem::take(&mut section))
+        } else {
+            SectionAction::Keep
+        };
+
+        index += 1;
+        Ok(action)
+    })?;
+
+    if index <= position {
+        out.write_all(&section)?;
+        written += section.len() as u64;
+    }
+
+    Ok(written)
+}
@@ -0,0 +1,157 @@
+//! Functions for writing values in the
+//! [WebAssembly binary format](https://webassembly.github.io/spec/core/binary/index.html).
+
+use crate::{
+    instruction_set::MemArg,
+    types::{
+        BlockType, GlobalMutability, HeapType, IdxType, Limits, RefType, Sharing, TableType,
+        ValType,
+    },
+};
+use alloc::vec::Vec;
+
+/// Writes an unsigned integer in the
+/// [*LEB128* format](https://webassembly.github.io/spec/core/binary/values.html#integers).
+pub(crate) fn u64(out: &mut Vec<u8>, mut value: u64) {
+    loop {
+        let byte = value.to_le_bytes()[0] & 0x7F;
+        value >>= 7;
+
+        if value == 0 {
+            out.push(byte);
+            return;
+        }
+
+        out.push(byte | 0x80);
+    }
+}
+
+/// Calculates the number of bytes needed to write an unsigned integer in the *LEB128* format.
+pub(crate) fn u64_length(value: u64) -> u64 {
+    u64::from((64 - (value | 1).leading_zeros() + 6) / 7)
+}
+
+#[inline]
+pub(crate) fn u32(out: &mut Vec<u8>, value: u32) {
+    u64(out, value.into())
+}
+
+/// Writes a signed integer in the
+/// [*LEB128* format](https://webassembly.github.io/spec/core/binary/values.html#integers).
+pub(crate) fn s64(out: &mut Vec<u8>, mut value: i64) {
+    loop {
+        let byte = value.to_le_bytes()[0] & 0x7F;
+        value >>= 7;
+
+        let sign_bit = byte & 0x40 != 0;
+        if (value == 0 && !sign_bit) || (value == -1 && sign_bit) {
+            out.push(byte);
+            return;
+        }
+
+        out.push(byte | 0x80);
+    }
+}
+
+/// Writes the length of the given `bytes`, followed by the `bytes` themselves.
+pub(crate) fn bytes(out: &mut Vec<u8>, bytes: &[u8]) {
+    u64(out, bytes.len() as u64);
+    out.extend_from_slice(bytes);
+}
+
+/// Writes a section with the given `id`, whose `contents` are prefixed with their size.
+pub(crate) fn section(out: &mut Vec<u8>, id: u8, contents: &[u8]) {
+    out.push(id);
+    bytes(out, contents);
+}
+
+pub(crate) fn val_type(out: &mut Vec<u8>, value_type: ValType) {
+    out.push(match value_type {
+        ValType::I32 => 0x7F,
+        ValType::I64 => 0x7E,
+        ValType::F32 => 0x7D,
+        ValType::F64 => 0x7C,
+        ValType::V128 => 0x7B,
+        ValType::FuncRef => 0x70,
+        ValType::ExternRef => 0x6F,
+        ValType::ExnRef => 0x69,
+        ValType::TypedRef {
+            nullable,
+            heap_type,
+        } => {
+            out.push(if nullable { 0x63 } else { 0x64 });
+            return self::heap_type(out, heap_type);
+        }
+    })
+}
+
+pub(crate) fn heap_type(out: &mut Vec<u8>, heap_type: HeapType) {
+    match heap_type {
+        HeapType::Func => out.push(0x70),
+        HeapType::Extern => out.push(0x6F),
+        HeapType::Exn => out.push(0x69),
+        HeapType::Index(index) => s64(out, index.to_u32().into()),
+    }
+}
+
+#[inline]
+pub(crate) fn ref_type(out: &mut Vec<u8>, ref_type: RefType) {
+    val_type(out, ref_type.into())
+}
+
+pub(crate) fn block_type(out: &mut Vec<u8>, block_type: BlockType) {
+    match block_type {
+        BlockType::Empty => out.push(0x40),
+        BlockType::Inline(value_type) => val_type(out, value_type),
+        BlockType::Index(index) => s64(out, index.to_u32().into()),
+    }
+}
+
+pub(crate) fn limits(out: &mut Vec<u8>, limits: &Limits) {
+    let mut flags = 0u8;
+    if limits.maximum().is_some() {
+        flags |= 1;
+    }
+    if limits.share() == Sharing::Shared {
+        flags |= 0b10;
+    }
+    if limits.index_type() == IdxType::I64 {
+        flags |= 0b100;
+    }
+    if limits.page_size_log2().is_some() {
+        flags |= 0b1000;
+    }
+
+    out.push(flags);
+    u64(out, limits.minimum());
+    if let Some(maximum) = limits.maximum() {
+        u64(out, maximum);
+    }
+    if let Some(log2) = limits.page_size_log2() {
+        u32(out, log2.into());
+    }
+}
+
+pub(crate) fn table_type(out: &mut Vec<u8>, table_type: &TableType) {
+    ref_type(out, table_type.element_type());
+    limits(out, table_type.limits());
+}
+
+pub(crate) fn global_type(out: &mut Vec<u8>, global_type: crate::types::GlobalType) {
+    val_type(out, global_type.value_type());
+    out.push(match global_type.mutability() {
+        GlobalMutability::Constant => 0,
+        GlobalMutability::Variable => 1,
+    });
+}
+
+pub(crate) fn memarg(out: &mut Vec<u8>, memarg: &MemArg) {
+    let align = u32::from(memarg.align().to_power());
+    if memarg.requires_multi_memory() {
+        u32(out, align | 64);
+        u32(out, memarg.memory().to_u32());
+    } else {
+        u32(out, align);
+    }
+    u64(out, memarg.offset());
+}
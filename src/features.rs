@@ -0,0 +1,123 @@
+//! Types and functions for determining which WebAssembly proposals a module uses.
+
+/// A WebAssembly proposal that a module makes use of.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[non_exhaustive]
+pub enum Feature {
+    /// The [import and export of mutable globals](https://github.com/WebAssembly/mutable-global).
+    MutableGlobal,
+    /// [Non-trapping float-to-int conversions](https://github.com/WebAssembly/nontrapping-float-to-int-conversions).
+    NonTrappingFloatToInt,
+    /// [Sign-extension operators](https://github.com/WebAssembly/sign-extension-ops).
+    SignExtension,
+    /// [Functions and blocks with multiple results](https://github.com/WebAssembly/multi-value).
+    MultiValue,
+    /// [Reference types](https://github.com/WebAssembly/reference-types), such as `externref`,
+    /// or more than one table.
+    ReferenceTypes,
+    /// [Bulk memory operations](https://github.com/WebAssembly/bulk-memory-operations), including
+    /// the *data count section*.
+    BulkMemory,
+    /// [Fixed-width SIMD](https://github.com/WebAssembly/simd), including the `v128` type.
+    Simd,
+    /// [Tail calls](https://github.com/WebAssembly/tail-call).
+    TailCall,
+    /// [Shared memories and atomic instructions](https://github.com/WebAssembly/threads).
+    Threads,
+    /// [Exception handling](https://github.com/WebAssembly/exception-handling), including tags
+    /// and `exnref`.
+    Exceptions,
+    /// [Memories and tables with 64-bit indices](https://github.com/WebAssembly/memory64).
+    Memory64,
+    /// [More than one memory](https://github.com/WebAssembly/multi-memory).
+    MultiMemory,
+    /// [Typed function references](https://github.com/WebAssembly/function-references).
+    FunctionReferences,
+}
+
+impl Feature {
+    /// Gets the name of the WebAssembly proposal corresponding to the feature.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::MutableGlobal => "mutable-global",
+            Self::NonTrappingFloatToInt => "nontrapping-float-to-int-conversions",
+            Self::SignExtension => "sign-extension-ops",
+            Self::MultiValue => "multi-value",
+            Self::ReferenceTypes => "reference-types",
+            Self::BulkMemory => "bulk-memory",
+            Self::Simd => "simd",
+            Self::TailCall => "tail-call",
+            Self::Threads => "threads",
+            Self::Exceptions => "exception-handling",
+            Self::Memory64 => "memory64",
+            Self::MultiMemory => "multi-memory",
+            Self::FunctionReferences => "function-references",
+        }
+    }
+
+    /// Returns `true` if the feature is included in the
+    /// [2.0 release](https://www.w3.org/TR/wasm-core-2/) of WebAssembly.
+    ///
+    /// The [`InstructionSet::WASM_2_0`](crate::instruction_set::InstructionSet::WASM_2_0) contains
+    /// the instructions introduced by these features.
+    pub const fn is_from_wasm_2_0(self) -> bool {
+        matches!(
+            self,
+            Self::MutableGlobal
+                | Self::NonTrappingFloatToInt
+                | Self::SignExtension
+                | Self::MultiValue
+                | Self::ReferenceTypes
+                | Self::BulkMemory
+                | Self::Simd
+        )
+    }
+}
+
+impl core::fmt::Display for Feature {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Parses all sections of a WebAssembly module, including function bodies, and returns the set
+/// of [`Feature`]s that it uses.
+///
+/// This is a shorthand for visiting the module with a
+/// [`FeatureDetector`](crate::analysis::FeatureDetector).
+///
+/// # Errors
+///
+/// Returns an error if any part of the module could not be parsed.
+///
+/// # Example
+///
+/// ```
+/// use wasmiter::features::Feature;
+///
+/// # #[cfg(feature = "threads")]
+/// # fn main() -> Result<(), wasmiter::parser::Error> {
+/// let wasm = wat::parse_str(r#"(module
+///     (memory 1 1 shared)
+///     (func (param i32) (result i32)
+///         local.get 0
+///         i32.const 1
+///         i32.atomic.rmw.add))"#).unwrap();
+///
+/// let features = wasmiter::features::detect(wasm.as_slice())?;
+/// assert_eq!(features.into_iter().collect::<Vec<_>>(), [Feature::Threads]);
+/// assert!(!Feature::Threads.is_from_wasm_2_0());
+/// # Ok(())
+/// # }
+/// # #[cfg(not(feature = "threads"))]
+/// # fn main() {}
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub fn detect<I: crate::input::Input>(
+    binary: I,
+) -> crate::parser::Parsed<alloc::collections::BTreeSet<Feature>> {
+    let mut detector = crate::analysis::FeatureDetector::new();
+    crate::visit::visit_module(binary, &mut detector)?;
+    Ok(detector.into_detected())
+}
@@ -246,4 +246,9 @@ indices! {
     /// introduced as part of the
     /// [exception handling proposal](https://github.com/WebAssembly/exception-handling).
     struct TagIdx = "tag index";
+
+    /// A [`fieldidx`](https://webassembly.github.io/gc/core/syntax/modules.html#indices) refers to
+    /// a field of a structure type, introduced as part of the
+    /// [garbage collection proposal](https://github.com/WebAssembly/gc).
+    struct FieldIdx = "field index";
 }
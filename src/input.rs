@@ -3,15 +3,25 @@
 //! The [`Input`] trait provides this functionality, and is used by [`wasmiter`](crate) to parse a
 //! WebAssembly binary from a source.
 
+#[cfg(feature = "std")]
+mod buffered;
 mod convert;
 mod error;
 mod hex_dump;
 mod input_impls;
+#[cfg(feature = "mmap")]
+mod shared_mmap;
 mod window;
 
-pub use convert::{BorrowInput, CloneInput, HasInput};
-pub use error::Error;
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+pub use buffered::BufferedInput;
+pub use convert::{BorrowInput, CloneInput, HasInput, IntoOwned};
+pub use error::{Error, ErrorKind};
 pub use hex_dump::{HexDump, HexDumpRow};
+#[cfg(feature = "mmap")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "mmap")))]
+pub use shared_mmap::SharedMmap;
 pub use window::Window;
 
 /// Result type used when an operation to read [`Input`] fails.
@@ -20,13 +30,13 @@ pub type Result<T> = core::result::Result<T, Error>;
 #[cold]
 #[inline(never)]
 pub(crate) fn out_of_bounds(offset: u64, length: Option<u64>) -> Error {
-    Error::new(error::ErrorKind::OutOfBounds, offset, length)
+    Error::new(ErrorKind::OutOfBounds, offset, length)
 }
 
 #[cold]
 #[inline(never)]
 pub(crate) fn offset_overflowed(offset: u64) -> Error {
-    Error::new(error::ErrorKind::OffsetOverflow, offset, None)
+    Error::new(ErrorKind::OffsetOverflow, offset, None)
 }
 
 #[inline]
@@ -61,7 +71,9 @@ pub trait Input {
     ///
     /// # Errors
     ///
-    /// The attempt to read the bytes failed for some reason, such as an I/O error.
+    /// The attempt to read the bytes failed for some reason, such as an I/O error. The
+    /// [`ErrorKind`] of the returned [`Error`] should describe the failure, allowing the caller to
+    /// distinguish between the end of the input being reached and the input itself failing.
     fn read_at<'b>(&self, offset: u64, buffer: &'b mut [u8]) -> Result<&'b mut [u8]>;
 
     /// Calculates the maximum number of bytes that can at read the given `offset`.
@@ -108,7 +120,7 @@ pub trait Input {
 
         if copied.len() != buffer_length {
             return Err(Error::new(
-                error::ErrorKind::CannotFillBuffer,
+                ErrorKind::UnexpectedEof,
                 offset,
                 copied.len().try_into().ok(),
             ));
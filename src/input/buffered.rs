@@ -0,0 +1,220 @@
+use crate::input::{Input, Result};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+struct Block {
+    /// The offset to the first byte of the block, always a multiple of the block size.
+    start: u64,
+    /// The number of bytes that were read into the block.
+    length: usize,
+    /// Used to determine which block was least recently used.
+    last_used: u64,
+    bytes: Box<[u8]>,
+}
+
+struct Cache {
+    blocks: Vec<Block>,
+    clock: u64,
+}
+
+/// Adapts an [`Input`] implementation to cache the bytes that were recently read, so that
+/// repeated reads of the same bytes do not have to access the underlying source every time.
+///
+/// Bytes are read from the inner [`Input`] in blocks of a fixed size, and the least recently used
+/// block is replaced when the cache is full. This is useful for [`Input`] implementations where
+/// each call to [`read_at`](Input::read_at) is expensive, such as those that perform a system call
+/// for every read, since parsers and the WAT printer often read the same bytes several times.
+///
+/// Calls to [`length_at`](Input::length_at) are not cached, and reads that are larger than the
+/// entire cache are passed directly to the inner [`Input`].
+///
+/// # Example
+///
+/// ```
+/// use wasmiter::input::{BufferedInput, Input};
+///
+/// let bytes: &[u8] = b"The quick brown fox jumps over the lazy dog";
+/// let input = BufferedInput::with_block_size_and_count(bytes, 16, 2);
+///
+/// let mut buffer = [0u8; 5];
+/// assert_eq!(input.read_at(10, &mut buffer)?, b"brown");
+/// assert_eq!(input.read_at(16, &mut buffer)?, b"fox j");
+/// assert_eq!(input.read_at(41, &mut buffer)?, b"og");
+/// # wasmiter::input::Result::Ok(())
+/// ```
+pub struct BufferedInput<I: Input> {
+    inner: I,
+    block_size: usize,
+    block_count: usize,
+    cache: Mutex<Cache>,
+}
+
+impl<I: Input> BufferedInput<I> {
+    /// The default size, in bytes, of each cached block.
+    pub const DEFAULT_BLOCK_SIZE: usize = 4096;
+
+    /// The default maximum number of cached blocks.
+    pub const DEFAULT_BLOCK_COUNT: usize = 8;
+
+    /// Creates a new [`BufferedInput`] using the
+    /// [default block size](BufferedInput::DEFAULT_BLOCK_SIZE) and
+    /// [block count](BufferedInput::DEFAULT_BLOCK_COUNT).
+    #[inline]
+    pub fn new(inner: I) -> Self {
+        Self::with_block_size_and_count(inner, Self::DEFAULT_BLOCK_SIZE, Self::DEFAULT_BLOCK_COUNT)
+    }
+
+    /// Creates a new [`BufferedInput`] that caches at most `block_count` blocks of `block_size`
+    /// bytes each.
+    ///
+    /// A `block_size` or `block_count` of `0` is treated as `1`.
+    pub fn with_block_size_and_count(inner: I, block_size: usize, block_count: usize) -> Self {
+        let block_count = block_count.max(1);
+        Self {
+            inner,
+            block_size: block_size.max(1),
+            block_count,
+            cache: Mutex::new(Cache {
+                blocks: Vec::with_capacity(block_count),
+                clock: 0,
+            }),
+        }
+    }
+
+    /// Gets the size, in bytes, of each cached block.
+    #[inline]
+    pub fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    /// Gets the maximum number of cached blocks.
+    #[inline]
+    pub fn block_count(&self) -> usize {
+        self.block_count
+    }
+
+    /// Gets a reference to the inner [`Input`], bypassing the cache.
+    #[inline]
+    pub fn inner(&self) -> &I {
+        &self.inner
+    }
+
+    /// Returns the inner [`Input`], discarding any cached bytes.
+    #[inline]
+    pub fn into_inner(self) -> I {
+        self.inner
+    }
+
+    /// Discards all cached bytes, such as when the contents of the inner [`Input`] have changed.
+    pub fn clear(&self) {
+        self.lock().blocks.clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Cache> {
+        // Blocks are only inserted after they are completely read, so the cache is never left in
+        // an inconsistent state
+        self.cache.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Gets the index into the cache of the block containing the given `offset`, reading it from
+    /// the inner [`Input`] if it is not already cached.
+    fn load(&self, cache: &mut Cache, offset: u64) -> Result<usize> {
+        let block_size = u64::try_from(self.block_size).unwrap_or(u64::MAX);
+        let start = offset - offset % block_size;
+        cache.clock = cache.clock.wrapping_add(1);
+        let now = cache.clock;
+
+        if let Some(index) = cache.blocks.iter().position(|block| block.start == start) {
+            cache.blocks[index].last_used = now;
+            return Ok(index);
+        }
+
+        let mut bytes = if cache.blocks.len() < self.block_count {
+            vec![0u8; self.block_size].into_boxed_slice()
+        } else {
+            let evicted = cache
+                .blocks
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, block)| block.last_used)
+                .map_or(0, |(index, _)| index);
+
+            cache.blocks.swap_remove(evicted).bytes
+        };
+
+        let length = self.inner.read_at(start, &mut bytes)?.len();
+        cache.blocks.push(Block {
+            start,
+            length,
+            last_used: now,
+            bytes,
+        });
+
+        Ok(cache.blocks.len() - 1)
+    }
+}
+
+impl<I: Input> Input for BufferedInput<I> {
+    fn read_at<'b>(&self, offset: u64, buffer: &'b mut [u8]) -> Result<&'b mut [u8]> {
+        if buffer.len() > self.block_size.saturating_mul(self.block_count) {
+            return self.inner.read_at(offset, buffer);
+        }
+
+        let mut cache = self.lock();
+        let mut copied = 0;
+        while copied < buffer.len() {
+            let current = match u64::try_from(copied)
+                .ok()
+                .and_then(|amount| offset.checked_add(amount))
+            {
+                Some(current) => current,
+                None => break,
+            };
+
+            let block = match self.load(&mut cache, current) {
+                Ok(index) => &cache.blocks[index],
+                // Let the inner input produce the error for the original offset
+                Err(_) if copied == 0 => return self.inner.read_at(offset, buffer),
+                Err(e) => return Err(e),
+            };
+
+            let available = usize::try_from(current - block.start)
+                .ok()
+                .and_then(|start| block.bytes[..block.length].get(start..))
+                .unwrap_or_default();
+
+            if available.is_empty() {
+                if copied == 0 {
+                    // Also handles offsets past the end of the inner input
+                    return self.inner.read_at(offset, buffer);
+                }
+                break;
+            }
+
+            let amount = available.len().min(buffer.len() - copied);
+            buffer[copied..copied + amount].copy_from_slice(&available[..amount]);
+            copied += amount;
+
+            if block.length < self.block_size {
+                // Block was only partially filled, so no bytes follow it
+                break;
+            }
+        }
+
+        Ok(&mut buffer[..copied])
+    }
+
+    #[inline]
+    fn length_at(&self, offset: u64) -> Result<u64> {
+        self.inner.length_at(offset)
+    }
+}
+
+impl<I: Input + core::fmt::Debug> core::fmt::Debug for BufferedInput<I> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("BufferedInput")
+            .field("inner", &self.inner)
+            .field("block_size", &self.block_size)
+            .field("block_count", &self.block_count)
+            .finish_non_exhaustive()
+    }
+}
@@ -23,3 +23,52 @@ pub trait CloneInput<'a, I: Clone + Input + 'a>: HasInput<&'a I> {
     /// Clones the underlying [`Input`].
     fn clone_input(&self) -> Self::Cloned;
 }
+
+/// Trait for converting a type that borrows its underlying [`Input`] into one that owns a clone
+/// of it.
+///
+/// This is implemented for every type that implements [`CloneInput`], so that parsers obtained
+/// through [`BorrowInput`] can be stored in long-lived structures in the same way, regardless of
+/// their type.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::Arc;
+/// use wasmiter::{input::IntoOwned as _, sections::SectionSequence};
+///
+/// # #[cfg(feature = "alloc")]
+/// # fn main() -> Result<(), wasmiter::parser::Error> {
+/// struct Cached {
+///     sections: SectionSequence<Arc<[u8]>>,
+/// }
+///
+/// let wasm: Arc<[u8]> = wat::parse_str("(module (func))").unwrap().into();
+/// let sections = wasmiter::parse_module_sections(&wasm)?;
+/// let cached = Cached { sections: sections.into_owned() };
+/// assert_eq!(cached.sections.count(), 3);
+/// # Ok(())
+/// # }
+/// # #[cfg(not(feature = "alloc"))]
+/// # fn main() {}
+/// ```
+pub trait IntoOwned<'a, I: Clone + Input + 'a>: CloneInput<'a, I> {
+    /// Creates a copy of `self` that owns a clone of the underlying [`Input`].
+    ///
+    /// This is equivalent to [`CloneInput::clone_input`].
+    #[inline]
+    fn to_owned_input(&self) -> Self::Cloned {
+        self.clone_input()
+    }
+
+    /// Converts `self` into a type that owns a clone of the underlying [`Input`].
+    #[inline]
+    fn into_owned(self) -> Self::Cloned
+    where
+        Self: Sized,
+    {
+        self.clone_input()
+    }
+}
+
+impl<'a, I: Clone + Input + 'a, T: CloneInput<'a, I> + ?Sized> IntoOwned<'a, I> for T {}
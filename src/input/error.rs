@@ -1,18 +1,47 @@
 use core::fmt::{Display, Formatter};
 
-#[derive(Clone, Copy, Debug)]
-pub(super) enum ErrorKind {
+/// Describes the kind of [`Error`] that occured when reading from an
+/// [`Input`](crate::input::Input).
+///
+/// Custom [`Input`](crate::input::Input) implementations can use these to distinguish between
+/// errors caused by reaching the end of the available bytes, such as
+/// [`OutOfBounds`](ErrorKind::OutOfBounds) and [`UnexpectedEof`](ErrorKind::UnexpectedEof), and
+/// errors that indicate the [`Input`](crate::input::Input) itself failed, such as
+/// [`Unsupported`](ErrorKind::Unsupported) and [`Io`](ErrorKind::Io). Callers that parse
+/// incrementally, such as from a stream where more bytes may become available later, can check
+/// [`ErrorKind::is_incomplete`] to decide whether to retry parsing or to abort.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// An attempt was made to read at an offset past the end of the input.
     OutOfBounds,
-    CannotFillBuffer,
+    /// The end of the input was reached before a buffer could be completely filled.
+    UnexpectedEof,
+    /// An offset into the input could not be incremented without overflowing.
     OffsetOverflow,
+    /// The input does not support the requested operation.
+    Unsupported,
+    /// An I/O error occured when reading from the input.
+    Io,
+}
+
+impl ErrorKind {
+    /// Returns `true` if the error was caused by the input ending before all the requested bytes
+    /// could be read, meaning that the operation may succeed if more bytes are later provided.
+    #[inline]
+    pub const fn is_incomplete(self) -> bool {
+        matches!(self, Self::OutOfBounds | Self::UnexpectedEof)
+    }
 }
 
 impl Display for ErrorKind {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         f.write_str(match self {
             Self::OutOfBounds => "operation would be out of bounds",
-            Self::CannotFillBuffer => "buffer could not be completely filled",
+            Self::UnexpectedEof => "buffer could not be completely filled",
             Self::OffsetOverflow => "offset would overflow",
+            Self::Unsupported => "operation is not supported by the input",
+            Self::Io => "an I/O error occured",
         })
     }
 }
@@ -22,7 +51,9 @@ cfg_if::cfg_if! {
         struct BoxedError {
             kind: ErrorKind,
             offset: u64,
-            length: Option<u64>
+            length: Option<u64>,
+            #[cfg(feature = "std")]
+            source: Option<std::io::Error>,
         }
 
         type ErrorInner = alloc::boxed::Box<BoxedError>;
@@ -49,12 +80,24 @@ impl Error {
     const _SIZE_CHECK: [(); 1] =
         [(); (core::mem::size_of::<Option<Self>>() == core::mem::size_of::<usize>()) as usize];
 
+    /// Creates a new [`Error`] that occured at the given `offset` into the
+    /// [`Input`](crate::input::Input).
+    ///
+    /// The `length` is the number of bytes remaining in the [`Input`](crate::input::Input) at the
+    /// `offset`, if it is known. Depending on the enabled features, the `offset` and `length` may
+    /// not be stored.
     #[must_use]
-    pub(super) fn new(kind: ErrorKind, offset: u64, length: Option<u64>) -> Self {
+    pub fn new(kind: ErrorKind, offset: u64, length: Option<u64>) -> Self {
         cfg_if::cfg_if! {
             if #[cfg(feature = "alloc")] {
                 Self {
-                    inner: alloc::boxed::Box::new(BoxedError { kind, offset, length }),
+                    inner: alloc::boxed::Box::new(BoxedError {
+                        kind,
+                        offset,
+                        length,
+                        #[cfg(feature = "std")]
+                        source: None,
+                    }),
                 }
             } else {
                 let _ = length;
@@ -69,7 +112,9 @@ impl Error {
         }
     }
 
-    fn kind(&self) -> ErrorKind {
+    /// Gets the kind of error that occured.
+    #[inline]
+    pub fn kind(&self) -> ErrorKind {
         cfg_if::cfg_if! {
             if #[cfg(feature = "alloc")] {
                 self.inner.kind
@@ -106,6 +151,11 @@ impl core::fmt::Debug for Error {
         cfg_if::cfg_if! {
             if #[cfg(feature = "alloc")] {
                 s.field("offset", &self.offset()).field("length", &self.length());
+
+                #[cfg(feature = "std")]
+                if let Some(source) = self.io_error() {
+                    s.field("source", source);
+                }
             } else {
                 if let InlineError::WithOffset { offset, .. } = self.inner {
                     s.field("offset", &offset);
@@ -141,4 +191,73 @@ impl Display for Error {
 
 #[cfg(feature = "std")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
-impl std::error::Error for Error {}
+impl Error {
+    /// Creates a new [`Error`] from a [`std::io::Error`] that occured at the given `offset`.
+    ///
+    /// The [`ErrorKind`] is derived from the [`std::io::ErrorKind`], with
+    /// [`UnexpectedEof`](std::io::ErrorKind::UnexpectedEof) and
+    /// [`Unsupported`](std::io::ErrorKind::Unsupported) being mapped to their equivalents, and
+    /// all other kinds being mapped to [`ErrorKind::Io`]. The original error is kept, and can be
+    /// obtained again with [`Error::io_error`] or by converting back into a [`std::io::Error`].
+    ///
+    /// A `From<std::io::Error>` implementation is not provided, since the `offset` where the
+    /// error occured would be unknown.
+    pub fn from_io_error(error: std::io::Error, offset: u64) -> Self {
+        let kind = match error.kind() {
+            std::io::ErrorKind::UnexpectedEof => ErrorKind::UnexpectedEof,
+            std::io::ErrorKind::Unsupported => ErrorKind::Unsupported,
+            _ => ErrorKind::Io,
+        };
+
+        let mut converted = Self::new(kind, offset, None);
+        converted.inner.source = Some(error);
+        converted
+    }
+
+    /// Gets the [`std::io::Error`] that this [`Error`] was created from with
+    /// [`Error::from_io_error`].
+    #[inline]
+    pub fn io_error(&self) -> Option<&std::io::Error> {
+        self.inner.source.as_ref()
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.inner
+            .source
+            .as_ref()
+            .map(|error| error as &(dyn std::error::Error + 'static))
+    }
+}
+
+/// Converts an [`Error`] into a [`std::io::Error`], returning the original error if it was
+/// created with [`Error::from_io_error`].
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl From<Error> for std::io::Error {
+    fn from(mut error: Error) -> Self {
+        if let Some(source) = error.inner.source.take() {
+            return source;
+        }
+
+        let kind = match error.kind() {
+            ErrorKind::OutOfBounds | ErrorKind::UnexpectedEof => std::io::ErrorKind::UnexpectedEof,
+            ErrorKind::OffsetOverflow => std::io::ErrorKind::InvalidInput,
+            ErrorKind::Unsupported => std::io::ErrorKind::Unsupported,
+            ErrorKind::Io => std::io::ErrorKind::Other,
+        };
+
+        std::io::Error::new(kind, error)
+    }
+}
+
+#[cfg(feature = "mem-usage")]
+impl crate::mem_usage::MemUsage for Error {
+    #[inline]
+    fn mem_usage(&self) -> usize {
+        core::mem::size_of::<BoxedError>()
+    }
+}
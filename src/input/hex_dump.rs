@@ -266,13 +266,8 @@ impl<I: Input> Display for HexDump<I> {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         const OFFSET_HEADER: &str = "offset";
 
-        let max_width = self
-            .window
-            .base()
-            .saturating_add(self.window.length())
-            .checked_ilog(16)
-            .unwrap_or(0u32)
-            .saturating_add(1);
+        let max_offset = self.window.base().saturating_add(self.window.length());
+        let max_width = core::cmp::max((u64::BITS - max_offset.leading_zeros() + 3) / 4, 1);
 
         let offset_width = core::cmp::max(crate::int::u32_to_usize(max_width), OFFSET_HEADER.len());
 
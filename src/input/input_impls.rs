@@ -71,6 +71,29 @@ impl Input for memmap2::Mmap {
     }
 }
 
+/// Allows reading bytes from the buffer underlying a [`Cursor`](std::io::Cursor).
+///
+/// Offsets are always relative to the start of the buffer, so the
+/// [position](std::io::Cursor::position) of the cursor is ignored.
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl<T: AsRef<[u8]>> Input for std::io::Cursor<T> {
+    #[inline]
+    fn read_at<'b>(&self, offset: u64, buffer: &'b mut [u8]) -> Result<&'b mut [u8]> {
+        <[u8] as Input>::read_at(self.get_ref().as_ref(), offset, buffer)
+    }
+
+    #[inline]
+    fn length_at(&self, offset: u64) -> Result<u64> {
+        <[u8] as Input>::length_at(self.get_ref().as_ref(), offset)
+    }
+
+    #[inline]
+    fn try_eq_at(&self, offset: u64, bytes: &[u8]) -> Result<bool> {
+        <[u8] as Input>::try_eq_at(self.get_ref().as_ref(), offset, bytes)
+    }
+}
+
 macro_rules! delegated_input_impl {
     ($b:ident in $($implementor:ty $(,)?)+) => {$(
         impl<$b: Input + ?Sized> Input for $implementor {
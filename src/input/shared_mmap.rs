@@ -0,0 +1,96 @@
+use crate::{
+    input::{Input, Result},
+    parser,
+};
+use alloc::sync::Arc;
+use memmap2::Mmap;
+use std::path::Path;
+
+/// A memory mapped file that can be cheaply cloned and shared across threads.
+///
+/// Each clone refers to the same mapping, so many concurrent parsing tasks can read from a single
+/// file without mapping it again. Unlike [`Mmap::map`], opening a [`SharedMmap`] does not require
+/// any `unsafe` code; see the [`Input`] implementation for [`Mmap`] for more information.
+///
+/// # Example
+///
+/// ```no_run
+/// use wasmiter::input::SharedMmap;
+///
+/// let binary = SharedMmap::open("module.wasm")?;
+/// let handles = (0..4)
+///     .map(|_| {
+///         let binary = binary.clone();
+///         std::thread::spawn(move || {
+///             let sections = wasmiter::parse_module_sections(binary)?;
+///             wasmiter::parser::Parsed::Ok(sections.display_module().to_string())
+///         })
+///     })
+///     .collect::<Vec<_>>();
+///
+/// for handle in handles {
+///     println!("{}", handle.join().unwrap()?);
+/// }
+/// # Ok::<(), wasmiter::parser::Error>(())
+/// ```
+///
+/// [`Mmap::map`]: memmap2::Mmap::map
+#[derive(Clone, Debug)]
+pub struct SharedMmap {
+    map: Arc<Mmap>,
+}
+
+impl SharedMmap {
+    /// Memory maps the file at the given [`Path`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file could not be opened or mapped.
+    pub fn open<P: AsRef<Path>>(path: P) -> parser::Parsed<Self> {
+        crate::mmap::map_file(path.as_ref()).map(Self::from)
+    }
+
+    /// Gets the underlying memory map.
+    #[inline]
+    pub fn as_mmap(&self) -> &Arc<Mmap> {
+        &self.map
+    }
+}
+
+impl From<Mmap> for SharedMmap {
+    #[inline]
+    fn from(map: Mmap) -> Self {
+        Self { map: Arc::new(map) }
+    }
+}
+
+impl From<Arc<Mmap>> for SharedMmap {
+    #[inline]
+    fn from(map: Arc<Mmap>) -> Self {
+        Self { map }
+    }
+}
+
+impl AsRef<[u8]> for SharedMmap {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        &self.map
+    }
+}
+
+impl Input for SharedMmap {
+    #[inline]
+    fn read_at<'b>(&self, offset: u64, buffer: &'b mut [u8]) -> Result<&'b mut [u8]> {
+        <[u8] as Input>::read_at(self.as_ref(), offset, buffer)
+    }
+
+    #[inline]
+    fn length_at(&self, offset: u64) -> Result<u64> {
+        <[u8] as Input>::length_at(self.as_ref(), offset)
+    }
+
+    #[inline]
+    fn try_eq_at(&self, offset: u64, bytes: &[u8]) -> Result<bool> {
+        <[u8] as Input>::try_eq_at(self.as_ref(), offset, bytes)
+    }
+}
@@ -69,6 +69,50 @@ impl<I: Input> Window<I> {
         self.inner
     }
 
+    /// Copies the contents of the [`Window`] to the given `writer` in fixed-size chunks, returning
+    /// the number of bytes copied.
+    ///
+    /// Exactly [`length`](Window::length) bytes are copied, without buffering the entire contents
+    /// in memory.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wasmiter::input::Window;
+    ///
+    /// let bytes: &[u8] = b"This is a test of the Window struct";
+    /// let mut copied = Vec::new();
+    /// assert_eq!(Window::with_offset_and_length(bytes, 10, 4).copy_to(&mut copied)?, 4);
+    /// assert_eq!(copied, b"test");
+    /// # Ok::<(), wasmiter::parser::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the [`Input`] ends before the end of the [`Window`], or if the bytes
+    /// could not be read or written.
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    pub fn copy_to<W: std::io::Write + ?Sized>(
+        &self,
+        writer: &mut W,
+    ) -> crate::parser::Parsed<u64> {
+        let mut buffer = [0u8; 4096];
+        let mut copied = 0u64;
+        while copied < self.length {
+            let chunk = match usize::try_from(self.length - copied) {
+                Ok(remaining) if remaining < buffer.len() => &mut buffer[..remaining],
+                _ => &mut buffer,
+            };
+
+            self.read_exact_at(self.base + copied, chunk)?;
+            writer.write_all(chunk)?;
+            copied += chunk.len() as u64;
+        }
+
+        Ok(copied)
+    }
+
     #[inline]
     pub(super) fn advance(&mut self, amount: u64) -> Result<()> {
         if self.length > 0 {
@@ -94,7 +138,7 @@ impl<I: Input> Window<I> {
             Ok(self.length - (offset - self.base))
         } else {
             Err(input::Error::new(
-                input::error::ErrorKind::OutOfBounds,
+                input::ErrorKind::OutOfBounds,
                 offset,
                 self.inner.length_at(offset).ok(),
             ))
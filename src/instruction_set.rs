@@ -1,13 +1,22 @@
 //! Model of the
 //! [WebAssembly instruction set](https://webassembly.github.io/spec/core/syntax/instructions.html).
 
+#[cfg(feature = "exceptions")]
+mod catch_clause;
+mod if_arms;
 mod instruction;
+mod instruction_opcode;
 mod instruction_sequence;
 mod is_constant;
 mod memarg;
 mod opcode;
 mod prefixed_opcode;
+mod stack_effect;
+#[cfg(feature = "alloc")]
+mod type_checker;
+mod v128;
 mod vector_opcode;
+mod versions;
 
 #[doc(no_inline)]
 pub use crate::index::{LabelIdx, LocalIdx};
@@ -15,13 +24,69 @@ pub use crate::index::{LabelIdx, LocalIdx};
 #[doc(no_inline)]
 pub use crate::types::BlockType;
 
-pub use instruction::{Instruction, LaneIdx};
-pub use instruction_sequence::InstructionSequence;
+#[cfg(feature = "exceptions")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "exceptions")))]
+pub use catch_clause::{CatchClause, CatchClauseIter, CatchClauses};
+pub use if_arms::{if_arms, IfArms};
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub use instruction::OwnedInstruction;
+pub use instruction::{Instruction, LaneIdx, VisitInstruction};
+pub use instruction_opcode::InstructionOpcode;
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub use instruction_sequence::OwnedInstructions;
+pub use instruction_sequence::{BlockTerminator, DebugInstructionSequence, InstructionSequence};
 pub use is_constant::IsConstant;
 pub use memarg::{Align, MemArg};
 pub use opcode::{InvalidOpcode, Opcode};
 pub use prefixed_opcode::{FCPrefixedOpcode, FEPrefixedOpcode};
+pub use stack_effect::StackEffect;
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub use type_checker::{TypeChecker, TypeContext};
+
+pub use v128::V128;
 pub use vector_opcode::VectorOpcode;
+pub use versions::InstructionSet;
+
+#[cfg(feature = "alloc")]
+pub(crate) use instruction::variant_named;
+#[cfg(feature = "alloc")]
+pub(crate) use type_checker::is_subtype;
+
+/// Reads an [`expr`](https://webassembly.github.io/spec/core/syntax/instructions.html) starting at
+/// the given `offset`, without requiring that it be contained within a section.
+///
+/// The returned [`InstructionSequence`] has a [nesting level](InstructionSequence::nesting_level)
+/// of `1`, meaning that it is finished once the final [**end**](Instruction::End) instruction is
+/// parsed. This is useful for reading expressions embedded in custom sections or arbitrary
+/// buffers.
+///
+/// # Example
+///
+/// ```
+/// use wasmiter::instruction_set::{self, Instruction};
+///
+/// let bytes = [0x41u8, 42, 0x0B]; // i32.const 42, end
+/// let mut expression = instruction_set::parse_expression(0, bytes.as_slice());
+///
+/// let value = expression.next(|instruction| match instruction {
+///     Instruction::I32Const(n) => wasmiter::parser::Parsed::Ok(*n),
+///     _ => unreachable!(),
+/// });
+/// assert_eq!(value.transpose()?, Some(42));
+/// assert_eq!(expression.finish()?, (false, 3));
+/// # Ok::<(), wasmiter::parser::Error>(())
+/// ```
+#[inline]
+pub fn parse_expression<O, I>(offset: O, input: I) -> InstructionSequence<O, I>
+where
+    O: crate::parser::Offset,
+    I: crate::input::Input,
+{
+    InstructionSequence::new(offset, input)
+}
 
 /// Error type used when an encoded `u32` value is not a valid prefixed opcode.
 #[derive(Clone, Debug)]
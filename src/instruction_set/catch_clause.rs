@@ -0,0 +1,265 @@
+use crate::{
+    component,
+    index::{LabelIdx, TagIdx},
+    input::{BorrowInput, CloneInput, HasInput, Input},
+    parser::{self, Error, ErrorKind, Offset, Parsed, ResultExt as _, Vector},
+};
+
+/// A
+/// [catch clause](https://webassembly.github.io/exception-handling/core/syntax/instructions.html#control-instructions)
+/// of a [**try_table**](crate::instruction_set::Instruction::TryTable) instruction, which
+/// specifies which exceptions are handled and the label that is branched to when one is caught.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum CatchClause {
+    /// A **catch** clause, which handles exceptions with the given [`Tag`](component::Tag) by
+    /// branching to the `label` with the exception's arguments.
+    Catch {
+        /// The tag of the exceptions that are caught.
+        tag: TagIdx,
+        /// The label that is branched to.
+        label: LabelIdx,
+    },
+    /// A **catch_ref** clause, which handles exceptions with the given [`Tag`](component::Tag) by
+    /// branching to the `label` with the exception's arguments followed by an `exnref`.
+    CatchRef {
+        /// The tag of the exceptions that are caught.
+        tag: TagIdx,
+        /// The label that is branched to.
+        label: LabelIdx,
+    },
+    /// A **catch_all** clause, which handles all exceptions by branching to the given label.
+    CatchAll(LabelIdx),
+    /// A **catch_all_ref** clause, which handles all exceptions by branching to the given label
+    /// with an `exnref`.
+    CatchAllRef(LabelIdx),
+}
+
+impl CatchClause {
+    /// Gets the tag of the exceptions handled by this clause, or `None` if all exceptions are
+    /// handled.
+    #[inline]
+    pub const fn tag(&self) -> Option<TagIdx> {
+        match self {
+            Self::Catch { tag, .. } | Self::CatchRef { tag, .. } => Some(*tag),
+            Self::CatchAll(_) | Self::CatchAllRef(_) => None,
+        }
+    }
+
+    /// Gets the label that is branched to when an exception is caught.
+    #[inline]
+    pub const fn label(&self) -> LabelIdx {
+        match self {
+            Self::Catch { label, .. } | Self::CatchRef { label, .. } => *label,
+            Self::CatchAll(label) | Self::CatchAllRef(label) => *label,
+        }
+    }
+
+    /// Returns `true` if an `exnref` to the caught exception is passed to the label.
+    #[inline]
+    pub const fn is_ref(&self) -> bool {
+        matches!(self, Self::CatchRef { .. } | Self::CatchAllRef(_))
+    }
+
+    /// Gets the name of the clause in the text format.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Catch { .. } => "catch",
+            Self::CatchRef { .. } => "catch_ref",
+            Self::CatchAll(_) => "catch_all",
+            Self::CatchAllRef(_) => "catch_all_ref",
+        }
+    }
+}
+
+fn catch_clause<I: Input>(offset: &mut u64, input: &I) -> Parsed<CatchClause> {
+    let kind_offset = *offset;
+    let kind = parser::one_byte_exact(offset, input).context("catch clause kind")?;
+    Ok(match kind {
+        0 | 1 => {
+            let tag = component::index(offset, input).context("catch clause tag")?;
+            let label = component::index(offset, input).context("catch clause label")?;
+            if kind == 0 {
+                CatchClause::Catch { tag, label }
+            } else {
+                CatchClause::CatchRef { tag, label }
+            }
+        }
+        2 => CatchClause::CatchAll(component::index(offset, input).context("catch_all label")?),
+        3 => CatchClause::CatchAllRef(
+            component::index(offset, input).context("catch_all_ref label")?,
+        ),
+        bad => {
+            #[inline(never)]
+            #[cold]
+            fn bad_kind(offset: u64, kind: u8) -> Error {
+                Error::new(ErrorKind::BadCatchClauseKind(kind))
+                    .with_location_context("catch clause", offset)
+            }
+
+            return Err(bad_kind(kind_offset, bad));
+        }
+    })
+}
+
+/// Refers to the catch clauses of a
+/// [**try_table**](crate::instruction_set::Instruction::TryTable) instruction.
+///
+/// To keep the size of an [`Instruction`](crate::instruction_set::Instruction) small, the
+/// catch clauses are only parsed when [`CatchClauses::iter`] or [`CatchClauses::finish`] is
+/// called.
+#[derive(Clone, Copy)]
+pub struct CatchClauses<O: Offset, I: Input> {
+    offset: O,
+    input: I,
+}
+
+impl<O: Offset, I: Input> CatchClauses<O, I> {
+    /// Creates a new [`CatchClauses`] for the [`Vector`] of catch clauses starting at the given
+    /// `offset` into the [`Input`].
+    #[inline]
+    pub fn new(offset: O, input: I) -> Self {
+        Self { offset, input }
+    }
+
+    /// Parses the number of catch clauses, returning an iterator over them.
+    ///
+    /// The offset of this [`CatchClauses`] is not advanced.
+    pub fn iter(&self) -> Parsed<CatchClauseIter<u64, &I>> {
+        CatchClauseIter::parse(self.offset.offset(), &self.input)
+    }
+
+    /// Parses all of the catch clauses, returning the offset to the end of the [`Vector`].
+    pub fn finish(self) -> Parsed<O> {
+        CatchClauseIter::parse(self.offset, self.input)?.finish()
+    }
+}
+
+impl<O: Offset, I: Input> HasInput<I> for CatchClauses<O, I> {
+    #[inline]
+    fn input(&self) -> &I {
+        &self.input
+    }
+}
+
+impl<'a, O: Offset, I: Input + 'a> BorrowInput<'a, I> for CatchClauses<O, I> {
+    type Borrowed = CatchClauses<u64, &'a I>;
+
+    #[inline]
+    fn borrow_input(&'a self) -> Self::Borrowed {
+        CatchClauses::new(self.offset.offset(), &self.input)
+    }
+}
+
+impl<'a, O: Offset, I: Clone + Input + 'a> CloneInput<'a, I> for CatchClauses<O, &'a I> {
+    type Cloned = CatchClauses<u64, I>;
+
+    #[inline]
+    fn clone_input(&self) -> Self::Cloned {
+        CatchClauses::new(self.offset.offset(), self.input.clone())
+    }
+}
+
+impl<O: Offset, I: Input> core::fmt::Debug for CatchClauses<O, I> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.iter() {
+            Ok(clauses) => core::fmt::Debug::fmt(&clauses, f),
+            Err(e) => f.debug_tuple("CatchClauses").field(&e).finish(),
+        }
+    }
+}
+
+/// Parses the [`Vector`] of [`CatchClause`]s of a
+/// [**try_table**](crate::instruction_set::Instruction::TryTable) instruction.
+#[derive(Clone, Copy)]
+pub struct CatchClauseIter<O: Offset, I: Input> {
+    clauses: Vector<O, I>,
+}
+
+impl<O: Offset, I: Input> CatchClauseIter<O, I> {
+    /// Creates a new [`CatchClauseIter`] with a parsed `u32` count from the given [`Input`].
+    #[inline]
+    pub fn parse(offset: O, input: I) -> Parsed<Self> {
+        Vector::parse(offset, input).map(Self::from)
+    }
+
+    /// Gets the remaining number of catch clauses.
+    #[inline]
+    pub fn remaining_count(&self) -> u32 {
+        self.clauses.remaining_count()
+    }
+
+    /// Gets the number of catch clauses that have been parsed so far.
+    #[inline]
+    pub fn consumed_count(&self) -> u32 {
+        self.clauses.consumed_count()
+    }
+
+    /// Gets the number of catch clauses that were declared.
+    #[inline]
+    pub fn declared_count(&self) -> u32 {
+        self.clauses.declared_count()
+    }
+
+    /// Parses the remaining catch clauses.
+    pub fn finish(mut self) -> Parsed<O> {
+        for result in &mut self {
+            let _ = result?;
+        }
+
+        Ok(self.clauses.into_offset())
+    }
+}
+
+impl<O: Offset, I: Input> HasInput<I> for CatchClauseIter<O, I> {
+    #[inline]
+    fn input(&self) -> &I {
+        self.clauses.input()
+    }
+}
+
+impl<'a, O: Offset, I: Input + 'a> BorrowInput<'a, I> for CatchClauseIter<O, I> {
+    type Borrowed = CatchClauseIter<u64, &'a I>;
+
+    #[inline]
+    fn borrow_input(&'a self) -> Self::Borrowed {
+        self.clauses.borrow_input().into()
+    }
+}
+
+impl<'a, O: Offset, I: Clone + Input + 'a> CloneInput<'a, I> for CatchClauseIter<O, &'a I> {
+    type Cloned = CatchClauseIter<u64, I>;
+
+    #[inline]
+    fn clone_input(&self) -> Self::Cloned {
+        self.clauses.clone_input().into()
+    }
+}
+
+impl<O: Offset, I: Input> From<Vector<O, I>> for CatchClauseIter<O, I> {
+    #[inline]
+    fn from(clauses: Vector<O, I>) -> Self {
+        Self { clauses }
+    }
+}
+
+impl<O: Offset, I: Input> Iterator for CatchClauseIter<O, I> {
+    type Item = Parsed<CatchClause>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.clauses.advance(catch_clause)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.clauses.size_hint()
+    }
+}
+
+impl<O: Offset, I: Input> core::iter::FusedIterator for CatchClauseIter<O, I> {}
+
+impl<O: Offset, I: Input> core::fmt::Debug for CatchClauseIter<O, I> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.borrow_input()).finish()
+    }
+}
@@ -0,0 +1,124 @@
+use crate::{
+    input::Input,
+    instruction_set::{BlockTerminator, Instruction, InstructionSequence, Opcode},
+    parser::{Error, ErrorKind, Parsed, ResultExt as _},
+};
+use core::ops::Range;
+
+/// Describes the location of the instructions within an [**if**](Instruction::If) block.
+///
+/// Returned by [`if_arms`].
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct IfArms {
+    then_arm: Range<u64>,
+    else_arm: Option<Range<u64>>,
+    end: u64,
+}
+
+impl IfArms {
+    /// The offsets of the instructions executed when the condition is true, excluding the
+    /// [**else**](Instruction::Else) or [**end**](Instruction::End) instruction that follows them.
+    #[inline]
+    pub fn then_arm(&self) -> Range<u64> {
+        self.then_arm.clone()
+    }
+
+    /// The offsets of the instructions executed when the condition is false, excluding the
+    /// [**else**](Instruction::Else) and [**end**](Instruction::End) instructions surrounding
+    /// them.
+    ///
+    /// Returns `None` if the block has no [**else**](Instruction::Else) instruction.
+    #[inline]
+    pub fn else_arm(&self) -> Option<Range<u64>> {
+        self.else_arm.clone()
+    }
+
+    /// The offset to the byte after the [**end**](Instruction::End) instruction of the block.
+    #[inline]
+    pub fn end(&self) -> u64 {
+        self.end
+    }
+}
+
+/// Finds the instructions making up each arm of the [**if**](Instruction::If) instruction at the
+/// given `offset`, using [`InstructionSequence::skip_block()`] to skip over their contents.
+///
+/// # Errors
+///
+/// Returns an error if the instruction at the `offset` is not an [**if**](Instruction::If)
+/// instruction, or if the instructions within the block could not be parsed.
+///
+/// # Example
+///
+/// ```
+/// use wasmiter::instruction_set;
+///
+/// // if, nop, else, nop, nop, end
+/// let bytes = [0x04u8, 0x40, 0x01, 0x05, 0x01, 0x01, 0x0B];
+/// let arms = instruction_set::if_arms(0, bytes.as_slice())?;
+/// assert_eq!(arms.then_arm(), 2..3);
+/// assert_eq!(arms.else_arm(), Some(4..6));
+/// assert_eq!(arms.end(), 7);
+/// # Ok::<(), wasmiter::parser::Error>(())
+/// ```
+pub fn if_arms<I: Input>(offset: u64, input: I) -> Parsed<IfArms> {
+    #[inline(never)]
+    #[cold]
+    fn expected_if() -> Error {
+        Error::new(ErrorKind::ExpectedIfInstruction)
+    }
+
+    #[inline(never)]
+    #[cold]
+    fn bad_terminator(terminator: BlockTerminator) -> Error {
+        Error::new(ErrorKind::BadIfBlockTerminator(match terminator {
+            BlockTerminator::End => Opcode::End,
+            BlockTerminator::Else => Opcode::Else,
+            BlockTerminator::Catch(_) => Opcode::Catch,
+            BlockTerminator::CatchAll => Opcode::CatchAll,
+            BlockTerminator::Delegate(_) => Opcode::Delegate,
+        }))
+    }
+
+    #[inline(never)]
+    #[cold]
+    fn missing_end() -> Error {
+        Error::new(ErrorKind::MissingEndInstructions(1))
+    }
+
+    let mut sequence = InstructionSequence::new(offset, input);
+    let is_if = sequence
+        .next(|instruction| Parsed::Ok(matches!(instruction, Instruction::If(_))))
+        .transpose()
+        .context("if instruction")?;
+
+    if is_if != Some(true) {
+        return Err(expected_if());
+    }
+
+    // Both the else and end instructions are encoded in a single byte
+    let then_start = sequence.offset();
+    let (then_end, terminator) = match sequence.skip_block() {
+        Some(result) => (sequence.offset() - 1, result.context("then arm")?),
+        None => return Err(missing_end()),
+    };
+
+    let else_arm = match terminator {
+        BlockTerminator::End => None,
+        BlockTerminator::Else => {
+            let else_start = then_end + 1;
+            match sequence.skip_block().transpose().context("else arm")? {
+                Some(BlockTerminator::End) => Some(else_start..sequence.offset() - 1),
+                Some(terminator) => return Err(bad_terminator(terminator)),
+                None => return Err(missing_end()),
+            }
+        }
+        terminator => return Err(bad_terminator(terminator)),
+    };
+
+    Ok(IfArms {
+        then_arm: then_start..then_end,
+        else_arm,
+        end: sequence.offset(),
+    })
+}
@@ -37,18 +37,297 @@ macro_rules! instruction_debug_impl {
     };
 }
 
+macro_rules! instruction_visit_method {
+    ($case:ident, $visit:ident, $name:literal) => {
+        #[doc = concat!("Visits the [**", $name, "**](Instruction::", stringify!($case), ") instruction.")]
+        #[inline]
+        fn $visit(&mut self) -> Self::Output {
+            self.visit_other($name)
+        }
+    };
+    // Arguments that are lazily parsed are passed by reference, so the remainder can be skipped
+    // if the visitor does not read all of it.
+    (BrTable, $visit:ident, $name:literal, ($(#[$a_meta:meta])* $a:ty)) => {
+        #[doc = concat!("Visits the [**", $name, "**](Instruction::BrTable) instruction.")]
+        #[inline]
+        fn $visit(&mut self, targets: &mut $a) -> Self::Output {
+            let _ = targets;
+            self.visit_other($name)
+        }
+    };
+    (Select, $visit:ident, $name:literal, ($(#[$a_meta:meta])* $a:ty)) => {
+        #[doc = concat!("Visits the [**", $name, "**](Instruction::Select) instruction.")]
+        #[inline]
+        fn $visit(&mut self, types: &mut $a) -> Self::Output {
+            let _ = types;
+            self.visit_other($name)
+        }
+    };
+    (TryTable, $visit:ident, $name:literal, ($(#[$a_meta:meta])* $a:ty, $(#[$b_meta:meta])* $b:ty)) => {
+        #[doc = concat!("Visits the [**", $name, "**](Instruction::TryTable) instruction.")]
+        #[inline]
+        fn $visit(&mut self, block_type: $a, clauses: &mut $b) -> Self::Output {
+            let _ = (block_type, clauses);
+            self.visit_other($name)
+        }
+    };
+    ($case:ident, $visit:ident, $name:literal, ($(#[$a_meta:meta])* $a:ty)) => {
+        #[doc = concat!("Visits the [**", $name, "**](Instruction::", stringify!($case), ") instruction.")]
+        #[inline]
+        fn $visit(&mut self, a: $a) -> Self::Output {
+            let _ = a;
+            self.visit_other($name)
+        }
+    };
+    ($case:ident, $visit:ident, $name:literal, ($(#[$a_meta:meta])* $a:ty, $(#[$b_meta:meta])* $b:ty)) => {
+        #[doc = concat!("Visits the [**", $name, "**](Instruction::", stringify!($case), ") instruction.")]
+        #[inline]
+        fn $visit(&mut self, a: $a, b: $b) -> Self::Output {
+            let _ = (a, b);
+            self.visit_other($name)
+        }
+    };
+    ($case:ident, $visit:ident, $name:literal, { $($(#[$field_meta:meta])* $field_name:ident: $field_ty:ty $(,)?)+ }) => {
+        #[doc = concat!("Visits the [**", $name, "**](Instruction::", stringify!($case), ") instruction.")]
+        #[inline]
+        fn $visit(&mut self, $($field_name: $field_ty),+) -> Self::Output {
+            let _ = ($($field_name),+);
+            self.visit_other($name)
+        }
+    };
+}
+
+macro_rules! instruction_decode_method {
+    ($visit:ident) => {
+        fn $visit(self) -> Parsed<Self::Output>;
+    };
+    ($visit:ident($(#[$a_meta:meta])* $a:ty)) => {
+        fn $visit(self, a: $a) -> Parsed<Self::Output>;
+    };
+    ($visit:ident($(#[$a_meta:meta])* $a:ty, $(#[$b_meta:meta])* $b:ty)) => {
+        fn $visit(self, a: $a, b: $b) -> Parsed<Self::Output>;
+    };
+    ($visit:ident { $($(#[$field_meta:meta])* $field_name:ident: $field_ty:ty $(,)?)+ }) => {
+        fn $visit(self, $($field_name: $field_ty),+) -> Parsed<Self::Output>;
+    };
+}
+
+macro_rules! instruction_build_method {
+    ($case:ident, $visit:ident) => {
+        #[inline]
+        fn $visit(self) -> Parsed<Self::Output> {
+            Ok(Instruction::$case)
+        }
+    };
+    ($case:ident, $visit:ident($(#[$a_meta:meta])* $a:ty)) => {
+        #[inline]
+        fn $visit(self, a: $a) -> Parsed<Self::Output> {
+            Ok(Instruction::$case(a))
+        }
+    };
+    ($case:ident, $visit:ident($(#[$a_meta:meta])* $a:ty, $(#[$b_meta:meta])* $b:ty)) => {
+        #[inline]
+        fn $visit(self, a: $a, b: $b) -> Parsed<Self::Output> {
+            Ok(Instruction::$case(a, b))
+        }
+    };
+    ($case:ident, $visit:ident { $($(#[$field_meta:meta])* $field_name:ident: $field_ty:ty $(,)?)+ }) => {
+        #[inline]
+        fn $visit(self, $($field_name: $field_ty),+) -> Parsed<Self::Output> {
+            Ok(Instruction::$case { $($field_name),+ })
+        }
+    };
+}
+
+macro_rules! instruction_visit_decoded {
+    ($case:ident, $visit:ident) => {
+        #[inline]
+        fn $visit(self) -> Parsed<Self::Output> {
+            Ok(V::$visit(self))
+        }
+    };
+    // Lazily parsed arguments are passed by reference, and any that the visitor did not read are
+    // skipped afterwards
+    (BrTable, $visit:ident($(#[$a_meta:meta])* $a:ty)) => {
+        #[inline]
+        fn $visit(self, mut targets: $a) -> Parsed<Self::Output> {
+            let output = V::$visit(self, &mut targets);
+            targets.finish().context("branch label table")?;
+            Ok(output)
+        }
+    };
+    (Select, $visit:ident($(#[$a_meta:meta])* $a:ty)) => {
+        #[inline]
+        fn $visit(self, mut types: $a) -> Parsed<Self::Output> {
+            let output = V::$visit(self, &mut types);
+            types.finish()?;
+            Ok(output)
+        }
+    };
+    (TryTable, $visit:ident($(#[$a_meta:meta])* $a:ty, $(#[$b_meta:meta])* $b:ty)) => {
+        #[inline]
+        fn $visit(self, block_type: $a, mut clauses: $b) -> Parsed<Self::Output> {
+            let output = V::$visit(self, block_type, &mut clauses);
+            clauses.finish().context("catch clauses")?;
+            Ok(output)
+        }
+    };
+    ($case:ident, $visit:ident($(#[$a_meta:meta])* $a:ty)) => {
+        #[inline]
+        fn $visit(self, a: $a) -> Parsed<Self::Output> {
+            Ok(V::$visit(self, a))
+        }
+    };
+    ($case:ident, $visit:ident($(#[$a_meta:meta])* $a:ty, $(#[$b_meta:meta])* $b:ty)) => {
+        #[inline]
+        fn $visit(self, a: $a, b: $b) -> Parsed<Self::Output> {
+            Ok(V::$visit(self, a, b))
+        }
+    };
+    ($case:ident, $visit:ident { $($(#[$field_meta:meta])* $field_name:ident: $field_ty:ty $(,)?)+ }) => {
+        #[inline]
+        fn $visit(self, $($field_name: $field_ty),+) -> Parsed<Self::Output> {
+            Ok(V::$visit(self, $($field_name),+))
+        }
+    };
+}
+
+/// Generates the [`OwnedInstruction`] enum, replacing the arguments of instructions that are lazily
+/// parsed with their contents.
+#[cfg(feature = "alloc")]
+macro_rules! owned_instruction_enum {
+    ([$($variants:tt)*]) => {
+        /// An [`Instruction`] whose arguments are completely parsed, allowing it to be stored
+        /// independently of the [`Input`] it was parsed from.
+        ///
+        /// The lazily parsed arguments of some instructions, such as the labels of a
+        /// [**br_table**](Instruction::BrTable) instruction, are collected into a [`Vec`].
+        ///
+        /// See [`Instruction::take_owned`] and
+        /// [`InstructionSequence::into_iter_owned`](crate::instruction_set::InstructionSequence::into_iter_owned)
+        /// for more information.
+        ///
+        /// [`Vec`]: alloc::vec::Vec
+        #[derive(Clone, Debug, PartialEq)]
+        #[non_exhaustive]
+        pub enum OwnedInstruction {
+            $($variants)*
+        }
+    };
+    ([$($variants:tt)*] [$cfg:meta] BrTable [$arguments:tt] = $name:literal, $($rest:tt)*) => {
+        owned_instruction_enum!([
+            $($variants)*
+            #[cfg($cfg)]
+            /// The [**br_table**](Instruction::BrTable) instruction, where the last label is the
+            /// default label.
+            BrTable(alloc::vec::Vec<LabelIdx>),
+        ] $($rest)*);
+    };
+    ([$($variants:tt)*] [$cfg:meta] Select [$arguments:tt] = $name:literal, $($rest:tt)*) => {
+        owned_instruction_enum!([
+            $($variants)*
+            #[cfg($cfg)]
+            /// The [**select**](Instruction::Select) instruction, with the types of its operands
+            /// if they were explicitly specified.
+            Select(alloc::vec::Vec<types::ValType>),
+        ] $($rest)*);
+    };
+    ([$($variants:tt)*] [$cfg:meta] TryTable [$arguments:tt] = $name:literal, $($rest:tt)*) => {
+        owned_instruction_enum!([
+            $($variants)*
+            #[cfg($cfg)]
+            /// The [**try_table**](Instruction::TryTable) instruction, with its catch clauses.
+            TryTable(BlockType, alloc::vec::Vec<crate::instruction_set::CatchClause>),
+        ] $($rest)*);
+    };
+    ([$($variants:tt)*] [$cfg:meta] $case:ident $([$arguments:tt])? = $name:literal, $($rest:tt)*) => {
+        owned_instruction_enum!([
+            $($variants)*
+            #[cfg($cfg)]
+            #[doc = concat!("The [**", $name, "**](Instruction::", stringify!($case), ") instruction.")]
+            $case $($arguments)?,
+        ] $($rest)*);
+    };
+}
+
+#[cfg(feature = "alloc")]
+#[inline(never)]
+#[cold]
+fn no_owned_form(name: &'static str) -> crate::parser::Error {
+    crate::parser::Error::new(crate::parser::ErrorKind::InvalidFormat).with_context(
+        crate::parser::Context::from_closure(move |f| {
+            write!(f, "{name} instruction has no owned form")
+        }),
+    )
+}
+
+#[cfg(feature = "alloc")]
+macro_rules! instruction_owned_impl {
+    ($e:expr, $case:ident) => {
+        if let Self::$case = $e {
+            return Ok(OwnedInstruction::$case);
+        }
+    };
+    ($e:expr, BrTable($(#[$a_meta:meta])* $a:ty)) => {
+        if let Self::BrTable(targets) = $e {
+            let targets = targets
+                .by_ref()
+                .collect::<Parsed<_>>()
+                .context("branch label table")?;
+            return Ok(OwnedInstruction::BrTable(targets));
+        }
+    };
+    ($e:expr, Select($(#[$a_meta:meta])* $a:ty)) => {
+        if let Self::Select(types) = $e {
+            return Ok(OwnedInstruction::Select(types.by_ref().collect::<Parsed<_>>()?));
+        }
+    };
+    ($e:expr, TryTable($(#[$a_meta:meta])* $a:ty, $(#[$b_meta:meta])* $b:ty)) => {
+        if let Self::TryTable(block_type, clauses) = $e {
+            let clauses = clauses
+                .iter()
+                .and_then(Iterator::collect::<Parsed<_>>)
+                .context("catch clauses")?;
+            return Ok(OwnedInstruction::TryTable(*block_type, clauses));
+        }
+    };
+    ($e:expr, $case:ident($(#[$a_meta:meta])* $a:ty)) => {
+        if let Self::$case(a) = $e {
+            return Ok(OwnedInstruction::$case(*a));
+        }
+    };
+    ($e:expr, $case:ident($(#[$a_meta:meta])* $a:ty, $(#[$b_meta:meta])* $b:ty)) => {
+        if let Self::$case(a, b) = $e {
+            return Ok(OwnedInstruction::$case(*a, *b));
+        }
+    };
+    ($e:expr, $case:ident { $($(#[$field_meta:meta])* $field_name:ident: $field_ty:ty $(,)?)+ }) => {
+        if let Self::$case { $($field_name,)+ } = $e {
+            return Ok(OwnedInstruction::$case { $($field_name: *$field_name,)+ });
+        }
+    };
+}
+
+/// A [`DecodeInstruction`] that builds an [`Instruction`].
+pub(crate) struct BuildInstruction;
+
 macro_rules! instructions {
     ($(
         $(#[$group_meta:meta])*
-        $group:ident {$(
+        $group:ident [$cfg:meta] {$(
             $(#[$meta:meta])*
-            $case:ident$([$arguments:tt])? = $name:literal,
+            $case:ident$([$arguments:tt])? = $name:literal => $visit:ident,
         )+}
     )*) => {
         /// Represents a
         /// [WebAssembly instruction](https://webassembly.github.io/spec/core/syntax/instructions.html).
+        ///
+        /// Instructions introduced by some proposals are only available when the corresponding
+        /// cargo feature is enabled, such as `simd` for the
+        /// [fixed-width SIMD proposal](https://github.com/webassembly/simd).
         #[non_exhaustive]
         pub enum Instruction<'a, I: Input> {$($(
+            #[cfg($cfg)]
             $(#[$meta])*
             $case $($arguments)?,
         )*)*}
@@ -57,17 +336,25 @@ macro_rules! instructions {
             /// Gets a string containing the name of the [`Instruction`].
             pub const fn name(&self) -> &'static str {
                 match self {
-                    $($(Self::$case { .. } => $name,)+)*
+                    $($(
+                        #[cfg($cfg)]
+                        Self::$case { .. } => $name,
+                    )+)*
                 }
             }
 
             $(
                 $(#[$group_meta])*
+                ///
+                /// Always returns `false` if the cargo feature enabling these instructions is
+                /// disabled.
+                #[allow(clippy::needless_return)]
                 pub const fn $group(&self) -> bool {
-                    match self {
-                        $(| Self::$case { .. })+ => true,
-                        _ => false,
-                    }
+                    #[cfg($cfg)]
+                    return matches!(self, $(| Self::$case { .. })+);
+
+                    #[cfg(not($cfg))]
+                    return false;
                 }
             )*
         }
@@ -75,10 +362,121 @@ macro_rules! instructions {
         impl<I: Input> core::fmt::Debug for Instruction<'_, I> {
             fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
                 $($(
+                    #[cfg($cfg)]
                     instruction_debug_impl!(f, self, $case $($arguments)?);
                 )+)*
 
-                unreachable!()
+                // Only reachable if a variant is somehow missing above
+                f.write_str(self.name())
+            }
+        }
+
+        /// Trait for visiting each kind of [`Instruction`], with one method for each instruction.
+        ///
+        /// Rather than matching on an [`Instruction`], a visitor receives the arguments of the
+        /// instruction directly, allowing interpreters and compilers to handle each kind of
+        /// instruction in its own method. Arguments that are lazily parsed, such as the labels of
+        /// a [**br_table**](Instruction::BrTable) instruction, are passed by mutable reference.
+        ///
+        /// Each method defaults to calling [`visit_other`](VisitInstruction::visit_other) with the
+        /// name of the instruction. Methods for instructions introduced by some proposals are only
+        /// available when the corresponding cargo feature is enabled.
+        ///
+        /// See [`InstructionSequence::visit`](crate::instruction_set::InstructionSequence::visit)
+        /// for more information.
+        pub trait VisitInstruction<'a, I: Input> {
+            /// The value returned by each method.
+            type Output;
+
+            /// Called for any instruction whose method is not overridden, with the `name` of the
+            /// instruction in the text format.
+            fn visit_other(&mut self, name: &'static str) -> Self::Output;
+
+            $($(
+                #[cfg($cfg)]
+                instruction_visit_method!($case, $visit, $name $(, $arguments)?);
+            )+)*
+        }
+
+        /// Receives the arguments of each kind of [`Instruction`] as it is decoded, allowing the
+        /// same decoder to either build an [`Instruction`] or call a [`VisitInstruction`].
+        pub(crate) trait DecodeInstruction<'a, I: Input> {
+            type Output;
+
+            $($(
+                #[cfg($cfg)]
+                instruction_decode_method!($visit $($arguments)?);
+            )+)*
+        }
+
+        impl<'a, I: Input> DecodeInstruction<'a, I> for BuildInstruction {
+            type Output = Instruction<'a, I>;
+
+            $($(
+                #[cfg($cfg)]
+                instruction_build_method!($case, $visit $($arguments)?);
+            )+)*
+        }
+
+        impl<'a, I, V> DecodeInstruction<'a, I> for &mut V
+        where
+            I: Input,
+            V: VisitInstruction<'a, I> + ?Sized,
+        {
+            type Output = V::Output;
+
+            $($(
+                #[cfg($cfg)]
+                instruction_visit_decoded!($case, $visit $($arguments)?);
+            )+)*
+        }
+
+        #[cfg(feature = "alloc")]
+        owned_instruction_enum!([] $($([$cfg] $case $([$arguments])? = $name,)+)*);
+
+        #[cfg(feature = "alloc")]
+        #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+        impl OwnedInstruction {
+            /// Gets a string containing the name of the [`OwnedInstruction`].
+            pub const fn name(&self) -> &'static str {
+                match self {
+                    $($(
+                        #[cfg($cfg)]
+                        Self::$case { .. } => $name,
+                    )+)*
+                }
+            }
+        }
+
+        #[cfg(feature = "alloc")]
+        #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+        impl<I: Input> Instruction<'_, I> {
+            /// Parses any lazily parsed arguments of the [`Instruction`], returning an
+            /// [`OwnedInstruction`] that does not borrow from the [`Input`].
+            ///
+            /// # Errors
+            ///
+            /// Returns an error if the arguments of the instruction could not be parsed.
+            pub fn take_owned(&mut self) -> Parsed<OwnedInstruction> {
+                $($(
+                    #[cfg($cfg)]
+                    instruction_owned_impl!(self, $case $($arguments)?);
+                )+)*
+
+                Err(no_owned_form(self.name()))
+            }
+        }
+
+        /// Gets the name of the [`Instruction`] variant with the given `name` in the text format,
+        /// used to look up its [`InstructionOpcode`](crate::instruction_set::InstructionOpcode).
+        #[cfg(feature = "alloc")]
+        pub(crate) fn variant_named(name: &str) -> Option<&'static str> {
+            match name {
+                $($(
+                    #[cfg($cfg)]
+                    $name => Some(stringify!($case)),
+                )+)*
+                _ => None,
             }
         }
     };
@@ -99,41 +497,41 @@ instructions! {
     ///
     /// Users that support only certain proposals must check themselves that instructions
     /// introduced in 1.0 do not make use of unsupported proposals.
-    is_from_mvp {
+    is_from_mvp [all()] {
         // Control Instructions
 
         /// The
         /// [**nop**](https://webassembly.github.io/spec/core/syntax/instructions.html#syntax-instr-control)
         /// instruction does nothing.
-        Nop = "nop",
+        Nop = "nop" => visit_nop,
         /// The
         /// [**unreachable**](https://webassembly.github.io/spec/core/syntax/instructions.html#syntax-instr-control)
         /// instruction causes an unconditional
         /// [trap](https://webassembly.github.io/spec/core/intro/overview.html#trap), preventing
         /// any instructions that follow from being executed.
-        Unreachable = "unreachable",
+        Unreachable = "unreachable" => visit_unreachable,
         /// The
         /// [**block**](https://webassembly.github.io/spec/core/syntax/instructions.html#syntax-instr-control)
         /// instruction marks the start of a block.
-        Block[(BlockType)] = "block",
+        Block[(BlockType)] = "block" => visit_block,
         /// The
         /// [**loop**](https://webassembly.github.io/spec/core/syntax/instructions.html#syntax-instr-control)
         /// instruction marks the start of a block where branches to the block transfer control
         /// flow to the start of the block.
-        Loop[(BlockType)] = "loop",
+        Loop[(BlockType)] = "loop" => visit_loop,
         /// The
         /// [**if**](https://webassembly.github.io/spec/core/syntax/instructions.html#syntax-instr-control)
         /// instruction marks the start of a block that control is transferred to when a condition
         /// is `true`.
-        If[(BlockType)] = "if",
+        If[(BlockType)] = "if" => visit_if,
         /// The
         /// [**br**](https://webassembly.github.io/spec/core/syntax/instructions.html#syntax-instr-control)
         /// instruction performs an unconditional branch.
-        Br[(LabelIdx)] = "br",
+        Br[(LabelIdx)] = "br" => visit_br,
         /// The
         /// [**br_if**](https://webassembly.github.io/spec/core/syntax/instructions.html#syntax-instr-control)
         /// instruction performs a conditional branch.
-        BrIf[(LabelIdx)] = "br_if",
+        BrIf[(LabelIdx)] = "br_if" => visit_br_if,
         /// The
         /// [**br_table**](https://webassembly.github.io/spec/core/syntax/instructions.html#syntax-instr-control)
         /// instruction performs an indirect branch, with the target being determined by an index
@@ -141,495 +539,495 @@ instructions! {
         ///
         /// The table of labels is encoded as a vector containing **at least one** [`LabelIdx`],
         /// with the last label specifies the default target.
-        BrTable[(component::IndexVector<LabelIdx, &'a mut u64, I>)] = "br_table",
+        BrTable[(component::IndexVector<LabelIdx, &'a mut u64, I>)] = "br_table" => visit_br_table,
         /// The
         /// [**return**](https://webassembly.github.io/spec/core/syntax/instructions.html#syntax-instr-control)
         /// instruction transfers control flow back to the calling function.
-        Return = "return",
+        Return = "return" => visit_return,
         /// The
         /// [**call**](https://webassembly.github.io/spec/core/syntax/instructions.html#syntax-instr-control)
         /// instruction calls a function.
-        Call[(FuncIdx)] = "call",
+        Call[(FuncIdx)] = "call" => visit_call,
         /// The
         /// [**call_indirect**](https://webassembly.github.io/spec/core/syntax/instructions.html#syntax-instr-control)
         /// instruction calls a function from a `funcref` stored in a table.
-        CallIndirect[(index::TypeIdx, TableIdx)] = "call_indirect",
+        CallIndirect[(index::TypeIdx, TableIdx)] = "call_indirect" => visit_call_indirect,
         /// The
         /// [**else**](https://webassembly.github.io/spec/core/syntax/instructions.html#syntax-instr-control)
         /// instruction marks the start of the block control flow is transferred to if the
         /// condition for an [**if**](Instruction::If) block is `false`.
-        Else = "else",
+        Else = "else" => visit_else,
         /// The
         /// [**end**](https://webassembly.github.io/spec/core/syntax/instructions.html#syntax-instr-control)
         /// instruction marks the end of an
         /// [expression](https://webassembly.github.io/spec/core/syntax/instructions.html#expressions)
         /// or a block.
-        End = "end",
+        End = "end" => visit_end,
 
         // Parametric Instructions
 
         /// The
         /// [**drop**](https://webassembly.github.io/spec/core/syntax/instructions.html#parametric-instructions)
         /// instruction discards an operand from the value stack.
-        Drop = "drop",
+        Drop = "drop" => visit_drop,
         /// The
         /// [**select**](https://webassembly.github.io/spec/core/syntax/instructions.html#parametric-instructions)
         /// instruction selects one of two operands based on a third condition operand.
         ///
         /// The types specify the type of the operand selected. Future versions of WebAssembly may
         /// allow selecting more than one value at a time, requiring more than one type.
-        Select[(component::ResultType<&'a mut u64, I>)] = "select",
+        Select[(component::ResultType<&'a mut u64, I>)] = "select" => visit_select,
 
         // Variable Instructions
 
         /// The
         /// [**local.get**](https://webassembly.github.io/spec/core/syntax/instructions.html#variable-instructions)
         /// instruction loads the value of a local variable onto the stack.
-        LocalGet[(LocalIdx)] = "local.get",
+        LocalGet[(LocalIdx)] = "local.get" => visit_local_get,
         /// The
         /// [**local.set**](https://webassembly.github.io/spec/core/syntax/instructions.html#variable-instructions)
         /// instruction pops a value from the stack and stores it into a local variable.
-        LocalSet[(LocalIdx)] = "local.set",
+        LocalSet[(LocalIdx)] = "local.set" => visit_local_set,
         /// The
         /// [**local.set**](https://webassembly.github.io/spec/core/syntax/instructions.html#variable-instructions)
         /// instruction pops a value from the stack and stores it into a local variable, pushing
         /// the previous value onto the stack.
-        LocalTee[(LocalIdx)] = "local.tee",
+        LocalTee[(LocalIdx)] = "local.tee" => visit_local_tee,
         /// The
         /// [**global.get**](https://webassembly.github.io/spec/core/syntax/instructions.html#variable-instructions)
         /// instruction loads the value of a global variable onto the stack.
-        GlobalGet[(index::GlobalIdx)] = "global.get",
+        GlobalGet[(index::GlobalIdx)] = "global.get" => visit_global_get,
         /// The
         /// [**global.set**](https://webassembly.github.io/spec/core/syntax/instructions.html#variable-instructions)
         /// instruction pops a value from the stack and stores it into a global variable.
-        GlobalSet[(index::GlobalIdx)] = "global.set",
+        GlobalSet[(index::GlobalIdx)] = "global.set" => visit_global_set,
 
         // Memory Instructions
 
         /// The
         /// [**i32.load**](https://webassembly.github.io/spec/core/syntax/instructions.html#memory-instructions)
         /// instruction reads a 32-bit integer from memory.
-        I32Load[(MemArg)] = "i32.load",
+        I32Load[(MemArg)] = "i32.load" => visit_i32_load,
         /// The
         /// [**i64.load**](https://webassembly.github.io/spec/core/syntax/instructions.html#memory-instructions)
         /// instruction reads a 64-bit integer from memory.
-        I64Load[(MemArg)] = "i64.load",
+        I64Load[(MemArg)] = "i64.load" => visit_i64_load,
         /// The
         /// [**f32.load**](https://webassembly.github.io/spec/core/syntax/instructions.html#memory-instructions)
         /// instruction reads a 32-bit IEEE-754 float from memory.
-        F32Load[(MemArg)] = "f32.load",
+        F32Load[(MemArg)] = "f32.load" => visit_f32_load,
         /// The
         /// [**f64.load**](https://webassembly.github.io/spec/core/syntax/instructions.html#memory-instructions)
         /// instruction reads a 64-bit IEEE-754 float from memory.
-        F64Load[(MemArg)] = "f64.load",
+        F64Load[(MemArg)] = "f64.load" => visit_f64_load,
 
         /// The
         /// [**i32.load8_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#memory-instructions)
         /// instruction reads a byte from memory, and sign-extends it into a 32-bit integer.
-        I32Load8S[(MemArg)] = "i32.load8_s",
+        I32Load8S[(MemArg)] = "i32.load8_s" => visit_i32_load8_s,
         /// The
         /// [**i32.load8_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#memory-instructions)
         /// instruction reads a byte from memory, and interprets zero-extends it into a 32-bit
         /// integer.
-        I32Load8U[(MemArg)] = "i32.load8_u",
+        I32Load8U[(MemArg)] = "i32.load8_u" => visit_i32_load8_u,
         /// The
         /// [**i32.load16_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#memory-instructions)
         /// instruction reads a 16-bit integer from memory, and sign-extends it into a 32-bit
         /// integer.
-        I32Load16S[(MemArg)] = "i32.load16_s",
+        I32Load16S[(MemArg)] = "i32.load16_s" => visit_i32_load16_s,
         /// The
         /// [**i32.load16_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#memory-instructions)
         /// instruction reads a 16-bit integer from memory, and interprets zero-extends it into a
         /// 32-bit integer.
-        I32Load16U[(MemArg)] = "i32.load16_u",
+        I32Load16U[(MemArg)] = "i32.load16_u" => visit_i32_load16_u,
 
         /// The
         /// [**i64.load8_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#memory-instructions)
         /// instruction reads a byte from memory, and sign-extends it into a 64-bit integer.
-        I64Load8S[(MemArg)] = "i64.load8_s",
+        I64Load8S[(MemArg)] = "i64.load8_s" => visit_i64_load8_s,
         /// The
         /// [**i64.load8_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#memory-instructions)
         /// instruction reads a byte from memory, and interprets zero-extends it into a 64-bit
         /// integer.
-        I64Load8U[(MemArg)] = "i64.load8_u",
+        I64Load8U[(MemArg)] = "i64.load8_u" => visit_i64_load8_u,
         /// The
         /// [**i64.load16_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#memory-instructions)
         /// instruction reads a 16-bit integer from memory, and sign-extends it into a 64-bit
         /// integer.
-        I64Load16S[(MemArg)] = "i64.load16_s",
+        I64Load16S[(MemArg)] = "i64.load16_s" => visit_i64_load16_s,
         /// The
         /// [**i64.load16_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#memory-instructions)
         /// instruction reads a 16-bit integer from memory, and interprets zero-extends it into a
         /// 64-bit integer.
-        I64Load16U[(MemArg)] = "i64.load16_u",
+        I64Load16U[(MemArg)] = "i64.load16_u" => visit_i64_load16_u,
         /// The
         /// [**i64.load32_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#memory-instructions)
         /// instruction reads a 32-bit integer from memory, and sign-extends it into a 64-bit
         /// integer.
-        I64Load32S[(MemArg)] = "i64.load32_s",
+        I64Load32S[(MemArg)] = "i64.load32_s" => visit_i64_load32_s,
         /// The
         /// [**i64.load32_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#memory-instructions)
         /// instruction reads a 32-bit integer from memory, and interprets zero-extends it into a
         /// 64-bit integer.
-        I64Load32U[(MemArg)] = "i64.load32_u",
+        I64Load32U[(MemArg)] = "i64.load32_u" => visit_i64_load32_u,
 
         /// The
         /// [**i32.load**](https://webassembly.github.io/spec/core/syntax/instructions.html#memory-instructions)
         /// instruction writes a 32-bit integer into memory.
-        I32Store[(MemArg)] = "i32.store",
+        I32Store[(MemArg)] = "i32.store" => visit_i32_store,
         /// The
         /// [**i64.load**](https://webassembly.github.io/spec/core/syntax/instructions.html#memory-instructions)
         /// instruction writes a 64-bit integer into memory.
-        I64Store[(MemArg)] = "i64.store",
+        I64Store[(MemArg)] = "i64.store" => visit_i64_store,
         /// The
         /// [**f32.load**](https://webassembly.github.io/spec/core/syntax/instructions.html#memory-instructions)
         /// instruction writes a 32-bit IEEE-754 float into memory.
-        F32Store[(MemArg)] = "f32.store",
+        F32Store[(MemArg)] = "f32.store" => visit_f32_store,
         /// The
         /// [**f64.load**](https://webassembly.github.io/spec/core/syntax/instructions.html#memory-instructions)
         /// instruction writes a 64-bit IEEE-754 float into memory.
-        F64Store[(MemArg)] = "f64.store",
+        F64Store[(MemArg)] = "f64.store" => visit_f64_store,
 
         /// The
         /// [**i32.store8**](https://webassembly.github.io/spec/core/syntax/instructions.html#memory-instructions)
         /// instruction writes a byte into memory.
-        I32Store8[(MemArg)] = "i32.store8",
+        I32Store8[(MemArg)] = "i32.store8" => visit_i32_store8,
         /// The
         /// [**i32.store16**](https://webassembly.github.io/spec/core/syntax/instructions.html#memory-instructions)
         /// instruction writes a 16-bit integer into memory.
-        I32Store16[(MemArg)] = "i32.store16",
+        I32Store16[(MemArg)] = "i32.store16" => visit_i32_store16,
 
         /// The
         /// [**i64.store8**](https://webassembly.github.io/spec/core/syntax/instructions.html#memory-instructions)
         /// instruction writes a byte into memory.
-        I64Store8[(MemArg)] = "i64.store8",
+        I64Store8[(MemArg)] = "i64.store8" => visit_i64_store8,
         /// The
         /// [**i64.store16**](https://webassembly.github.io/spec/core/syntax/instructions.html#memory-instructions)
         /// instruction writes a 16-bit integer into memory.
-        I64Store16[(MemArg)] = "i64.store16",
+        I64Store16[(MemArg)] = "i64.store16" => visit_i64_store16,
         /// The
         /// [**i64.store32**](https://webassembly.github.io/spec/core/syntax/instructions.html#memory-instructions)
         /// instruction writes a 32-bit integer into memory.
-        I64Store32[(MemArg)] = "i64.store32",
+        I64Store32[(MemArg)] = "i64.store32" => visit_i64_store32,
 
         /// The
         /// [**memory.size**](https://webassembly.github.io/spec/core/syntax/instructions.html#memory-instructions)
         /// instruction returns the current number of pages allocated for the given memory.
-        MemorySize[(MemIdx)] = "memory.size",
+        MemorySize[(MemIdx)] = "memory.size" => visit_memory_size,
         /// The
         /// [**memory.grow**](https://webassembly.github.io/spec/core/syntax/instructions.html#memory-instructions)
         /// instruction increases the number of pages allocated for the given memory by an amount.
-        MemoryGrow[(MemIdx)] = "memory.grow",
+        MemoryGrow[(MemIdx)] = "memory.grow" => visit_memory_grow,
 
         // Numeric Instructions
 
         /// The
         /// [**i32.const**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
         /// instruction returns a 32-bit integer constant.
-        I32Const[(i32)] = "i32.const",
+        I32Const[(i32)] = "i32.const" => visit_i32_const,
         /// The
         /// [**i64.const**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
         /// instruction returns a 64-bit integer constant.
-        I64Const[(i64)] = "i64.const",
+        I64Const[(i64)] = "i64.const" => visit_i64_const,
         /// The
         /// [**f32.const**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
         /// instruction returns a 32-bit IEEE-754 floating point constant.
-        F32Const[(f32)] = "f32.const",
+        F32Const[(f32)] = "f32.const" => visit_f32_const,
         /// The
         /// [**f64.const**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
         /// instruction returns a 64-bit IEEE-754 floating point constant.
-        F64Const[(f64)] = "f64.const",
+        F64Const[(f64)] = "f64.const" => visit_f64_const,
 
         /// [**i32.eqz**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I32Eqz = "i32.eqz",
+        I32Eqz = "i32.eqz" => visit_i32_eqz,
         /// [**i32.eq**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I32Eq = "i32.eq",
+        I32Eq = "i32.eq" => visit_i32_eq,
         /// [**i32.ne**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I32Ne = "i32.ne",
+        I32Ne = "i32.ne" => visit_i32_ne,
         /// [**i32.lt_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I32LtS = "i32.lt_s",
+        I32LtS = "i32.lt_s" => visit_i32_lt_s,
         /// [**i32.lt_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I32LtU = "i32.lt_u",
+        I32LtU = "i32.lt_u" => visit_i32_lt_u,
         /// [**i32.gt_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I32GtS = "i32.gt_s",
+        I32GtS = "i32.gt_s" => visit_i32_gt_s,
         /// [**i32.gt_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I32GtU = "i32.gt_u",
+        I32GtU = "i32.gt_u" => visit_i32_gt_u,
         /// [**i32.le_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I32LeS = "i32.le_s",
+        I32LeS = "i32.le_s" => visit_i32_le_s,
         /// [**i32.le_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I32LeU = "i32.le_u",
+        I32LeU = "i32.le_u" => visit_i32_le_u,
         /// [**i32.ge_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I32GeS = "i32.ge_s",
+        I32GeS = "i32.ge_s" => visit_i32_ge_s,
         /// [**i32.ge_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I32GeU = "i32.ge_u",
+        I32GeU = "i32.ge_u" => visit_i32_ge_u,
 
         /// [**i64.eqz**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I64Eqz = "i64.eqz",
+        I64Eqz = "i64.eqz" => visit_i64_eqz,
         /// [**i64.eq**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I64Eq = "i64.eq",
+        I64Eq = "i64.eq" => visit_i64_eq,
         /// [**i64.ne**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I64Ne = "i64.ne",
+        I64Ne = "i64.ne" => visit_i64_ne,
         /// [**i64.lt_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I64LtS = "i64.lt_s",
+        I64LtS = "i64.lt_s" => visit_i64_lt_s,
         /// [**i64.lt_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I64LtU = "i64.lt_u",
+        I64LtU = "i64.lt_u" => visit_i64_lt_u,
         /// [**i64.gt_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I64GtS = "i64.gt_s",
+        I64GtS = "i64.gt_s" => visit_i64_gt_s,
         /// [**i64.gt_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I64GtU = "i64.gt_u",
+        I64GtU = "i64.gt_u" => visit_i64_gt_u,
         /// [**i64.le_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I64LeS = "i64.le_s",
+        I64LeS = "i64.le_s" => visit_i64_le_s,
         /// [**i64.le_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I64LeU = "i64.le_u",
+        I64LeU = "i64.le_u" => visit_i64_le_u,
         /// [**i64.ge_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I64GeS = "i64.ge_s",
+        I64GeS = "i64.ge_s" => visit_i64_ge_s,
         /// [**i64.ge_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I64GeU = "i64.ge_u",
+        I64GeU = "i64.ge_u" => visit_i64_ge_u,
 
         /// [**f32.eq**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F32Eq = "f32.eq",
+        F32Eq = "f32.eq" => visit_f32_eq,
         /// [**f32.ne**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F32Ne = "f32.ne",
+        F32Ne = "f32.ne" => visit_f32_ne,
         /// [**f32.lt**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F32Lt = "f32.lt",
+        F32Lt = "f32.lt" => visit_f32_lt,
         /// [**f32.gt**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F32Gt = "f32.gt",
+        F32Gt = "f32.gt" => visit_f32_gt,
         /// [**f32.le_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F32Le = "f32.le",
+        F32Le = "f32.le" => visit_f32_le,
         /// [**f32.ge**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F32Ge = "f32.ge",
+        F32Ge = "f32.ge" => visit_f32_ge,
 
         /// [**f64.eq**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F64Eq = "f64.eq",
+        F64Eq = "f64.eq" => visit_f64_eq,
         /// [**f64.ne**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F64Ne = "f64.ne",
+        F64Ne = "f64.ne" => visit_f64_ne,
         /// [**f64.lt**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F64Lt = "f64.lt",
+        F64Lt = "f64.lt" => visit_f64_lt,
         /// [**f64.gt**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F64Gt = "f64.gt",
+        F64Gt = "f64.gt" => visit_f64_gt,
         /// [**f64.le_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F64Le = "f64.le",
+        F64Le = "f64.le" => visit_f64_le,
         /// [**f64.ge**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F64Ge = "f64.ge",
+        F64Ge = "f64.ge" => visit_f64_ge,
 
         /// [**i32.clz**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I32Clz = "i32.clz",
+        I32Clz = "i32.clz" => visit_i32_clz,
         /// [**i32.ctz**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I32Ctz = "i32.ctz",
+        I32Ctz = "i32.ctz" => visit_i32_ctz,
         /// [**i32.popcnt**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I32Popcnt = "i32.popcnt",
+        I32Popcnt = "i32.popcnt" => visit_i32_popcnt,
         /// [**i32.add**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I32Add = "i32.add",
+        I32Add = "i32.add" => visit_i32_add,
         /// [**i32.sub**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I32Sub = "i32.sub",
+        I32Sub = "i32.sub" => visit_i32_sub,
         /// [**i32.mul**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I32Mul = "i32.mul",
+        I32Mul = "i32.mul" => visit_i32_mul,
         /// [**i32.div_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I32DivS = "i32.div_s",
+        I32DivS = "i32.div_s" => visit_i32_div_s,
         /// [**i32.div_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I32DivU = "i32.div_u",
+        I32DivU = "i32.div_u" => visit_i32_div_u,
         /// [**i32.rem_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I32RemS = "i32.rem_s",
+        I32RemS = "i32.rem_s" => visit_i32_rem_s,
         /// [**i32.rem_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I32RemU = "i32.rem_u",
+        I32RemU = "i32.rem_u" => visit_i32_rem_u,
         /// [**i32.and**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I32And = "i32.and",
+        I32And = "i32.and" => visit_i32_and,
         /// [**i32.or**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I32Or = "i32.or",
+        I32Or = "i32.or" => visit_i32_or,
         /// [**i32.xor**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I32Xor = "i32.xor",
+        I32Xor = "i32.xor" => visit_i32_xor,
         /// [**i32.shl**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I32Shl = "i32.shl",
+        I32Shl = "i32.shl" => visit_i32_shl,
         /// [**i32.shr_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I32ShrS = "i32.shr_s",
+        I32ShrS = "i32.shr_s" => visit_i32_shr_s,
         /// [**i32.shr_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I32ShrU = "i32.shr_u",
+        I32ShrU = "i32.shr_u" => visit_i32_shr_u,
         /// [**i32.rotl**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I32Rotl = "i32.rotl",
+        I32Rotl = "i32.rotl" => visit_i32_rotl,
         /// [**i32.rotr**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I32Rotr = "i32.rotr",
+        I32Rotr = "i32.rotr" => visit_i32_rotr,
 
         /// [**i64.clz**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I64Clz = "i64.clz",
+        I64Clz = "i64.clz" => visit_i64_clz,
         /// [**i64.ctz**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I64Ctz = "i64.ctz",
+        I64Ctz = "i64.ctz" => visit_i64_ctz,
         /// [**i64.popcnt**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I64Popcnt = "i64.popcnt",
+        I64Popcnt = "i64.popcnt" => visit_i64_popcnt,
         /// [**i64.add**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I64Add = "i64.add",
+        I64Add = "i64.add" => visit_i64_add,
         /// [**i64.sub**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I64Sub = "i64.sub",
+        I64Sub = "i64.sub" => visit_i64_sub,
         /// [**i64.mul**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I64Mul = "i64.mul",
+        I64Mul = "i64.mul" => visit_i64_mul,
         /// [**i64.div_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I64DivS = "i64.div_s",
+        I64DivS = "i64.div_s" => visit_i64_div_s,
         /// [**i64.div_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I64DivU = "i64.div_u",
+        I64DivU = "i64.div_u" => visit_i64_div_u,
         /// [**i64.rem_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I64RemS = "i64.rem_s",
+        I64RemS = "i64.rem_s" => visit_i64_rem_s,
         /// [**i64.rem_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I64RemU = "i64.rem_u",
+        I64RemU = "i64.rem_u" => visit_i64_rem_u,
         /// [**i64.and**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I64And = "i64.and",
+        I64And = "i64.and" => visit_i64_and,
         /// [**i64.or**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I64Or = "i64.or",
+        I64Or = "i64.or" => visit_i64_or,
         /// [**i64.xor**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I64Xor = "i64.xor",
+        I64Xor = "i64.xor" => visit_i64_xor,
         /// [**i64.shl**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I64Shl = "i64.shl",
+        I64Shl = "i64.shl" => visit_i64_shl,
         /// [**i64.shr_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I64ShrS = "i64.shr_s",
+        I64ShrS = "i64.shr_s" => visit_i64_shr_s,
         /// [**i64.shr_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I64ShrU = "i64.shr_u",
+        I64ShrU = "i64.shr_u" => visit_i64_shr_u,
         /// [**i64.rotl**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I64Rotl = "i64.rotl",
+        I64Rotl = "i64.rotl" => visit_i64_rotl,
         /// [**i64.rotr**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I64Rotr = "i64.rotr",
+        I64Rotr = "i64.rotr" => visit_i64_rotr,
 
         /// [**f32.abs**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F32Abs = "f32.abs",
+        F32Abs = "f32.abs" => visit_f32_abs,
         /// [**f32.neg**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F32Neg = "f32.neg",
+        F32Neg = "f32.neg" => visit_f32_neg,
         /// [**f32.ceil**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F32Ceil = "f32.ceil",
+        F32Ceil = "f32.ceil" => visit_f32_ceil,
         /// [**f32.floor**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F32Floor = "f32.floor",
+        F32Floor = "f32.floor" => visit_f32_floor,
         /// [**f32.trunc**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F32Trunc = "f32.trunc",
+        F32Trunc = "f32.trunc" => visit_f32_trunc,
         /// [**f32.nearest**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F32Nearest = "f32.nearest",
+        F32Nearest = "f32.nearest" => visit_f32_nearest,
         /// [**f32.sqrt**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F32Sqrt = "f32.sqrt",
+        F32Sqrt = "f32.sqrt" => visit_f32_sqrt,
         /// [**f32.add**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F32Add = "f32.add",
+        F32Add = "f32.add" => visit_f32_add,
         /// [**f32.sub**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F32Sub = "f32.sub",
+        F32Sub = "f32.sub" => visit_f32_sub,
         /// [**f32.mul**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F32Mul = "f32.mul",
+        F32Mul = "f32.mul" => visit_f32_mul,
         /// [**f32.div**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F32Div = "f32.div",
+        F32Div = "f32.div" => visit_f32_div,
         /// [**f32.min**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F32Min = "f32.min",
+        F32Min = "f32.min" => visit_f32_min,
         /// [**f32.max**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F32Max = "f32.max",
+        F32Max = "f32.max" => visit_f32_max,
         /// [**f32.copysign**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F32Copysign = "f32.copysign",
+        F32Copysign = "f32.copysign" => visit_f32_copysign,
 
         /// [**f64.abs**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F64Abs = "f64.abs",
+        F64Abs = "f64.abs" => visit_f64_abs,
         /// [**f64.neg**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F64Neg = "f64.neg",
+        F64Neg = "f64.neg" => visit_f64_neg,
         /// [**f64.ceil**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F64Ceil = "f64.ceil",
+        F64Ceil = "f64.ceil" => visit_f64_ceil,
         /// [**f64.floor**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F64Floor = "f64.floor",
+        F64Floor = "f64.floor" => visit_f64_floor,
         /// [**f64.trunc**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F64Trunc = "f64.trunc",
+        F64Trunc = "f64.trunc" => visit_f64_trunc,
         /// [**f64.nearest**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F64Nearest = "f64.nearest",
+        F64Nearest = "f64.nearest" => visit_f64_nearest,
         /// [**f64.sqrt**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F64Sqrt = "f64.sqrt",
+        F64Sqrt = "f64.sqrt" => visit_f64_sqrt,
         /// [**f64.add**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F64Add = "f64.add",
+        F64Add = "f64.add" => visit_f64_add,
         /// [**f64.sub**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F64Sub = "f64.sub",
+        F64Sub = "f64.sub" => visit_f64_sub,
         /// [**f64.mul**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F64Mul = "f64.mul",
+        F64Mul = "f64.mul" => visit_f64_mul,
         /// [**f64.div**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F64Div = "f64.div",
+        F64Div = "f64.div" => visit_f64_div,
         /// [**f64.min**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F64Min = "f64.min",
+        F64Min = "f64.min" => visit_f64_min,
         /// [**f64.max**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F64Max = "f64.max",
+        F64Max = "f64.max" => visit_f64_max,
         /// [**f64.copysign**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F64Copysign = "f64.copysign",
+        F64Copysign = "f64.copysign" => visit_f64_copysign,
 
         /// [**i32.wrap_i64**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I32WrapI64 = "i32.wrap_i64",
+        I32WrapI64 = "i32.wrap_i64" => visit_i32_wrap_i64,
         /// [**i32.trunc_f32_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I32TruncF32S = "i32.trunc_f32_s",
+        I32TruncF32S = "i32.trunc_f32_s" => visit_i32_trunc_f32_s,
         /// [**i32.trunc_f32_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I32TruncF32U = "i32.trunc_f64_u",
+        I32TruncF32U = "i32.trunc_f32_u" => visit_i32_trunc_f32_u,
         /// [**i32.trunc_f64_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I32TruncF64S = "i32.trunc_f64_s",
+        I32TruncF64S = "i32.trunc_f64_s" => visit_i32_trunc_f64_s,
         /// [**i32.trunc_f64_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I32TruncF64U = "i32.trunc_f64_u",
+        I32TruncF64U = "i32.trunc_f64_u" => visit_i32_trunc_f64_u,
         /// [**i64.extend_i32_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I64ExtendI32S = "i64.extend_i32_s",
+        I64ExtendI32S = "i64.extend_i32_s" => visit_i64_extend_i32_s,
         /// [**i64.extend_i32_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I64ExtendI32U = "i64.extend_i32_u",
+        I64ExtendI32U = "i64.extend_i32_u" => visit_i64_extend_i32_u,
         /// [**i64.trunc_f32_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I64TruncF32S = "i64.trunc_f32_s",
+        I64TruncF32S = "i64.trunc_f32_s" => visit_i64_trunc_f32_s,
         /// [**i64.trunc_f32_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I64TruncF32U = "i64.trunc_f64_u",
+        I64TruncF32U = "i64.trunc_f32_u" => visit_i64_trunc_f32_u,
         /// [**i64.trunc_f64_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I64TruncF64S = "i64.trunc_f64_s",
+        I64TruncF64S = "i64.trunc_f64_s" => visit_i64_trunc_f64_s,
         /// [**i64.trunc_f64_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I64TruncF64U = "i64.trunc_f64_u",
+        I64TruncF64U = "i64.trunc_f64_u" => visit_i64_trunc_f64_u,
         /// [**f32.convert_i32_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F32ConvertI32S = "f32.convert_i32_s",
+        F32ConvertI32S = "f32.convert_i32_s" => visit_f32_convert_i32_s,
         /// [**f32.convert_i32_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F32ConvertI32U = "f32.convert_i32_u",
+        F32ConvertI32U = "f32.convert_i32_u" => visit_f32_convert_i32_u,
         /// [**f32.convert_i64_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F32ConvertI64S = "f32.convert_i64_s",
+        F32ConvertI64S = "f32.convert_i64_s" => visit_f32_convert_i64_s,
         /// [**f32.convert_i64_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F32ConvertI64U = "f32.convert_i64_u",
+        F32ConvertI64U = "f32.convert_i64_u" => visit_f32_convert_i64_u,
         /// [**f32.demote_f64**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F32DemoteF64 = "f32.demote_f64",
+        F32DemoteF64 = "f32.demote_f64" => visit_f32_demote_f64,
         /// [**f64.convert_i32_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F64ConvertI32S = "f64.convert_i32_s",
+        F64ConvertI32S = "f64.convert_i32_s" => visit_f64_convert_i32_s,
         /// [**f64.convert_i32_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F64ConvertI32U = "f64.convert_i32_u",
+        F64ConvertI32U = "f64.convert_i32_u" => visit_f64_convert_i32_u,
         /// [**f64.convert_i64_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F64ConvertI64S = "f64.convert_i64_s",
+        F64ConvertI64S = "f64.convert_i64_s" => visit_f64_convert_i64_s,
         /// [**f64.convert_i64_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F64ConvertI64U = "f64.convert_i64_u",
+        F64ConvertI64U = "f64.convert_i64_u" => visit_f64_convert_i64_u,
         /// [**f64.promote_f32**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F64PromoteF32 = "f64.promote_f32",
+        F64PromoteF32 = "f64.promote_f32" => visit_f64_promote_f32,
         /// [**i32.reinterpret_f32**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I32ReinterpretF32 = "i32.reinterpret_f32",
+        I32ReinterpretF32 = "i32.reinterpret_f32" => visit_i32_reinterpret_f32,
         /// [**i64.reinterpret_f64**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I64ReinterpretF64 = "i64.reinterpret_f64",
+        I64ReinterpretF64 = "i64.reinterpret_f64" => visit_i64_reinterpret_f64,
         /// [**f32.reinterpret_i32**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F32ReinterpretI32 = "f32.reinterpret_i32",
+        F32ReinterpretI32 = "f32.reinterpret_i32" => visit_f32_reinterpret_i32,
         /// [**f64.reinterpret_i64**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        F64ReinterpretI64 = "f64.reinterpret_i64",
+        F64ReinterpretI64 = "f64.reinterpret_i64" => visit_f64_reinterpret_i64,
     }
 
     /// Returns `true` if the [`Instruction`] was introduced as part of the
     /// [non-trapping float-to-int conversions proposal](https://github.com/WebAssembly/nontrapping-float-to-int-conversions).
-    is_from_non_trapping_float_to_int_conversions {
+    is_from_non_trapping_float_to_int_conversions [all()] {
         /// [**i32.trunc_sat_f32_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I32TruncSatF32S = "i32.trunc_sat_f32_s",
+        I32TruncSatF32S = "i32.trunc_sat_f32_s" => visit_i32_trunc_sat_f32_s,
         /// [**i32.trunc_sat_f32_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I32TruncSatF32U = "i32.trunc_sat_f32_u",
+        I32TruncSatF32U = "i32.trunc_sat_f32_u" => visit_i32_trunc_sat_f32_u,
         /// [**i32.trunc_sat_f64_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I32TruncSatF64S = "i32.trunc_sat_f64_s",
+        I32TruncSatF64S = "i32.trunc_sat_f64_s" => visit_i32_trunc_sat_f64_s,
         /// [**i32.trunc_sat_f64_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I32TruncSatF64U = "i32.trunc_sat_f64_u",
+        I32TruncSatF64U = "i32.trunc_sat_f64_u" => visit_i32_trunc_sat_f64_u,
         /// [**i64.trunc_sat_f32_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I64TruncSatF32S = "i64.trunc_sat_f32_s",
+        I64TruncSatF32S = "i64.trunc_sat_f32_s" => visit_i64_trunc_sat_f32_s,
         /// [**i64.trunc_sat_f32_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I64TruncSatF32U = "i64.trunc_sat_f32_u",
+        I64TruncSatF32U = "i64.trunc_sat_f32_u" => visit_i64_trunc_sat_f32_u,
         /// [**i64.trunc_sat_f64_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I64TruncSatF64S = "i64.trunc_sat_f64_s",
+        I64TruncSatF64S = "i64.trunc_sat_f64_s" => visit_i64_trunc_sat_f64_s,
         /// [**i64.trunc_sat_f64_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I64TruncSatF64U = "i64.trunc_sat_f64_u",
+        I64TruncSatF64U = "i64.trunc_sat_f64_u" => visit_i64_trunc_sat_f64_u,
     }
 
     /// Returns `true` if the [`Instruction`] was introduced as part of the
     /// [sign-extension operators proposal](https://github.com/WebAssembly/sign-extension-ops).
-    is_from_sign_extension_operators {
+    is_from_sign_extension_operators [all()] {
         /// [**i32.extend8_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I32Extend8S = "i32.extend8_s",
+        I32Extend8S = "i32.extend8_s" => visit_i32_extend8_s,
         /// [**i32.extend16_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I32Extend16S = "i32.extend16_s",
+        I32Extend16S = "i32.extend16_s" => visit_i32_extend16_s,
         /// [**i64.extend8_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I64Extend8S = "i64.extend8_s",
+        I64Extend8S = "i64.extend8_s" => visit_i64_extend8_s,
         /// [**i64.extend16_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I64Extend16S = "i64.extend16_s",
+        I64Extend16S = "i64.extend16_s" => visit_i64_extend16_s,
         /// [**i64.extend32_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#numeric-instructions)
-        I64Extend32S = "i64.extend32_s",
+        I64Extend32S = "i64.extend32_s" => visit_i64_extend32_s,
     }
 
     /// Returns `true` if the [`Instruction`] was introduced as part of the
@@ -637,49 +1035,49 @@ instructions! {
     ///
     /// Note that the `table.fill` and `table.grow` are excluded, as they were also (concurrently?)
     /// introduced in the [bulk memory operations proposal](Instruction::is_from_bulk_memory_operations).
-    is_from_reference_types {
+    is_from_reference_types [all()] {
         // Reference Instructions
 
         /// The
         /// [**ref.null**](https://webassembly.github.io/spec/core/syntax/instructions.html#reference-instructions)
         /// instruction produces a `null` value of the specified reference type.
-        RefNull[(types::RefType)] = "ref.null",
+        RefNull[(types::RefType)] = "ref.null" => visit_ref_null,
         /// The
         /// [**ref.is_null**](https://webassembly.github.io/spec/core/syntax/instructions.html#reference-instructions)
         /// instruction checks if an operand is `null`.
-        RefIsNull = "ref.is_null",
+        RefIsNull = "ref.is_null" => visit_ref_is_null,
         /// The
         /// [**ref.func**](https://webassembly.github.io/spec/core/syntax/instructions.html#reference-instructions)
         /// instruction produces a reference to a given function (a `funcref`).
-        RefFunc[(FuncIdx)] = "ref.func",
+        RefFunc[(FuncIdx)] = "ref.func" => visit_ref_func,
 
         // Table Instructions
 
         /// The
         /// [**table.get**](https://webassembly.github.io/spec/core/syntax/instructions.html#table-instructions)
         /// instruction loads an element in the specified table.
-        TableGet[(TableIdx)] = "table.get",
+        TableGet[(TableIdx)] = "table.get" => visit_table_get,
         /// The
         /// [**table.set**](https://webassembly.github.io/spec/core/syntax/instructions.html#table-instructions)
         /// instruction stores an element in the specified table.
-        TableSet[(TableIdx)] = "table.set",
+        TableSet[(TableIdx)] = "table.set" => visit_table_set,
         /// The
         /// [**table.size**](https://webassembly.github.io/spec/core/syntax/instructions.html#table-instructions)
         /// instruction returns the current number of elements in the table.
-        TableSize[(TableIdx)] = "table.size",
+        TableSize[(TableIdx)] = "table.size" => visit_table_size,
         /// The
         /// [**table.grow**](https://webassembly.github.io/spec/core/syntax/instructions.html#table-instructions)
         /// instruction increases the number of elements that can be stored in a table.
-        TableGrow[(TableIdx)] = "table.grow",
+        TableGrow[(TableIdx)] = "table.grow" => visit_table_grow,
         /// The
         /// [**table.fill**](https://webassembly.github.io/spec/core/syntax/instructions.html#table-instructions)
         /// instruction sets all elements in the table to the value specified by an operand.
-        TableFill[(TableIdx)] = "table.fill",
+        TableFill[(TableIdx)] = "table.fill" => visit_table_fill,
     }
 
     /// Returns `true` if the [`Instruction`] was introduced as part of the
     /// [bulk memory operations proposal](https://github.com/WebAssembly/bulk-memory-operations).
-    is_from_bulk_memory_operations {
+    is_from_bulk_memory_operations [all()] {
         /// The
         /// [**memory.copy**](https://webassembly.github.io/spec/core/syntax/instructions.html#memory-instructions)
         /// instruction copies bytes from one memory into another memory.
@@ -688,7 +1086,7 @@ instructions! {
             destination: MemIdx,
             /// The memory that bytes are copied from.
             source: MemIdx,
-        }] = "memory.copy",
+        }] = "memory.copy" => visit_memory_copy,
 
         /// The
         /// [**table.copy**](https://webassembly.github.io/spec/core/syntax/instructions.html#table-instructions)
@@ -698,738 +1096,764 @@ instructions! {
             destination: TableIdx,
             /// The table elements are copied from.
             source: TableIdx
-        }] = "table.copy",
+        }] = "table.copy" => visit_table_copy,
 
         /// The
         /// [**memory.fill**](https://webassembly.github.io/spec/core/syntax/instructions.html#memory-instructions)
         /// instruction fills a region of memory with byte value.
-        MemoryFill[(MemIdx)] = "memory.fill",
+        MemoryFill[(MemIdx)] = "memory.fill" => visit_memory_fill,
 
         /// The
         /// [**memory.init**](https://webassembly.github.io/spec/core/syntax/instructions.html#memory-instructions)
         /// instruction copies bytes from a
         /// [passive data segment](https://webassembly.github.io/spec/core/syntax/modules.html#syntax-data)
         /// into the given memory.
-        MemoryInit[(index::DataIdx, MemIdx)] = "memory.init",
+        MemoryInit[(index::DataIdx, MemIdx)] = "memory.init" => visit_memory_init,
         /// The
         /// [**table.init**](https://webassembly.github.io/spec/core/syntax/instructions.html#table-instructions)
         /// instruction copies elements from a
         /// [passive element segment](https://webassembly.github.io/spec/core/syntax/modules.html#syntax-elem)
         /// into the specified table.
-        TableInit[(index::ElemIdx, TableIdx)] = "table.init",
+        TableInit[(index::ElemIdx, TableIdx)] = "table.init" => visit_table_init,
 
         /// The
         /// [**data.drop**](https://webassembly.github.io/spec/core/syntax/instructions.html#memory-instructions)
         /// instruction serves as a hint that the given
         /// [data segment](https://webassembly.github.io/spec/core/syntax/modules.html#syntax-data)
         /// will no longer be used.
-        DataDrop[(index::DataIdx)] = "data.drop",
+        DataDrop[(index::DataIdx)] = "data.drop" => visit_data_drop,
         /// The
         /// [**elem.drop**](https://webassembly.github.io/spec/core/syntax/instructions.html#table-instructions)
         /// instruction serves as a hint that the given
         /// [element segment](https://webassembly.github.io/spec/core/syntax/modules.html#syntax-elem)
         /// will no longer be used.
-        ElemDrop[(index::ElemIdx)] = "elem.drop",
+        ElemDrop[(index::ElemIdx)] = "elem.drop" => visit_elem_drop,
     }
 
     /// Returns `true` if the [`Instruction`] was introduced as part of the
     /// [fixed-width SIMD proposal](https://github.com/webassembly/simd), which introduced
     /// operations on 128-bit wide vectors.
-    is_from_fixed_width_simd {
+    is_from_fixed_width_simd [feature = "simd"] {
         /// The
         /// [**v128.load**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
         /// instruction reads an 128-bit vector from memory.
-        V128Load[(MemArg)] = "v128.load",
+        V128Load[(MemArg)] = "v128.load" => visit_v128_load,
 
         /// [**v128.load8x8_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        V128Load8x8S[(MemArg)] = "v128.load8x8_s",
+        V128Load8x8S[(MemArg)] = "v128.load8x8_s" => visit_v128_load8x8_s,
         /// [**v128.load8x8_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        V128Load8x8U[(MemArg)] = "v128.load8x8_u",
+        V128Load8x8U[(MemArg)] = "v128.load8x8_u" => visit_v128_load8x8_u,
         /// [**v128.load16x4_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        V128Load16x4S[(MemArg)] = "v128.load16x4_s",
+        V128Load16x4S[(MemArg)] = "v128.load16x4_s" => visit_v128_load16x4_s,
         /// [**v128.load16x4_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        V128Load16x4U[(MemArg)] = "v128.load16x4_u",
+        V128Load16x4U[(MemArg)] = "v128.load16x4_u" => visit_v128_load16x4_u,
         /// [**v128.load32x2_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        V128Load32x2S[(MemArg)] = "v128.load32x2_s",
+        V128Load32x2S[(MemArg)] = "v128.load32x2_s" => visit_v128_load32x2_s,
         /// [**v128.load32x2_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        V128Load32x2U[(MemArg)] = "v128.load32x2_u",
+        V128Load32x2U[(MemArg)] = "v128.load32x2_u" => visit_v128_load32x2_u,
 
         /// [**v128.load8_splat**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        V128Load8Splat[(MemArg)] = "v128.load8_splat",
+        V128Load8Splat[(MemArg)] = "v128.load8_splat" => visit_v128_load8_splat,
         /// [**v128.load16_splat**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        V128Load16Splat[(MemArg)] = "v128.load16_splat",
+        V128Load16Splat[(MemArg)] = "v128.load16_splat" => visit_v128_load16_splat,
         /// [**v128.load32_splat**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        V128Load32Splat[(MemArg)] = "v128.load32_splat",
+        V128Load32Splat[(MemArg)] = "v128.load32_splat" => visit_v128_load32_splat,
         /// [**v128.load64_splat**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        V128Load64Splat[(MemArg)] = "v128.load64_splat",
+        V128Load64Splat[(MemArg)] = "v128.load64_splat" => visit_v128_load64_splat,
 
         /// [**v128.load32_zero**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        V128Load32Zero[(MemArg)] = "v128.load32_zero",
+        V128Load32Zero[(MemArg)] = "v128.load32_zero" => visit_v128_load32_zero,
         /// [**v128.load64_zero**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        V128Load64Zero[(MemArg)] = "v128.load64_zero",
+        V128Load64Zero[(MemArg)] = "v128.load64_zero" => visit_v128_load64_zero,
 
         /// The
         /// [**v128.store**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
         /// instruction stores an 128-bit vector into memory.
-        V128Store[(MemArg)] = "v128.store",
+        V128Store[(MemArg)] = "v128.store" => visit_v128_store,
 
         /// [**v128.load8_lane**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        V128Load8Lane[(MemArg, LaneIdx)] = "v128.load8_lane",
+        V128Load8Lane[(MemArg, LaneIdx)] = "v128.load8_lane" => visit_v128_load8_lane,
         /// [**v128.load16_lane**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        V128Load16Lane[(MemArg, LaneIdx)] = "v128.load16_lane",
+        V128Load16Lane[(MemArg, LaneIdx)] = "v128.load16_lane" => visit_v128_load16_lane,
         /// [**v128.load32_lane**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        V128Load32Lane[(MemArg, LaneIdx)] = "v128.load32_lane",
+        V128Load32Lane[(MemArg, LaneIdx)] = "v128.load32_lane" => visit_v128_load32_lane,
         /// [**v128.load64_lane**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        V128Load64Lane[(MemArg, LaneIdx)] = "v128.load64_lane",
+        V128Load64Lane[(MemArg, LaneIdx)] = "v128.load64_lane" => visit_v128_load64_lane,
 
         /// [**v128.store8_lane**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        V128Store8Lane[(MemArg, LaneIdx)] = "v128.store8_lane",
+        V128Store8Lane[(MemArg, LaneIdx)] = "v128.store8_lane" => visit_v128_store8_lane,
         /// [**v128.store16_lane**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        V128Store16Lane[(MemArg, LaneIdx)] = "v128.store16_lane",
+        V128Store16Lane[(MemArg, LaneIdx)] = "v128.store16_lane" => visit_v128_store16_lane,
         /// [**v128.store32_lane**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        V128Store32Lane[(MemArg, LaneIdx)] = "v128.store32_lane",
+        V128Store32Lane[(MemArg, LaneIdx)] = "v128.store32_lane" => visit_v128_store32_lane,
         /// [**v128.store64_lane**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        V128Store64Lane[(MemArg, LaneIdx)] = "v128.store64_lane",
+        V128Store64Lane[(MemArg, LaneIdx)] = "v128.store64_lane" => visit_v128_store64_lane,
 
         /// The
         /// [**v128.const**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
         /// instruction returns a 128-bit vector constant.
-        V128Const[(u128)] = "v128.const",
+        V128Const[(u128)] = "v128.const" => visit_v128_const,
 
         /// [**i8x16.shuffle**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I8x16Shuffle[([LaneIdx; 16])] = "i8x16.shuffle",
+        I8x16Shuffle[([LaneIdx; 16])] = "i8x16.shuffle" => visit_i8x16_shuffle,
 
         /// [**i8x16.extract_lane_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I8x16ExtractLaneS[(LaneIdx)] = "i8x16.extract_lane_s",
+        I8x16ExtractLaneS[(LaneIdx)] = "i8x16.extract_lane_s" => visit_i8x16_extract_lane_s,
         /// [**i8x16.extract_lane_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I8x16ExtractLaneU[(LaneIdx)] = "i8x16.extract_lane_u",
+        I8x16ExtractLaneU[(LaneIdx)] = "i8x16.extract_lane_u" => visit_i8x16_extract_lane_u,
         /// [**i8x16.replace_lane**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I8x16ReplaceLane[(LaneIdx)] = "i8x16.replace_lane",
+        I8x16ReplaceLane[(LaneIdx)] = "i8x16.replace_lane" => visit_i8x16_replace_lane,
 
         /// [**i16x8.extract_lane_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8ExtractLaneS[(LaneIdx)] = "i16x8.extract_lane_s",
+        I16x8ExtractLaneS[(LaneIdx)] = "i16x8.extract_lane_s" => visit_i16x8_extract_lane_s,
         /// [**i16x8.extract_lane_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8ExtractLaneU[(LaneIdx)] = "i16x8.extract_lane_u",
+        I16x8ExtractLaneU[(LaneIdx)] = "i16x8.extract_lane_u" => visit_i16x8_extract_lane_u,
         /// [**i16x8.replace_lane**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8ReplaceLane[(LaneIdx)] = "i16x8.replace_lane",
+        I16x8ReplaceLane[(LaneIdx)] = "i16x8.replace_lane" => visit_i16x8_replace_lane,
 
         /// [**i32x4.extract_lane**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I32x4ExtractLane[(LaneIdx)] = "i32x4.extract_lane",
+        I32x4ExtractLane[(LaneIdx)] = "i32x4.extract_lane" => visit_i32x4_extract_lane,
         /// [**i32x4.replace_lane**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I32x4ReplaceLane[(LaneIdx)] = "i32x4.replace_lane",
+        I32x4ReplaceLane[(LaneIdx)] = "i32x4.replace_lane" => visit_i32x4_replace_lane,
 
         /// [**i64x2.extract_lane**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I64x2ExtractLane[(LaneIdx)] = "i64x2.extract_lane",
+        I64x2ExtractLane[(LaneIdx)] = "i64x2.extract_lane" => visit_i64x2_extract_lane,
         /// [**i64x2.replace_lane**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I64x2ReplaceLane[(LaneIdx)] = "i64x2.replace_lane",
+        I64x2ReplaceLane[(LaneIdx)] = "i64x2.replace_lane" => visit_i64x2_replace_lane,
 
         /// [**f32x4.extract_lane**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F32x4ExtractLane[(LaneIdx)] = "f32x4.extract_lane",
+        F32x4ExtractLane[(LaneIdx)] = "f32x4.extract_lane" => visit_f32x4_extract_lane,
         /// [**f32x4.replace_lane**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F32x4ReplaceLane[(LaneIdx)] = "f32x4.replace_lane",
+        F32x4ReplaceLane[(LaneIdx)] = "f32x4.replace_lane" => visit_f32x4_replace_lane,
 
         /// [**f64x2.extract_lane**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F64x2ExtractLane[(LaneIdx)] = "f64x2.extract_lane",
+        F64x2ExtractLane[(LaneIdx)] = "f64x2.extract_lane" => visit_f64x2_extract_lane,
         /// [**f64x2.replace_lane**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F64x2ReplaceLane[(LaneIdx)] = "f64x2.replace_lane",
+        F64x2ReplaceLane[(LaneIdx)] = "f64x2.replace_lane" => visit_f64x2_replace_lane,
 
         /// [**i8x16.swizzle**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I8x16Swizzle = "i8x16.swizzle",
+        I8x16Swizzle = "i8x16.swizzle" => visit_i8x16_swizzle,
 
         /// [**i8x16.splat**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I8x16Splat = "i8x16.splat",
+        I8x16Splat = "i8x16.splat" => visit_i8x16_splat,
         /// [**i16x8.splat**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8Splat = "i16x8.splat",
+        I16x8Splat = "i16x8.splat" => visit_i16x8_splat,
         /// [**i32x4.splat**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I32x4Splat = "i32x4.splat",
+        I32x4Splat = "i32x4.splat" => visit_i32x4_splat,
         /// [**i64x2.splat**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I64x2Splat = "i64x2.splat",
+        I64x2Splat = "i64x2.splat" => visit_i64x2_splat,
         /// [**f32x4.splat**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F32x4Splat = "f32x4.splat",
+        F32x4Splat = "f32x4.splat" => visit_f32x4_splat,
         /// [**f64x2.splat**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F64x2Splat = "f64x2.splat",
+        F64x2Splat = "f64x2.splat" => visit_f64x2_splat,
 
         /// [**i8x16.eq**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I8x16Eq = "i8x16.eq",
+        I8x16Eq = "i8x16.eq" => visit_i8x16_eq,
         /// [**i8x16.ne**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I8x16Ne = "i8x16.ne",
+        I8x16Ne = "i8x16.ne" => visit_i8x16_ne,
         /// [**i8x16.lt_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I8x16LtS = "i8x16.lt_s",
+        I8x16LtS = "i8x16.lt_s" => visit_i8x16_lt_s,
         /// [**i8x16.lt_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I8x16LtU = "i8x16.lt_u",
+        I8x16LtU = "i8x16.lt_u" => visit_i8x16_lt_u,
         /// [**i8x16.gt_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I8x16GtS = "i8x16.gt_s",
+        I8x16GtS = "i8x16.gt_s" => visit_i8x16_gt_s,
         /// [**i8x16.gt_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I8x16GtU = "i8x16.gt_u",
+        I8x16GtU = "i8x16.gt_u" => visit_i8x16_gt_u,
         /// [**i8x16.le_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I8x16LeS = "i8x16.le_s",
+        I8x16LeS = "i8x16.le_s" => visit_i8x16_le_s,
         /// [**i8x16.le_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I8x16LeU = "i8x16.le_u",
+        I8x16LeU = "i8x16.le_u" => visit_i8x16_le_u,
         /// [**i8x16.ge_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I8x16GeS = "i8x16.ge_s",
+        I8x16GeS = "i8x16.ge_s" => visit_i8x16_ge_s,
         /// [**i8x16.ge_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I8x16GeU = "i8x16.ge_u",
+        I8x16GeU = "i8x16.ge_u" => visit_i8x16_ge_u,
 
         /// [**i16x8.eq**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8Eq = "i16x8.eq",
+        I16x8Eq = "i16x8.eq" => visit_i16x8_eq,
         /// [**i16x8.ne**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8Ne = "i16x8.ne",
+        I16x8Ne = "i16x8.ne" => visit_i16x8_ne,
         /// [**i16x8.lt_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8LtS = "i16x8.lt_s",
+        I16x8LtS = "i16x8.lt_s" => visit_i16x8_lt_s,
         /// [**i16x8.lt_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8LtU = "i16x8.lt_u",
+        I16x8LtU = "i16x8.lt_u" => visit_i16x8_lt_u,
         /// [**i16x8.gt_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8GtS = "i16x8.gt_s",
+        I16x8GtS = "i16x8.gt_s" => visit_i16x8_gt_s,
         /// [**i16x8.gt_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8GtU = "i16x8.gt_u",
+        I16x8GtU = "i16x8.gt_u" => visit_i16x8_gt_u,
         /// [**i16x8.le_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8LeS = "i16x8.le_s",
+        I16x8LeS = "i16x8.le_s" => visit_i16x8_le_s,
         /// [**i16x8.le_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8LeU = "i16x8.le_u",
+        I16x8LeU = "i16x8.le_u" => visit_i16x8_le_u,
         /// [**i16x8.ge_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8GeS = "i16x8.ge_s",
+        I16x8GeS = "i16x8.ge_s" => visit_i16x8_ge_s,
         /// [**i16x8.ge_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8GeU = "i16x8.ge_u",
+        I16x8GeU = "i16x8.ge_u" => visit_i16x8_ge_u,
 
         /// [**i32x4.eq**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I32x4Eq = "i32x4.eq",
+        I32x4Eq = "i32x4.eq" => visit_i32x4_eq,
         /// [**i32x4.ne**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I32x4Ne = "i32x4.ne",
+        I32x4Ne = "i32x4.ne" => visit_i32x4_ne,
         /// [**i32x4.lt_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I32x4LtS = "i32x4.lt_s",
+        I32x4LtS = "i32x4.lt_s" => visit_i32x4_lt_s,
         /// [**i32x4.lt_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I32x4LtU = "i32x4.lt_u",
+        I32x4LtU = "i32x4.lt_u" => visit_i32x4_lt_u,
         /// [**i32x4.gt_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I32x4GtS = "i32x4.gt_s",
+        I32x4GtS = "i32x4.gt_s" => visit_i32x4_gt_s,
         /// [**i32x4.gt_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I32x4GtU = "i32x4.gt_u",
+        I32x4GtU = "i32x4.gt_u" => visit_i32x4_gt_u,
         /// [**i32x4.le_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I32x4LeS = "i32x4.le_s",
+        I32x4LeS = "i32x4.le_s" => visit_i32x4_le_s,
         /// [**i32x4.le_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I32x4LeU = "i32x4.le_u",
+        I32x4LeU = "i32x4.le_u" => visit_i32x4_le_u,
         /// [**i32x4.ge_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I32x4GeS = "i32x4.ge_s",
+        I32x4GeS = "i32x4.ge_s" => visit_i32x4_ge_s,
         /// [**i32x4.ge_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I32x4GeU = "i32x4.ge_u",
+        I32x4GeU = "i32x4.ge_u" => visit_i32x4_ge_u,
 
         /// [**i64x2.eq**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I64x2Eq = "i64x2.eq",
+        I64x2Eq = "i64x2.eq" => visit_i64x2_eq,
         /// [**i64x2.ne**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I64x2Ne = "i64x2.ne",
+        I64x2Ne = "i64x2.ne" => visit_i64x2_ne,
         /// [**i64x2.lt_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I64x2LtS = "i64x2.lt_s",
+        I64x2LtS = "i64x2.lt_s" => visit_i64x2_lt_s,
         /// [**i64x2.gt_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I64x2GtS = "i64x2.gt_s",
+        I64x2GtS = "i64x2.gt_s" => visit_i64x2_gt_s,
         /// [**i64x2.le_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I64x2LeS = "i64x2.le_s",
+        I64x2LeS = "i64x2.le_s" => visit_i64x2_le_s,
         /// [**i64x2.ge_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I64x2GeS = "i64x2.ge_s",
+        I64x2GeS = "i64x2.ge_s" => visit_i64x2_ge_s,
 
         /// [**f32x4.eq**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F32x4Eq = "f32x4.eq",
+        F32x4Eq = "f32x4.eq" => visit_f32x4_eq,
         /// [**f32x4.ne**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F32x4Ne = "f32x4.ne",
+        F32x4Ne = "f32x4.ne" => visit_f32x4_ne,
         /// [**f32x4.lt**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F32x4Lt = "f32x4.lt",
+        F32x4Lt = "f32x4.lt" => visit_f32x4_lt,
         /// [**f32x4.gt**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F32x4Gt = "f32x4.gt",
+        F32x4Gt = "f32x4.gt" => visit_f32x4_gt,
         /// [**f32x4.le**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F32x4Le = "f32x4.le",
+        F32x4Le = "f32x4.le" => visit_f32x4_le,
         /// [**f32x4.ge**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F32x4Ge = "f32x4.ge",
+        F32x4Ge = "f32x4.ge" => visit_f32x4_ge,
 
         /// [**f64x2.eq**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F64x2Eq = "f64x2.eq",
+        F64x2Eq = "f64x2.eq" => visit_f64x2_eq,
         /// [**f64x2.ne**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F64x2Ne = "f64x2.ne",
+        F64x2Ne = "f64x2.ne" => visit_f64x2_ne,
         /// [**f64x2.lt**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F64x2Lt = "f64x2.lt",
+        F64x2Lt = "f64x2.lt" => visit_f64x2_lt,
         /// [**f64x2.gt**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F64x2Gt = "f64x2.gt",
+        F64x2Gt = "f64x2.gt" => visit_f64x2_gt,
         /// [**f64x2.le**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F64x2Le = "f64x2.le",
+        F64x2Le = "f64x2.le" => visit_f64x2_le,
         /// [**f64x2.ge**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F64x2Ge = "f64x2.ge",
+        F64x2Ge = "f64x2.ge" => visit_f64x2_ge,
 
         /// [**v128.not**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        V128Not = "v128.not",
+        V128Not = "v128.not" => visit_v128_not,
         /// [**v128.and**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        V128And = "v128.and",
+        V128And = "v128.and" => visit_v128_and,
         /// [**v128.andnot**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        V128AndNot = "v128.andnot",
+        V128AndNot = "v128.andnot" => visit_v128_andnot,
         /// [**v128.or**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        V128Or = "v128.or",
+        V128Or = "v128.or" => visit_v128_or,
         /// [**v128.xor**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        V128Xor = "v128.xor",
+        V128Xor = "v128.xor" => visit_v128_xor,
         /// [**v128.bitselect**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        V128Bitselect = "v128.bitselect",
+        V128Bitselect = "v128.bitselect" => visit_v128_bitselect,
         /// [**v128.any_true**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        V128AnyTrue = "v128.any_true",
+        V128AnyTrue = "v128.any_true" => visit_v128_any_true,
 
         /// [**i8x16.abs**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I8x16Abs = "i8x16.abs",
+        I8x16Abs = "i8x16.abs" => visit_i8x16_abs,
         /// [**i8x16.neg**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I8x16Neg = "i8x16.neg",
+        I8x16Neg = "i8x16.neg" => visit_i8x16_neg,
         /// [**i8x16.popcnt**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I8x16Popcnt = "i8x16.popcnt",
+        I8x16Popcnt = "i8x16.popcnt" => visit_i8x16_popcnt,
         /// [**i8x16.all_true**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I8x16AllTrue = "i8x16.all_true",
+        I8x16AllTrue = "i8x16.all_true" => visit_i8x16_all_true,
         /// [**i8x16.bitmask**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I8x16Bitmask = "i8x16.bitmask",
+        I8x16Bitmask = "i8x16.bitmask" => visit_i8x16_bitmask,
         /// [**i8x16.narrow_i16x8_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I8x16NarrowI16x8S = "i8x16.narrow_i16x8_s",
+        I8x16NarrowI16x8S = "i8x16.narrow_i16x8_s" => visit_i8x16_narrow_i16x8_s,
         /// [**i8x16.narrow_i16x8_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I8x16NarrowI16x8U = "i8x16.narrow_i16x8_u",
+        I8x16NarrowI16x8U = "i8x16.narrow_i16x8_u" => visit_i8x16_narrow_i16x8_u,
         /// [**i8x16.shl**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I8x16Shl = "i8x16.shl",
+        I8x16Shl = "i8x16.shl" => visit_i8x16_shl,
         /// [**i8x16.shr_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I8x16ShrS = "i8x16.shr_s",
+        I8x16ShrS = "i8x16.shr_s" => visit_i8x16_shr_s,
         /// [**i8x16.shr_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I8x16ShrU = "i8x16.shr_u",
+        I8x16ShrU = "i8x16.shr_u" => visit_i8x16_shr_u,
         /// [**i8x16.add**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I8x16Add = "i8x16.add",
+        I8x16Add = "i8x16.add" => visit_i8x16_add,
         /// [**i8x16.add_sat_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I8x16AddSatS = "i8x16.add_sat_s",
+        I8x16AddSatS = "i8x16.add_sat_s" => visit_i8x16_add_sat_s,
         /// [**i8x16.add_sat_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I8x16AddSatU = "i8x16.add_sat_u",
+        I8x16AddSatU = "i8x16.add_sat_u" => visit_i8x16_add_sat_u,
         /// [**i8x16.sub**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I8x16Sub = "i8x16.sub",
+        I8x16Sub = "i8x16.sub" => visit_i8x16_sub,
         /// [**i8x16.sub_sat_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I8x16SubSatS = "i8x16.sub_sat_s",
+        I8x16SubSatS = "i8x16.sub_sat_s" => visit_i8x16_sub_sat_s,
         /// [**i8x16.sub_sat_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I8x16SubSatU = "i8x16.sub_sat_u",
+        I8x16SubSatU = "i8x16.sub_sat_u" => visit_i8x16_sub_sat_u,
         /// [**i8x16.min_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I8x16MinS = "i8x16.min_s",
+        I8x16MinS = "i8x16.min_s" => visit_i8x16_min_s,
         /// [**i8x16.min_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I8x16MinU = "i8x16.min_u",
+        I8x16MinU = "i8x16.min_u" => visit_i8x16_min_u,
         /// [**i8x16.max_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I8x16MaxS = "i8x16.max_s",
+        I8x16MaxS = "i8x16.max_s" => visit_i8x16_max_s,
         /// [**i8x16.max_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I8x16MaxU = "i8x16.max_u",
+        I8x16MaxU = "i8x16.max_u" => visit_i8x16_max_u,
         /// [**i8x16.avgr_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I8x16AvgrU = "i8x16.avgr_u",
+        I8x16AvgrU = "i8x16.avgr_u" => visit_i8x16_avgr_u,
 
         /// [**i16x8.extadd_pairwise_i8x16_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8ExtaddPairwiseI8x16S = "i16x8.extadd_pairwise_i8x16_s",
+        I16x8ExtaddPairwiseI8x16S = "i16x8.extadd_pairwise_i8x16_s" => visit_i16x8_extadd_pairwise_i8x16_s,
         /// [**i16x8.extadd_pairwise_i8x16_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8ExtaddPairwiseI8x16U = "i16x8.extadd_pairwise_i8x16_u",
+        I16x8ExtaddPairwiseI8x16U = "i16x8.extadd_pairwise_i8x16_u" => visit_i16x8_extadd_pairwise_i8x16_u,
         /// [**i16x8.abs**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8Abs = "i16x8.abs",
+        I16x8Abs = "i16x8.abs" => visit_i16x8_abs,
         /// [**i16x8.neg**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8Neg = "i16x8.neg",
+        I16x8Neg = "i16x8.neg" => visit_i16x8_neg,
         /// [**i16x8.q15mulr_sat_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8Q15MulrSatS = "i16x8.q15mulr_sat_s",
+        I16x8Q15MulrSatS = "i16x8.q15mulr_sat_s" => visit_i16x8_q15mulr_sat_s,
         /// [**i16x8.all_true**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8AllTrue = "i16x8.all_true",
+        I16x8AllTrue = "i16x8.all_true" => visit_i16x8_all_true,
         /// [**i16x8.bitmask**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8Bitmask = "i16x8.bitmask",
+        I16x8Bitmask = "i16x8.bitmask" => visit_i16x8_bitmask,
         /// [**i16x8.narrow_i32x4_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8NarrowI32x4S = "i16x8.narrow_i32x4_s",
+        I16x8NarrowI32x4S = "i16x8.narrow_i32x4_s" => visit_i16x8_narrow_i32x4_s,
         /// [**i16x8.narrow_i32x4_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8NarrowI32x4U = "i16x8.narrow_i32x4_u",
+        I16x8NarrowI32x4U = "i16x8.narrow_i32x4_u" => visit_i16x8_narrow_i32x4_u,
         /// [**i16x8.extend_low_i8x16_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8ExtendLowI8x16S = "i16x8.extend_low_i8x16_s",
+        I16x8ExtendLowI8x16S = "i16x8.extend_low_i8x16_s" => visit_i16x8_extend_low_i8x16_s,
         /// [**i16x8.extend_high_i8x16_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8ExtendHighI8x16S = "i16x8.extend_high_i8x16_s",
+        I16x8ExtendHighI8x16S = "i16x8.extend_high_i8x16_s" => visit_i16x8_extend_high_i8x16_s,
         /// [**i16x8.extend_low_i8x16_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8ExtendLowI8x16U = "i16x8.extend_low_i8x16_u",
+        I16x8ExtendLowI8x16U = "i16x8.extend_low_i8x16_u" => visit_i16x8_extend_low_i8x16_u,
         /// [**i16x8.extend_high_i8x16_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8ExtendHighI8x16U = "i16x8.extend_high_i8x16_u",
+        I16x8ExtendHighI8x16U = "i16x8.extend_high_i8x16_u" => visit_i16x8_extend_high_i8x16_u,
         /// [**i16x8.shl**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8Shl = "i16x8.shl",
+        I16x8Shl = "i16x8.shl" => visit_i16x8_shl,
         /// [**i16x8.shr_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8ShrS = "i16x8.shr_s",
+        I16x8ShrS = "i16x8.shr_s" => visit_i16x8_shr_s,
         /// [**i16x8.shr_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8ShrU = "i16x8.shr_u",
+        I16x8ShrU = "i16x8.shr_u" => visit_i16x8_shr_u,
         /// [**i16x8.add**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8Add = "i16x8.add",
+        I16x8Add = "i16x8.add" => visit_i16x8_add,
         /// [**i16x8.add_sat_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8AddSatS = "i16x8.add_sat_s",
+        I16x8AddSatS = "i16x8.add_sat_s" => visit_i16x8_add_sat_s,
         /// [**i16x8.add_sat_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8AddSatU = "i16x8.add_sat_u",
+        I16x8AddSatU = "i16x8.add_sat_u" => visit_i16x8_add_sat_u,
         /// [**i16x8.sub**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8Sub = "i16x8.sub",
+        I16x8Sub = "i16x8.sub" => visit_i16x8_sub,
         /// [**i16x8.sub_sat_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8SubSatS = "i16x8.sub_sat_s",
+        I16x8SubSatS = "i16x8.sub_sat_s" => visit_i16x8_sub_sat_s,
         /// [**i16x8.sub_sat_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8SubSatU = "i16x8.sub_sat_u",
+        I16x8SubSatU = "i16x8.sub_sat_u" => visit_i16x8_sub_sat_u,
         /// [**i16x8.mul**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8Mul = "i16x8.mul",
+        I16x8Mul = "i16x8.mul" => visit_i16x8_mul,
         /// [**i16x8.min_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8MinS = "i16x8.min_s",
+        I16x8MinS = "i16x8.min_s" => visit_i16x8_min_s,
         /// [**i16x8.min_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8MinU = "i16x8.min_u",
+        I16x8MinU = "i16x8.min_u" => visit_i16x8_min_u,
         /// [**i16x8.max_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8MaxS = "i16x8.max_s",
+        I16x8MaxS = "i16x8.max_s" => visit_i16x8_max_s,
         /// [**i16x8.max_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8MaxU = "i16x8.max_u",
+        I16x8MaxU = "i16x8.max_u" => visit_i16x8_max_u,
         /// [**i16x8.avgr_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8AvgrU = "i16x8.avgr_u",
+        I16x8AvgrU = "i16x8.avgr_u" => visit_i16x8_avgr_u,
         /// [**i16x8.extmul_low_i8x16_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8ExtmulLowI8x16S = "i16x8.extmul_low_i8x16_s",
+        I16x8ExtmulLowI8x16S = "i16x8.extmul_low_i8x16_s" => visit_i16x8_extmul_low_i8x16_s,
         /// [**i16x8.extmul_high_i8x16_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8ExtmulHighI8x16S = "i16x8.extmul_high_i8x16_s",
+        I16x8ExtmulHighI8x16S = "i16x8.extmul_high_i8x16_s" => visit_i16x8_extmul_high_i8x16_s,
         /// [**i16x8.extmul_low_i8x16_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8ExtmulLowI8x16U = "i16x8.extmul_low_i8x16_u",
+        I16x8ExtmulLowI8x16U = "i16x8.extmul_low_i8x16_u" => visit_i16x8_extmul_low_i8x16_u,
         /// [**i16x8.extmul_high_i8x16_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I16x8ExtmulHighI8x16U = "i16x8.extmul_high_i8x16_u",
+        I16x8ExtmulHighI8x16U = "i16x8.extmul_high_i8x16_u" => visit_i16x8_extmul_high_i8x16_u,
 
         /// [**i32x4.extadd_pairwise_i16x8_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I32x4ExtaddPairwiseI16x8S = "i32x4.extadd_pairwise_i16x8_s",
+        I32x4ExtaddPairwiseI16x8S = "i32x4.extadd_pairwise_i16x8_s" => visit_i32x4_extadd_pairwise_i16x8_s,
         /// [**i32x4.extadd_pairwise_i16x8_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I32x4ExtaddPairwiseI16x8U = "i32x4.extadd_pairwise_i16x8_u",
+        I32x4ExtaddPairwiseI16x8U = "i32x4.extadd_pairwise_i16x8_u" => visit_i32x4_extadd_pairwise_i16x8_u,
         /// [**i32x4.abs**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I32x4Abs = "i32x4.abs",
+        I32x4Abs = "i32x4.abs" => visit_i32x4_abs,
         /// [**i32x4.neg**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I32x4Neg = "i32x4.neg",
+        I32x4Neg = "i32x4.neg" => visit_i32x4_neg,
         /// [**i32x4.all_true**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I32x4AllTrue = "i32x4.all_true",
+        I32x4AllTrue = "i32x4.all_true" => visit_i32x4_all_true,
         /// [**i32x4.bitmask**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I32x4Bitmask = "i32x4.bitmask",
+        I32x4Bitmask = "i32x4.bitmask" => visit_i32x4_bitmask,
         /// [**i32x4.extend_low_i16x8_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I32x4ExtendLowI16x8S = "i32x4.extend_low_i16x8_s",
+        I32x4ExtendLowI16x8S = "i32x4.extend_low_i16x8_s" => visit_i32x4_extend_low_i16x8_s,
         /// [**i32x4.extend_high_i16x8_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I32x4ExtendHighI16x8S = "i32x4.extend_high_i16x8_s",
+        I32x4ExtendHighI16x8S = "i32x4.extend_high_i16x8_s" => visit_i32x4_extend_high_i16x8_s,
         /// [**i32x4.extend_low_i16x8_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I32x4ExtendLowI16x8U = "i32x4.extend_low_i16x8_u",
+        I32x4ExtendLowI16x8U = "i32x4.extend_low_i16x8_u" => visit_i32x4_extend_low_i16x8_u,
         /// [**i32x4.extend_high_i16x8_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I32x4ExtendHighI16x8U = "i32x4.extend_high_i16x8_u",
+        I32x4ExtendHighI16x8U = "i32x4.extend_high_i16x8_u" => visit_i32x4_extend_high_i16x8_u,
         /// [**i32x4.shl**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I32x4Shl = "i32x4.shl",
+        I32x4Shl = "i32x4.shl" => visit_i32x4_shl,
         /// [**i32x4.shr_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I32x4ShrS = "i32x4.shr_s",
+        I32x4ShrS = "i32x4.shr_s" => visit_i32x4_shr_s,
         /// [**i32x4.shr_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I32x4ShrU = "i32x4.shr_u",
+        I32x4ShrU = "i32x4.shr_u" => visit_i32x4_shr_u,
         /// [**i32x4.add**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I32x4Add = "i32x4.add",
+        I32x4Add = "i32x4.add" => visit_i32x4_add,
         /// [**i32x4.sub**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I32x4Sub = "i32x4.sub",
+        I32x4Sub = "i32x4.sub" => visit_i32x4_sub,
         /// [**i32x4.mul**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I32x4Mul = "i32x4.mul",
+        I32x4Mul = "i32x4.mul" => visit_i32x4_mul,
         /// [**i32x4.min_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I32x4MinS = "i32x4.min_s",
+        I32x4MinS = "i32x4.min_s" => visit_i32x4_min_s,
         /// [**i32x4.min_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I32x4MinU = "i32x4.min_u",
+        I32x4MinU = "i32x4.min_u" => visit_i32x4_min_u,
         /// [**i32x4.max_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I32x4MaxS = "i32x4.max_s",
+        I32x4MaxS = "i32x4.max_s" => visit_i32x4_max_s,
         /// [**i32x4.max_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I32x4MaxU = "i32x4.max_u",
+        I32x4MaxU = "i32x4.max_u" => visit_i32x4_max_u,
         /// [**i32x4.dot_i16x8_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I32x4DotI16x8S = "i32x4.dot_i16x8_s",
+        I32x4DotI16x8S = "i32x4.dot_i16x8_s" => visit_i32x4_dot_i16x8_s,
         /// [**i32x4.extmul_low_i16x8_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I32x4ExtmulLowI16x8S = "i32x4.extmul_low_i16x8_s",
+        I32x4ExtmulLowI16x8S = "i32x4.extmul_low_i16x8_s" => visit_i32x4_extmul_low_i16x8_s,
         /// [**i32x4.extmul_high_i16x8_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I32x4ExtmulHighI16x8S = "i32x4.extmul_high_i16x8_s",
+        I32x4ExtmulHighI16x8S = "i32x4.extmul_high_i16x8_s" => visit_i32x4_extmul_high_i16x8_s,
         /// [**i32x4.extmul_low_i16x8_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I32x4ExtmulLowI16x8U = "i32x4.extmul_low_i16x8_u",
+        I32x4ExtmulLowI16x8U = "i32x4.extmul_low_i16x8_u" => visit_i32x4_extmul_low_i16x8_u,
         /// [**i32x4.extmul_high_i16x8_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I32x4ExtmulHighI16x8U = "i32x4.extmul_high_i16x8_u",
+        I32x4ExtmulHighI16x8U = "i32x4.extmul_high_i16x8_u" => visit_i32x4_extmul_high_i16x8_u,
 
         /// [**i64x2.abs**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I64x2Abs = "i64x2.abs",
+        I64x2Abs = "i64x2.abs" => visit_i64x2_abs,
         /// [**i64x2.neg**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I64x2Neg = "i64x2.neg",
+        I64x2Neg = "i64x2.neg" => visit_i64x2_neg,
         /// [**i64x2.all_true**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I64x2AllTrue = "i64x2.all_true",
+        I64x2AllTrue = "i64x2.all_true" => visit_i64x2_all_true,
         /// [**i64x2.bitmask**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I64x2Bitmask = "i64x2.bitmask",
+        I64x2Bitmask = "i64x2.bitmask" => visit_i64x2_bitmask,
         /// [**i64x2.extend_low_i32x4_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I64x2ExtendLowI32x4S = "i64x2.extend_low_i32x4_s",
+        I64x2ExtendLowI32x4S = "i64x2.extend_low_i32x4_s" => visit_i64x2_extend_low_i32x4_s,
         /// [**i64x2.extend_high_i32x4_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I64x2ExtendHighI32x4S = "i64x2.extend_high_i32x4_s",
+        I64x2ExtendHighI32x4S = "i64x2.extend_high_i32x4_s" => visit_i64x2_extend_high_i32x4_s,
         /// [**i64x2.extend_low_i32x4_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I64x2ExtendLowI32x4U = "i64x2.extend_low_i32x4_u",
+        I64x2ExtendLowI32x4U = "i64x2.extend_low_i32x4_u" => visit_i64x2_extend_low_i32x4_u,
         /// [**i64x2.extend_high_i32x4_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I64x2ExtendHighI32x4U = "i64x2.extend_high_i32x4_u",
+        I64x2ExtendHighI32x4U = "i64x2.extend_high_i32x4_u" => visit_i64x2_extend_high_i32x4_u,
         /// [**i64x2.shl**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I64x2Shl = "i64x2.shl",
+        I64x2Shl = "i64x2.shl" => visit_i64x2_shl,
         /// [**i64x2.shr_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I64x2ShrS = "i64x2.shr_s",
+        I64x2ShrS = "i64x2.shr_s" => visit_i64x2_shr_s,
         /// [**i64x2.shr_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I64x2ShrU = "i64x2.shr_u",
+        I64x2ShrU = "i64x2.shr_u" => visit_i64x2_shr_u,
         /// [**i64x2.add**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I64x2Add = "i64x2.add",
+        I64x2Add = "i64x2.add" => visit_i64x2_add,
         /// [**i64x2.sub**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I64x2Sub = "i64x2.sub",
+        I64x2Sub = "i64x2.sub" => visit_i64x2_sub,
         /// [**i64x2.mul**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I64x2Mul = "i64x2.mul",
+        I64x2Mul = "i64x2.mul" => visit_i64x2_mul,
         /// [**i64x2.extmul_low_i32x4_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I64x2ExtmulLowI32x4S = "i64x2.extmul_low_i32x4_s",
+        I64x2ExtmulLowI32x4S = "i64x2.extmul_low_i32x4_s" => visit_i64x2_extmul_low_i32x4_s,
         /// [**i64x2.extmul_high_i32x4_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I64x2ExtmulHighI32x4S = "i64x2.extmul_high_i32x4_s",
+        I64x2ExtmulHighI32x4S = "i64x2.extmul_high_i32x4_s" => visit_i64x2_extmul_high_i32x4_s,
         /// [**i64x2.extmul_low_i32x4_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I64x2ExtmulLowI32x4U = "i64x2.extmul_low_i32x4_u",
+        I64x2ExtmulLowI32x4U = "i64x2.extmul_low_i32x4_u" => visit_i64x2_extmul_low_i32x4_u,
         /// [**i64x2.extmul_high_i32x4_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I64x2ExtmulHighI32x4U = "i64x2.extmul_high_i32x4_u",
+        I64x2ExtmulHighI32x4U = "i64x2.extmul_high_i32x4_u" => visit_i64x2_extmul_high_i32x4_u,
 
         /// [**f32x4.ceil**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F32x4Ceil = "f32x4.ceil",
+        F32x4Ceil = "f32x4.ceil" => visit_f32x4_ceil,
         /// [**f32x4.floor**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F32x4Floor = "f32x4.floor",
+        F32x4Floor = "f32x4.floor" => visit_f32x4_floor,
         /// [**f32x4.trunc**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F32x4Trunc = "f32x4.trunc",
+        F32x4Trunc = "f32x4.trunc" => visit_f32x4_trunc,
         /// [**f32x4.nearest**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F32x4Nearest = "f32x4.nearest",
+        F32x4Nearest = "f32x4.nearest" => visit_f32x4_nearest,
         /// [**f32x4.abs**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F32x4Abs = "f32x4.abs",
+        F32x4Abs = "f32x4.abs" => visit_f32x4_abs,
         /// [**f32x4.neg**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F32x4Neg = "f32x4.neg",
+        F32x4Neg = "f32x4.neg" => visit_f32x4_neg,
         /// [**f32x4.sqrt**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F32x4Sqrt = "f32x4.sqrt",
+        F32x4Sqrt = "f32x4.sqrt" => visit_f32x4_sqrt,
         /// [**f32x4.add**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F32x4Add = "f32x4.add",
+        F32x4Add = "f32x4.add" => visit_f32x4_add,
         /// [**f32x4.sub**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F32x4Sub = "f32x4.sub",
+        F32x4Sub = "f32x4.sub" => visit_f32x4_sub,
         /// [**f32x4.mul**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F32x4Mul = "f32x4.mul",
+        F32x4Mul = "f32x4.mul" => visit_f32x4_mul,
         /// [**f32x4.div**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F32x4Div = "f32x4.div",
+        F32x4Div = "f32x4.div" => visit_f32x4_div,
         /// [**f32x4.min**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F32x4Min = "f32x4.min",
+        F32x4Min = "f32x4.min" => visit_f32x4_min,
         /// [**f32x4.max**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F32x4Max = "f32x4.max",
+        F32x4Max = "f32x4.max" => visit_f32x4_max,
         /// [**f32x4.pmin**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F32x4Pmin = "f32x4.pmin",
+        F32x4Pmin = "f32x4.pmin" => visit_f32x4_pmin,
         /// [**f32x4.pmax**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F32x4Pmax = "f32x4.pmax",
+        F32x4Pmax = "f32x4.pmax" => visit_f32x4_pmax,
 
         /// [**f64x2.ceil**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F64x2Ceil = "f64x2.ceil",
+        F64x2Ceil = "f64x2.ceil" => visit_f64x2_ceil,
         /// [**f64x2.floor**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F64x2Floor = "f64x2.floor",
+        F64x2Floor = "f64x2.floor" => visit_f64x2_floor,
         /// [**f64x2.trunc**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F64x2Trunc = "f64x2.trunc",
+        F64x2Trunc = "f64x2.trunc" => visit_f64x2_trunc,
         /// [**f64x2.nearest**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F64x2Nearest = "f64x2.nearest",
+        F64x2Nearest = "f64x2.nearest" => visit_f64x2_nearest,
         /// [**f64x2.abs**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F64x2Abs = "f64x2.abs",
+        F64x2Abs = "f64x2.abs" => visit_f64x2_abs,
         /// [**f64x2.neg**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F64x2Neg = "f64x2.neg",
+        F64x2Neg = "f64x2.neg" => visit_f64x2_neg,
         /// [**f64x2.sqrt**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F64x2Sqrt = "f64x2.sqrt",
+        F64x2Sqrt = "f64x2.sqrt" => visit_f64x2_sqrt,
         /// [**f64x2.add**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F64x2Add = "f64x2.add",
+        F64x2Add = "f64x2.add" => visit_f64x2_add,
         /// [**f64x2.sub**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F64x2Sub = "f64x2.sub",
+        F64x2Sub = "f64x2.sub" => visit_f64x2_sub,
         /// [**f64x2.mul**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F64x2Mul = "f64x2.mul",
+        F64x2Mul = "f64x2.mul" => visit_f64x2_mul,
         /// [**f64x2.div**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F64x2Div = "f64x2.div",
+        F64x2Div = "f64x2.div" => visit_f64x2_div,
         /// [**f64x2.min**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F64x2Min = "f64x2.min",
+        F64x2Min = "f64x2.min" => visit_f64x2_min,
         /// [**f64x2.max**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F64x2Max = "f64x2.max",
+        F64x2Max = "f64x2.max" => visit_f64x2_max,
         /// [**f64x2.pmin**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F64x2Pmin = "f64x2.pmin",
+        F64x2Pmin = "f64x2.pmin" => visit_f64x2_pmin,
         /// [**f64x2.pmax**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F64x2Pmax = "f64x2.pmax",
+        F64x2Pmax = "f64x2.pmax" => visit_f64x2_pmax,
 
         /// [**i32x4.trunc_sat_f32x4_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I32x4TruncSatF32x4S = "i32x4.trunc_sat_f32x4_s",
+        I32x4TruncSatF32x4S = "i32x4.trunc_sat_f32x4_s" => visit_i32x4_trunc_sat_f32x4_s,
         /// [**i32x4.trunc_sat_f32x4_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I32x4TruncSatF32x4U = "i32x4.trunc_sat_f32x4_u",
+        I32x4TruncSatF32x4U = "i32x4.trunc_sat_f32x4_u" => visit_i32x4_trunc_sat_f32x4_u,
         /// [**f32x4.convert_i32x4_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F32x4ConvertI32x4S = "f32x4.convert_i32x4_s",
+        F32x4ConvertI32x4S = "f32x4.convert_i32x4_s" => visit_f32x4_convert_i32x4_s,
         /// [**f32x4.convert_i32x4_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F32x4ConvertI32x4U = "f32x4.convert_i32x4_u",
+        F32x4ConvertI32x4U = "f32x4.convert_i32x4_u" => visit_f32x4_convert_i32x4_u,
         /// [**i32x4.trunc_sat_f64x2_s_zero**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I32x4TruncSatF64x2SZero = "i32x4.trunc_sat_f64x2_s_zero",
+        I32x4TruncSatF64x2SZero = "i32x4.trunc_sat_f64x2_s_zero" => visit_i32x4_trunc_sat_f64x2_s_zero,
         /// [**i32x4.trunc_sat_f64x2_u_zero**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        I32x4TruncSatF64x2UZero = "i32x4.trunc_sat_f64x2_u_zero",
+        I32x4TruncSatF64x2UZero = "i32x4.trunc_sat_f64x2_u_zero" => visit_i32x4_trunc_sat_f64x2_u_zero,
         /// [**f64x2.convert_low_i32x4_s**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F64x2ConvertLowI32x4S = "f64x2.convert_low_i32x4_s",
+        F64x2ConvertLowI32x4S = "f64x2.convert_low_i32x4_s" => visit_f64x2_convert_low_i32x4_s,
         /// [**f64x2.convert_low_i32x4_u**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F64x2ConvertLowI32x4U = "f64x2.convert_low_i32x4_u",
+        F64x2ConvertLowI32x4U = "f64x2.convert_low_i32x4_u" => visit_f64x2_convert_low_i32x4_u,
         /// [**f32x4.demote_f64x2_zero**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F32x4DemoteF64x2Zero = "f32x4.demote_f64x2_zero",
+        F32x4DemoteF64x2Zero = "f32x4.demote_f64x2_zero" => visit_f32x4_demote_f64x2_zero,
         /// [**f64x2.promote_low_f32x4**](https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions)
-        F64x2PromoteLowF32x4 = "f64x2.promote_low_f32x4",
+        F64x2PromoteLowF32x4 = "f64x2.promote_low_f32x4" => visit_f64x2_promote_low_f32x4,
     }
 
     /// Returns `true` if the [`Instruction`] was introduced as part of the
     /// [tail call proposal](https://github.com/WebAssembly/tail-call).
-    is_from_tail_call {
+    is_from_tail_call [feature = "tail-call"] {
         /// [**return_call**](https://webassembly.github.io/tail-call/core/syntax/instructions.html#control-instructions)
-        ReturnCall[(FuncIdx)] = "return_call",
+        ReturnCall[(FuncIdx)] = "return_call" => visit_return_call,
         /// [**return_call_indirect**](https://webassembly.github.io/tail-call/core/syntax/instructions.html#control-instructions)
-        ReturnCallIndirect[(index::TypeIdx, TableIdx)] = "return_call_indirect",
+        ReturnCallIndirect[(index::TypeIdx, TableIdx)] = "return_call_indirect" => visit_return_call_indirect,
+    }
+
+    /// Returns `true` if the [`Instruction`] was introduced as part of the
+    /// [typed function references proposal](https://github.com/WebAssembly/function-references).
+    is_from_function_references [feature = "function-references"] {
+        /// The
+        /// [**call_ref**](https://webassembly.github.io/function-references/core/syntax/instructions.html#control-instructions)
+        /// instruction calls the function reference operand, whose type is given by the type index.
+        CallRef[(index::TypeIdx)] = "call_ref" => visit_call_ref,
+        /// The
+        /// [**return_call_ref**](https://webassembly.github.io/function-references/core/syntax/instructions.html#control-instructions)
+        /// instruction is the tail call version of [**call_ref**](Instruction::CallRef).
+        ReturnCallRef[(index::TypeIdx)] = "return_call_ref" => visit_return_call_ref,
+        /// The
+        /// [**ref.as_non_null**](https://webassembly.github.io/function-references/core/syntax/instructions.html#reference-instructions)
+        /// instruction traps if the reference operand is `null`, otherwise it is returned as a
+        /// non-nullable reference.
+        RefAsNonNull = "ref.as_non_null" => visit_ref_as_non_null,
+        /// The
+        /// [**br_on_null**](https://webassembly.github.io/function-references/core/syntax/instructions.html#control-instructions)
+        /// instruction branches to the given label if the reference operand is `null`.
+        BrOnNull[(LabelIdx)] = "br_on_null" => visit_br_on_null,
+        /// The
+        /// [**br_on_non_null**](https://webassembly.github.io/function-references/core/syntax/instructions.html#control-instructions)
+        /// instruction branches to the given label if the reference operand is not `null`.
+        BrOnNonNull[(LabelIdx)] = "br_on_non_null" => visit_br_on_non_null,
     }
 
     /// Returns `true` if the [`Instruction`] is an atomic memory instruction, introduced as part
     /// of the [threads proposal](https://github.com/webassembly/threads).
-    is_from_threads {
+    is_from_threads [feature = "threads"] {
         /// [**memory.atomic.notify**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        MemoryAtomicNotify[(MemArg)] = "memory.atomic.notify",
+        MemoryAtomicNotify[(MemArg)] = "memory.atomic.notify" => visit_memory_atomic_notify,
         /// [**memory.atomic.wait32**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        MemoryAtomicWait32[(MemArg)] = "memory.atomic.wait32",
+        MemoryAtomicWait32[(MemArg)] = "memory.atomic.wait32" => visit_memory_atomic_wait32,
         /// [**memory.atomic.wait64**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        MemoryAtomicWait64[(MemArg)] = "memory.atomic.wait64",
+        MemoryAtomicWait64[(MemArg)] = "memory.atomic.wait64" => visit_memory_atomic_wait64,
 
         /// [**i32.atomic.load**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I32AtomicLoad[(MemArg)] = "i32.atomic.load",
+        I32AtomicLoad[(MemArg)] = "i32.atomic.load" => visit_i32_atomic_load,
         /// [**i64.atomic.load**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I64AtomicLoad[(MemArg)] = "i64.atomic.load",
+        I64AtomicLoad[(MemArg)] = "i64.atomic.load" => visit_i64_atomic_load,
         /// [**i32.atomic.load8_u**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I32AtomicLoad8U[(MemArg)] = "i32.atomic.load8_u",
+        I32AtomicLoad8U[(MemArg)] = "i32.atomic.load8_u" => visit_i32_atomic_load8_u,
         /// [**i32.atomic.load16_u**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I32AtomicLoad16U[(MemArg)] = "i32.atomic.load16_u",
+        I32AtomicLoad16U[(MemArg)] = "i32.atomic.load16_u" => visit_i32_atomic_load16_u,
         /// [**i64.atomic.load8_u**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I64AtomicLoad8U[(MemArg)] = "i64.atomic.load8_u",
+        I64AtomicLoad8U[(MemArg)] = "i64.atomic.load8_u" => visit_i64_atomic_load8_u,
         /// [**i64.atomic.load16_u**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I64AtomicLoad16U[(MemArg)] = "i64.atomic.load16_u",
+        I64AtomicLoad16U[(MemArg)] = "i64.atomic.load16_u" => visit_i64_atomic_load16_u,
         /// [**i64.atomic.load32_u**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I64AtomicLoad32U[(MemArg)] = "i64.atomic.load32_u",
+        I64AtomicLoad32U[(MemArg)] = "i64.atomic.load32_u" => visit_i64_atomic_load32_u,
 
         /// [**i32.atomic.store**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I32AtomicStore[(MemArg)] = "i32.atomic.store",
+        I32AtomicStore[(MemArg)] = "i32.atomic.store" => visit_i32_atomic_store,
         /// [**i64.atomic.store**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I64AtomicStore[(MemArg)] = "i64.atomic.store",
+        I64AtomicStore[(MemArg)] = "i64.atomic.store" => visit_i64_atomic_store,
         /// [**i32.atomic.store8_u**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I32AtomicStore8U[(MemArg)] = "i32.atomic.store8_u",
+        I32AtomicStore8U[(MemArg)] = "i32.atomic.store8_u" => visit_i32_atomic_store8_u,
         /// [**i32.atomic.store16_u**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I32AtomicStore16U[(MemArg)] = "i32.atomic.store16_u",
+        I32AtomicStore16U[(MemArg)] = "i32.atomic.store16_u" => visit_i32_atomic_store16_u,
         /// [**i64.atomic.store8_u**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I64AtomicStore8U[(MemArg)] = "i64.atomic.store8_u",
+        I64AtomicStore8U[(MemArg)] = "i64.atomic.store8_u" => visit_i64_atomic_store8_u,
         /// [**i64.atomic.store16_u**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I64AtomicStore16U[(MemArg)] = "i64.atomic.store16_u",
+        I64AtomicStore16U[(MemArg)] = "i64.atomic.store16_u" => visit_i64_atomic_store16_u,
         /// [**i64.atomic.store32_u**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I64AtomicStore32U[(MemArg)] = "i64.atomic.store32_u",
+        I64AtomicStore32U[(MemArg)] = "i64.atomic.store32_u" => visit_i64_atomic_store32_u,
 
         /// [**i32.atomic.rmw.add**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I32AtomicRmwAdd[(MemArg)] = "i32.atomic.rmw.add",
+        I32AtomicRmwAdd[(MemArg)] = "i32.atomic.rmw.add" => visit_i32_atomic_rmw_add,
         /// [**i64.atomic.rmw.add**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I64AtomicRmwAdd[(MemArg)] = "i64.atomic.rmw.add",
+        I64AtomicRmwAdd[(MemArg)] = "i64.atomic.rmw.add" => visit_i64_atomic_rmw_add,
         /// [**i32.atomic.rmw8.add_u**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I32AtomicRmw8AddU[(MemArg)] = "i32.atomic.rmw8.add_u",
+        I32AtomicRmw8AddU[(MemArg)] = "i32.atomic.rmw8.add_u" => visit_i32_atomic_rmw8_add_u,
         /// [**i32.atomic.rmw16.add_u**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I32AtomicRmw16AddU[(MemArg)] = "i32.atomic.rmw16.add_u",
+        I32AtomicRmw16AddU[(MemArg)] = "i32.atomic.rmw16.add_u" => visit_i32_atomic_rmw16_add_u,
         /// [**i64.atomic.rmw8.add_u**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I64AtomicRmw8AddU[(MemArg)] = "i64.atomic.rmw8.add_u",
+        I64AtomicRmw8AddU[(MemArg)] = "i64.atomic.rmw8.add_u" => visit_i64_atomic_rmw8_add_u,
         /// [**i64.atomic.rmw16.add_u**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I64AtomicRmw16AddU[(MemArg)] = "i64.atomic.rmw16.add_u",
+        I64AtomicRmw16AddU[(MemArg)] = "i64.atomic.rmw16.add_u" => visit_i64_atomic_rmw16_add_u,
         /// [**i64.atomic.rmw32.add_u**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I64AtomicRmw32AddU[(MemArg)] = "i64.atomic.rmw32.add_u",
+        I64AtomicRmw32AddU[(MemArg)] = "i64.atomic.rmw32.add_u" => visit_i64_atomic_rmw32_add_u,
 
         /// [**i32.atomic.rmw.sub**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I32AtomicRmwSub[(MemArg)] = "i32.atomic.rmw.sub",
+        I32AtomicRmwSub[(MemArg)] = "i32.atomic.rmw.sub" => visit_i32_atomic_rmw_sub,
         /// [**i64.atomic.rmw.sub**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I64AtomicRmwSub[(MemArg)] = "i64.atomic.rmw.sub",
+        I64AtomicRmwSub[(MemArg)] = "i64.atomic.rmw.sub" => visit_i64_atomic_rmw_sub,
         /// [**i32.atomic.rmw8.sub_u**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I32AtomicRmw8SubU[(MemArg)] = "i32.atomic.rmw8.sub_u",
+        I32AtomicRmw8SubU[(MemArg)] = "i32.atomic.rmw8.sub_u" => visit_i32_atomic_rmw8_sub_u,
         /// [**i32.atomic.rmw16.sub_u**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I32AtomicRmw16SubU[(MemArg)] = "i32.atomic.rmw16.sub_u",
+        I32AtomicRmw16SubU[(MemArg)] = "i32.atomic.rmw16.sub_u" => visit_i32_atomic_rmw16_sub_u,
         /// [**i64.atomic.rmw8.sub_u**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I64AtomicRmw8SubU[(MemArg)] = "i64.atomic.rmw8.sub_u",
+        I64AtomicRmw8SubU[(MemArg)] = "i64.atomic.rmw8.sub_u" => visit_i64_atomic_rmw8_sub_u,
         /// [**i64.atomic.rmw16.sub_u**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I64AtomicRmw16SubU[(MemArg)] = "i64.atomic.rmw16.sub_u",
+        I64AtomicRmw16SubU[(MemArg)] = "i64.atomic.rmw16.sub_u" => visit_i64_atomic_rmw16_sub_u,
         /// [**i64.atomic.rmw32.sub_u**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I64AtomicRmw32SubU[(MemArg)] = "i64.atomic.rmw32.sub_u",
+        I64AtomicRmw32SubU[(MemArg)] = "i64.atomic.rmw32.sub_u" => visit_i64_atomic_rmw32_sub_u,
 
         /// [**i32.atomic.rmw.and**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I32AtomicRmwAnd[(MemArg)] = "i32.atomic.rmw.and",
+        I32AtomicRmwAnd[(MemArg)] = "i32.atomic.rmw.and" => visit_i32_atomic_rmw_and,
         /// [**i64.atomic.rmw.and**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I64AtomicRmwAnd[(MemArg)] = "i64.atomic.rmw.and",
+        I64AtomicRmwAnd[(MemArg)] = "i64.atomic.rmw.and" => visit_i64_atomic_rmw_and,
         /// [**i32.atomic.rmw8.and_u**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I32AtomicRmw8AndU[(MemArg)] = "i32.atomic.rmw8.and_u",
+        I32AtomicRmw8AndU[(MemArg)] = "i32.atomic.rmw8.and_u" => visit_i32_atomic_rmw8_and_u,
         /// [**i32.atomic.rmw16.and_u**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I32AtomicRmw16AndU[(MemArg)] = "i32.atomic.rmw16.and_u",
+        I32AtomicRmw16AndU[(MemArg)] = "i32.atomic.rmw16.and_u" => visit_i32_atomic_rmw16_and_u,
         /// [**i64.atomic.rmw8.and_u**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I64AtomicRmw8AndU[(MemArg)] = "i64.atomic.rmw8.and_u",
+        I64AtomicRmw8AndU[(MemArg)] = "i64.atomic.rmw8.and_u" => visit_i64_atomic_rmw8_and_u,
         /// [**i64.atomic.rmw16.and_u**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I64AtomicRmw16AndU[(MemArg)] = "i64.atomic.rmw16.and_u",
+        I64AtomicRmw16AndU[(MemArg)] = "i64.atomic.rmw16.and_u" => visit_i64_atomic_rmw16_and_u,
         /// [**i64.atomic.rmw32.and_u**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I64AtomicRmw32AndU[(MemArg)] = "i64.atomic.rmw32.and_u",
+        I64AtomicRmw32AndU[(MemArg)] = "i64.atomic.rmw32.and_u" => visit_i64_atomic_rmw32_and_u,
 
         /// [**i32.atomic.rmw.or**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I32AtomicRmwOr[(MemArg)] = "i32.atomic.rmw.or",
+        I32AtomicRmwOr[(MemArg)] = "i32.atomic.rmw.or" => visit_i32_atomic_rmw_or,
         /// [**i64.atomic.rmw.or**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I64AtomicRmwOr[(MemArg)] = "i64.atomic.rmw.or",
+        I64AtomicRmwOr[(MemArg)] = "i64.atomic.rmw.or" => visit_i64_atomic_rmw_or,
         /// [**i32.atomic.rmw8.or_u**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I32AtomicRmw8OrU[(MemArg)] = "i32.atomic.rmw8.or_u",
+        I32AtomicRmw8OrU[(MemArg)] = "i32.atomic.rmw8.or_u" => visit_i32_atomic_rmw8_or_u,
         /// [**i32.atomic.rmw16.or_u**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I32AtomicRmw16OrU[(MemArg)] = "i32.atomic.rmw16.or_u",
+        I32AtomicRmw16OrU[(MemArg)] = "i32.atomic.rmw16.or_u" => visit_i32_atomic_rmw16_or_u,
         /// [**i64.atomic.rmw8.or_u**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I64AtomicRmw8OrU[(MemArg)] = "i64.atomic.rmw8.or_u",
+        I64AtomicRmw8OrU[(MemArg)] = "i64.atomic.rmw8.or_u" => visit_i64_atomic_rmw8_or_u,
         /// [**i64.atomic.rmw16.or_u**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I64AtomicRmw16OrU[(MemArg)] = "i64.atomic.rmw16.or_u",
+        I64AtomicRmw16OrU[(MemArg)] = "i64.atomic.rmw16.or_u" => visit_i64_atomic_rmw16_or_u,
         /// [**i64.atomic.rmw32.or_u**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I64AtomicRmw32OrU[(MemArg)] = "i64.atomic.rmw32.or_u",
+        I64AtomicRmw32OrU[(MemArg)] = "i64.atomic.rmw32.or_u" => visit_i64_atomic_rmw32_or_u,
 
         /// [**i32.atomic.rmw.xor**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I32AtomicRmwXor[(MemArg)] = "i32.atomic.rmw.xor",
+        I32AtomicRmwXor[(MemArg)] = "i32.atomic.rmw.xor" => visit_i32_atomic_rmw_xor,
         /// [**i64.atomic.rmw.xor**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I64AtomicRmwXor[(MemArg)] = "i64.atomic.rmw.xor",
+        I64AtomicRmwXor[(MemArg)] = "i64.atomic.rmw.xor" => visit_i64_atomic_rmw_xor,
         /// [**i32.atomic.rmw8.xor_u**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I32AtomicRmw8XorU[(MemArg)] = "i32.atomic.rmw8.xor_u",
+        I32AtomicRmw8XorU[(MemArg)] = "i32.atomic.rmw8.xor_u" => visit_i32_atomic_rmw8_xor_u,
         /// [**i32.atomic.rmw16.xor_u**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I32AtomicRmw16XorU[(MemArg)] = "i32.atomic.rmw16.xor_u",
+        I32AtomicRmw16XorU[(MemArg)] = "i32.atomic.rmw16.xor_u" => visit_i32_atomic_rmw16_xor_u,
         /// [**i64.atomic.rmw8.xor_u**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I64AtomicRmw8XorU[(MemArg)] = "i64.atomic.rmw8.xor_u",
+        I64AtomicRmw8XorU[(MemArg)] = "i64.atomic.rmw8.xor_u" => visit_i64_atomic_rmw8_xor_u,
         /// [**i64.atomic.rmw16.xor_u**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I64AtomicRmw16XorU[(MemArg)] = "i64.atomic.rmw16.xor_u",
+        I64AtomicRmw16XorU[(MemArg)] = "i64.atomic.rmw16.xor_u" => visit_i64_atomic_rmw16_xor_u,
         /// [**i64.atomic.rmw32.xor_u**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I64AtomicRmw32XorU[(MemArg)] = "i64.atomic.rmw32.xor_u",
+        I64AtomicRmw32XorU[(MemArg)] = "i64.atomic.rmw32.xor_u" => visit_i64_atomic_rmw32_xor_u,
 
         /// [**i32.atomic.rmw.xchg**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I32AtomicRmwXchg[(MemArg)] = "i32.atomic.rmw.xchg",
+        I32AtomicRmwXchg[(MemArg)] = "i32.atomic.rmw.xchg" => visit_i32_atomic_rmw_xchg,
         /// [**i64.atomic.rmw.xchg**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I64AtomicRmwXchg[(MemArg)] = "i64.atomic.rmw.xchg",
+        I64AtomicRmwXchg[(MemArg)] = "i64.atomic.rmw.xchg" => visit_i64_atomic_rmw_xchg,
         /// [**i32.atomic.rmw8.xchg_u**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I32AtomicRmw8XchgU[(MemArg)] = "i32.atomic.rmw8.xchg_u",
+        I32AtomicRmw8XchgU[(MemArg)] = "i32.atomic.rmw8.xchg_u" => visit_i32_atomic_rmw8_xchg_u,
         /// [**i32.atomic.rmw16.xchg_u**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I32AtomicRmw16XchgU[(MemArg)] = "i32.atomic.rmw16.xchg_u",
+        I32AtomicRmw16XchgU[(MemArg)] = "i32.atomic.rmw16.xchg_u" => visit_i32_atomic_rmw16_xchg_u,
         /// [**i64.atomic.rmw8.xchg_u**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I64AtomicRmw8XchgU[(MemArg)] = "i64.atomic.rmw8.xchg_u",
+        I64AtomicRmw8XchgU[(MemArg)] = "i64.atomic.rmw8.xchg_u" => visit_i64_atomic_rmw8_xchg_u,
         /// [**i64.atomic.rmw16.xchg_u**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I64AtomicRmw16XchgU[(MemArg)] = "i64.atomic.rmw16.xchg_u",
+        I64AtomicRmw16XchgU[(MemArg)] = "i64.atomic.rmw16.xchg_u" => visit_i64_atomic_rmw16_xchg_u,
         /// [**i64.atomic.rmw32.xchg_u**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I64AtomicRmw32XchgU[(MemArg)] = "i64.atomic.rmw32.xchg_u",
+        I64AtomicRmw32XchgU[(MemArg)] = "i64.atomic.rmw32.xchg_u" => visit_i64_atomic_rmw32_xchg_u,
 
         /// [**i32.atomic.rmw.cmpxchg**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I32AtomicRmwCmpxchg[(MemArg)] = "i32.atomic.rmw.cmpxchg",
+        I32AtomicRmwCmpxchg[(MemArg)] = "i32.atomic.rmw.cmpxchg" => visit_i32_atomic_rmw_cmpxchg,
         /// [**i64.atomic.rmw.cmpxchg**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I64AtomicRmwCmpxchg[(MemArg)] = "i64.atomic.rmw.cmpxchg",
+        I64AtomicRmwCmpxchg[(MemArg)] = "i64.atomic.rmw.cmpxchg" => visit_i64_atomic_rmw_cmpxchg,
         /// [**i32.atomic.rmw8.cmpxchg_u**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I32AtomicRmw8CmpxchgU[(MemArg)] = "i32.atomic.rmw8.cmpxchg_u",
+        I32AtomicRmw8CmpxchgU[(MemArg)] = "i32.atomic.rmw8.cmpxchg_u" => visit_i32_atomic_rmw8_cmpxchg_u,
         /// [**i32.atomic.rmw16.cmpxchg_u**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I32AtomicRmw16CmpxchgU[(MemArg)] = "i32.atomic.rmw16.cmpxchg_u",
+        I32AtomicRmw16CmpxchgU[(MemArg)] = "i32.atomic.rmw16.cmpxchg_u" => visit_i32_atomic_rmw16_cmpxchg_u,
         /// [**i64.atomic.rmw8.cmpxchg_u**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I64AtomicRmw8CmpxchgU[(MemArg)] = "i64.atomic.rmw8.cmpxchg_u",
+        I64AtomicRmw8CmpxchgU[(MemArg)] = "i64.atomic.rmw8.cmpxchg_u" => visit_i64_atomic_rmw8_cmpxchg_u,
         /// [**i64.atomic.rmw16.cmpxchg_u**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I64AtomicRmw16CmpxchgU[(MemArg)] = "i64.atomic.rmw16.cmpxchg_u",
+        I64AtomicRmw16CmpxchgU[(MemArg)] = "i64.atomic.rmw16.cmpxchg_u" => visit_i64_atomic_rmw16_cmpxchg_u,
         /// [**i64.atomic.rmw32.cmpxchg_u**](https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions)
-        I64AtomicRmw32CmpxchgU[(MemArg)] = "i64.atomic.rmw32.cmpxchg_u",
+        I64AtomicRmw32CmpxchgU[(MemArg)] = "i64.atomic.rmw32.cmpxchg_u" => visit_i64_atomic_rmw32_cmpxchg_u,
     }
 
     /// Returns `true` if the [`Instruction`] was introduced as part of the
     /// [exception handling proposal](https://github.com/WebAssembly/exception-handling).
-    is_exception_handling {
+    is_exception_handling [feature = "exceptions"] {
         /// The [**try**](https://webassembly.github.io/exception-handling/core/syntax/instructions.html#control-instructions)
         /// instruction marks the start of a block that can catch exceptions.
-        Try[(BlockType)] = "try",
+        Try[(BlockType)] = "try" => visit_try,
         /// The
         /// [**catch**](https://webassembly.github.io/exception-handling/core/syntax/instructions.html#control-instructions)
         /// instruction marks the start of an exception handler for the given
         /// [`Tag`](crate::component::Tag) for a corresponding
         /// [**try**](https://webassembly.github.io/exception-handling/core/syntax/instructions.html#control-instructions)
         /// instruction.
-        Catch[(index::TagIdx)] = "catch",
+        Catch[(index::TagIdx)] = "catch" => visit_catch,
         /// The
         /// [**throw**](https://webassembly.github.io/exception-handling/core/syntax/instructions.html#control-instructions)
         /// instruction create an exception with the given [`Tag`](crate::component::Tag), then
         /// [throws it](https://webassembly.github.io/exception-handling/core/exec/instructions.html#exception-handling).
-        Throw[(index::TagIdx)] = "throw",
+        Throw[(index::TagIdx)] = "throw" => visit_throw,
         /// The
         /// [**rethrow**](https://webassembly.github.io/exception-handling/core/syntax/instructions.html#control-instructions)
         /// throws a caught exception so that it can be handled by a different enclosing block
         /// referred to by the given label.
-        Rethrow[(LabelIdx)] = "rethrow",
+        Rethrow[(LabelIdx)] = "rethrow" => visit_rethrow,
         /// The
         /// [**catch_all**](https://webassembly.github.io/exception-handling/core/syntax/instructions.html#control-instructions)
         /// instruction marks the start of the handler for uncaught exceptions in the block of a corresponding
         /// [**try**](https://webassembly.github.io/exception-handling/core/syntax/instructions.html#control-instructions)
         /// instruction.
-        CatchAll = "catch_all",
+        CatchAll = "catch_all" => visit_catch_all,
         /// The
         /// [**delegate**](https://webassembly.github.io/exception-handling/core/syntax/instructions.html#control-instructions)
         /// instruction delegates exception handling within the block of an associated
@@ -1438,7 +1862,18 @@ instructions! {
         ///
         /// [**catch**]: https://webassembly.github.io/exception-handling/core/syntax/instructions.html#control-instructions
         /// [**catch_all**]: https://webassembly.github.io/exception-handling/core/syntax/instructions.html#control-instructions
-        Delegate[(LabelIdx)] = "delegate",
+        Delegate[(LabelIdx)] = "delegate" => visit_delegate,
+        /// The
+        /// [**try_table**](https://webassembly.github.io/exception-handling/core/syntax/instructions.html#control-instructions)
+        /// instruction marks the start of a block whose exceptions are handled by the given
+        /// [catch clauses](crate::instruction_set::CatchClause), each of which branch to a label.
+        ///
+        /// This replaces the legacy [**try**](Instruction::Try) instruction.
+        TryTable[(BlockType, crate::instruction_set::CatchClauses<&'a mut u64, I>)] = "try_table" => visit_try_table,
+        /// The
+        /// [**throw_ref**](https://webassembly.github.io/exception-handling/core/syntax/instructions.html#control-instructions)
+        /// instruction rethrows the exception referred to by an `exnref` operand.
+        ThrowRef = "throw_ref" => visit_throw_ref,
     }
 }
 
@@ -1452,8 +1887,25 @@ impl<I: Input> Instruction<'_, I> {
             Self::Select(types) => {
                 types.finish()?;
             }
+            #[cfg(feature = "exceptions")]
+            Self::TryTable(_, clauses) => {
+                clauses.finish().context("catch clauses")?;
+            }
             _ => (),
         }
         Ok(())
     }
 }
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+impl<I: Input> TryFrom<&mut Instruction<'_, I>> for OwnedInstruction {
+    type Error = crate::parser::Error;
+
+    /// Parses any lazily parsed arguments of the `instruction`, as in
+    /// [`Instruction::take_owned`].
+    #[inline]
+    fn try_from(instruction: &mut Instruction<'_, I>) -> Parsed<Self> {
+        instruction.take_owned()
+    }
+}
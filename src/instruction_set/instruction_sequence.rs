@@ -2,928 +2,1100 @@ use crate::{
     component, index,
     input::{BorrowInput, CloneInput, HasInput, Input},
     instruction_set::{
-        self, FCPrefixedOpcode, FEPrefixedOpcode, Instruction, Opcode, VectorOpcode,
+        self,
+        instruction::{BuildInstruction, DecodeInstruction},
+        FCPrefixedOpcode, Instruction, InstructionOpcode, Opcode,
     },
     parser::{self, leb128, Error, ErrorKind, Offset, ResultExt as _},
 };
 
-fn memarg<I: Input>(offset: &mut u64, input: &I) -> parser::Parsed<instruction_set::MemArg> {
-    let a = leb128::u32(offset, input).context("memory argument alignment")?;
-    let o = leb128::u64(offset, input).context("memory argument offset")?;
+#[cfg(feature = "threads")]
+use crate::instruction_set::FEPrefixedOpcode;
 
-    let (a, memory) = if a < 64 {
-        (a, index::MemIdx::from(0u8))
-    } else {
-        (
-            a - 64,
-            component::index(offset, input).context("memory argument target")?,
-        )
-    };
+#[cfg(feature = "simd")]
+use crate::instruction_set::VectorOpcode;
 
-    if let Some(align) = u8::try_from(a).ok().and_then(instruction_set::Align::new) {
-        Ok(instruction_set::MemArg::new(o, align, memory))
-    } else {
-        #[inline(never)]
-        #[cold]
-        fn align_power_too_large(power: u32) -> Error {
-            Error::new(ErrorKind::BadMemArgAlignPower(power))
-        }
+/// Identifies the instruction that a [`MemArg`](instruction_set::MemArg) belongs to, used to
+/// provide context when it could not be parsed.
+#[derive(Clone, Copy)]
+enum MemArgOwner {
+    Opcode(Opcode),
+    #[cfg(feature = "simd")]
+    Vector(VectorOpcode),
+    #[cfg(feature = "threads")]
+    Atomic(FEPrefixedOpcode),
+}
 
-        Err(align_power_too_large(a))
+impl From<Opcode> for MemArgOwner {
+    #[inline]
+    fn from(opcode: Opcode) -> Self {
+        Self::Opcode(opcode)
     }
 }
 
-/// Parses a WebAssembly [`Instruction`].
-///
-/// In order to ensure the instruction is completely parsed, callers should call
-/// [Instruction::finish].
-fn instruction<'a, 'b, I: Input>(
-    offset: &'a mut u64,
-    input: &'b I,
-) -> parser::Parsed<Instruction<'a, &'b I>> {
-    let opcode = Opcode::try_from(parser::one_byte_exact(offset, input).context("opcode byte")?)?;
-    Ok(match opcode {
-        Opcode::Unreachable => Instruction::Unreachable,
-        Opcode::Nop => Instruction::Nop,
-        Opcode::Block => Instruction::Block(component::block_type(offset, input)?),
-        Opcode::Loop => {
-            Instruction::Loop(component::block_type(offset, input).context("loop block type")?)
-        }
-        Opcode::If => {
-            Instruction::If(component::block_type(offset, input).context("if block type")?)
-        }
-        Opcode::Else => Instruction::Else,
-        Opcode::Try => {
-            Instruction::Try(component::block_type(offset, input).context("try block type")?)
-        }
-        Opcode::Catch => Instruction::Catch(component::index(offset, input).context("catch tag")?),
-        Opcode::Throw => Instruction::Throw(component::index(offset, input).context("throw tag")?),
-        Opcode::Rethrow => {
-            Instruction::Rethrow(component::index(offset, input).context("rethrow label")?)
-        }
-        Opcode::End => Instruction::End,
-        Opcode::Br => Instruction::Br(component::index(offset, input).context("br label")?),
-        Opcode::BrIf => Instruction::BrIf(component::index(offset, input).context("br_if label")?),
-        Opcode::BrTable => {
-            let branch_count = parser::leb128::u32(offset.offset_mut(), input)
-                .context("could not parse branch table label count")?;
-
-            if let Some(total_count) = branch_count.checked_add(1) {
-                Instruction::BrTable(component::IndexVector::new(total_count, offset, input))
-            } else {
-                #[inline(never)]
-                #[cold]
-                fn branch_count_overflowed() -> Error {
-                    Error::new(ErrorKind::BranchTableCountOverflow)
-                }
+#[cfg(feature = "simd")]
+impl From<VectorOpcode> for MemArgOwner {
+    #[inline]
+    fn from(opcode: VectorOpcode) -> Self {
+        Self::Vector(opcode)
+    }
+}
 
-                return Err(branch_count_overflowed());
+#[cfg(feature = "threads")]
+impl From<FEPrefixedOpcode> for MemArgOwner {
+    #[inline]
+    fn from(opcode: FEPrefixedOpcode) -> Self {
+        Self::Atomic(opcode)
+    }
+}
+
+impl core::fmt::Display for MemArgOwner {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Opcode(opcode) => write!(f, "{opcode:?} instruction ({:#04X})", *opcode as u8),
+            #[cfg(feature = "simd")]
+            Self::Vector(opcode) => {
+                write!(
+                    f,
+                    "{opcode:?} vector instruction (0xFD {})",
+                    u32::from(*opcode)
+                )
+            }
+            #[cfg(feature = "threads")]
+            Self::Atomic(opcode) => {
+                write!(f, "{opcode:?} atomic instruction (0xFE {})", *opcode as u8)
             }
         }
-        Opcode::Return => Instruction::Return,
-        Opcode::Call => Instruction::Call(component::index(offset, input).context("call target")?),
-        Opcode::CallIndirect => Instruction::CallIndirect(
-            component::index(offset, input).context("indirect call signature")?,
-            component::index(offset, input).context("indirect call target")?,
-        ),
-        Opcode::ReturnCall => {
-            Instruction::ReturnCall(component::index(offset, input).context("tail call target")?)
-        }
-        Opcode::ReturnCallIndirect => Instruction::ReturnCallIndirect(
-            component::index(offset, input).context("indirect tail call signature")?,
-            component::index(offset, input).context("indirect tail call target")?,
-        ),
-        Opcode::Delegate => {
-            Instruction::Delegate(component::index(offset, input).context("delegate label")?)
-        }
-        Opcode::CatchAll => Instruction::CatchAll,
+    }
+}
 
-        Opcode::Drop => Instruction::Drop,
-        Opcode::Select => {
-            Instruction::Select(component::ResultType::empty_with_offset(offset, input))
-        }
-        Opcode::SelectMany => Instruction::Select(
-            component::ResultType::parse(offset, input).context("select types")?,
-        ),
+fn memarg<I: Input>(
+    offset: &mut u64,
+    input: &I,
+    owner: impl Into<MemArgOwner>,
+) -> parser::Parsed<instruction_set::MemArg> {
+    let parse = |offset: &mut u64| {
+        let a = leb128::u32(offset, input).context("memory argument alignment")?;
+
+        // The memory index, if present, precedes the offset
+        let (a, memory) = if a < 64 {
+            (a, index::MemIdx::from(0u8))
+        } else {
+            (
+                a - 64,
+                component::index(offset, input).context("memory argument target")?,
+            )
+        };
 
-        Opcode::LocalGet => Instruction::LocalGet(component::index(offset, input)?),
-        Opcode::LocalSet => Instruction::LocalSet(component::index(offset, input)?),
-        Opcode::LocalTee => Instruction::LocalTee(component::index(offset, input)?),
-        Opcode::GlobalGet => Instruction::GlobalGet(component::index(offset, input)?),
-        Opcode::GlobalSet => Instruction::GlobalSet(component::index(offset, input)?),
-
-        Opcode::TableGet => Instruction::TableGet(component::index(offset, input)?),
-        Opcode::TableSet => Instruction::TableSet(component::index(offset, input)?),
-
-        Opcode::I32Load => Instruction::I32Load(memarg(offset, input)?),
-        Opcode::I64Load => Instruction::I64Load(memarg(offset, input)?),
-        Opcode::F32Load => Instruction::F32Load(memarg(offset, input)?),
-        Opcode::F64Load => Instruction::F64Load(memarg(offset, input)?),
-
-        Opcode::I32Load8S => Instruction::I32Load8S(memarg(offset, input)?),
-        Opcode::I32Load8U => Instruction::I32Load8U(memarg(offset, input)?),
-        Opcode::I32Load16S => Instruction::I32Load16S(memarg(offset, input)?),
-        Opcode::I32Load16U => Instruction::I32Load16U(memarg(offset, input)?),
-        Opcode::I64Load8S => Instruction::I64Load8S(memarg(offset, input)?),
-        Opcode::I64Load8U => Instruction::I64Load8U(memarg(offset, input)?),
-        Opcode::I64Load16S => Instruction::I64Load16S(memarg(offset, input)?),
-        Opcode::I64Load16U => Instruction::I64Load16U(memarg(offset, input)?),
-        Opcode::I64Load32S => Instruction::I64Load32S(memarg(offset, input)?),
-        Opcode::I64Load32U => Instruction::I64Load32U(memarg(offset, input)?),
-
-        Opcode::I32Store => Instruction::I32Store(memarg(offset, input)?),
-        Opcode::I64Store => Instruction::I64Store(memarg(offset, input)?),
-        Opcode::F32Store => Instruction::F32Store(memarg(offset, input)?),
-        Opcode::F64Store => Instruction::F64Store(memarg(offset, input)?),
-
-        Opcode::I32Store8 => Instruction::I32Store8(memarg(offset, input)?),
-        Opcode::I32Store16 => Instruction::I32Store16(memarg(offset, input)?),
-        Opcode::I64Store8 => Instruction::I64Store8(memarg(offset, input)?),
-        Opcode::I64Store16 => Instruction::I64Store16(memarg(offset, input)?),
-        Opcode::I64Store32 => Instruction::I64Store32(memarg(offset, input)?),
-
-        Opcode::MemorySize => Instruction::MemorySize(component::index(offset, input)?),
-        Opcode::MemoryGrow => Instruction::MemoryGrow(component::index(offset, input)?),
-
-        Opcode::I32Const => Instruction::I32Const(leb128::s32(offset, input)?),
-        Opcode::I64Const => Instruction::I64Const(leb128::s64(offset, input)?),
-        Opcode::F32Const => Instruction::F32Const(f32::from_le_bytes(
-            parser::byte_array(offset, input).context("32-bit float constant")?,
-        )),
-        Opcode::F64Const => Instruction::F64Const(f64::from_le_bytes(
-            parser::byte_array(offset, input).context("64-bit float constant")?,
-        )),
-
-        Opcode::I32Eqz => Instruction::I32Eqz,
-        Opcode::I32Eq => Instruction::I32Eq,
-        Opcode::I32Ne => Instruction::I32Ne,
-        Opcode::I32LtS => Instruction::I32LtS,
-        Opcode::I32LtU => Instruction::I32LtU,
-        Opcode::I32GtS => Instruction::I32GtS,
-        Opcode::I32GtU => Instruction::I32GtU,
-        Opcode::I32LeS => Instruction::I32LeS,
-        Opcode::I32LeU => Instruction::I32LeU,
-        Opcode::I32GeS => Instruction::I32GeS,
-        Opcode::I32GeU => Instruction::I32GeU,
-
-        Opcode::I64Eqz => Instruction::I64Eqz,
-        Opcode::I64Eq => Instruction::I64Eq,
-        Opcode::I64Ne => Instruction::I64Ne,
-        Opcode::I64LtS => Instruction::I64LtS,
-        Opcode::I64LtU => Instruction::I64LtU,
-        Opcode::I64GtS => Instruction::I64GtS,
-        Opcode::I64GtU => Instruction::I64GtU,
-        Opcode::I64LeS => Instruction::I64LeS,
-        Opcode::I64LeU => Instruction::I64LeU,
-        Opcode::I64GeS => Instruction::I64GeS,
-        Opcode::I64GeU => Instruction::I64GeU,
-
-        Opcode::F32Eq => Instruction::F32Eq,
-        Opcode::F32Ne => Instruction::F32Ne,
-        Opcode::F32Lt => Instruction::F32Lt,
-        Opcode::F32Gt => Instruction::F32Gt,
-        Opcode::F32Le => Instruction::F32Le,
-        Opcode::F32Ge => Instruction::F32Ge,
-        Opcode::F64Eq => Instruction::F64Eq,
-        Opcode::F64Ne => Instruction::F64Ne,
-        Opcode::F64Lt => Instruction::F64Lt,
-        Opcode::F64Gt => Instruction::F64Gt,
-        Opcode::F64Le => Instruction::F64Le,
-        Opcode::F64Ge => Instruction::F64Ge,
-
-        Opcode::I32Clz => Instruction::I32Clz,
-        Opcode::I32Ctz => Instruction::I32Ctz,
-        Opcode::I32Popcnt => Instruction::I32Popcnt,
-        Opcode::I32Add => Instruction::I32Add,
-        Opcode::I32Sub => Instruction::I32Sub,
-        Opcode::I32Mul => Instruction::I32Mul,
-        Opcode::I32DivS => Instruction::I32DivS,
-        Opcode::I32DivU => Instruction::I32DivU,
-        Opcode::I32RemS => Instruction::I32RemS,
-        Opcode::I32RemU => Instruction::I32RemU,
-        Opcode::I32And => Instruction::I32And,
-        Opcode::I32Or => Instruction::I32Or,
-        Opcode::I32Xor => Instruction::I32Xor,
-        Opcode::I32Shl => Instruction::I32Shl,
-        Opcode::I32ShrS => Instruction::I32ShrS,
-        Opcode::I32ShrU => Instruction::I32ShrU,
-        Opcode::I32Rotl => Instruction::I32Rotl,
-        Opcode::I32Rotr => Instruction::I32Rotr,
-
-        Opcode::I64Clz => Instruction::I64Clz,
-        Opcode::I64Ctz => Instruction::I64Ctz,
-        Opcode::I64Popcnt => Instruction::I64Popcnt,
-        Opcode::I64Add => Instruction::I64Add,
-        Opcode::I64Sub => Instruction::I64Sub,
-        Opcode::I64Mul => Instruction::I64Mul,
-        Opcode::I64DivS => Instruction::I64DivS,
-        Opcode::I64DivU => Instruction::I64DivU,
-        Opcode::I64RemS => Instruction::I64RemS,
-        Opcode::I64RemU => Instruction::I64RemU,
-        Opcode::I64And => Instruction::I64And,
-        Opcode::I64Or => Instruction::I64Or,
-        Opcode::I64Xor => Instruction::I64Xor,
-        Opcode::I64Shl => Instruction::I64Shl,
-        Opcode::I64ShrS => Instruction::I64ShrS,
-        Opcode::I64ShrU => Instruction::I64ShrU,
-        Opcode::I64Rotl => Instruction::I64Rotl,
-        Opcode::I64Rotr => Instruction::I64Rotr,
-
-        Opcode::F32Abs => Instruction::F32Abs,
-        Opcode::F32Neg => Instruction::F32Neg,
-        Opcode::F32Ceil => Instruction::F32Ceil,
-        Opcode::F32Floor => Instruction::F32Floor,
-        Opcode::F32Trunc => Instruction::F32Trunc,
-        Opcode::F32Nearest => Instruction::F32Nearest,
-        Opcode::F32Sqrt => Instruction::F32Sqrt,
-        Opcode::F32Add => Instruction::F32Add,
-        Opcode::F32Sub => Instruction::F32Sub,
-        Opcode::F32Mul => Instruction::F32Mul,
-        Opcode::F32Div => Instruction::F32Div,
-        Opcode::F32Min => Instruction::F32Min,
-        Opcode::F32Max => Instruction::F32Max,
-        Opcode::F32Copysign => Instruction::F32Copysign,
-
-        Opcode::F64Abs => Instruction::F64Abs,
-        Opcode::F64Neg => Instruction::F64Neg,
-        Opcode::F64Ceil => Instruction::F64Ceil,
-        Opcode::F64Floor => Instruction::F64Floor,
-        Opcode::F64Trunc => Instruction::F64Trunc,
-        Opcode::F64Nearest => Instruction::F64Nearest,
-        Opcode::F64Sqrt => Instruction::F64Sqrt,
-        Opcode::F64Add => Instruction::F64Add,
-        Opcode::F64Sub => Instruction::F64Sub,
-        Opcode::F64Mul => Instruction::F64Mul,
-        Opcode::F64Div => Instruction::F64Div,
-        Opcode::F64Min => Instruction::F64Min,
-        Opcode::F64Max => Instruction::F64Max,
-        Opcode::F64Copysign => Instruction::F64Copysign,
-
-        Opcode::I32WrapI64 => Instruction::I32WrapI64,
-        Opcode::I32TruncF32S => Instruction::I32TruncF32S,
-        Opcode::I32TruncF32U => Instruction::I32TruncF32U,
-        Opcode::I32TruncF64S => Instruction::I32TruncF64S,
-        Opcode::I32TruncF64U => Instruction::I32TruncF64U,
-        Opcode::I64ExtendI32S => Instruction::I64ExtendI32S,
-        Opcode::I64ExtendI32U => Instruction::I64ExtendI32U,
-        Opcode::I64TruncF32S => Instruction::I64TruncF32S,
-        Opcode::I64TruncF32U => Instruction::I64TruncF32U,
-        Opcode::I64TruncF64S => Instruction::I64TruncF64S,
-        Opcode::I64TruncF64U => Instruction::I64TruncF64U,
-        Opcode::F32ConvertI32S => Instruction::F32ConvertI32S,
-        Opcode::F32ConvertI32U => Instruction::F32ConvertI32U,
-        Opcode::F32ConvertI64S => Instruction::F32ConvertI64S,
-        Opcode::F32ConvertI64U => Instruction::F32ConvertI64U,
-        Opcode::F32DemoteF64 => Instruction::F32DemoteF64,
-        Opcode::F64ConvertI32S => Instruction::F64ConvertI32S,
-        Opcode::F64ConvertI32U => Instruction::F64ConvertI32U,
-        Opcode::F64ConvertI64S => Instruction::F64ConvertI64S,
-        Opcode::F64ConvertI64U => Instruction::F64ConvertI64U,
-        Opcode::F64PromoteF32 => Instruction::F64PromoteF32,
-        Opcode::I32ReinterpretF32 => Instruction::I32ReinterpretF32,
-        Opcode::I64ReinterpretF64 => Instruction::I64ReinterpretF64,
-        Opcode::F32ReinterpretI32 => Instruction::F32ReinterpretI32,
-        Opcode::F64ReinterpretI64 => Instruction::F64ReinterpretI64,
-
-        Opcode::I32Extend8S => Instruction::I32Extend8S,
-        Opcode::I32Extend16S => Instruction::I32Extend16S,
-        Opcode::I64Extend8S => Instruction::I64Extend8S,
-        Opcode::I64Extend16S => Instruction::I64Extend16S,
-        Opcode::I64Extend32S => Instruction::I64Extend32S,
-
-        Opcode::RefNull => {
-            Instruction::RefNull(component::ref_type(offset, input).context("type for null")?)
-        }
-        Opcode::RefIsNull => Instruction::RefIsNull,
-        Opcode::RefFunc => Instruction::RefFunc(
-            component::index(offset, input).context("invalid reference to function")?,
-        ),
+        let o = leb128::u64(offset, input).context("memory argument offset")?;
 
-        Opcode::PrefixFC => {
-            let actual_opcode = leb128::u32(offset, input)
-                .context("actual opcode")?
-                .try_into()?;
-
-            match actual_opcode {
-                FCPrefixedOpcode::MemoryInit => Instruction::MemoryInit(
-                    component::index(offset, input)?,
-                    component::index(offset, input)?,
-                ),
-                FCPrefixedOpcode::DataDrop => {
-                    Instruction::DataDrop(component::index(offset, input)?)
-                }
-                FCPrefixedOpcode::MemoryCopy => Instruction::MemoryCopy {
-                    destination: component::index(offset, input).context("destination memory")?,
-                    source: component::index(offset, input).context("source memory")?,
-                },
-                FCPrefixedOpcode::MemoryFill => {
-                    Instruction::MemoryFill(component::index(offset, input)?)
-                }
-                FCPrefixedOpcode::TableInit => Instruction::TableInit(
-                    component::index(offset, input)?,
-                    component::index(offset, input)?,
-                ),
-                FCPrefixedOpcode::ElemDrop => {
-                    Instruction::ElemDrop(component::index(offset, input)?)
-                }
-                FCPrefixedOpcode::TableCopy => Instruction::TableCopy {
-                    destination: component::index(offset, input).context("destination table")?,
-                    source: component::index(offset, input).context("source table")?,
-                },
-                FCPrefixedOpcode::TableGrow => {
-                    Instruction::TableGrow(component::index(offset, input)?)
-                }
-                FCPrefixedOpcode::TableSize => {
-                    Instruction::TableSize(component::index(offset, input)?)
-                }
-                FCPrefixedOpcode::TableFill => {
-                    Instruction::TableFill(component::index(offset, input)?)
-                }
-                FCPrefixedOpcode::I32TruncSatF32S => Instruction::I32TruncSatF32S,
-                FCPrefixedOpcode::I32TruncSatF32U => Instruction::I32TruncSatF32U,
-                FCPrefixedOpcode::I32TruncSatF64S => Instruction::I32TruncSatF64S,
-                FCPrefixedOpcode::I32TruncSatF64U => Instruction::I32TruncSatF64U,
-                FCPrefixedOpcode::I64TruncSatF32S => Instruction::I64TruncSatF32S,
-                FCPrefixedOpcode::I64TruncSatF32U => Instruction::I64TruncSatF32U,
-                FCPrefixedOpcode::I64TruncSatF64S => Instruction::I64TruncSatF64S,
-                FCPrefixedOpcode::I64TruncSatF64U => Instruction::I64TruncSatF64U,
+        if let Some(align) = u8::try_from(a).ok().and_then(instruction_set::Align::new) {
+            Ok(instruction_set::MemArg::new(o, align, memory))
+        } else {
+            #[inline(never)]
+            #[cold]
+            fn align_power_too_large(power: u32) -> Error {
+                Error::new(ErrorKind::BadMemArgAlignPower(power))
             }
+
+            Err(align_power_too_large(a))
         }
-        Opcode::PrefixV128 => {
-            let actual_opcode = leb128::u32(offset, input)
+    };
+
+    let owner = owner.into();
+    parse(offset).with_context(|| move |f| write!(f, "memory argument of {owner}"))
+}
+
+/// Reads the opcode of the next instruction, including the opcode following a prefix byte.
+fn opcode<I: Input>(offset: &mut u64, input: &I) -> parser::Parsed<InstructionOpcode> {
+    let opcode_offset = *offset;
+    let opcode = Opcode::try_from(parser::one_byte_exact(offset, input).context("opcode byte")?)
+        .map_err(|e| parser::Error::from(e).with_offset(opcode_offset))?;
+    Ok(match opcode {
+        Opcode::PrefixFC => InstructionOpcode::FC(
+            leb128::u32(offset, input)
                 .context("actual opcode")?
-                .try_into()?;
-
-            match actual_opcode {
-                VectorOpcode::Load => Instruction::V128Load(memarg(offset, input)?),
-
-                VectorOpcode::Load8x8S => Instruction::V128Load8x8S(memarg(offset, input)?),
-                VectorOpcode::Load8x8U => Instruction::V128Load8x8U(memarg(offset, input)?),
-                VectorOpcode::Load16x4S => Instruction::V128Load16x4S(memarg(offset, input)?),
-                VectorOpcode::Load16x4U => Instruction::V128Load16x4U(memarg(offset, input)?),
-                VectorOpcode::Load32x2S => Instruction::V128Load32x2S(memarg(offset, input)?),
-                VectorOpcode::Load32x2U => Instruction::V128Load32x2U(memarg(offset, input)?),
-
-                VectorOpcode::Load8Splat => Instruction::V128Load8Splat(memarg(offset, input)?),
-                VectorOpcode::Load16Splat => Instruction::V128Load16Splat(memarg(offset, input)?),
-                VectorOpcode::Load32Splat => Instruction::V128Load32Splat(memarg(offset, input)?),
-                VectorOpcode::Load64Splat => Instruction::V128Load64Splat(memarg(offset, input)?),
-                VectorOpcode::Load32Zero => Instruction::V128Load32Zero(memarg(offset, input)?),
-                VectorOpcode::Load64Zero => Instruction::V128Load64Zero(memarg(offset, input)?),
-
-                VectorOpcode::Store => Instruction::V128Store(memarg(offset, input)?),
-
-                VectorOpcode::Load8Lane => Instruction::V128Load8Lane(
-                    memarg(offset, input)?,
-                    parser::one_byte_exact(offset, input)?,
-                ),
-                VectorOpcode::Load16Lane => Instruction::V128Load16Lane(
-                    memarg(offset, input)?,
-                    parser::one_byte_exact(offset, input)?,
-                ),
-                VectorOpcode::Load32Lane => Instruction::V128Load32Lane(
-                    memarg(offset, input)?,
-                    parser::one_byte_exact(offset, input)?,
-                ),
-                VectorOpcode::Load64Lane => Instruction::V128Load64Lane(
-                    memarg(offset, input)?,
-                    parser::one_byte_exact(offset, input)?,
-                ),
-
-                VectorOpcode::Store8Lane => Instruction::V128Store8Lane(
-                    memarg(offset, input)?,
-                    parser::one_byte_exact(offset, input)?,
-                ),
-                VectorOpcode::Store16Lane => Instruction::V128Store16Lane(
-                    memarg(offset, input)?,
-                    parser::one_byte_exact(offset, input)?,
-                ),
-                VectorOpcode::Store32Lane => Instruction::V128Store32Lane(
-                    memarg(offset, input)?,
-                    parser::one_byte_exact(offset, input)?,
-                ),
-                VectorOpcode::Store64Lane => Instruction::V128Store64Lane(
-                    memarg(offset, input)?,
-                    parser::one_byte_exact(offset, input)?,
-                ),
-
-                VectorOpcode::Const => Instruction::V128Const(u128::from_le_bytes(
-                    parser::byte_array(offset, input).context("constant 128-bit vector")?,
-                )),
-
-                VectorOpcode::I8x16Shuffle => Instruction::I8x16Shuffle(
-                    parser::byte_array(offset, input).context("shuffle lane indices")?,
-                ),
-
-                VectorOpcode::I8x16ExtractLaneS => Instruction::I8x16ExtractLaneS(
-                    parser::one_byte_exact(offset, input).context("vector lane index")?,
-                ),
-                VectorOpcode::I8x16ExtractLaneU => Instruction::I8x16ExtractLaneU(
-                    parser::one_byte_exact(offset, input).context("vector lane index")?,
-                ),
-                VectorOpcode::I8x16ReplaceLane => Instruction::I8x16ReplaceLane(
-                    parser::one_byte_exact(offset, input).context("vector lane index")?,
-                ),
-
-                VectorOpcode::I16x8ExtractLaneS => Instruction::I16x8ExtractLaneS(
-                    parser::one_byte_exact(offset, input).context("vector lane index")?,
-                ),
-                VectorOpcode::I16x8ExtractLaneU => Instruction::I16x8ExtractLaneU(
-                    parser::one_byte_exact(offset, input).context("vector lane index")?,
-                ),
-                VectorOpcode::I16x8ReplaceLane => Instruction::I16x8ReplaceLane(
-                    parser::one_byte_exact(offset, input).context("vector lane index")?,
-                ),
-
-                VectorOpcode::I32x4ExtractLane => Instruction::I32x4ExtractLane(
-                    parser::one_byte_exact(offset, input).context("vector lane index")?,
-                ),
-                VectorOpcode::I32x4ReplaceLane => Instruction::I32x4ReplaceLane(
-                    parser::one_byte_exact(offset, input).context("vector lane index")?,
-                ),
-
-                VectorOpcode::I64x2ExtractLane => Instruction::I64x2ExtractLane(
-                    parser::one_byte_exact(offset, input).context("vector lane index")?,
-                ),
-                VectorOpcode::I64x2ReplaceLane => Instruction::I64x2ReplaceLane(
-                    parser::one_byte_exact(offset, input).context("vector lane index")?,
-                ),
-
-                VectorOpcode::F32x4ExtractLane => Instruction::F32x4ExtractLane(
-                    parser::one_byte_exact(offset, input).context("vector lane index")?,
-                ),
-                VectorOpcode::F32x4ReplaceLane => Instruction::F32x4ReplaceLane(
-                    parser::one_byte_exact(offset, input).context("vector lane index")?,
-                ),
-
-                VectorOpcode::F64x2ExtractLane => Instruction::F64x2ExtractLane(
-                    parser::one_byte_exact(offset, input).context("vector lane index")?,
-                ),
-                VectorOpcode::F64x2ReplaceLane => Instruction::F64x2ReplaceLane(
-                    parser::one_byte_exact(offset, input).context("vector lane index")?,
-                ),
-                VectorOpcode::I8x16Swizzle => Instruction::I8x16Swizzle,
-
-                VectorOpcode::I8x16Splat => Instruction::I8x16Splat,
-                VectorOpcode::I16x8Splat => Instruction::I16x8Splat,
-                VectorOpcode::I32x4Splat => Instruction::I32x4Splat,
-                VectorOpcode::I64x2Splat => Instruction::I64x2Splat,
-                VectorOpcode::F32x4Splat => Instruction::F32x4Splat,
-                VectorOpcode::F64x2Splat => Instruction::F64x2Splat,
-
-                VectorOpcode::I8x16Eq => Instruction::I8x16Eq,
-                VectorOpcode::I8x16Ne => Instruction::I8x16Ne,
-                VectorOpcode::I8x16LtS => Instruction::I8x16LtS,
-                VectorOpcode::I8x16LtU => Instruction::I8x16LtU,
-                VectorOpcode::I8x16GtS => Instruction::I8x16GtS,
-                VectorOpcode::I8x16GtU => Instruction::I8x16GtU,
-                VectorOpcode::I8x16LeS => Instruction::I8x16LeS,
-                VectorOpcode::I8x16LeU => Instruction::I8x16LeU,
-                VectorOpcode::I8x16GeS => Instruction::I8x16GeS,
-                VectorOpcode::I8x16GeU => Instruction::I8x16GeU,
-
-                VectorOpcode::I16x8Eq => Instruction::I16x8Eq,
-                VectorOpcode::I16x8Ne => Instruction::I16x8Ne,
-                VectorOpcode::I16x8LtS => Instruction::I16x8LtS,
-                VectorOpcode::I16x8LtU => Instruction::I16x8LtU,
-                VectorOpcode::I16x8GtS => Instruction::I16x8GtS,
-                VectorOpcode::I16x8GtU => Instruction::I16x8GtU,
-                VectorOpcode::I16x8LeS => Instruction::I16x8LeS,
-                VectorOpcode::I16x8LeU => Instruction::I16x8LeU,
-                VectorOpcode::I16x8GeS => Instruction::I16x8GeS,
-                VectorOpcode::I16x8GeU => Instruction::I16x8GeU,
-
-                VectorOpcode::I32x4Eq => Instruction::I32x4Eq,
-                VectorOpcode::I32x4Ne => Instruction::I32x4Ne,
-                VectorOpcode::I32x4LtS => Instruction::I32x4LtS,
-                VectorOpcode::I32x4LtU => Instruction::I32x4LtU,
-                VectorOpcode::I32x4GtS => Instruction::I32x4GtS,
-                VectorOpcode::I32x4GtU => Instruction::I32x4GtU,
-                VectorOpcode::I32x4LeS => Instruction::I32x4LeS,
-                VectorOpcode::I32x4LeU => Instruction::I32x4LeU,
-                VectorOpcode::I32x4GeS => Instruction::I32x4GeS,
-                VectorOpcode::I32x4GeU => Instruction::I32x4GeU,
-
-                VectorOpcode::I64x2Eq => Instruction::I64x2Eq,
-                VectorOpcode::I64x2Ne => Instruction::I64x2Ne,
-                VectorOpcode::I64x2LtS => Instruction::I64x2LtS,
-                VectorOpcode::I64x2GtS => Instruction::I64x2GtS,
-                VectorOpcode::I64x2LeS => Instruction::I64x2LeS,
-                VectorOpcode::I64x2GeS => Instruction::I64x2GeS,
-
-                VectorOpcode::F32x4Eq => Instruction::F32x4Eq,
-                VectorOpcode::F32x4Ne => Instruction::F32x4Ne,
-                VectorOpcode::F32x4Lt => Instruction::F32x4Lt,
-                VectorOpcode::F32x4Gt => Instruction::F32x4Gt,
-                VectorOpcode::F32x4Le => Instruction::F32x4Le,
-                VectorOpcode::F32x4Ge => Instruction::F32x4Ge,
-
-                VectorOpcode::F64x2Eq => Instruction::F64x2Eq,
-                VectorOpcode::F64x2Ne => Instruction::F64x2Ne,
-                VectorOpcode::F64x2Lt => Instruction::F64x2Lt,
-                VectorOpcode::F64x2Gt => Instruction::F64x2Gt,
-                VectorOpcode::F64x2Le => Instruction::F64x2Le,
-                VectorOpcode::F64x2Ge => Instruction::F64x2Ge,
-
-                VectorOpcode::Not => Instruction::V128Not,
-                VectorOpcode::And => Instruction::V128And,
-                VectorOpcode::AndNot => Instruction::V128AndNot,
-                VectorOpcode::Or => Instruction::V128Or,
-                VectorOpcode::Xor => Instruction::V128Xor,
-                VectorOpcode::Bitselect => Instruction::V128Bitselect,
-                VectorOpcode::AnyTrue => Instruction::V128AnyTrue,
-
-                VectorOpcode::I8x16Abs => Instruction::I8x16Abs,
-                VectorOpcode::I8x16Neg => Instruction::I8x16Neg,
-                VectorOpcode::I8x16Popcnt => Instruction::I8x16Popcnt,
-                VectorOpcode::I8x16AllTrue => Instruction::I8x16AllTrue,
-                VectorOpcode::I8x16Bitmask => Instruction::I8x16Bitmask,
-                VectorOpcode::I8x16NarrowI16x8S => Instruction::I8x16NarrowI16x8S,
-                VectorOpcode::I8x16NarrowI16x8U => Instruction::I8x16NarrowI16x8U,
-                VectorOpcode::I8x16Shl => Instruction::I8x16Shl,
-                VectorOpcode::I8x16ShrS => Instruction::I8x16ShrS,
-                VectorOpcode::I8x16ShrU => Instruction::I8x16ShrU,
-                VectorOpcode::I8x16Add => Instruction::I8x16Add,
-                VectorOpcode::I8x16AddSatS => Instruction::I8x16AddSatS,
-                VectorOpcode::I8x16AddSatU => Instruction::I8x16AddSatU,
-                VectorOpcode::I8x16Sub => Instruction::I8x16Sub,
-                VectorOpcode::I8x16SubSatS => Instruction::I8x16SubSatS,
-                VectorOpcode::I8x16SubSatU => Instruction::I8x16SubSatU,
-                VectorOpcode::I8x16MinS => Instruction::I8x16MinS,
-                VectorOpcode::I8x16MinU => Instruction::I8x16MinU,
-                VectorOpcode::I8x16MaxS => Instruction::I8x16MaxS,
-                VectorOpcode::I8x16MaxU => Instruction::I8x16MaxU,
-                VectorOpcode::I8x16AvgrU => Instruction::I8x16AvgrU,
-
-                VectorOpcode::I16x8ExtaddPairwiseI8x16S => Instruction::I16x8ExtaddPairwiseI8x16S,
-                VectorOpcode::I16x8ExtaddPairwiseI8x16U => Instruction::I16x8ExtaddPairwiseI8x16U,
-                VectorOpcode::I16x8Abs => Instruction::I16x8Abs,
-                VectorOpcode::I16x8Neg => Instruction::I16x8Neg,
-                VectorOpcode::I16x8Q15MulrSatS => Instruction::I16x8Q15MulrSatS,
-                VectorOpcode::I16x8AllTrue => Instruction::I16x8AllTrue,
-                VectorOpcode::I16x8Bitmask => Instruction::I16x8Bitmask,
-                VectorOpcode::I16x8NarrowI32x4S => Instruction::I16x8NarrowI32x4S,
-                VectorOpcode::I16x8NarrowI32x4U => Instruction::I16x8NarrowI32x4U,
-                VectorOpcode::I16x8ExtendLowI8x16S => Instruction::I16x8ExtendLowI8x16S,
-                VectorOpcode::I16x8ExtendHighI8x16S => Instruction::I16x8ExtendHighI8x16S,
-                VectorOpcode::I16x8ExtendLowI8x16U => Instruction::I16x8ExtendLowI8x16U,
-                VectorOpcode::I16x8ExtendHighI8x16U => Instruction::I16x8ExtendHighI8x16U,
-                VectorOpcode::I16x8Shl => Instruction::I16x8Shl,
-                VectorOpcode::I16x8ShrS => Instruction::I16x8ShrS,
-                VectorOpcode::I16x8ShrU => Instruction::I16x8ShrU,
-                VectorOpcode::I16x8Add => Instruction::I16x8Add,
-                VectorOpcode::I16x8AddSatS => Instruction::I16x8AddSatS,
-                VectorOpcode::I16x8AddSatU => Instruction::I16x8AddSatU,
-                VectorOpcode::I16x8Sub => Instruction::I16x8Sub,
-                VectorOpcode::I16x8SubSatS => Instruction::I16x8SubSatS,
-                VectorOpcode::I16x8SubSatU => Instruction::I16x8SubSatU,
-                VectorOpcode::I16x8Mul => Instruction::I16x8Mul,
-                VectorOpcode::I16x8MinS => Instruction::I16x8MinS,
-                VectorOpcode::I16x8MinU => Instruction::I16x8MinU,
-                VectorOpcode::I16x8MaxS => Instruction::I16x8MaxS,
-                VectorOpcode::I16x8MaxU => Instruction::I16x8MaxU,
-                VectorOpcode::I16x8AvgrU => Instruction::I16x8AvgrU,
-                VectorOpcode::I16x8ExtmulLowI8x16S => Instruction::I16x8ExtmulLowI8x16S,
-                VectorOpcode::I16x8ExtmulHighI8x16S => Instruction::I16x8ExtmulHighI8x16S,
-                VectorOpcode::I16x8ExtmulLowI8x16U => Instruction::I16x8ExtmulLowI8x16U,
-                VectorOpcode::I16x8ExtmulHighI8x16U => Instruction::I16x8ExtmulHighI8x16U,
-
-                VectorOpcode::I32x4ExtaddPairwiseI16x8S => Instruction::I32x4ExtaddPairwiseI16x8S,
-                VectorOpcode::I32x4ExtaddPairwiseI16x8U => Instruction::I32x4ExtaddPairwiseI16x8U,
-                VectorOpcode::I32x4Abs => Instruction::I32x4Abs,
-                VectorOpcode::I32x4Neg => Instruction::I32x4Neg,
-                VectorOpcode::I32x4AllTrue => Instruction::I32x4AllTrue,
-                VectorOpcode::I32x4Bitmask => Instruction::I32x4Bitmask,
-                VectorOpcode::I32x4ExtendLowI16x8S => Instruction::I32x4ExtendLowI16x8S,
-                VectorOpcode::I32x4ExtendHighI16x8S => Instruction::I32x4ExtendHighI16x8S,
-                VectorOpcode::I32x4ExtendLowI16x8U => Instruction::I32x4ExtendLowI16x8U,
-                VectorOpcode::I32x4ExtendHighI16x8U => Instruction::I32x4ExtendHighI16x8U,
-                VectorOpcode::I32x4Shl => Instruction::I32x4Shl,
-                VectorOpcode::I32x4ShrS => Instruction::I32x4ShrS,
-                VectorOpcode::I32x4ShrU => Instruction::I32x4ShrU,
-                VectorOpcode::I32x4Add => Instruction::I32x4Add,
-                VectorOpcode::I32x4Sub => Instruction::I32x4Sub,
-                VectorOpcode::I32x4Mul => Instruction::I32x4Mul,
-                VectorOpcode::I32x4MinS => Instruction::I32x4MinS,
-                VectorOpcode::I32x4MinU => Instruction::I32x4MinU,
-                VectorOpcode::I32x4MaxS => Instruction::I32x4MaxS,
-                VectorOpcode::I32x4MaxU => Instruction::I32x4MaxU,
-                VectorOpcode::I32x4DotI16x8S => Instruction::I32x4DotI16x8S,
-                VectorOpcode::I32x4ExtmulLowI16x8S => Instruction::I32x4ExtmulLowI16x8S,
-                VectorOpcode::I32x4ExtmulHighI16x8S => Instruction::I32x4ExtmulHighI16x8S,
-                VectorOpcode::I32x4ExtmulLowI16x8U => Instruction::I32x4ExtmulLowI16x8U,
-                VectorOpcode::I32x4ExtmulHighI16x8U => Instruction::I32x4ExtmulHighI16x8U,
-
-                VectorOpcode::I64x2Abs => Instruction::I64x2Abs,
-                VectorOpcode::I64x2Neg => Instruction::I64x2Neg,
-                VectorOpcode::I64x2AllTrue => Instruction::I64x2AllTrue,
-                VectorOpcode::I64x2Bitmask => Instruction::I64x2Bitmask,
-                VectorOpcode::I64x2ExtendLowI32x4S => Instruction::I64x2ExtendLowI32x4S,
-                VectorOpcode::I64x2ExtendHighI32x4S => Instruction::I64x2ExtendHighI32x4S,
-                VectorOpcode::I64x2ExtendLowI32x4U => Instruction::I64x2ExtendLowI32x4U,
-                VectorOpcode::I64x2ExtendHighI32x4U => Instruction::I64x2ExtendHighI32x4U,
-                VectorOpcode::I64x2Shl => Instruction::I64x2Shl,
-                VectorOpcode::I64x2ShrS => Instruction::I64x2ShrS,
-                VectorOpcode::I64x2ShrU => Instruction::I64x2ShrU,
-                VectorOpcode::I64x2Add => Instruction::I64x2Add,
-                VectorOpcode::I64x2Sub => Instruction::I64x2Sub,
-                VectorOpcode::I64x2Mul => Instruction::I64x2Mul,
-                VectorOpcode::I64x2ExtmulLowI32x4S => Instruction::I64x2ExtmulLowI32x4S,
-                VectorOpcode::I64x2ExtmulHighI32x4S => Instruction::I64x2ExtmulHighI32x4S,
-                VectorOpcode::I64x2ExtmulLowI32x4U => Instruction::I64x2ExtmulLowI32x4U,
-                VectorOpcode::I64x2ExtmulHighI32x4U => Instruction::I64x2ExtmulHighI32x4U,
-
-                VectorOpcode::F32x4Ceil => Instruction::F32x4Ceil,
-                VectorOpcode::F32x4Floor => Instruction::F32x4Floor,
-                VectorOpcode::F32x4Trunc => Instruction::F32x4Trunc,
-                VectorOpcode::F32x4Nearest => Instruction::F32x4Nearest,
-                VectorOpcode::F32x4Abs => Instruction::F32x4Abs,
-                VectorOpcode::F32x4Neg => Instruction::F32x4Neg,
-                VectorOpcode::F32x4Sqrt => Instruction::F32x4Sqrt,
-                VectorOpcode::F32x4Add => Instruction::F32x4Add,
-                VectorOpcode::F32x4Sub => Instruction::F32x4Sub,
-                VectorOpcode::F32x4Mul => Instruction::F32x4Mul,
-                VectorOpcode::F32x4Div => Instruction::F32x4Div,
-                VectorOpcode::F32x4Min => Instruction::F32x4Min,
-                VectorOpcode::F32x4Max => Instruction::F32x4Max,
-                VectorOpcode::F32x4Pmin => Instruction::F32x4Pmin,
-                VectorOpcode::F32x4Pmax => Instruction::F32x4Pmax,
-
-                VectorOpcode::F64x2Ceil => Instruction::F64x2Ceil,
-                VectorOpcode::F64x2Floor => Instruction::F64x2Floor,
-                VectorOpcode::F64x2Trunc => Instruction::F64x2Trunc,
-                VectorOpcode::F64x2Nearest => Instruction::F64x2Nearest,
-                VectorOpcode::F64x2Abs => Instruction::F64x2Abs,
-                VectorOpcode::F64x2Neg => Instruction::F64x2Neg,
-                VectorOpcode::F64x2Sqrt => Instruction::F64x2Sqrt,
-                VectorOpcode::F64x2Add => Instruction::F64x2Add,
-                VectorOpcode::F64x2Sub => Instruction::F64x2Sub,
-                VectorOpcode::F64x2Mul => Instruction::F64x2Mul,
-                VectorOpcode::F64x2Div => Instruction::F64x2Div,
-                VectorOpcode::F64x2Min => Instruction::F64x2Min,
-                VectorOpcode::F64x2Max => Instruction::F64x2Max,
-                VectorOpcode::F64x2Pmin => Instruction::F64x2Pmin,
-                VectorOpcode::F64x2Pmax => Instruction::F64x2Pmax,
-
-                VectorOpcode::I32x4TruncSatF32x4S => Instruction::I32x4TruncSatF32x4S,
-                VectorOpcode::I32x4TruncSatF32x4U => Instruction::I32x4TruncSatF32x4U,
-                VectorOpcode::F32x4ConvertI32x4S => Instruction::F32x4ConvertI32x4S,
-                VectorOpcode::F32x4ConvertI32x4U => Instruction::F32x4ConvertI32x4U,
-                VectorOpcode::I32x4TruncSatF64x2SZero => Instruction::I32x4TruncSatF64x2SZero,
-                VectorOpcode::I32x4TruncSatF64x2UZero => Instruction::I32x4TruncSatF64x2UZero,
-                VectorOpcode::F64x2ConvertLowI32x4S => Instruction::F64x2ConvertLowI32x4S,
-                VectorOpcode::F64x2ConvertLowI32x4U => Instruction::F64x2ConvertLowI32x4U,
-                VectorOpcode::F32x4DemoteF64x2Zero => Instruction::F32x4DemoteF64x2Zero,
-                VectorOpcode::F64x2PromoteLowF32x4 => Instruction::F64x2PromoteLowF32x4,
-            }
-        }
+                .try_into()?,
+        ),
+        #[cfg(feature = "simd")]
+        Opcode::PrefixV128 => InstructionOpcode::V128(
+            leb128::u32(offset, input)
+                .context("actual opcode")?
+                .try_into()?,
+        ),
+        #[cfg(feature = "threads")]
         Opcode::PrefixFE => {
             // This will eventually be a leb128::u32
-            let actual_opcode =
+            InstructionOpcode::FE(
                 u32::from(parser::one_byte_exact(offset, input).context("actual opcode")?)
-                    .try_into()?;
-
-            match actual_opcode {
-                FEPrefixedOpcode::MemoryAtomicNotify => {
-                    Instruction::MemoryAtomicNotify(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::MemoryAtomicWait32 => {
-                    Instruction::MemoryAtomicWait32(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::MemoryAtomicWait64 => {
-                    Instruction::MemoryAtomicWait64(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I32AtomicLoad => {
-                    Instruction::I32AtomicLoad(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I64AtomicLoad => {
-                    Instruction::I64AtomicLoad(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I32AtomicLoad8U => {
-                    Instruction::I32AtomicLoad8U(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I32AtomicLoad16U => {
-                    Instruction::I32AtomicLoad16U(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I64AtomicLoad8U => {
-                    Instruction::I64AtomicLoad8U(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I64AtomicLoad16U => {
-                    Instruction::I64AtomicLoad16U(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I64AtomicLoad32U => {
-                    Instruction::I64AtomicLoad32U(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I32AtomicStore => {
-                    Instruction::I32AtomicStore(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I64AtomicStore => {
-                    Instruction::I64AtomicStore(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I32AtomicStore8U => {
-                    Instruction::I32AtomicStore8U(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I32AtomicStore16U => {
-                    Instruction::I32AtomicStore16U(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I64AtomicStore8U => {
-                    Instruction::I64AtomicStore8U(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I64AtomicStore16U => {
-                    Instruction::I64AtomicStore16U(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I64AtomicStore32U => {
-                    Instruction::I64AtomicStore32U(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I32AtomicRmwAdd => {
-                    Instruction::I32AtomicRmwAdd(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I64AtomicRmwAdd => {
-                    Instruction::I64AtomicRmwAdd(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I32AtomicRmw8AddU => {
-                    Instruction::I32AtomicRmw8AddU(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I32AtomicRmw16AddU => {
-                    Instruction::I32AtomicRmw16AddU(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I64AtomicRmw8AddU => {
-                    Instruction::I64AtomicRmw8AddU(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I64AtomicRmw16AddU => {
-                    Instruction::I64AtomicRmw16AddU(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I64AtomicRmw32AddU => {
-                    Instruction::I64AtomicRmw32AddU(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I32AtomicRmwSub => {
-                    Instruction::I32AtomicRmwSub(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I64AtomicRmwSub => {
-                    Instruction::I64AtomicRmwSub(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I32AtomicRmw8SubU => {
-                    Instruction::I32AtomicRmw8SubU(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I32AtomicRmw16SubU => {
-                    Instruction::I32AtomicRmw16SubU(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I64AtomicRmw8SubU => {
-                    Instruction::I64AtomicRmw8SubU(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I64AtomicRmw16SubU => {
-                    Instruction::I64AtomicRmw16SubU(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I64AtomicRmw32SubU => {
-                    Instruction::I64AtomicRmw32SubU(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I32AtomicRmwAnd => {
-                    Instruction::I32AtomicRmwAnd(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I64AtomicRmwAnd => {
-                    Instruction::I64AtomicRmwAnd(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I32AtomicRmw8AndU => {
-                    Instruction::I32AtomicRmw8AndU(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I32AtomicRmw16AndU => {
-                    Instruction::I32AtomicRmw16AndU(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I64AtomicRmw8AndU => {
-                    Instruction::I64AtomicRmw8AndU(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I64AtomicRmw16AndU => {
-                    Instruction::I64AtomicRmw16AndU(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I64AtomicRmw32AndU => {
-                    Instruction::I64AtomicRmw32AndU(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I32AtomicRmwOr => {
-                    Instruction::I32AtomicRmwOr(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I64AtomicRmwOr => {
-                    Instruction::I64AtomicRmwOr(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I32AtomicRmw8OrU => {
-                    Instruction::I32AtomicRmw8OrU(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I32AtomicRmw16OrU => {
-                    Instruction::I32AtomicRmw16OrU(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I64AtomicRmw8OrU => {
-                    Instruction::I64AtomicRmw8OrU(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I64AtomicRmw16OrU => {
-                    Instruction::I64AtomicRmw16OrU(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I64AtomicRmw32OrU => {
-                    Instruction::I64AtomicRmw32OrU(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I32AtomicRmwXor => {
-                    Instruction::I32AtomicRmwXor(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I64AtomicRmwXor => {
-                    Instruction::I64AtomicRmwXor(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I32AtomicRmw8XorU => {
-                    Instruction::I32AtomicRmw8XorU(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I32AtomicRmw16XorU => {
-                    Instruction::I32AtomicRmw16XorU(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I64AtomicRmw8XorU => {
-                    Instruction::I64AtomicRmw8XorU(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I64AtomicRmw16XorU => {
-                    Instruction::I64AtomicRmw16XorU(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I64AtomicRmw32XorU => {
-                    Instruction::I64AtomicRmw32XorU(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I32AtomicRmwXchg => {
-                    Instruction::I32AtomicRmwXchg(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I64AtomicRmwXchg => {
-                    Instruction::I64AtomicRmwXchg(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I32AtomicRmw8XchgU => {
-                    Instruction::I32AtomicRmw8XchgU(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I32AtomicRmw16XchgU => {
-                    Instruction::I32AtomicRmw16XchgU(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I64AtomicRmw8XchgU => {
-                    Instruction::I64AtomicRmw8XchgU(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I64AtomicRmw16XchgU => {
-                    Instruction::I64AtomicRmw16XchgU(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I64AtomicRmw32XchgU => {
-                    Instruction::I64AtomicRmw32XchgU(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I32AtomicRmwCmpxchg => {
-                    Instruction::I32AtomicRmwCmpxchg(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I64AtomicRmwCmpxchg => {
-                    Instruction::I64AtomicRmwCmpxchg(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I32AtomicRmw8CmpxchgU => {
-                    Instruction::I32AtomicRmw8CmpxchgU(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I32AtomicRmw16CmpxchgU => {
-                    Instruction::I32AtomicRmw16CmpxchgU(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I64AtomicRmw8CmpxchgU => {
-                    Instruction::I64AtomicRmw8CmpxchgU(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I64AtomicRmw16CmpxchgU => {
-                    Instruction::I64AtomicRmw16CmpxchgU(memarg(offset, input)?)
-                }
-                FEPrefixedOpcode::I64AtomicRmw32CmpxchgU => {
-                    Instruction::I64AtomicRmw32CmpxchgU(memarg(offset, input)?)
-                }
-            }
+                    .try_into()?,
+            )
         }
-    }) //.context() // the opcode name
+        _ => InstructionOpcode::Byte(opcode),
+    })
 }
 
-#[inline]
-fn next_instruction<'a, T, E, I, F>(
+#[inline(never)]
+#[cold]
+fn disabled_instruction(opcode: Opcode) -> Error {
+    Error::new(ErrorKind::DisabledInstruction(opcode))
+}
+
+/// Parses the immediate arguments of the instruction with the given `opcode`, passing them to the
+/// corresponding method of the `decoder`.
+///
+/// If the `decoder` builds an [`Instruction`], callers should call [`Instruction::finish`] in
+/// order to ensure the instruction is completely parsed.
+fn decode<'a, 'b, I, D>(
+    opcode: InstructionOpcode,
     offset: &'a mut u64,
-    input: &'a I,
-    blocks: &mut u32,
-    f: F,
-) -> Result<T, E>
+    input: &'b I,
+    decoder: D,
+) -> parser::Parsed<D::Output>
 where
-    E: From<parser::Error>,
     I: Input,
-    F: FnOnce(&mut Instruction<'a, &'a I>) -> Result<T, E>,
+    D: DecodeInstruction<'a, &'b I>,
 {
-    let mut instruction = self::instruction(offset, input)?;
-    let result = f(&mut instruction)?;
+    match opcode {
+        InstructionOpcode::Byte(opcode) => match opcode {
+            Opcode::Unreachable => decoder.visit_unreachable(),
+            Opcode::Nop => decoder.visit_nop(),
+            Opcode::Block => decoder.visit_block(component::block_type(offset, input)?),
+            Opcode::Loop => {
+                decoder.visit_loop(component::block_type(offset, input).context("loop block type")?)
+            }
+            Opcode::If => {
+                decoder.visit_if(component::block_type(offset, input).context("if block type")?)
+            }
+            Opcode::Else => decoder.visit_else(),
+            #[cfg(feature = "exceptions")]
+            Opcode::Try => {
+                decoder.visit_try(component::block_type(offset, input).context("try block type")?)
+            }
+            #[cfg(feature = "exceptions")]
+            Opcode::Catch => {
+                decoder.visit_catch(component::index(offset, input).context("catch tag")?)
+            }
+            #[cfg(feature = "exceptions")]
+            Opcode::Throw => {
+                decoder.visit_throw(component::index(offset, input).context("throw tag")?)
+            }
+            #[cfg(feature = "exceptions")]
+            Opcode::Rethrow => {
+                decoder.visit_rethrow(component::index(offset, input).context("rethrow label")?)
+            }
+            Opcode::End => decoder.visit_end(),
+            Opcode::Br => decoder.visit_br(component::index(offset, input).context("br label")?),
+            Opcode::BrIf => {
+                decoder.visit_br_if(component::index(offset, input).context("br_if label")?)
+            }
+            Opcode::BrTable => {
+                let branch_count = parser::leb128::u32(offset.offset_mut(), input)
+                    .context("could not parse branch table label count")?;
 
-    match instruction {
-        Instruction::Block(_) | Instruction::Loop(_) | Instruction::If(_) | Instruction::Try(_) => {
-            if let Some(entered) = blocks.checked_add(1) {
-                *blocks = entered
-            } else {
-                #[inline(never)]
-                #[cold]
-                fn block_nesting_counter_overflowed() -> Error {
-                    Error::new(ErrorKind::BlockNestingCounterOverflow)
+                if let Some(total_count) = branch_count.checked_add(1) {
+                    decoder.visit_br_table(component::IndexVector::new(total_count, offset, input))
+                } else {
+                    #[inline(never)]
+                    #[cold]
+                    fn branch_count_overflowed() -> Error {
+                        Error::new(ErrorKind::BranchTableCountOverflow)
+                    }
+
+                    Err(branch_count_overflowed())
                 }
+            }
+            Opcode::Return => decoder.visit_return(),
+            Opcode::Call => {
+                decoder.visit_call(component::index(offset, input).context("call target")?)
+            }
+            Opcode::CallIndirect => decoder.visit_call_indirect(
+                component::index(offset, input).context("indirect call signature")?,
+                component::index(offset, input).context("indirect call target")?,
+            ),
+            #[cfg(feature = "tail-call")]
+            Opcode::ReturnCall => decoder
+                .visit_return_call(component::index(offset, input).context("tail call target")?),
+            #[cfg(feature = "tail-call")]
+            Opcode::ReturnCallIndirect => decoder.visit_return_call_indirect(
+                component::index(offset, input).context("indirect tail call signature")?,
+                component::index(offset, input).context("indirect tail call target")?,
+            ),
+            #[cfg(feature = "function-references")]
+            Opcode::CallRef => decoder
+                .visit_call_ref(component::index(offset, input).context("call_ref signature")?),
+            #[cfg(feature = "function-references")]
+            Opcode::ReturnCallRef => decoder.visit_return_call_ref(
+                component::index(offset, input).context("return_call_ref signature")?,
+            ),
+            #[cfg(feature = "exceptions")]
+            Opcode::Delegate => {
+                decoder.visit_delegate(component::index(offset, input).context("delegate label")?)
+            }
+            #[cfg(feature = "exceptions")]
+            Opcode::CatchAll => decoder.visit_catch_all(),
+            #[cfg(feature = "exceptions")]
+            Opcode::TryTable => decoder.visit_try_table(
+                component::block_type(offset, input).context("try_table block type")?,
+                instruction_set::CatchClauses::new(offset, input),
+            ),
+            #[cfg(feature = "exceptions")]
+            Opcode::ThrowRef => decoder.visit_throw_ref(),
+
+            Opcode::Drop => decoder.visit_drop(),
+            Opcode::Select => {
+                decoder.visit_select(component::ResultType::empty_with_offset(offset, input))
+            }
+            Opcode::SelectMany => decoder
+                .visit_select(component::ResultType::parse(offset, input).context("select types")?),
+
+            Opcode::LocalGet => decoder.visit_local_get(component::index(offset, input)?),
+            Opcode::LocalSet => decoder.visit_local_set(component::index(offset, input)?),
+            Opcode::LocalTee => decoder.visit_local_tee(component::index(offset, input)?),
+            Opcode::GlobalGet => decoder.visit_global_get(component::index(offset, input)?),
+            Opcode::GlobalSet => decoder.visit_global_set(component::index(offset, input)?),
+
+            Opcode::TableGet => decoder.visit_table_get(component::index(offset, input)?),
+            Opcode::TableSet => decoder.visit_table_set(component::index(offset, input)?),
+
+            Opcode::I32Load => decoder.visit_i32_load(memarg(offset, input, opcode)?),
+            Opcode::I64Load => decoder.visit_i64_load(memarg(offset, input, opcode)?),
+            Opcode::F32Load => decoder.visit_f32_load(memarg(offset, input, opcode)?),
+            Opcode::F64Load => decoder.visit_f64_load(memarg(offset, input, opcode)?),
+
+            Opcode::I32Load8S => decoder.visit_i32_load8_s(memarg(offset, input, opcode)?),
+            Opcode::I32Load8U => decoder.visit_i32_load8_u(memarg(offset, input, opcode)?),
+            Opcode::I32Load16S => decoder.visit_i32_load16_s(memarg(offset, input, opcode)?),
+            Opcode::I32Load16U => decoder.visit_i32_load16_u(memarg(offset, input, opcode)?),
+            Opcode::I64Load8S => decoder.visit_i64_load8_s(memarg(offset, input, opcode)?),
+            Opcode::I64Load8U => decoder.visit_i64_load8_u(memarg(offset, input, opcode)?),
+            Opcode::I64Load16S => decoder.visit_i64_load16_s(memarg(offset, input, opcode)?),
+            Opcode::I64Load16U => decoder.visit_i64_load16_u(memarg(offset, input, opcode)?),
+            Opcode::I64Load32S => decoder.visit_i64_load32_s(memarg(offset, input, opcode)?),
+            Opcode::I64Load32U => decoder.visit_i64_load32_u(memarg(offset, input, opcode)?),
+
+            Opcode::I32Store => decoder.visit_i32_store(memarg(offset, input, opcode)?),
+            Opcode::I64Store => decoder.visit_i64_store(memarg(offset, input, opcode)?),
+            Opcode::F32Store => decoder.visit_f32_store(memarg(offset, input, opcode)?),
+            Opcode::F64Store => decoder.visit_f64_store(memarg(offset, input, opcode)?),
+
+            Opcode::I32Store8 => decoder.visit_i32_store8(memarg(offset, input, opcode)?),
+            Opcode::I32Store16 => decoder.visit_i32_store16(memarg(offset, input, opcode)?),
+            Opcode::I64Store8 => decoder.visit_i64_store8(memarg(offset, input, opcode)?),
+            Opcode::I64Store16 => decoder.visit_i64_store16(memarg(offset, input, opcode)?),
+            Opcode::I64Store32 => decoder.visit_i64_store32(memarg(offset, input, opcode)?),
+
+            Opcode::MemorySize => decoder.visit_memory_size(component::index(offset, input)?),
+            Opcode::MemoryGrow => decoder.visit_memory_grow(component::index(offset, input)?),
+
+            Opcode::I32Const => decoder.visit_i32_const(leb128::s32(offset, input)?),
+            Opcode::I64Const => decoder.visit_i64_const(leb128::s64(offset, input)?),
+            Opcode::F32Const => decoder.visit_f32_const(f32::from_le_bytes(
+                parser::byte_array(offset, input).context("32-bit float constant")?,
+            )),
+            Opcode::F64Const => decoder.visit_f64_const(f64::from_le_bytes(
+                parser::byte_array(offset, input).context("64-bit float constant")?,
+            )),
+
+            Opcode::I32Eqz => decoder.visit_i32_eqz(),
+            Opcode::I32Eq => decoder.visit_i32_eq(),
+            Opcode::I32Ne => decoder.visit_i32_ne(),
+            Opcode::I32LtS => decoder.visit_i32_lt_s(),
+            Opcode::I32LtU => decoder.visit_i32_lt_u(),
+            Opcode::I32GtS => decoder.visit_i32_gt_s(),
+            Opcode::I32GtU => decoder.visit_i32_gt_u(),
+            Opcode::I32LeS => decoder.visit_i32_le_s(),
+            Opcode::I32LeU => decoder.visit_i32_le_u(),
+            Opcode::I32GeS => decoder.visit_i32_ge_s(),
+            Opcode::I32GeU => decoder.visit_i32_ge_u(),
+
+            Opcode::I64Eqz => decoder.visit_i64_eqz(),
+            Opcode::I64Eq => decoder.visit_i64_eq(),
+            Opcode::I64Ne => decoder.visit_i64_ne(),
+            Opcode::I64LtS => decoder.visit_i64_lt_s(),
+            Opcode::I64LtU => decoder.visit_i64_lt_u(),
+            Opcode::I64GtS => decoder.visit_i64_gt_s(),
+            Opcode::I64GtU => decoder.visit_i64_gt_u(),
+            Opcode::I64LeS => decoder.visit_i64_le_s(),
+            Opcode::I64LeU => decoder.visit_i64_le_u(),
+            Opcode::I64GeS => decoder.visit_i64_ge_s(),
+            Opcode::I64GeU => decoder.visit_i64_ge_u(),
+
+            Opcode::F32Eq => decoder.visit_f32_eq(),
+            Opcode::F32Ne => decoder.visit_f32_ne(),
+            Opcode::F32Lt => decoder.visit_f32_lt(),
+            Opcode::F32Gt => decoder.visit_f32_gt(),
+            Opcode::F32Le => decoder.visit_f32_le(),
+            Opcode::F32Ge => decoder.visit_f32_ge(),
+            Opcode::F64Eq => decoder.visit_f64_eq(),
+            Opcode::F64Ne => decoder.visit_f64_ne(),
+            Opcode::F64Lt => decoder.visit_f64_lt(),
+            Opcode::F64Gt => decoder.visit_f64_gt(),
+            Opcode::F64Le => decoder.visit_f64_le(),
+            Opcode::F64Ge => decoder.visit_f64_ge(),
+
+            Opcode::I32Clz => decoder.visit_i32_clz(),
+            Opcode::I32Ctz => decoder.visit_i32_ctz(),
+            Opcode::I32Popcnt => decoder.visit_i32_popcnt(),
+            Opcode::I32Add => decoder.visit_i32_add(),
+            Opcode::I32Sub => decoder.visit_i32_sub(),
+            Opcode::I32Mul => decoder.visit_i32_mul(),
+            Opcode::I32DivS => decoder.visit_i32_div_s(),
+            Opcode::I32DivU => decoder.visit_i32_div_u(),
+            Opcode::I32RemS => decoder.visit_i32_rem_s(),
+            Opcode::I32RemU => decoder.visit_i32_rem_u(),
+            Opcode::I32And => decoder.visit_i32_and(),
+            Opcode::I32Or => decoder.visit_i32_or(),
+            Opcode::I32Xor => decoder.visit_i32_xor(),
+            Opcode::I32Shl => decoder.visit_i32_shl(),
+            Opcode::I32ShrS => decoder.visit_i32_shr_s(),
+            Opcode::I32ShrU => decoder.visit_i32_shr_u(),
+            Opcode::I32Rotl => decoder.visit_i32_rotl(),
+            Opcode::I32Rotr => decoder.visit_i32_rotr(),
+
+            Opcode::I64Clz => decoder.visit_i64_clz(),
+            Opcode::I64Ctz => decoder.visit_i64_ctz(),
+            Opcode::I64Popcnt => decoder.visit_i64_popcnt(),
+            Opcode::I64Add => decoder.visit_i64_add(),
+            Opcode::I64Sub => decoder.visit_i64_sub(),
+            Opcode::I64Mul => decoder.visit_i64_mul(),
+            Opcode::I64DivS => decoder.visit_i64_div_s(),
+            Opcode::I64DivU => decoder.visit_i64_div_u(),
+            Opcode::I64RemS => decoder.visit_i64_rem_s(),
+            Opcode::I64RemU => decoder.visit_i64_rem_u(),
+            Opcode::I64And => decoder.visit_i64_and(),
+            Opcode::I64Or => decoder.visit_i64_or(),
+            Opcode::I64Xor => decoder.visit_i64_xor(),
+            Opcode::I64Shl => decoder.visit_i64_shl(),
+            Opcode::I64ShrS => decoder.visit_i64_shr_s(),
+            Opcode::I64ShrU => decoder.visit_i64_shr_u(),
+            Opcode::I64Rotl => decoder.visit_i64_rotl(),
+            Opcode::I64Rotr => decoder.visit_i64_rotr(),
+
+            Opcode::F32Abs => decoder.visit_f32_abs(),
+            Opcode::F32Neg => decoder.visit_f32_neg(),
+            Opcode::F32Ceil => decoder.visit_f32_ceil(),
+            Opcode::F32Floor => decoder.visit_f32_floor(),
+            Opcode::F32Trunc => decoder.visit_f32_trunc(),
+            Opcode::F32Nearest => decoder.visit_f32_nearest(),
+            Opcode::F32Sqrt => decoder.visit_f32_sqrt(),
+            Opcode::F32Add => decoder.visit_f32_add(),
+            Opcode::F32Sub => decoder.visit_f32_sub(),
+            Opcode::F32Mul => decoder.visit_f32_mul(),
+            Opcode::F32Div => decoder.visit_f32_div(),
+            Opcode::F32Min => decoder.visit_f32_min(),
+            Opcode::F32Max => decoder.visit_f32_max(),
+            Opcode::F32Copysign => decoder.visit_f32_copysign(),
+
+            Opcode::F64Abs => decoder.visit_f64_abs(),
+            Opcode::F64Neg => decoder.visit_f64_neg(),
+            Opcode::F64Ceil => decoder.visit_f64_ceil(),
+            Opcode::F64Floor => decoder.visit_f64_floor(),
+            Opcode::F64Trunc => decoder.visit_f64_trunc(),
+            Opcode::F64Nearest => decoder.visit_f64_nearest(),
+            Opcode::F64Sqrt => decoder.visit_f64_sqrt(),
+            Opcode::F64Add => decoder.visit_f64_add(),
+            Opcode::F64Sub => decoder.visit_f64_sub(),
+            Opcode::F64Mul => decoder.visit_f64_mul(),
+            Opcode::F64Div => decoder.visit_f64_div(),
+            Opcode::F64Min => decoder.visit_f64_min(),
+            Opcode::F64Max => decoder.visit_f64_max(),
+            Opcode::F64Copysign => decoder.visit_f64_copysign(),
+
+            Opcode::I32WrapI64 => decoder.visit_i32_wrap_i64(),
+            Opcode::I32TruncF32S => decoder.visit_i32_trunc_f32_s(),
+            Opcode::I32TruncF32U => decoder.visit_i32_trunc_f32_u(),
+            Opcode::I32TruncF64S => decoder.visit_i32_trunc_f64_s(),
+            Opcode::I32TruncF64U => decoder.visit_i32_trunc_f64_u(),
+            Opcode::I64ExtendI32S => decoder.visit_i64_extend_i32_s(),
+            Opcode::I64ExtendI32U => decoder.visit_i64_extend_i32_u(),
+            Opcode::I64TruncF32S => decoder.visit_i64_trunc_f32_s(),
+            Opcode::I64TruncF32U => decoder.visit_i64_trunc_f32_u(),
+            Opcode::I64TruncF64S => decoder.visit_i64_trunc_f64_s(),
+            Opcode::I64TruncF64U => decoder.visit_i64_trunc_f64_u(),
+            Opcode::F32ConvertI32S => decoder.visit_f32_convert_i32_s(),
+            Opcode::F32ConvertI32U => decoder.visit_f32_convert_i32_u(),
+            Opcode::F32ConvertI64S => decoder.visit_f32_convert_i64_s(),
+            Opcode::F32ConvertI64U => decoder.visit_f32_convert_i64_u(),
+            Opcode::F32DemoteF64 => decoder.visit_f32_demote_f64(),
+            Opcode::F64ConvertI32S => decoder.visit_f64_convert_i32_s(),
+            Opcode::F64ConvertI32U => decoder.visit_f64_convert_i32_u(),
+            Opcode::F64ConvertI64S => decoder.visit_f64_convert_i64_s(),
+            Opcode::F64ConvertI64U => decoder.visit_f64_convert_i64_u(),
+            Opcode::F64PromoteF32 => decoder.visit_f64_promote_f32(),
+            Opcode::I32ReinterpretF32 => decoder.visit_i32_reinterpret_f32(),
+            Opcode::I64ReinterpretF64 => decoder.visit_i64_reinterpret_f64(),
+            Opcode::F32ReinterpretI32 => decoder.visit_f32_reinterpret_i32(),
+            Opcode::F64ReinterpretI64 => decoder.visit_f64_reinterpret_i64(),
+
+            Opcode::I32Extend8S => decoder.visit_i32_extend8_s(),
+            Opcode::I32Extend16S => decoder.visit_i32_extend16_s(),
+            Opcode::I64Extend8S => decoder.visit_i64_extend8_s(),
+            Opcode::I64Extend16S => decoder.visit_i64_extend16_s(),
+            Opcode::I64Extend32S => decoder.visit_i64_extend32_s(),
+
+            Opcode::RefNull => decoder.visit_ref_null(crate::types::RefType::new(
+                true,
+                component::heap_type(offset, input).context("type for null")?,
+            )),
+            Opcode::RefIsNull => decoder.visit_ref_is_null(),
+            Opcode::RefFunc => decoder.visit_ref_func(
+                component::index(offset, input).context("invalid reference to function")?,
+            ),
+            #[cfg(feature = "function-references")]
+            Opcode::RefAsNonNull => decoder.visit_ref_as_non_null(),
+            #[cfg(feature = "function-references")]
+            Opcode::BrOnNull => decoder
+                .visit_br_on_null(component::index(offset, input).context("br_on_null label")?),
+            #[cfg(feature = "function-references")]
+            Opcode::BrOnNonNull => decoder.visit_br_on_non_null(
+                component::index(offset, input).context("br_on_non_null label")?,
+            ),
+            // Instructions whose cargo feature is disabled
+            #[allow(unreachable_patterns)]
+            _ => Err(disabled_instruction(opcode)),
+        },
+        InstructionOpcode::FC(actual_opcode) => match actual_opcode {
+            FCPrefixedOpcode::MemoryInit => decoder.visit_memory_init(
+                component::index(offset, input)?,
+                component::index(offset, input)?,
+            ),
+            FCPrefixedOpcode::DataDrop => decoder.visit_data_drop(component::index(offset, input)?),
+            FCPrefixedOpcode::MemoryCopy => decoder.visit_memory_copy(
+                component::index(offset, input).context("destination memory")?,
+                component::index(offset, input).context("source memory")?,
+            ),
+            FCPrefixedOpcode::MemoryFill => {
+                decoder.visit_memory_fill(component::index(offset, input)?)
+            }
+            FCPrefixedOpcode::TableInit => decoder.visit_table_init(
+                component::index(offset, input)?,
+                component::index(offset, input)?,
+            ),
+            FCPrefixedOpcode::ElemDrop => decoder.visit_elem_drop(component::index(offset, input)?),
+            FCPrefixedOpcode::TableCopy => decoder.visit_table_copy(
+                component::index(offset, input).context("destination table")?,
+                component::index(offset, input).context("source table")?,
+            ),
+            FCPrefixedOpcode::TableGrow => {
+                decoder.visit_table_grow(component::index(offset, input)?)
+            }
+            FCPrefixedOpcode::TableSize => {
+                decoder.visit_table_size(component::index(offset, input)?)
+            }
+            FCPrefixedOpcode::TableFill => {
+                decoder.visit_table_fill(component::index(offset, input)?)
+            }
+            FCPrefixedOpcode::I32TruncSatF32S => decoder.visit_i32_trunc_sat_f32_s(),
+            FCPrefixedOpcode::I32TruncSatF32U => decoder.visit_i32_trunc_sat_f32_u(),
+            FCPrefixedOpcode::I32TruncSatF64S => decoder.visit_i32_trunc_sat_f64_s(),
+            FCPrefixedOpcode::I32TruncSatF64U => decoder.visit_i32_trunc_sat_f64_u(),
+            FCPrefixedOpcode::I64TruncSatF32S => decoder.visit_i64_trunc_sat_f32_s(),
+            FCPrefixedOpcode::I64TruncSatF32U => decoder.visit_i64_trunc_sat_f32_u(),
+            FCPrefixedOpcode::I64TruncSatF64S => decoder.visit_i64_trunc_sat_f64_s(),
+            FCPrefixedOpcode::I64TruncSatF64U => decoder.visit_i64_trunc_sat_f64_u(),
+        },
+        #[cfg(feature = "simd")]
+        InstructionOpcode::V128(actual_opcode) => match actual_opcode {
+            VectorOpcode::Load => decoder.visit_v128_load(memarg(offset, input, actual_opcode)?),
+
+            VectorOpcode::Load8x8S => {
+                decoder.visit_v128_load8x8_s(memarg(offset, input, actual_opcode)?)
+            }
+            VectorOpcode::Load8x8U => {
+                decoder.visit_v128_load8x8_u(memarg(offset, input, actual_opcode)?)
+            }
+            VectorOpcode::Load16x4S => {
+                decoder.visit_v128_load16x4_s(memarg(offset, input, actual_opcode)?)
+            }
+            VectorOpcode::Load16x4U => {
+                decoder.visit_v128_load16x4_u(memarg(offset, input, actual_opcode)?)
+            }
+            VectorOpcode::Load32x2S => {
+                decoder.visit_v128_load32x2_s(memarg(offset, input, actual_opcode)?)
+            }
+            VectorOpcode::Load32x2U => {
+                decoder.visit_v128_load32x2_u(memarg(offset, input, actual_opcode)?)
+            }
+
+            VectorOpcode::Load8Splat => {
+                decoder.visit_v128_load8_splat(memarg(offset, input, actual_opcode)?)
+            }
+            VectorOpcode::Load16Splat => {
+                decoder.visit_v128_load16_splat(memarg(offset, input, actual_opcode)?)
+            }
+            VectorOpcode::Load32Splat => {
+                decoder.visit_v128_load32_splat(memarg(offset, input, actual_opcode)?)
+            }
+            VectorOpcode::Load64Splat => {
+                decoder.visit_v128_load64_splat(memarg(offset, input, actual_opcode)?)
+            }
+            VectorOpcode::Load32Zero => {
+                decoder.visit_v128_load32_zero(memarg(offset, input, actual_opcode)?)
+            }
+            VectorOpcode::Load64Zero => {
+                decoder.visit_v128_load64_zero(memarg(offset, input, actual_opcode)?)
+            }
+
+            VectorOpcode::Store => decoder.visit_v128_store(memarg(offset, input, actual_opcode)?),
+
+            VectorOpcode::Load8Lane => decoder.visit_v128_load8_lane(
+                memarg(offset, input, actual_opcode)?,
+                parser::one_byte_exact(offset, input)?,
+            ),
+            VectorOpcode::Load16Lane => decoder.visit_v128_load16_lane(
+                memarg(offset, input, actual_opcode)?,
+                parser::one_byte_exact(offset, input)?,
+            ),
+            VectorOpcode::Load32Lane => decoder.visit_v128_load32_lane(
+                memarg(offset, input, actual_opcode)?,
+                parser::one_byte_exact(offset, input)?,
+            ),
+            VectorOpcode::Load64Lane => decoder.visit_v128_load64_lane(
+                memarg(offset, input, actual_opcode)?,
+                parser::one_byte_exact(offset, input)?,
+            ),
+
+            VectorOpcode::Store8Lane => decoder.visit_v128_store8_lane(
+                memarg(offset, input, actual_opcode)?,
+                parser::one_byte_exact(offset, input)?,
+            ),
+            VectorOpcode::Store16Lane => decoder.visit_v128_store16_lane(
+                memarg(offset, input, actual_opcode)?,
+                parser::one_byte_exact(offset, input)?,
+            ),
+            VectorOpcode::Store32Lane => decoder.visit_v128_store32_lane(
+                memarg(offset, input, actual_opcode)?,
+                parser::one_byte_exact(offset, input)?,
+            ),
+            VectorOpcode::Store64Lane => decoder.visit_v128_store64_lane(
+                memarg(offset, input, actual_opcode)?,
+                parser::one_byte_exact(offset, input)?,
+            ),
+
+            VectorOpcode::Const => decoder.visit_v128_const(u128::from_le_bytes(
+                parser::byte_array(offset, input).context("constant 128-bit vector")?,
+            )),
+
+            VectorOpcode::I8x16Shuffle => decoder.visit_i8x16_shuffle(
+                parser::byte_array(offset, input).context("shuffle lane indices")?,
+            ),
+
+            VectorOpcode::I8x16ExtractLaneS => decoder.visit_i8x16_extract_lane_s(
+                parser::one_byte_exact(offset, input).context("vector lane index")?,
+            ),
+            VectorOpcode::I8x16ExtractLaneU => decoder.visit_i8x16_extract_lane_u(
+                parser::one_byte_exact(offset, input).context("vector lane index")?,
+            ),
+            VectorOpcode::I8x16ReplaceLane => decoder.visit_i8x16_replace_lane(
+                parser::one_byte_exact(offset, input).context("vector lane index")?,
+            ),
+
+            VectorOpcode::I16x8ExtractLaneS => decoder.visit_i16x8_extract_lane_s(
+                parser::one_byte_exact(offset, input).context("vector lane index")?,
+            ),
+            VectorOpcode::I16x8ExtractLaneU => decoder.visit_i16x8_extract_lane_u(
+                parser::one_byte_exact(offset, input).context("vector lane index")?,
+            ),
+            VectorOpcode::I16x8ReplaceLane => decoder.visit_i16x8_replace_lane(
+                parser::one_byte_exact(offset, input).context("vector lane index")?,
+            ),
+
+            VectorOpcode::I32x4ExtractLane => decoder.visit_i32x4_extract_lane(
+                parser::one_byte_exact(offset, input).context("vector lane index")?,
+            ),
+            VectorOpcode::I32x4ReplaceLane => decoder.visit_i32x4_replace_lane(
+                parser::one_byte_exact(offset, input).context("vector lane index")?,
+            ),
+
+            VectorOpcode::I64x2ExtractLane => decoder.visit_i64x2_extract_lane(
+                parser::one_byte_exact(offset, input).context("vector lane index")?,
+            ),
+            VectorOpcode::I64x2ReplaceLane => decoder.visit_i64x2_replace_lane(
+                parser::one_byte_exact(offset, input).context("vector lane index")?,
+            ),
+
+            VectorOpcode::F32x4ExtractLane => decoder.visit_f32x4_extract_lane(
+                parser::one_byte_exact(offset, input).context("vector lane index")?,
+            ),
+            VectorOpcode::F32x4ReplaceLane => decoder.visit_f32x4_replace_lane(
+                parser::one_byte_exact(offset, input).context("vector lane index")?,
+            ),
+
+            VectorOpcode::F64x2ExtractLane => decoder.visit_f64x2_extract_lane(
+                parser::one_byte_exact(offset, input).context("vector lane index")?,
+            ),
+            VectorOpcode::F64x2ReplaceLane => decoder.visit_f64x2_replace_lane(
+                parser::one_byte_exact(offset, input).context("vector lane index")?,
+            ),
+            VectorOpcode::I8x16Swizzle => decoder.visit_i8x16_swizzle(),
+
+            VectorOpcode::I8x16Splat => decoder.visit_i8x16_splat(),
+            VectorOpcode::I16x8Splat => decoder.visit_i16x8_splat(),
+            VectorOpcode::I32x4Splat => decoder.visit_i32x4_splat(),
+            VectorOpcode::I64x2Splat => decoder.visit_i64x2_splat(),
+            VectorOpcode::F32x4Splat => decoder.visit_f32x4_splat(),
+            VectorOpcode::F64x2Splat => decoder.visit_f64x2_splat(),
+
+            VectorOpcode::I8x16Eq => decoder.visit_i8x16_eq(),
+            VectorOpcode::I8x16Ne => decoder.visit_i8x16_ne(),
+            VectorOpcode::I8x16LtS => decoder.visit_i8x16_lt_s(),
+            VectorOpcode::I8x16LtU => decoder.visit_i8x16_lt_u(),
+            VectorOpcode::I8x16GtS => decoder.visit_i8x16_gt_s(),
+            VectorOpcode::I8x16GtU => decoder.visit_i8x16_gt_u(),
+            VectorOpcode::I8x16LeS => decoder.visit_i8x16_le_s(),
+            VectorOpcode::I8x16LeU => decoder.visit_i8x16_le_u(),
+            VectorOpcode::I8x16GeS => decoder.visit_i8x16_ge_s(),
+            VectorOpcode::I8x16GeU => decoder.visit_i8x16_ge_u(),
+
+            VectorOpcode::I16x8Eq => decoder.visit_i16x8_eq(),
+            VectorOpcode::I16x8Ne => decoder.visit_i16x8_ne(),
+            VectorOpcode::I16x8LtS => decoder.visit_i16x8_lt_s(),
+            VectorOpcode::I16x8LtU => decoder.visit_i16x8_lt_u(),
+            VectorOpcode::I16x8GtS => decoder.visit_i16x8_gt_s(),
+            VectorOpcode::I16x8GtU => decoder.visit_i16x8_gt_u(),
+            VectorOpcode::I16x8LeS => decoder.visit_i16x8_le_s(),
+            VectorOpcode::I16x8LeU => decoder.visit_i16x8_le_u(),
+            VectorOpcode::I16x8GeS => decoder.visit_i16x8_ge_s(),
+            VectorOpcode::I16x8GeU => decoder.visit_i16x8_ge_u(),
+
+            VectorOpcode::I32x4Eq => decoder.visit_i32x4_eq(),
+            VectorOpcode::I32x4Ne => decoder.visit_i32x4_ne(),
+            VectorOpcode::I32x4LtS => decoder.visit_i32x4_lt_s(),
+            VectorOpcode::I32x4LtU => decoder.visit_i32x4_lt_u(),
+            VectorOpcode::I32x4GtS => decoder.visit_i32x4_gt_s(),
+            VectorOpcode::I32x4GtU => decoder.visit_i32x4_gt_u(),
+            VectorOpcode::I32x4LeS => decoder.visit_i32x4_le_s(),
+            VectorOpcode::I32x4LeU => decoder.visit_i32x4_le_u(),
+            VectorOpcode::I32x4GeS => decoder.visit_i32x4_ge_s(),
+            VectorOpcode::I32x4GeU => decoder.visit_i32x4_ge_u(),
+
+            VectorOpcode::I64x2Eq => decoder.visit_i64x2_eq(),
+            VectorOpcode::I64x2Ne => decoder.visit_i64x2_ne(),
+            VectorOpcode::I64x2LtS => decoder.visit_i64x2_lt_s(),
+            VectorOpcode::I64x2GtS => decoder.visit_i64x2_gt_s(),
+            VectorOpcode::I64x2LeS => decoder.visit_i64x2_le_s(),
+            VectorOpcode::I64x2GeS => decoder.visit_i64x2_ge_s(),
+
+            VectorOpcode::F32x4Eq => decoder.visit_f32x4_eq(),
+            VectorOpcode::F32x4Ne => decoder.visit_f32x4_ne(),
+            VectorOpcode::F32x4Lt => decoder.visit_f32x4_lt(),
+            VectorOpcode::F32x4Gt => decoder.visit_f32x4_gt(),
+            VectorOpcode::F32x4Le => decoder.visit_f32x4_le(),
+            VectorOpcode::F32x4Ge => decoder.visit_f32x4_ge(),
+
+            VectorOpcode::F64x2Eq => decoder.visit_f64x2_eq(),
+            VectorOpcode::F64x2Ne => decoder.visit_f64x2_ne(),
+            VectorOpcode::F64x2Lt => decoder.visit_f64x2_lt(),
+            VectorOpcode::F64x2Gt => decoder.visit_f64x2_gt(),
+            VectorOpcode::F64x2Le => decoder.visit_f64x2_le(),
+            VectorOpcode::F64x2Ge => decoder.visit_f64x2_ge(),
+
+            VectorOpcode::Not => decoder.visit_v128_not(),
+            VectorOpcode::And => decoder.visit_v128_and(),
+            VectorOpcode::AndNot => decoder.visit_v128_andnot(),
+            VectorOpcode::Or => decoder.visit_v128_or(),
+            VectorOpcode::Xor => decoder.visit_v128_xor(),
+            VectorOpcode::Bitselect => decoder.visit_v128_bitselect(),
+            VectorOpcode::AnyTrue => decoder.visit_v128_any_true(),
+
+            VectorOpcode::I8x16Abs => decoder.visit_i8x16_abs(),
+            VectorOpcode::I8x16Neg => decoder.visit_i8x16_neg(),
+            VectorOpcode::I8x16Popcnt => decoder.visit_i8x16_popcnt(),
+            VectorOpcode::I8x16AllTrue => decoder.visit_i8x16_all_true(),
+            VectorOpcode::I8x16Bitmask => decoder.visit_i8x16_bitmask(),
+            VectorOpcode::I8x16NarrowI16x8S => decoder.visit_i8x16_narrow_i16x8_s(),
+            VectorOpcode::I8x16NarrowI16x8U => decoder.visit_i8x16_narrow_i16x8_u(),
+            VectorOpcode::I8x16Shl => decoder.visit_i8x16_shl(),
+            VectorOpcode::I8x16ShrS => decoder.visit_i8x16_shr_s(),
+            VectorOpcode::I8x16ShrU => decoder.visit_i8x16_shr_u(),
+            VectorOpcode::I8x16Add => decoder.visit_i8x16_add(),
+            VectorOpcode::I8x16AddSatS => decoder.visit_i8x16_add_sat_s(),
+            VectorOpcode::I8x16AddSatU => decoder.visit_i8x16_add_sat_u(),
+            VectorOpcode::I8x16Sub => decoder.visit_i8x16_sub(),
+            VectorOpcode::I8x16SubSatS => decoder.visit_i8x16_sub_sat_s(),
+            VectorOpcode::I8x16SubSatU => decoder.visit_i8x16_sub_sat_u(),
+            VectorOpcode::I8x16MinS => decoder.visit_i8x16_min_s(),
+            VectorOpcode::I8x16MinU => decoder.visit_i8x16_min_u(),
+            VectorOpcode::I8x16MaxS => decoder.visit_i8x16_max_s(),
+            VectorOpcode::I8x16MaxU => decoder.visit_i8x16_max_u(),
+            VectorOpcode::I8x16AvgrU => decoder.visit_i8x16_avgr_u(),
+
+            VectorOpcode::I16x8ExtaddPairwiseI8x16S => {
+                decoder.visit_i16x8_extadd_pairwise_i8x16_s()
+            }
+            VectorOpcode::I16x8ExtaddPairwiseI8x16U => {
+                decoder.visit_i16x8_extadd_pairwise_i8x16_u()
+            }
+            VectorOpcode::I16x8Abs => decoder.visit_i16x8_abs(),
+            VectorOpcode::I16x8Neg => decoder.visit_i16x8_neg(),
+            VectorOpcode::I16x8Q15MulrSatS => decoder.visit_i16x8_q15mulr_sat_s(),
+            VectorOpcode::I16x8AllTrue => decoder.visit_i16x8_all_true(),
+            VectorOpcode::I16x8Bitmask => decoder.visit_i16x8_bitmask(),
+            VectorOpcode::I16x8NarrowI32x4S => decoder.visit_i16x8_narrow_i32x4_s(),
+            VectorOpcode::I16x8NarrowI32x4U => decoder.visit_i16x8_narrow_i32x4_u(),
+            VectorOpcode::I16x8ExtendLowI8x16S => decoder.visit_i16x8_extend_low_i8x16_s(),
+            VectorOpcode::I16x8ExtendHighI8x16S => decoder.visit_i16x8_extend_high_i8x16_s(),
+            VectorOpcode::I16x8ExtendLowI8x16U => decoder.visit_i16x8_extend_low_i8x16_u(),
+            VectorOpcode::I16x8ExtendHighI8x16U => decoder.visit_i16x8_extend_high_i8x16_u(),
+            VectorOpcode::I16x8Shl => decoder.visit_i16x8_shl(),
+            VectorOpcode::I16x8ShrS => decoder.visit_i16x8_shr_s(),
+            VectorOpcode::I16x8ShrU => decoder.visit_i16x8_shr_u(),
+            VectorOpcode::I16x8Add => decoder.visit_i16x8_add(),
+            VectorOpcode::I16x8AddSatS => decoder.visit_i16x8_add_sat_s(),
+            VectorOpcode::I16x8AddSatU => decoder.visit_i16x8_add_sat_u(),
+            VectorOpcode::I16x8Sub => decoder.visit_i16x8_sub(),
+            VectorOpcode::I16x8SubSatS => decoder.visit_i16x8_sub_sat_s(),
+            VectorOpcode::I16x8SubSatU => decoder.visit_i16x8_sub_sat_u(),
+            VectorOpcode::I16x8Mul => decoder.visit_i16x8_mul(),
+            VectorOpcode::I16x8MinS => decoder.visit_i16x8_min_s(),
+            VectorOpcode::I16x8MinU => decoder.visit_i16x8_min_u(),
+            VectorOpcode::I16x8MaxS => decoder.visit_i16x8_max_s(),
+            VectorOpcode::I16x8MaxU => decoder.visit_i16x8_max_u(),
+            VectorOpcode::I16x8AvgrU => decoder.visit_i16x8_avgr_u(),
+            VectorOpcode::I16x8ExtmulLowI8x16S => decoder.visit_i16x8_extmul_low_i8x16_s(),
+            VectorOpcode::I16x8ExtmulHighI8x16S => decoder.visit_i16x8_extmul_high_i8x16_s(),
+            VectorOpcode::I16x8ExtmulLowI8x16U => decoder.visit_i16x8_extmul_low_i8x16_u(),
+            VectorOpcode::I16x8ExtmulHighI8x16U => decoder.visit_i16x8_extmul_high_i8x16_u(),
+
+            VectorOpcode::I32x4ExtaddPairwiseI16x8S => {
+                decoder.visit_i32x4_extadd_pairwise_i16x8_s()
+            }
+            VectorOpcode::I32x4ExtaddPairwiseI16x8U => {
+                decoder.visit_i32x4_extadd_pairwise_i16x8_u()
+            }
+            VectorOpcode::I32x4Abs => decoder.visit_i32x4_abs(),
+            VectorOpcode::I32x4Neg => decoder.visit_i32x4_neg(),
+            VectorOpcode::I32x4AllTrue => decoder.visit_i32x4_all_true(),
+            VectorOpcode::I32x4Bitmask => decoder.visit_i32x4_bitmask(),
+            VectorOpcode::I32x4ExtendLowI16x8S => decoder.visit_i32x4_extend_low_i16x8_s(),
+            VectorOpcode::I32x4ExtendHighI16x8S => decoder.visit_i32x4_extend_high_i16x8_s(),
+            VectorOpcode::I32x4ExtendLowI16x8U => decoder.visit_i32x4_extend_low_i16x8_u(),
+            VectorOpcode::I32x4ExtendHighI16x8U => decoder.visit_i32x4_extend_high_i16x8_u(),
+            VectorOpcode::I32x4Shl => decoder.visit_i32x4_shl(),
+            VectorOpcode::I32x4ShrS => decoder.visit_i32x4_shr_s(),
+            VectorOpcode::I32x4ShrU => decoder.visit_i32x4_shr_u(),
+            VectorOpcode::I32x4Add => decoder.visit_i32x4_add(),
+            VectorOpcode::I32x4Sub => decoder.visit_i32x4_sub(),
+            VectorOpcode::I32x4Mul => decoder.visit_i32x4_mul(),
+            VectorOpcode::I32x4MinS => decoder.visit_i32x4_min_s(),
+            VectorOpcode::I32x4MinU => decoder.visit_i32x4_min_u(),
+            VectorOpcode::I32x4MaxS => decoder.visit_i32x4_max_s(),
+            VectorOpcode::I32x4MaxU => decoder.visit_i32x4_max_u(),
+            VectorOpcode::I32x4DotI16x8S => decoder.visit_i32x4_dot_i16x8_s(),
+            VectorOpcode::I32x4ExtmulLowI16x8S => decoder.visit_i32x4_extmul_low_i16x8_s(),
+            VectorOpcode::I32x4ExtmulHighI16x8S => decoder.visit_i32x4_extmul_high_i16x8_s(),
+            VectorOpcode::I32x4ExtmulLowI16x8U => decoder.visit_i32x4_extmul_low_i16x8_u(),
+            VectorOpcode::I32x4ExtmulHighI16x8U => decoder.visit_i32x4_extmul_high_i16x8_u(),
+
+            VectorOpcode::I64x2Abs => decoder.visit_i64x2_abs(),
+            VectorOpcode::I64x2Neg => decoder.visit_i64x2_neg(),
+            VectorOpcode::I64x2AllTrue => decoder.visit_i64x2_all_true(),
+            VectorOpcode::I64x2Bitmask => decoder.visit_i64x2_bitmask(),
+            VectorOpcode::I64x2ExtendLowI32x4S => decoder.visit_i64x2_extend_low_i32x4_s(),
+            VectorOpcode::I64x2ExtendHighI32x4S => decoder.visit_i64x2_extend_high_i32x4_s(),
+            VectorOpcode::I64x2ExtendLowI32x4U => decoder.visit_i64x2_extend_low_i32x4_u(),
+            VectorOpcode::I64x2ExtendHighI32x4U => decoder.visit_i64x2_extend_high_i32x4_u(),
+            VectorOpcode::I64x2Shl => decoder.visit_i64x2_shl(),
+            VectorOpcode::I64x2ShrS => decoder.visit_i64x2_shr_s(),
+            VectorOpcode::I64x2ShrU => decoder.visit_i64x2_shr_u(),
+            VectorOpcode::I64x2Add => decoder.visit_i64x2_add(),
+            VectorOpcode::I64x2Sub => decoder.visit_i64x2_sub(),
+            VectorOpcode::I64x2Mul => decoder.visit_i64x2_mul(),
+            VectorOpcode::I64x2ExtmulLowI32x4S => decoder.visit_i64x2_extmul_low_i32x4_s(),
+            VectorOpcode::I64x2ExtmulHighI32x4S => decoder.visit_i64x2_extmul_high_i32x4_s(),
+            VectorOpcode::I64x2ExtmulLowI32x4U => decoder.visit_i64x2_extmul_low_i32x4_u(),
+            VectorOpcode::I64x2ExtmulHighI32x4U => decoder.visit_i64x2_extmul_high_i32x4_u(),
+
+            VectorOpcode::F32x4Ceil => decoder.visit_f32x4_ceil(),
+            VectorOpcode::F32x4Floor => decoder.visit_f32x4_floor(),
+            VectorOpcode::F32x4Trunc => decoder.visit_f32x4_trunc(),
+            VectorOpcode::F32x4Nearest => decoder.visit_f32x4_nearest(),
+            VectorOpcode::F32x4Abs => decoder.visit_f32x4_abs(),
+            VectorOpcode::F32x4Neg => decoder.visit_f32x4_neg(),
+            VectorOpcode::F32x4Sqrt => decoder.visit_f32x4_sqrt(),
+            VectorOpcode::F32x4Add => decoder.visit_f32x4_add(),
+            VectorOpcode::F32x4Sub => decoder.visit_f32x4_sub(),
+            VectorOpcode::F32x4Mul => decoder.visit_f32x4_mul(),
+            VectorOpcode::F32x4Div => decoder.visit_f32x4_div(),
+            VectorOpcode::F32x4Min => decoder.visit_f32x4_min(),
+            VectorOpcode::F32x4Max => decoder.visit_f32x4_max(),
+            VectorOpcode::F32x4Pmin => decoder.visit_f32x4_pmin(),
+            VectorOpcode::F32x4Pmax => decoder.visit_f32x4_pmax(),
+
+            VectorOpcode::F64x2Ceil => decoder.visit_f64x2_ceil(),
+            VectorOpcode::F64x2Floor => decoder.visit_f64x2_floor(),
+            VectorOpcode::F64x2Trunc => decoder.visit_f64x2_trunc(),
+            VectorOpcode::F64x2Nearest => decoder.visit_f64x2_nearest(),
+            VectorOpcode::F64x2Abs => decoder.visit_f64x2_abs(),
+            VectorOpcode::F64x2Neg => decoder.visit_f64x2_neg(),
+            VectorOpcode::F64x2Sqrt => decoder.visit_f64x2_sqrt(),
+            VectorOpcode::F64x2Add => decoder.visit_f64x2_add(),
+            VectorOpcode::F64x2Sub => decoder.visit_f64x2_sub(),
+            VectorOpcode::F64x2Mul => decoder.visit_f64x2_mul(),
+            VectorOpcode::F64x2Div => decoder.visit_f64x2_div(),
+            VectorOpcode::F64x2Min => decoder.visit_f64x2_min(),
+            VectorOpcode::F64x2Max => decoder.visit_f64x2_max(),
+            VectorOpcode::F64x2Pmin => decoder.visit_f64x2_pmin(),
+            VectorOpcode::F64x2Pmax => decoder.visit_f64x2_pmax(),
+
+            VectorOpcode::I32x4TruncSatF32x4S => decoder.visit_i32x4_trunc_sat_f32x4_s(),
+            VectorOpcode::I32x4TruncSatF32x4U => decoder.visit_i32x4_trunc_sat_f32x4_u(),
+            VectorOpcode::F32x4ConvertI32x4S => decoder.visit_f32x4_convert_i32x4_s(),
+            VectorOpcode::F32x4ConvertI32x4U => decoder.visit_f32x4_convert_i32x4_u(),
+            VectorOpcode::I32x4TruncSatF64x2SZero => decoder.visit_i32x4_trunc_sat_f64x2_s_zero(),
+            VectorOpcode::I32x4TruncSatF64x2UZero => decoder.visit_i32x4_trunc_sat_f64x2_u_zero(),
+            VectorOpcode::F64x2ConvertLowI32x4S => decoder.visit_f64x2_convert_low_i32x4_s(),
+            VectorOpcode::F64x2ConvertLowI32x4U => decoder.visit_f64x2_convert_low_i32x4_u(),
+            VectorOpcode::F32x4DemoteF64x2Zero => decoder.visit_f32x4_demote_f64x2_zero(),
+            VectorOpcode::F64x2PromoteLowF32x4 => decoder.visit_f64x2_promote_low_f32x4(),
+        },
+        #[cfg(not(feature = "simd"))]
+        InstructionOpcode::V128(_) => Err(disabled_instruction(Opcode::PrefixV128)),
+        #[cfg(feature = "threads")]
+        InstructionOpcode::FE(actual_opcode) => match actual_opcode {
+            FEPrefixedOpcode::MemoryAtomicNotify => {
+                decoder.visit_memory_atomic_notify(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::MemoryAtomicWait32 => {
+                decoder.visit_memory_atomic_wait32(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::MemoryAtomicWait64 => {
+                decoder.visit_memory_atomic_wait64(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I32AtomicLoad => {
+                decoder.visit_i32_atomic_load(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I64AtomicLoad => {
+                decoder.visit_i64_atomic_load(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I32AtomicLoad8U => {
+                decoder.visit_i32_atomic_load8_u(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I32AtomicLoad16U => {
+                decoder.visit_i32_atomic_load16_u(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I64AtomicLoad8U => {
+                decoder.visit_i64_atomic_load8_u(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I64AtomicLoad16U => {
+                decoder.visit_i64_atomic_load16_u(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I64AtomicLoad32U => {
+                decoder.visit_i64_atomic_load32_u(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I32AtomicStore => {
+                decoder.visit_i32_atomic_store(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I64AtomicStore => {
+                decoder.visit_i64_atomic_store(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I32AtomicStore8U => {
+                decoder.visit_i32_atomic_store8_u(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I32AtomicStore16U => {
+                decoder.visit_i32_atomic_store16_u(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I64AtomicStore8U => {
+                decoder.visit_i64_atomic_store8_u(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I64AtomicStore16U => {
+                decoder.visit_i64_atomic_store16_u(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I64AtomicStore32U => {
+                decoder.visit_i64_atomic_store32_u(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I32AtomicRmwAdd => {
+                decoder.visit_i32_atomic_rmw_add(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I64AtomicRmwAdd => {
+                decoder.visit_i64_atomic_rmw_add(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I32AtomicRmw8AddU => {
+                decoder.visit_i32_atomic_rmw8_add_u(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I32AtomicRmw16AddU => {
+                decoder.visit_i32_atomic_rmw16_add_u(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I64AtomicRmw8AddU => {
+                decoder.visit_i64_atomic_rmw8_add_u(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I64AtomicRmw16AddU => {
+                decoder.visit_i64_atomic_rmw16_add_u(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I64AtomicRmw32AddU => {
+                decoder.visit_i64_atomic_rmw32_add_u(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I32AtomicRmwSub => {
+                decoder.visit_i32_atomic_rmw_sub(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I64AtomicRmwSub => {
+                decoder.visit_i64_atomic_rmw_sub(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I32AtomicRmw8SubU => {
+                decoder.visit_i32_atomic_rmw8_sub_u(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I32AtomicRmw16SubU => {
+                decoder.visit_i32_atomic_rmw16_sub_u(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I64AtomicRmw8SubU => {
+                decoder.visit_i64_atomic_rmw8_sub_u(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I64AtomicRmw16SubU => {
+                decoder.visit_i64_atomic_rmw16_sub_u(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I64AtomicRmw32SubU => {
+                decoder.visit_i64_atomic_rmw32_sub_u(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I32AtomicRmwAnd => {
+                decoder.visit_i32_atomic_rmw_and(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I64AtomicRmwAnd => {
+                decoder.visit_i64_atomic_rmw_and(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I32AtomicRmw8AndU => {
+                decoder.visit_i32_atomic_rmw8_and_u(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I32AtomicRmw16AndU => {
+                decoder.visit_i32_atomic_rmw16_and_u(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I64AtomicRmw8AndU => {
+                decoder.visit_i64_atomic_rmw8_and_u(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I64AtomicRmw16AndU => {
+                decoder.visit_i64_atomic_rmw16_and_u(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I64AtomicRmw32AndU => {
+                decoder.visit_i64_atomic_rmw32_and_u(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I32AtomicRmwOr => {
+                decoder.visit_i32_atomic_rmw_or(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I64AtomicRmwOr => {
+                decoder.visit_i64_atomic_rmw_or(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I32AtomicRmw8OrU => {
+                decoder.visit_i32_atomic_rmw8_or_u(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I32AtomicRmw16OrU => {
+                decoder.visit_i32_atomic_rmw16_or_u(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I64AtomicRmw8OrU => {
+                decoder.visit_i64_atomic_rmw8_or_u(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I64AtomicRmw16OrU => {
+                decoder.visit_i64_atomic_rmw16_or_u(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I64AtomicRmw32OrU => {
+                decoder.visit_i64_atomic_rmw32_or_u(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I32AtomicRmwXor => {
+                decoder.visit_i32_atomic_rmw_xor(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I64AtomicRmwXor => {
+                decoder.visit_i64_atomic_rmw_xor(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I32AtomicRmw8XorU => {
+                decoder.visit_i32_atomic_rmw8_xor_u(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I32AtomicRmw16XorU => {
+                decoder.visit_i32_atomic_rmw16_xor_u(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I64AtomicRmw8XorU => {
+                decoder.visit_i64_atomic_rmw8_xor_u(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I64AtomicRmw16XorU => {
+                decoder.visit_i64_atomic_rmw16_xor_u(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I64AtomicRmw32XorU => {
+                decoder.visit_i64_atomic_rmw32_xor_u(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I32AtomicRmwXchg => {
+                decoder.visit_i32_atomic_rmw_xchg(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I64AtomicRmwXchg => {
+                decoder.visit_i64_atomic_rmw_xchg(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I32AtomicRmw8XchgU => {
+                decoder.visit_i32_atomic_rmw8_xchg_u(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I32AtomicRmw16XchgU => {
+                decoder.visit_i32_atomic_rmw16_xchg_u(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I64AtomicRmw8XchgU => {
+                decoder.visit_i64_atomic_rmw8_xchg_u(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I64AtomicRmw16XchgU => {
+                decoder.visit_i64_atomic_rmw16_xchg_u(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I64AtomicRmw32XchgU => {
+                decoder.visit_i64_atomic_rmw32_xchg_u(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I32AtomicRmwCmpxchg => {
+                decoder.visit_i32_atomic_rmw_cmpxchg(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I64AtomicRmwCmpxchg => {
+                decoder.visit_i64_atomic_rmw_cmpxchg(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I32AtomicRmw8CmpxchgU => {
+                decoder.visit_i32_atomic_rmw8_cmpxchg_u(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I32AtomicRmw16CmpxchgU => {
+                decoder.visit_i32_atomic_rmw16_cmpxchg_u(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I64AtomicRmw8CmpxchgU => {
+                decoder.visit_i64_atomic_rmw8_cmpxchg_u(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I64AtomicRmw16CmpxchgU => {
+                decoder.visit_i64_atomic_rmw16_cmpxchg_u(memarg(offset, input, actual_opcode)?)
+            }
+            FEPrefixedOpcode::I64AtomicRmw32CmpxchgU => {
+                decoder.visit_i64_atomic_rmw32_cmpxchg_u(memarg(offset, input, actual_opcode)?)
+            }
+        },
+        #[cfg(not(feature = "threads"))]
+        InstructionOpcode::FE(_) => Err(disabled_instruction(Opcode::PrefixFE)),
+    }
+}
 
-                return Err(block_nesting_counter_overflowed().into());
+/// Updates the number of `blocks` that have been entered after an instruction with the given
+/// `opcode` was parsed.
+fn update_block_nesting(opcode: InstructionOpcode, blocks: &mut u32) -> parser::Parsed<()> {
+    let enter = |blocks: &mut u32| {
+        if let Some(entered) = blocks.checked_add(1) {
+            *blocks = entered;
+            Ok(())
+        } else {
+            #[inline(never)]
+            #[cold]
+            fn block_nesting_counter_overflowed() -> Error {
+                Error::new(ErrorKind::BlockNestingCounterOverflow)
             }
+
+            Err(block_nesting_counter_overflowed())
         }
-        Instruction::End => {
+    };
+
+    let opcode = match opcode {
+        InstructionOpcode::Byte(opcode) => opcode,
+        _ => return Ok(()),
+    };
+
+    match opcode {
+        Opcode::Block | Opcode::Loop | Opcode::If => enter(blocks),
+        #[cfg(feature = "exceptions")]
+        Opcode::Try | Opcode::TryTable => enter(blocks),
+        Opcode::End => {
             // Won't underflow, check for self.blocks == 0 ensures None is returned early
             *blocks -= 1;
+            Ok(())
         }
-        Instruction::Delegate(_) => {
+        #[cfg(feature = "exceptions")]
+        Opcode::Delegate => {
             if *blocks > 1 {
                 // Check above ensures a "delegate" won't erroneously mark the end of an expression
                 *blocks -= 1;
+                Ok(())
             } else {
                 #[inline(never)]
                 #[cold]
@@ -931,16 +1103,50 @@ where
                     Error::new(ErrorKind::ExpectedEndInstructionButGotDelegate)
                 }
 
-                return Err(unexpected_delegate().into());
+                Err(unexpected_delegate())
             }
         }
-        _ => {}
+        _ => Ok(()),
     }
+}
 
+#[inline]
+fn next_instruction<'a, T, E, I, F>(
+    offset: &'a mut u64,
+    input: &'a I,
+    blocks: &mut u32,
+    f: F,
+) -> Result<T, E>
+where
+    E: From<parser::Error>,
+    I: Input,
+    F: FnOnce(&mut Instruction<'a, &'a I>) -> Result<T, E>,
+{
+    let opcode = self::opcode(offset, input)?;
+    let mut instruction = decode(opcode, offset, input, BuildInstruction)?;
+    let result = f(&mut instruction)?;
+    update_block_nesting(opcode, blocks)?;
     instruction.finish()?;
     Ok(result)
 }
 
+#[inline]
+fn visit_next_instruction<'a, I, V>(
+    offset: &'a mut u64,
+    input: &'a I,
+    blocks: &mut u32,
+    visitor: &mut V,
+) -> parser::Parsed<(InstructionOpcode, V::Output)>
+where
+    I: Input,
+    V: instruction_set::VisitInstruction<'a, &'a I> + ?Sized,
+{
+    let opcode = self::opcode(offset, input)?;
+    let output = decode(opcode, offset, input, visitor)?;
+    update_block_nesting(opcode, blocks)?;
+    Ok((opcode, output))
+}
+
 /// Represents an expression or
 /// [`expr`](https://webassembly.github.io/spec/core/syntax/instructions.html), which is a sequence
 /// of instructions that is terminated by an [**end**](Instruction::End) instruction.
@@ -981,6 +1187,12 @@ impl<O: Offset, I: Input> InstructionSequence<O, I> {
         self.blocks
     }
 
+    /// Gets the offset to the next instruction to be parsed.
+    #[inline]
+    pub fn offset(&self) -> u64 {
+        self.offset.offset()
+    }
+
     /// Processes the next [`Instruction`] in the sequence, providing it to the given closure.
     pub fn next<'a, T, E, F>(&'a mut self, f: F) -> Option<Result<T, E>>
     where
@@ -1001,6 +1213,112 @@ impl<O: Offset, I: Input> InstructionSequence<O, I> {
         Some(result)
     }
 
+    /// Processes the next [`Instruction`] in the sequence like [`next`](Self::next), additionally
+    /// providing the [`Span`](parser::Span) of bytes the instruction was parsed from.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wasmiter::instruction_set::{Instruction, InstructionSequence};
+    ///
+    /// // i32.const 300, drop, end
+    /// let bytes = [0x41, 0xAC, 0x02, 0x1A, 0x0B];
+    /// let mut instructions = InstructionSequence::new(0, bytes.as_slice());
+    /// let constant = instructions
+    ///     .next_spanned(|i| Ok::<_, wasmiter::parser::Error>(matches!(i, Instruction::I32Const(300))))
+    ///     .unwrap()?;
+    ///
+    /// assert!(*constant.item());
+    /// assert_eq!(constant.span(), wasmiter::parser::Span::new(0, 3));
+    /// # Ok::<(), wasmiter::parser::Error>(())
+    /// ```
+    pub fn next_spanned<T, E, F>(&mut self, f: F) -> Option<Result<parser::Spanned<T>, E>>
+    where
+        E: From<parser::Error>,
+        F: for<'a> FnOnce(&mut Instruction<'a, &'a I>) -> Result<T, E>,
+    {
+        let start = self.offset.offset();
+        let result = self.next(f)?;
+        Some(
+            result.map(|item| {
+                parser::Spanned::new(parser::Span::new(start, self.offset.offset()), item)
+            }),
+        )
+    }
+
+    /// Processes the next [`Instruction`] in the sequence, calling the corresponding method of the
+    /// `visitor`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wasmiter::{input::Input, instruction_set::{InstructionSequence, VisitInstruction}};
+    ///
+    /// struct Sum(i32);
+    ///
+    /// impl<'a, I: Input> VisitInstruction<'a, I> for Sum {
+    ///     type Output = ();
+    ///
+    ///     fn visit_other(&mut self, _: &'static str) {}
+    ///
+    ///     fn visit_i32_const(&mut self, value: i32) {
+    ///         self.0 += value;
+    ///     }
+    /// }
+    ///
+    /// // i32.const 300, i32.const 2, drop, drop, end
+    /// let bytes = [0x41, 0xAC, 0x02, 0x41, 0x02, 0x1A, 0x1A, 0x0B];
+    /// let mut instructions = InstructionSequence::new(0, bytes.as_slice());
+    /// let mut sum = Sum(0);
+    /// while let Some(result) = instructions.visit(&mut sum) {
+    ///     result?;
+    /// }
+    ///
+    /// assert_eq!(sum.0, 302);
+    /// # Ok::<(), wasmiter::parser::Error>(())
+    /// ```
+    pub fn visit<'a, V>(&'a mut self, visitor: &mut V) -> Option<parser::Parsed<V::Output>>
+    where
+        V: instruction_set::VisitInstruction<'a, &'a I> + ?Sized,
+    {
+        Some(self.visit_with_opcode(visitor)?.map(|(_, output)| output))
+    }
+
+    fn visit_with_opcode<'a, V>(
+        &'a mut self,
+        visitor: &mut V,
+    ) -> Option<parser::Parsed<(InstructionOpcode, V::Output)>>
+    where
+        V: instruction_set::VisitInstruction<'a, &'a I> + ?Sized,
+    {
+        if self.is_finished() {
+            return None;
+        }
+
+        let result = visit_next_instruction(
+            self.offset.offset_mut(),
+            &self.input,
+            &mut self.blocks,
+            visitor,
+        );
+
+        if result.is_err() {
+            // If error is encountered, no more instructions should be returned
+            self.blocks = 0u32;
+        }
+
+        Some(result)
+    }
+
+    /// Skips over the next instruction without building an [`Instruction`], returning its
+    /// [`InstructionOpcode`].
+    pub(crate) fn skip_next(&mut self) -> Option<parser::Parsed<InstructionOpcode>> {
+        Some(
+            self.visit_with_opcode(&mut SkipInstruction)?
+                .map(|(opcode, ())| opcode),
+        )
+    }
+
     /// Processes the remaining instructions in the sequence. Returns `true` if all instructions
     /// were already processed, and the offset to the byte after the last byte of the last
     /// instruction.
@@ -1029,6 +1347,128 @@ impl<O: Offset, I: Input> InstructionSequence<O, I> {
             Err(missing_end_instructions(self.blocks))
         }
     }
+
+    /// Skips over the remaining instructions in the innermost block that has been entered, up
+    /// to and including the instruction that ends it or begins its next arm.
+    ///
+    /// This is intended to be called immediately after a [**block**], [**loop**], [**if**], or
+    /// [**try**] instruction is parsed, allowing nested instructions to be skipped without
+    /// providing them to a closure. If an [**else**], [**catch**], or [**catch_all**] instruction
+    /// is returned, the block is still entered, and this method can be called again to skip the
+    /// next arm.
+    ///
+    /// Returns `None` if there are no more instructions to parse.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wasmiter::instruction_set::{self, BlockTerminator, Instruction};
+    ///
+    /// // block, i32.const 1, drop, end, nop, end
+    /// let bytes = [0x02u8, 0x40, 0x41, 1, 0x1A, 0x0B, 0x01, 0x0B];
+    /// let mut expression = instruction_set::parse_expression(0, bytes.as_slice());
+    ///
+    /// expression.next(|_| wasmiter::parser::Parsed::Ok(())).transpose()?;
+    /// assert_eq!(expression.skip_block().transpose()?, Some(BlockTerminator::End));
+    /// assert_eq!(expression.nesting_level(), 1);
+    ///
+    /// let is_nop = expression.next(|i| wasmiter::parser::Parsed::Ok(matches!(i, Instruction::Nop)));
+    /// assert_eq!(is_nop.transpose()?, Some(true));
+    /// # Ok::<(), wasmiter::parser::Error>(())
+    /// ```
+    ///
+    /// [**block**]: Instruction::Block
+    /// [**loop**]: Instruction::Loop
+    /// [**if**]: Instruction::If
+    /// [**try**]: Instruction::Try
+    /// [**else**]: Instruction::Else
+    /// [**catch**]: Instruction::Catch
+    /// [**catch_all**]: Instruction::CatchAll
+    pub fn skip_block(&mut self) -> Option<parser::Parsed<BlockTerminator>> {
+        let level = self.blocks;
+        loop {
+            // Terminators of nested blocks are parsed at a deeper nesting level
+            let nested = self.blocks > level;
+            let result = self.visit(&mut FindBlockTerminator)?;
+
+            match result {
+                Ok(Some(terminator)) if !nested => return Some(Ok(terminator)),
+                Ok(_) => (),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+/// Indicates which instruction ended or split a block skipped by
+/// [`InstructionSequence::skip_block()`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum BlockTerminator {
+    /// An [**end**](Instruction::End) instruction marked the end of the block.
+    End,
+    /// An [**else**](Instruction::Else) instruction marked the start of the second arm of an
+    /// [**if**](Instruction::If) block.
+    Else,
+    /// A [**catch**](Instruction::Catch) instruction marked the start of an exception handler.
+    Catch(index::TagIdx),
+    /// A [**catch_all**](Instruction::CatchAll) instruction marked the start of the handler for
+    /// uncaught exceptions.
+    CatchAll,
+    /// A [**delegate**](Instruction::Delegate) instruction marked the end of a
+    /// [**try**](Instruction::Try) block.
+    Delegate(index::LabelIdx),
+}
+
+/// Visits any instruction without reading its arguments, allowing them to be skipped.
+struct SkipInstruction;
+
+impl<'a, I: Input> instruction_set::VisitInstruction<'a, I> for SkipInstruction {
+    type Output = ();
+
+    #[inline]
+    fn visit_other(&mut self, _: &'static str) {}
+}
+
+/// Visits only the instructions that end or split a block, allowing the other instructions to be
+/// skipped without building an [`Instruction`] for each one.
+struct FindBlockTerminator;
+
+impl<'a, I: Input> instruction_set::VisitInstruction<'a, I> for FindBlockTerminator {
+    type Output = Option<BlockTerminator>;
+
+    #[inline]
+    fn visit_other(&mut self, _: &'static str) -> Self::Output {
+        None
+    }
+
+    #[inline]
+    fn visit_end(&mut self) -> Self::Output {
+        Some(BlockTerminator::End)
+    }
+
+    #[inline]
+    fn visit_else(&mut self) -> Self::Output {
+        Some(BlockTerminator::Else)
+    }
+
+    #[cfg(feature = "exceptions")]
+    #[inline]
+    fn visit_catch(&mut self, tag: index::TagIdx) -> Self::Output {
+        Some(BlockTerminator::Catch(tag))
+    }
+
+    #[cfg(feature = "exceptions")]
+    #[inline]
+    fn visit_catch_all(&mut self) -> Self::Output {
+        Some(BlockTerminator::CatchAll)
+    }
+
+    #[cfg(feature = "exceptions")]
+    #[inline]
+    fn visit_delegate(&mut self, label: index::LabelIdx) -> Self::Output {
+        Some(BlockTerminator::Delegate(label))
+    }
 }
 
 impl<O: Offset, I: Input> HasInput<I> for InstructionSequence<O, I> {
@@ -1045,7 +1485,7 @@ impl<'a, O: Offset, I: Input + 'a> BorrowInput<'a, I> for InstructionSequence<O,
     fn borrow_input(&'a self) -> Self::Borrowed {
         InstructionSequence {
             blocks: self.blocks,
-            offset: self.offset.offset(),
+            offset: self.offset(),
             input: &self.input,
         }
     }
@@ -1058,12 +1498,114 @@ impl<'a, O: Offset, I: Clone + Input + 'a> CloneInput<'a, I> for InstructionSequ
     fn clone_input(&self) -> Self::Cloned {
         InstructionSequence {
             blocks: self.blocks,
-            offset: self.offset.offset(),
+            offset: self.offset(),
             input: self.input.clone(),
         }
     }
 }
 
+/// Provides a configurable [`Debug`](core::fmt::Debug) implementation for an
+/// [`InstructionSequence`].
+///
+/// Returned by [`InstructionSequence::debug_with()`].
+#[derive(Clone, Copy)]
+pub struct DebugInstructionSequence<'a, O: Offset, I: Input> {
+    sequence: &'a InstructionSequence<O, I>,
+    offsets: bool,
+    limit: Option<usize>,
+}
+
+impl<O: Offset, I: Input> InstructionSequence<O, I> {
+    /// Returns a wrapper whose [`Debug`](core::fmt::Debug) implementation optionally includes the
+    /// byte offset of each instruction, and stops after a `limit` number of instructions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wasmiter::instruction_set;
+    ///
+    /// // i32.const 1, i32.const 2, i32.add, end
+    /// let bytes = [0x41u8, 1, 0x41, 2, 0x6A, 0x0B];
+    /// let expression = instruction_set::parse_expression(0, bytes.as_slice());
+    /// let debug = format!("{:?}", expression.debug_with(true, Some(2)));
+    /// assert_eq!(debug, "[0x0: I32Const(1), 0x2: I32Const(2), ...]");
+    /// ```
+    #[inline]
+    pub fn debug_with(
+        &self,
+        offsets: bool,
+        limit: Option<usize>,
+    ) -> DebugInstructionSequence<'_, O, I> {
+        DebugInstructionSequence {
+            sequence: self,
+            offsets,
+            limit,
+        }
+    }
+}
+
+impl<O: Offset, I: Input> core::fmt::Debug for DebugInstructionSequence<'_, O, I> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        struct Entry<T> {
+            offset: Option<u64>,
+            value: T,
+        }
+
+        impl<T: core::fmt::Debug> core::fmt::Debug for Entry<T> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                if let Some(offset) = self.offset {
+                    write!(f, "{offset:#X}: ")?;
+                }
+                core::fmt::Debug::fmt(&self.value, f)
+            }
+        }
+
+        struct Omitted;
+
+        impl core::fmt::Debug for Omitted {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.write_str("...")
+            }
+        }
+
+        let mut instructions = self.sequence.borrow_input();
+        let mut list = f.debug_list();
+        let mut count = 0usize;
+        loop {
+            if instructions.is_finished() {
+                break;
+            } else if self.limit.map_or(false, |limit| count >= limit) {
+                list.entry(&Omitted);
+                break;
+            }
+
+            let offset = if self.offsets {
+                Some(instructions.offset)
+            } else {
+                None
+            };
+
+            let result = instructions.next(|i| {
+                list.entry(&Entry { offset, value: i });
+                Ok(())
+            });
+
+            match result {
+                None => break,
+                Some(Err(e)) => {
+                    list.entry(&Entry {
+                        offset,
+                        value: parser::Parsed::<()>::Err(e),
+                    });
+                    break;
+                }
+                Some(Ok(())) => count += 1,
+            }
+        }
+        list.finish()
+    }
+}
+
 impl<O: Offset, I: Input> core::fmt::Debug for InstructionSequence<O, I> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let mut instructions = self.borrow_input();
@@ -1086,3 +1628,77 @@ impl<O: Offset, I: Input> core::fmt::Debug for InstructionSequence<O, I> {
         list.finish()
     }
 }
+
+/// An [`Iterator`] over the instructions in an [`InstructionSequence`], yielding each one as an
+/// [`OwnedInstruction`](instruction_set::OwnedInstruction).
+///
+/// See [`InstructionSequence::into_iter_owned`] for more information.
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[derive(Clone, Copy)]
+pub struct OwnedInstructions<O: Offset, I: Input> {
+    sequence: InstructionSequence<O, I>,
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+impl<O: Offset, I: Input> InstructionSequence<O, I> {
+    /// Returns an [`Iterator`] over the remaining instructions in the sequence, which parses the
+    /// arguments of each instruction into an
+    /// [`OwnedInstruction`](instruction_set::OwnedInstruction).
+    ///
+    /// Unlike [`next`](Self::next), this allows instructions to be collected, filtered, and
+    /// stored, at the cost of allocating the arguments of some instructions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wasmiter::instruction_set::{self, OwnedInstruction};
+    ///
+    /// // i32.const 1, i32.const 2, i32.add, end
+    /// let bytes = [0x41u8, 1, 0x41, 2, 0x6A, 0x0B];
+    /// let constants = instruction_set::parse_expression(0, bytes.as_slice())
+    ///     .into_iter_owned()
+    ///     .filter(|i| !matches!(i, Ok(OwnedInstruction::I32Add | OwnedInstruction::End)))
+    ///     .collect::<wasmiter::parser::Parsed<Vec<_>>>()?;
+    ///
+    /// assert_eq!(
+    ///     constants,
+    ///     [OwnedInstruction::I32Const(1), OwnedInstruction::I32Const(2)]
+    /// );
+    /// # Ok::<(), wasmiter::parser::Error>(())
+    /// ```
+    #[inline]
+    pub fn into_iter_owned(self) -> OwnedInstructions<O, I> {
+        OwnedInstructions { sequence: self }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<O: Offset, I: Input> OwnedInstructions<O, I> {
+    /// Gets the [`InstructionSequence`] containing the remaining instructions.
+    #[inline]
+    pub fn into_inner(self) -> InstructionSequence<O, I> {
+        self.sequence
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<O: Offset, I: Input> Iterator for OwnedInstructions<O, I> {
+    type Item = parser::Parsed<instruction_set::OwnedInstruction>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.sequence.next(Instruction::take_owned)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<O: Offset, I: Input> core::iter::FusedIterator for OwnedInstructions<O, I> {}
+
+#[cfg(feature = "alloc")]
+impl<O: Offset, I: Input> core::fmt::Debug for OwnedInstructions<O, I> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&self.sequence, f)
+    }
+}
@@ -27,9 +27,10 @@ impl<B: crate::input::Input> Instruction<'_, B> {
             | Self::I64Const(_)
             | Self::F32Const(_)
             | Self::F64Const(_)
-            | Self::V128Const(_)
             | Self::RefNull(_)
             | Self::RefFunc(_) => IsConstant::Constant,
+            #[cfg(feature = "simd")]
+            Self::V128Const(_) => IsConstant::Constant,
             Self::GlobalGet(index) => IsConstant::Global(*index),
             _ => IsConstant::NotConstant,
         }
@@ -127,7 +127,13 @@ macro_rules! match_instruction_memarg {
             | Self::I64Store8($memarg)
             | Self::I64Store16($memarg)
             | Self::I64Store32($memarg)
-            | Self::V128Load($memarg)
+    };
+}
+
+#[cfg(feature = "simd")]
+macro_rules! match_vector_memarg {
+    ($memarg:pat) => {
+        Self::V128Load($memarg)
             | Self::V128Load8x8S($memarg)
             | Self::V128Load8x8U($memarg)
             | Self::V128Load16x4S($memarg)
@@ -149,7 +155,13 @@ macro_rules! match_instruction_memarg {
             | Self::V128Store16Lane($memarg, _)
             | Self::V128Store32Lane($memarg, _)
             | Self::V128Store64Lane($memarg, _)
-            | Self::MemoryAtomicNotify($memarg)
+    };
+}
+
+#[cfg(feature = "threads")]
+macro_rules! match_atomic_memarg {
+    ($memarg:pat) => {
+        Self::MemoryAtomicNotify($memarg)
             | Self::MemoryAtomicWait32($memarg)
             | Self::MemoryAtomicWait64($memarg)
             | Self::I32AtomicLoad($memarg)
@@ -219,12 +231,158 @@ macro_rules! match_instruction_memarg {
 }
 
 impl<B: crate::input::Input> crate::instruction_set::Instruction<'_, B> {
+    /// Gets the [`MemArg`] of a memory load or store instruction, or `None` if the instruction
+    /// does not have one.
+    pub const fn memarg(&self) -> Option<MemArg> {
+        match self {
+            match_instruction_memarg!(memarg) => Some(*memarg),
+            #[cfg(feature = "simd")]
+            match_vector_memarg!(memarg) => Some(*memarg),
+            #[cfg(feature = "threads")]
+            match_atomic_memarg!(memarg) => Some(*memarg),
+            _ => None,
+        }
+    }
+
+    /// Gets the natural alignment of a memory load or store instruction, which is the number of
+    /// bytes that it accesses. Returns `None` if the instruction does not access memory with a
+    /// [`MemArg`].
+    ///
+    /// Atomic instructions require that their alignment is equal to their natural alignment.
+    pub const fn natural_alignment(&self) -> Option<Align> {
+        Some(match self {
+            Self::I32Load8S(_)
+            | Self::I32Load8U(_)
+            | Self::I64Load8S(_)
+            | Self::I64Load8U(_)
+            | Self::I32Store8(_)
+            | Self::I64Store8(_) => Align::None,
+            Self::I32Load16S(_)
+            | Self::I32Load16U(_)
+            | Self::I64Load16S(_)
+            | Self::I64Load16U(_)
+            | Self::I32Store16(_)
+            | Self::I64Store16(_) => Align::Two,
+            Self::I32Load(_)
+            | Self::F32Load(_)
+            | Self::I64Load32S(_)
+            | Self::I64Load32U(_)
+            | Self::I32Store(_)
+            | Self::F32Store(_)
+            | Self::I64Store32(_) => Align::Four,
+            Self::I64Load(_) | Self::F64Load(_) | Self::I64Store(_) | Self::F64Store(_) => {
+                Align::Eight
+            }
+            #[cfg(feature = "simd")]
+            Self::V128Load8Splat(_) | Self::V128Load8Lane(_, _) | Self::V128Store8Lane(_, _) => {
+                Align::None
+            }
+            #[cfg(feature = "simd")]
+            Self::V128Load16Splat(_) | Self::V128Load16Lane(_, _) | Self::V128Store16Lane(_, _) => {
+                Align::Two
+            }
+            #[cfg(feature = "simd")]
+            Self::V128Load32Splat(_)
+            | Self::V128Load32Zero(_)
+            | Self::V128Load32Lane(_, _)
+            | Self::V128Store32Lane(_, _) => Align::Four,
+            #[cfg(feature = "simd")]
+            Self::V128Load8x8S(_)
+            | Self::V128Load8x8U(_)
+            | Self::V128Load16x4S(_)
+            | Self::V128Load16x4U(_)
+            | Self::V128Load32x2S(_)
+            | Self::V128Load32x2U(_)
+            | Self::V128Load64Splat(_)
+            | Self::V128Load64Zero(_)
+            | Self::V128Load64Lane(_, _)
+            | Self::V128Store64Lane(_, _) => Align::Eight,
+            #[cfg(feature = "simd")]
+            Self::V128Load(_) | Self::V128Store(_) => Align::Sixteen,
+            #[cfg(feature = "threads")]
+            Self::I32AtomicLoad8U(_)
+            | Self::I64AtomicLoad8U(_)
+            | Self::I32AtomicStore8U(_)
+            | Self::I64AtomicStore8U(_)
+            | Self::I32AtomicRmw8AddU(_)
+            | Self::I64AtomicRmw8AddU(_)
+            | Self::I32AtomicRmw8SubU(_)
+            | Self::I64AtomicRmw8SubU(_)
+            | Self::I32AtomicRmw8AndU(_)
+            | Self::I64AtomicRmw8AndU(_)
+            | Self::I32AtomicRmw8OrU(_)
+            | Self::I64AtomicRmw8OrU(_)
+            | Self::I32AtomicRmw8XorU(_)
+            | Self::I64AtomicRmw8XorU(_)
+            | Self::I32AtomicRmw8XchgU(_)
+            | Self::I64AtomicRmw8XchgU(_)
+            | Self::I32AtomicRmw8CmpxchgU(_)
+            | Self::I64AtomicRmw8CmpxchgU(_) => Align::None,
+            #[cfg(feature = "threads")]
+            Self::I32AtomicLoad16U(_)
+            | Self::I64AtomicLoad16U(_)
+            | Self::I32AtomicStore16U(_)
+            | Self::I64AtomicStore16U(_)
+            | Self::I32AtomicRmw16AddU(_)
+            | Self::I64AtomicRmw16AddU(_)
+            | Self::I32AtomicRmw16SubU(_)
+            | Self::I64AtomicRmw16SubU(_)
+            | Self::I32AtomicRmw16AndU(_)
+            | Self::I64AtomicRmw16AndU(_)
+            | Self::I32AtomicRmw16OrU(_)
+            | Self::I64AtomicRmw16OrU(_)
+            | Self::I32AtomicRmw16XorU(_)
+            | Self::I64AtomicRmw16XorU(_)
+            | Self::I32AtomicRmw16XchgU(_)
+            | Self::I64AtomicRmw16XchgU(_)
+            | Self::I32AtomicRmw16CmpxchgU(_)
+            | Self::I64AtomicRmw16CmpxchgU(_) => Align::Two,
+            #[cfg(feature = "threads")]
+            Self::MemoryAtomicNotify(_)
+            | Self::MemoryAtomicWait32(_)
+            | Self::I32AtomicLoad(_)
+            | Self::I64AtomicLoad32U(_)
+            | Self::I32AtomicStore(_)
+            | Self::I64AtomicStore32U(_)
+            | Self::I32AtomicRmwAdd(_)
+            | Self::I64AtomicRmw32AddU(_)
+            | Self::I32AtomicRmwSub(_)
+            | Self::I64AtomicRmw32SubU(_)
+            | Self::I32AtomicRmwAnd(_)
+            | Self::I64AtomicRmw32AndU(_)
+            | Self::I32AtomicRmwOr(_)
+            | Self::I64AtomicRmw32OrU(_)
+            | Self::I32AtomicRmwXor(_)
+            | Self::I64AtomicRmw32XorU(_)
+            | Self::I32AtomicRmwXchg(_)
+            | Self::I64AtomicRmw32XchgU(_)
+            | Self::I32AtomicRmwCmpxchg(_)
+            | Self::I64AtomicRmw32CmpxchgU(_) => Align::Four,
+            #[cfg(feature = "threads")]
+            Self::MemoryAtomicWait64(_)
+            | Self::I64AtomicLoad(_)
+            | Self::I64AtomicStore(_)
+            | Self::I64AtomicRmwAdd(_)
+            | Self::I64AtomicRmwSub(_)
+            | Self::I64AtomicRmwAnd(_)
+            | Self::I64AtomicRmwOr(_)
+            | Self::I64AtomicRmwXor(_)
+            | Self::I64AtomicRmwXchg(_)
+            | Self::I64AtomicRmwCmpxchg(_) => Align::Eight,
+            _ => return None,
+        })
+    }
+
     /// Returns `true` if the instruction references memory other than the default memory (index
     /// `0`), which requires the
     /// [multiple memory proposal](https://github.com/WebAssembly/multi-memory).
     pub const fn requires_multi_memory(&self) -> bool {
         match self {
             match_instruction_memarg!(memarg) => memarg.requires_multi_memory(),
+            #[cfg(feature = "simd")]
+            match_vector_memarg!(memarg) => memarg.requires_multi_memory(),
+            #[cfg(feature = "threads")]
+            match_atomic_memarg!(memarg) => memarg.requires_multi_memory(),
             Self::MemorySize(index)
             | Self::MemoryGrow(index)
             | Self::MemoryInit(_, index)
@@ -242,6 +400,10 @@ impl<B: crate::input::Input> crate::instruction_set::Instruction<'_, B> {
     pub const fn requires_memory_64(&self) -> bool {
         match self {
             match_instruction_memarg!(memarg) => memarg.requires_memory_64(),
+            #[cfg(feature = "simd")]
+            match_vector_memarg!(memarg) => memarg.requires_memory_64(),
+            #[cfg(feature = "threads")]
+            match_atomic_memarg!(memarg) => memarg.requires_memory_64(),
             _ => false,
         }
     }
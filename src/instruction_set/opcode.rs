@@ -63,6 +63,7 @@ opcodes! {
     Catch = 7,
     Throw = 8,
     Rethrow = 9,
+    ThrowRef = 0xA,
     End = 0xB,
     Br = 0xC,
     BrIf = 0xD,
@@ -72,8 +73,11 @@ opcodes! {
     CallIndirect = 0x11,
     ReturnCall = 0x12,
     ReturnCallIndirect = 0x13,
+    CallRef = 0x14,
+    ReturnCallRef = 0x15,
     Delegate = 0x18,
     CatchAll = 0x19,
+    TryTable = 0x1F,
 
     Drop = 0x1A,
     Select = 0x1B,
@@ -265,6 +269,9 @@ opcodes! {
     RefNull = 0xD0,
     RefIsNull = 0xD1,
     RefFunc = 0xD2,
+    RefAsNonNull = 0xD4,
+    BrOnNull = 0xD5,
+    BrOnNonNull = 0xD6,
 
     /// A special instruction whose actual opcode is stored in a `u32` value following the prefix byte `0xFC`.
     ///
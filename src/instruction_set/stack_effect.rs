@@ -0,0 +1,571 @@
+use crate::{input::Input, instruction_set::Instruction};
+
+/// Describes the number of operands that an [`Instruction`] pops from and pushes onto the
+/// [value stack](https://webassembly.github.io/spec/core/exec/runtime.html#stack).
+///
+/// Returned by [`Instruction::stack_effect()`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct StackEffect {
+    pops: u8,
+    pushes: u8,
+}
+
+impl StackEffect {
+    const fn new(pops: u8, pushes: u8) -> Self {
+        Self { pops, pushes }
+    }
+
+    /// The number of operands popped from the value stack.
+    #[inline]
+    pub const fn pops(&self) -> u8 {
+        self.pops
+    }
+
+    /// The number of operands pushed onto the value stack.
+    #[inline]
+    pub const fn pushes(&self) -> u8 {
+        self.pushes
+    }
+}
+
+impl<I: Input> Instruction<'_, I> {
+    /// Gets the [`StackEffect`] of the [`Instruction`].
+    ///
+    /// Returns `None` for instructions whose effect on the value stack depends on types defined
+    /// elsewhere in the module, such as [**call**](Instruction::Call) or
+    /// [**block**](Instruction::Block), or that make the stack polymorphic, such as
+    /// [**br**](Instruction::Br) and [**unreachable**](Instruction::Unreachable).
+    pub const fn stack_effect(&self) -> Option<StackEffect> {
+        match self {
+            Self::Nop { .. } | Self::DataDrop { .. } | Self::ElemDrop { .. } => {
+                Some(StackEffect::new(0, 0))
+            }
+            Self::LocalGet { .. }
+            | Self::GlobalGet { .. }
+            | Self::MemorySize { .. }
+            | Self::I32Const { .. }
+            | Self::I64Const { .. }
+            | Self::F32Const { .. }
+            | Self::F64Const { .. }
+            | Self::RefNull { .. }
+            | Self::RefFunc { .. }
+            | Self::TableSize { .. } => Some(StackEffect::new(0, 1)),
+            #[cfg(feature = "simd")]
+            Self::V128Const { .. } => Some(StackEffect::new(0, 1)),
+            #[cfg(feature = "function-references")]
+            Self::RefAsNonNull { .. } => Some(StackEffect::new(1, 1)),
+            Self::Drop { .. } | Self::LocalSet { .. } | Self::GlobalSet { .. } => {
+                Some(StackEffect::new(1, 0))
+            }
+            Self::LocalTee { .. }
+            | Self::I32Load { .. }
+            | Self::I64Load { .. }
+            | Self::F32Load { .. }
+            | Self::F64Load { .. }
+            | Self::I32Load8S { .. }
+            | Self::I32Load8U { .. }
+            | Self::I32Load16S { .. }
+            | Self::I32Load16U { .. }
+            | Self::I64Load8S { .. }
+            | Self::I64Load8U { .. }
+            | Self::I64Load16S { .. }
+            | Self::I64Load16U { .. }
+            | Self::I64Load32S { .. }
+            | Self::I64Load32U { .. }
+            | Self::MemoryGrow { .. }
+            | Self::I32Eqz { .. }
+            | Self::I64Eqz { .. }
+            | Self::I32Clz { .. }
+            | Self::I32Ctz { .. }
+            | Self::I32Popcnt { .. }
+            | Self::I64Clz { .. }
+            | Self::I64Ctz { .. }
+            | Self::I64Popcnt { .. }
+            | Self::F32Abs { .. }
+            | Self::F32Neg { .. }
+            | Self::F32Ceil { .. }
+            | Self::F32Floor { .. }
+            | Self::F32Trunc { .. }
+            | Self::F32Nearest { .. }
+            | Self::F32Sqrt { .. }
+            | Self::F64Abs { .. }
+            | Self::F64Neg { .. }
+            | Self::F64Ceil { .. }
+            | Self::F64Floor { .. }
+            | Self::F64Trunc { .. }
+            | Self::F64Nearest { .. }
+            | Self::F64Sqrt { .. }
+            | Self::I32WrapI64 { .. }
+            | Self::I32TruncF32S { .. }
+            | Self::I32TruncF32U { .. }
+            | Self::I32TruncF64S { .. }
+            | Self::I32TruncF64U { .. }
+            | Self::I64ExtendI32S { .. }
+            | Self::I64ExtendI32U { .. }
+            | Self::I64TruncF32S { .. }
+            | Self::I64TruncF32U { .. }
+            | Self::I64TruncF64S { .. }
+            | Self::I64TruncF64U { .. }
+            | Self::F32ConvertI32S { .. }
+            | Self::F32ConvertI32U { .. }
+            | Self::F32ConvertI64S { .. }
+            | Self::F32ConvertI64U { .. }
+            | Self::F32DemoteF64 { .. }
+            | Self::F64ConvertI32S { .. }
+            | Self::F64ConvertI32U { .. }
+            | Self::F64ConvertI64S { .. }
+            | Self::F64ConvertI64U { .. }
+            | Self::F64PromoteF32 { .. }
+            | Self::I32ReinterpretF32 { .. }
+            | Self::I64ReinterpretF64 { .. }
+            | Self::F32ReinterpretI32 { .. }
+            | Self::F64ReinterpretI64 { .. }
+            | Self::I32TruncSatF32S { .. }
+            | Self::I32TruncSatF32U { .. }
+            | Self::I32TruncSatF64S { .. }
+            | Self::I32TruncSatF64U { .. }
+            | Self::I64TruncSatF32S { .. }
+            | Self::I64TruncSatF32U { .. }
+            | Self::I64TruncSatF64S { .. }
+            | Self::I64TruncSatF64U { .. }
+            | Self::I32Extend8S { .. }
+            | Self::I32Extend16S { .. }
+            | Self::I64Extend8S { .. }
+            | Self::I64Extend16S { .. }
+            | Self::I64Extend32S { .. }
+            | Self::RefIsNull { .. }
+            | Self::TableGet { .. } => Some(StackEffect::new(1, 1)),
+            #[cfg(feature = "simd")]
+            Self::V128Load { .. }
+            | Self::V128Load8x8S { .. }
+            | Self::V128Load8x8U { .. }
+            | Self::V128Load16x4S { .. }
+            | Self::V128Load16x4U { .. }
+            | Self::V128Load32x2S { .. }
+            | Self::V128Load32x2U { .. }
+            | Self::V128Load8Splat { .. }
+            | Self::V128Load16Splat { .. }
+            | Self::V128Load32Splat { .. }
+            | Self::V128Load64Splat { .. }
+            | Self::V128Load32Zero { .. }
+            | Self::V128Load64Zero { .. }
+            | Self::I8x16ExtractLaneS { .. }
+            | Self::I8x16ExtractLaneU { .. }
+            | Self::I16x8ExtractLaneS { .. }
+            | Self::I16x8ExtractLaneU { .. }
+            | Self::I32x4ExtractLane { .. }
+            | Self::I64x2ExtractLane { .. }
+            | Self::F32x4ExtractLane { .. }
+            | Self::F64x2ExtractLane { .. }
+            | Self::I8x16Splat { .. }
+            | Self::I16x8Splat { .. }
+            | Self::I32x4Splat { .. }
+            | Self::I64x2Splat { .. }
+            | Self::F32x4Splat { .. }
+            | Self::F64x2Splat { .. }
+            | Self::V128Not { .. }
+            | Self::V128AnyTrue { .. }
+            | Self::I8x16Abs { .. }
+            | Self::I8x16Neg { .. }
+            | Self::I8x16Popcnt { .. }
+            | Self::I8x16AllTrue { .. }
+            | Self::I8x16Bitmask { .. }
+            | Self::I16x8ExtaddPairwiseI8x16S { .. }
+            | Self::I16x8ExtaddPairwiseI8x16U { .. }
+            | Self::I16x8Abs { .. }
+            | Self::I16x8Neg { .. }
+            | Self::I16x8AllTrue { .. }
+            | Self::I16x8Bitmask { .. }
+            | Self::I16x8ExtendLowI8x16S { .. }
+            | Self::I16x8ExtendHighI8x16S { .. }
+            | Self::I16x8ExtendLowI8x16U { .. }
+            | Self::I16x8ExtendHighI8x16U { .. }
+            | Self::I32x4ExtaddPairwiseI16x8S { .. }
+            | Self::I32x4ExtaddPairwiseI16x8U { .. }
+            | Self::I32x4Abs { .. }
+            | Self::I32x4Neg { .. }
+            | Self::I32x4AllTrue { .. }
+            | Self::I32x4Bitmask { .. }
+            | Self::I32x4ExtendLowI16x8S { .. }
+            | Self::I32x4ExtendHighI16x8S { .. }
+            | Self::I32x4ExtendLowI16x8U { .. }
+            | Self::I32x4ExtendHighI16x8U { .. }
+            | Self::I64x2Abs { .. }
+            | Self::I64x2Neg { .. }
+            | Self::I64x2AllTrue { .. }
+            | Self::I64x2Bitmask { .. }
+            | Self::I64x2ExtendLowI32x4S { .. }
+            | Self::I64x2ExtendHighI32x4S { .. }
+            | Self::I64x2ExtendLowI32x4U { .. }
+            | Self::I64x2ExtendHighI32x4U { .. }
+            | Self::F32x4Ceil { .. }
+            | Self::F32x4Floor { .. }
+            | Self::F32x4Trunc { .. }
+            | Self::F32x4Nearest { .. }
+            | Self::F32x4Abs { .. }
+            | Self::F32x4Neg { .. }
+            | Self::F32x4Sqrt { .. }
+            | Self::F64x2Ceil { .. }
+            | Self::F64x2Floor { .. }
+            | Self::F64x2Trunc { .. }
+            | Self::F64x2Nearest { .. }
+            | Self::F64x2Abs { .. }
+            | Self::F64x2Neg { .. }
+            | Self::F64x2Sqrt { .. }
+            | Self::I32x4TruncSatF32x4S { .. }
+            | Self::I32x4TruncSatF32x4U { .. }
+            | Self::F32x4ConvertI32x4S { .. }
+            | Self::F32x4ConvertI32x4U { .. }
+            | Self::I32x4TruncSatF64x2SZero { .. }
+            | Self::I32x4TruncSatF64x2UZero { .. }
+            | Self::F64x2ConvertLowI32x4S { .. }
+            | Self::F64x2ConvertLowI32x4U { .. }
+            | Self::F32x4DemoteF64x2Zero { .. }
+            | Self::F64x2PromoteLowF32x4 { .. } => Some(StackEffect::new(1, 1)),
+            #[cfg(feature = "threads")]
+            Self::I32AtomicLoad { .. }
+            | Self::I64AtomicLoad { .. }
+            | Self::I32AtomicLoad8U { .. }
+            | Self::I32AtomicLoad16U { .. }
+            | Self::I64AtomicLoad8U { .. }
+            | Self::I64AtomicLoad16U { .. }
+            | Self::I64AtomicLoad32U { .. } => Some(StackEffect::new(1, 1)),
+            Self::I32Store { .. }
+            | Self::I64Store { .. }
+            | Self::F32Store { .. }
+            | Self::F64Store { .. }
+            | Self::I32Store8 { .. }
+            | Self::I32Store16 { .. }
+            | Self::I64Store8 { .. }
+            | Self::I64Store16 { .. }
+            | Self::I64Store32 { .. }
+            | Self::TableSet { .. } => Some(StackEffect::new(2, 0)),
+            #[cfg(feature = "simd")]
+            Self::V128Store { .. }
+            | Self::V128Store8Lane { .. }
+            | Self::V128Store16Lane { .. }
+            | Self::V128Store32Lane { .. }
+            | Self::V128Store64Lane { .. } => Some(StackEffect::new(2, 0)),
+            #[cfg(feature = "threads")]
+            Self::I32AtomicStore { .. }
+            | Self::I64AtomicStore { .. }
+            | Self::I32AtomicStore8U { .. }
+            | Self::I32AtomicStore16U { .. }
+            | Self::I64AtomicStore8U { .. }
+            | Self::I64AtomicStore16U { .. }
+            | Self::I64AtomicStore32U { .. } => Some(StackEffect::new(2, 0)),
+            Self::I32Eq { .. }
+            | Self::I32Ne { .. }
+            | Self::I32LtS { .. }
+            | Self::I32LtU { .. }
+            | Self::I32GtS { .. }
+            | Self::I32GtU { .. }
+            | Self::I32LeS { .. }
+            | Self::I32LeU { .. }
+            | Self::I32GeS { .. }
+            | Self::I32GeU { .. }
+            | Self::I64Eq { .. }
+            | Self::I64Ne { .. }
+            | Self::I64LtS { .. }
+            | Self::I64LtU { .. }
+            | Self::I64GtS { .. }
+            | Self::I64GtU { .. }
+            | Self::I64LeS { .. }
+            | Self::I64LeU { .. }
+            | Self::I64GeS { .. }
+            | Self::I64GeU { .. }
+            | Self::F32Eq { .. }
+            | Self::F32Ne { .. }
+            | Self::F32Lt { .. }
+            | Self::F32Gt { .. }
+            | Self::F32Le { .. }
+            | Self::F32Ge { .. }
+            | Self::F64Eq { .. }
+            | Self::F64Ne { .. }
+            | Self::F64Lt { .. }
+            | Self::F64Gt { .. }
+            | Self::F64Le { .. }
+            | Self::F64Ge { .. }
+            | Self::I32Add { .. }
+            | Self::I32Sub { .. }
+            | Self::I32Mul { .. }
+            | Self::I32DivS { .. }
+            | Self::I32DivU { .. }
+            | Self::I32RemS { .. }
+            | Self::I32RemU { .. }
+            | Self::I32And { .. }
+            | Self::I32Or { .. }
+            | Self::I32Xor { .. }
+            | Self::I32Shl { .. }
+            | Self::I32ShrS { .. }
+            | Self::I32ShrU { .. }
+            | Self::I32Rotl { .. }
+            | Self::I32Rotr { .. }
+            | Self::I64Add { .. }
+            | Self::I64Sub { .. }
+            | Self::I64Mul { .. }
+            | Self::I64DivS { .. }
+            | Self::I64DivU { .. }
+            | Self::I64RemS { .. }
+            | Self::I64RemU { .. }
+            | Self::I64And { .. }
+            | Self::I64Or { .. }
+            | Self::I64Xor { .. }
+            | Self::I64Shl { .. }
+            | Self::I64ShrS { .. }
+            | Self::I64ShrU { .. }
+            | Self::I64Rotl { .. }
+            | Self::I64Rotr { .. }
+            | Self::F32Add { .. }
+            | Self::F32Sub { .. }
+            | Self::F32Mul { .. }
+            | Self::F32Div { .. }
+            | Self::F32Min { .. }
+            | Self::F32Max { .. }
+            | Self::F32Copysign { .. }
+            | Self::F64Add { .. }
+            | Self::F64Sub { .. }
+            | Self::F64Mul { .. }
+            | Self::F64Div { .. }
+            | Self::F64Min { .. }
+            | Self::F64Max { .. }
+            | Self::F64Copysign { .. }
+            | Self::TableGrow { .. } => Some(StackEffect::new(2, 1)),
+            #[cfg(feature = "simd")]
+            Self::V128Load8Lane { .. }
+            | Self::V128Load16Lane { .. }
+            | Self::V128Load32Lane { .. }
+            | Self::V128Load64Lane { .. }
+            | Self::I8x16Shuffle { .. }
+            | Self::I8x16ReplaceLane { .. }
+            | Self::I16x8ReplaceLane { .. }
+            | Self::I32x4ReplaceLane { .. }
+            | Self::I64x2ReplaceLane { .. }
+            | Self::F32x4ReplaceLane { .. }
+            | Self::F64x2ReplaceLane { .. }
+            | Self::I8x16Swizzle { .. }
+            | Self::I8x16Eq { .. }
+            | Self::I8x16Ne { .. }
+            | Self::I8x16LtS { .. }
+            | Self::I8x16LtU { .. }
+            | Self::I8x16GtS { .. }
+            | Self::I8x16GtU { .. }
+            | Self::I8x16LeS { .. }
+            | Self::I8x16LeU { .. }
+            | Self::I8x16GeS { .. }
+            | Self::I8x16GeU { .. }
+            | Self::I16x8Eq { .. }
+            | Self::I16x8Ne { .. }
+            | Self::I16x8LtS { .. }
+            | Self::I16x8LtU { .. }
+            | Self::I16x8GtS { .. }
+            | Self::I16x8GtU { .. }
+            | Self::I16x8LeS { .. }
+            | Self::I16x8LeU { .. }
+            | Self::I16x8GeS { .. }
+            | Self::I16x8GeU { .. }
+            | Self::I32x4Eq { .. }
+            | Self::I32x4Ne { .. }
+            | Self::I32x4LtS { .. }
+            | Self::I32x4LtU { .. }
+            | Self::I32x4GtS { .. }
+            | Self::I32x4GtU { .. }
+            | Self::I32x4LeS { .. }
+            | Self::I32x4LeU { .. }
+            | Self::I32x4GeS { .. }
+            | Self::I32x4GeU { .. }
+            | Self::I64x2Eq { .. }
+            | Self::I64x2Ne { .. }
+            | Self::I64x2LtS { .. }
+            | Self::I64x2GtS { .. }
+            | Self::I64x2LeS { .. }
+            | Self::I64x2GeS { .. }
+            | Self::F32x4Eq { .. }
+            | Self::F32x4Ne { .. }
+            | Self::F32x4Lt { .. }
+            | Self::F32x4Gt { .. }
+            | Self::F32x4Le { .. }
+            | Self::F32x4Ge { .. }
+            | Self::F64x2Eq { .. }
+            | Self::F64x2Ne { .. }
+            | Self::F64x2Lt { .. }
+            | Self::F64x2Gt { .. }
+            | Self::F64x2Le { .. }
+            | Self::F64x2Ge { .. }
+            | Self::V128And { .. }
+            | Self::V128AndNot { .. }
+            | Self::V128Or { .. }
+            | Self::V128Xor { .. }
+            | Self::I8x16NarrowI16x8S { .. }
+            | Self::I8x16NarrowI16x8U { .. }
+            | Self::I8x16Shl { .. }
+            | Self::I8x16ShrS { .. }
+            | Self::I8x16ShrU { .. }
+            | Self::I8x16Add { .. }
+            | Self::I8x16AddSatS { .. }
+            | Self::I8x16AddSatU { .. }
+            | Self::I8x16Sub { .. }
+            | Self::I8x16SubSatS { .. }
+            | Self::I8x16SubSatU { .. }
+            | Self::I8x16MinS { .. }
+            | Self::I8x16MinU { .. }
+            | Self::I8x16MaxS { .. }
+            | Self::I8x16MaxU { .. }
+            | Self::I8x16AvgrU { .. }
+            | Self::I16x8Q15MulrSatS { .. }
+            | Self::I16x8NarrowI32x4S { .. }
+            | Self::I16x8NarrowI32x4U { .. }
+            | Self::I16x8Shl { .. }
+            | Self::I16x8ShrS { .. }
+            | Self::I16x8ShrU { .. }
+            | Self::I16x8Add { .. }
+            | Self::I16x8AddSatS { .. }
+            | Self::I16x8AddSatU { .. }
+            | Self::I16x8Sub { .. }
+            | Self::I16x8SubSatS { .. }
+            | Self::I16x8SubSatU { .. }
+            | Self::I16x8Mul { .. }
+            | Self::I16x8MinS { .. }
+            | Self::I16x8MinU { .. }
+            | Self::I16x8MaxS { .. }
+            | Self::I16x8MaxU { .. }
+            | Self::I16x8AvgrU { .. }
+            | Self::I16x8ExtmulLowI8x16S { .. }
+            | Self::I16x8ExtmulHighI8x16S { .. }
+            | Self::I16x8ExtmulLowI8x16U { .. }
+            | Self::I16x8ExtmulHighI8x16U { .. }
+            | Self::I32x4Shl { .. }
+            | Self::I32x4ShrS { .. }
+            | Self::I32x4ShrU { .. }
+            | Self::I32x4Add { .. }
+            | Self::I32x4Sub { .. }
+            | Self::I32x4Mul { .. }
+            | Self::I32x4MinS { .. }
+            | Self::I32x4MinU { .. }
+            | Self::I32x4MaxS { .. }
+            | Self::I32x4MaxU { .. }
+            | Self::I32x4DotI16x8S { .. }
+            | Self::I32x4ExtmulLowI16x8S { .. }
+            | Self::I32x4ExtmulHighI16x8S { .. }
+            | Self::I32x4ExtmulLowI16x8U { .. }
+            | Self::I32x4ExtmulHighI16x8U { .. }
+            | Self::I64x2Shl { .. }
+            | Self::I64x2ShrS { .. }
+            | Self::I64x2ShrU { .. }
+            | Self::I64x2Add { .. }
+            | Self::I64x2Sub { .. }
+            | Self::I64x2Mul { .. }
+            | Self::I64x2ExtmulLowI32x4S { .. }
+            | Self::I64x2ExtmulHighI32x4S { .. }
+            | Self::I64x2ExtmulLowI32x4U { .. }
+            | Self::I64x2ExtmulHighI32x4U { .. }
+            | Self::F32x4Add { .. }
+            | Self::F32x4Sub { .. }
+            | Self::F32x4Mul { .. }
+            | Self::F32x4Div { .. }
+            | Self::F32x4Min { .. }
+            | Self::F32x4Max { .. }
+            | Self::F32x4Pmin { .. }
+            | Self::F32x4Pmax { .. }
+            | Self::F64x2Add { .. }
+            | Self::F64x2Sub { .. }
+            | Self::F64x2Mul { .. }
+            | Self::F64x2Div { .. }
+            | Self::F64x2Min { .. }
+            | Self::F64x2Max { .. }
+            | Self::F64x2Pmin { .. }
+            | Self::F64x2Pmax { .. } => Some(StackEffect::new(2, 1)),
+            #[cfg(feature = "threads")]
+            Self::MemoryAtomicNotify { .. }
+            | Self::I32AtomicRmwAdd { .. }
+            | Self::I64AtomicRmwAdd { .. }
+            | Self::I32AtomicRmw8AddU { .. }
+            | Self::I32AtomicRmw16AddU { .. }
+            | Self::I64AtomicRmw8AddU { .. }
+            | Self::I64AtomicRmw16AddU { .. }
+            | Self::I64AtomicRmw32AddU { .. }
+            | Self::I32AtomicRmwSub { .. }
+            | Self::I64AtomicRmwSub { .. }
+            | Self::I32AtomicRmw8SubU { .. }
+            | Self::I32AtomicRmw16SubU { .. }
+            | Self::I64AtomicRmw8SubU { .. }
+            | Self::I64AtomicRmw16SubU { .. }
+            | Self::I64AtomicRmw32SubU { .. }
+            | Self::I32AtomicRmwAnd { .. }
+            | Self::I64AtomicRmwAnd { .. }
+            | Self::I32AtomicRmw8AndU { .. }
+            | Self::I32AtomicRmw16AndU { .. }
+            | Self::I64AtomicRmw8AndU { .. }
+            | Self::I64AtomicRmw16AndU { .. }
+            | Self::I64AtomicRmw32AndU { .. }
+            | Self::I32AtomicRmwOr { .. }
+            | Self::I64AtomicRmwOr { .. }
+            | Self::I32AtomicRmw8OrU { .. }
+            | Self::I32AtomicRmw16OrU { .. }
+            | Self::I64AtomicRmw8OrU { .. }
+            | Self::I64AtomicRmw16OrU { .. }
+            | Self::I64AtomicRmw32OrU { .. }
+            | Self::I32AtomicRmwXor { .. }
+            | Self::I64AtomicRmwXor { .. }
+            | Self::I32AtomicRmw8XorU { .. }
+            | Self::I32AtomicRmw16XorU { .. }
+            | Self::I64AtomicRmw8XorU { .. }
+            | Self::I64AtomicRmw16XorU { .. }
+            | Self::I64AtomicRmw32XorU { .. }
+            | Self::I32AtomicRmwXchg { .. }
+            | Self::I64AtomicRmwXchg { .. }
+            | Self::I32AtomicRmw8XchgU { .. }
+            | Self::I32AtomicRmw16XchgU { .. }
+            | Self::I64AtomicRmw8XchgU { .. }
+            | Self::I64AtomicRmw16XchgU { .. }
+            | Self::I64AtomicRmw32XchgU { .. } => Some(StackEffect::new(2, 1)),
+            Self::TableFill { .. }
+            | Self::MemoryCopy { .. }
+            | Self::TableCopy { .. }
+            | Self::MemoryFill { .. }
+            | Self::MemoryInit { .. }
+            | Self::TableInit { .. } => Some(StackEffect::new(3, 0)),
+            Self::Select { .. } => Some(StackEffect::new(3, 1)),
+            #[cfg(feature = "simd")]
+            Self::V128Bitselect { .. } => Some(StackEffect::new(3, 1)),
+            #[cfg(feature = "threads")]
+            Self::MemoryAtomicWait32 { .. }
+            | Self::MemoryAtomicWait64 { .. }
+            | Self::I32AtomicRmwCmpxchg { .. }
+            | Self::I64AtomicRmwCmpxchg { .. }
+            | Self::I32AtomicRmw8CmpxchgU { .. }
+            | Self::I32AtomicRmw16CmpxchgU { .. }
+            | Self::I64AtomicRmw8CmpxchgU { .. }
+            | Self::I64AtomicRmw16CmpxchgU { .. }
+            | Self::I64AtomicRmw32CmpxchgU { .. } => Some(StackEffect::new(3, 1)),
+            Self::Unreachable { .. }
+            | Self::Block { .. }
+            | Self::Loop { .. }
+            | Self::If { .. }
+            | Self::Br { .. }
+            | Self::BrIf { .. }
+            | Self::BrTable { .. }
+            | Self::Return { .. }
+            | Self::Call { .. }
+            | Self::CallIndirect { .. }
+            | Self::Else { .. }
+            | Self::End { .. } => None,
+            #[cfg(feature = "tail-call")]
+            Self::ReturnCall { .. } | Self::ReturnCallIndirect { .. } => None,
+            #[cfg(feature = "function-references")]
+            Self::CallRef { .. }
+            | Self::ReturnCallRef { .. }
+            | Self::BrOnNull { .. }
+            | Self::BrOnNonNull { .. } => None,
+            #[cfg(feature = "exceptions")]
+            Self::Try { .. }
+            | Self::Catch { .. }
+            | Self::CatchAll { .. }
+            | Self::Throw { .. }
+            | Self::Rethrow { .. }
+            | Self::Delegate { .. }
+            | Self::TryTable { .. }
+            | Self::ThrowRef { .. } => None,
+        }
+    }
+}
@@ -0,0 +1,1697 @@
+use crate::{
+    component::LocalTypes,
+    index::{
+        DataIdx, ElemIdx, FuncIdx, GlobalIdx, LabelIdx, LocalIdx, MemIdx, TableIdx, TagIdx, TypeIdx,
+    },
+    input::Input,
+    instruction_set::Instruction,
+    types::{
+        BlockType, FuncType, GlobalMutability, GlobalType, HeapType, IdxType, MemType, RefType,
+        TableType, ValType,
+    },
+    validate::{ErrorKind, ValidationError},
+};
+use alloc::vec::Vec;
+
+#[cfg(feature = "exceptions")]
+use crate::instruction_set::CatchClause;
+
+type Checked<T> = Result<T, ErrorKind>;
+
+/// Provides the types of the module-level definitions that instructions can refer to, such as
+/// functions, tables, and globals.
+///
+/// Every method has a default implementation that indicates the definition does not exist, so
+/// only the methods for definitions an embedder keeps track of need to be implemented.
+pub trait TypeContext {
+    /// Gets the function type with the given index.
+    fn func_type(&self, index: TypeIdx) -> Option<&FuncType> {
+        let _ = index;
+        None
+    }
+
+    /// Gets the index of the type of the given function.
+    fn function(&self, index: FuncIdx) -> Option<TypeIdx> {
+        let _ = index;
+        None
+    }
+
+    /// Gets the type of the given table.
+    fn table(&self, index: TableIdx) -> Option<TableType> {
+        let _ = index;
+        None
+    }
+
+    /// Gets the type of the given memory.
+    fn memory(&self, index: MemIdx) -> Option<MemType> {
+        let _ = index;
+        None
+    }
+
+    /// Gets the type of the given global.
+    fn global(&self, index: GlobalIdx) -> Option<GlobalType> {
+        let _ = index;
+        None
+    }
+
+    /// Gets the index of the function type of the given exception tag.
+    fn tag(&self, index: TagIdx) -> Option<TypeIdx> {
+        let _ = index;
+        None
+    }
+
+    /// Gets the type of the references in the given element segment.
+    fn element(&self, index: ElemIdx) -> Option<RefType> {
+        let _ = index;
+        None
+    }
+
+    /// Gets the number of data segments specified by the *data count* section, or `None` if the
+    /// module does not have one.
+    fn data_count(&self) -> Option<u32> {
+        None
+    }
+
+    /// Returns `true` if the given function can be referred to by a **ref.func** instruction in a
+    /// function body, which requires that it be referred to outside of any function bodies.
+    fn is_declared_reference(&self, index: FuncIdx) -> bool {
+        let _ = index;
+        false
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum FrameKind {
+    Function,
+    Block,
+    Loop,
+    If,
+    Else,
+    #[cfg(feature = "exceptions")]
+    Try,
+    #[cfg(feature = "exceptions")]
+    Catch,
+    #[cfg(feature = "exceptions")]
+    CatchAll,
+}
+
+/// A control frame, corresponding to a block or the function body itself.
+#[derive(Clone, Copy, Debug)]
+struct Frame<'a> {
+    kind: FrameKind,
+    parameters: Types<'a>,
+    results: Types<'a>,
+    /// The height of the operand stack when the block was entered.
+    height: usize,
+    /// Set after an unconditional branch, which makes the operand stack polymorphic.
+    unreachable: bool,
+}
+
+impl<'a> Frame<'a> {
+    /// Gets the types of the operands that a branch to this frame's label expects.
+    fn label_types(&self) -> Types<'a> {
+        if self.kind == FrameKind::Loop {
+            self.parameters
+        } else {
+            self.results
+        }
+    }
+}
+
+/// The parameter or result types of a [`Frame`], which are either those of a function type, or
+/// the single result of a block with an inline type.
+#[derive(Clone, Copy, Debug)]
+enum Types<'a> {
+    Borrowed(&'a [ValType]),
+    Single([ValType; 1]),
+}
+
+impl core::ops::Deref for Types<'_> {
+    type Target = [ValType];
+
+    fn deref(&self) -> &[ValType] {
+        match self {
+            Self::Borrowed(types) => types,
+            Self::Single(single) => single,
+        }
+    }
+}
+
+fn index_type(index_type: IdxType) -> ValType {
+    match index_type {
+        IdxType::I32 => ValType::I32,
+        IdxType::I64 => ValType::I64,
+    }
+}
+
+fn is_reference(value_type: ValType) -> bool {
+    value_type.try_to_ref_type().is_some()
+}
+
+/// Gets the type of a nullable reference to a function with the given type.
+#[cfg(feature = "function-references")]
+fn typed_reference(signature: TypeIdx) -> ValType {
+    ValType::from(RefType::new(true, HeapType::Index(signature)))
+}
+
+/// Gets the non-nullable version of the given reference type.
+#[cfg(feature = "function-references")]
+fn non_nullable(reference: ValType) -> ValType {
+    reference
+        .try_to_ref_type()
+        .map_or(reference, |reference| reference.as_non_nullable().into())
+}
+
+/// Returns `true` if a value of the `actual` type can be used where a value of the `expected` type
+/// is required, since typed references are subtypes of other reference types.
+pub(crate) fn is_subtype(actual: ValType, expected: ValType) -> bool {
+    match (actual.try_to_ref_type(), expected.try_to_ref_type()) {
+        (Some(actual), Some(expected)) => {
+            (!actual.is_nullable() || expected.is_nullable())
+                && match (actual.heap_type(), expected.heap_type()) {
+                    (HeapType::Index(_), HeapType::Func) => true,
+                    (actual, expected) => actual == expected,
+                }
+        }
+        _ => actual == expected,
+    }
+}
+
+/// Checks the types of the operands of each [`Instruction`] in a function body, following the
+/// [validation algorithm](https://webassembly.github.io/spec/core/appendix/algorithm.html) in the
+/// WebAssembly specification.
+///
+/// A [`TypeChecker`] is fed each instruction in order, allowing function bodies to be type
+/// checked while they are being parsed for some other purpose. Use
+/// [`validate_module`](crate::validate::validate_module) to validate an entire module.
+///
+/// # Example
+///
+/// ```
+/// use wasmiter::component::LocalTypes;
+/// use wasmiter::instruction_set::{self, TypeChecker, TypeContext};
+/// use wasmiter::types::{FuncType, ValType};
+///
+/// struct NoDefinitions;
+///
+/// impl TypeContext for NoDefinitions {}
+///
+/// let signature = FuncType::new([], [ValType::I64]);
+/// let mut checker = TypeChecker::new(&NoDefinitions, &signature, LocalTypes::default());
+///
+/// // i32.const 1, end
+/// let mut body = instruction_set::parse_expression(0, [0x41u8, 1, 0x0B].as_slice());
+/// let offset = body.offset();
+/// body.next(|instruction| checker.instruction(offset, instruction)).transpose()?;
+/// let offset = body.offset();
+/// let error = body
+///     .next(|instruction| checker.instruction(offset, instruction))
+///     .transpose()
+///     .unwrap_err();
+///
+/// assert_eq!(error.offset(), Some(2));
+/// assert_eq!(error.expected_type(), Some(ValType::I64));
+/// assert_eq!(error.actual_type(), Some(ValType::I32));
+/// # Ok::<(), wasmiter::validate::ValidationError>(())
+/// ```
+pub struct TypeChecker<'a, C: ?Sized> {
+    context: &'a C,
+    locals: LocalTypes,
+    /// Operands whose types are not known are represented by `None`.
+    operands: Vec<Option<ValType>>,
+    frames: Vec<Frame<'a>>,
+    /// If type checking a constant expression, the number of globals that can be referred to.
+    constant: Option<u32>,
+}
+
+impl<C: ?Sized> core::fmt::Debug for TypeChecker<'_, C> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("TypeChecker")
+            .field("locals", &self.locals)
+            .field("operands", &self.operands)
+            .field("frames", &self.frames)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, C: TypeContext + ?Sized> TypeChecker<'a, C> {
+    /// Creates a new [`TypeChecker`] for the body of a function with the given `signature`.
+    ///
+    /// The `locals` must include the types of the function's parameters, followed by the
+    /// types of its local variables.
+    pub fn new(context: &'a C, signature: &'a FuncType, locals: LocalTypes) -> Self {
+        Self::with_frame(
+            context,
+            Types::Borrowed(signature.parameters()),
+            Types::Borrowed(signature.results()),
+            locals,
+            None,
+        )
+    }
+
+    /// Creates a type checker for a
+    /// [constant expression](https://webassembly.github.io/spec/core/valid/instructions.html#constant-expressions)
+    /// that produces a value of the given type, and can only refer to the first `globals`.
+    pub(crate) fn constant(context: &'a C, result_type: ValType, globals: u32) -> Self {
+        Self::with_frame(
+            context,
+            Types::Borrowed(&[]),
+            Types::Single([result_type]),
+            LocalTypes::default(),
+            Some(globals),
+        )
+    }
+
+    fn with_frame(
+        context: &'a C,
+        parameters: Types<'a>,
+        results: Types<'a>,
+        locals: LocalTypes,
+        constant: Option<u32>,
+    ) -> Self {
+        Self {
+            context,
+            locals,
+            operands: Vec::new(),
+            frames: alloc::vec![Frame {
+                kind: FrameKind::Function,
+                parameters,
+                results,
+                height: 0,
+                unreachable: false,
+            }],
+            constant,
+        }
+    }
+
+    /// Gets the types of the values currently on the operand stack, with the top of the stack
+    /// last. Once the function body is [finished](TypeChecker::is_finished), this contains the
+    /// types of the function's results.
+    ///
+    /// Operands whose types are not known, which are produced after an unconditional branch, are
+    /// represented by `None`.
+    #[inline]
+    pub fn operands(&self) -> &[Option<ValType>] {
+        &self.operands
+    }
+
+    /// Returns `true` if the final **end** instruction of the function body has been checked.
+    #[inline]
+    pub fn is_finished(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    fn func_type(&self, index: TypeIdx) -> Checked<&'a FuncType> {
+        self.context
+            .func_type(index)
+            .ok_or(ErrorKind::UnknownIndex {
+                space: "type",
+                index: index.to_u32(),
+            })
+    }
+
+    fn block_types(&self, block_type: BlockType) -> Checked<(Types<'a>, Types<'a>)> {
+        Ok(match block_type {
+            BlockType::Empty => (Types::Borrowed(&[]), Types::Borrowed(&[])),
+            BlockType::Inline(result) => {
+                self.check_val_type(result)?;
+                (Types::Borrowed(&[]), Types::Single([result]))
+            }
+            BlockType::Index(index) => {
+                let ty = self.func_type(index)?;
+                (
+                    Types::Borrowed(ty.parameters()),
+                    Types::Borrowed(ty.results()),
+                )
+            }
+        })
+    }
+
+    /// Checks that a typed reference refers to a type that exists.
+    fn check_val_type(&self, value_type: ValType) -> Checked<()> {
+        if let Some(HeapType::Index(index)) = value_type.try_to_ref_type().map(RefType::heap_type) {
+            self.func_type(index)?;
+        }
+
+        Ok(())
+    }
+
+    fn current_frame(&self) -> Checked<&Frame<'a>> {
+        self.frames
+            .last()
+            .ok_or(ErrorKind::Invalid("instruction after end of function body"))
+    }
+
+    fn push(&mut self, value_type: ValType) {
+        self.operands.push(Some(value_type));
+    }
+
+    fn push_all(&mut self, types: &[ValType]) {
+        self.operands.extend(types.iter().copied().map(Some));
+    }
+
+    fn pop_operand(&mut self, expected: Option<ValType>) -> Checked<Option<ValType>> {
+        let frame = self.current_frame()?;
+        if self.operands.len() <= frame.height {
+            return if frame.unreachable {
+                Ok(None)
+            } else {
+                Err(ErrorKind::TypeMismatch {
+                    expected,
+                    actual: None,
+                })
+            };
+        }
+
+        Ok(self.operands.pop().flatten())
+    }
+
+    /// Pops an operand of any type.
+    fn pop_any(&mut self) -> Checked<Option<ValType>> {
+        self.pop_operand(None)
+    }
+
+    fn pop(&mut self, expected: ValType) -> Checked<()> {
+        match self.pop_operand(Some(expected))? {
+            Some(actual) if !is_subtype(actual, expected) => Err(ErrorKind::TypeMismatch {
+                expected: Some(expected),
+                actual: Some(actual),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Pops an operand of any reference type.
+    fn pop_reference(&mut self) -> Checked<Option<ValType>> {
+        match self.pop_operand(Some(ValType::FuncRef))? {
+            Some(actual) if !is_reference(actual) => Err(ErrorKind::TypeMismatch {
+                expected: Some(ValType::FuncRef),
+                actual: Some(actual),
+            }),
+            popped => Ok(popped),
+        }
+    }
+
+    fn pop_all(&mut self, types: &[ValType]) -> Checked<()> {
+        types.iter().rev().try_for_each(|ty| self.pop(*ty))
+    }
+
+    fn enter(&mut self, kind: FrameKind, parameters: Types<'a>, results: Types<'a>) {
+        self.frames.push(Frame {
+            kind,
+            parameters,
+            results,
+            height: self.operands.len(),
+            unreachable: false,
+        });
+        self.push_all(&parameters);
+    }
+
+    /// Enters a block, moving its parameters from the operand stack into the new frame.
+    fn block(&mut self, kind: FrameKind, block_type: BlockType) -> Checked<()> {
+        let (parameters, results) = self.block_types(block_type)?;
+        self.pop_all(&parameters)?;
+        self.enter(kind, parameters, results);
+        Ok(())
+    }
+
+    fn exit(&mut self) -> Checked<Frame<'a>> {
+        let results = self.current_frame()?.results;
+        self.pop_all(&results)?;
+
+        let frame = self
+            .frames
+            .pop()
+            .ok_or(ErrorKind::Invalid("instruction after end of function body"))?;
+
+        if self.operands.len() != frame.height {
+            return Err(ErrorKind::UnexpectedOperands(
+                self.operands.len() - frame.height,
+            ));
+        }
+
+        Ok(frame)
+    }
+
+    fn set_unreachable(&mut self) -> Checked<()> {
+        let frame = self
+            .frames
+            .last_mut()
+            .ok_or(ErrorKind::Invalid("instruction after end of function body"))?;
+        self.operands.truncate(frame.height);
+        frame.unreachable = true;
+        Ok(())
+    }
+
+    fn label(&self, label: LabelIdx) -> Checked<&Frame<'a>> {
+        let depth = label.to_usize();
+        if depth < self.frames.len() {
+            Ok(&self.frames[self.frames.len() - 1 - depth])
+        } else {
+            Err(ErrorKind::UnknownIndex {
+                space: "label",
+                index: label.to_u32(),
+            })
+        }
+    }
+
+    fn label_types(&self, label: LabelIdx) -> Checked<Types<'a>> {
+        self.label(label).map(Frame::label_types)
+    }
+
+    fn function_results(&self) -> Types<'a> {
+        self.frames
+            .first()
+            .map_or(Types::Borrowed(&[]), |frame| frame.results)
+    }
+
+    fn memory_index_type(&self, memory: MemIdx) -> Checked<ValType> {
+        self.context
+            .memory(memory)
+            .map(|memory| index_type(memory.index_type()))
+            .ok_or(ErrorKind::UnknownIndex {
+                space: "memory",
+                index: memory.to_u32(),
+            })
+    }
+
+    fn table(&self, table: TableIdx) -> Checked<(ValType, ValType)> {
+        self.context
+            .table(table)
+            .map(|table| {
+                (
+                    index_type(table.limits().index_type()),
+                    ValType::from(table.element_type()),
+                )
+            })
+            .ok_or(ErrorKind::UnknownIndex {
+                space: "table",
+                index: table.to_u32(),
+            })
+    }
+
+    fn function_type(&self, function: FuncIdx) -> Checked<TypeIdx> {
+        self.context
+            .function(function)
+            .ok_or(ErrorKind::UnknownIndex {
+                space: "function",
+                index: function.to_u32(),
+            })
+    }
+
+    fn global_type(&self, global: GlobalIdx) -> Checked<GlobalType> {
+        self.context.global(global).ok_or(ErrorKind::UnknownIndex {
+            space: "global",
+            index: global.to_u32(),
+        })
+    }
+
+    fn check_data_index(&self, data: DataIdx) -> Checked<()> {
+        match self.context.data_count() {
+            None => Err(ErrorKind::Invalid("data count section is required")),
+            Some(count) if data.to_u32() >= count => Err(ErrorKind::UnknownIndex {
+                space: "data segment",
+                index: data.to_u32(),
+            }),
+            Some(_) => Ok(()),
+        }
+    }
+
+    fn element_type(&self, element: ElemIdx) -> Checked<ValType> {
+        self.context
+            .element(element)
+            .map(ValType::from)
+            .ok_or(ErrorKind::UnknownIndex {
+                space: "element segment",
+                index: element.to_u32(),
+            })
+    }
+
+    /// Pops the parameters and pushes the results of a call to a function with the given type.
+    fn call(&mut self, signature: TypeIdx) -> Checked<()> {
+        let ty = self.func_type(signature)?;
+        self.pop_all(ty.parameters())?;
+        self.push_all(ty.results());
+        Ok(())
+    }
+
+    #[cfg(any(feature = "tail-call", feature = "function-references"))]
+    fn return_call(&mut self, signature: TypeIdx) -> Checked<()> {
+        let ty = self.func_type(signature)?;
+        if ty.results() != &*self.function_results() {
+            return Err(ErrorKind::Invalid(
+                "tail call results do not match the results of the caller",
+            ));
+        }
+
+        self.pop_all(ty.parameters())?;
+        self.set_unreachable()
+    }
+
+    fn check_memarg<I: Input>(&self, instruction: &Instruction<'_, I>) -> Checked<ValType> {
+        let memarg = match instruction.memarg() {
+            Some(memarg) => memarg,
+            None => return Err(ErrorKind::Invalid("expected memory instruction")),
+        };
+
+        let address = self.memory_index_type(memarg.memory())?;
+        if let Some(natural) = instruction.natural_alignment() {
+            let (actual, natural) = (memarg.align().to_power(), natural.to_power());
+            #[cfg(feature = "threads")]
+            if instruction.is_from_threads() && actual != natural {
+                return Err(ErrorKind::Invalid(
+                    "alignment of atomic instruction must be equal to its natural alignment",
+                ));
+            }
+
+            if actual > natural {
+                return Err(ErrorKind::BadAlignment { actual, natural });
+            }
+        }
+
+        if address == ValType::I32 && memarg.offset() > u64::from(u32::MAX) {
+            return Err(ErrorKind::Invalid("offset out of range for 32-bit memory"));
+        }
+
+        Ok(address)
+    }
+
+    /// Checks the operands of the given [`Instruction`] located at the given `offset`, and
+    /// updates the operand stack with its results.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the instruction is not valid, such as when its operands are of the
+    /// wrong type, or it refers to a definition that does not exist in the [`TypeContext`].
+    pub fn instruction<I: Input>(
+        &mut self,
+        offset: u64,
+        instruction: &mut Instruction<'_, I>,
+    ) -> Result<(), ValidationError> {
+        self.check(instruction)
+            .map_err(|kind| ValidationError::new(kind, offset))
+    }
+
+    fn check<I: Input>(&mut self, instruction: &mut Instruction<'_, I>) -> Checked<()> {
+        if self.constant.is_some() && !self.constant_instruction(instruction)? {
+            return Err(ErrorKind::Invalid("constant expression required"));
+        }
+
+        if let Some((parameters, results)) = numeric_signature(instruction) {
+            self.pop_all(parameters)?;
+            self.push_all(results);
+            return Ok(());
+        }
+
+        if instruction.memarg().is_some() {
+            let address = self.check_memarg(instruction)?;
+            return self.memory_instruction(instruction, address);
+        }
+
+        match instruction {
+            Instruction::Nop => (),
+            Instruction::Unreachable => self.set_unreachable()?,
+            Instruction::Block(block_type) => self.block(FrameKind::Block, *block_type)?,
+            Instruction::Loop(block_type) => self.block(FrameKind::Loop, *block_type)?,
+            Instruction::If(block_type) => {
+                self.pop(ValType::I32)?;
+                self.block(FrameKind::If, *block_type)?;
+            }
+            Instruction::Else => {
+                let frame = self.exit()?;
+                if frame.kind != FrameKind::If {
+                    return Err(ErrorKind::Invalid("else must be within an if block"));
+                }
+
+                self.enter(FrameKind::Else, frame.parameters, frame.results);
+            }
+            Instruction::End => {
+                let frame = self.exit()?;
+                if frame.kind == FrameKind::If && *frame.parameters != *frame.results {
+                    return Err(ErrorKind::Invalid(
+                        "if block without else must have matching parameter and result types",
+                    ));
+                }
+
+                self.push_all(&frame.results);
+            }
+            Instruction::Br(label) => {
+                self.pop_all(&self.label_types(*label)?)?;
+                self.set_unreachable()?;
+            }
+            Instruction::BrIf(label) => {
+                self.pop(ValType::I32)?;
+                let types = self.label_types(*label)?;
+                self.pop_all(&types)?;
+                self.push_all(&types);
+            }
+            Instruction::BrTable(labels) => {
+                self.pop(ValType::I32)?;
+
+                let mut arity = None;
+                let mut default = None;
+                for result in labels {
+                    let label = result?;
+                    let types = self.label_types(label)?;
+                    if *arity.get_or_insert(types.len()) != types.len() {
+                        return Err(ErrorKind::Invalid(
+                            "br_table labels must have the same number of types",
+                        ));
+                    }
+
+                    // Check the types without removing them from the stack
+                    let height = self.operands.len();
+                    self.pop_all(&types)?;
+                    let popped = height - self.operands.len();
+                    self.operands
+                        .extend(types[types.len() - popped..].iter().copied().map(Some));
+
+                    default = Some(types);
+                }
+
+                let default = default.ok_or(ErrorKind::Invalid("br_table has no labels"))?;
+                self.pop_all(&default)?;
+                self.set_unreachable()?;
+            }
+            Instruction::Return => {
+                self.pop_all(&self.function_results())?;
+                self.set_unreachable()?;
+            }
+            Instruction::Call(function) => {
+                let signature = self.function_type(*function)?;
+                self.call(signature)?;
+            }
+            Instruction::CallIndirect(signature, table) => {
+                let (index, element) = self.table(*table)?;
+                if element != ValType::FuncRef {
+                    return Err(ErrorKind::Invalid("call_indirect requires a funcref table"));
+                }
+
+                self.pop(index)?;
+                self.call(*signature)?;
+            }
+            #[cfg(feature = "tail-call")]
+            Instruction::ReturnCall(function) => {
+                let signature = self.function_type(*function)?;
+                self.return_call(signature)?;
+            }
+            #[cfg(feature = "tail-call")]
+            Instruction::ReturnCallIndirect(signature, table) => {
+                let (index, element) = self.table(*table)?;
+                if element != ValType::FuncRef {
+                    return Err(ErrorKind::Invalid(
+                        "return_call_indirect requires a funcref table",
+                    ));
+                }
+
+                self.pop(index)?;
+                self.return_call(*signature)?;
+            }
+            #[cfg(feature = "function-references")]
+            Instruction::CallRef(signature) => {
+                self.pop(typed_reference(*signature))?;
+                self.call(*signature)?;
+            }
+            #[cfg(feature = "function-references")]
+            Instruction::ReturnCallRef(signature) => {
+                self.pop(typed_reference(*signature))?;
+                self.return_call(*signature)?;
+            }
+            #[cfg(feature = "function-references")]
+            Instruction::RefAsNonNull => {
+                let reference = self.pop_reference()?;
+                self.operands.push(reference.map(non_nullable));
+            }
+            #[cfg(feature = "function-references")]
+            Instruction::BrOnNull(label) => {
+                let reference = self.pop_reference()?;
+                let types = self.label_types(*label)?;
+                self.pop_all(&types)?;
+                self.push_all(&types);
+                self.operands.push(reference.map(non_nullable));
+            }
+            #[cfg(feature = "function-references")]
+            Instruction::BrOnNonNull(label) => {
+                let types = self.label_types(*label)?;
+                let (last, rest) =
+                    match types.split_last().filter(|(t, _)| is_reference(**t)) {
+                        Some(split) => split,
+                        None => return Err(ErrorKind::Invalid(
+                            "br_on_non_null target must have a reference type as its last result",
+                        )),
+                    };
+
+                match self.pop_reference()? {
+                    Some(reference) if !is_subtype(non_nullable(reference), *last) => {
+                        return Err(ErrorKind::TypeMismatch {
+                            expected: Some(*last),
+                            actual: Some(reference),
+                        });
+                    }
+                    _ => (),
+                }
+
+                self.pop_all(rest)?;
+                self.push_all(rest);
+            }
+            #[cfg(feature = "exceptions")]
+            Instruction::Try(block_type) => self.block(FrameKind::Try, *block_type)?,
+            #[cfg(feature = "exceptions")]
+            Instruction::Catch(tag) => {
+                let signature = self.tag_type(*tag)?;
+                let frame = self.exit()?;
+                if !matches!(frame.kind, FrameKind::Try | FrameKind::Catch) {
+                    return Err(ErrorKind::Invalid("catch must be within a try block"));
+                }
+
+                self.enter(FrameKind::Catch, Types::Borrowed(&[]), frame.results);
+                self.push_all(self.func_type(signature)?.parameters());
+            }
+            #[cfg(feature = "exceptions")]
+            Instruction::CatchAll => {
+                let frame = self.exit()?;
+                if !matches!(frame.kind, FrameKind::Try | FrameKind::Catch) {
+                    return Err(ErrorKind::Invalid("catch_all must be within a try block"));
+                }
+
+                self.enter(FrameKind::CatchAll, Types::Borrowed(&[]), frame.results);
+            }
+            #[cfg(feature = "exceptions")]
+            Instruction::Delegate(label) => {
+                let frame = self.exit()?;
+                if frame.kind != FrameKind::Try {
+                    return Err(ErrorKind::Invalid("delegate must end a try block"));
+                }
+
+                self.label(*label)?;
+                self.push_all(&frame.results);
+            }
+            #[cfg(feature = "exceptions")]
+            Instruction::Throw(tag) => {
+                let signature = self.tag_type(*tag)?;
+                self.pop_all(self.func_type(signature)?.parameters())?;
+                self.set_unreachable()?;
+            }
+            #[cfg(feature = "exceptions")]
+            Instruction::TryTable(block_type, clauses) => {
+                // Labels of catch clauses are resolved outside of the try_table block
+                for result in clauses.iter()? {
+                    self.catch_clause(result?)?;
+                }
+
+                self.block(FrameKind::Block, *block_type)?;
+            }
+            #[cfg(feature = "exceptions")]
+            Instruction::ThrowRef => {
+                self.pop(ValType::ExnRef)?;
+                self.set_unreachable()?;
+            }
+            #[cfg(feature = "exceptions")]
+            Instruction::Rethrow(label) => {
+                if !matches!(
+                    self.label(*label)?.kind,
+                    FrameKind::Catch | FrameKind::CatchAll
+                ) {
+                    return Err(ErrorKind::Invalid("rethrow target must be a catch block"));
+                }
+
+                self.set_unreachable()?;
+            }
+            Instruction::Drop => {
+                self.pop_any()?;
+            }
+            Instruction::Select(types) => {
+                let selected = match types.next().transpose()? {
+                    Some(selected) => {
+                        if types.next().is_some() {
+                            return Err(ErrorKind::Invalid(
+                                "select must not have more than one result type",
+                            ));
+                        }
+
+                        self.check_val_type(selected)?;
+                        self.pop(ValType::I32)?;
+                        self.pop(selected)?;
+                        self.pop(selected)?;
+                        Some(selected)
+                    }
+                    None => {
+                        self.pop(ValType::I32)?;
+                        let first = self.pop_any()?;
+                        let second = self.pop_any()?;
+                        if let Some(reference) = first.or(second).filter(|t| is_reference(*t)) {
+                            return Err(ErrorKind::TypeMismatch {
+                                expected: None,
+                                actual: Some(reference),
+                            });
+                        }
+
+                        match (first, second) {
+                            (Some(a), Some(b)) if a != b => {
+                                return Err(ErrorKind::TypeMismatch {
+                                    expected: Some(a),
+                                    actual: Some(b),
+                                })
+                            }
+                            _ => first.or(second),
+                        }
+                    }
+                };
+
+                self.operands.push(selected);
+            }
+            Instruction::LocalGet(local) => {
+                let ty = self.local_type(*local)?;
+                self.push(ty);
+            }
+            Instruction::LocalSet(local) => {
+                let ty = self.local_type(*local)?;
+                self.pop(ty)?;
+            }
+            Instruction::LocalTee(local) => {
+                let ty = self.local_type(*local)?;
+                self.pop(ty)?;
+                self.push(ty);
+            }
+            Instruction::GlobalGet(global) => {
+                let ty = self.global_type(*global)?;
+                self.push(ty.value_type());
+            }
+            Instruction::GlobalSet(global) => {
+                let ty = self.global_type(*global)?;
+                if ty.mutability() != GlobalMutability::Variable {
+                    return Err(ErrorKind::ImmutableGlobal(global.to_u32()));
+                }
+
+                self.pop(ty.value_type())?;
+            }
+            Instruction::MemorySize(memory) => {
+                let address = self.memory_index_type(*memory)?;
+                self.push(address);
+            }
+            Instruction::MemoryGrow(memory) => {
+                let address = self.memory_index_type(*memory)?;
+                self.pop(address)?;
+                self.push(address);
+            }
+            Instruction::MemoryFill(memory) => {
+                let address = self.memory_index_type(*memory)?;
+                self.pop(address)?;
+                self.pop(ValType::I32)?;
+                self.pop(address)?;
+            }
+            Instruction::MemoryCopy {
+                destination,
+                source,
+            } => {
+                let destination = self.memory_index_type(*destination)?;
+                let source = self.memory_index_type(*source)?;
+                let length = if destination == ValType::I32 {
+                    destination
+                } else {
+                    source
+                };
+
+                self.pop(length)?;
+                self.pop(source)?;
+                self.pop(destination)?;
+            }
+            Instruction::MemoryInit(data, memory) => {
+                self.check_data_index(*data)?;
+                let address = self.memory_index_type(*memory)?;
+                self.pop(ValType::I32)?;
+                self.pop(ValType::I32)?;
+                self.pop(address)?;
+            }
+            Instruction::DataDrop(data) => self.check_data_index(*data)?,
+            Instruction::I32Const(_) => self.push(ValType::I32),
+            Instruction::I64Const(_) => self.push(ValType::I64),
+            Instruction::F32Const(_) => self.push(ValType::F32),
+            Instruction::F64Const(_) => self.push(ValType::F64),
+            #[cfg(feature = "simd")]
+            Instruction::V128Const(_) => self.push(ValType::V128),
+            Instruction::RefNull(reference) => {
+                let reference = ValType::from(*reference);
+                self.check_val_type(reference)?;
+                self.push(reference);
+            }
+            Instruction::RefIsNull => {
+                self.pop_reference()?;
+                self.push(ValType::I32);
+            }
+            Instruction::RefFunc(function) => {
+                let signature = self.function_type(*function)?;
+                if self.constant.is_none() && !self.context.is_declared_reference(*function) {
+                    return Err(ErrorKind::UndeclaredFunctionReference(function.to_u32()));
+                }
+
+                self.push(if cfg!(feature = "function-references") {
+                    ValType::from(RefType::new(false, HeapType::Index(signature)))
+                } else {
+                    ValType::FuncRef
+                });
+            }
+            Instruction::TableGet(table) => {
+                let (index, element) = self.table(*table)?;
+                self.pop(index)?;
+                self.push(element);
+            }
+            Instruction::TableSet(table) => {
+                let (index, element) = self.table(*table)?;
+                self.pop(element)?;
+                self.pop(index)?;
+            }
+            Instruction::TableSize(table) => {
+                let (index, _) = self.table(*table)?;
+                self.push(index);
+            }
+            Instruction::TableGrow(table) => {
+                let (index, element) = self.table(*table)?;
+                self.pop(index)?;
+                self.pop(element)?;
+                self.push(index);
+            }
+            Instruction::TableFill(table) => {
+                let (index, element) = self.table(*table)?;
+                self.pop(index)?;
+                self.pop(element)?;
+                self.pop(index)?;
+            }
+            Instruction::TableCopy {
+                destination,
+                source,
+            } => {
+                let (destination, destination_element) = self.table(*destination)?;
+                let (source, source_element) = self.table(*source)?;
+                if destination_element != source_element {
+                    return Err(ErrorKind::TypeMismatch {
+                        expected: Some(destination_element),
+                        actual: Some(source_element),
+                    });
+                }
+
+                let length = if destination == ValType::I32 {
+                    destination
+                } else {
+                    source
+                };
+
+                self.pop(length)?;
+                self.pop(source)?;
+                self.pop(destination)?;
+            }
+            Instruction::TableInit(element, table) => {
+                let segment = self.element_type(*element)?;
+                let (index, element) = self.table(*table)?;
+                if segment != element {
+                    return Err(ErrorKind::TypeMismatch {
+                        expected: Some(element),
+                        actual: Some(segment),
+                    });
+                }
+
+                self.pop(ValType::I32)?;
+                self.pop(ValType::I32)?;
+                self.pop(index)?;
+            }
+            Instruction::ElemDrop(element) => {
+                self.element_type(*element)?;
+            }
+            #[cfg(feature = "simd")]
+            Instruction::I8x16Shuffle(lanes) => {
+                if let Some(lane) = lanes.iter().find(|lane| **lane >= 32) {
+                    return Err(ErrorKind::BadLaneIndex(*lane));
+                }
+
+                self.pop(ValType::V128)?;
+                self.pop(ValType::V128)?;
+                self.push(ValType::V128);
+            }
+            #[cfg(feature = "simd")]
+            Instruction::I8x16ExtractLaneS(lane)
+            | Instruction::I8x16ExtractLaneU(lane)
+            | Instruction::I8x16ReplaceLane(lane)
+            | Instruction::I16x8ExtractLaneS(lane)
+            | Instruction::I16x8ExtractLaneU(lane)
+            | Instruction::I16x8ReplaceLane(lane)
+            | Instruction::I32x4ExtractLane(lane)
+            | Instruction::I32x4ReplaceLane(lane)
+            | Instruction::I64x2ExtractLane(lane)
+            | Instruction::I64x2ReplaceLane(lane)
+            | Instruction::F32x4ExtractLane(lane)
+            | Instruction::F32x4ReplaceLane(lane)
+            | Instruction::F64x2ExtractLane(lane)
+            | Instruction::F64x2ReplaceLane(lane) => {
+                let lane = *lane;
+                if lane >= lane_count(instruction) {
+                    return Err(ErrorKind::BadLaneIndex(lane));
+                }
+
+                let (parameters, results) = lane_signature(instruction);
+                self.pop_all(parameters)?;
+                self.push_all(results);
+            }
+            _ => return Err(ErrorKind::Invalid("unsupported instruction")),
+        }
+
+        Ok(())
+    }
+
+    fn constant_instruction<I: Input>(&self, instruction: &Instruction<'_, I>) -> Checked<bool> {
+        use crate::instruction_set::IsConstant;
+
+        if matches!(instruction, Instruction::End) {
+            return Ok(true);
+        }
+
+        match instruction.valid_in_extended_constant() {
+            IsConstant::Constant => Ok(true),
+            IsConstant::NotConstant => Ok(false),
+            IsConstant::Global(global) => {
+                let ty = self.global_type(global)?;
+                if self
+                    .constant
+                    .map_or(false, |globals| global.to_u32() >= globals)
+                {
+                    Err(ErrorKind::UnknownIndex {
+                        space: "global",
+                        index: global.to_u32(),
+                    })
+                } else {
+                    Ok(ty.mutability() == GlobalMutability::Constant)
+                }
+            }
+        }
+    }
+
+    fn local_type(&self, local: LocalIdx) -> Checked<ValType> {
+        self.locals.get(local).ok_or(ErrorKind::UnknownIndex {
+            space: "local",
+            index: local.to_u32(),
+        })
+    }
+
+    #[cfg(feature = "exceptions")]
+    fn tag_type(&self, tag: TagIdx) -> Checked<TypeIdx> {
+        self.context.tag(tag).ok_or(ErrorKind::UnknownIndex {
+            space: "tag",
+            index: tag.to_u32(),
+        })
+    }
+
+    /// Checks that the label of a catch clause expects the values passed to it when an exception
+    /// is caught.
+    #[cfg(feature = "exceptions")]
+    fn catch_clause(&self, clause: CatchClause) -> Checked<()> {
+        let arguments = match clause.tag() {
+            Some(tag) => self.func_type(self.tag_type(tag)?)?.parameters(),
+            None => &[],
+        };
+
+        let reference: &[ValType] = if clause.is_ref() {
+            &[ValType::ExnRef]
+        } else {
+            &[]
+        };
+
+        let expected = self.label_types(clause.label())?;
+        let matches = expected.len() == arguments.len() + reference.len()
+            && arguments
+                .iter()
+                .chain(reference)
+                .zip(expected.iter())
+                .all(|(actual, expected)| is_subtype(*actual, *expected));
+
+        if matches {
+            Ok(())
+        } else {
+            Err(ErrorKind::Invalid(
+                "catch clause values do not match the types of its label",
+            ))
+        }
+    }
+
+    fn memory_instruction<I: Input>(
+        &mut self,
+        instruction: &Instruction<'_, I>,
+        address: ValType,
+    ) -> Checked<()> {
+        use ValType::{F32, F64, I32, I64};
+
+        enum Access {
+            Load(ValType),
+            Store(ValType),
+            #[cfg(feature = "simd")]
+            LoadLane(u8),
+            #[cfg(feature = "simd")]
+            StoreLane(u8),
+            #[cfg(feature = "threads")]
+            ReadModifyWrite(ValType),
+            #[cfg(feature = "threads")]
+            CompareExchange(ValType),
+            #[cfg(feature = "threads")]
+            Wait(ValType),
+        }
+
+        let access = match instruction {
+            Instruction::I32Load(_)
+            | Instruction::I32Load8S(_)
+            | Instruction::I32Load8U(_)
+            | Instruction::I32Load16S(_)
+            | Instruction::I32Load16U(_) => Access::Load(I32),
+            Instruction::I64Load(_)
+            | Instruction::I64Load8S(_)
+            | Instruction::I64Load8U(_)
+            | Instruction::I64Load16S(_)
+            | Instruction::I64Load16U(_)
+            | Instruction::I64Load32S(_)
+            | Instruction::I64Load32U(_) => Access::Load(I64),
+            Instruction::F32Load(_) => Access::Load(F32),
+            Instruction::F64Load(_) => Access::Load(F64),
+            Instruction::I32Store(_) | Instruction::I32Store8(_) | Instruction::I32Store16(_) => {
+                Access::Store(I32)
+            }
+            Instruction::I64Store(_)
+            | Instruction::I64Store8(_)
+            | Instruction::I64Store16(_)
+            | Instruction::I64Store32(_) => Access::Store(I64),
+            Instruction::F32Store(_) => Access::Store(F32),
+            Instruction::F64Store(_) => Access::Store(F64),
+            #[cfg(feature = "simd")]
+            Instruction::V128Store(_) => Access::Store(ValType::V128),
+            #[cfg(feature = "simd")]
+            Instruction::V128Load8Lane(_, lane) => Access::LoadLane(*lane),
+            #[cfg(feature = "simd")]
+            Instruction::V128Load16Lane(_, lane) => Access::LoadLane(lane.saturating_add(8)),
+            #[cfg(feature = "simd")]
+            Instruction::V128Load32Lane(_, lane) => Access::LoadLane(lane.saturating_add(12)),
+            #[cfg(feature = "simd")]
+            Instruction::V128Load64Lane(_, lane) => Access::LoadLane(lane.saturating_add(14)),
+            #[cfg(feature = "simd")]
+            Instruction::V128Store8Lane(_, lane) => Access::StoreLane(*lane),
+            #[cfg(feature = "simd")]
+            Instruction::V128Store16Lane(_, lane) => Access::StoreLane(lane.saturating_add(8)),
+            #[cfg(feature = "simd")]
+            Instruction::V128Store32Lane(_, lane) => Access::StoreLane(lane.saturating_add(12)),
+            #[cfg(feature = "simd")]
+            Instruction::V128Store64Lane(_, lane) => Access::StoreLane(lane.saturating_add(14)),
+            #[cfg(feature = "simd")]
+            _ if instruction.is_from_fixed_width_simd() => Access::Load(ValType::V128),
+            #[cfg(feature = "threads")]
+            Instruction::MemoryAtomicNotify(_) => Access::ReadModifyWrite(I32),
+            #[cfg(feature = "threads")]
+            Instruction::MemoryAtomicWait32(_) => Access::Wait(I32),
+            #[cfg(feature = "threads")]
+            Instruction::MemoryAtomicWait64(_) => Access::Wait(I64),
+            #[cfg(feature = "threads")]
+            Instruction::I32AtomicLoad(_)
+            | Instruction::I32AtomicLoad8U(_)
+            | Instruction::I32AtomicLoad16U(_) => Access::Load(I32),
+            #[cfg(feature = "threads")]
+            Instruction::I64AtomicLoad(_)
+            | Instruction::I64AtomicLoad8U(_)
+            | Instruction::I64AtomicLoad16U(_)
+            | Instruction::I64AtomicLoad32U(_) => Access::Load(I64),
+            #[cfg(feature = "threads")]
+            Instruction::I32AtomicStore(_)
+            | Instruction::I32AtomicStore8U(_)
+            | Instruction::I32AtomicStore16U(_) => Access::Store(I32),
+            #[cfg(feature = "threads")]
+            Instruction::I64AtomicStore(_)
+            | Instruction::I64AtomicStore8U(_)
+            | Instruction::I64AtomicStore16U(_)
+            | Instruction::I64AtomicStore32U(_) => Access::Store(I64),
+            #[cfg(feature = "threads")]
+            Instruction::I32AtomicRmwCmpxchg(_)
+            | Instruction::I32AtomicRmw8CmpxchgU(_)
+            | Instruction::I32AtomicRmw16CmpxchgU(_) => Access::CompareExchange(I32),
+            #[cfg(feature = "threads")]
+            Instruction::I64AtomicRmwCmpxchg(_)
+            | Instruction::I64AtomicRmw8CmpxchgU(_)
+            | Instruction::I64AtomicRmw16CmpxchgU(_)
+            | Instruction::I64AtomicRmw32CmpxchgU(_) => Access::CompareExchange(I64),
+            #[cfg(feature = "threads")]
+            _ if instruction.name().starts_with("i32.") => Access::ReadModifyWrite(I32),
+            #[cfg(feature = "threads")]
+            _ if instruction.name().starts_with("i64.") => Access::ReadModifyWrite(I64),
+            _ => return Err(ErrorKind::Invalid("unsupported memory instruction")),
+        };
+
+        match access {
+            Access::Load(loaded) => {
+                self.pop(address)?;
+                self.push(loaded);
+            }
+            Access::Store(stored) => {
+                self.pop(stored)?;
+                self.pop(address)?;
+            }
+            #[cfg(feature = "simd")]
+            Access::LoadLane(lane) | Access::StoreLane(lane) => {
+                if lane >= 16 {
+                    return Err(ErrorKind::BadLaneIndex(lane));
+                }
+
+                self.pop(ValType::V128)?;
+                self.pop(address)?;
+                if matches!(access, Access::LoadLane(_)) {
+                    self.push(ValType::V128);
+                }
+            }
+            #[cfg(feature = "threads")]
+            Access::ReadModifyWrite(operand) => {
+                self.pop(operand)?;
+                self.pop(address)?;
+                self.push(operand);
+            }
+            #[cfg(feature = "threads")]
+            Access::CompareExchange(operand) => {
+                self.pop(operand)?;
+                self.pop(operand)?;
+                self.pop(address)?;
+                self.push(operand);
+            }
+            #[cfg(feature = "threads")]
+            Access::Wait(expected) => {
+                self.pop(I64)?;
+                self.pop(expected)?;
+                self.pop(address)?;
+                self.push(I32);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the final **end** instruction of the function body was reached.
+    ///
+    /// The `offset` should refer to the end of the function body.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the function body is missing an **end** instruction.
+    pub fn finish(&self, offset: u64) -> Result<(), ValidationError> {
+        if self.is_finished() {
+            Ok(())
+        } else {
+            Err(ValidationError::new(
+                ErrorKind::Invalid("expected end of function body"),
+                offset,
+            ))
+        }
+    }
+}
+
+#[cfg(feature = "simd")]
+fn lane_count<I: Input>(instruction: &Instruction<'_, I>) -> u8 {
+    match instruction {
+        Instruction::I8x16ExtractLaneS(_)
+        | Instruction::I8x16ExtractLaneU(_)
+        | Instruction::I8x16ReplaceLane(_) => 16,
+        Instruction::I16x8ExtractLaneS(_)
+        | Instruction::I16x8ExtractLaneU(_)
+        | Instruction::I16x8ReplaceLane(_) => 8,
+        Instruction::I32x4ExtractLane(_)
+        | Instruction::I32x4ReplaceLane(_)
+        | Instruction::F32x4ExtractLane(_)
+        | Instruction::F32x4ReplaceLane(_) => 4,
+        _ => 2,
+    }
+}
+
+#[cfg(feature = "simd")]
+fn lane_signature<I: Input>(
+    instruction: &Instruction<'_, I>,
+) -> (&'static [ValType], &'static [ValType]) {
+    use ValType::{F32, F64, I32, I64, V128};
+
+    match instruction {
+        Instruction::I8x16ExtractLaneS(_)
+        | Instruction::I8x16ExtractLaneU(_)
+        | Instruction::I16x8ExtractLaneS(_)
+        | Instruction::I16x8ExtractLaneU(_)
+        | Instruction::I32x4ExtractLane(_) => (&[V128], &[I32]),
+        Instruction::I64x2ExtractLane(_) => (&[V128], &[I64]),
+        Instruction::F32x4ExtractLane(_) => (&[V128], &[F32]),
+        Instruction::F64x2ExtractLane(_) => (&[V128], &[F64]),
+        Instruction::I8x16ReplaceLane(_)
+        | Instruction::I16x8ReplaceLane(_)
+        | Instruction::I32x4ReplaceLane(_) => (&[V128, I32], &[V128]),
+        Instruction::I64x2ReplaceLane(_) => (&[V128, I64], &[V128]),
+        Instruction::F32x4ReplaceLane(_) => (&[V128, F32], &[V128]),
+        _ => (&[V128, F64], &[V128]),
+    }
+}
+
+/// Gets the types of the operands and results of numeric and vector instructions that do not
+/// have any immediate arguments.
+fn numeric_signature<I: Input>(
+    instruction: &Instruction<'_, I>,
+) -> Option<(&'static [ValType], &'static [ValType])> {
+    #[cfg(feature = "simd")]
+    use ValType::V128;
+    use ValType::{F32, F64, I32, I64};
+
+    Some(match instruction {
+        Instruction::I32Eqz { .. }
+        | Instruction::I32Clz { .. }
+        | Instruction::I32Ctz { .. }
+        | Instruction::I32Popcnt { .. }
+        | Instruction::I32Extend8S { .. }
+        | Instruction::I32Extend16S { .. } => (&[I32], &[I32]),
+        Instruction::I32Eq { .. }
+        | Instruction::I32Ne { .. }
+        | Instruction::I32LtS { .. }
+        | Instruction::I32LtU { .. }
+        | Instruction::I32GtS { .. }
+        | Instruction::I32GtU { .. }
+        | Instruction::I32LeS { .. }
+        | Instruction::I32LeU { .. }
+        | Instruction::I32GeS { .. }
+        | Instruction::I32GeU { .. }
+        | Instruction::I32Add { .. }
+        | Instruction::I32Sub { .. }
+        | Instruction::I32Mul { .. }
+        | Instruction::I32DivS { .. }
+        | Instruction::I32DivU { .. }
+        | Instruction::I32RemS { .. }
+        | Instruction::I32RemU { .. }
+        | Instruction::I32And { .. }
+        | Instruction::I32Or { .. }
+        | Instruction::I32Xor { .. }
+        | Instruction::I32Shl { .. }
+        | Instruction::I32ShrS { .. }
+        | Instruction::I32ShrU { .. }
+        | Instruction::I32Rotl { .. }
+        | Instruction::I32Rotr { .. } => (&[I32, I32], &[I32]),
+        Instruction::I64Eqz { .. } | Instruction::I32WrapI64 { .. } => (&[I64], &[I32]),
+        Instruction::I64Eq { .. }
+        | Instruction::I64Ne { .. }
+        | Instruction::I64LtS { .. }
+        | Instruction::I64LtU { .. }
+        | Instruction::I64GtS { .. }
+        | Instruction::I64GtU { .. }
+        | Instruction::I64LeS { .. }
+        | Instruction::I64LeU { .. }
+        | Instruction::I64GeS { .. }
+        | Instruction::I64GeU { .. } => (&[I64, I64], &[I32]),
+        Instruction::F32Eq { .. }
+        | Instruction::F32Ne { .. }
+        | Instruction::F32Lt { .. }
+        | Instruction::F32Gt { .. }
+        | Instruction::F32Le { .. }
+        | Instruction::F32Ge { .. } => (&[F32, F32], &[I32]),
+        Instruction::F64Eq { .. }
+        | Instruction::F64Ne { .. }
+        | Instruction::F64Lt { .. }
+        | Instruction::F64Gt { .. }
+        | Instruction::F64Le { .. }
+        | Instruction::F64Ge { .. } => (&[F64, F64], &[I32]),
+        Instruction::I64Clz { .. }
+        | Instruction::I64Ctz { .. }
+        | Instruction::I64Popcnt { .. }
+        | Instruction::I64Extend8S { .. }
+        | Instruction::I64Extend16S { .. }
+        | Instruction::I64Extend32S { .. } => (&[I64], &[I64]),
+        Instruction::I64Add { .. }
+        | Instruction::I64Sub { .. }
+        | Instruction::I64Mul { .. }
+        | Instruction::I64DivS { .. }
+        | Instruction::I64DivU { .. }
+        | Instruction::I64RemS { .. }
+        | Instruction::I64RemU { .. }
+        | Instruction::I64And { .. }
+        | Instruction::I64Or { .. }
+        | Instruction::I64Xor { .. }
+        | Instruction::I64Shl { .. }
+        | Instruction::I64ShrS { .. }
+        | Instruction::I64ShrU { .. }
+        | Instruction::I64Rotl { .. }
+        | Instruction::I64Rotr { .. } => (&[I64, I64], &[I64]),
+        Instruction::F32Abs { .. }
+        | Instruction::F32Neg { .. }
+        | Instruction::F32Ceil { .. }
+        | Instruction::F32Floor { .. }
+        | Instruction::F32Trunc { .. }
+        | Instruction::F32Nearest { .. }
+        | Instruction::F32Sqrt { .. } => (&[F32], &[F32]),
+        Instruction::F32Add { .. }
+        | Instruction::F32Sub { .. }
+        | Instruction::F32Mul { .. }
+        | Instruction::F32Div { .. }
+        | Instruction::F32Min { .. }
+        | Instruction::F32Max { .. }
+        | Instruction::F32Copysign { .. } => (&[F32, F32], &[F32]),
+        Instruction::F64Abs { .. }
+        | Instruction::F64Neg { .. }
+        | Instruction::F64Ceil { .. }
+        | Instruction::F64Floor { .. }
+        | Instruction::F64Trunc { .. }
+        | Instruction::F64Nearest { .. }
+        | Instruction::F64Sqrt { .. } => (&[F64], &[F64]),
+        Instruction::F64Add { .. }
+        | Instruction::F64Sub { .. }
+        | Instruction::F64Mul { .. }
+        | Instruction::F64Div { .. }
+        | Instruction::F64Min { .. }
+        | Instruction::F64Max { .. }
+        | Instruction::F64Copysign { .. } => (&[F64, F64], &[F64]),
+        Instruction::I32TruncF32S { .. }
+        | Instruction::I32TruncF32U { .. }
+        | Instruction::I32ReinterpretF32 { .. }
+        | Instruction::I32TruncSatF32S { .. }
+        | Instruction::I32TruncSatF32U { .. } => (&[F32], &[I32]),
+        Instruction::I32TruncF64S { .. }
+        | Instruction::I32TruncF64U { .. }
+        | Instruction::I32TruncSatF64S { .. }
+        | Instruction::I32TruncSatF64U { .. } => (&[F64], &[I32]),
+        Instruction::I64ExtendI32S { .. } | Instruction::I64ExtendI32U { .. } => (&[I32], &[I64]),
+        Instruction::I64TruncF32S { .. }
+        | Instruction::I64TruncF32U { .. }
+        | Instruction::I64TruncSatF32S { .. }
+        | Instruction::I64TruncSatF32U { .. } => (&[F32], &[I64]),
+        Instruction::I64TruncF64S { .. }
+        | Instruction::I64TruncF64U { .. }
+        | Instruction::I64ReinterpretF64 { .. }
+        | Instruction::I64TruncSatF64S { .. }
+        | Instruction::I64TruncSatF64U { .. } => (&[F64], &[I64]),
+        Instruction::F32ConvertI32S { .. }
+        | Instruction::F32ConvertI32U { .. }
+        | Instruction::F32ReinterpretI32 { .. } => (&[I32], &[F32]),
+        Instruction::F32ConvertI64S { .. } | Instruction::F32ConvertI64U { .. } => (&[I64], &[F32]),
+        Instruction::F32DemoteF64 { .. } => (&[F64], &[F32]),
+        Instruction::F64ConvertI32S { .. } | Instruction::F64ConvertI32U { .. } => (&[I32], &[F64]),
+        Instruction::F64ConvertI64S { .. }
+        | Instruction::F64ConvertI64U { .. }
+        | Instruction::F64ReinterpretI64 { .. } => (&[I64], &[F64]),
+        Instruction::F64PromoteF32 { .. } => (&[F32], &[F64]),
+        #[cfg(feature = "simd")]
+        Instruction::I8x16Swizzle { .. }
+        | Instruction::I8x16Eq { .. }
+        | Instruction::I8x16Ne { .. }
+        | Instruction::I8x16LtS { .. }
+        | Instruction::I8x16LtU { .. }
+        | Instruction::I8x16GtS { .. }
+        | Instruction::I8x16GtU { .. }
+        | Instruction::I8x16LeS { .. }
+        | Instruction::I8x16LeU { .. }
+        | Instruction::I8x16GeS { .. }
+        | Instruction::I8x16GeU { .. }
+        | Instruction::I16x8Eq { .. }
+        | Instruction::I16x8Ne { .. }
+        | Instruction::I16x8LtS { .. }
+        | Instruction::I16x8LtU { .. }
+        | Instruction::I16x8GtS { .. }
+        | Instruction::I16x8GtU { .. }
+        | Instruction::I16x8LeS { .. }
+        | Instruction::I16x8LeU { .. }
+        | Instruction::I16x8GeS { .. }
+        | Instruction::I16x8GeU { .. }
+        | Instruction::I32x4Eq { .. }
+        | Instruction::I32x4Ne { .. }
+        | Instruction::I32x4LtS { .. }
+        | Instruction::I32x4LtU { .. }
+        | Instruction::I32x4GtS { .. }
+        | Instruction::I32x4GtU { .. }
+        | Instruction::I32x4LeS { .. }
+        | Instruction::I32x4LeU { .. }
+        | Instruction::I32x4GeS { .. }
+        | Instruction::I32x4GeU { .. }
+        | Instruction::I64x2Eq { .. }
+        | Instruction::I64x2Ne { .. }
+        | Instruction::I64x2LtS { .. }
+        | Instruction::I64x2GtS { .. }
+        | Instruction::I64x2LeS { .. }
+        | Instruction::I64x2GeS { .. }
+        | Instruction::F32x4Eq { .. }
+        | Instruction::F32x4Ne { .. }
+        | Instruction::F32x4Lt { .. }
+        | Instruction::F32x4Gt { .. }
+        | Instruction::F32x4Le { .. }
+        | Instruction::F32x4Ge { .. }
+        | Instruction::F64x2Eq { .. }
+        | Instruction::F64x2Ne { .. }
+        | Instruction::F64x2Lt { .. }
+        | Instruction::F64x2Gt { .. }
+        | Instruction::F64x2Le { .. }
+        | Instruction::F64x2Ge { .. }
+        | Instruction::V128And { .. }
+        | Instruction::V128AndNot { .. }
+        | Instruction::V128Or { .. }
+        | Instruction::V128Xor { .. }
+        | Instruction::I8x16NarrowI16x8S { .. }
+        | Instruction::I8x16NarrowI16x8U { .. }
+        | Instruction::I8x16Add { .. }
+        | Instruction::I8x16AddSatS { .. }
+        | Instruction::I8x16AddSatU { .. }
+        | Instruction::I8x16Sub { .. }
+        | Instruction::I8x16SubSatS { .. }
+        | Instruction::I8x16SubSatU { .. }
+        | Instruction::I8x16MinS { .. }
+        | Instruction::I8x16MinU { .. }
+        | Instruction::I8x16MaxS { .. }
+        | Instruction::I8x16MaxU { .. }
+        | Instruction::I8x16AvgrU { .. }
+        | Instruction::I16x8Q15MulrSatS { .. }
+        | Instruction::I16x8NarrowI32x4S { .. }
+        | Instruction::I16x8NarrowI32x4U { .. }
+        | Instruction::I16x8Add { .. }
+        | Instruction::I16x8AddSatS { .. }
+        | Instruction::I16x8AddSatU { .. }
+        | Instruction::I16x8Sub { .. }
+        | Instruction::I16x8SubSatS { .. }
+        | Instruction::I16x8SubSatU { .. }
+        | Instruction::I16x8Mul { .. }
+        | Instruction::I16x8MinS { .. }
+        | Instruction::I16x8MinU { .. }
+        | Instruction::I16x8MaxS { .. }
+        | Instruction::I16x8MaxU { .. }
+        | Instruction::I16x8AvgrU { .. }
+        | Instruction::I16x8ExtmulLowI8x16S { .. }
+        | Instruction::I16x8ExtmulHighI8x16S { .. }
+        | Instruction::I16x8ExtmulLowI8x16U { .. }
+        | Instruction::I16x8ExtmulHighI8x16U { .. }
+        | Instruction::I32x4Add { .. }
+        | Instruction::I32x4Sub { .. }
+        | Instruction::I32x4Mul { .. }
+        | Instruction::I32x4MinS { .. }
+        | Instruction::I32x4MinU { .. }
+        | Instruction::I32x4MaxS { .. }
+        | Instruction::I32x4MaxU { .. }
+        | Instruction::I32x4DotI16x8S { .. }
+        | Instruction::I32x4ExtmulLowI16x8S { .. }
+        | Instruction::I32x4ExtmulHighI16x8S { .. }
+        | Instruction::I32x4ExtmulLowI16x8U { .. }
+        | Instruction::I32x4ExtmulHighI16x8U { .. }
+        | Instruction::I64x2Add { .. }
+        | Instruction::I64x2Sub { .. }
+        | Instruction::I64x2Mul { .. }
+        | Instruction::I64x2ExtmulLowI32x4S { .. }
+        | Instruction::I64x2ExtmulHighI32x4S { .. }
+        | Instruction::I64x2ExtmulLowI32x4U { .. }
+        | Instruction::I64x2ExtmulHighI32x4U { .. }
+        | Instruction::F32x4Add { .. }
+        | Instruction::F32x4Sub { .. }
+        | Instruction::F32x4Mul { .. }
+        | Instruction::F32x4Div { .. }
+        | Instruction::F32x4Min { .. }
+        | Instruction::F32x4Max { .. }
+        | Instruction::F32x4Pmin { .. }
+        | Instruction::F32x4Pmax { .. }
+        | Instruction::F64x2Add { .. }
+        | Instruction::F64x2Sub { .. }
+        | Instruction::F64x2Mul { .. }
+        | Instruction::F64x2Div { .. }
+        | Instruction::F64x2Min { .. }
+        | Instruction::F64x2Max { .. }
+        | Instruction::F64x2Pmin { .. }
+        | Instruction::F64x2Pmax { .. } => (&[V128, V128], &[V128]),
+        #[cfg(feature = "simd")]
+        Instruction::I8x16Splat { .. }
+        | Instruction::I16x8Splat { .. }
+        | Instruction::I32x4Splat { .. } => (&[I32], &[V128]),
+        #[cfg(feature = "simd")]
+        Instruction::I64x2Splat { .. } => (&[I64], &[V128]),
+        #[cfg(feature = "simd")]
+        Instruction::F32x4Splat { .. } => (&[F32], &[V128]),
+        #[cfg(feature = "simd")]
+        Instruction::F64x2Splat { .. } => (&[F64], &[V128]),
+        #[cfg(feature = "simd")]
+        Instruction::V128Not { .. }
+        | Instruction::I8x16Abs { .. }
+        | Instruction::I8x16Neg { .. }
+        | Instruction::I8x16Popcnt { .. }
+        | Instruction::I16x8ExtaddPairwiseI8x16S { .. }
+        | Instruction::I16x8ExtaddPairwiseI8x16U { .. }
+        | Instruction::I16x8Abs { .. }
+        | Instruction::I16x8Neg { .. }
+        | Instruction::I16x8ExtendLowI8x16S { .. }
+        | Instruction::I16x8ExtendHighI8x16S { .. }
+        | Instruction::I16x8ExtendLowI8x16U { .. }
+        | Instruction::I16x8ExtendHighI8x16U { .. }
+        | Instruction::I32x4ExtaddPairwiseI16x8S { .. }
+        | Instruction::I32x4ExtaddPairwiseI16x8U { .. }
+        | Instruction::I32x4Abs { .. }
+        | Instruction::I32x4Neg { .. }
+        | Instruction::I32x4ExtendLowI16x8S { .. }
+        | Instruction::I32x4ExtendHighI16x8S { .. }
+        | Instruction::I32x4ExtendLowI16x8U { .. }
+        | Instruction::I32x4ExtendHighI16x8U { .. }
+        | Instruction::I64x2Abs { .. }
+        | Instruction::I64x2Neg { .. }
+        | Instruction::I64x2ExtendLowI32x4S { .. }
+        | Instruction::I64x2ExtendHighI32x4S { .. }
+        | Instruction::I64x2ExtendLowI32x4U { .. }
+        | Instruction::I64x2ExtendHighI32x4U { .. }
+        | Instruction::F32x4Ceil { .. }
+        | Instruction::F32x4Floor { .. }
+        | Instruction::F32x4Trunc { .. }
+        | Instruction::F32x4Nearest { .. }
+        | Instruction::F32x4Abs { .. }
+        | Instruction::F32x4Neg { .. }
+        | Instruction::F32x4Sqrt { .. }
+        | Instruction::F64x2Ceil { .. }
+        | Instruction::F64x2Floor { .. }
+        | Instruction::F64x2Trunc { .. }
+        | Instruction::F64x2Nearest { .. }
+        | Instruction::F64x2Abs { .. }
+        | Instruction::F64x2Neg { .. }
+        | Instruction::F64x2Sqrt { .. }
+        | Instruction::I32x4TruncSatF32x4S { .. }
+        | Instruction::I32x4TruncSatF32x4U { .. }
+        | Instruction::F32x4ConvertI32x4S { .. }
+        | Instruction::F32x4ConvertI32x4U { .. }
+        | Instruction::I32x4TruncSatF64x2SZero { .. }
+        | Instruction::I32x4TruncSatF64x2UZero { .. }
+        | Instruction::F64x2ConvertLowI32x4S { .. }
+        | Instruction::F64x2ConvertLowI32x4U { .. }
+        | Instruction::F32x4DemoteF64x2Zero { .. }
+        | Instruction::F64x2PromoteLowF32x4 { .. } => (&[V128], &[V128]),
+        #[cfg(feature = "simd")]
+        Instruction::V128Bitselect { .. } => (&[V128, V128, V128], &[V128]),
+        #[cfg(feature = "simd")]
+        Instruction::V128AnyTrue { .. }
+        | Instruction::I8x16AllTrue { .. }
+        | Instruction::I8x16Bitmask { .. }
+        | Instruction::I16x8AllTrue { .. }
+        | Instruction::I16x8Bitmask { .. }
+        | Instruction::I32x4AllTrue { .. }
+        | Instruction::I32x4Bitmask { .. }
+        | Instruction::I64x2AllTrue { .. }
+        | Instruction::I64x2Bitmask { .. } => (&[V128], &[I32]),
+        #[cfg(feature = "simd")]
+        Instruction::I8x16Shl { .. }
+        | Instruction::I8x16ShrS { .. }
+        | Instruction::I8x16ShrU { .. }
+        | Instruction::I16x8Shl { .. }
+        | Instruction::I16x8ShrS { .. }
+        | Instruction::I16x8ShrU { .. }
+        | Instruction::I32x4Shl { .. }
+        | Instruction::I32x4ShrS { .. }
+        | Instruction::I32x4ShrU { .. }
+        | Instruction::I64x2Shl { .. }
+        | Instruction::I64x2ShrS { .. }
+        | Instruction::I64x2ShrU { .. } => (&[V128, I32], &[V128]),
+        _ => return None,
+    })
+}
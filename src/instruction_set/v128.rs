@@ -0,0 +1,56 @@
+/// A 128-bit vector stored as its little-endian bytes, which can be converted to and from the
+/// [`u128`] immediate of the [**v128.const**](crate::instruction_set::Instruction::V128Const)
+/// instruction.
+///
+/// # Example
+///
+/// ```
+/// use wasmiter::instruction_set::V128;
+///
+/// let vector = V128::from(0x0F0E_0D0C_0B0A_0908_0706_0504_0302_0100u128);
+/// assert_eq!(vector.to_le_bytes(), [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]);
+/// assert_eq!(u128::from(vector), 0x0F0E_0D0C_0B0A_0908_0706_0504_0302_0100);
+/// ```
+#[derive(Clone, Copy, Default, Eq, Hash, PartialEq)]
+#[repr(transparent)]
+pub struct V128([u8; 16]);
+
+impl V128 {
+    /// Creates a vector from its little-endian byte representation.
+    #[inline]
+    pub const fn from_le_bytes(bytes: [u8; 16]) -> Self {
+        Self(bytes)
+    }
+
+    /// Gets the little-endian byte representation of the vector.
+    #[inline]
+    pub const fn to_le_bytes(self) -> [u8; 16] {
+        self.0
+    }
+
+    /// Interprets the vector as a 128-bit integer.
+    #[inline]
+    pub const fn to_u128(self) -> u128 {
+        u128::from_le_bytes(self.0)
+    }
+}
+
+impl From<u128> for V128 {
+    #[inline]
+    fn from(value: u128) -> Self {
+        Self(value.to_le_bytes())
+    }
+}
+
+impl From<V128> for u128 {
+    #[inline]
+    fn from(value: V128) -> Self {
+        value.to_u128()
+    }
+}
+
+impl core::fmt::Debug for V128 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:#034X}", self.to_u128())
+    }
+}
@@ -0,0 +1,126 @@
+use crate::{input::Input, instruction_set::Instruction};
+
+/// Describes a set of [`Instruction`]s, in terms of the version of WebAssembly or the proposals
+/// that introduced them.
+///
+/// This allows expressing which instructions are allowed declaratively, rather than by
+/// combining the `is_from_*` methods of [`Instruction`], such as
+/// [`is_from_mvp`](Instruction::is_from_mvp).
+///
+/// # Example
+///
+/// ```
+/// use wasmiter::instruction_set::{Instruction, InstructionSet};
+///
+/// let sign_extension = Instruction::<&[u8]>::I32Extend8S;
+/// assert!(InstructionSet::WASM_2_0.contains(&sign_extension));
+/// assert!(!InstructionSet::MVP.contains(&sign_extension));
+///
+/// let allowed = InstructionSet::MVP.with(InstructionSet::SIGN_EXTENSION_OPERATORS);
+/// assert!(allowed.contains(&sign_extension));
+/// assert!(!allowed.contains(&Instruction::<&[u8]>::I32TruncSatF32S));
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct InstructionSet {
+    proposals: u16,
+}
+
+impl InstructionSet {
+    /// The empty set, containing no instructions.
+    pub const EMPTY: Self = Self { proposals: 0 };
+
+    /// Instructions [introduced in the 1.0 release](Instruction::is_from_mvp) of WebAssembly.
+    pub const MVP: Self = Self { proposals: 1 };
+
+    /// Instructions introduced in the
+    /// [non-trapping float-to-int conversions proposal](Instruction::is_from_non_trapping_float_to_int_conversions).
+    pub const NON_TRAPPING_FLOAT_TO_INT_CONVERSIONS: Self = Self { proposals: 1 << 1 };
+
+    /// Instructions introduced in the
+    /// [sign-extension operators proposal](Instruction::is_from_sign_extension_operators).
+    pub const SIGN_EXTENSION_OPERATORS: Self = Self { proposals: 1 << 2 };
+
+    /// Instructions introduced in the
+    /// [reference types proposal](Instruction::is_from_reference_types).
+    pub const REFERENCE_TYPES: Self = Self { proposals: 1 << 3 };
+
+    /// Instructions introduced in the
+    /// [bulk memory operations proposal](Instruction::is_from_bulk_memory_operations).
+    pub const BULK_MEMORY_OPERATIONS: Self = Self { proposals: 1 << 4 };
+
+    /// Instructions introduced in the
+    /// [fixed-width SIMD proposal](Instruction::is_from_fixed_width_simd).
+    pub const FIXED_WIDTH_SIMD: Self = Self { proposals: 1 << 5 };
+
+    /// Instructions introduced in the [tail call proposal](Instruction::is_from_tail_call).
+    pub const TAIL_CALL: Self = Self { proposals: 1 << 6 };
+
+    /// Instructions introduced in the
+    /// [typed function references proposal](Instruction::is_from_function_references).
+    pub const FUNCTION_REFERENCES: Self = Self { proposals: 1 << 7 };
+
+    /// Atomic memory instructions introduced in the
+    /// [threads proposal](Instruction::is_from_threads).
+    pub const THREADS: Self = Self { proposals: 1 << 8 };
+
+    /// Instructions introduced in the
+    /// [exception handling proposal](Instruction::is_exception_handling).
+    pub const EXCEPTION_HANDLING: Self = Self { proposals: 1 << 9 };
+
+    /// Instructions included in the
+    /// [2.0 release](https://www.w3.org/TR/wasm-core-2/) of WebAssembly.
+    pub const WASM_2_0: Self = Self::MVP
+        .with(Self::NON_TRAPPING_FLOAT_TO_INT_CONVERSIONS)
+        .with(Self::SIGN_EXTENSION_OPERATORS)
+        .with(Self::REFERENCE_TYPES)
+        .with(Self::BULK_MEMORY_OPERATIONS)
+        .with(Self::FIXED_WIDTH_SIMD);
+
+    /// Every instruction that can be parsed, including those introduced in proposals whose cargo
+    /// features are disabled.
+    pub const ALL: Self = Self::WASM_2_0
+        .with(Self::TAIL_CALL)
+        .with(Self::FUNCTION_REFERENCES)
+        .with(Self::THREADS)
+        .with(Self::EXCEPTION_HANDLING);
+
+    /// Returns a set containing the instructions in both `self` and `other`.
+    #[inline]
+    pub const fn with(self, other: Self) -> Self {
+        Self {
+            proposals: self.proposals | other.proposals,
+        }
+    }
+
+    /// Returns a set containing the instructions in `self` that are not in `other`.
+    #[inline]
+    pub const fn without(self, other: Self) -> Self {
+        Self {
+            proposals: self.proposals & !other.proposals,
+        }
+    }
+
+    /// Returns `true` if all of the instructions in `other` are also in `self`.
+    #[inline]
+    pub const fn includes(&self, other: Self) -> bool {
+        self.proposals & other.proposals == other.proposals
+    }
+
+    /// Returns `true` if the given [`Instruction`] is in this set.
+    pub const fn contains<I: Input>(&self, instruction: &Instruction<'_, I>) -> bool {
+        (self.includes(Self::MVP) && instruction.is_from_mvp())
+            || (self.includes(Self::NON_TRAPPING_FLOAT_TO_INT_CONVERSIONS)
+                && instruction.is_from_non_trapping_float_to_int_conversions())
+            || (self.includes(Self::SIGN_EXTENSION_OPERATORS)
+                && instruction.is_from_sign_extension_operators())
+            || (self.includes(Self::REFERENCE_TYPES) && instruction.is_from_reference_types())
+            || (self.includes(Self::BULK_MEMORY_OPERATIONS)
+                && instruction.is_from_bulk_memory_operations())
+            || (self.includes(Self::FIXED_WIDTH_SIMD) && instruction.is_from_fixed_width_simd())
+            || (self.includes(Self::TAIL_CALL) && instruction.is_from_tail_call())
+            || (self.includes(Self::FUNCTION_REFERENCES)
+                && instruction.is_from_function_references())
+            || (self.includes(Self::THREADS) && instruction.is_from_threads())
+            || (self.includes(Self::EXCEPTION_HANDLING) && instruction.is_exception_handling())
+    }
+}
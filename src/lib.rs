@@ -7,10 +7,54 @@
 //! - `alloc`: Enables support for heap allocations with [`alloc`]. This allows for more
 //!   descriptive [`parser::Error`] messages.
 //! - `backtrace`: Enables attaching [`std::backtrace::Backtrace`]s to [`parser::Error`]s. Requires
-//!   the `std` flag.
+//!   the `std` flag and Rust 1.65.
 //! - `mmap`: Enables the optional dependency on [`memmap2`](https://docs.rs/memmap2/), which
-//!   allows treating a memory mapped file as an [`Input`](input::Input) to the parser. Requires
-//!   the `std` flag.
+//!   allows treating a memory mapped file as an [`Input`](input::Input) to the parser, provides
+//!   the [`SharedMmap`](input::SharedMmap) wrapper for sharing one mapping across threads, and
+//!   enables the [`batch`] module for processing many files in parallel. Requires the `std` flag.
+//! - `mem-usage`: Enables the [`mem_usage::MemUsage`] trait, used to approximate the amount of
+//!   heap memory used by parser objects such as [`parser::Error`]s. Requires the `alloc` flag.
+//! - `cookbook`: Enables the [`cookbook`] module, containing short examples of how to use this
+//!   crate. Requires the `alloc` flag.
+//! - `trace`: Enables the optional dependency on [`tracing`](https://docs.rs/tracing/), emitting
+//!   spans and events at the `TRACE` level when parsing sections and *code section* entries, and
+//!   when writing modules in the WebAssembly text format. These include the offsets and counts of
+//!   the parsed structures, allowing time spent within the parser to be profiled.
+//! - `simd`: Enables parsing of
+//!   [fixed-width SIMD](https://github.com/WebAssembly/simd) instructions.
+//! - `threads`: Enables parsing of the atomic instructions introduced in the
+//!   [threads proposal](https://github.com/WebAssembly/threads).
+//! - `exceptions`: Enables parsing of the instructions introduced in the
+//!   [exception handling proposal](https://github.com/WebAssembly/exception-handling).
+//! - `tail-call`: Enables parsing of the
+//!   [tail call](https://github.com/WebAssembly/tail-call) instructions.
+//! - `function-references`: Enables parsing of the instructions introduced in the
+//!   [typed function references proposal](https://github.com/WebAssembly/function-references).
+//!   Requires the `tail-call` flag.
+//! - `custom-page-sizes`: Enables parsing of the page sizes of linear memories introduced in the
+//!   [custom page sizes proposal](https://github.com/WebAssembly/custom-page-sizes).
+//!
+//! Disabling the `simd`, `threads`, `exceptions`, `tail-call`, or `function-references` flags
+//! removes the corresponding [`Instruction`](instruction_set::Instruction) variants, and causes
+//! the parser to return an error when encountering them.
+//!
+//! ## Thread Safety
+//!
+//! Parser types such as [`SectionSequence`](sections::SectionSequence) only contain their
+//! [`Input`](input::Input) and offsets into it, so they implement [`Send`] and [`Sync`] whenever
+//! the [`Input`](input::Input) does. This includes byte slices, `Arc<[u8]>`, and memory mapped
+//! files, allowing a module to be parsed on several threads at once. Errors returned by the parser
+//! always implement [`Send`] and [`Sync`].
+//!
+//! ## Untrusted Input
+//!
+//! Parsing malformed or malicious input never panics; instead, an [`parser::Error`] is returned.
+//! Panics encountered when parsing any input, including through the [`Debug`](core::fmt::Debug)
+//! and [`Display`](core::fmt::Display) implementations of parsed structures, are considered bugs.
+//! The `no_panic` fuzz target checks this guarantee for the public entry points of the crate.
+//!
+//! Note that this guarantee does not extend to panics originating from user code, such as
+//! [`Input`](input::Input) implementations or closures passed to the parser.
 //!
 //! [`std`]: https://doc.rust-lang.org/std/
 //! [`std::backtrace::Backtrace`]: https://doc.rust-lang.org/std/backtrace/struct.Backtrace.html
@@ -26,21 +70,59 @@
 #![deny(clippy::std_instead_of_alloc)]
 #![cfg_attr(not(feature = "std"), no_std)]
 #![cfg_attr(doc_cfg, feature(doc_cfg))]
+// Required to generate the `OwnedInstruction` enum, which expands once per instruction
+#![recursion_limit = "1024"]
 
 #[cfg(feature = "alloc")]
 extern crate alloc;
 
+#[cfg(feature = "alloc")]
+mod encode;
 mod int;
+mod module;
+mod preamble_options;
+mod thread_safety;
+mod trace;
 mod wat;
 
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub mod addrmap;
+pub mod analysis;
 pub mod component;
+pub mod component_model;
+#[cfg(feature = "cookbook")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "cookbook")))]
+pub mod cookbook;
 pub mod custom;
+pub mod dump;
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub mod edit;
+pub mod features;
 pub mod index;
 pub mod input;
 pub mod instruction_set;
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub mod manifest;
+#[cfg(feature = "mem-usage")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "mem-usage")))]
+pub mod mem_usage;
 pub mod parser;
 pub mod sections;
+pub mod text;
 pub mod types;
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub mod validate;
+pub mod visit;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub use manifest::manifest;
+pub use module::Module;
+pub use preamble_options::PreambleOptions;
 
 const _CHECK_POINTER_SIZE: () = if usize::BITS < 32 {
     panic!("wasmiter is not supported in environments with a pointer size less than 32-bits")
@@ -50,47 +132,67 @@ cfg_if::cfg_if! {
     if #[cfg(feature = "mmap")] {
         mod mmap;
 
+        #[cfg_attr(doc_cfg, doc(cfg(feature = "mmap")))]
+        pub mod batch;
+
         pub use mmap::parse_module_sections as parse_module_sections_from_mmap_file;
     }
 }
 
 const PREAMBLE_LENGTH: u8 = 8;
 
-fn parse_module_preamble<I: input::Input>(input: &I) -> parser::Parsed<()> {
+/// Checks the magic number at the given `offset`, returning the version field of the preamble.
+fn parse_preamble_version<I: input::Input>(mut offset: u64, input: &I) -> parser::Parsed<[u8; 4]> {
     use parser::ResultExt;
 
     const MAGIC: [u8; 4] = *b"\0asm";
-    const VERSION: [u8; 4] = u32::to_le_bytes(1);
 
+    let start = offset;
     let mut preamble = [0u8; PREAMBLE_LENGTH as usize];
-    parser::bytes_exact(&mut 0, input, &mut preamble)
+    parser::bytes_exact(&mut offset, input, &mut preamble)
         .context("expected WebAssembly module preamble")?;
 
     if preamble[0..4] != MAGIC {
         #[inline(never)]
         #[cold]
-        fn bad_magic() -> parser::Error {
-            parser::Error::new(parser::ErrorKind::BadWasmMagic)
+        fn bad_magic(offset: u64) -> parser::Error {
+            parser::Error::new(parser::ErrorKind::BadWasmMagic).with_offset(offset)
         }
 
-        return Err(bad_magic());
+        return Err(bad_magic(start));
     }
 
-    let version = <[u8; 4]>::try_from(&preamble[4..8]).unwrap();
-    if version != VERSION {
+    Ok(<[u8; 4]>::try_from(&preamble[4..8]).unwrap())
+}
+
+/// Checks that the preamble at the given `offset` has the given `expected` version field.
+fn parse_preamble_with_version<I: input::Input>(
+    offset: u64,
+    input: &I,
+    expected: [u8; 4],
+) -> parser::Parsed<()> {
+    let version = parse_preamble_version(offset, input)?;
+    if version != expected {
         #[inline(never)]
         #[cold]
-        fn unsupported_wasm_version(version: u32) -> parser::Error {
+        fn unsupported_wasm_version(version: u32, offset: u64) -> parser::Error {
             parser::Error::new(parser::ErrorKind::UnsupportedWasmVersion(version))
-                .with_location_context("preamble", 0)
+                .with_location_context("preamble", offset)
         }
 
-        return Err(unsupported_wasm_version(u32::from_le_bytes(version)));
+        return Err(unsupported_wasm_version(
+            u32::from_le_bytes(version),
+            offset,
+        ));
     }
 
     Ok(())
 }
 
+fn parse_module_preamble<I: input::Input>(offset: u64, input: &I) -> parser::Parsed<()> {
+    parse_preamble_with_version(offset, input, u32::to_le_bytes(1))
+}
+
 /// Reads a [WebAssembly module binary](https://webassembly.github.io/spec/core/binary/index.html),
 /// returning the sequence of sections.
 ///
@@ -100,9 +202,99 @@ fn parse_module_preamble<I: input::Input>(input: &I) -> parser::Parsed<()> {
 pub fn parse_module_sections<I: input::Input>(
     binary: I,
 ) -> parser::Parsed<sections::SectionSequence<I>> {
-    parse_module_preamble(&binary)?;
+    parse_module_preamble(0, &binary)?;
     Ok(sections::SectionSequence::new(
         u64::from(PREAMBLE_LENGTH),
         binary,
     ))
 }
+
+/// Reads a WebAssembly binary whose preamble is accepted by the given [`PreambleOptions`],
+/// returning the kind of binary along with its sequence of sections.
+///
+/// This allows opting into the parsing of binaries with *version* fields other than `1`, including
+/// the preambles of [components](component_model), which use a *layer* field of `1`. The contents
+/// of sections are not affected by the options, so component-level sections should be interpreted
+/// using [`component_model::KnownComponentSection::interpret`] rather than
+/// [`component::KnownSection::interpret`].
+///
+/// # Errors
+///
+/// Returns an error if the magic number is incorrect, or if the *version* is not accepted by the
+/// `options`. Encountering a component when no [component versions] are accepted results in an
+/// error stating that a component was found instead of a module.
+///
+/// # Example
+///
+/// ```
+/// use wasmiter::{component_model::BinaryKind, PreambleOptions};
+///
+/// let options = PreambleOptions::new().with_component_versions(&[0xA, 0xD]);
+///
+/// let module = wat::parse_str("(module)").unwrap();
+/// let (kind, _) = wasmiter::parse_module_sections_with_options(module.as_slice(), &options)?;
+/// assert_eq!(kind, BinaryKind::Module(1));
+///
+/// let component = b"\0asm\x0D\0\x01\0";
+/// let (kind, _) = wasmiter::parse_module_sections_with_options(component.as_slice(), &options)?;
+/// assert_eq!(kind, BinaryKind::Component(0xD));
+///
+/// let error = wasmiter::parse_module_sections(component.as_slice()).unwrap_err();
+/// assert!(error.to_string().contains("unsupported WebAssembly version"));
+///
+/// let error = wasmiter::parse_module_sections_with_options(
+///     component.as_slice(),
+///     &PreambleOptions::new(),
+/// ).unwrap_err();
+/// assert!(error.to_string().contains("got a component"));
+/// # Ok::<(), wasmiter::parser::Error>(())
+/// ```
+///
+/// [component versions]: PreambleOptions::with_component_versions
+pub fn parse_module_sections_with_options<I: input::Input>(
+    binary: I,
+    options: &PreambleOptions<'_>,
+) -> parser::Parsed<(component_model::BinaryKind, sections::SectionSequence<I>)> {
+    use component_model::BinaryKind;
+
+    let kind = component_model::parse_binary_kind(&binary)?;
+    match kind {
+        BinaryKind::Module(version) if !options.module_versions().contains(&version) => {
+            #[inline(never)]
+            #[cold]
+            fn unsupported_module_version(version: u16) -> parser::Error {
+                parser::Error::new(parser::ErrorKind::UnsupportedWasmVersion(u32::from(
+                    version,
+                )))
+                .with_location_context("preamble", 0)
+            }
+
+            return Err(unsupported_module_version(version));
+        }
+        BinaryKind::Component(version) if !options.component_versions().contains(&version) => {
+            #[inline(never)]
+            #[cold]
+            fn unsupported_component_version(version: u16, any_allowed: bool) -> parser::Error {
+                let kind = if any_allowed {
+                    let layer = u32::from(component_model::LAYER) << 16;
+                    parser::ErrorKind::UnsupportedWasmVersion(layer | u32::from(version))
+                } else {
+                    parser::ErrorKind::UnexpectedComponent(version)
+                };
+
+                parser::Error::new(kind).with_location_context("preamble", 0)
+            }
+
+            return Err(unsupported_component_version(
+                version,
+                !options.component_versions().is_empty(),
+            ));
+        }
+        _ => (),
+    }
+
+    Ok((
+        kind,
+        sections::SectionSequence::new(u64::from(PREAMBLE_LENGTH), binary),
+    ))
+}
@@ -0,0 +1,531 @@
+//! Provides the [`manifest()`] function, which summarizes the interface of a WebAssembly module
+//! without parsing its function bodies or data segments.
+
+use crate::{
+    analysis::FeatureDetector,
+    component::{ExportKind, ImportKind, KnownSection, Tag},
+    custom::{
+        name::NameSubsection, producers::ProducersSection, CustomSection, KnownCustomSection,
+    },
+    features::Feature,
+    index::TypeIdx,
+    input::{BorrowInput as _, Input},
+    parser::{Context, Error, ErrorKind, Parsed},
+    sections::{id as section_id, SectionSequence},
+    types::{FuncType, GlobalType, MemType, TableType},
+};
+use alloc::{string::String, vec::Vec};
+
+#[inline(never)]
+#[cold]
+fn unknown_index(space: &'static str, index: u32) -> Error {
+    Error::new(ErrorKind::InvalidFormat).with_context(Context::from_closure(move |f| {
+        write!(f, "{space} index {index} is out of bounds")
+    }))
+}
+
+/// The type of an imported or exported definition.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum Signature {
+    /// A function with the given type.
+    Function(FuncType),
+    /// A table.
+    Table(TableType),
+    /// A linear memory.
+    Memory(MemType),
+    /// A global variable.
+    Global(GlobalType),
+    /// A tag, whose type specifies the parameters of the exceptions it describes.
+    Tag(FuncType),
+}
+
+/// Describes an entry in the *import section*.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ImportSummary {
+    module: String,
+    name: String,
+    signature: Signature,
+}
+
+impl ImportSummary {
+    /// Gets the name of the module the definition is imported from.
+    #[inline]
+    pub fn module(&self) -> &str {
+        &self.module
+    }
+
+    /// Gets the name of the imported definition.
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Gets the type of the imported definition.
+    #[inline]
+    pub fn signature(&self) -> &Signature {
+        &self.signature
+    }
+}
+
+/// Describes an entry in the *export section*.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ExportSummary {
+    name: String,
+    signature: Signature,
+}
+
+impl ExportSummary {
+    /// Gets the name of the export.
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Gets the type of the exported definition.
+    #[inline]
+    pub fn signature(&self) -> &Signature {
+        &self.signature
+    }
+}
+
+/// A language, tool, or SDK listed in the
+/// [`producers` section](crate::custom::producers::ProducersSection).
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Producer {
+    field: String,
+    name: String,
+    version: String,
+}
+
+impl Producer {
+    /// Gets the name of the field the producer was listed in, such as
+    /// [`language`](crate::custom::producers::LANGUAGE).
+    #[inline]
+    pub fn field(&self) -> &str {
+        &self.field
+    }
+
+    /// Gets the name of the language, tool, or SDK.
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Gets the version of the language, tool, or SDK, which may be empty.
+    #[inline]
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+}
+
+/// Describes a section of a WebAssembly module.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct SectionSummary {
+    id: u8,
+    custom_name: Option<String>,
+    offset: u64,
+    length: u64,
+}
+
+impl SectionSummary {
+    /// Gets the [*id*](section_id) of the section.
+    #[inline]
+    pub fn id(&self) -> u8 {
+        self.id
+    }
+
+    /// Gets the name of the section if it is a *custom section*.
+    #[inline]
+    pub fn custom_name(&self) -> Option<&str> {
+        self.custom_name.as_deref()
+    }
+
+    /// Gets the offset to the start of the section's contents.
+    #[inline]
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Gets the length, in bytes, of the section's contents.
+    #[inline]
+    pub fn length(&self) -> u64 {
+        self.length
+    }
+}
+
+/// A summary of the interface of a WebAssembly module, returned by [`manifest()`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Manifest {
+    name: Option<String>,
+    sections: Vec<SectionSummary>,
+    functions: Vec<FuncType>,
+    imports: Vec<ImportSummary>,
+    exports: Vec<ExportSummary>,
+    tables: Vec<TableType>,
+    memories: Vec<MemType>,
+    features: Vec<Feature>,
+    producers: Vec<Producer>,
+}
+
+impl Manifest {
+    /// Gets the name of the module specified in the
+    /// [*module name subsection*](NameSubsection::ModuleName), if one is present.
+    #[inline]
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Gets the sections of the module, including *custom sections*, in the order they appear.
+    #[inline]
+    pub fn sections(&self) -> &[SectionSummary] {
+        &self.sections
+    }
+
+    /// Gets the types of the imported and defined functions, in index order.
+    #[inline]
+    pub fn functions(&self) -> &[FuncType] {
+        &self.functions
+    }
+
+    /// Gets the imports of the module, in the order they are declared.
+    #[inline]
+    pub fn imports(&self) -> &[ImportSummary] {
+        &self.imports
+    }
+
+    /// Gets the exports of the module, in the order they are declared.
+    #[inline]
+    pub fn exports(&self) -> &[ExportSummary] {
+        &self.exports
+    }
+
+    /// Gets the types of the imported and defined tables, in index order.
+    #[inline]
+    pub fn tables(&self) -> &[TableType] {
+        &self.tables
+    }
+
+    /// Gets the types of the imported and defined memories, in index order.
+    #[inline]
+    pub fn memories(&self) -> &[MemType] {
+        &self.memories
+    }
+
+    /// Gets the [`Feature`]s the module requires, in sorted order.
+    ///
+    /// Only the types of the module's imports and definitions are used to determine which
+    /// features are required, so features that are only used within function bodies, such as
+    /// SIMD instructions or tail calls, are not detected. To also check function bodies, use a
+    /// [`FeatureDetector`].
+    #[inline]
+    pub fn features(&self) -> &[Feature] {
+        &self.features
+    }
+
+    /// Gets the producers listed in the module's `producers` section, if one is present.
+    #[inline]
+    pub fn producers(&self) -> &[Producer] {
+        &self.producers
+    }
+}
+
+/// The index spaces needed to determine the types of exports.
+#[derive(Default)]
+struct Definitions {
+    types: Vec<FuncType>,
+    functions: Vec<TypeIdx>,
+    globals: Vec<GlobalType>,
+    tags: Vec<TypeIdx>,
+    features: FeatureDetector,
+}
+
+impl Definitions {
+    fn func_type(&self, index: TypeIdx) -> Parsed<FuncType> {
+        self.types
+            .get(usize::from(index))
+            .cloned()
+            .ok_or_else(|| unknown_index("type", index.to_u32()))
+    }
+}
+
+/// Summarizes the sections, imports, exports, function signatures, tables, memories, name,
+/// producers, and required [`Feature`]s of a WebAssembly module in a single pass.
+///
+/// The contents of the *element*, *code*, and *data* sections are skipped without being parsed,
+/// so a [`Manifest`] can be produced quickly even for large modules. As a result, a module whose
+/// function bodies or data segments are malformed may still have a [`Manifest`].
+///
+/// # Errors
+///
+/// Returns an error if the module's preamble or any other section could not be parsed, or if an
+/// import or export refers to a type or definition that does not exist.
+///
+/// # Example
+///
+/// ```
+/// use wasmiter::{manifest::Signature, types::ValType};
+///
+/// let wasm = wat::parse_str(r#"(module $example
+///     (import "env" "memory" (memory 1))
+///     (func (export "add") (param i32 i32) (result i32)
+///         local.get 0
+///         local.get 1
+///         i32.add))"#).unwrap();
+///
+/// let manifest = wasmiter::manifest(wasm.as_slice())?;
+/// assert_eq!(manifest.name(), Some("example"));
+/// assert_eq!(manifest.imports()[0].module(), "env");
+/// assert_eq!(manifest.memories()[0].minimum(), 1);
+///
+/// let add = &manifest.exports()[0];
+/// assert_eq!(add.name(), "add");
+/// match add.signature() {
+///     Signature::Function(signature) => {
+///         assert_eq!(signature.parameters(), &[ValType::I32, ValType::I32]);
+///         assert_eq!(signature.results(), &[ValType::I32]);
+///     }
+///     _ => unreachable!(),
+/// }
+/// # Ok::<(), wasmiter::parser::Error>(())
+/// ```
+pub fn manifest<I: Input>(binary: I) -> Parsed<Manifest> {
+    manifest_sections(&crate::parse_module_sections(binary)?)
+}
+
+/// Summarizes a WebAssembly module, given its `sections`.
+///
+/// See the documentation for [`manifest()`] for more information.
+///
+/// # Errors
+///
+/// Returns an error if any section could not be parsed, or if an import or export refers to a
+/// type or definition that does not exist.
+pub fn manifest_sections<I: Input>(sections: &SectionSequence<I>) -> Parsed<Manifest> {
+    let mut manifest = Manifest::default();
+    let mut definitions = Definitions::default();
+
+    for result in sections.borrow_input() {
+        let section = result?;
+        let summary = SectionSummary {
+            id: section.id(),
+            custom_name: None,
+            offset: section.contents().base(),
+            length: section.length(),
+        };
+
+        match section.id() {
+            section_id::START | section_id::ELEMENT | section_id::CODE | section_id::DATA => {
+                manifest.sections.push(summary);
+                continue;
+            }
+            section_id::CUSTOM => {
+                let custom = match CustomSection::try_from_section(section) {
+                    Ok(custom) => custom?,
+                    Err(_) => continue,
+                };
+
+                manifest.sections.push(SectionSummary {
+                    custom_name: Some(custom.name().try_into_string()?),
+                    ..summary
+                });
+
+                let custom = match ProducersSection::interpret(custom) {
+                    Ok(producers) => {
+                        producers_section(producers?, &mut manifest.producers)?;
+                        continue;
+                    }
+                    Err(custom) => custom,
+                };
+
+                if let Ok(KnownCustomSection::Name(mut names)) =
+                    KnownCustomSection::interpret(custom)
+                {
+                    // Unrecognized name subsections are ignored
+                    while let Some(subsection) = names.parse() {
+                        if let Ok(subsection) = subsection {
+                            if let NameSubsection::ModuleName(name) = subsection? {
+                                manifest.name = Some(name.try_into_string()?);
+                            }
+                        }
+                    }
+                }
+
+                continue;
+            }
+            _ => manifest.sections.push(summary),
+        }
+
+        let known = match KnownSection::interpret(section) {
+            Ok(known) => known?,
+            Err(_) => continue,
+        };
+
+        match known {
+            KnownSection::Type(mut types) => {
+                while let Some(ty) = types.parse(
+                    |parameters| parameters.collect::<Parsed<Vec<_>>>(),
+                    |parameters, results| {
+                        Ok(FuncType::new(
+                            parameters,
+                            results.collect::<Parsed<Vec<_>>>()?,
+                        ))
+                    },
+                )? {
+                    definitions
+                        .features
+                        .func_type(ty.parameters(), ty.results());
+
+                    definitions.types.push(ty);
+                }
+            }
+            KnownSection::Import(mut imports) => {
+                while let Some(import) = imports.parse()? {
+                    let signature = match *import.kind() {
+                        ImportKind::Function(ty) => {
+                            definitions.functions.push(ty);
+                            Signature::Function(definitions.func_type(ty)?)
+                        }
+                        ImportKind::Table(table) => {
+                            definitions.features.table(&table);
+                            manifest.tables.push(table);
+                            Signature::Table(table)
+                        }
+                        ImportKind::Memory(memory) => {
+                            definitions.features.memory(&memory);
+                            manifest.memories.push(memory);
+                            Signature::Memory(memory)
+                        }
+                        ImportKind::Global(global) => {
+                            global_type(&mut definitions, global);
+                            definitions.globals.push(global);
+                            Signature::Global(global)
+                        }
+                        ImportKind::Tag(Tag::Exception(ty)) => {
+                            definitions.features.insert(Feature::Exceptions);
+                            definitions.tags.push(ty);
+                            Signature::Tag(definitions.func_type(ty)?)
+                        }
+                    };
+
+                    manifest.imports.push(ImportSummary {
+                        module: import.module().try_into_string()?,
+                        name: import.name().try_into_string()?,
+                        signature,
+                    });
+                }
+            }
+            KnownSection::Function(functions) => {
+                for result in functions {
+                    definitions.functions.push(result?);
+                }
+            }
+            KnownSection::Table(tables) => {
+                for result in tables {
+                    let table = result?;
+                    definitions.features.table(&table);
+                    manifest.tables.push(table);
+                }
+            }
+            KnownSection::Memory(memories) => {
+                for result in memories {
+                    let memory = result?;
+                    definitions.features.memory(&memory);
+                    manifest.memories.push(memory);
+                }
+            }
+            KnownSection::Global(mut globals) => {
+                // Initializer expressions are skipped
+                while let Some(global) = globals.parse(|global, _| Ok(global))? {
+                    definitions.features.val_type(global.value_type());
+                    definitions.globals.push(global);
+                }
+            }
+            KnownSection::Export(mut exports) => {
+                while let Some(export) = exports.parse()? {
+                    let signature = match *export.kind() {
+                        ExportKind::Function(index) => definitions
+                            .functions
+                            .get(usize::from(index))
+                            .ok_or_else(|| unknown_index("function", index.to_u32()))
+                            .and_then(|ty| definitions.func_type(*ty))
+                            .map(Signature::Function)?,
+                        ExportKind::Table(index) => manifest
+                            .tables
+                            .get(usize::from(index))
+                            .map(|table| Signature::Table(*table))
+                            .ok_or_else(|| unknown_index("table", index.to_u32()))?,
+                        ExportKind::Memory(index) => manifest
+                            .memories
+                            .get(usize::from(index))
+                            .map(|memory| Signature::Memory(*memory))
+                            .ok_or_else(|| unknown_index("memory", index.to_u32()))?,
+                        ExportKind::Global(index) => {
+                            let global = *definitions
+                                .globals
+                                .get(usize::from(index))
+                                .ok_or_else(|| unknown_index("global", index.to_u32()))?;
+                            global_type(&mut definitions, global);
+                            Signature::Global(global)
+                        }
+                        ExportKind::Tag(index) => definitions
+                            .tags
+                            .get(usize::from(index))
+                            .ok_or_else(|| unknown_index("tag", index.to_u32()))
+                            .and_then(|ty| definitions.func_type(*ty))
+                            .map(Signature::Tag)?,
+                    };
+
+                    manifest.exports.push(ExportSummary {
+                        name: export.name().try_into_string()?,
+                        signature,
+                    });
+                }
+            }
+            KnownSection::DataCount(_) => {
+                definitions.features.insert(Feature::BulkMemory);
+            }
+            KnownSection::Tag(tags) => {
+                for result in tags {
+                    let Tag::Exception(ty) = result?;
+                    definitions.features.insert(Feature::Exceptions);
+                    definitions.tags.push(ty);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    manifest.functions = definitions
+        .functions
+        .iter()
+        .map(|ty| definitions.func_type(*ty))
+        .collect::<Parsed<_>>()?;
+    manifest.features = definitions.features.features().collect();
+    Ok(manifest)
+}
+
+/// Records the features required by an imported or exported global.
+fn global_type(definitions: &mut Definitions, global: GlobalType) {
+    definitions.features.external_global(global);
+    definitions.features.val_type(global.value_type());
+}
+
+fn producers_section<I: Input>(
+    mut producers: ProducersSection<I>,
+    output: &mut Vec<Producer>,
+) -> Parsed<()> {
+    while let Some(field) = producers.parse()? {
+        let field_name = field.name().try_into_string()?;
+        for result in field.values() {
+            let value = result?;
+            output.push(Producer {
+                field: field_name.clone(),
+                name: value.name().try_into_string()?,
+                version: value.version().try_into_string()?,
+            });
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,117 @@
+//! Provides the [`MemUsage`] trait, used to account for the heap memory owned by long-lived parser
+//! objects.
+
+use alloc::{boxed::Box, collections::BTreeMap, string::String, vec::Vec};
+use core::mem::size_of;
+
+/// Trait for types that can report an approximation of the amount of heap memory they own.
+///
+/// This allows services that embed the parser to account for memory usage on a per-request
+/// basis, for example, by summing the usage of all [`parser::Error`](crate::parser::Error)s that
+/// are retained.
+///
+/// This trait is implemented for parser errors, and for the caches and indexes built from a module
+/// that are typically kept alive alongside it, such as
+/// [`SignatureResolver`](crate::component::SignatureResolver),
+/// [`CodeSectionIndex`](crate::component::CodeSectionIndex), and
+/// [`ExportIndex`](crate::analysis::ExportIndex). Memory owned by an [`Input`](crate::input::Input)
+/// is never included.
+///
+/// The following types are not expected to be retained between requests, and do not implement
+/// this trait:
+/// - Builders and other types used to write modules, such as those in [`edit`](crate::edit).
+/// - Reports that summarize a module, such as the [`Manifest`](crate::manifest::Manifest),
+///   [`SizeProfile`](crate::analysis::SizeProfile), and
+///   [`BatchReport`](crate::batch::BatchReport).
+/// - The [`TypeChecker`](crate::instruction_set::TypeChecker), whose state only lasts for the
+///   validation of a single function.
+///
+/// # Example
+///
+/// ```
+/// use wasmiter::mem_usage::MemUsage as _;
+///
+/// let error = wasmiter::parse_module_sections([0u8; 8].as_slice()).unwrap_err();
+/// assert!(error.mem_usage() > 0);
+/// ```
+pub trait MemUsage {
+    /// Returns the approximate number of bytes of heap memory owned by `self`.
+    ///
+    /// The returned value does not include the size of `self`, as it may not be stored on the
+    /// heap.
+    fn mem_usage(&self) -> usize;
+}
+
+impl<T: MemUsage + ?Sized> MemUsage for &T {
+    #[inline]
+    fn mem_usage(&self) -> usize {
+        T::mem_usage(self)
+    }
+}
+
+impl<T: MemUsage> MemUsage for Option<T> {
+    #[inline]
+    fn mem_usage(&self) -> usize {
+        self.as_ref().map_or(0, T::mem_usage)
+    }
+}
+
+impl<T: MemUsage, E: MemUsage> MemUsage for Result<T, E> {
+    #[inline]
+    fn mem_usage(&self) -> usize {
+        match self {
+            Ok(value) => value.mem_usage(),
+            Err(error) => error.mem_usage(),
+        }
+    }
+}
+
+impl<T: MemUsage> MemUsage for [T] {
+    fn mem_usage(&self) -> usize {
+        self.iter().map(T::mem_usage).sum()
+    }
+}
+
+impl<T: MemUsage + ?Sized> MemUsage for Box<T> {
+    #[inline]
+    fn mem_usage(&self) -> usize {
+        core::mem::size_of_val::<T>(self) + T::mem_usage(self)
+    }
+}
+
+impl<T: MemUsage> MemUsage for Vec<T> {
+    #[inline]
+    fn mem_usage(&self) -> usize {
+        self.capacity() * size_of::<T>() + self.as_slice().mem_usage()
+    }
+}
+
+impl MemUsage for String {
+    #[inline]
+    fn mem_usage(&self) -> usize {
+        self.capacity()
+    }
+}
+
+impl<K: MemUsage, V: MemUsage> MemUsage for BTreeMap<K, V> {
+    fn mem_usage(&self) -> usize {
+        self.iter()
+            .map(|(key, value)| key.mem_usage() + value.mem_usage())
+            .sum::<usize>()
+            + btree_map_entries(self)
+    }
+}
+
+/// Approximates the heap memory used by the elements of a [`Vec`], for elements that do not own
+/// any heap memory.
+#[inline]
+pub(crate) fn vec_capacity<T>(vec: &Vec<T>) -> usize {
+    vec.capacity() * size_of::<T>()
+}
+
+/// Approximates the heap memory used to store the entries of a [`BTreeMap`], excluding any heap
+/// memory owned by the keys and values.
+#[inline]
+pub(crate) fn btree_map_entries<K, V>(map: &BTreeMap<K, V>) -> usize {
+    map.len() * (size_of::<K>() + size_of::<V>())
+}
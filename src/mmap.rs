@@ -5,7 +5,8 @@ use crate::{
 use memmap2::Mmap;
 use std::path::Path;
 
-fn parse_module_sections_inner(path: &Path) -> parser::Parsed<SectionSequence<Mmap>> {
+/// Memory maps the file at the given [`Path`].
+pub(crate) fn map_file(path: &Path) -> parser::Parsed<Mmap> {
     let file = std::fs::File::open(path).with_context(|| {
         let path_buf = Box::<Path>::from(path);
         move |f| write!(f, "could not open file at path {}", path_buf.display())
@@ -14,10 +15,14 @@ fn parse_module_sections_inner(path: &Path) -> parser::Parsed<SectionSequence<Mm
     // Safety: See documentation for Input impl on Mmap on how unsafe behavior is "ignored"
     let binary = unsafe { memmap2::Mmap::map(&file) };
 
-    crate::parse_module_sections(binary.with_context(|| {
+    binary.with_context(|| {
         let path_buf = Box::<Path>::from(path);
         move |f| write!(f, "could not open file at path {}", path_buf.display())
-    })?)
+    })
+}
+
+fn parse_module_sections_inner(path: &Path) -> parser::Parsed<SectionSequence<Mmap>> {
+    crate::parse_module_sections(map_file(path)?)
 }
 
 /// Opens a memory-mapped file containing a
@@ -0,0 +1,430 @@
+use crate::{
+    component::{self, ImportKind},
+    custom::{name::NameSection, CustomSection},
+    index::{FuncIdx, TypeIdx},
+    input::{BorrowInput as _, HasInput as _, Input, Window},
+    parser::{self, Parsed, ResultExt as _},
+    sections::{id as section_id, Section, SectionSequence},
+};
+
+#[derive(Clone, Copy, Debug)]
+struct SectionRange {
+    offset: u64,
+    length: u64,
+}
+
+impl SectionRange {
+    fn of<I: Input>(contents: &Window<I>) -> Self {
+        Self {
+            offset: contents.base(),
+            length: contents.length(),
+        }
+    }
+}
+
+#[inline(never)]
+#[cold]
+fn duplicate_section(id: u8, offset: u64) -> parser::Error {
+    parser::Error::new(parser::ErrorKind::DuplicateSection(id))
+        .with_location_context("section", offset)
+}
+
+const KNOWN_SECTION_COUNT: usize = section_id::TAG as usize + 1;
+
+/// A WebAssembly module whose sections were located in a single pass, providing access to the
+/// contents of each section without having to search for it again.
+///
+/// Only the location of each section is stored, along with the number of imported functions. The
+/// contents of a section are parsed each time an accessor such as [`Module::types()`] is called,
+/// so no allocations are needed.
+///
+/// Modules containing more than one non-custom section with the same *id* are rejected, as in
+/// [`visit_module_sections`](crate::visit::visit_module_sections).
+///
+/// # Example
+///
+/// ```
+/// use wasmiter::{index::FuncIdx, types::ValType};
+///
+/// let wasm = wat::parse_str(r#"(module
+///     (import "env" "log" (func (param i32)))
+///     (func (param i64) (result i64) local.get 0))"#).unwrap();
+///
+/// let module = wasmiter::Module::parse(wasm.as_slice())?;
+/// assert_eq!(module.imported_function_count(), 1);
+/// assert_eq!(module.code()?.unwrap().remaining_count(), 1);
+///
+/// # #[cfg(feature = "alloc")]
+/// # {
+/// let signature = module.func_type(FuncIdx::from(1u32))?.unwrap();
+/// assert_eq!(signature.parameters(), &[ValType::I64]);
+/// assert_eq!(signature.results(), &[ValType::I64]);
+/// # }
+/// # Ok::<(), wasmiter::parser::Error>(())
+/// ```
+#[derive(Clone, Copy)]
+pub struct Module<I: Input> {
+    sections: SectionSequence<I>,
+    known: [Option<SectionRange>; KNOWN_SECTION_COUNT],
+    names: Option<SectionRange>,
+    imported_function_count: u32,
+}
+
+impl<I: Input> Module<I> {
+    /// Reads all of the `sections` of a module, recording the location of each known section and
+    /// the `name` custom section.
+    ///
+    /// The *import section* is also read to count the number of imported functions.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a section, the name of a custom section, or an import could not be
+    /// parsed, or if a known section appeared more than once.
+    pub fn new(sections: SectionSequence<I>) -> Parsed<Self> {
+        let mut known = [None; KNOWN_SECTION_COUNT];
+        let mut names = None;
+        let mut imported_function_count = 0u32;
+
+        for result in sections.borrow_input() {
+            let section = result?;
+            let id = usize::from(section.id());
+            if section.id() == section_id::CUSTOM {
+                if names.is_none() {
+                    let custom = CustomSection::try_from_section(section)
+                        .ok()
+                        .transpose()
+                        .context("custom section")?;
+
+                    if let Some(custom) = custom {
+                        if custom.name().try_eq_str(section_id::NAME)? {
+                            names = Some(SectionRange::of(custom.contents()));
+                        }
+                    }
+                }
+            } else if let Some(range) = known.get_mut(id) {
+                if range.is_some() {
+                    return Err(duplicate_section(section.id(), section.contents().base()));
+                }
+
+                *range = Some(SectionRange::of(section.contents()));
+
+                if section.id() == section_id::IMPORT {
+                    let contents = section.into_contents();
+                    let mut imports = component::ImportsComponent::new(contents.base(), contents)?;
+                    while let Some(import) = imports.parse()? {
+                        if let ImportKind::Function(_) = import.kind() {
+                            imported_function_count = imported_function_count.saturating_add(1);
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(Self {
+            sections,
+            known,
+            names,
+            imported_function_count,
+        })
+    }
+
+    /// Reads a [WebAssembly module binary](https://webassembly.github.io/spec/core/binary/index.html),
+    /// as in [`parse_module_sections()`](crate::parse_module_sections), then locates each of its
+    /// sections with [`Module::new()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the preamble or any section could not be parsed.
+    pub fn parse(binary: I) -> Parsed<Self> {
+        Self::new(crate::parse_module_sections(binary)?)
+    }
+
+    /// Gets the sequence of sections in the module.
+    #[inline]
+    pub fn sections(&self) -> &SectionSequence<I> {
+        &self.sections
+    }
+
+    /// Gets the number of functions imported by the module.
+    #[inline]
+    pub fn imported_function_count(&self) -> u32 {
+        self.imported_function_count
+    }
+
+    fn window(&self, range: SectionRange) -> Window<&I> {
+        Window::with_offset_and_length(self.sections.input(), range.offset, range.length)
+    }
+
+    /// Gets the first section with the given [*id*](section_id), or `None` if the module does not
+    /// contain one.
+    ///
+    /// Custom sections cannot be retrieved with this method.
+    pub fn section(&self, id: u8) -> Option<Section<&I>> {
+        let range = (*self.known.get(usize::from(id))?)?;
+        Some(Section::new(id, self.window(range)))
+    }
+
+    fn component<'a, T>(
+        &'a self,
+        id: u8,
+        f: impl FnOnce(u64, Window<&'a I>) -> Parsed<T>,
+    ) -> Parsed<Option<T>> {
+        match self.known[usize::from(id)] {
+            Some(range) => f(range.offset, self.window(range)).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Gets the *type section*, or `Ok(None)` if the module does not have one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of types could not be parsed.
+    pub fn types(&self) -> Parsed<Option<component::TypesComponent<Window<&I>>>> {
+        self.component(section_id::TYPE, component::TypesComponent::new)
+    }
+
+    /// Gets the *import section*, or `Ok(None)` if the module does not have one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of imports could not be parsed.
+    pub fn imports(&self) -> Parsed<Option<component::ImportsComponent<Window<&I>>>> {
+        self.component(section_id::IMPORT, component::ImportsComponent::new)
+    }
+
+    /// Gets the *function section*, or `Ok(None)` if the module does not have one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of functions could not be parsed.
+    pub fn functions(&self) -> Parsed<Option<component::FunctionSection<Window<&I>>>> {
+        self.component(section_id::FUNC, component::FunctionSection::new)
+    }
+
+    /// Gets the *table section*, or `Ok(None)` if the module does not have one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of tables could not be parsed.
+    pub fn tables(&self) -> Parsed<Option<component::TablesComponent<Window<&I>>>> {
+        self.component(section_id::TABLE, component::TablesComponent::new)
+    }
+
+    /// Gets the *memory section*, or `Ok(None)` if the module does not have one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of memories could not be parsed.
+    pub fn memories(&self) -> Parsed<Option<component::MemsComponent<Window<&I>>>> {
+        self.component(section_id::MEMORY, component::MemsComponent::new)
+    }
+
+    /// Gets the *global section*, or `Ok(None)` if the module does not have one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of globals could not be parsed.
+    pub fn globals(&self) -> Parsed<Option<component::GlobalsComponent<Window<&I>>>> {
+        self.component(section_id::GLOBAL, component::GlobalsComponent::new)
+    }
+
+    /// Gets the *export section*, or `Ok(None)` if the module does not have one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of exports could not be parsed.
+    pub fn exports(&self) -> Parsed<Option<component::ExportsComponent<Window<&I>>>> {
+        self.component(section_id::EXPORT, component::ExportsComponent::new)
+    }
+
+    /// Gets the function specified in the *start section*, or `Ok(None)` if the module does not
+    /// have one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the index of the function could not be parsed.
+    pub fn start(&self) -> Parsed<Option<FuncIdx>> {
+        self.component(section_id::START, |mut offset, contents| {
+            component::index(&mut offset, contents).context("start section")
+        })
+    }
+
+    /// Gets the *element section*, or `Ok(None)` if the module does not have one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of element segments could not be parsed.
+    pub fn elements(&self) -> Parsed<Option<component::ElemsComponent<Window<&I>>>> {
+        self.component(section_id::ELEMENT, component::ElemsComponent::new)
+    }
+
+    /// Gets the *code section*, or `Ok(None)` if the module does not have one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of entries could not be parsed.
+    pub fn code(&self) -> Parsed<Option<component::CodeSection<Window<&I>>>> {
+        self.component(section_id::CODE, component::CodeSection::new)
+    }
+
+    /// Gets the *data section*, or `Ok(None)` if the module does not have one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of data segments could not be parsed.
+    pub fn datas(&self) -> Parsed<Option<component::DatasComponent<Window<&I>>>> {
+        self.component(section_id::DATA, component::DatasComponent::new)
+    }
+
+    /// Gets the number of data segments specified in the *data count section*, or `Ok(None)` if
+    /// the module does not have one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the count could not be parsed.
+    pub fn data_count(&self) -> Parsed<Option<u32>> {
+        self.component(section_id::DATA_COUNT, |mut offset, contents| {
+            parser::leb128::u32(&mut offset, contents).context("data count section")
+        })
+    }
+
+    /// Gets the *tag section*, or `Ok(None)` if the module does not have one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of tags could not be parsed.
+    pub fn tags(&self) -> Parsed<Option<component::TagsComponent<Window<&I>>>> {
+        self.component(section_id::TAG, component::TagsComponent::new)
+    }
+
+    /// Gets the subsections of the first
+    /// [`name` custom section](crate::custom::name), or `None` if the module does not have one.
+    pub fn names(&self) -> Option<NameSection<Window<&I>>> {
+        let range = self.names?;
+        Some(NameSection::new(SectionSequence::new(
+            range.offset,
+            self.window(range),
+        )))
+    }
+
+    /// Gets the index of the type of the given `function`, which is either specified in its
+    /// import or in the *function section*.
+    ///
+    /// Returns `Ok(None)` if the `function` does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an import or the *function section* could not be parsed.
+    pub fn func_type_index(&self, function: FuncIdx) -> Parsed<Option<TypeIdx>> {
+        let index = function.to_u32();
+        if let Some(defined) = index.checked_sub(self.imported_function_count) {
+            return match self.functions()? {
+                Some(mut functions) => functions.nth(crate::int::u32_to_usize(defined)).transpose(),
+                None => Ok(None),
+            };
+        }
+
+        let mut imports = match self.imports()? {
+            Some(imports) => imports,
+            None => return Ok(None),
+        };
+
+        let mut remaining = index;
+        while let Some(import) = imports.parse()? {
+            if let ImportKind::Function(ty) = import.kind() {
+                if remaining == 0 {
+                    return Ok(Some(*ty));
+                }
+
+                remaining -= 1;
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Gets the signature of the given `function` by looking up the index of its type with
+    /// [`Module::func_type_index()`], then reading the type from the *type section*.
+    ///
+    /// Returns `Ok(None)` if the `function` or its type does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an import, the *function section*, or the *type section* could not be
+    /// parsed.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    pub fn func_type(&self, function: FuncIdx) -> Parsed<Option<crate::types::FuncType>> {
+        use alloc::vec::Vec;
+
+        let index = match self.func_type_index(function)? {
+            Some(index) => index,
+            None => return Ok(None),
+        };
+
+        let mut types = match self.types()? {
+            Some(types) => types,
+            None => return Ok(None),
+        };
+
+        for _ in 0..index.to_u32() {
+            if types.parse(|_| Ok(()), |(), _| Ok(()))?.is_none() {
+                return Ok(None);
+            }
+        }
+
+        types.parse(
+            |parameters| parameters.collect::<Parsed<Vec<_>>>(),
+            |parameters, results| {
+                Ok(crate::types::FuncType::new(
+                    parameters,
+                    results.collect::<Parsed<Vec<_>>>()?,
+                ))
+            },
+        )
+    }
+
+    /// Reads the *type*, *import*, and *function* sections into a
+    /// [`SignatureResolver`](component::SignatureResolver), which is faster than
+    /// [`Module::func_type()`] when the signatures of many functions are needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a type, an import, or an entry in the *function section* could not be
+    /// parsed.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    pub fn signature_resolver(&self) -> Parsed<component::SignatureResolver> {
+        component::SignatureResolver::from_components(
+            self.types()?,
+            self.imports()?,
+            self.functions()?,
+        )
+    }
+}
+
+impl<I: Input> core::fmt::Debug for Module<I> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        struct KnownSections<'a>(&'a [Option<SectionRange>]);
+
+        impl core::fmt::Debug for KnownSections<'_> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.debug_map()
+                    .entries(
+                        self.0
+                            .iter()
+                            .enumerate()
+                            .filter_map(|(id, range)| Some((id, (*range)?))),
+                    )
+                    .finish()
+            }
+        }
+
+        f.debug_struct("Module")
+            .field("sections", &KnownSections(&self.known))
+            .field("names", &self.names)
+            .field("imported_function_count", &self.imported_function_count)
+            .finish()
+    }
+}
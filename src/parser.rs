@@ -3,9 +3,12 @@
 use crate::input::Input;
 
 mod ascending_order;
+#[cfg_attr(feature = "backtrace", clippy::msrv = "1.65")]
 mod error;
 mod offset;
+mod position;
 mod result_ext;
+mod span;
 mod vector;
 
 pub mod leb128;
@@ -15,8 +18,10 @@ pub(crate) use ascending_order::AscendingOrder;
 pub(crate) use error::{Context, ErrorKind};
 pub(crate) use result_ext::ResultExt;
 
-pub use error::Error;
+pub use error::{Error, ErrorCode};
 pub use offset::Offset;
+pub use position::Position;
+pub use span::{Span, Spanned, SpannedItems, SpannedIter};
 pub use vector::Vector;
 
 /// Result type used when parsing bytes from an [`Input`].
@@ -17,12 +17,14 @@ pub(crate) enum ErrorKind {
     BadInput(crate::input::Error),
     BadWasmMagic,
     UnsupportedWasmVersion(u32),
+    UnexpectedComponent(u16),
     InvalidOpcode(crate::instruction_set::InvalidOpcode),
     EmptyBlockTypeInValType,
     TypeIndexInValType(crate::index::TypeIdx),
-    ExpectedRefType(crate::types::ValType),
     BadElementKind(u8),
     BadTagAttribute(u8),
+    #[cfg(feature = "exceptions")]
+    BadCatchClauseKind(u8),
     BadDataSegmentMode(u32),
     BadElementSegmentMode(u32),
     BadExportKind(u8),
@@ -30,16 +32,34 @@ pub(crate) enum ErrorKind {
     BadGlobalMutability(u8),
     BadLimitFlags(u8),
     BadFuncTypeTag(u8),
+    BadRelocationType(u8),
+    BadSymbolKind(u8),
+    BadComdatSymbolKind(u8),
+    UnsupportedLinkingVersion(u32),
     BadMemArgAlignPower(u32),
     BranchTableCountOverflow,
     BlockNestingCounterOverflow,
+    LocalCountOverflow,
+    LocalLimitExceeded(u32),
+    #[cfg(feature = "alloc")]
+    MergeConflict(&'static str),
+    #[cfg(feature = "alloc")]
+    RenameConflict(&'static str),
+    #[cfg(feature = "exceptions")]
     ExpectedEndInstructionButGotDelegate,
     MissingEndInstructions(u32),
+    DisabledInstruction(crate::instruction_set::Opcode),
+    ExpectedIfInstruction,
+    TrailingBytes(u32),
+    BadIfBlockTerminator(crate::instruction_set::Opcode),
+    PositionMismatch,
+    DuplicateSection(u8),
     VarLenIntTooLarge {
         bits: u8,
         signed: bool,
     },
     InvalidFormat,
+    Custom(Context),
 }
 
 impl Display for ErrorKind {
@@ -56,6 +76,10 @@ impl Display for ErrorKind {
             Self::UnsupportedWasmVersion(bad) => {
                 write!(f, "unsupported WebAssembly version {bad} ({bad:#010X})")
             }
+            Self::UnexpectedComponent(version) => write!(
+                f,
+                "expected WebAssembly module, but got a component with version {version:#06X}"
+            ),
             Self::InvalidOpcode(err) => Display::fmt(err, f),
             Self::EmptyBlockTypeInValType => {
                 f.write_str("expected value type but got empty block type")
@@ -63,9 +87,10 @@ impl Display for ErrorKind {
             Self::TypeIndexInValType(idx) => {
                 write!(f, "expected value type but got type index {idx:?}")
             }
-            Self::ExpectedRefType(actual) => write!(f, "expected reference type but got {actual}"),
             Self::BadElementKind(bad) => write!(f, "{bad:#04X} is not a valid elemkind"),
             Self::BadTagAttribute(bad) => write!(f, "{bad:#04X} is not a valid tag attribute"),
+            #[cfg(feature = "exceptions")]
+            Self::BadCatchClauseKind(bad) => write!(f, "{bad:#04X} is not a known catch clause"),
             Self::BadDataSegmentMode(bad) => {
                 write!(f, "{bad} is not a supported data segment mode")
             }
@@ -74,6 +99,16 @@ impl Display for ErrorKind {
             }
             Self::BadExportKind(bad) => write!(f, "{bad:#04X} is not a known export kind"),
             Self::BadImportKind(bad) => write!(f, "{bad:#04X} is not a known import kind"),
+            Self::BadRelocationType(bad) => {
+                write!(f, "{bad} is not a known relocation type")
+            }
+            Self::BadSymbolKind(bad) => write!(f, "{bad} is not a known symbol kind"),
+            Self::BadComdatSymbolKind(bad) => {
+                write!(f, "{bad} is not a known COMDAT symbol kind")
+            }
+            Self::UnsupportedLinkingVersion(bad) => {
+                write!(f, "unsupported linking metadata version {bad}")
+            }
             Self::BadGlobalMutability(bad) => {
                 write!(f, "{bad:#04X} is not a valid global mutability flag")
             }
@@ -90,6 +125,18 @@ impl Display for ErrorKind {
                 u32::MAX as u64 + 1
             ),
             Self::BlockNestingCounterOverflow => f.write_str("block nesting counter overflowed"),
+            Self::LocalCountOverflow => f.write_str("function has too many locals"),
+            Self::LocalLimitExceeded(limit) => {
+                write!(
+                    f,
+                    "function declares more than the maximum of {limit} locals"
+                )
+            }
+            #[cfg(feature = "alloc")]
+            Self::MergeConflict(reason) => write!(f, "could not merge modules, {reason}"),
+            #[cfg(feature = "alloc")]
+            Self::RenameConflict(reason) => write!(f, "could not rename exports, {reason}"),
+            #[cfg(feature = "exceptions")]
             Self::ExpectedEndInstructionButGotDelegate => {
                 f.write_str("expected end instruction to mark end of expression, but got delegate")
             }
@@ -102,6 +149,37 @@ impl Display for ErrorKind {
                     "blocks are not structured correctly, {missing} end instructions were missing"
                 )
             }
+            Self::DisabledInstruction(opcode) => {
+                use crate::instruction_set::Opcode;
+
+                match opcode {
+                    Opcode::PrefixV128 => f.write_str("support for vector instructions"),
+                    Opcode::PrefixFE => f.write_str("support for atomic instructions"),
+                    _ => write!(f, "support for the {opcode:?} instruction"),
+                }?;
+
+                let feature = match opcode {
+                    Opcode::ReturnCall | Opcode::ReturnCallIndirect => "tail-call",
+                    Opcode::CallRef
+                    | Opcode::ReturnCallRef
+                    | Opcode::RefAsNonNull
+                    | Opcode::BrOnNull
+                    | Opcode::BrOnNonNull => "function-references",
+                    Opcode::PrefixV128 => "simd",
+                    Opcode::PrefixFE => "threads",
+                    _ => "exceptions",
+                };
+
+                write!(f, " was disabled, enable the {feature:?} feature")
+            }
+            Self::ExpectedIfInstruction => f.write_str("expected if instruction"),
+            Self::TrailingBytes(count) => write!(f, "{count} bytes were left over after parsing"),
+            Self::BadIfBlockTerminator(opcode) => write!(
+                f,
+                "expected end or else instruction in if block, but got {opcode:?}"
+            ),
+            Self::PositionMismatch => f.write_str("position was obtained from a different input"),
+            Self::DuplicateSection(id) => write!(f, "duplicate section with id {id}"),
             Self::VarLenIntTooLarge { bits, signed } => {
                 let signedness = if *signed { "signed" } else { "unsigned" };
                 write!(
@@ -110,6 +188,168 @@ impl Display for ErrorKind {
                 )
             }
             Self::InvalidFormat => f.write_str("input was malformed"),
+            #[cfg(feature = "alloc")]
+            Self::Custom(message) => Display::fmt(message, f),
+            #[cfg(not(feature = "alloc"))]
+            Self::Custom(_) => f.write_str("an error occured outside of the parser"),
+        }
+    }
+}
+
+/// Identifies the kind of an [`Error`], allowing callers to distinguish between errors without
+/// inspecting their messages.
+///
+/// The discriminant of each variant is stable, and can be obtained with an `as` cast to
+/// [`u16`]. Variants may be added in future versions, but existing discriminants will not change.
+///
+/// # Example
+///
+/// ```
+/// use wasmiter::parser::ErrorCode;
+///
+/// let error = wasmiter::parse_module_sections(b"\0asm\x02\0\0\0".as_slice()).unwrap_err();
+/// assert_eq!(error.code(), ErrorCode::UnsupportedWasmVersion);
+/// assert_eq!(error.code() as u16, 6);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[non_exhaustive]
+#[repr(u16)]
+pub enum ErrorCode {
+    /// An I/O error occured.
+    Io = 1,
+    /// An index could not be converted.
+    BadIndexConversion = 2,
+    /// A name was not valid UTF-8.
+    BadStringEncoding = 3,
+    /// Reading from the [`Input`](crate::input::Input) failed, see [`Error::input_error`].
+    BadInput = 4,
+    /// The input did not start with the WebAssembly magic bytes `\0asm`.
+    BadWasmMagic = 5,
+    /// The version field of the preamble is not supported.
+    UnsupportedWasmVersion = 6,
+    /// A component was encountered where a module was expected.
+    UnexpectedComponent = 7,
+    /// A byte was not a known instruction opcode.
+    InvalidOpcode = 8,
+    /// A value type was expected, but an empty block type or a type index was encountered.
+    BadValType = 9,
+    /// An element segment had an unknown *elemkind*.
+    BadElementKind = 10,
+    /// A tag had an unknown attribute.
+    BadTagAttribute = 11,
+    /// A catch clause of a **try_table** instruction had an unknown kind.
+    BadCatchClauseKind = 12,
+    /// A data segment had an unknown mode.
+    BadDataSegmentMode = 13,
+    /// An element segment had an unknown mode.
+    BadElementSegmentMode = 14,
+    /// An export had an unknown kind.
+    BadExportKind = 15,
+    /// An import had an unknown kind.
+    BadImportKind = 16,
+    /// A global type had an unknown mutability flag.
+    BadGlobalMutability = 17,
+    /// Limits had unknown flags.
+    BadLimitFlags = 18,
+    /// A function type did not start with `0x60`.
+    BadFuncTypeTag = 19,
+    /// A relocation entry had an unknown type.
+    BadRelocationType = 20,
+    /// A symbol table entry had an unknown kind.
+    BadSymbolKind = 21,
+    /// A COMDAT symbol had an unknown kind.
+    BadComdatSymbolKind = 22,
+    /// The version of the `linking` custom section is not supported.
+    UnsupportedLinkingVersion = 23,
+    /// The alignment of a memory argument was too large.
+    BadMemArgAlignment = 24,
+    /// A **br_table** instruction had too many labels.
+    BranchTableCountOverflow = 25,
+    /// Blocks were nested too deeply.
+    BlockNestingCounterOverflow = 26,
+    /// A function declared too many locals.
+    LocalCountOverflow = 27,
+    /// Modules could not be merged.
+    MergeConflict = 28,
+    /// Exports could not be renamed.
+    RenameConflict = 29,
+    /// A **delegate** instruction was encountered at the end of an expression.
+    UnexpectedDelegate = 30,
+    /// An expression was missing **end** instructions.
+    MissingEndInstructions = 31,
+    /// An instruction was encountered whose cargo feature is disabled.
+    DisabledInstruction = 32,
+    /// An **if** block was not structured correctly.
+    BadIfBlock = 33,
+    /// Bytes were left over after parsing.
+    TrailingBytes = 34,
+    /// A position was used with a different [`Input`](crate::input::Input).
+    PositionMismatch = 35,
+    /// A section appeared more than once.
+    DuplicateSection = 36,
+    /// A LEB128 encoded integer was too large.
+    IntegerTooLarge = 37,
+    /// The input was malformed in some other way.
+    InvalidFormat = 38,
+    /// The error was created with [`Error::custom`].
+    Custom = 39,
+}
+
+impl ErrorKind {
+    fn code(&self) -> ErrorCode {
+        match self {
+            #[cfg(feature = "std")]
+            ErrorKind::IO(_) => ErrorCode::Io,
+            #[cfg(feature = "alloc")]
+            ErrorKind::BadIndexConversion(_) => ErrorCode::BadIndexConversion,
+            #[cfg(feature = "alloc")]
+            ErrorKind::BadStringEncoding(_) => ErrorCode::BadStringEncoding,
+            ErrorKind::BadInput(_) => ErrorCode::BadInput,
+            ErrorKind::BadWasmMagic => ErrorCode::BadWasmMagic,
+            ErrorKind::UnsupportedWasmVersion(_) => ErrorCode::UnsupportedWasmVersion,
+            ErrorKind::UnexpectedComponent(_) => ErrorCode::UnexpectedComponent,
+            ErrorKind::InvalidOpcode(_) => ErrorCode::InvalidOpcode,
+            ErrorKind::EmptyBlockTypeInValType | ErrorKind::TypeIndexInValType(_) => {
+                ErrorCode::BadValType
+            }
+            ErrorKind::BadElementKind(_) => ErrorCode::BadElementKind,
+            ErrorKind::BadTagAttribute(_) => ErrorCode::BadTagAttribute,
+            #[cfg(feature = "exceptions")]
+            ErrorKind::BadCatchClauseKind(_) => ErrorCode::BadCatchClauseKind,
+            ErrorKind::BadDataSegmentMode(_) => ErrorCode::BadDataSegmentMode,
+            ErrorKind::BadElementSegmentMode(_) => ErrorCode::BadElementSegmentMode,
+            ErrorKind::BadExportKind(_) => ErrorCode::BadExportKind,
+            ErrorKind::BadImportKind(_) => ErrorCode::BadImportKind,
+            ErrorKind::BadGlobalMutability(_) => ErrorCode::BadGlobalMutability,
+            ErrorKind::BadLimitFlags(_) => ErrorCode::BadLimitFlags,
+            ErrorKind::BadFuncTypeTag(_) => ErrorCode::BadFuncTypeTag,
+            ErrorKind::BadRelocationType(_) => ErrorCode::BadRelocationType,
+            ErrorKind::BadSymbolKind(_) => ErrorCode::BadSymbolKind,
+            ErrorKind::BadComdatSymbolKind(_) => ErrorCode::BadComdatSymbolKind,
+            ErrorKind::UnsupportedLinkingVersion(_) => ErrorCode::UnsupportedLinkingVersion,
+            ErrorKind::BadMemArgAlignPower(_) => ErrorCode::BadMemArgAlignment,
+            ErrorKind::BranchTableCountOverflow => ErrorCode::BranchTableCountOverflow,
+            ErrorKind::BlockNestingCounterOverflow => ErrorCode::BlockNestingCounterOverflow,
+            ErrorKind::LocalCountOverflow | ErrorKind::LocalLimitExceeded(_) => {
+                ErrorCode::LocalCountOverflow
+            }
+            #[cfg(feature = "alloc")]
+            ErrorKind::MergeConflict(_) => ErrorCode::MergeConflict,
+            #[cfg(feature = "alloc")]
+            ErrorKind::RenameConflict(_) => ErrorCode::RenameConflict,
+            #[cfg(feature = "exceptions")]
+            ErrorKind::ExpectedEndInstructionButGotDelegate => ErrorCode::UnexpectedDelegate,
+            ErrorKind::MissingEndInstructions(_) => ErrorCode::MissingEndInstructions,
+            ErrorKind::DisabledInstruction(_) => ErrorCode::DisabledInstruction,
+            ErrorKind::ExpectedIfInstruction | ErrorKind::BadIfBlockTerminator(_) => {
+                ErrorCode::BadIfBlock
+            }
+            ErrorKind::TrailingBytes(_) => ErrorCode::TrailingBytes,
+            ErrorKind::PositionMismatch => ErrorCode::PositionMismatch,
+            ErrorKind::DuplicateSection(_) => ErrorCode::DuplicateSection,
+            ErrorKind::VarLenIntTooLarge { .. } => ErrorCode::IntegerTooLarge,
+            ErrorKind::InvalidFormat => ErrorCode::InvalidFormat,
+            ErrorKind::Custom(_) => ErrorCode::Custom,
         }
     }
 }
@@ -169,15 +409,27 @@ cfg_if::cfg_if! {
             }
         }
 
+        /// The default value for [`Error::max_context_length`].
+        const DEFAULT_MAX_CONTEXT_LENGTH: usize = 16;
+
+        static MAX_CONTEXT_LENGTH: core::sync::atomic::AtomicUsize =
+            core::sync::atomic::AtomicUsize::new(DEFAULT_MAX_CONTEXT_LENGTH);
+
         struct BoxedError {
             kind: ErrorKind,
+            /// The offset into the input closest to where the error occured.
+            offset: Option<u64>,
             context: Vec<Context>,
+            /// The number of contexts that were not stored, due to the length of `context` reaching
+            /// the [`Error::max_context_length`].
+            omitted_context: usize,
             #[cfg(feature = "backtrace")]
             backtrace: Backtrace,
         }
 
         type ErrorInner = alloc::boxed::Box<BoxedError>;
     } else {
+        #[derive(Debug)]
         pub(crate) struct Context;
 
         impl Context {
@@ -225,7 +477,9 @@ impl Error {
                 Self {
                     inner: Box::new(BoxedError {
                         kind,
+                        offset: None,
                         context: Vec::new(),
+                        omitted_context: 0,
                         #[cfg(feature = "backtrace")]
                         backtrace: Backtrace::capture(),
                     }),
@@ -236,6 +490,53 @@ impl Error {
         }
     }
 
+    /// Creates an [`Error`] indicating that the input was malformed, with the given `message`
+    /// describing the problem.
+    ///
+    /// This allows code outside of this crate, such as parsers for custom sections or
+    /// [`ModuleVisitor`](crate::visit::ModuleVisitor) implementations, to report malformed input
+    /// in the same way as the parsers in this crate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wasmiter::parser::Error;
+    ///
+    /// let error = Error::bad_format("expected a producers field name");
+    /// # #[cfg(feature = "alloc")]
+    /// assert_eq!(format!("{error:#}"), "input was malformed\n- expected a producers field name\n");
+    /// ```
+    #[cold]
+    #[inline(never)]
+    pub fn bad_format(message: &'static str) -> Self {
+        Self::new(ErrorKind::InvalidFormat).with_context(Context::from(message))
+    }
+
+    /// Creates an [`Error`] with a custom `message`, which is displayed in place of the
+    /// description of the error.
+    ///
+    /// Additional context is appended to the error as it is propagated through the parser, as is
+    /// the case for any other [`Error`]. If the `alloc` feature is not enabled, the `message` is
+    /// discarded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wasmiter::parser::Error;
+    ///
+    /// let error = Error::custom(format!("symbol {} is undefined", 42));
+    /// # #[cfg(feature = "alloc")]
+    /// assert_eq!(format!("{error:#}"), "symbol 42 is undefined\n");
+    /// ```
+    #[cold]
+    #[inline(never)]
+    pub fn custom<D>(message: D) -> Self
+    where
+        D: Display + Send + Sync + 'static,
+    {
+        Self::new(ErrorKind::Custom(Context::from_display(message)))
+    }
+
     /// Gets a [`Backtrace`] describing where in the code the error occured.
     #[cfg(feature = "backtrace")]
     #[cfg_attr(doc_cfg, doc(cfg(feature = "backtrace")))]
@@ -244,11 +545,87 @@ impl Error {
         &self.inner.backtrace
     }
 
+    /// Gets the [`input::Error`](crate::input::Error) that caused this error, if it occured when
+    /// reading from the [`Input`](crate::input::Input).
+    ///
+    /// This can be used to check if parsing failed only because the input was incomplete, using
+    /// [`input::ErrorKind::is_incomplete`](crate::input::ErrorKind::is_incomplete).
+    #[inline]
+    pub fn input_error(&self) -> Option<&crate::input::Error> {
+        match &self.inner.kind {
+            ErrorKind::BadInput(error) => Some(error),
+            _ => None,
+        }
+    }
+
+    /// Gets an [`ErrorCode`] describing the kind of error that occured.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wasmiter::parser::ErrorCode;
+    ///
+    /// let error = wasmiter::parse_module_sections(b"\0wasm\x01\0\0".as_slice()).unwrap_err();
+    /// assert_eq!(error.code(), ErrorCode::BadWasmMagic);
+    /// ```
+    #[inline]
+    pub fn code(&self) -> ErrorCode {
+        self.inner.kind.code()
+    }
+
+    /// Gets the offset into the [`Input`](crate::input::Input) closest to where the error
+    /// occured, if it is known.
+    ///
+    /// Offsets are only recorded when the `alloc` feature is enabled, otherwise this always
+    /// returns `None`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// struct Visitor;
+    ///
+    /// impl wasmiter::visit::ModuleVisitor for Visitor {}
+    ///
+    /// // A function body containing the invalid opcode 0xFF
+    /// let wasm = b"\0asm\x01\0\0\0\x0A\x04\x01\x02\0\xFF";
+    /// let error = wasmiter::visit::visit_module(wasm.as_slice(), &mut Visitor).unwrap_err();
+    /// assert_eq!(error.code(), wasmiter::parser::ErrorCode::InvalidOpcode);
+    /// # #[cfg(feature = "alloc")]
+    /// assert_eq!(error.offset(), Some(13));
+    /// ```
+    #[inline]
+    pub fn offset(&self) -> Option<u64> {
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "alloc")] {
+                self.inner.offset
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Records the `offset` where the error occured, unless a more precise offset was already
+    /// recorded.
+    #[inline]
+    pub(crate) fn with_offset(self, offset: u64) -> Self {
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "alloc")] {
+                let mut error = self;
+                error.inner.offset.get_or_insert(offset);
+                error
+            } else {
+                let _ = offset;
+                self
+            }
+        }
+    }
+
     #[inline]
     pub(crate) fn with_location_context(self, description: &'static str, offset: u64) -> Self {
-        self.with_context(Context::from_closure(move |f| {
-            write!(f, "within the {description}, at offset {offset:#X}")
-        }))
+        self.with_offset(offset)
+            .with_context(Context::from_closure(move |f| {
+                write!(f, "within the {description}, at offset {offset:#X}")
+            }))
     }
 }
 
@@ -259,7 +636,11 @@ impl Debug for Error {
         s.field("kind", &self.inner.kind);
 
         #[cfg(feature = "alloc")]
-        s.field("context", &self.inner.context);
+        s.field("offset", &self.inner.offset);
+
+        #[cfg(feature = "alloc")]
+        s.field("context", &self.inner.context)
+            .field("omitted_context", &self.inner.omitted_context);
 
         #[cfg(feature = "backtrace")]
         s.field("backtrace", self.backtrace());
@@ -278,6 +659,10 @@ impl Display for Error {
                 writeln!(f, "- {context}")?;
             }
 
+            if self.inner.omitted_context > 0 {
+                writeln!(f, "- … {} more", self.inner.omitted_context)?;
+            }
+
             #[cfg(feature = "backtrace")]
             if !f.alternate()
                 && self.backtrace().status() == std::backtrace::BacktraceStatus::Captured
@@ -294,18 +679,61 @@ impl Display for Error {
 impl From<crate::input::Error> for Error {
     #[inline]
     fn from(error: crate::input::Error) -> Self {
-        Self::new(ErrorKind::BadInput(error))
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "alloc")] {
+                let offset = error.offset();
+                Self::new(ErrorKind::BadInput(error)).with_offset(offset)
+            } else {
+                Self::new(ErrorKind::BadInput(error))
+            }
+        }
     }
 }
 
 cfg_if::cfg_if! {
     if #[cfg(feature = "alloc")] {
         impl Error {
+            /// Gets the maximum number of context messages that are stored in an [`Error`].
+            ///
+            /// Any additional context beyond this limit is discarded, and is instead indicated by
+            /// a "… N more" marker when the error is displayed. Defaults to `16`.
+            #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
             #[inline]
-            pub(crate) fn with_context(mut self, context: Context) -> Self {
-                self.inner.context.push(context);
+            pub fn max_context_length() -> usize {
+                MAX_CONTEXT_LENGTH.load(core::sync::atomic::Ordering::Relaxed)
+            }
+
+            /// Sets the maximum number of context messages that are stored in an [`Error`], which
+            /// affects all [`Error`]s created afterwards.
+            ///
+            /// Lowering this limit reduces allocator pressure when parsing deeply nested
+            /// malformed input, such as in fuzzing workloads.
+            #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+            #[inline]
+            pub fn set_max_context_length(length: usize) {
+                MAX_CONTEXT_LENGTH.store(length, core::sync::atomic::Ordering::Relaxed)
+            }
+
+            #[inline]
+            pub(crate) fn is_context_full(&self) -> bool {
+                self.inner.context.len() >= Self::max_context_length()
+            }
+
+            #[inline]
+            pub(crate) fn omit_context(mut self) -> Self {
+                self.inner.omitted_context = self.inner.omitted_context.saturating_add(1);
                 self
             }
+
+            #[inline]
+            pub(crate) fn with_context(mut self, context: Context) -> Self {
+                if self.is_context_full() {
+                    self.omit_context()
+                } else {
+                    self.inner.context.push(context);
+                    self
+                }
+            }
         }
 
         impl From<alloc::string::FromUtf8Error> for Error {
@@ -324,6 +752,38 @@ cfg_if::cfg_if! {
     }
 }
 
+#[cfg(feature = "mem-usage")]
+impl crate::mem_usage::MemUsage for Error {
+    fn mem_usage(&self) -> usize {
+        // Memory used by any captured backtrace cannot be determined, and is not included
+        let contexts = self
+            .inner
+            .context
+            .iter()
+            .map(|context| match context {
+                Context::Literal(_) => 0,
+                Context::Boxed(boxed) => {
+                    core::mem::size_of_val::<dyn Display + Send + Sync>(boxed.as_ref())
+                }
+            })
+            .sum::<usize>();
+
+        let kind = match &self.inner.kind {
+            ErrorKind::BadInput(error) => error.mem_usage(),
+            ErrorKind::BadStringEncoding(error) => error.as_bytes().len(),
+            ErrorKind::Custom(Context::Boxed(boxed)) => {
+                core::mem::size_of_val::<dyn Display + Send + Sync>(boxed.as_ref())
+            }
+            _ => 0,
+        };
+
+        core::mem::size_of::<BoxedError>()
+            + self.inner.context.capacity() * core::mem::size_of::<Context>()
+            + contexts
+            + kind
+    }
+}
+
 cfg_if::cfg_if! {
     if #[cfg(feature = "std")] {
         impl std::error::Error for Error {
@@ -11,6 +11,10 @@ use crate::parser::{Context, Error, ErrorKind, Parsed, ResultExt as _};
 #[doc(hidden)]
 pub mod simple;
 
+mod s33;
+
+pub use s33::S33;
+
 // TODO: Add modules for platform-specific SIMD acceleration
 
 use simple as implementation;
@@ -31,9 +35,9 @@ fn too_large<T>(signed: bool) -> Error {
 #[cold]
 #[inline(never)]
 fn bad_continuation(bytes: &[u8]) -> Error {
-    let length = bytes.len();
     let mut buffer = [0u8; 16];
-    buffer[..length].copy_from_slice(bytes);
+    let length = bytes.len().min(buffer.len());
+    buffer[..length].copy_from_slice(&bytes[..length]);
 
     Error::new(ErrorKind::InvalidFormat).with_context(Context::from_closure(move |f| {
         write!(
@@ -90,3 +94,42 @@ pub fn s32<I: Input>(offset: &mut u64, input: I) -> Parsed<i32> {
 pub fn s64<I: Input>(offset: &mut u64, input: I) -> Parsed<i64> {
     implementation::s64(offset, input).context("could not parse signed 64-bit integer")
 }
+
+/// Attempts to parse a signed 33-bit integer encoded in the
+/// [*LEB128* format](https://webassembly.github.io/spec/core/binary/values.html#integers).
+///
+/// # Errors
+///
+/// Returns an error if the integer is encoded in more than 5 bytes, or if its value is outside of
+/// the range [`S33::MIN`] to [`S33::MAX`].
+///
+/// # Example
+///
+/// ```
+/// use wasmiter::{parser::leb128, types::BlockType};
+///
+/// let mut offset = 0;
+/// let value = leb128::s33(&mut offset, [0x40u8].as_slice())?;
+/// assert_eq!(value.get(), -64);
+/// assert_eq!(value.to_block_type(), Some(BlockType::Empty));
+/// assert_eq!(offset, 1);
+/// # Ok::<(), wasmiter::parser::Error>(())
+/// ```
+pub fn s33<I: Input>(offset: &mut u64, input: I) -> Parsed<S33> {
+    #[cold]
+    #[inline(never)]
+    fn s33_too_large() -> Error {
+        Error::new(ErrorKind::VarLenIntTooLarge {
+            bits: 33,
+            signed: true,
+        })
+    }
+
+    let start = *offset;
+    let value =
+        implementation::s64(offset, input).context("could not parse signed 33-bit integer")?;
+    match S33::new(value) {
+        Some(value) if *offset - start <= 5 => Ok(value),
+        _ => Err(s33_too_large()),
+    }
+}
@@ -0,0 +1,124 @@
+use crate::{
+    index::TypeIdx,
+    types::{BlockType, ValType},
+};
+
+/// A signed 33-bit integer, encoded in the
+/// [*LEB128* format](https://webassembly.github.io/spec/core/binary/values.html#integers).
+///
+/// The WebAssembly binary format uses `s33` values to encode
+/// [block types](https://webassembly.github.io/spec/core/binary/instructions.html#binary-blocktype),
+/// where negative values indicate a [`BlockType::Empty`] or a [`BlockType::Inline`] value type,
+/// and non-negative values indicate a [`BlockType::Index`]. Other proposals reuse this encoding
+/// to distinguish between type indices and type constructors.
+///
+/// To parse an [`S33`], use [`leb128::s33`](crate::parser::leb128::s33).
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct S33(i64);
+
+impl S33 {
+    /// The smallest value that can be represented by an [`S33`], `-2^32`.
+    pub const MIN: Self = Self(-(1 << 32));
+
+    /// The largest value that can be represented by an [`S33`], `2^32 - 1`.
+    pub const MAX: Self = Self((1 << 32) - 1);
+
+    /// Creates a new [`S33`], returning `None` if the `value` is outside of the range
+    /// [`S33::MIN`] to [`S33::MAX`].
+    #[inline]
+    pub const fn new(value: i64) -> Option<Self> {
+        if value >= Self::MIN.0 && value <= Self::MAX.0 {
+            Some(Self(value))
+        } else {
+            None
+        }
+    }
+
+    /// Gets the value as a 64-bit integer.
+    #[inline]
+    pub const fn get(self) -> i64 {
+        self.0
+    }
+
+    /// Interprets the value as a [`TypeIdx`], returning `None` if the value is negative.
+    #[inline]
+    pub fn to_type_index(self) -> Option<TypeIdx> {
+        u32::try_from(self.0).ok().map(TypeIdx::from)
+    }
+
+    /// Interprets the value as a [`BlockType`], returning `None` if the value is negative and
+    /// does not correspond to a known value type.
+    pub fn to_block_type(self) -> Option<BlockType> {
+        Some(match self.0 {
+            -64 => BlockType::Empty,
+            -1 => BlockType::Inline(ValType::I32),
+            -2 => BlockType::Inline(ValType::I64),
+            -3 => BlockType::Inline(ValType::F32),
+            -4 => BlockType::Inline(ValType::F64),
+            -5 => BlockType::Inline(ValType::V128),
+            -16 => BlockType::Inline(ValType::FuncRef),
+            -17 => BlockType::Inline(ValType::ExternRef),
+            -23 => BlockType::Inline(ValType::ExnRef),
+            _ => BlockType::Index(self.to_type_index()?),
+        })
+    }
+}
+
+impl From<i32> for S33 {
+    #[inline]
+    fn from(value: i32) -> Self {
+        Self(value.into())
+    }
+}
+
+impl From<u32> for S33 {
+    #[inline]
+    fn from(value: u32) -> Self {
+        Self(value.into())
+    }
+}
+
+impl From<TypeIdx> for S33 {
+    #[inline]
+    fn from(index: TypeIdx) -> Self {
+        Self::from(index.to_u32())
+    }
+}
+
+/// Typed references are encoded as a prefix indicating whether they are nullable followed by
+/// their [`HeapType`](crate::types::HeapType), so only the value of the prefix is returned for
+/// them.
+impl From<BlockType> for S33 {
+    fn from(block_type: BlockType) -> Self {
+        Self(match block_type {
+            BlockType::Empty => -64,
+            BlockType::Index(index) => index.to_u32().into(),
+            BlockType::Inline(ValType::I32) => -1,
+            BlockType::Inline(ValType::I64) => -2,
+            BlockType::Inline(ValType::F32) => -3,
+            BlockType::Inline(ValType::F64) => -4,
+            BlockType::Inline(ValType::V128) => -5,
+            BlockType::Inline(ValType::FuncRef) => -16,
+            BlockType::Inline(ValType::ExternRef) => -17,
+            BlockType::Inline(ValType::ExnRef) => -23,
+            BlockType::Inline(ValType::TypedRef { nullable: true, .. }) => -29,
+            BlockType::Inline(ValType::TypedRef {
+                nullable: false, ..
+            }) => -28,
+        })
+    }
+}
+
+impl From<S33> for i64 {
+    #[inline]
+    fn from(value: S33) -> Self {
+        value.0
+    }
+}
+
+impl core::fmt::Display for S33 {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(&self.0, f)
+    }
+}
@@ -11,6 +11,7 @@ mod name_fmt;
 
 pub use char_iterators::{Chars, CharsLossy};
 pub use error::{InvalidCodePoint, NameError};
+pub use name_fmt::EscapedName;
 
 /// A UTF-8 string [name](https://webassembly.github.io/spec/core/binary/values.html#names).
 #[derive(Clone, Copy)]
@@ -62,6 +63,24 @@ impl<I: Input> Name<I> {
         CharsLossy::new(Chars::new(self))
     }
 
+    /// Returns a wrapper used to display the [`Name`] as a quoted WebAssembly text format string,
+    /// escaping any invalid UTF-8 byte sequences rather than returning an error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wasmiter::parser::name::Name;
+    ///
+    /// let bytes = [6u8, b'a', b'\n', 0xFF, b'"', 0xC3, 0xA9];
+    /// let name = Name::new(bytes.as_slice(), &mut 0)?;
+    /// assert_eq!(name.escaped().to_string(), r#""a\n\ff\"\u{e9}""#);
+    /// # Ok::<(), wasmiter::parser::Error>(())
+    /// ```
+    #[inline]
+    pub fn escaped(self) -> EscapedName<I> {
+        EscapedName::new(self)
+    }
+
     /// Copies the contents of the [`Name`] into the specified `buffer`.
     ///
     /// If the length of the `buffer` is less than the length, in bytes, of the [`Name`], then only
@@ -104,6 +123,40 @@ impl<I: Input> Name<I> {
             .try_eq_at(self.offset, s.as_bytes())
             .map_err(Into::into)
     }
+
+    /// Attempts to compare the contents of this [`Name`] to the contents of another [`Name`],
+    /// returning `true` if they are equal.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the name bytes could not be fetched from either [`Input`].
+    pub fn try_eq_name<J: Input>(&self, other: &Name<J>) -> parser::Parsed<bool> {
+        if self.length != other.length {
+            return Ok(false);
+        }
+
+        const BUFFER_LEN: u32 = 32;
+        let mut buffer = [0u8; BUFFER_LEN as usize];
+        let mut offset = 0u32;
+        while offset < self.length {
+            let chunk_length = core::cmp::min(BUFFER_LEN, self.length - offset);
+            let chunk = &mut buffer[..chunk_length as usize];
+            self.input
+                .read_exact_at(self.offset + u64::from(offset), chunk)
+                .context("string contents")?;
+
+            if !other
+                .input
+                .try_eq_at(other.offset + u64::from(offset), chunk)?
+            {
+                return Ok(false);
+            }
+
+            offset += chunk_length;
+        }
+
+        Ok(true)
+    }
 }
 
 impl<I: Input> input::HasInput<I> for Name<I> {
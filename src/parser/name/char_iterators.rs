@@ -1,5 +1,5 @@
 use crate::{
-    input::{BorrowInput, HasInput, Input},
+    input::{BorrowInput, CloneInput, HasInput, Input},
     parser::name::{InvalidCodePoint, Name, NameError},
 };
 
@@ -71,8 +71,8 @@ impl CharsBuffer {
 
             Ok(Some(c))
         } else if let Some(bad) = self.bad_sequence.take() {
-            let bad_len = usize::from(bad.get());
             let mut bytes = [0u8; 4];
+            let bad_len = usize::from(bad.get()).min(bytes.len());
             bytes[0..bad_len].copy_from_slice(&self.buffer[0..bad_len]);
 
             // Skip the invalid sequence
@@ -205,6 +205,18 @@ impl<'a, I: Input + 'a> BorrowInput<'a, I> for Chars<I> {
     }
 }
 
+impl<'a, I: Clone + Input + 'a> CloneInput<'a, I> for Chars<&'a I> {
+    type Cloned = Chars<I>;
+
+    #[inline]
+    fn clone_input(&self) -> Chars<I> {
+        Chars {
+            name: self.name.clone_input(),
+            buffer: self.buffer,
+        }
+    }
+}
+
 impl<I: Input> Iterator for Chars<I> {
     type Item = Result<char, NameError>;
 
@@ -253,6 +265,15 @@ impl<'a, I: Input + 'a> BorrowInput<'a, I> for CharsLossy<I> {
     }
 }
 
+impl<'a, I: Clone + Input + 'a> CloneInput<'a, I> for CharsLossy<&'a I> {
+    type Cloned = CharsLossy<I>;
+
+    #[inline]
+    fn clone_input(&self) -> Self::Cloned {
+        CharsLossy::new(self.inner.clone_input())
+    }
+}
+
 impl<I: Input> Iterator for CharsLossy<I> {
     type Item = char;
 
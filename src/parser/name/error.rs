@@ -9,7 +9,7 @@ pub struct InvalidCodePoint {
 }
 
 impl InvalidCodePoint {
-    fn bytes(&self) -> Option<&[u8]> {
+    pub(super) fn bytes(&self) -> Option<&[u8]> {
         let length = usize::from(self.length.get());
         if length <= self.bytes.len() {
             Some(&self.bytes[0..length])
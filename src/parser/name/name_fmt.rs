@@ -1,6 +1,6 @@
 use crate::{
     input::{BorrowInput, Input},
-    parser::name::{self, CharsLossy},
+    parser::name::{self, CharsLossy, NameError},
 };
 use core::fmt::{Debug, Display, Formatter, Result, Write as _};
 
@@ -68,3 +68,59 @@ impl<I: Input> Display for name::Name<I> {
         self.borrow_input().chars_lossy().fmt_display(f)
     }
 }
+
+/// Formats a [`Name`](name::Name) as a quoted string in the
+/// [WebAssembly text format](https://webassembly.github.io/spec/core/text/values.html#strings).
+///
+/// Unlike the [`Debug`] implementation for [`Name`](name::Name), invalid UTF-8 byte sequences are
+/// not replaced, and are instead written as `\hh` escape sequences, preserving the original
+/// contents of the [`Name`](name::Name). Control and non-ASCII characters are written as
+/// `\u{...}` escape sequences.
+///
+/// Returned by [`Name::escaped()`](name::Name::escaped).
+#[derive(Clone, Copy)]
+pub struct EscapedName<I: Input> {
+    name: name::Name<I>,
+}
+
+impl<I: Input> EscapedName<I> {
+    pub(super) fn new(name: name::Name<I>) -> Self {
+        Self { name }
+    }
+}
+
+impl<I: Input> Display for EscapedName<I> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.write_char('"')?;
+
+        for result in self.name.borrow_input().chars() {
+            match result {
+                Ok('\t') => f.write_str("\\t")?,
+                Ok('\n') => f.write_str("\\n")?,
+                Ok('\r') => f.write_str("\\r")?,
+                Ok('\'') => f.write_str("\\'")?,
+                Ok('\"') => f.write_str("\\\"")?,
+                Ok('\\') => f.write_str("\\\\")?,
+                Ok(c @ ' '..='~') => f.write_char(c)?,
+                Ok(c) => write!(f, "\\u{{{:x}}}", u32::from(c))?,
+                Err(NameError::BadBytes(bad)) => match bad.bytes() {
+                    Some(bytes) => {
+                        for b in bytes {
+                            write!(f, "\\{b:02x}")?;
+                        }
+                    }
+                    None => f.write_str("\\u{fffd}")?,
+                },
+                Err(NameError::BadInput(_)) => f.write_str("\\u{fffd}")?,
+            }
+        }
+
+        f.write_char('"')
+    }
+}
+
+impl<I: Input> Debug for EscapedName<I> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        Display::fmt(self, f)
+    }
+}
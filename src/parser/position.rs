@@ -0,0 +1,118 @@
+use crate::{
+    input::Input,
+    parser::{Error, ErrorKind, Parsed, ResultExt as _},
+};
+
+/// The number of bytes, starting at the offset of a [`Position`], used to compute its
+/// fingerprint.
+const FINGERPRINT_LENGTH: usize = 32;
+
+/// Computes a [FNV-1a](http://www.isthe.com/chongo/tech/comp/fnv/index.html) hash of the bytes
+/// at the given `offset`.
+fn fingerprint<I: Input>(offset: u64, input: &I) -> Parsed<u64> {
+    let mut buffer = [0u8; FINGERPRINT_LENGTH];
+    let bytes = input
+        .read_at(offset, &mut buffer)
+        .context("could not read bytes for position fingerprint")?;
+
+    Ok(bytes.iter().fold(0xCBF2_9CE4_8422_2325u64, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0000_0100_0000_01B3)
+    }))
+}
+
+/// A saved location within an [`Input`], used to resume parsing at a later time.
+///
+/// Unlike an [`Offset`](crate::parser::Offset), a [`Position`] records enough information to
+/// detect when it is used with an [`Input`] other than the one it was obtained from. This check
+/// is done by comparing the number of bytes available at the offset, along with a hash of the
+/// bytes immediately following it, so it can only detect most, and not all, kinds of misuse.
+///
+/// Positions are obtained by calling methods such as
+/// [`SectionSequence::current_position`](crate::sections::SectionSequence::current_position) or
+/// [`Vector::current_position`](crate::parser::Vector::current_position), and are used to
+/// reconstruct parsers with the corresponding `from_position` method.
+///
+/// # Example
+///
+/// ```
+/// use wasmiter::sections::SectionSequence;
+///
+/// let wasm = wat::parse_str("(module (memory 1) (func))").unwrap();
+/// let mut sections = wasmiter::parse_module_sections(wasm.as_slice())?;
+/// let first_id = sections.parse()?.unwrap().id();
+/// let position = sections.current_position()?;
+///
+/// // ...later, with the same bytes
+/// let mut resumed = SectionSequence::from_position(&position, wasm.as_slice())?;
+/// assert_ne!(resumed.parse()?.unwrap().id(), first_id);
+///
+/// // Positions used with a different input are rejected
+/// assert!(SectionSequence::from_position(&position, [0u8; 4].as_slice()).is_err());
+/// # Ok::<(), wasmiter::parser::Error>(())
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Position {
+    offset: u64,
+    length: u64,
+    fingerprint: u64,
+    /// The number of elements that were declared in a [`Vector`](crate::parser::Vector), and the
+    /// number that remain.
+    counts: Option<(u32, u32)>,
+}
+
+impl Position {
+    /// Captures the given `offset` into the [`Input`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bytes at the `offset` could not be read.
+    pub fn new<I: Input>(offset: u64, input: &I) -> Parsed<Self> {
+        Ok(Self {
+            offset,
+            length: input
+                .length_at(offset)
+                .context("could not obtain input length for position")?,
+            fingerprint: fingerprint(offset, input)?,
+            counts: None,
+        })
+    }
+
+    #[inline]
+    pub(crate) fn with_counts(mut self, total: u32, remaining: u32) -> Self {
+        self.counts = Some((total, remaining));
+        self
+    }
+
+    #[inline]
+    pub(crate) fn counts(&self) -> Option<(u32, u32)> {
+        self.counts
+    }
+
+    /// Gets the offset into the [`Input`].
+    #[inline]
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Checks that the [`Position`] could have been obtained from the given [`Input`], returning
+    /// the offset.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the [`Input`] is detected to differ from the one the [`Position`] was
+    /// obtained from, or if the bytes at the offset could not be read.
+    pub fn resume<I: Input>(&self, input: &I) -> Parsed<u64> {
+        let actual = Self::new(self.offset, input)?;
+        if actual.length != self.length || actual.fingerprint != self.fingerprint {
+            #[inline(never)]
+            #[cold]
+            fn position_mismatch(offset: u64) -> Error {
+                Error::new(ErrorKind::PositionMismatch).with_location_context("position", offset)
+            }
+
+            return Err(position_mismatch(self.offset));
+        }
+
+        Ok(self.offset)
+    }
+}
@@ -48,7 +48,13 @@ impl<T, E: Into<Error>> ResultExt<T> for Result<T, E> {
                             Context::from_closure(c)
                         }
 
-                        Err(e.into().with_context(make_context_closure(f())))
+                        let e = e.into();
+                        if e.is_context_full() {
+                            // Avoid boxing context that would be discarded anyway
+                            Err(e.omit_context())
+                        } else {
+                            Err(e.with_context(make_context_closure(f())))
+                        }
                     } else {
                         let _ = f;
                         Err(e.into())
@@ -0,0 +1,191 @@
+use crate::parser::Parsed;
+
+/// A range of byte offsets into an [`Input`](crate::input::Input), from `start` (inclusive) to
+/// `end` (exclusive).
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Span {
+    start: u64,
+    end: u64,
+}
+
+impl Span {
+    /// Creates a new [`Span`] covering the bytes from `start` up to but not including `end`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `end` is less than `start`.
+    #[inline]
+    pub const fn new(start: u64, end: u64) -> Self {
+        assert!(start <= end, "end of span must not be less than the start");
+        Self { start, end }
+    }
+
+    /// Gets the offset to the first byte of the span.
+    #[inline]
+    pub const fn start(&self) -> u64 {
+        self.start
+    }
+
+    /// Gets the offset to the byte after the last byte of the span.
+    #[inline]
+    pub const fn end(&self) -> u64 {
+        self.end
+    }
+
+    /// Gets the number of bytes in the span.
+    #[inline]
+    pub const fn len(&self) -> u64 {
+        self.end - self.start
+    }
+
+    /// Returns `true` if the span contains no bytes.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Returns `true` if the given `offset` is within the span.
+    #[inline]
+    pub const fn contains(&self, offset: u64) -> bool {
+        self.start <= offset && offset < self.end
+    }
+}
+
+impl From<Span> for core::ops::Range<u64> {
+    #[inline]
+    fn from(span: Span) -> Self {
+        span.start..span.end
+    }
+}
+
+impl core::fmt::Display for Span {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:#X}..{:#X}", self.start, self.end)
+    }
+}
+
+/// An item along with the [`Span`] of bytes it was parsed from.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Spanned<T> {
+    span: Span,
+    item: T,
+}
+
+impl<T> Spanned<T> {
+    /// Associates an `item` with the [`Span`] it was parsed from.
+    #[inline]
+    pub const fn new(span: Span, item: T) -> Self {
+        Self { span, item }
+    }
+
+    /// Gets the range of bytes the item was parsed from.
+    #[inline]
+    pub const fn span(&self) -> Span {
+        self.span
+    }
+
+    /// Gets a reference to the item.
+    #[inline]
+    pub const fn item(&self) -> &T {
+        &self.item
+    }
+
+    /// Gets the item, discarding its [`Span`].
+    #[inline]
+    pub fn into_item(self) -> T {
+        self.item
+    }
+
+    /// Transforms the item, keeping the same [`Span`].
+    #[inline]
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> Spanned<U> {
+        Spanned {
+            span: self.span,
+            item: f(self.item),
+        }
+    }
+}
+
+impl<T> core::ops::Deref for Spanned<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.item
+    }
+}
+
+/// Trait for iterators over the entries of a section, such as
+/// [`ImportsComponent`](crate::component::ImportsComponent), that allows obtaining the [`Span`]
+/// of each entry.
+///
+/// # Example
+///
+/// ```
+/// use wasmiter::{component::KnownSection, parser::SpannedItems as _};
+///
+/// let wasm = wat::parse_str(r#"(module
+///     (func (export "a"))
+///     (func (export "bc")))"#).unwrap();
+///
+/// for result in wasmiter::parse_module_sections(wasm.as_slice())? {
+///     if let Ok(Ok(KnownSection::Export(exports))) = KnownSection::interpret(result?) {
+///         let spans = exports
+///             .spanned()
+///             .map(|export| Ok(export?.span().len()))
+///             .collect::<wasmiter::parser::Parsed<Vec<_>>>()?;
+///
+///         // Name length, name, kind, and function index
+///         assert_eq!(spans, [1 + 1 + 1 + 1, 1 + 2 + 1 + 1]);
+///     }
+/// }
+/// # Ok::<(), wasmiter::parser::Error>(())
+/// ```
+pub trait SpannedItems: Iterator + Sized {
+    /// Gets the offset to the next entry to be parsed.
+    fn next_item_offset(&self) -> u64;
+
+    /// Returns an iterator that provides the [`Span`] of each entry along with the entry itself.
+    #[inline]
+    fn spanned(self) -> SpannedIter<Self> {
+        SpannedIter { items: self }
+    }
+}
+
+/// Iterator adapter returned by [`SpannedItems::spanned`], which pairs each parsed entry with its
+/// [`Span`].
+#[derive(Clone, Copy, Debug)]
+pub struct SpannedIter<C> {
+    items: C,
+}
+
+impl<C> SpannedIter<C> {
+    /// Gets the underlying iterator.
+    #[inline]
+    pub fn into_inner(self) -> C {
+        self.items
+    }
+}
+
+impl<T, C> Iterator for SpannedIter<C>
+where
+    C: SpannedItems + Iterator<Item = Parsed<T>>,
+{
+    type Item = Parsed<Spanned<T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.items.next_item_offset();
+        let result = self.items.next()?;
+        Some(result.map(|item| Spanned::new(Span::new(start, self.items.next_item_offset()), item)))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.items.size_hint()
+    }
+}
+
+impl<T, C> core::iter::FusedIterator for SpannedIter<C> where
+    C: SpannedItems + core::iter::FusedIterator<Item = Parsed<T>>
+{
+}
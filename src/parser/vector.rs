@@ -9,7 +9,11 @@ use crate::{
 #[derive(Clone, Copy)]
 pub struct Vector<O: Offset, I: Input> {
     total: u32,
+    /// The number of elements that have yet to be parsed, including an element that could not be
+    /// parsed.
     remaining: u32,
+    /// Set when an error occurs while parsing an element, after which no more elements are parsed.
+    failed: bool,
     offset: O,
     input: I,
 }
@@ -21,6 +25,7 @@ impl<O: Offset, I: Input> Vector<O, I> {
         Self {
             total: count,
             remaining: count,
+            failed: false,
             offset,
             input,
         }
@@ -35,17 +40,35 @@ impl<O: Offset, I: Input> Vector<O, I> {
     }
 
     /// Gets the expected remaining number of elements in the [`Vector`].
+    ///
+    /// Returns `0` if an error occured while parsing an element.
     #[inline]
     pub fn remaining_count(&self) -> u32 {
-        self.remaining
+        if self.failed {
+            0
+        } else {
+            self.remaining
+        }
+    }
+
+    /// Gets the number of elements that have been parsed so far.
+    ///
+    /// If an error occured while parsing an element, returns the index of that element.
+    #[inline]
+    pub fn consumed_count(&self) -> u32 {
+        self.total - self.remaining
+    }
+
+    /// Gets the number of elements that the [`Vector`] was declared to contain.
+    #[inline]
+    pub fn declared_count(&self) -> u32 {
+        self.total
     }
 
     /// Implementation of [`Iterator::size_hint`].
     pub fn size_hint(&self) -> (usize, Option<usize>) {
-        (
-            usize::from(self.remaining != 0),
-            usize::try_from(self.remaining).ok(),
-        )
+        let remaining = self.remaining_count();
+        (usize::from(remaining != 0), usize::try_from(remaining).ok())
     }
 
     /// Parses an element with the given closure, passing the number of items that have been parsed
@@ -54,49 +77,95 @@ impl<O: Offset, I: Input> Vector<O, I> {
     /// # Errors
     ///
     /// See [`Vector::advance`] for more information.
-    pub fn advance_with_index<'a, T, E, F>(&'a mut self, f: F) -> Option<Result<T, E>>
+    pub fn advance_with_index<'a, T, F>(&'a mut self, f: F) -> Option<parser::Parsed<T>>
     where
-        F: FnOnce(u32, &'a mut u64, &'a I) -> Result<T, E>,
+        F: FnOnce(u32, &'a mut u64, &'a I) -> parser::Parsed<T>,
     {
-        if self.remaining == 0 {
+        if self.remaining_count() == 0 {
             return None;
         }
 
-        let result = f(
-            self.total - self.remaining,
-            self.offset.offset_mut(),
-            &self.input,
-        );
+        let index = self.total - self.remaining;
+        let total = self.total;
+        let result = f(index, self.offset.offset_mut(), &self.input);
 
         if result.is_ok() {
             self.remaining -= 1;
         } else {
-            self.remaining = 0;
+            self.failed = true;
         }
 
-        Some(result)
+        Some(result.with_context(|| move |f| write!(f, "vector element #{index} of {total}")))
     }
 
     /// Parses an element with the given closure.
     ///
     /// # Errors
     ///
-    /// Returns any errors returned by the closure. If an error was returned, then future calls to
+    /// Returns any errors returned by the closure, along with the index of the element that could
+    /// not be parsed. If an error was returned, then future calls to
     /// [`advance`](Vector::advance) will return `None`.
     #[inline]
-    pub fn advance<'a, T, E, F>(&'a mut self, f: F) -> Option<Result<T, E>>
+    pub fn advance<'a, T, F>(&'a mut self, f: F) -> Option<parser::Parsed<T>>
     where
-        F: FnOnce(&'a mut u64, &'a I) -> Result<T, E>,
+        F: FnOnce(&'a mut u64, &'a I) -> parser::Parsed<T>,
     {
         self.advance_with_index(|_, offset, bytes| f(offset, bytes))
     }
 
+    /// Captures the offset and number of elements remaining in the [`Vector`], allowing it to
+    /// be reconstructed later with [`Vector::from_position`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bytes at the current offset could not be read.
+    pub fn current_position(&self) -> parser::Parsed<parser::Position> {
+        Ok(parser::Position::new(self.offset.offset(), &self.input)?
+            .with_counts(self.total, self.remaining_count()))
+    }
+
+    #[inline]
+    pub(crate) fn next_offset(&self) -> u64 {
+        self.offset.offset()
+    }
+
     #[inline]
     pub(crate) fn into_offset(self) -> O {
         self.offset
     }
 }
 
+impl<I: Input> Vector<u64, I> {
+    /// Reconstructs a [`Vector`] from a [`Position`](parser::Position) obtained by calling
+    /// [`Vector::current_position`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `position` was not obtained from a [`Vector`], or if it was
+    /// obtained from a different [`Input`].
+    pub fn from_position(position: &parser::Position, input: I) -> parser::Parsed<Self> {
+        let offset = position.resume(&input)?;
+        if let Some((total, remaining)) = position.counts() {
+            Ok(Self {
+                total,
+                remaining,
+                failed: false,
+                offset,
+                input,
+            })
+        } else {
+            #[inline(never)]
+            #[cold]
+            fn not_a_vector_position() -> parser::Error {
+                parser::Error::new(parser::ErrorKind::PositionMismatch)
+                    .with_context("position was not obtained from a vector".into())
+            }
+
+            Err(not_a_vector_position())
+        }
+    }
+}
+
 impl<O: Offset, I: Input> input::HasInput<I> for Vector<O, I> {
     #[inline]
     fn input(&self) -> &I {
@@ -112,6 +181,7 @@ impl<'a, O: Offset, I: Input + 'a> input::BorrowInput<'a, I> for Vector<O, I> {
         Vector {
             total: self.total,
             remaining: self.remaining,
+            failed: self.failed,
             offset: self.offset.offset(),
             input: &self.input,
         }
@@ -126,6 +196,7 @@ impl<'a, O: Offset, I: Clone + Input + 'a> input::CloneInput<'a, I> for Vector<O
         Vector {
             total: self.total,
             remaining: self.remaining,
+            failed: self.failed,
             offset: self.offset.offset(),
             input: self.input.clone(),
         }
@@ -135,7 +206,7 @@ impl<'a, O: Offset, I: Clone + Input + 'a> input::CloneInput<'a, I> for Vector<O
 impl<O: Offset, I: Input> core::fmt::Debug for Vector<O, I> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("Vector")
-            .field("remaining", &self.remaining)
+            .field("remaining", &self.remaining_count())
             .field("offset", &self.offset.offset())
             .finish_non_exhaustive()
     }
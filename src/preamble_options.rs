@@ -0,0 +1,55 @@
+/// Specifies which preambles are accepted by
+/// [`parse_module_sections_with_options`](crate::parse_module_sections_with_options).
+///
+/// By default, only core WebAssembly modules with a *version* of `1` are accepted, which matches
+/// the behavior of [`parse_module_sections`](crate::parse_module_sections).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct PreambleOptions<'a> {
+    module_versions: &'a [u16],
+    component_versions: &'a [u16],
+}
+
+impl<'a> PreambleOptions<'a> {
+    /// Creates new [`PreambleOptions`] that only accept core modules with a *version* of `1`.
+    pub const fn new() -> Self {
+        Self {
+            module_versions: &[1],
+            component_versions: &[],
+        }
+    }
+
+    /// Sets the *version* fields of core WebAssembly modules that are accepted.
+    pub const fn with_module_versions(mut self, versions: &'a [u16]) -> Self {
+        self.module_versions = versions;
+        self
+    }
+
+    /// Sets the *version* fields of [components](crate::component_model) that are accepted, such
+    /// as `0xA` or [`0xD`](crate::component_model::VERSION).
+    ///
+    /// If no component versions are accepted, which is the default, encountering the preamble of
+    /// a component results in an error indicating that a component was found.
+    pub const fn with_component_versions(mut self, versions: &'a [u16]) -> Self {
+        self.component_versions = versions;
+        self
+    }
+
+    /// The *version* fields of core WebAssembly modules that are accepted.
+    #[inline]
+    pub const fn module_versions(&self) -> &'a [u16] {
+        self.module_versions
+    }
+
+    /// The *version* fields of components that are accepted.
+    #[inline]
+    pub const fn component_versions(&self) -> &'a [u16] {
+        self.component_versions
+    }
+}
+
+impl Default for PreambleOptions<'_> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
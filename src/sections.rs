@@ -9,14 +9,18 @@
 
 use crate::input::{BorrowInput, CloneInput, HasInput, Input, Window};
 use crate::parser::{self, Parsed, ResultExt};
+use crate::trace;
 use core::fmt::Debug;
 
 mod debug_module;
+mod display_function;
 mod display_module;
+mod module_info;
 
 pub mod id;
 
 pub use debug_module::{DebugModule, DebugModuleSection};
+pub use display_function::DisplayFunction;
 pub use display_module::DisplayModule;
 
 /// Represents a
@@ -50,6 +54,10 @@ impl<I: Input> Section<I> {
     }
 
     /// Gets the length, in bytes, of the content of the section.
+    ///
+    /// This is the size declared in the section header, and is obtained without reading the
+    /// contents. Sections parsed by a [`SectionSequence`] are checked to not be larger than the
+    /// remaining [`Input`].
     #[inline]
     pub fn length(&self) -> u64 {
         self.contents.length()
@@ -70,6 +78,21 @@ impl<I: Input> Section<I> {
         self.contents
     }
 
+    /// Copies the contents of the section to the given `writer`, returning the number of bytes
+    /// copied.
+    ///
+    /// See [`Window::copy_to`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the section's contents could not be read or written.
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    #[inline]
+    pub fn copy_to<W: std::io::Write + ?Sized>(&self, writer: &mut W) -> parser::Parsed<u64> {
+        self.contents.copy_to(writer)
+    }
+
     /// Returns a [`Debug`] implementation that attempts to interpret the contents as a WebAssembly
     /// module section.
     #[inline]
@@ -146,13 +169,88 @@ impl<I: Input> SectionSequence<I> {
         self.offset
     }
 
+    /// Gets the number of bytes remaining in the [`Input`], starting at the next section.
+    ///
+    /// Along with [`Section::length`], this can be used to report progress when parsing a
+    /// sequence of sections, without reading the contents of any section.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the length of the [`Input`] could not be determined.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let wasm = wat::parse_str("(module (memory 1) (func))").unwrap();
+    /// let mut sections = wasmiter::parse_module_sections(wasm.as_slice())?;
+    /// let total = sections.remaining_input_len()?;
+    /// assert_eq!(total, wasm.len() as u64 - 8);
+    ///
+    /// let first = sections.parse()?.unwrap();
+    /// let header_length = total - first.length() - sections.remaining_input_len()?;
+    /// assert_eq!(header_length, 2);
+    /// # Ok::<(), wasmiter::parser::Error>(())
+    /// ```
+    #[inline]
+    pub fn remaining_input_len(&self) -> Parsed<u64> {
+        self.input
+            .length_at(self.offset)
+            .context("could not determine remaining length of section sequence")
+    }
+
+    /// Captures the offset of the next section, allowing the [`SectionSequence`] to be
+    /// reconstructed later with [`SectionSequence::from_position`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bytes at the current offset could not be read.
+    #[inline]
+    pub fn current_position(&self) -> Parsed<parser::Position> {
+        parser::Position::new(self.offset, &self.input)
+    }
+
+    /// Reconstructs a [`SectionSequence`] from a [`Position`](parser::Position).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `position` was detected to have been obtained from a different
+    /// [`Input`].
+    pub fn from_position(position: &parser::Position, input: I) -> Parsed<Self> {
+        Ok(Self::new(position.resume(&input)?, input))
+    }
+
     /// Parses the next section. If there are no more sections remaining, returns `Ok(None)`.
     ///
+    /// If an error occurs, the remainder of the [`Input`] is skipped, so that subsequent calls
+    /// return `Ok(None)`.
+    ///
     /// # Errors
     ///
-    /// Returns an error if the [`Input`] bytes could not be read, or if a structure was not
-    /// formatted correctly.
+    /// Returns an error if the [`Input`] bytes could not be read, if a structure was not
+    /// formatted correctly, or if the contents of the section extend past the end of the
+    /// [`Input`].
     pub fn parse(&mut self) -> Parsed<Option<Section<&I>>> {
+        match self.parse_header() {
+            Ok(Some((id, content_length))) => Ok(Some(Section {
+                id,
+                contents: Window::with_offset_and_length(
+                    &self.input,
+                    self.offset - content_length,
+                    content_length,
+                ),
+            })),
+            Ok(None) => Ok(None),
+            Err(e) => {
+                self.offset = self
+                    .offset
+                    .saturating_add(self.input.length_at(self.offset).unwrap_or(0));
+                Err(e)
+            }
+        }
+    }
+
+    /// Parses the *id* and content size of the next section, advancing past its contents.
+    fn parse_header(&mut self) -> Parsed<Option<(u8, u64)>> {
         let id = if let Some(value) = parser::one_byte(&mut self.offset, &self.input)? {
             value
         } else {
@@ -163,15 +261,29 @@ impl<I: Input> SectionSequence<I> {
             parser::leb128::u32(&mut self.offset, &self.input).context("section content size")?,
         );
 
-        let contents = Window::with_offset_and_length(&self.input, self.offset, content_length);
+        let available = self.input.length_at(self.offset)?;
+        if content_length > available {
+            #[inline(never)]
+            #[cold]
+            fn section_out_of_bounds(id: u8, offset: u64, available: u64) -> parser::Error {
+                parser::Error::from(crate::input::out_of_bounds(offset, Some(available)))
+                    .with_context(parser::Context::from_closure(move |f| {
+                        write!(f, "contents of section with id {id:#04X}")
+                    }))
+            }
+
+            return Err(section_out_of_bounds(id, self.offset, available));
+        }
 
-        // TODO: Duplicate code w/ leb128, increment offset
-        // self.parser
-        //     .skip_exact(content_length)
-        //     .context("section content")?;
-        self.offset += content_length;
+        trace::event!(
+            id,
+            offset = self.offset,
+            length = content_length,
+            "parsed section header"
+        );
 
-        Ok(Some(Section { id, contents }))
+        self.offset += content_length;
+        Ok(Some((id, content_length)))
     }
 
     /// Returns a [`Debug`] implementation that attempts to interpret the sequence of sections as a
@@ -186,7 +298,112 @@ impl<I: Input> SectionSequence<I> {
     /// [WebAssembly text](https://webassembly.github.io/spec/core/text/index.html).
     #[inline]
     pub fn display_module(&self) -> DisplayModule<'_, I> {
-        DisplayModule::new(self)
+        DisplayModule::new(self, |_| true)
+    }
+
+    /// Returns a [`Display`](core::fmt::Display) implementation that writes the
+    /// [WebAssembly text](https://webassembly.github.io/spec/core/text/index.html) for the module
+    /// using the options in the given [`Config`](crate::text::Config).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wasmiter::text::Config;
+    ///
+    /// let wasm = wat::parse_str(r#"(module
+    ///     (func (param i32) (result i32)
+    ///         local.get 0
+    ///         if (result i32)
+    ///             i32.const 1
+    ///         else
+    ///             i32.const 2
+    ///         end))"#).unwrap();
+    ///
+    /// let sections = wasmiter::parse_module_sections(wasm.as_slice())?;
+    /// let config = Config::new().with_folded_instructions(true).with_comments(false);
+    /// let text = sections.display_module_with(config).to_string();
+    ///
+    /// assert!(text.contains("(if (result i32) (local.get 0)\n    (then\n      (i32.const 1))"));
+    /// assert!(text.contains("(else\n      (i32.const 2)))"));
+    /// # Ok::<(), wasmiter::parser::Error>(())
+    /// ```
+    #[inline]
+    pub fn display_module_with(&self, config: crate::text::Config) -> DisplayModule<'_, I> {
+        DisplayModule::new(self, |_| true).with_config(config)
+    }
+
+    /// Returns a [`Display`](core::fmt::Display) implementation that writes the
+    /// [WebAssembly text](https://webassembly.github.io/spec/core/text/index.html) for only the
+    /// sections whose [`id`](Section::id) matches the `filter`.
+    ///
+    /// This avoids the cost of writing the *code section* when only the interface of a module is
+    /// needed. Sections that are filtered out are still read when other sections depend on them,
+    /// such as the *type section* being used to write the parameters of tags.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wasmiter::sections::id;
+    ///
+    /// let wasm = wat::parse_str(r#"(module
+    ///     (import "env" "log" (func (param i32)))
+    ///     (func (export "main") i32.const 42 call 0))"#).unwrap();
+    ///
+    /// let sections = wasmiter::parse_module_sections(wasm.as_slice())?;
+    /// let interface = sections
+    ///     .display_sections(|section| matches!(section, id::TYPE | id::IMPORT))
+    ///     .to_string();
+    ///
+    /// assert!(interface.contains("(import \"env\" \"log\""));
+    /// assert!(!interface.contains("i32.const"));
+    /// # Ok::<(), wasmiter::parser::Error>(())
+    /// ```
+    #[inline]
+    pub fn display_sections(&self, filter: fn(u8) -> bool) -> DisplayModule<'_, I> {
+        DisplayModule::new(self, filter)
+    }
+
+    /// Returns a [`Display`](core::fmt::Display) implementation that writes the
+    /// [WebAssembly text](https://webassembly.github.io/spec/core/text/index.html) for only the
+    /// function with the given `index`.
+    ///
+    /// The *code section* entries before the function are skipped without reading their contents,
+    /// making this much cheaper than [`display_module`](SectionSequence::display_module) when
+    /// inspecting a single function of a large module. If the *name section* contains a name for
+    /// the function, it is written as the function's identifier.
+    ///
+    /// Imported functions are written using the
+    /// [inline abbreviation](https://webassembly.github.io/spec/core/text/modules.html#text-func-abbrev-import),
+    /// and a comment is written if there is no function with the given `index`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wasmiter::index::FuncIdx;
+    ///
+    /// # #[cfg(feature = "alloc")]
+    /// # fn main() -> Result<(), wasmiter::parser::Error> {
+    /// let wasm = wat::parse_str(r#"(module
+    ///     (import "env" "log" (func $log (param i32)))
+    ///     (func $first i32.const 1 drop)
+    ///     (func $second (result i32) i32.const 2))"#).unwrap();
+    ///
+    /// let sections = wasmiter::parse_module_sections(wasm.as_slice())?;
+    /// let text = sections.display_function(FuncIdx::from(2u32)).to_string();
+    /// assert!(text.starts_with("(func $second (type 2)"));
+    /// assert!(text.contains("i32.const 0x00000002"));
+    /// assert!(!text.contains("i32.const 0x00000001"));
+    ///
+    /// let text = sections.display_function(FuncIdx::from(0u32)).to_string();
+    /// assert_eq!(text, "(func $log (import \"env\" \"log\") (type 0))");
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))]
+    /// # fn main() {}
+    /// ```
+    #[inline]
+    pub fn display_function(&self, index: crate::index::FuncIdx) -> DisplayFunction<'_, I> {
+        DisplayFunction::new(self, index)
     }
 }
 
@@ -0,0 +1,42 @@
+use crate::{index::FuncIdx, input::Input, sections::SectionSequence};
+
+/// Helper struct to display a single function of a WebAssembly module in the
+/// [WebAssembly text format](https://webassembly.github.io/spec/core/text/index.html).
+///
+/// Returned by the [`SectionSequence::display_function`] method.
+pub struct DisplayFunction<'a, I: Input> {
+    sections: &'a SectionSequence<I>,
+    index: FuncIdx,
+}
+
+impl<'a, I: Input> DisplayFunction<'a, I> {
+    pub(crate) fn new(sections: &'a SectionSequence<I>, index: FuncIdx) -> Self {
+        Self { sections, index }
+    }
+
+    /// Gets the index of the function to write.
+    #[inline]
+    pub fn index(&self) -> FuncIdx {
+        self.index
+    }
+
+    #[inline]
+    pub(crate) fn as_sections(&self) -> &'a SectionSequence<I> {
+        self.sections
+    }
+}
+
+impl<I: Input> Clone for DisplayFunction<'_, I> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<I: Input> Copy for DisplayFunction<'_, I> {}
+
+impl<I: Input> core::fmt::Debug for DisplayFunction<'_, I> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "\"{}\"", self)
+    }
+}
@@ -1,24 +1,229 @@
-use crate::{input::Input, sections::SectionSequence};
+use crate::{input::Input, sections::SectionSequence, text::Config};
 
 /// Helper struct to display the sections of a WebAssembly module in the
 /// [WebAssembly text format](https://webassembly.github.io/spec/core/text/index.html).
 ///
-/// Returned by the [`SectionSequence::display_module`] method.
+/// Returned by the [`SectionSequence::display_module`], [`SectionSequence::display_module_with`],
+/// and [`SectionSequence::display_sections`] methods.
 pub struct DisplayModule<'a, I: Input> {
     sections: &'a SectionSequence<I>,
+    section_filter: fn(u8) -> bool,
+    /// Start and end positions of the sections to write, since a `Range` is not `Copy`.
+    section_range: (usize, usize),
+    /// Start and end positions of the function bodies to write.
+    function_range: (u32, u32),
+    function_bodies: bool,
+    relocation_annotations: bool,
+    inline_imports: bool,
+    custom_sections: bool,
+    config: Config,
 }
 
 impl<'a, I: Input> DisplayModule<'a, I> {
-    pub(crate) fn new(sections: &'a SectionSequence<I>) -> Self {
-        Self { sections }
+    pub(crate) fn new(sections: &'a SectionSequence<I>, section_filter: fn(u8) -> bool) -> Self {
+        Self {
+            sections,
+            section_filter,
+            section_range: (0, usize::MAX),
+            function_range: (0, u32::MAX),
+            function_bodies: true,
+            relocation_annotations: false,
+            inline_imports: false,
+            custom_sections: true,
+            config: Config::new(),
+        }
+    }
+
+    /// Uses the given [`Config`] to control how the module is written.
+    #[inline]
+    pub fn with_config(mut self, config: Config) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Gets the options used to write the module.
+    #[inline]
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Omits the local variables and instructions of each function, only printing the number of
+    /// entries in the *code section*.
+    ///
+    /// This can be used to print the skeleton of a module whose functions are printed separately,
+    /// for example with the [`Display`](core::fmt::Display) implementation for
+    /// [`Func`](crate::component::Func).
+    #[inline]
+    pub fn without_function_bodies(mut self) -> Self {
+        self.function_bodies = false;
+        self
+    }
+
+    /// Only writes the sections whose position in the module, counting from `0`, is within the
+    /// given `range`.
+    ///
+    /// As with [`SectionSequence::display_sections`], sections that are not written are still
+    /// read when other sections depend on them. This allows a module to be written in pieces, such
+    /// as when writing the sections before and after the *code section* separately from the
+    /// function bodies.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let wasm = wat::parse_str("(module (func) (memory 1))").unwrap();
+    /// let sections = wasmiter::parse_module_sections(wasm.as_slice())?;
+    ///
+    /// // The type, function, and memory sections precede the code section
+    /// let text = sections
+    ///     .display_module()
+    ///     .without_function_bodies()
+    ///     .with_section_range(3..4)
+    ///     .to_string();
+    /// assert_eq!(text, "(module\n;; code section count = 1)");
+    /// # Ok::<(), wasmiter::parser::Error>(())
+    /// ```
+    #[inline]
+    pub fn with_section_range(mut self, range: core::ops::Range<usize>) -> Self {
+        self.section_range = (range.start, range.end);
+        self
+    }
+
+    /// Only writes the function bodies whose position in the *code section*, counting from `0`, is
+    /// within the given `range`.
+    ///
+    /// Functions are still referred to by their indices in the module, and the functions before the
+    /// `range` are still read. Combined with [`DisplayModule::with_section_range`], this allows the
+    /// functions of a module to be written in pieces with the same names and signatures as when the
+    /// entire module is written.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")]
+    /// # fn main() -> Result<(), wasmiter::parser::Error> {
+    /// let wasm = wat::parse_str("(module (func $a) (func $b) (func $c))").unwrap();
+    /// let sections = wasmiter::parse_module_sections(wasm.as_slice())?;
+    ///
+    /// // The code section follows the type and function sections
+    /// let text = sections
+    ///     .display_module()
+    ///     .with_section_range(2..3)
+    ///     .with_function_range(1..2)
+    ///     .to_string();
+    /// assert!(text.contains("(func $b"));
+    /// assert!(!text.contains("(func $a"));
+    /// assert!(!text.contains("(func $c"));
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))]
+    /// # fn main() {}
+    /// ```
+    #[inline]
+    pub fn with_function_range(mut self, range: core::ops::Range<u32>) -> Self {
+        self.function_range = (range.start, range.end);
+        self
+    }
+
+    /// Annotates instructions in function bodies with the relocations that apply to them, as
+    /// described by the module's
+    /// [relocation section](crate::custom::reloc::RelocationSection) for the *code section*.
+    ///
+    /// Each relocation is written as a comment following the instruction, containing the
+    /// [`RelocationType`](crate::custom::reloc::RelocationType) and the name of the symbol from
+    /// the symbol table in the [`linking` section](crate::custom::linking::LinkingSection). The
+    /// index of the symbol is written instead if it does not have a name, and type indices are
+    /// written for [`R_WASM_TYPE_INDEX_LEB`](crate::custom::reloc::RelocationType::R_WASM_TYPE_INDEX_LEB)
+    /// relocations.
+    #[inline]
+    pub fn with_relocation_annotations(mut self) -> Self {
+        self.relocation_annotations = true;
+        self
+    }
+
+    /// Writes each import using the
+    /// [inline abbreviation](https://webassembly.github.io/spec/core/text/modules.html#text-func-abbrev-import)
+    /// on the imported definition, such as `(func (import "m" "n") (type 0))`, rather than as a
+    /// standalone `(import "m" "n" (func (type 0)))` item.
+    #[inline]
+    pub fn with_inline_imports(mut self) -> Self {
+        self.inline_imports = true;
+        self
+    }
+
+    /// Treats custom sections as opaque, writing only their location instead of their contents,
+    /// and skips reading the *name section* and relocation sections.
+    ///
+    /// This avoids the cost of decoding and dumping custom sections when they are not needed,
+    /// which can make up most of the time spent writing modules built with debugging information.
+    /// In the `custom_sections` benchmark, a module containing a *name section* with names for
+    /// `50000` functions along with `512 KiB` of DWARF sections was written about 10 times faster.
+    ///
+    /// Since the *name section* is not read, names are not written, and
+    /// [relocation annotations](DisplayModule::with_relocation_annotations) are omitted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let wasm = wat::parse_str("(module $example (func $f))").unwrap();
+    /// let sections = wasmiter::parse_module_sections(wasm.as_slice())?;
+    ///
+    /// let text = sections.display_module().without_custom_sections().to_string();
+    /// assert!(text.contains("(; CUSTOM @ "));
+    /// assert!(!text.contains("UNRECOGNIZED"));
+    /// # Ok::<(), wasmiter::parser::Error>(())
+    /// ```
+    #[inline]
+    pub fn without_custom_sections(mut self) -> Self {
+        self.custom_sections = false;
+        self
     }
 
     #[inline]
     pub(crate) fn as_sections(&self) -> &'a SectionSequence<I> {
         self.sections
     }
+
+    /// Returns `true` if the section with the given `id` at the given `index` should be written.
+    #[inline]
+    pub(crate) fn includes_section(&self, index: usize, id: u8) -> bool {
+        (self.section_range.0..self.section_range.1).contains(&index) && (self.section_filter)(id)
+    }
+
+    #[inline]
+    pub(crate) fn function_bodies(&self) -> bool {
+        self.function_bodies
+    }
+
+    #[inline]
+    pub(crate) fn function_range(&self) -> (u32, u32) {
+        self.function_range
+    }
+
+    #[inline]
+    pub(crate) fn relocation_annotations(&self) -> bool {
+        self.relocation_annotations
+    }
+
+    #[inline]
+    pub(crate) fn inline_imports(&self) -> bool {
+        self.inline_imports
+    }
+
+    #[inline]
+    pub(crate) fn custom_sections(&self) -> bool {
+        self.custom_sections
+    }
 }
 
+impl<I: Input> Clone for DisplayModule<'_, I> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<I: Input> Copy for DisplayModule<'_, I> {}
+
 impl<I: Input> core::fmt::Debug for DisplayModule<'_, I> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "\"{}\"", self)
@@ -96,13 +96,18 @@ known_custom_ids! {
     /// [The `linking` custom section](https://github.com/WebAssembly/tool-conventions/blob/main/Linking.md#linking-metadata-section),
     /// described in the [WebAssembly tool conventions](https://github.com/WebAssembly/tool-conventions) for static linking.
     LINKING = "linking";
+    /// [The `external_debug_info` custom section](https://github.com/WebAssembly/tool-conventions/blob/main/Debugging.md#external-dwarf),
+    /// described in the [WebAssembly tool conventions](https://github.com/WebAssembly/tool-conventions) for debugging.
+    EXTERNAL_DEBUG_INFO = "external_debug_info";
 }
 
 pub(crate) fn is_custom_name_recognized<B: crate::input::Input>(
     name: crate::parser::name::Name<B>,
 ) -> Option<&'static str> {
-    let mut buffer = [0u8; 16]; // Should be enough to fit the largest known static custom name
-    if let Ok(slice) = name.copy_to_slice(&mut buffer) {
+    let mut buffer = [0u8; 24]; // Should be enough to fit the largest known static custom name
+    if name.length() > buffer.len() as u64 {
+        None
+    } else if let Ok(slice) = name.copy_to_slice(&mut buffer) {
         if let Ok(actual) = core::str::from_utf8(slice) {
             cached_custom_name(actual)
         } else {
@@ -112,3 +117,56 @@ pub(crate) fn is_custom_name_recognized<B: crate::input::Input>(
         None
     }
 }
+
+/// Gets the name of the section with the given *id*, as used in the section headers printed by
+/// [`dump`](crate::dump), or `None` if the *id* is not recognized.
+///
+/// # Example
+///
+/// ```
+/// use wasmiter::sections::id;
+///
+/// assert_eq!(id::name(id::ELEMENT), Some("Elem"));
+/// assert_eq!(id::name(id::DATA_COUNT), Some("DataCount"));
+/// assert_eq!(id::name(42), None);
+/// ```
+pub fn name(id: u8) -> Option<&'static str> {
+    Some(match id {
+        CUSTOM => "Custom",
+        TYPE => "Type",
+        IMPORT => "Import",
+        FUNC => "Function",
+        TABLE => "Table",
+        MEMORY => "Memory",
+        GLOBAL => "Global",
+        EXPORT => "Export",
+        START => "Start",
+        ELEMENT => "Elem",
+        CODE => "Code",
+        DATA => "Data",
+        DATA_COUNT => "DataCount",
+        TAG => "Tag",
+        _ => return None,
+    })
+}
+
+/// Gets the position of a non-custom section within a module, or `None` for custom and unknown
+/// sections.
+pub(crate) fn order(id: u8) -> Option<u8> {
+    Some(match id {
+        TYPE => 1,
+        IMPORT => 2,
+        FUNC => 3,
+        TABLE => 4,
+        MEMORY => 5,
+        TAG => 6,
+        GLOBAL => 7,
+        EXPORT => 8,
+        START => 9,
+        ELEMENT => 10,
+        DATA_COUNT => 11,
+        CODE => 12,
+        DATA => 13,
+        _ => return None,
+    })
+}
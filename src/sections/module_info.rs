@@ -0,0 +1,263 @@
+use crate::{
+    component::{ImportKind, KnownSection},
+    custom::{name::NameSubsection, CustomSection, KnownCustomSection},
+    index::FuncIdx,
+    input::{BorrowInput as _, Input, Window},
+    parser::Parsed,
+    sections::{id as section_id, SectionSequence},
+    types::{MemType, Sharing},
+};
+
+/// Returns `true` if the module name of an import refers to a
+/// [WASI](https://github.com/WebAssembly/WASI) interface, such as `wasi_snapshot_preview1`.
+fn is_wasi_module_name<I: Input>(name: &crate::parser::name::Name<I>) -> Parsed<bool> {
+    let mut buffer = [0u8; 5];
+    let prefix = name.copy_to_slice(&mut buffer)?;
+    Ok(match &*prefix {
+        b"wasi" => true,
+        [b'w', b'a', b's', b'i', separator] => matches!(separator, b'_' | b':'),
+        _ => false,
+    })
+}
+
+impl<I: Input> SectionSequence<I> {
+    /// Interprets each section whose [`id`](super::Section::id) is in `ids`, stopping once the
+    /// last of the `ids` has been passed or `f` returns `Some`.
+    ///
+    /// The `ids` must be in the order that the sections appear in a module.
+    fn find_in_sections<T>(
+        &self,
+        ids: &[u8],
+        mut f: impl FnMut(KnownSection<Window<&I>>) -> Parsed<Option<T>>,
+    ) -> Parsed<Option<T>> {
+        let last = ids.last().copied().and_then(section_id::order);
+        for result in self.borrow_input() {
+            let section = result?;
+            let order = section_id::order(section.id());
+            if order.is_some() && order > last {
+                break;
+            } else if !ids.contains(&section.id()) {
+                continue;
+            }
+
+            if let Ok(known) = KnownSection::interpret(section) {
+                if let Some(value) = f(known?)? {
+                    return Ok(Some(value));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Calls `f` with each imported and defined memory, stopping when `f` returns `Some`.
+    fn find_memory<T>(&self, mut f: impl FnMut(MemType) -> Option<T>) -> Parsed<Option<T>> {
+        self.find_in_sections(&[section_id::IMPORT, section_id::MEMORY], |known| {
+            match known {
+                KnownSection::Import(mut imports) => {
+                    while let Some(import) = imports.parse()? {
+                        if let ImportKind::Memory(memory) = import.kind() {
+                            if let Some(value) = f(*memory) {
+                                return Ok(Some(value));
+                            }
+                        }
+                    }
+                }
+                KnownSection::Memory(memories) => {
+                    for result in memories {
+                        if let Some(value) = f(result?) {
+                            return Ok(Some(value));
+                        }
+                    }
+                }
+                _ => (),
+            }
+
+            Ok(None)
+        })
+    }
+
+    /// Returns `true` if the module imports anything from a [WASI](https://github.com/WebAssembly/WASI)
+    /// module, such as `wasi_snapshot_preview1` or `wasi_unstable`.
+    ///
+    /// Only the *import* section is read.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a section or import could not be parsed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let wasm = wat::parse_str(r#"(module
+    ///     (import "wasi_snapshot_preview1" "proc_exit" (func (param i32))))"#).unwrap();
+    ///
+    /// let sections = wasmiter::parse_module_sections(wasm.as_slice())?;
+    /// assert!(sections.has_wasi_imports()?);
+    /// # Ok::<(), wasmiter::parser::Error>(())
+    /// ```
+    pub fn has_wasi_imports(&self) -> Parsed<bool> {
+        let found = self.find_in_sections(&[section_id::IMPORT], |known| {
+            if let KnownSection::Import(mut imports) = known {
+                while let Some(import) = imports.parse()? {
+                    if is_wasi_module_name(import.module())? {
+                        return Ok(Some(()));
+                    }
+                }
+            }
+
+            Ok(None)
+        })?;
+
+        Ok(found.is_some())
+    }
+
+    /// Gets the [minimum](crate::types::Limits::minimum) number of pages of the memory at index
+    /// `0`, which is either the first imported memory or the first memory defined in the module.
+    ///
+    /// Returns `Ok(None)` if the module has no memories. Note that the size of a page is 64 KiB,
+    /// unless a [custom page size](crate::types::Limits::page_size) is used.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a section, import, or memory could not be parsed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let wasm = wat::parse_str("(module (memory 3 10) (memory 1))").unwrap();
+    /// let sections = wasmiter::parse_module_sections(wasm.as_slice())?;
+    /// assert_eq!(sections.initial_memory_pages()?, Some(3));
+    /// # Ok::<(), wasmiter::parser::Error>(())
+    /// ```
+    pub fn initial_memory_pages(&self) -> Parsed<Option<u64>> {
+        self.find_memory(|memory| Some(memory.minimum()))
+    }
+
+    /// Returns `true` if any imported or defined memory is
+    /// [shared](https://github.com/WebAssembly/threads).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a section, import, or memory could not be parsed.
+    pub fn uses_shared_memory(&self) -> Parsed<bool> {
+        let shared =
+            self.find_memory(|memory| Some(()).filter(|_| memory.share() == Sharing::Shared))?;
+        Ok(shared.is_some())
+    }
+
+    /// Gets the function specified in the
+    /// [*start* section](https://webassembly.github.io/spec/core/binary/modules.html#binary-startsec),
+    /// or `Ok(None)` if the module does not have one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a section could not be parsed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wasmiter::index::FuncIdx;
+    ///
+    /// let wasm = wat::parse_str("(module (func) (func) (start 1))").unwrap();
+    /// let sections = wasmiter::parse_module_sections(wasm.as_slice())?;
+    /// assert_eq!(sections.start_function()?, Some(FuncIdx::from(1u32)));
+    /// # Ok::<(), wasmiter::parser::Error>(())
+    /// ```
+    pub fn start_function(&self) -> Parsed<Option<FuncIdx>> {
+        self.find_in_sections(&[section_id::START], |known| {
+            Ok(match known {
+                KnownSection::Start(start) => Some(start),
+                _ => None,
+            })
+        })
+    }
+
+    /// Gets the total number of imported and defined functions in the module.
+    ///
+    /// Only the *import* and *function* sections are read.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a section or import could not be parsed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let wasm = wat::parse_str(r#"(module
+    ///     (import "env" "f" (func))
+    ///     (func)
+    ///     (func))"#).unwrap();
+    ///
+    /// let sections = wasmiter::parse_module_sections(wasm.as_slice())?;
+    /// assert_eq!(sections.function_count()?, 3);
+    /// # Ok::<(), wasmiter::parser::Error>(())
+    /// ```
+    pub fn function_count(&self) -> Parsed<u32> {
+        let mut count = 0u32;
+        self.find_in_sections(&[section_id::IMPORT, section_id::FUNC], |known| {
+            match known {
+                KnownSection::Import(mut imports) => {
+                    while let Some(import) = imports.parse()? {
+                        if let ImportKind::Function(_) = import.kind() {
+                            count = count.saturating_add(1);
+                        }
+                    }
+                }
+                KnownSection::Function(functions) => {
+                    count = count.saturating_add(functions.declared_count());
+                }
+                _ => (),
+            }
+
+            Ok(None::<()>)
+        })?;
+
+        Ok(count)
+    }
+
+    /// Looks up the index of the function with the given `name` in the module's
+    /// [*name section*](crate::custom::name), returning `Ok(None)` if no function has that name or
+    /// if the module has no *name section*.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a section or the *function name subsection* could not be parsed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wasmiter::index::FuncIdx;
+    ///
+    /// let wasm = wat::parse_str("(module (func $a) (func $b))").unwrap();
+    /// let sections = wasmiter::parse_module_sections(wasm.as_slice())?;
+    /// assert_eq!(sections.find_function_by_name("b")?, Some(FuncIdx::from(1u32)));
+    /// assert_eq!(sections.find_function_by_name("c")?, None);
+    /// # Ok::<(), wasmiter::parser::Error>(())
+    /// ```
+    pub fn find_function_by_name(&self, name: &str) -> Parsed<Option<FuncIdx>> {
+        for result in self.borrow_input() {
+            let custom = match CustomSection::try_from_section(result?) {
+                Ok(custom) => custom?,
+                Err(_) => continue,
+            };
+
+            if let Ok(KnownCustomSection::Name(mut names)) = KnownCustomSection::interpret(custom) {
+                while let Some(subsection) = names.parse() {
+                    // Unrecognized subsections are skipped
+                    if let Ok(subsection) = subsection {
+                        if let NameSubsection::FunctionName(mut function_names) = subsection? {
+                            while let Some(name_assoc) = function_names.parse()? {
+                                if name_assoc.name().try_eq_str(name)? {
+                                    return Ok(Some(name_assoc.index()));
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(None)
+    }
+}
@@ -0,0 +1,225 @@
+//! Functions for printing parsed structures in the
+//! [WebAssembly text format](https://webassembly.github.io/spec/core/text/index.html).
+//!
+//! Sections and entire modules can be printed with their [`Display`] implementations, such as
+//! the one returned by
+//! [`SectionSequence::display_module`](crate::sections::SectionSequence::display_module). This
+//! module allows printing smaller structures that implement [`Wat`], such as a single
+//! [`Import`](crate::component::Import), [`Export`](crate::component::Export), or
+//! [`Instruction`](crate::instruction_set::Instruction), along with types such as a
+//! [`GlobalType`](crate::types::GlobalType).
+//!
+//! The formatting of the text, such as whether instructions are folded or comments are written,
+//! can be changed with a [`Config`], for example by using
+//! [`SectionSequence::display_module_with`](crate::sections::SectionSequence::display_module_with).
+//!
+//! Writing text does not require allocation, so [`write_to`] can be used with a [`SliceWriter`]
+//! in `no_std` environments.
+//!
+//! When the `alloc` feature is enabled, modules in the text format can also be parsed into their
+//! binary encoding with [`parse_str`] or [`parse_module`].
+//!
+//! # Determinism
+//!
+//! The text written for a given input and configuration is byte-for-byte identical across runs,
+//! threads, and platforms. Definitions are written in the order they appear in the binary,
+//! identifiers are derived only from indices and names in the module, and floating-point constants
+//! are written using their exact bit patterns. The output only changes
+//! intentionally between versions of this crate, in which case [`FORMAT_VERSION`] is changed, so
+//! that users comparing the output against snapshots know to update them.
+//!
+//! # Example
+//!
+//! ```
+//! use wasmiter::component::KnownSection;
+//!
+//! # #[cfg(feature = "alloc")]
+//! # fn main() -> Result<(), wasmiter::parser::Error> {
+//! let wasm = wat::parse_str(r#"(module
+//!     (import "env" "log" (func (param i32)))
+//!     (func (param i32) (result i32) local.get 0))"#).unwrap();
+//!
+//! let mut import = None;
+//! let mut first_instruction = String::new();
+//! for result in wasmiter::parse_module_sections(wasm.as_slice())? {
+//!     match KnownSection::interpret(result?) {
+//!         Ok(known) => match known? {
+//!             KnownSection::Import(mut imports) => {
+//!                 import = Some(wasmiter::text::to_string(imports.next().unwrap()?));
+//!             }
+//!             KnownSection::Code(mut code) => {
+//!                 let body = code.next().unwrap()?;
+//!                 body.read(|_| wasmiter::parser::Parsed::Ok(()), |(), instructions| {
+//!                     instructions.next(|instruction| {
+//!                         let _ = wasmiter::text::write_to(instruction, &mut first_instruction);
+//!                         wasmiter::parser::Parsed::Ok(())
+//!                     });
+//!                     Ok(())
+//!                 })?;
+//!             }
+//!             _ => (),
+//!         },
+//!         Err(_) => (),
+//!     }
+//! }
+//!
+//! assert_eq!(import.as_deref(), Some(r#"(import "env" "log" (func (type 0)))"#));
+//! assert_eq!(first_instruction, "local.get 0");
+//! # Ok(())
+//! # }
+//! # #[cfg(not(feature = "alloc"))]
+//! # fn main() {}
+//! ```
+//!
+//! [`Display`]: core::fmt::Display
+
+use core::fmt::{Display, Formatter};
+
+pub use crate::wat::{Config, Wat, Writer};
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub use crate::wat::text_parser::{parse_module, parse_str, ParseError};
+
+/// Identifies the current version of the text written by this module.
+///
+/// This is changed whenever the text written for the same input and configuration changes, such
+/// as when the formatting of instructions or comments is altered.
+pub const FORMAT_VERSION: &str = "5";
+
+/// Writes the `structure` to the given [`Formatter`], writing a comment describing the error if
+/// it could not be parsed.
+///
+/// This can be used in [`Display`] implementations.
+///
+/// # Errors
+///
+/// Returns an error if writing to the [`Formatter`] failed.
+#[inline]
+pub fn write<T: Wat>(structure: T, f: &mut Formatter<'_>) -> core::fmt::Result {
+    crate::wat::display_impls::write_wat(structure, f)
+}
+
+/// Wraps a [`Wat`] structure so that it can be written once with [`Display`].
+struct Once<T>(core::cell::Cell<Option<T>>);
+
+impl<T: Wat> Display for Once<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self.0.take() {
+            Some(structure) => write(structure, f),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Writes the `structure` to the given `output`, writing a comment describing the error if it
+/// could not be parsed.
+///
+/// # Errors
+///
+/// Returns an error if writing to the `output` failed.
+pub fn write_to<T: Wat, W: core::fmt::Write + ?Sized>(
+    structure: T,
+    output: &mut W,
+) -> core::fmt::Result {
+    output.write_fmt(format_args!(
+        "{}",
+        Once(core::cell::Cell::new(Some(structure)))
+    ))
+}
+
+/// A [`Write`](core::fmt::Write) implementation that writes text into a fixed-size buffer, without
+/// allocating.
+///
+/// If the text does not fit, as much of it as possible is written, and an error is returned to
+/// stop any further writes. This allows [`write_to`] to be used in `no_std` environments, such as
+/// when printing a trace of instructions in an embedded debugger.
+///
+/// # Example
+///
+/// ```
+/// use wasmiter::{text, types::{GlobalType, GlobalMutability, ValType}};
+///
+/// let mut buffer = [0u8; 16];
+/// let mut writer = text::SliceWriter::new(&mut buffer);
+/// let global = GlobalType::new(GlobalMutability::Variable, ValType::I64);
+/// text::write_to(global, &mut writer).unwrap();
+/// assert_eq!(writer.as_str(), "(mut i64)");
+///
+/// let mut small = [0u8; 4];
+/// let mut writer = text::SliceWriter::new(&mut small);
+/// assert!(text::write_to(global, &mut writer).is_err());
+/// assert_eq!(writer.as_str(), "(mut");
+/// assert!(writer.is_truncated());
+/// ```
+#[derive(Debug)]
+pub struct SliceWriter<'a> {
+    buffer: &'a mut [u8],
+    length: usize,
+    truncated: bool,
+}
+
+impl<'a> SliceWriter<'a> {
+    /// Creates a new [`SliceWriter`] that writes to the start of the given `buffer`.
+    pub fn new(buffer: &'a mut [u8]) -> Self {
+        Self {
+            buffer,
+            length: 0,
+            truncated: false,
+        }
+    }
+
+    /// Gets the text that was written so far.
+    pub fn as_str(&self) -> &str {
+        // Only whole characters are copied into the buffer
+        core::str::from_utf8(&self.buffer[..self.length]).unwrap_or_default()
+    }
+
+    /// Returns `true` if some text could not be written because the buffer was full.
+    #[inline]
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Discards any text that was written, allowing the buffer to be reused.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.length = 0;
+        self.truncated = false;
+    }
+}
+
+impl core::fmt::Write for SliceWriter<'_> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        if self.truncated {
+            return Err(core::fmt::Error);
+        }
+
+        let remaining = self.buffer.len() - self.length;
+        let mut amount = s.len().min(remaining);
+        while !s.is_char_boundary(amount) {
+            amount -= 1;
+        }
+
+        self.buffer[self.length..self.length + amount].copy_from_slice(&s.as_bytes()[..amount]);
+        self.length += amount;
+
+        if amount < s.len() {
+            self.truncated = true;
+            Err(core::fmt::Error)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Allocates a [`String`](alloc::string::String) containing the `structure` in the WebAssembly
+/// text format.
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub fn to_string<T: Wat>(structure: T) -> alloc::string::String {
+    let mut text = alloc::string::String::new();
+    // Writing to a String never fails
+    let _ = write_to(structure, &mut text);
+    text
+}
@@ -0,0 +1,74 @@
+//! Compile-time checks that parser types can be sent and shared across threads.
+//!
+//! Parser types only store their [`Input`](crate::input::Input) and offsets into it, so they are
+//! [`Send`] and [`Sync`] whenever the [`Input`](crate::input::Input) is. These assertions ensure
+//! that this does not change by accident, such as by storing a [`Cell`](core::cell::Cell) or an
+//! [`Rc`](alloc::rc::Rc) within a parser.
+
+const fn assert_send_sync<T: Send + Sync>() {}
+
+macro_rules! assert_parsers_send_sync {
+    ($($input:ty),+ $(,)?) => {$({
+        use crate::{component, component_model, custom, index, input, instruction_set, parser, sections};
+
+        type I = $input;
+
+        assert_send_sync::<sections::Section<I>>();
+        assert_send_sync::<sections::SectionSequence<I>>();
+        assert_send_sync::<sections::DebugModule<'static, I>>();
+        assert_send_sync::<sections::DisplayModule<'static, I>>();
+        assert_send_sync::<input::Window<I>>();
+        assert_send_sync::<parser::name::Name<I>>();
+        assert_send_sync::<parser::Vector<u64, I>>();
+        assert_send_sync::<component::KnownSection<I>>();
+        assert_send_sync::<component::TypesComponent<I>>();
+        assert_send_sync::<component::ImportsComponent<I>>();
+        assert_send_sync::<component::Import<I>>();
+        assert_send_sync::<component::FunctionSection<I>>();
+        assert_send_sync::<component::TablesComponent<I>>();
+        assert_send_sync::<component::MemsComponent<I>>();
+        assert_send_sync::<component::GlobalsComponent<I>>();
+        assert_send_sync::<component::ExportsComponent<I>>();
+        assert_send_sync::<component::Export<I>>();
+        assert_send_sync::<component::ElemsComponent<I>>();
+        assert_send_sync::<component::CodeSection<I>>();
+        assert_send_sync::<component::Code<I>>();
+        assert_send_sync::<component::FuncsComponent<I, I>>();
+        assert_send_sync::<component::Func<I>>();
+        assert_send_sync::<component::DatasComponent<I>>();
+        assert_send_sync::<component::TagsComponent<I>>();
+        assert_send_sync::<component::Locals<u64, I>>();
+        assert_send_sync::<component::ResultType<u64, I>>();
+        assert_send_sync::<component::IndexVector<index::FuncIdx, u64, I>>();
+        assert_send_sync::<instruction_set::InstructionSequence<u64, I>>();
+        assert_send_sync::<instruction_set::Instruction<'static, I>>();
+        assert_send_sync::<custom::CustomSection<I>>();
+        assert_send_sync::<custom::KnownCustomSection<I>>();
+        assert_send_sync::<custom::name::NameSection<I>>();
+        assert_send_sync::<custom::name::NameMap<index::FuncIdx, u64, I>>();
+        assert_send_sync::<custom::reloc::RelocationSection<I>>();
+        assert_send_sync::<custom::dwarf::DwarfSection<I>>();
+        assert_send_sync::<custom::dwarf::ExternalDebugInfo<I>>();
+        assert_send_sync::<custom::linking::LinkingSection<I>>();
+        assert_send_sync::<custom::linking::SymbolTable<I>>();
+        assert_send_sync::<custom::linking::Comdat<I>>();
+        assert_send_sync::<component_model::KnownComponentSection<I>>();
+    })+};
+}
+
+const _: () = {
+    assert_send_sync::<crate::parser::Error>();
+    assert_send_sync::<crate::parser::Position>();
+    assert_send_sync::<crate::input::Error>();
+
+    assert_parsers_send_sync!(&'static [u8], &'static &'static [u8]);
+
+    #[cfg(feature = "alloc")]
+    assert_parsers_send_sync!(alloc::sync::Arc<[u8]>, alloc::boxed::Box<[u8]>);
+
+    #[cfg(feature = "std")]
+    assert_parsers_send_sync!(crate::input::BufferedInput<&'static [u8]>);
+
+    #[cfg(feature = "mmap")]
+    assert_parsers_send_sync!(memmap2::Mmap);
+};
@@ -0,0 +1,31 @@
+//! Optional instrumentation of the parser using the [`tracing`](https://docs.rs/tracing/) crate,
+//! enabled by the `trace` feature.
+//!
+//! When the `trace` feature is disabled, the macros in this module expand to nothing, and any
+//! fields passed to them are not evaluated.
+
+/// Placeholder for an entered span when the `trace` feature is disabled.
+#[cfg(not(feature = "trace"))]
+#[derive(Debug)]
+pub(crate) struct NoSpan;
+
+/// Enters a new span at the `TRACE` level, returning a guard that exits the span when dropped.
+macro_rules! span {
+    ($name:literal $(, $($fields:tt)+)?) => {{
+        #[cfg(feature = "trace")]
+        let guard = tracing::trace_span!($name $(, $($fields)+)?).entered();
+        #[cfg(not(feature = "trace"))]
+        let guard = $crate::trace::NoSpan;
+        guard
+    }};
+}
+
+/// Records an event at the `TRACE` level.
+macro_rules! event {
+    ($($args:tt)+) => {
+        #[cfg(feature = "trace")]
+        tracing::trace!($($args)+);
+    };
+}
+
+pub(crate) use {event, span};
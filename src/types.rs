@@ -3,11 +3,16 @@
 use core::fmt::{Display, Formatter};
 
 mod block_type;
+#[cfg(feature = "alloc")]
+mod func_type;
 mod global_type;
 mod limits;
 mod table_type;
 
 pub use block_type::BlockType;
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub use func_type::FuncType;
 pub use global_type::{GlobalMutability, GlobalType};
 pub use limits::{IdxType, Limits, MemType, Sharing};
 pub use table_type::TableType;
@@ -34,14 +39,92 @@ pub enum VecType {
     V128,
 }
 
+/// Represents a
+/// [heap type](https://webassembly.github.io/function-references/core/syntax/types.html#heap-types),
+/// which indicates what a reference refers to.
+///
+/// Type indices were introduced in the
+/// [typed function references proposal](https://github.com/WebAssembly/function-references).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum HeapType {
+    /// A reference to any function.
+    Func,
+    /// A reference to an opaque object provided by the WebAssembly embedder.
+    Extern,
+    /// A reference to a caught exception, introduced in the
+    /// [exception handling proposal](https://github.com/WebAssembly/exception-handling).
+    Exn,
+    /// A reference to a function with the given type.
+    Index(crate::index::TypeIdx),
+}
+
 /// Represents a
 /// [WebAssembly reference type](https://webassembly.github.io/spec/core/syntax/types.html#reference-types).
+///
+/// Reference types with a nullable [`HeapType::Func`], [`HeapType::Extern`], or [`HeapType::Exn`]
+/// are always represented by the [`RefType::Func`], [`RefType::Extern`], and [`RefType::Exn`]
+/// variants, which can be ensured by using [`RefType::new`].
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum RefType {
     /// A `funcref`, a reference to a function.
     Func,
     /// An `externref`, an opaque reference to some object provided by the WebAssembly embedder.
     Extern,
+    /// An `exnref`, a reference to an exception caught by a
+    /// [**try_table**](crate::instruction_set::Instruction::TryTable) block.
+    ///
+    /// Introduced in the
+    /// [exception handling proposal](https://github.com/WebAssembly/exception-handling).
+    Exn,
+    /// A `(ref null? ht)`, introduced in the
+    /// [typed function references proposal](https://github.com/WebAssembly/function-references).
+    Typed {
+        /// Whether the reference can be `null`.
+        nullable: bool,
+        /// What the reference refers to.
+        heap_type: HeapType,
+    },
+}
+
+impl RefType {
+    /// Creates a reference to the given [`HeapType`].
+    pub const fn new(nullable: bool, heap_type: HeapType) -> Self {
+        match (nullable, heap_type) {
+            (true, HeapType::Func) => Self::Func,
+            (true, HeapType::Extern) => Self::Extern,
+            (true, HeapType::Exn) => Self::Exn,
+            _ => Self::Typed {
+                nullable,
+                heap_type,
+            },
+        }
+    }
+
+    /// Returns `true` if the reference can be `null`.
+    #[inline]
+    pub const fn is_nullable(self) -> bool {
+        match self {
+            Self::Func | Self::Extern | Self::Exn => true,
+            Self::Typed { nullable, .. } => nullable,
+        }
+    }
+
+    /// Gets what the reference refers to.
+    #[inline]
+    pub const fn heap_type(self) -> HeapType {
+        match self {
+            Self::Func => HeapType::Func,
+            Self::Extern => HeapType::Extern,
+            Self::Exn => HeapType::Exn,
+            Self::Typed { heap_type, .. } => heap_type,
+        }
+    }
+
+    /// Gets the non-nullable version of this reference type.
+    #[inline]
+    pub const fn as_non_nullable(self) -> Self {
+        Self::new(false, self.heap_type())
+    }
 }
 
 /// Represents a
@@ -63,6 +146,15 @@ pub enum ValType {
     ExternRef,
     /// [`v128`](VecType::V128)
     V128,
+    /// [`exnref`](RefType::Exn)
+    ExnRef,
+    /// A [typed reference](RefType::Typed).
+    TypedRef {
+        /// Whether the reference can be `null`.
+        nullable: bool,
+        /// What the reference refers to.
+        heap_type: HeapType,
+    },
 }
 
 impl ValType {
@@ -70,6 +162,11 @@ impl ValType {
         match self {
             Self::FuncRef => Some(RefType::Func),
             Self::ExternRef => Some(RefType::Extern),
+            Self::ExnRef => Some(RefType::Exn),
+            Self::TypedRef {
+                nullable,
+                heap_type,
+            } => Some(RefType::new(nullable, heap_type)),
             _ => None,
         }
     }
@@ -91,6 +188,14 @@ impl From<RefType> for ValType {
         match ty {
             RefType::Extern => Self::ExternRef,
             RefType::Func => Self::FuncRef,
+            RefType::Exn => Self::ExnRef,
+            RefType::Typed {
+                nullable,
+                heap_type,
+            } => Self::TypedRef {
+                nullable,
+                heap_type,
+            },
         }
     }
 }
@@ -112,7 +217,12 @@ impl Display for ValType {
             Self::F64 => "f64",
             Self::FuncRef => "funcref",
             Self::ExternRef => "externref",
+            Self::ExnRef => "exnref",
             Self::V128 => "v128",
+            Self::TypedRef {
+                nullable,
+                heap_type,
+            } => return Display::fmt(&RefType::new(*nullable, *heap_type), f),
         })
     }
 }
@@ -128,11 +238,31 @@ impl Display for NumType {
     }
 }
 
+impl Display for HeapType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Func => f.write_str("func"),
+            Self::Extern => f.write_str("extern"),
+            Self::Exn => f.write_str("exn"),
+            Self::Index(index) => write!(f, "{}", index.to_u32()),
+        }
+    }
+}
+
 impl Display for RefType {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-        f.write_str(match self {
-            Self::Func => "funcref",
-            Self::Extern => "externref",
-        })
+        match self {
+            Self::Func => f.write_str("funcref"),
+            Self::Extern => f.write_str("externref"),
+            Self::Exn => f.write_str("exnref"),
+            Self::Typed {
+                nullable: true,
+                heap_type,
+            } => write!(f, "(ref null {heap_type})"),
+            Self::Typed {
+                nullable: false,
+                heap_type,
+            } => write!(f, "(ref {heap_type})"),
+        }
     }
 }
@@ -0,0 +1,52 @@
+use crate::types::ValType;
+use alloc::vec::Vec;
+
+/// Represents a
+/// [WebAssembly function type](https://webassembly.github.io/spec/core/syntax/types.html#function-types),
+/// which specifies the types of a function's parameters and results.
+///
+/// Unlike the function types read from a *type* section, a [`FuncType`] owns its parameter and
+/// result types.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct FuncType {
+    /// The types of the parameters, followed by the types of the results.
+    types: Vec<ValType>,
+    parameter_count: usize,
+}
+
+impl FuncType {
+    /// Creates a new function type with the given `parameters` and `results`.
+    pub fn new<P, R>(parameters: P, results: R) -> Self
+    where
+        P: IntoIterator<Item = ValType>,
+        R: IntoIterator<Item = ValType>,
+    {
+        let mut types = Vec::from_iter(parameters);
+        let parameter_count = types.len();
+        types.extend(results);
+        Self {
+            types,
+            parameter_count,
+        }
+    }
+
+    /// Gets the types of the function's parameters.
+    #[inline]
+    pub fn parameters(&self) -> &[ValType] {
+        &self.types[..self.parameter_count]
+    }
+
+    /// Gets the types of the function's results.
+    #[inline]
+    pub fn results(&self) -> &[ValType] {
+        &self.types[self.parameter_count..]
+    }
+}
+
+#[cfg(feature = "mem-usage")]
+impl crate::mem_usage::MemUsage for FuncType {
+    #[inline]
+    fn mem_usage(&self) -> usize {
+        crate::mem_usage::vec_capacity(&self.types)
+    }
+}
@@ -31,6 +31,7 @@ pub struct Limits {
     maximum: Option<u64>,
     share: Sharing,
     index_type: IdxType,
+    page_size_log2: Option<u8>,
 }
 
 impl Limits {
@@ -48,10 +49,24 @@ impl Limits {
                 maximum,
                 share,
                 index_type,
+                page_size_log2: None,
             }),
         }
     }
 
+    /// Sets the size of the pages of a linear memory to `2` to the power of `log2`.
+    ///
+    /// Returns `None` if the page size is not `1` or `65536`, the only sizes allowed by the
+    /// [WebAssembly custom page sizes proposal](https://github.com/WebAssembly/custom-page-sizes).
+    pub const fn with_page_size_log2(mut self, log2: u8) -> Option<Self> {
+        if log2 == 0 || log2 == 16 {
+            self.page_size_log2 = Some(log2);
+            Some(self)
+        } else {
+            None
+        }
+    }
+
     /// The minimum.
     #[inline]
     pub const fn minimum(&self) -> u64 {
@@ -79,6 +94,26 @@ impl Limits {
         self.index_type
     }
 
+    /// The base-2 logarithm of the size of each page of a linear memory, if it was explicitly
+    /// specified.
+    ///
+    /// See the [WebAssembly custom page sizes proposal](https://github.com/WebAssembly/custom-page-sizes)
+    /// for more information.
+    #[inline]
+    pub const fn page_size_log2(&self) -> Option<u8> {
+        self.page_size_log2
+    }
+
+    /// The size, in bytes, of each page of a linear memory.
+    ///
+    /// Defaults to `65536` (64 KiB) if a page size was not specified.
+    pub const fn page_size(&self) -> u64 {
+        match self.page_size_log2 {
+            Some(log2) => 1 << log2,
+            None => 65536,
+        }
+    }
+
     /// Returns `true` if the [`Limits`] would require the
     /// [64-bit memory proposal](https://github.com/WebAssembly/memory64).
     pub fn requires_memory_64(&self) -> bool {
@@ -86,6 +121,22 @@ impl Limits {
             || matches!(self.index_type, IdxType::I64)
             || matches!(self.maximum, Some(max) if max > u64::from(u32::MAX))
     }
+
+    /// Checks if these [`Limits`] are within the `required` limits, as defined by the
+    /// [import matching rules](https://webassembly.github.io/spec/core/exec/modules.html#import-matching).
+    #[cfg(feature = "alloc")]
+    pub(crate) fn matches(&self, required: &Self) -> bool {
+        self.minimum >= required.minimum
+            && match required.maximum {
+                Some(required_maximum) => self
+                    .maximum
+                    .map_or(false, |maximum| maximum <= required_maximum),
+                None => true,
+            }
+            && self.share == required.share
+            && self.index_type == required.index_type
+            && self.page_size() == required.page_size()
+    }
 }
 
 /// A
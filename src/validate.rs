@@ -0,0 +1,641 @@
+//! Types and functions for
+//! [validating](https://webassembly.github.io/spec/core/valid/index.html) WebAssembly modules.
+//!
+//! The parsers in this crate only check that a module is correctly encoded. A module that is
+//! successfully parsed may still be invalid, such as when a function body uses an operand of the
+//! wrong type, or an instruction refers to a function that does not exist.
+
+mod error;
+
+pub use error::ValidationError;
+
+pub(crate) use error::ErrorKind;
+
+use crate::{
+    component::{
+        DataMode, ElementInit, ElementMode, ExportKind, ImportKind, KnownSection, LocalTypes, Tag,
+    },
+    index::{ElemIdx, FuncIdx, GlobalIdx, MemIdx, TableIdx, TagIdx, TypeIdx},
+    input::{BorrowInput as _, Input},
+    instruction_set::{self, Instruction, InstructionSequence, TypeChecker, TypeContext},
+    parser::Offset,
+    sections::{id as section_id, SectionSequence},
+    types::{
+        FuncType, GlobalType, HeapType, IdxType, MemType, RefType, Sharing, TableType, ValType,
+    },
+};
+use alloc::{collections::BTreeSet, vec::Vec};
+
+/// The
+/// [validation context](https://webassembly.github.io/spec/core/valid/conventions.html#contexts)
+/// for a module, built up as each section is validated.
+#[derive(Default)]
+struct ModuleContext {
+    types: Vec<FuncType>,
+    /// The type of each imported and defined function.
+    functions: Vec<TypeIdx>,
+    tables: Vec<TableType>,
+    memories: Vec<MemType>,
+    globals: Vec<GlobalType>,
+    /// The number of globals that can be referred to in constant expressions.
+    constant_globals: u32,
+    tags: Vec<TypeIdx>,
+    elements: Vec<RefType>,
+    data_count: Option<u32>,
+    /// Functions that can be referred to by **ref.func** instructions in function bodies.
+    references: BTreeSet<u32>,
+}
+
+impl ModuleContext {
+    fn func_type(&self, index: TypeIdx) -> Result<&FuncType, ErrorKind> {
+        self.types
+            .get(usize::from(index))
+            .ok_or(ErrorKind::UnknownIndex {
+                space: "type",
+                index: index.to_u32(),
+            })
+    }
+
+    fn function_count(&self) -> u32 {
+        u32::try_from(self.functions.len()).unwrap_or(u32::MAX)
+    }
+
+    fn check_function(&self, index: u32) -> Result<(), ErrorKind> {
+        if index < self.function_count() {
+            Ok(())
+        } else {
+            Err(ErrorKind::UnknownIndex {
+                space: "function",
+                index,
+            })
+        }
+    }
+
+    fn check_limits(&self, limits: &crate::types::Limits, bound: u64) -> Result<(), ErrorKind> {
+        if limits.minimum() > bound || limits.maximum().map_or(false, |maximum| maximum > bound) {
+            Err(ErrorKind::Invalid("limits exceed the maximum size"))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn check_memory(&self, memory: &MemType) -> Result<(), ErrorKind> {
+        let bound = match memory.index_type() {
+            IdxType::I32 => (1u64 << 32) / memory.page_size(),
+            IdxType::I64 => (u64::MAX / memory.page_size()).saturating_add(1),
+        };
+
+        self.check_limits(memory, bound)?;
+
+        if memory.share() == Sharing::Shared && memory.maximum().is_none() {
+            return Err(ErrorKind::Invalid("shared memory must have a maximum size"));
+        }
+
+        Ok(())
+    }
+
+    /// Checks that a typed reference refers to a type that exists.
+    fn check_val_type(&self, value_type: ValType) -> Result<(), ErrorKind> {
+        match value_type.try_to_ref_type().map(RefType::heap_type) {
+            Some(HeapType::Index(index)) => self.func_type(index).map(|_| ()),
+            _ => Ok(()),
+        }
+    }
+
+    fn check_table(&self, table: &TableType) -> Result<(), ErrorKind> {
+        self.check_val_type(table.element_type().into())?;
+        let bound = match table.limits().index_type() {
+            IdxType::I32 => u64::from(u32::MAX),
+            IdxType::I64 => u64::MAX,
+        };
+
+        self.check_limits(table.limits(), bound)
+    }
+
+    fn check_tag(&self, tag: Tag) -> Result<TypeIdx, ErrorKind> {
+        let Tag::Exception(index) = tag;
+        if self.func_type(index)?.results().is_empty() {
+            Ok(index)
+        } else {
+            Err(ErrorKind::Invalid(
+                "exception tag type must not have results",
+            ))
+        }
+    }
+}
+
+impl TypeContext for ModuleContext {
+    fn func_type(&self, index: TypeIdx) -> Option<&FuncType> {
+        self.types.get(usize::from(index))
+    }
+
+    fn function(&self, index: FuncIdx) -> Option<TypeIdx> {
+        self.functions.get(usize::from(index)).copied()
+    }
+
+    fn table(&self, index: TableIdx) -> Option<TableType> {
+        self.tables.get(usize::from(index)).copied()
+    }
+
+    fn memory(&self, index: MemIdx) -> Option<MemType> {
+        self.memories.get(usize::from(index)).copied()
+    }
+
+    fn global(&self, index: GlobalIdx) -> Option<GlobalType> {
+        self.globals.get(usize::from(index)).copied()
+    }
+
+    fn tag(&self, index: TagIdx) -> Option<TypeIdx> {
+        self.tags.get(usize::from(index)).copied()
+    }
+
+    fn element(&self, index: ElemIdx) -> Option<RefType> {
+        self.elements.get(usize::from(index)).copied()
+    }
+
+    fn data_count(&self) -> Option<u32> {
+        self.data_count
+    }
+
+    fn is_declared_reference(&self, index: FuncIdx) -> bool {
+        self.references.contains(&index.to_u32())
+    }
+}
+
+/// Validates a
+/// [constant expression](https://webassembly.github.io/spec/core/valid/instructions.html#constant-expressions),
+/// appending any functions referred to by **ref.func** instructions to `references`.
+fn constant_expression<O: Offset, I: Input>(
+    context: &ModuleContext,
+    expression: &mut InstructionSequence<O, I>,
+    result_type: ValType,
+    references: &mut Vec<u32>,
+) -> Result<(), ValidationError> {
+    let mut checker = TypeChecker::constant(context, result_type, context.constant_globals);
+
+    loop {
+        let offset = expression.offset();
+        let result = expression.next(|instruction| {
+            if let Instruction::RefFunc(function) = instruction {
+                references.push(function.to_u32());
+            }
+
+            checker.instruction(offset, instruction)
+        });
+
+        match result {
+            Some(result) => result?,
+            None => break,
+        }
+    }
+
+    checker.finish(expression.offset())
+}
+
+/// Performs [validation](https://webassembly.github.io/spec/core/valid/index.html) of an entire
+/// WebAssembly module binary, including type checking of each function body.
+///
+/// In addition to the checks performed when parsing each section, this checks that:
+/// - Sections appear in the correct order, and non-custom sections are not duplicated.
+/// - Indices refer to types, functions, tables, memories, globals, tags, and segments that exist.
+/// - Function bodies and constant expressions are well-typed.
+/// - The *function* and *code* sections have the same number of entries, and the number of data
+///   segments matches the *data count* section, which must be present if the **memory.init** or
+///   **data.drop** instructions are used.
+/// - Element segments are compatible with the tables they initialize, and only refer to
+///   functions and tables that exist.
+/// - Export names are unique.
+///
+/// Instructions from proposals enabled by cargo features, such as `simd` and `threads`, are
+/// validated as well.
+///
+/// # Errors
+///
+/// Returns an error if the module is invalid, or could not be parsed.
+///
+/// # Example
+///
+/// ```
+/// let valid = wat::parse_str("(module (func (result i32) i32.const 1))").unwrap();
+/// assert!(wasmiter::validate::validate_module(valid.as_slice()).is_ok());
+///
+/// let invalid = wat::parse_str("(module (func (result i32) i64.const 1))").unwrap();
+/// let error = wasmiter::validate::validate_module(invalid.as_slice()).unwrap_err();
+/// assert!(error.to_string().contains("expected i32 but got i64"));
+/// ```
+pub fn validate_module<I: Input>(binary: I) -> Result<(), ValidationError> {
+    validate_module_sections(&crate::parse_module_sections(binary)?)
+}
+
+/// Performs [validation](https://webassembly.github.io/spec/core/valid/index.html) of the
+/// `sections` of a WebAssembly module whose preamble has already been parsed.
+///
+/// See [`validate_module`] for more information.
+///
+/// # Errors
+///
+/// Returns an error if the module is invalid, or could not be parsed.
+pub fn validate_module_sections<I: Input>(
+    sections: &SectionSequence<I>,
+) -> Result<(), ValidationError> {
+    let mut context = ModuleContext::default();
+    let mut previous_order = 0u8;
+    let mut imported_functions = 0u32;
+    let mut defined_functions = 0u32;
+    let mut code_entries = 0u32;
+    let mut data_segments = 0u32;
+    let mut references = Vec::new();
+    let mut end = sections.offset();
+
+    for result in sections.borrow_input() {
+        let section = result?;
+        let id = section.id();
+        let section_offset = section.contents().base();
+        end = section_offset.saturating_add(section.length());
+        let fail = |kind| ValidationError::new(kind, section_offset);
+
+        if id == section_id::CUSTOM {
+            continue;
+        }
+
+        match section_id::order(id) {
+            Some(order) if order > previous_order => previous_order = order,
+            Some(_) => return Err(fail(ErrorKind::SectionOutOfOrder(id))),
+            None => return Err(fail(ErrorKind::Invalid("unknown section"))),
+        }
+
+        let known = match KnownSection::interpret(section) {
+            Ok(known) => known?,
+            Err(_) => return Err(fail(ErrorKind::Invalid("unknown section"))),
+        };
+
+        match known {
+            KnownSection::Type(mut types) => {
+                while let Some(ty) = types.parse(
+                    |parameters| {
+                        let mut types = Vec::new();
+                        for result in parameters {
+                            types.push(result?);
+                        }
+                        Ok(types)
+                    },
+                    |parameters, results| {
+                        let mut types = Vec::new();
+                        for result in results {
+                            types.push(result?);
+                        }
+                        Ok(FuncType::new(parameters, types))
+                    },
+                )? {
+                    context.types.push(ty);
+                }
+
+                // Types can refer to types that are defined after them
+                for ty in context.types.iter() {
+                    for value_type in ty.parameters().iter().chain(ty.results()) {
+                        context.check_val_type(*value_type).map_err(fail)?;
+                    }
+                }
+            }
+            KnownSection::Import(mut imports) => {
+                while let Some(import) = imports.parse()? {
+                    match *import.kind() {
+                        ImportKind::Function(ty) => {
+                            context.func_type(ty).map_err(fail)?;
+                            context.functions.push(ty);
+                            imported_functions += 1;
+                        }
+                        ImportKind::Table(table) => {
+                            context.check_table(&table).map_err(fail)?;
+                            context.tables.push(table);
+                        }
+                        ImportKind::Memory(memory) => {
+                            context.check_memory(&memory).map_err(fail)?;
+                            context.memories.push(memory);
+                        }
+                        ImportKind::Global(global) => {
+                            context.check_val_type(global.value_type()).map_err(fail)?;
+                            context.globals.push(global);
+                        }
+                        ImportKind::Tag(tag) => {
+                            let ty = context.check_tag(tag).map_err(fail)?;
+                            context.tags.push(ty);
+                        }
+                    }
+                }
+            }
+            KnownSection::Function(functions) => {
+                for result in functions {
+                    let ty = result?;
+                    context.func_type(ty).map_err(fail)?;
+                    context.functions.push(ty);
+                    defined_functions += 1;
+                }
+            }
+            KnownSection::Table(tables) => {
+                for result in tables {
+                    let table = result?;
+                    context.check_table(&table).map_err(fail)?;
+                    context.tables.push(table);
+                }
+            }
+            KnownSection::Memory(memories) => {
+                for result in memories {
+                    let memory = result?;
+                    context.check_memory(&memory).map_err(fail)?;
+                    context.memories.push(memory);
+                }
+            }
+            KnownSection::Tag(tags) => {
+                for result in tags {
+                    let ty = context.check_tag(result?).map_err(fail)?;
+                    context.tags.push(ty);
+                }
+            }
+            KnownSection::Global(mut globals) => loop {
+                // Globals can only refer to globals that were defined before them
+                context.constant_globals = u32::try_from(context.globals.len()).unwrap_or(u32::MAX);
+
+                let global = globals.parse(|ty, expression| {
+                    if let Err(kind) = context.check_val_type(ty.value_type()) {
+                        return Ok(Err(fail(kind)));
+                    }
+
+                    Ok(
+                        constant_expression(&context, expression, ty.value_type(), &mut references)
+                            .map(|()| ty),
+                    )
+                })?;
+
+                match global {
+                    Some(global) => context.globals.push(global?),
+                    None => break,
+                }
+            },
+            KnownSection::Export(mut exports) => {
+                let mut names = BTreeSet::new();
+                while let Some(export) = exports.parse()? {
+                    let (space, index, count) = match *export.kind() {
+                        ExportKind::Function(index) => {
+                            references.push(index.to_u32());
+                            ("function", index.to_u32(), context.functions.len())
+                        }
+                        ExportKind::Table(index) => ("table", index.to_u32(), context.tables.len()),
+                        ExportKind::Memory(index) => {
+                            ("memory", index.to_u32(), context.memories.len())
+                        }
+                        ExportKind::Global(index) => {
+                            ("global", index.to_u32(), context.globals.len())
+                        }
+                        ExportKind::Tag(index) => ("tag", index.to_u32(), context.tags.len()),
+                    };
+
+                    if crate::int::u32_to_usize(index) >= count {
+                        return Err(fail(ErrorKind::UnknownIndex { space, index }));
+                    }
+
+                    if !names.insert(export.name().into_bytes()?) {
+                        return Err(fail(ErrorKind::DuplicateExport));
+                    }
+                }
+            }
+            KnownSection::Start(start) => {
+                let ty = context
+                    .functions
+                    .get(start.to_usize())
+                    .ok_or(ErrorKind::UnknownIndex {
+                        space: "function",
+                        index: start.to_u32(),
+                    })
+                    .and_then(|ty| context.func_type(*ty))
+                    .map_err(fail)?;
+
+                if !ty.parameters().is_empty() || !ty.results().is_empty() {
+                    return Err(fail(ErrorKind::Invalid(
+                        "start function must not have any parameters or results",
+                    )));
+                }
+            }
+            KnownSection::Element(mut elements) => {
+                context.constant_globals = u32::try_from(context.globals.len()).unwrap_or(u32::MAX);
+                context.references.extend(references.drain(..));
+
+                loop {
+                    let element = elements.parse(
+                        |mode| {
+                            Ok(match mode {
+                                ElementMode::Active(table, offset) => {
+                                    let table = context
+                                        .tables
+                                        .get(usize::from(*table))
+                                        .copied()
+                                        .ok_or(ErrorKind::UnknownIndex {
+                                            space: "table",
+                                            index: table.to_u32(),
+                                        })
+                                        .map_err(fail);
+
+                                    table.and_then(|table| {
+                                        let index = match table.limits().index_type() {
+                                            IdxType::I32 => ValType::I32,
+                                            IdxType::I64 => ValType::I64,
+                                        };
+
+                                        // Offsets cannot contain valid ref.func instructions
+                                        constant_expression(
+                                            &context,
+                                            offset,
+                                            index,
+                                            &mut Vec::new(),
+                                        )
+                                        .map(|()| Some(table.element_type()))
+                                    })
+                                }
+                                ElementMode::Passive | ElementMode::Declarative => Ok(None),
+                            })
+                        },
+                        |table, init| {
+                            let table = match table {
+                                Ok(table) => table,
+                                Err(error) => return Ok(Err(error)),
+                            };
+
+                            let element_type = match init {
+                                ElementInit::Functions(functions) => {
+                                    for result in functions {
+                                        let index = result?.to_u32();
+                                        if let Err(kind) = context.check_function(index) {
+                                            return Ok(Err(fail(kind)));
+                                        }
+
+                                        references.push(index);
+                                    }
+
+                                    RefType::Func
+                                }
+                                ElementInit::Expressions(element_type, expressions) => {
+                                    let mut error = context
+                                        .check_val_type(ValType::from(*element_type))
+                                        .map_err(fail);
+                                    while error.is_ok() {
+                                        let next = expressions.next(|expression| {
+                                            Ok(constant_expression(
+                                                &context,
+                                                expression,
+                                                ValType::from(*element_type),
+                                                &mut references,
+                                            ))
+                                        })?;
+
+                                        match next {
+                                            Some(result) => error = result,
+                                            None => break,
+                                        }
+                                    }
+
+                                    if let Err(error) = error {
+                                        return Ok(Err(error));
+                                    }
+
+                                    *element_type
+                                }
+                            };
+
+                            Ok(match table {
+                                Some(table)
+                                    if !instruction_set::is_subtype(
+                                        ValType::from(element_type),
+                                        ValType::from(table),
+                                    ) =>
+                                {
+                                    Err(fail(ErrorKind::TypeMismatch {
+                                        expected: Some(ValType::from(table)),
+                                        actual: Some(ValType::from(element_type)),
+                                    }))
+                                }
+                                _ => Ok(element_type),
+                            })
+                        },
+                    )?;
+
+                    match element {
+                        Some(element_type) => context.elements.push(element_type?),
+                        None => break,
+                    }
+                }
+            }
+            KnownSection::DataCount(count) => context.data_count = Some(count),
+            KnownSection::Code(mut code) => {
+                context.constant_globals = u32::try_from(context.globals.len()).unwrap_or(u32::MAX);
+                context.references.extend(references.drain(..));
+
+                while let Some(entry) = code.parse()? {
+                    let function = imported_functions.saturating_add(entry.index());
+                    code_entries = code_entries.saturating_add(1);
+
+                    let signature = match context.functions.get(crate::int::u32_to_usize(function))
+                    {
+                        Some(signature) => signature,
+                        None => {
+                            return Err(fail(ErrorKind::FunctionCountMismatch {
+                                functions: defined_functions,
+                                bodies: code_entries,
+                            }))
+                        }
+                    };
+
+                    let signature = context.func_type(*signature).map_err(fail)?;
+                    let parameters = signature.parameters();
+                    entry.read(
+                        |locals| {
+                            LocalTypes::new(parameters.iter().copied().map(Ok), locals)
+                                .map_err(ValidationError::from)
+                        },
+                        |locals, body| {
+                            let mut checker = TypeChecker::new(&context, signature, locals);
+                            loop {
+                                let offset = body.offset();
+                                let result = body
+                                    .next(|instruction| checker.instruction(offset, instruction));
+
+                                match result {
+                                    Some(result) => result?,
+                                    None => break,
+                                }
+                            }
+
+                            checker.finish(body.offset())
+                        },
+                    )?;
+                }
+
+                if code_entries != defined_functions {
+                    return Err(fail(ErrorKind::FunctionCountMismatch {
+                        functions: defined_functions,
+                        bodies: code_entries,
+                    }));
+                }
+            }
+            KnownSection::Data(mut datas) => loop {
+                let data = datas.parse(
+                    |mode| {
+                        Ok(match mode {
+                            DataMode::Active(memory, offset) => {
+                                match context.memories.get(usize::from(*memory)) {
+                                    Some(memory) => {
+                                        let index = match memory.index_type() {
+                                            IdxType::I32 => ValType::I32,
+                                            IdxType::I64 => ValType::I64,
+                                        };
+
+                                        constant_expression(
+                                            &context,
+                                            offset,
+                                            index,
+                                            &mut references,
+                                        )
+                                    }
+                                    None => Err(fail(ErrorKind::UnknownIndex {
+                                        space: "memory",
+                                        index: memory.to_u32(),
+                                    })),
+                                }
+                            }
+                            DataMode::Passive => Ok(()),
+                        })
+                    },
+                    |result, _| Ok(result),
+                )?;
+
+                match data {
+                    Some(result) => {
+                        result?;
+                        data_segments = data_segments.saturating_add(1);
+                    }
+                    None => break,
+                }
+            },
+        }
+    }
+
+    if code_entries != defined_functions {
+        return Err(ValidationError::new(
+            ErrorKind::FunctionCountMismatch {
+                functions: defined_functions,
+                bodies: code_entries,
+            },
+            end,
+        ));
+    }
+
+    match context.data_count {
+        Some(declared) if declared != data_segments => Err(ValidationError::new(
+            ErrorKind::DataCountMismatch {
+                declared,
+                actual: data_segments,
+            },
+            end,
+        )),
+        _ => Ok(()),
+    }
+}
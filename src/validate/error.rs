@@ -0,0 +1,182 @@
+use crate::{parser, types::ValType};
+use core::fmt::{Display, Formatter};
+
+#[derive(Debug)]
+pub(crate) enum ErrorKind {
+    Parse(parser::Error),
+    /// An operand of the `expected` type was required, but the operand stack contained an operand
+    /// of the `actual` type, or was empty.
+    TypeMismatch {
+        expected: Option<ValType>,
+        actual: Option<ValType>,
+    },
+    /// Operands remained on the stack at the end of a block.
+    UnexpectedOperands(usize),
+    UnknownIndex {
+        space: &'static str,
+        index: u32,
+    },
+    ImmutableGlobal(u32),
+    UndeclaredFunctionReference(u32),
+    BadAlignment {
+        actual: u8,
+        natural: u8,
+    },
+    #[cfg(feature = "simd")]
+    BadLaneIndex(u8),
+    FunctionCountMismatch {
+        functions: u32,
+        bodies: u32,
+    },
+    DataCountMismatch {
+        declared: u32,
+        actual: u32,
+    },
+    DuplicateExport,
+    SectionOutOfOrder(u8),
+    Invalid(&'static str),
+}
+
+impl From<parser::Error> for ErrorKind {
+    #[inline]
+    fn from(error: parser::Error) -> Self {
+        Self::Parse(error)
+    }
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Parse(error) => write!(f, "could not parse module: {error}"),
+            Self::TypeMismatch {
+                expected: Some(expected),
+                actual: Some(actual),
+            } => write!(f, "type mismatch, expected {expected} but got {actual}"),
+            Self::TypeMismatch {
+                expected: Some(expected),
+                actual: None,
+            } => write!(f, "type mismatch, expected {expected} but operand stack was empty"),
+            Self::TypeMismatch { expected: None, .. } => {
+                f.write_str("type mismatch, expected an operand but operand stack was empty")
+            }
+            Self::UnexpectedOperands(count) => {
+                write!(f, "type mismatch, {count} operands remained at end of block")
+            }
+            Self::UnknownIndex { space, index } => write!(f, "unknown {space} {index}"),
+            Self::ImmutableGlobal(index) => write!(f, "global {index} is immutable"),
+            Self::UndeclaredFunctionReference(index) => {
+                write!(f, "undeclared function reference to function {index}")
+            }
+            Self::BadAlignment { actual, natural } => write!(
+                f,
+                "alignment of 2^{actual} must not be larger than natural alignment of 2^{natural}"
+            ),
+            #[cfg(feature = "simd")]
+            Self::BadLaneIndex(lane) => write!(f, "invalid lane index {lane}"),
+            Self::FunctionCountMismatch { functions, bodies } => write!(
+                f,
+                "function and code section have inconsistent lengths, {functions} functions were declared but {bodies} bodies were defined"
+            ),
+            Self::DataCountMismatch { declared, actual } => write!(
+                f,
+                "data count section specifies {declared} data segments, but {actual} were defined"
+            ),
+            Self::DuplicateExport => f.write_str("duplicate export name"),
+            Self::SectionOutOfOrder(id) => {
+                write!(f, "section with id {id} is out of order or duplicated")
+            }
+            Self::Invalid(message) => f.write_str(message),
+        }
+    }
+}
+
+/// Describes why a WebAssembly module is not
+/// [valid](https://webassembly.github.io/spec/core/valid/index.html).
+///
+/// Returned by [`validate_module`](crate::validate::validate_module), and when type checking a
+/// function body with a [`TypeChecker`](crate::instruction_set::TypeChecker).
+#[derive(Debug)]
+pub struct ValidationError {
+    kind: ErrorKind,
+    offset: Option<u64>,
+}
+
+impl ValidationError {
+    pub(crate) fn new(kind: ErrorKind, offset: u64) -> Self {
+        Self {
+            kind,
+            offset: Some(offset),
+        }
+    }
+
+    /// Gets the offset into the module binary where the error was detected, if one is known.
+    ///
+    /// For errors in function bodies and constant expressions, this is the offset to the
+    /// instruction that caused the error.
+    #[inline]
+    pub fn offset(&self) -> Option<u64> {
+        self.offset
+    }
+
+    /// If an operand had the wrong type, gets the type that was expected.
+    #[inline]
+    pub fn expected_type(&self) -> Option<ValType> {
+        match &self.kind {
+            ErrorKind::TypeMismatch { expected, .. } => *expected,
+            _ => None,
+        }
+    }
+
+    /// If an operand had the wrong type, gets the type of the operand that was actually on the
+    /// operand stack.
+    ///
+    /// Returns `None` if the operand stack was empty, or if the error was not caused by a type
+    /// mismatch.
+    #[inline]
+    pub fn actual_type(&self) -> Option<ValType> {
+        match &self.kind {
+            ErrorKind::TypeMismatch { actual, .. } => *actual,
+            _ => None,
+        }
+    }
+
+    /// Gets the [`parser::Error`] that caused validation to fail, if the module could not be
+    /// parsed.
+    #[inline]
+    pub fn parse_error(&self) -> Option<&parser::Error> {
+        match &self.kind {
+            ErrorKind::Parse(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<parser::Error> for ValidationError {
+    #[inline]
+    fn from(error: parser::Error) -> Self {
+        Self {
+            kind: ErrorKind::Parse(error),
+            offset: None,
+        }
+    }
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&self.kind, f)?;
+        if let Some(offset) = self.offset {
+            write!(f, ", at offset {offset:#X}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ValidationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.kind {
+            ErrorKind::Parse(error) => Some(error),
+            _ => None,
+        }
+    }
+}
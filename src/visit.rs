@@ -0,0 +1,484 @@
+//! Provides the [`ModuleVisitor`] trait, allowing the contents of an entire WebAssembly module to
+//! be processed in a single pass without needing to manually interpret each section.
+
+use crate::{
+    component::{Code, DataMode, ElementInit, ElementMode, Export, Import, KnownSection, Tag},
+    custom::CustomSection,
+    index::{FuncIdx, TypeIdx},
+    input::{BorrowInput as _, CloneInput as _, Input, Window},
+    instruction_set::{Instruction, InstructionSequence},
+    parser::{Error, ErrorKind},
+    parser::{Offset, Parsed, ResultExt as _},
+    sections::{id as section_id, Section, SectionSequence},
+    types::{GlobalType, MemType, TableType, ValType},
+};
+
+/// Trait for processing the sections, entries, and instructions of a WebAssembly module.
+///
+/// Every method has a default implementation that does nothing, so implementors only need to
+/// override the methods corresponding to the parts of the module they are interested in.
+///
+/// Expressions, such as function bodies and the offsets of active data segments, are provided
+/// to [`visit_instruction`](ModuleVisitor::visit_instruction) immediately after the method for
+/// the entry that contains them is called.
+///
+/// See [`visit_module`] and [`visit_module_sections`] for more information.
+#[allow(unused_variables)]
+pub trait ModuleVisitor {
+    /// Called for every section in the module, before its contents are interpreted.
+    fn visit_section<I: Input>(&mut self, section: &Section<I>) -> Parsed<()> {
+        Ok(())
+    }
+
+    /// Called for every non-custom section that is skipped because another section with the
+    /// same *id* was kept, according to the [`DuplicateSections`] option.
+    fn visit_duplicate_section<I: Input>(&mut self, section: &Section<I>) -> Parsed<()> {
+        Ok(())
+    }
+
+    /// Called for every section that is a [`KnownSection`], before any of its entries are
+    /// visited.
+    fn visit_known_section<I: Input>(&mut self, section: &KnownSection<I>) -> Parsed<()> {
+        Ok(())
+    }
+
+    /// Called for every [custom section](https://webassembly.github.io/spec/core/appendix/custom.html).
+    fn visit_custom_section<I: Input>(&mut self, section: &CustomSection<I>) -> Parsed<()> {
+        Ok(())
+    }
+
+    /// Called for each function type in the *type section*.
+    fn visit_type<I: Input>(
+        &mut self,
+        parameters: crate::component::ResultType<u64, I>,
+        results: crate::component::ResultType<u64, I>,
+    ) -> Parsed<()> {
+        Ok(())
+    }
+
+    /// Called for each entry in the *import section*.
+    fn visit_import<I: Input>(&mut self, import: &Import<I>) -> Parsed<()> {
+        Ok(())
+    }
+
+    /// Called for each entry in the *function section*.
+    fn visit_function(&mut self, signature: TypeIdx) -> Parsed<()> {
+        Ok(())
+    }
+
+    /// Called for each entry in the *table section*.
+    fn visit_table(&mut self, table: TableType) -> Parsed<()> {
+        Ok(())
+    }
+
+    /// Called for each entry in the *memory section*.
+    fn visit_memory(&mut self, memory: MemType) -> Parsed<()> {
+        Ok(())
+    }
+
+    /// Called for each entry in the *global section*, followed by the instructions of the
+    /// global's initializer expression.
+    fn visit_global(&mut self, global: GlobalType) -> Parsed<()> {
+        Ok(())
+    }
+
+    /// Called for each entry in the *export section*.
+    fn visit_export<I: Input>(&mut self, export: &Export<I>) -> Parsed<()> {
+        Ok(())
+    }
+
+    /// Called when the *start section* is encountered.
+    fn visit_start(&mut self, start: FuncIdx) -> Parsed<()> {
+        Ok(())
+    }
+
+    /// Called for each entry in the *element section*. If the segment is
+    /// [**active**](ElementMode::Active), then this is followed by the instructions of the offset
+    /// expression.
+    fn visit_element_segment<O: Offset, I: Input>(
+        &mut self,
+        mode: &ElementMode<O, I>,
+    ) -> Parsed<()> {
+        Ok(())
+    }
+
+    /// Called after the mode of an element segment is visited, before its elements are visited.
+    fn visit_element_init<O: Offset, I: Input>(&mut self, init: &ElementInit<O, I>) -> Parsed<()> {
+        Ok(())
+    }
+
+    /// Called for each function reference in an element segment containing a vector of
+    /// [function indices](ElementInit::Functions).
+    fn visit_element_function(&mut self, function: FuncIdx) -> Parsed<()> {
+        Ok(())
+    }
+
+    /// Called for each entry in the *code section*, followed by the groups of local variable
+    /// declarations and the instructions of the function body.
+    fn visit_code<I: Input>(&mut self, code: &Code<I>) -> Parsed<()> {
+        Ok(())
+    }
+
+    /// Called for each group of local variables declared in a *code section* entry.
+    fn visit_local_group(&mut self, count: u32, local_type: ValType) -> Parsed<()> {
+        Ok(())
+    }
+
+    /// Called for each entry in the *data section*. If the segment is
+    /// [**active**](DataMode::Active), then this is followed by the instructions of the offset
+    /// expression, and then a call to [`visit_data`](ModuleVisitor::visit_data).
+    fn visit_data_segment<O: Offset, I: Input>(&mut self, mode: &DataMode<O, I>) -> Parsed<()> {
+        Ok(())
+    }
+
+    /// Called with the contents of each data segment.
+    fn visit_data<I: Input>(&mut self, data: &Window<I>) -> Parsed<()> {
+        Ok(())
+    }
+
+    /// Called when the *data count section* is encountered.
+    fn visit_data_count(&mut self, count: u32) -> Parsed<()> {
+        Ok(())
+    }
+
+    /// Called for each entry in the *tag section*.
+    fn visit_tag(&mut self, tag: Tag) -> Parsed<()> {
+        Ok(())
+    }
+
+    /// Called for every instruction within a function body or constant expression.
+    fn visit_instruction<I: Input>(&mut self, instruction: &mut Instruction<'_, I>) -> Parsed<()> {
+        Ok(())
+    }
+}
+
+fn visit_expression<O, I, V>(
+    expression: &mut InstructionSequence<O, I>,
+    visitor: &mut V,
+) -> Parsed<()>
+where
+    O: Offset,
+    I: Input,
+    V: ModuleVisitor + ?Sized,
+{
+    while let Some(result) = expression.next(|instruction| visitor.visit_instruction(instruction)) {
+        result?;
+    }
+    Ok(())
+}
+
+fn visit_known_section<I, V>(known: KnownSection<Window<I>>, visitor: &mut V) -> Parsed<()>
+where
+    I: Clone + Input,
+    V: ModuleVisitor + ?Sized,
+{
+    visitor.visit_known_section(&known)?;
+
+    match known {
+        KnownSection::Type(mut types) => loop {
+            let result = types.parse(
+                |parameters| Ok(parameters.clone_input()),
+                |parameters, results| visitor.visit_type(parameters, results.clone_input()),
+            )?;
+
+            if result.is_none() {
+                break;
+            }
+        },
+        KnownSection::Import(mut imports) => {
+            while let Some(import) = imports.parse()? {
+                visitor.visit_import(&import)?;
+            }
+        }
+        KnownSection::Function(functions) => {
+            for result in functions {
+                visitor.visit_function(result?)?;
+            }
+        }
+        KnownSection::Table(tables) => {
+            for result in tables {
+                visitor.visit_table(result?)?;
+            }
+        }
+        KnownSection::Memory(memories) => {
+            for result in memories {
+                visitor.visit_memory(result?)?;
+            }
+        }
+        KnownSection::Global(mut globals) => loop {
+            let result = globals.parse(|global_type, initializer| {
+                visitor.visit_global(global_type)?;
+                visit_expression(initializer, visitor)
+            })?;
+
+            if result.is_none() {
+                break;
+            }
+        },
+        KnownSection::Export(mut exports) => {
+            while let Some(export) = exports.parse()? {
+                visitor.visit_export(&export)?;
+            }
+        }
+        KnownSection::Start(start) => visitor.visit_start(start)?,
+        KnownSection::Element(mut elements) => loop {
+            let result = elements.parse(
+                |mode| {
+                    visitor.visit_element_segment(mode)?;
+                    if let ElementMode::Active(_, offset) = mode {
+                        visit_expression(offset, visitor)?;
+                    }
+                    Ok(&mut *visitor)
+                },
+                |visitor, init| {
+                    visitor.visit_element_init(init)?;
+                    match init {
+                        ElementInit::Functions(functions) => {
+                            for result in functions {
+                                visitor.visit_element_function(result?)?;
+                            }
+                        }
+                        ElementInit::Expressions(_, expressions) => {
+                            while expressions
+                                .next(|expression| visit_expression(expression, visitor))?
+                                .is_some()
+                            {}
+                        }
+                    }
+                    Ok(())
+                },
+            )?;
+
+            if result.is_none() {
+                break;
+            }
+        },
+        KnownSection::Code(mut code) => {
+            while let Some(entry) = code.parse()? {
+                visitor.visit_code(&entry)?;
+                entry.read(
+                    |locals| {
+                        while let Some((count, local_type)) = locals.next_group()? {
+                            visitor.visit_local_group(count.get(), local_type)?;
+                        }
+                        Ok(&mut *visitor)
+                    },
+                    |visitor, body| visit_expression(body, visitor),
+                )?;
+            }
+        }
+        KnownSection::Data(mut datas) => loop {
+            let result = datas.parse(
+                |mode| {
+                    visitor.visit_data_segment(mode)?;
+                    if let DataMode::Active(_, offset) = mode {
+                        visit_expression(offset, visitor)?;
+                    }
+                    Ok(&mut *visitor)
+                },
+                |visitor, data| visitor.visit_data(&data),
+            )?;
+
+            if result.is_none() {
+                break;
+            }
+        },
+        KnownSection::DataCount(count) => visitor.visit_data_count(count)?,
+        KnownSection::Tag(tags) => {
+            for result in tags {
+                visitor.visit_tag(result?)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Specifies how non-custom sections that appear more than once in a module are handled.
+///
+/// Modules containing duplicate sections are invalid, but are emitted by some producers.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum DuplicateSections {
+    /// Duplicate sections result in an error.
+    #[default]
+    Error,
+    /// Only the first section with a given *id* is visited.
+    KeepFirst,
+    /// Only the last section with a given *id* is visited.
+    KeepLast,
+}
+
+/// Gets a bit corresponding to a non-custom section *id*, or `0` for custom or unknown sections.
+fn section_bit(id: u8) -> u16 {
+    if (section_id::TYPE..=section_id::TAG).contains(&id) {
+        1 << id
+    } else {
+        0
+    }
+}
+
+#[inline(never)]
+#[cold]
+fn duplicate_section(id: u8, offset: u64) -> Error {
+    Error::new(ErrorKind::DuplicateSection(id)).with_location_context("section", offset)
+}
+
+/// Visits the contents of each section in the given sequence of module `sections`.
+///
+/// Duplicate non-custom sections result in an error. To visit modules containing duplicate
+/// sections, use [`visit_module_sections_with`].
+///
+/// # Errors
+///
+/// Returns an error if any section could not be parsed, if a non-custom section appeared more than
+/// once, or if the `visitor` returned an error.
+#[inline]
+pub fn visit_module_sections<I, V>(sections: &SectionSequence<I>, visitor: &mut V) -> Parsed<()>
+where
+    I: Input,
+    V: ModuleVisitor + ?Sized,
+{
+    visit_module_sections_with(sections, DuplicateSections::Error, visitor)
+}
+
+/// Visits the contents of each section in the given sequence of module `sections`, using the
+/// given [`DuplicateSections`] option to handle non-custom sections that appear more than once.
+///
+/// Skipped sections are passed to
+/// [`visit_duplicate_section`](ModuleVisitor::visit_duplicate_section) instead.
+///
+/// # Example
+///
+/// ```
+/// use wasmiter::{input::Input, sections::Section, parser::Parsed, visit};
+///
+/// #[derive(Default)]
+/// struct Visitor {
+///     starts: Vec<u32>,
+///     duplicates: usize,
+/// }
+///
+/// impl visit::ModuleVisitor for Visitor {
+///     fn visit_start(&mut self, start: wasmiter::index::FuncIdx) -> Parsed<()> {
+///         self.starts.push(start.into());
+///         Ok(())
+///     }
+///
+///     fn visit_duplicate_section<I: Input>(&mut self, _: &Section<I>) -> Parsed<()> {
+///         self.duplicates += 1;
+///         Ok(())
+///     }
+/// }
+///
+/// // Module with two start sections
+/// let wasm = b"\0asm\x01\0\0\0\x08\x01\x00\x08\x01\x01";
+/// let sections = wasmiter::parse_module_sections(wasm.as_slice())?;
+/// assert!(visit::visit_module_sections(&sections, &mut Visitor::default()).is_err());
+///
+/// let mut visitor = Visitor::default();
+/// visit::visit_module_sections_with(&sections, visit::DuplicateSections::KeepLast, &mut visitor)?;
+/// assert_eq!(visitor.starts, [1]);
+/// assert_eq!(visitor.duplicates, 1);
+/// # Ok::<(), wasmiter::parser::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if any section could not be parsed, if a non-custom section appeared more than
+/// once when using [`DuplicateSections::Error`], or if the `visitor` returned an error.
+pub fn visit_module_sections_with<I, V>(
+    sections: &SectionSequence<I>,
+    duplicates: DuplicateSections,
+    visitor: &mut V,
+) -> Parsed<()>
+where
+    I: Input,
+    V: ModuleVisitor + ?Sized,
+{
+    // Offsets to the contents of the last section with each id
+    let mut last_offsets = [u64::MAX; section_id::TAG as usize + 1];
+    if duplicates == DuplicateSections::KeepLast {
+        for result in sections.borrow_input() {
+            let section = result?;
+            if section_bit(section.id()) != 0 {
+                last_offsets[usize::from(section.id())] = section.contents().base();
+            }
+        }
+    }
+
+    let mut visited = 0u16;
+    for result in sections.borrow_input() {
+        let section = result?;
+        let bit = section_bit(section.id());
+        let is_duplicate = match duplicates {
+            DuplicateSections::KeepLast => {
+                bit != 0 && last_offsets[usize::from(section.id())] != section.contents().base()
+            }
+            _ => visited & bit != 0,
+        };
+
+        visited |= bit;
+        if is_duplicate {
+            if duplicates == DuplicateSections::Error {
+                return Err(duplicate_section(section.id(), section.contents().base()));
+            }
+
+            visitor.visit_duplicate_section(&section)?;
+            continue;
+        }
+
+        visitor.visit_section(&section)?;
+
+        match KnownSection::interpret(section) {
+            Ok(known) => visit_known_section(known?, visitor)?,
+            Err(section) => {
+                if let Ok(custom) = CustomSection::try_from_section(section) {
+                    visitor.visit_custom_section(&custom.context("custom section name")?)?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Reads a WebAssembly module binary, visiting the contents of each of its sections.
+///
+/// See [`visit_module_sections`] for more information.
+///
+/// # Example
+///
+/// ```
+/// use wasmiter::{input::Input, instruction_set::Instruction, parser::Parsed, visit};
+///
+/// #[derive(Default)]
+/// struct CountCalls(usize);
+///
+/// impl visit::ModuleVisitor for CountCalls {
+///     fn visit_instruction<I: Input>(&mut self, instruction: &mut Instruction<'_, I>) -> Parsed<()> {
+///         if let Instruction::Call(_) = instruction {
+///             self.0 += 1;
+///         }
+///         Ok(())
+///     }
+/// }
+///
+/// let wasm = wat::parse_str("(module (func $f call $f call $f))").unwrap();
+/// let mut counter = CountCalls::default();
+/// visit::visit_module(wasm.as_slice(), &mut counter)?;
+/// assert_eq!(counter.0, 2);
+/// # Ok::<(), wasmiter::parser::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the module preamble was invalid, if any section could not be parsed, or
+/// if the `visitor` returned an error.
+pub fn visit_module<I, V>(binary: I, visitor: &mut V) -> Parsed<()>
+where
+    I: Input,
+    V: ModuleVisitor + ?Sized,
+{
+    visit_module_sections(&crate::parse_module_sections(binary)?, visitor)
+}
@@ -7,8 +7,9 @@ use crate::{
 };
 use core::fmt::Formatter;
 
+mod config;
 mod datas_text;
-mod display_impls;
+pub(crate) mod display_impls;
 mod elems_text;
 mod exports_text;
 mod funcs_text;
@@ -16,27 +17,57 @@ mod globals_text;
 mod imports_text;
 mod instruction_text;
 mod mems_text;
+mod module_context;
 mod module_text;
+mod name_table;
+mod reloc_text;
 mod tables_text;
 mod tags_text;
+#[cfg(feature = "alloc")]
+pub(crate) mod text_parser;
 mod types_text;
 
+/// Writes the [WebAssembly text format](https://webassembly.github.io/spec/core/text/index.html)
+/// to a [`Formatter`].
+///
+/// Any parentheses that are left open, such as when an error occurs while writing a structure, are
+/// closed when the [`Writer`] is [`finish`](Writer::finish)ed.
 #[must_use]
-struct Writer<'a, 'b> {
+pub struct Writer<'a, 'b> {
     fmt: &'a mut Formatter<'b>,
     paren_count: u32,
     err: core::fmt::Result,
+    config: Config,
+    names: NameTable,
+    /// The index of the function whose locals are being written.
+    function: Option<u32>,
 }
 
 impl<'a, 'b> Writer<'a, 'b> {
-    fn new(fmt: &'a mut Formatter<'b>) -> Self {
+    /// Creates a new [`Writer`] that writes to the given [`Formatter`].
+    pub fn new(fmt: &'a mut Formatter<'b>) -> Self {
+        Self::with_config(fmt, Config::new())
+    }
+
+    /// Creates a new [`Writer`] that writes to the given [`Formatter`] using the options in the
+    /// [`Config`].
+    pub fn with_config(fmt: &'a mut Formatter<'b>, config: Config) -> Self {
         Self {
             fmt,
             err: Ok(()),
             paren_count: 0,
+            config,
+            names: NameTable::new(),
+            function: None,
         }
     }
 
+    /// Gets the options used when writing text.
+    #[inline]
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
     #[inline]
     fn with_fmt<F: FnOnce(&mut Formatter<'b>) -> core::fmt::Result>(&mut self, f: F) {
         self.err = self.err.and_then(|()| f(self.fmt));
@@ -56,19 +87,86 @@ impl<'a, 'b> Writer<'a, 'b> {
         }
     }
 
-    fn write_char(&mut self, c: char) {
+    pub(crate) fn write_char(&mut self, c: char) {
         self.with_fmt(|f| core::fmt::Write::write_char(f, c))
     }
 
-    fn write_str(&mut self, s: &str) {
+    pub(crate) fn write_str(&mut self, s: &str) {
         self.with_fmt(|f| f.write_str(s))
     }
 
-    fn write_fmt(&mut self, args: core::fmt::Arguments<'_>) {
+    pub(crate) fn write_fmt(&mut self, args: core::fmt::Arguments<'_>) {
         self.with_fmt(|f| f.write_fmt(args))
     }
 
-    fn finish(mut self) -> core::fmt::Result {
+    /// Writes the name of the definition at the given `index` as an identifier, preceded by the
+    /// `prefix`, returning `false` if it does not have a name.
+    fn write_name(&mut self, space: name_table::Space, index: u32, prefix: &str) -> bool {
+        let Self {
+            fmt, err, names, ..
+        } = self;
+
+        match names.get(space, index) {
+            Some(name) => {
+                *err = err.and_then(|()| write!(fmt, "{prefix}${name}"));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Writes the name of the module as an identifier, preceded by a space, if it has one.
+    fn write_module_name(&mut self) {
+        let Self {
+            fmt, err, names, ..
+        } = self;
+
+        if let Some(name) = names.module() {
+            *err = err.and_then(|()| write!(fmt, " ${name}"));
+        }
+    }
+
+    /// Returns `true` if the local at the given `index` in the function being written has a name.
+    fn has_local_name(&self, index: u32) -> bool {
+        self.function
+            .and_then(|function| self.names.local(function, index))
+            .is_some()
+    }
+
+    /// Like [`Writer::write_name`], but for a local of the function being written.
+    fn write_local_name(&mut self, index: u32, prefix: &str) -> bool {
+        let Self {
+            fmt,
+            err,
+            names,
+            function,
+            ..
+        } = self;
+
+        match function.and_then(|function| names.local(function, index)) {
+            Some(name) => {
+                *err = err.and_then(|()| write!(fmt, "{prefix}${name}"));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Writes the indentation for the given nesting `level`.
+    fn write_indentation(&mut self, level: u32) {
+        let width = usize::from(self.config.indentation());
+        for _ in 0..level {
+            self.with_fmt(|f| write!(f, "{:width$}", ""));
+        }
+    }
+
+    /// Closes any remaining open parentheses, returning any error that occured while writing to
+    /// the [`Formatter`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to the underlying [`Formatter`] failed.
+    pub fn finish(mut self) -> core::fmt::Result {
         for _ in 0..self.paren_count {
             self.write_char(')');
         }
@@ -76,9 +174,33 @@ impl<'a, 'b> Writer<'a, 'b> {
     }
 }
 
-const INDENTATION: &str = "  ";
+impl core::fmt::Debug for Writer<'_, '_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Writer")
+            .field("paren_count", &self.paren_count)
+            .field("err", &self.err)
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+pub use config::Config;
+use module_context::{ModuleContext, NoContext};
+use name_table::{NameTable, Named};
+use reloc_text::{NoRelocations, RelocationAnnotations};
 
-trait Wat {
+/// Trait for structures that can be written in the
+/// [WebAssembly text format](https://webassembly.github.io/spec/core/text/index.html).
+///
+/// Structures printed in isolation do not have access to the rest of the module, so definitions
+/// are referred to by their indices rather than their names.
+pub trait Wat {
+    /// Writes the structure using the given [`Writer`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the structure could not be parsed. Any text written before the error
+    /// occured is kept.
     fn write(self, writer: &mut Writer) -> Parsed<()>;
 }
 
@@ -96,18 +218,58 @@ fn write_types<I: IntoIterator<Item = Parsed<types::ValType>>>(
     Ok(())
 }
 
-fn write_index<I: Into<u32>>(declaration: bool, index: I, w: &mut Writer) {
-    let idx: u32 = index.into();
-    if declaration {
-        write!(w, "(; {idx} ;)")
-    } else {
-        write!(w, "{idx}")
+fn write_index<I: Into<u32>>(index: I, w: &mut Writer) {
+    write!(w, "{}", index.into())
+}
+
+/// Writes the name of the definition with the given `index` as an identifier if it has one,
+/// otherwise writes the `index`.
+fn write_id<N: Named>(index: N, w: &mut Writer) {
+    if !w.write_name(N::SPACE, index.into(), "") {
+        write_index(index, w)
+    }
+}
+
+/// Writes the name of the definition being declared, preceded by a space, if it has one,
+/// otherwise writes a comment containing its `index`.
+fn write_declaration_id<N: Named>(index: N, w: &mut Writer) {
+    if !w.write_name(N::SPACE, index.into(), " ") {
+        write_declaration_index(index, w)
+    }
+}
+
+/// Writes the name of the definition being declared, preceded by a space, if it has one.
+fn write_declaration_name<N: Named>(index: N, w: &mut Writer) {
+    w.write_name(N::SPACE, index.into(), " ");
+}
+
+/// Writes the name of a local of the function being written, or its `index` if it does not have
+/// a name.
+fn write_local_id(index: crate::index::LocalIdx, w: &mut Writer) {
+    if !w.write_local_name(index.to_u32(), "") {
+        write_index(index, w)
+    }
+}
+
+/// Writes a comment containing the index of a definition, preceded by a space, unless comments
+/// are disabled.
+fn write_declaration_index<I: Into<u32>>(index: I, w: &mut Writer) {
+    if w.config().comments() {
+        write!(w, " (; {} ;)", index.into())
+    }
+}
+
+/// Writes a comment indicating that the `remaining` items of a section were not written due to
+/// the [`Config::max_items`] limit.
+fn write_omitted_items(remaining: u32, kind: &str, w: &mut Writer) {
+    if remaining > 0 {
+        writeln!(w, "(; {remaining} more {kind} ;)");
     }
 }
 
 fn write_type_use(index: crate::index::TypeIdx, w: &mut Writer) {
     w.write_str("(type ");
-    write_index(false, index, w);
+    write_id(index, w);
     w.write_char(')');
 }
 
@@ -119,6 +281,10 @@ fn write_limits(limits: &types::Limits, w: &mut Writer) {
 }
 
 fn write_table_type(table_type: &types::TableType, w: &mut Writer) {
+    if matches!(table_type.limits().index_type(), types::IdxType::I64) {
+        w.write_str("i64 ");
+    }
+
     write_limits(table_type.limits(), w);
     write!(w, " {}", table_type.element_type());
 }
@@ -132,7 +298,11 @@ fn write_mem_type(memory_type: &types::MemType, w: &mut Writer) {
         w.write_str("(; shared ;) ");
     }
 
-    write_limits(memory_type, w)
+    write_limits(memory_type, w);
+
+    if memory_type.page_size_log2().is_some() {
+        write!(w, " (pagesize {})", memory_type.page_size());
+    }
 }
 
 fn write_global_type(global_type: types::GlobalType, w: &mut Writer) {
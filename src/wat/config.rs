@@ -0,0 +1,140 @@
+/// Options that control how structures are written in the
+/// [WebAssembly text format](https://webassembly.github.io/spec/core/text/index.html).
+///
+/// The [`Config::default()`] options produce the same text as the [`Display`](core::fmt::Display)
+/// implementations, which includes comments such as the decimal value of each integer constant.
+/// Disabling [`comments`](Config::with_comments) writes constants in plain decimal instead, which
+/// allows the text to be parsed again by tools that do not expect the annotations.
+///
+/// # Example
+///
+/// ```
+/// use wasmiter::text::Config;
+///
+/// let wasm = wat::parse_str("(module (func (result i32) i32.const -1))").unwrap();
+/// let sections = wasmiter::parse_module_sections(wasm.as_slice())?;
+///
+/// let text = sections
+///     .display_module_with(Config::new().with_comments(false).with_indentation(4))
+///     .to_string();
+///
+/// assert!(text.contains("\n    i32.const -1"));
+/// assert!(!text.contains("(;"));
+/// # Ok::<(), wasmiter::parser::Error>(())
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Config {
+    folded_instructions: bool,
+    names: bool,
+    comments: bool,
+    indentation: u8,
+    max_items: u32,
+}
+
+impl Config {
+    /// Creates a new [`Config`] with the default options, writing instructions in the flat form
+    /// with names, comments, two spaces of indentation, and no limit on the number of items.
+    pub const fn new() -> Self {
+        Self {
+            folded_instructions: false,
+            names: true,
+            comments: true,
+            indentation: 2,
+            max_items: u32::MAX,
+        }
+    }
+
+    /// Sets whether instructions are written in the
+    /// [folded form](https://webassembly.github.io/spec/core/text/instructions.html#folded-instructions),
+    /// such as `(i32.add (local.get 0) (i32.const 1))`, where each instruction encloses the
+    /// instructions that produce its operands, and each block is enclosed in parentheses.
+    ///
+    /// Only instructions with a known [`StackEffect`](crate::instruction_set::StackEffect) have
+    /// their operands folded, so instructions such as `call` are written in the flat form.
+    /// Instructions are also written in the flat form when
+    /// [relocation annotations](crate::sections::DisplayModule::with_relocation_annotations) are
+    /// written, and for blocks that are nested more than `128` levels deep.
+    pub const fn with_folded_instructions(mut self, folded: bool) -> Self {
+        self.folded_instructions = folded;
+        self
+    }
+
+    /// Sets whether names from the *name section* are used as identifiers for definitions, rather
+    /// than only referring to them by their indices.
+    ///
+    /// Names are written for the module, for locals, and for every index space that is named by
+    /// the *name section*, both where definitions are declared and where they are referred to.
+    /// Labels are always written as relative depths, so label names are not used. Names that
+    /// cannot be written as identifiers are ignored.
+    ///
+    /// Names are only written when the `alloc` feature is enabled, since they are read into a
+    /// table before the module is written. Disabling names also avoids the cost of reading the
+    /// *name section*.
+    pub const fn with_names(mut self, names: bool) -> Self {
+        self.names = names;
+        self
+    }
+
+    /// Sets whether comments are written, such as the indices of definitions, the size of each
+    /// function's code, and the decimal values of constants, which are otherwise written in
+    /// hexadecimal.
+    ///
+    /// Comments indicating that items were omitted or that an error occured are always written.
+    pub const fn with_comments(mut self, comments: bool) -> Self {
+        self.comments = comments;
+        self
+    }
+
+    /// Sets the number of spaces used for each level of indentation.
+    pub const fn with_indentation(mut self, width: u8) -> Self {
+        self.indentation = width;
+        self
+    }
+
+    /// Sets the maximum number of items that are written for each section, such as the number of
+    /// imports in the *import section* or the number of functions in the *code section*.
+    ///
+    /// Items past the limit are skipped, and a comment indicating how many were omitted is
+    /// written instead.
+    pub const fn with_max_items(mut self, count: u32) -> Self {
+        self.max_items = count;
+        self
+    }
+
+    /// Whether instructions are written in the folded form.
+    #[inline]
+    pub const fn folded_instructions(&self) -> bool {
+        self.folded_instructions
+    }
+
+    /// Whether names from the *name section* are written.
+    #[inline]
+    pub const fn names(&self) -> bool {
+        self.names
+    }
+
+    /// Whether comments are written.
+    #[inline]
+    pub const fn comments(&self) -> bool {
+        self.comments
+    }
+
+    /// The number of spaces used for each level of indentation.
+    #[inline]
+    pub const fn indentation(&self) -> u8 {
+        self.indentation
+    }
+
+    /// The maximum number of items that are written for each section.
+    #[inline]
+    pub const fn max_items(&self) -> u32 {
+        self.max_items
+    }
+}
+
+impl Default for Config {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
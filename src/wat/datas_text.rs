@@ -1,93 +1,119 @@
-use crate::input::Input;
-use crate::{component::DataMode, wat};
+use crate::{
+    component::{DataMode, DatasComponent, SegmentLimits},
+    input::Input,
+    wat,
+};
 
-impl<I: Input> wat::Wat for crate::component::DatasComponent<I> {
-    fn write(mut self, mut w: &mut wat::Writer) -> wat::Parsed<()> {
-        for i in (0u32..).flat_map(crate::index::DataIdx::try_from) {
-            let result = self.parse(
-                move |m| {
-                    w.open_paren();
-                    w.write_str("data ");
-                    wat::write_index(true, i, w);
-                    match m {
-                        DataMode::Passive => Ok(w),
-                        DataMode::Active(memory, offset) => {
-                            if memory.to_u32() != 0 {
-                                w.write_char(' ');
-                                w.open_paren();
-                                w.write_str("memory ");
-                                wat::write_index(false, *memory, w);
-                                w.close_paren();
-                            }
+/// Writes the data segments, skipping over any segments and bytes past the `limits`.
+pub(super) fn write<I: Input>(
+    mut datas: DatasComponent<I>,
+    limits: &SegmentLimits,
+    mut w: &mut wat::Writer,
+) -> wat::Parsed<()> {
+    let max_segments = limits.max_segments().min(w.config().max_items());
+    for i in (0u32..).flat_map(crate::index::DataIdx::try_from) {
+        if i.to_u32() >= max_segments {
+            if datas.remaining_count() > 0 {
+                writeln!(w, "(; {} more data segments ;)", datas.remaining_count());
+            }
+            break;
+        }
 
+        let result = datas.parse(
+            move |m| {
+                w.open_paren();
+                w.write_str("data");
+                wat::write_declaration_id(i, w);
+                match m {
+                    DataMode::Passive => Ok(w),
+                    DataMode::Active(memory, offset) => {
+                        if memory.to_u32() != 0 {
                             w.write_char(' ');
                             w.open_paren();
-                            w.write_str("offset ");
-                            wat::instruction_text::expression_linear(offset, w)?;
+                            w.write_str("memory ");
+                            wat::write_id(*memory, w);
                             w.close_paren();
-                            Ok(w)
                         }
+
+                        w.write_char(' ');
+                        w.open_paren();
+                        w.write_str("offset");
+                        wat::instruction_text::expression_linear(offset, w)?;
+                        w.close_paren();
+                        Ok(w)
                     }
-                },
-                |w: &mut wat::Writer, data| {
-                    let mut length = usize::try_from(data.length()).unwrap_or(usize::MAX);
+                }
+            },
+            |w: &mut wat::Writer, data| {
+                let omitted = data.length().saturating_sub(limits.max_data_bytes());
+                let mut length = usize::try_from(data.length() - omitted).unwrap_or(usize::MAX);
 
-                    if length > 0 {
-                        if length > 16 {
-                            writeln!(w);
-                            w.write_str(wat::INDENTATION);
-                        } else {
-                            w.write_char(' ');
-                        }
+                if length > 0 {
+                    if length > 16 {
+                        writeln!(w);
+                        w.write_indentation(1);
+                    } else {
+                        w.write_char(' ');
+                    }
 
-                        let mut buffer = [0u8; 16];
-                        let mut offset = data.base();
+                    let mut buffer = [0u8; 16];
+                    let mut offset = data.base();
 
-                        while length > 0 {
-                            let buffer_size = core::cmp::min(buffer.len(), length);
-                            data.read_exact(&mut offset, &mut buffer[..buffer_size])?;
-                            length -= buffer_size;
+                    while length > 0 {
+                        let buffer_size = core::cmp::min(buffer.len(), length);
+                        data.read_exact(&mut offset, &mut buffer[..buffer_size])?;
+                        length -= buffer_size;
 
-                            w.write_char('"');
-                            for b in &buffer[..buffer_size] {
-                                match b {
-                                    0x20..=0x21 | 0x23..=0x26 | 0x28..=0x5B | 0x5D..=0x7E => {
-                                        w.write_char(char::from_u32(u32::from(*b)).unwrap())
-                                    }
-                                    b'\t' => w.write_str("\\t"),
-                                    b'\n' => w.write_str("\\n"),
-                                    b'\r' => w.write_str("\\r"),
-                                    b'\'' => w.write_str("\\'"),
-                                    b'\"' => w.write_str("\\\""),
-                                    b'\\' => w.write_str("\\\\"),
-                                    _ => write!(w, "\\{b:02X}"),
+                        w.write_char('"');
+                        for b in &buffer[..buffer_size] {
+                            match b {
+                                0x20..=0x21 | 0x23..=0x26 | 0x28..=0x5B | 0x5D..=0x7E => {
+                                    w.write_char(char::from_u32(u32::from(*b)).unwrap())
                                 }
+                                b'\t' => w.write_str("\\t"),
+                                b'\n' => w.write_str("\\n"),
+                                b'\r' => w.write_str("\\r"),
+                                b'\'' => w.write_str("\\'"),
+                                b'\"' => w.write_str("\\\""),
+                                b'\\' => w.write_str("\\\\"),
+                                _ => write!(w, "\\{b:02X}"),
                             }
+                        }
 
-                            w.write_char('"');
+                        w.write_char('"');
 
-                            // Write indentation for next line if there are more bytes to write
-                            if length > 0 {
-                                writeln!(w);
-                                w.write_str(wat::INDENTATION);
-                            }
+                        // Write indentation for next line if there are more bytes to write
+                        if length > 0 {
+                            writeln!(w);
+                            w.write_indentation(1);
                         }
-                    } else {
-                        w.write_str("\"\"");
                     }
+                } else if omitted == 0 {
+                    w.write_str("\"\"");
+                }
 
-                    w.close_paren();
-                    writeln!(w);
-                    Ok(w)
-                },
-            )?;
+                if omitted > 0 {
+                    write!(w, " (; {omitted} more bytes ;)");
+                }
 
-            match result {
-                Some(wr) => w = wr,
-                None => break,
-            }
+                w.close_paren();
+                writeln!(w);
+                Ok(w)
+            },
+        )?;
+
+        match result {
+            Some(wr) => w = wr,
+            None => break,
         }
+    }
+
+    Ok(())
+}
 
-        Ok(())
+impl<I: Input> wat::Wat for DatasComponent<I> {
+    #[inline]
+    fn write(self, w: &mut wat::Writer) -> wat::Parsed<()> {
+        write(self, &SegmentLimits::UNLIMITED, w)
     }
 }
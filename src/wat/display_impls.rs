@@ -5,8 +5,12 @@ use crate::{
 };
 use core::fmt::{Display, Formatter, Result};
 
-fn write_wat(thing: impl Wat, f: &mut Formatter) -> Result {
-    let mut writer = Writer::new(f);
+pub(crate) fn write_wat(thing: impl Wat, f: &mut Formatter) -> Result {
+    write_wat_with(thing, wat::Config::new(), f)
+}
+
+fn write_wat_with(thing: impl Wat, config: wat::Config, f: &mut Formatter) -> Result {
+    let mut writer = Writer::with_config(f, config);
     if let Err(e) = thing.write(&mut writer) {
         wat::write_err(&e, &mut writer);
     }
@@ -20,6 +24,13 @@ impl<T: Input, C: Input> Display for component::FuncsComponent<T, C> {
     }
 }
 
+impl<I: Input> Display for component::Func<I> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write_wat(self.borrow_input(), f)
+    }
+}
+
 impl<I: Input> Display for component::TypesComponent<I> {
     #[inline]
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
@@ -94,9 +105,38 @@ impl<O: crate::parser::Offset, I: Input> Display
     }
 }
 
+impl<I: Input> Display for component::DisplaySegments<'_, component::ElemsComponent<I>> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let mut writer = Writer::new(f);
+        let elems = self.segments().borrow_input();
+        if let Err(e) = wat::elems_text::write(elems, self.limits(), &mut writer) {
+            wat::write_err(&e, &mut writer);
+        }
+        writer.finish()
+    }
+}
+
+impl<I: Input> Display for component::DisplaySegments<'_, component::DatasComponent<I>> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let mut writer = Writer::new(f);
+        let datas = self.segments().borrow_input();
+        if let Err(e) = wat::datas_text::write(datas, self.limits(), &mut writer) {
+            wat::write_err(&e, &mut writer);
+        }
+        writer.finish()
+    }
+}
+
 impl<I: Input> Display for crate::sections::DisplayModule<'_, I> {
     #[inline]
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        write_wat(Self::new(self.as_sections()), f)
+        write_wat_with(*self, *self.config(), f)
+    }
+}
+
+impl<I: Input> Display for crate::sections::DisplayFunction<'_, I> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write_wat(*self, f)
     }
 }
@@ -1,78 +1,113 @@
-use crate::{component::ElementInit, component::ElementMode, input::Input, wat};
+use crate::{
+    component::{ElementInit, ElementMode, ElemsComponent, SegmentLimits},
+    input::Input,
+    wat,
+};
 
-impl<I: Input> wat::Wat for crate::component::ElemsComponent<I> {
-    fn write(mut self, mut w: &mut wat::Writer) -> wat::Parsed<()> {
-        for i in (0u32..).flat_map(crate::index::ElemIdx::try_from) {
-            let result = self.parse(
-                |mode| {
-                    w.open_paren();
-                    w.write_str("elem ");
-                    wat::write_index(true, i, w);
-                    w.write_char(' ');
+/// Writes the element segments, skipping over any segments and elements past the `limits`.
+pub(super) fn write<I: Input>(
+    mut elems: ElemsComponent<I>,
+    limits: &SegmentLimits,
+    mut w: &mut wat::Writer,
+) -> wat::Parsed<()> {
+    let max_segments = limits.max_segments().min(w.config().max_items());
+    for i in (0u32..).flat_map(crate::index::ElemIdx::try_from) {
+        if i.to_u32() >= max_segments {
+            if elems.remaining_count() > 0 {
+                writeln!(w, "(; {} more elem segments ;)", elems.remaining_count());
+            }
+            break;
+        }
 
-                    match mode {
-                        ElementMode::Passive => (),
-                        ElementMode::Declarative => w.write_str("declare"),
-                        ElementMode::Active(table, offset) => {
-                            write!(w, "(table ");
-                            wat::write_index(false, *table, w);
-                            w.write_str(") ");
-                            w.open_paren();
-                            w.write_str("offset ");
-                            wat::instruction_text::expression_linear(offset, w)?;
-                            w.close_paren();
-                        }
+        let result = elems.parse(
+            |mode| {
+                w.open_paren();
+                w.write_str("elem");
+                wat::write_declaration_id(i, w);
+
+                match mode {
+                    ElementMode::Passive => (),
+                    ElementMode::Declarative => w.write_str(" declare"),
+                    ElementMode::Active(table, offset) => {
+                        w.write_str(" (table ");
+                        wat::write_id(*table, w);
+                        w.write_str(") ");
+                        w.open_paren();
+                        w.write_str("offset");
+                        wat::instruction_text::expression_linear(offset, w)?;
+                        w.close_paren();
                     }
+                }
 
-                    w.write_char(' ');
-                    Ok(w)
-                },
-                |mut w, init| {
-                    match init {
-                        ElementInit::Functions(functions) => {
-                            w.write_str("func");
-                            for idx in functions {
-                                w.write_char(' ');
-                                wat::write_index(false, idx?, w);
+                w.write_char(' ');
+                Ok(w)
+            },
+            |mut w, init| {
+                let max_elements = limits.max_elements();
+                let remaining = match init {
+                    ElementInit::Functions(functions) => {
+                        w.write_str("func");
+                        while functions.consumed_count() < max_elements {
+                            match functions.next() {
+                                Some(idx) => {
+                                    w.write_char(' ');
+                                    wat::write_id(idx?, w);
+                                }
+                                None => break,
                             }
                         }
-                        ElementInit::Expressions(ref_type, expressions) => {
-                            write!(w, "{ref_type} ");
 
-                            let writer = core::cell::RefCell::new(w);
+                        functions.remaining_count()
+                    }
+                    ElementInit::Expressions(ref_type, expressions) => {
+                        write!(w, "{ref_type}");
 
-                            loop {
-                                let result = expressions.next(|item| {
-                                    let mut w = writer.borrow_mut();
-                                    w.open_paren();
-                                    w.write_str("item ");
-                                    wat::instruction_text::expression_linear(item, &mut w)?;
-                                    w.close_paren();
-                                    Ok(())
-                                })?;
+                        let writer = core::cell::RefCell::new(w);
 
-                                match result {
-                                    Some(()) => (),
-                                    None => break,
-                                }
-                            }
+                        while expressions.consumed_count() < max_elements {
+                            let result = expressions.next(|item| {
+                                let mut w = writer.borrow_mut();
+                                w.write_char(' ');
+                                w.open_paren();
+                                w.write_str("item");
+                                wat::instruction_text::expression_linear(item, &mut w)?;
+                                w.close_paren();
+                                Ok(())
+                            })?;
 
-                            w = writer.into_inner();
+                            match result {
+                                Some(()) => (),
+                                None => break,
+                            }
                         }
+
+                        w = writer.into_inner();
+                        expressions.remaining_count()
                     }
+                };
 
-                    w.close_paren();
-                    writeln!(w);
-                    Ok(w)
-                },
-            )?;
+                if remaining > 0 {
+                    write!(w, " (; {remaining} more ;)");
+                }
 
-            match result {
-                Some(wr) => w = wr,
-                None => break,
-            }
+                w.close_paren();
+                writeln!(w);
+                Ok(w)
+            },
+        )?;
+
+        match result {
+            Some(wr) => w = wr,
+            None => break,
         }
+    }
+
+    Ok(())
+}
 
-        Ok(())
+impl<I: Input> wat::Wat for ElemsComponent<I> {
+    #[inline]
+    fn write(self, w: &mut wat::Writer) -> wat::Parsed<()> {
+        write(self, &SegmentLimits::UNLIMITED, w)
     }
 }
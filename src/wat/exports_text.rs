@@ -1,40 +1,64 @@
-use crate::{component::ExportKind, input::BorrowInput as _, wat};
+use crate::{
+    component::{Export, ExportKind, ExportsComponent},
+    input::{BorrowInput as _, Input},
+    wat,
+};
 
-impl<B: crate::input::Input> wat::Wat for crate::component::ExportsComponent<B> {
-    fn write(self, w: &mut wat::Writer) -> wat::Parsed<()> {
-        for result in self.borrow_input() {
-            w.open_paren();
-            w.write_str("export ");
-            let export = result?;
-            write!(w, "{:?} ", export.name());
-            w.open_paren();
-            match export.kind() {
-                ExportKind::Function(idx) => {
-                    w.write_str("func ");
-                    wat::write_index(false, *idx, w)
-                }
-                ExportKind::Table(idx) => {
-                    w.write_str("table ");
-                    wat::write_index(false, *idx, w)
-                }
-                ExportKind::Memory(idx) => {
-                    w.write_str("memory ");
-                    wat::write_index(false, *idx, w)
-                }
-                ExportKind::Global(idx) => {
-                    w.write_str("global ");
-                    wat::write_index(false, *idx, w)
-                }
-                ExportKind::Tag(idx) => {
-                    w.write_str("tag ");
-                    wat::write_index(false, *idx, w)
-                }
-            }
-            w.close_paren();
-            w.close_paren();
-            writeln!(w);
+fn write_export<B: Input>(export: &Export<B>, w: &mut wat::Writer) {
+    w.open_paren();
+    w.write_str("export ");
+    write!(w, "{} ", export.name().borrow_input().escaped());
+    w.open_paren();
+    match export.kind() {
+        ExportKind::Function(idx) => {
+            w.write_str("func ");
+            wat::write_id(*idx, w)
+        }
+        ExportKind::Table(idx) => {
+            w.write_str("table ");
+            wat::write_id(*idx, w)
+        }
+        ExportKind::Memory(idx) => {
+            w.write_str("memory ");
+            wat::write_id(*idx, w)
+        }
+        ExportKind::Global(idx) => {
+            w.write_str("global ");
+            wat::write_id(*idx, w)
         }
+        ExportKind::Tag(idx) => {
+            w.write_str("tag ");
+            wat::write_id(*idx, w)
+        }
+    }
+    w.close_paren();
+    w.close_paren();
+}
+
+pub(super) fn write_exports<B: Input>(
+    exports: ExportsComponent<B>,
+    w: &mut wat::Writer,
+) -> wat::Parsed<()> {
+    let mut exports = exports.borrow_input();
+    let max_items = crate::int::u32_to_usize(w.config().max_items());
+    for result in exports.by_ref().take(max_items) {
+        write_export(&result?, w);
+        writeln!(w);
+    }
+
+    wat::write_omitted_items(exports.remaining_count(), "exports", w);
+    Ok(())
+}
+
+impl<B: Input> wat::Wat for ExportsComponent<B> {
+    fn write(self, w: &mut wat::Writer) -> wat::Parsed<()> {
+        write_exports(self, w)
+    }
+}
 
+impl<B: Input> wat::Wat for Export<B> {
+    fn write(self, w: &mut wat::Writer) -> wat::Parsed<()> {
+        write_export(&self, w);
         Ok(())
     }
 }
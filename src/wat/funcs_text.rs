@@ -1,43 +1,212 @@
-use crate::{input::Input, wat};
+use crate::{
+    component::{Func, ImportKind, KnownSection},
+    index::FuncIdx,
+    input::{BorrowInput as _, Input},
+    wat,
+};
+
+/// Writes a function, where `index` is its index if it is known.
+fn write_func<C: Input, T: Clone + Input>(
+    func: Func<C>,
+    index: Option<FuncIdx>,
+    context: &wat::ModuleContext<T>,
+    relocations: Option<&mut wat::RelocationAnnotations<impl Input>>,
+    w: &mut wat::Writer,
+) -> wat::Parsed<()> {
+    w.open_paren();
+    w.write_str("func");
+    if let Some(index) = index {
+        wat::write_declaration_name(index, w);
+    }
+    w.write_char(' ');
+    write_func_contents(func, index, context, relocations, w)
+}
+
+/// Writes the type use, local variables, and body of a function, along with the closing
+/// parenthesis.
+///
+/// The names of the function's locals are only written if its `index` and the number of its
+/// parameters are known.
+fn write_func_contents<C: Input, T: Clone + Input>(
+    func: Func<C>,
+    index: Option<FuncIdx>,
+    context: &wat::ModuleContext<T>,
+    relocations: Option<&mut wat::RelocationAnnotations<impl Input>>,
+    mut w: &mut wat::Writer,
+) -> wat::Parsed<()> {
+    w.function = index.map(FuncIdx::to_u32);
+    let parameter_count = context.write_signature(func.signature(), w)?;
+    if parameter_count.is_none() {
+        w.function = None;
+    }
+
+    let code = func.into_code();
+    if w.config().comments() {
+        write!(w, " ;; code size = {}", code.content().length());
+    }
+    writeln!(w);
+
+    w = code.read(
+        move |locals| {
+            let first = parameter_count.unwrap_or(0);
+            for (i, result) in (first..)
+                .flat_map(crate::index::LocalIdx::try_from)
+                .zip(locals)
+            {
+                let local_type = result?;
+                w.write_indentation(1);
+                w.open_paren();
+                w.write_str("local");
+                if !w.write_local_name(i.to_u32(), " ") {
+                    wat::write_declaration_index(i, w);
+                }
+                write!(w, " {local_type}");
+                w.close_paren();
+                writeln!(w);
+            }
+            wat::Parsed::Ok(w)
+        },
+        |w, code| {
+            wat::instruction_text::expression_indented(code, true, relocations, w)?;
+            Ok(w)
+        },
+    )?;
+
+    w.function = None;
+    w.close_paren();
+    Ok(())
+}
+
+/// Writes the functions whose positions are within the `range`, where `first` is the index of the
+/// first function, annotating instructions with the given `relocations` if they are provided.
+pub(super) fn write_funcs<T: Clone + Input, C: Clone + Input, M: Clone + Input>(
+    mut funcs: crate::component::FuncsComponent<T, C>,
+    context: &wat::ModuleContext<M>,
+    first: u32,
+    range: (u32, u32),
+    mut relocations: Option<&mut wat::RelocationAnnotations<impl Input>>,
+    w: &mut wat::Writer,
+) -> wat::Parsed<()> {
+    let (start, end) = range;
+    for result in funcs.by_ref().take(crate::int::u32_to_usize(start)) {
+        result?;
+    }
+
+    let count = end.saturating_sub(start).min(funcs.remaining_count());
+    let written = count.min(w.config().max_items());
+    for (index, result) in
+        (first.saturating_add(start)..).zip(funcs.by_ref().take(crate::int::u32_to_usize(written)))
+    {
+        let index = Some(FuncIdx::from(index));
+        write_func(result?, index, context, relocations.as_deref_mut(), w)?;
+        writeln!(w);
+    }
+
+    wat::write_omitted_items(count - written, "functions", w);
+    Ok(())
+}
+
+impl<C: Input> wat::Wat for Func<C> {
+    #[inline]
+    fn write(self, w: &mut wat::Writer) -> wat::Parsed<()> {
+        write_func(
+            self,
+            None,
+            &wat::NoContext::new(),
+            None::<&mut wat::NoRelocations>,
+            w,
+        )
+    }
+}
 
 impl<T: Clone + Input, C: Clone + Input> wat::Wat for crate::component::FuncsComponent<T, C> {
-    fn write(self, mut w: &mut wat::Writer) -> wat::Parsed<()> {
-        for result in self {
-            let func = result?;
-            w.open_paren();
-            w.write_str("func ");
-            wat::write_type_use(func.signature(), w);
-            let code = func.into_code();
-            write!(w, " ;; code size = {}", code.content().length());
-            writeln!(w);
-
-            w = code.read(
-                move |locals| {
-                    for (i, result) in (0u32..)
-                        .flat_map(crate::index::LocalIdx::try_from)
-                        .zip(locals)
-                    {
-                        let local_type = result?;
-                        w.write_str(wat::INDENTATION);
-                        w.open_paren();
-                        w.write_str("local ");
-                        wat::write_index(true, i, w);
-                        write!(w, " {local_type}");
-                        w.close_paren();
-                        writeln!(w);
+    #[inline]
+    fn write(self, w: &mut wat::Writer) -> wat::Parsed<()> {
+        write_funcs(
+            self,
+            &wat::NoContext::new(),
+            0,
+            (0, u32::MAX),
+            None::<&mut wat::NoRelocations>,
+            w,
+        )
+    }
+}
+
+impl<B: Input> wat::Wat for crate::sections::DisplayFunction<'_, B> {
+    fn write(self, w: &mut wat::Writer) -> wat::Parsed<()> {
+        let index = self.index();
+        if w.config().names() {
+            w.names = wat::NameTable::read(self.as_sections());
+        }
+
+        let mut context = wat::ModuleContext::new();
+        let mut imported_function_count = 0u32;
+        let mut signature = None;
+        for result in self.as_sections().borrow_input() {
+            let known = match KnownSection::interpret(result?) {
+                Ok(known) => known?,
+                Err(_) => continue,
+            };
+
+            match known {
+                KnownSection::Type(types) => context.set_types(types),
+                KnownSection::Import(mut imports) => {
+                    while let Some(import) = imports.parse()? {
+                        if let ImportKind::Function(type_index) = import.kind() {
+                            if imported_function_count == index.to_u32() {
+                                w.open_paren();
+                                w.write_str("func");
+                                wat::write_declaration_name(index, w);
+                                write!(
+                                    w,
+                                    " (import {} {}) ",
+                                    import.module().borrow_input().escaped(),
+                                    import.name().borrow_input().escaped()
+                                );
+                                wat::write_type_use(*type_index, w);
+                                w.close_paren();
+                                return Ok(());
+                            }
+
+                            imported_function_count += 1;
+                        }
+                    }
+                }
+                KnownSection::Function(mut types) => {
+                    signature = match index.to_u32().checked_sub(imported_function_count) {
+                        Some(entry) => types.nth(crate::int::u32_to_usize(entry)).transpose()?,
+                        None => None,
+                    };
+                }
+                KnownSection::Code(mut code) => {
+                    let signature = match signature {
+                        Some(signature) => signature,
+                        None => break,
+                    };
+
+                    // Only the sizes of the preceding entries are read
+                    for _ in imported_function_count..index.to_u32() {
+                        code.parse()?;
                     }
-                    wat::Parsed::Ok(w)
-                },
-                |w, code| {
-                    wat::instruction_text::expression_indented(code, true, w)?;
-                    Ok(w)
-                },
-            )?;
-
-            w.close_paren();
-            writeln!(w);
+
+                    if let Some(body) = code.parse()? {
+                        return write_func(
+                            Func::new(signature, body),
+                            Some(index),
+                            &context,
+                            None::<&mut wat::NoRelocations>,
+                            w,
+                        );
+                    }
+
+                    break;
+                }
+                _ => (),
+            }
         }
 
+        write!(w, "(; function {} is not defined ;)", index.to_u32());
         Ok(())
     }
 }
@@ -1,22 +1,41 @@
-use crate::wat;
+use crate::{component::GlobalsComponent, index::GlobalIdx, input::Input, wat};
 
-impl<B: crate::input::Input> wat::Wat for crate::component::GlobalsComponent<B> {
-    fn write(mut self, mut w: &mut wat::Writer) -> wat::Parsed<()> {
-        loop {
-            let result = self.parse(move |global_type, init| {
-                w.open_paren();
-                w.write_str("global ");
-                wat::write_global_type(global_type, w);
-                wat::instruction_text::expression_linear(init, w)?;
-                w.close_paren();
-                writeln!(w);
-                Ok(w)
-            })?;
+/// Writes the globals in the *global section*, where `first` is the index of the first defined
+/// global, which is the number of imported globals.
+pub(super) fn write_globals<B: Input>(
+    mut globals: GlobalsComponent<B>,
+    first: u32,
+    mut w: &mut wat::Writer,
+) -> wat::Parsed<()> {
+    loop {
+        if globals.consumed_count() >= w.config().max_items() {
+            wat::write_omitted_items(globals.remaining_count(), "globals", w);
+            return Ok(());
+        }
+
+        let index = GlobalIdx::from(first.saturating_add(globals.consumed_count()));
+        let result = globals.parse(move |global_type, init| {
+            w.open_paren();
+            w.write_str("global");
+            wat::write_declaration_name(index, w);
+            w.write_char(' ');
+            wat::write_global_type(global_type, w);
+            wat::instruction_text::expression_linear(init, w)?;
+            w.close_paren();
+            writeln!(w);
+            Ok(w)
+        })?;
 
-            match result {
-                None => return Ok(()),
-                Some(wr) => w = wr,
-            }
+        match result {
+            None => return Ok(()),
+            Some(wr) => w = wr,
         }
     }
 }
+
+impl<B: Input> wat::Wat for GlobalsComponent<B> {
+    #[inline]
+    fn write(self, w: &mut wat::Writer) -> wat::Parsed<()> {
+        write_globals(self, 0, w)
+    }
+}
@@ -1,60 +1,168 @@
-use crate::{component::ImportKind, index, wat};
+use crate::{
+    component::{Import, ImportKind, ImportsComponent},
+    index,
+    input::{BorrowInput as _, Input},
+    wat::{self, ModuleContext},
+};
 
-impl<B: Clone + crate::input::Input> wat::Wat for crate::component::ImportsComponent<B> {
-    fn write(self, w: &mut wat::Writer) -> wat::Parsed<()> {
-        let mut function_count = 0u32;
-        let mut table_count = 0u32;
-        let mut memory_count = 0u32;
-        let mut global_count = 0u32;
-        let mut tag_count = 0u32;
-        for result in self {
+/// The number of each kind of definition that has been imported so far, used to write the index
+/// of each import.
+#[derive(Default)]
+struct ImportCounts {
+    function: u32,
+    table: u32,
+    memory: u32,
+    global: u32,
+}
+
+fn write_import_names<B: Input>(import: &Import<B>, w: &mut wat::Writer) {
+    write!(
+        w,
+        "{} {}",
+        import.module().borrow_input().escaped(),
+        import.name().borrow_input().escaped()
+    );
+}
+
+/// Writes a single import, writing the index of the imported definition only if `counts` are
+/// provided.
+///
+/// If `inline` is set, the import is written using the
+/// [abbreviated form](https://webassembly.github.io/spec/core/text/modules.html#text-func-abbrev-import),
+/// where the import appears within the definition.
+fn write_import<B, T>(
+    import: &Import<B>,
+    mut counts: Option<&mut ImportCounts>,
+    context: &mut ModuleContext<T>,
+    inline: bool,
+    w: &mut wat::Writer,
+) -> wat::Parsed<()>
+where
+    B: Input,
+    T: Clone + Input,
+{
+    fn next_index<N: wat::Named>(count: Option<&mut u32>, w: &mut wat::Writer) {
+        if let Some(count) = count {
+            wat::write_declaration_id(N::from(*count), w);
+            *count += 1;
+        }
+
+        w.write_char(' ');
+    }
+
+    let inline_names = |w: &mut wat::Writer| {
+        if inline {
             w.open_paren();
             w.write_str("import ");
-            let import = result?;
-            write!(w, "{:?} {:?} ", import.module(), import.name());
-            w.open_paren();
-            match import.kind() {
-                ImportKind::Function(ty) => {
-                    w.write_str("func ");
-                    wat::write_index(true, index::FuncIdx::try_from(function_count).unwrap(), w);
-                    w.write_char(' ');
-                    wat::write_type_use(*ty, w);
-                    function_count += 1;
-                }
-                ImportKind::Table(ty) => {
-                    w.write_str("table ");
-                    wat::write_index(true, index::TableIdx::try_from(table_count).unwrap(), w);
-                    w.write_char(' ');
-                    wat::write_table_type(ty, w);
-                    table_count += 1;
-                }
-                ImportKind::Memory(ty) => {
-                    w.write_str("memory ");
-                    wat::write_index(true, index::MemIdx::try_from(memory_count).unwrap(), w);
-                    w.write_char(' ');
-                    wat::write_mem_type(ty, w);
-                    memory_count += 1;
-                }
-                ImportKind::Global(ty) => {
-                    w.write_str("global ");
-                    wat::write_index(true, index::GlobalIdx::try_from(global_count).unwrap(), w);
-                    w.write_char(' ');
-                    wat::write_global_type(*ty, w);
-                    global_count += 1;
-                }
-                ImportKind::Tag(tag) => {
-                    w.write_str("tag ");
-                    wat::write_index(true, index::TagIdx::try_from(tag_count).unwrap(), w);
-                    w.write_char(' ');
-                    wat::tags_text::write_tag(*tag, w);
-                    tag_count += 1;
-                }
-            }
-            w.close_paren();
+            write_import_names(import, w);
             w.close_paren();
+            w.write_char(' ');
+        }
+    };
+
+    if !inline {
+        w.open_paren();
+        w.write_str("import ");
+        write_import_names(import, w);
+        w.write_char(' ');
+    }
+
+    w.open_paren();
+    match import.kind() {
+        ImportKind::Function(ty) => {
+            w.write_str("func");
+            next_index::<index::FuncIdx>(counts.as_deref_mut().map(|c| &mut c.function), w);
+            inline_names(w);
+            wat::write_type_use(*ty, w);
+        }
+        ImportKind::Table(ty) => {
+            w.write_str("table");
+            next_index::<index::TableIdx>(counts.as_deref_mut().map(|c| &mut c.table), w);
+            inline_names(w);
+            wat::write_table_type(ty, w);
+        }
+        ImportKind::Memory(ty) => {
+            w.write_str("memory");
+            next_index::<index::MemIdx>(counts.as_deref_mut().map(|c| &mut c.memory), w);
+            inline_names(w);
+            wat::write_mem_type(ty, w);
+        }
+        ImportKind::Global(ty) => {
+            w.write_str("global");
+            next_index::<index::GlobalIdx>(counts.as_deref_mut().map(|c| &mut c.global), w);
+            inline_names(w);
+            wat::write_global_type(*ty, w);
+        }
+        ImportKind::Tag(tag) => {
+            w.write_str("tag");
+            if counts.is_some() {
+                wat::write_declaration_id(index::TagIdx::from(context.tag_count), w);
+                context.tag_count += 1;
+            }
+            w.write_char(' ');
+            inline_names(w);
+            context.write_tag_type(*tag, w)?;
+        }
+    }
+    w.close_paren();
+
+    if !inline {
+        w.close_paren();
+    }
+
+    Ok(())
+}
+
+pub(super) fn write_imports<B, T>(
+    imports: ImportsComponent<B>,
+    context: &mut ModuleContext<T>,
+    inline: bool,
+    w: &mut wat::Writer,
+) -> wat::Parsed<()>
+where
+    B: Clone + Input,
+    T: Clone + Input,
+{
+    let mut counts = ImportCounts::default();
+    let mut omitted = 0u32;
+    for (i, result) in (0u32..).zip(imports) {
+        let import = result?;
+        if i < w.config().max_items() {
+            write_import(&import, Some(&mut counts), context, inline, w)?;
             writeln!(w);
+            continue;
+        }
+
+        // Imports that are not written are still counted, since they affect later indices
+        match import.kind() {
+            ImportKind::Function(_) => counts.function += 1,
+            ImportKind::Table(_) => counts.table += 1,
+            ImportKind::Memory(_) => counts.memory += 1,
+            ImportKind::Global(_) => counts.global += 1,
+            ImportKind::Tag(_) => context.tag_count += 1,
         }
+        omitted += 1;
+    }
+
+    wat::write_omitted_items(omitted, "imports", w);
+
+    context.function_count = counts.function;
+    context.table_count = counts.table;
+    context.memory_count = counts.memory;
+    context.global_count = counts.global;
+
+    Ok(())
+}
 
-        Ok(())
+impl<B: Clone + Input> wat::Wat for ImportsComponent<B> {
+    fn write(self, w: &mut wat::Writer) -> wat::Parsed<()> {
+        write_imports(self, &mut wat::NoContext::new(), false, w)
+    }
+}
+
+/// Writes the import without the index of the imported definition, since it is not known.
+impl<B: Input> wat::Wat for Import<B> {
+    fn write(self, w: &mut wat::Writer) -> wat::Parsed<()> {
+        write_import(&self, None, &mut wat::NoContext::new(), false, w)
     }
 }
@@ -1,8 +1,8 @@
 use crate::{
-    input::{BorrowInput as _, Input},
-    instruction_set::{self, Instruction as Instr, InstructionSequence},
+    input::{BorrowInput as _, HasInput as _, Input},
+    instruction_set::{self, Instruction as Instr, InstructionSequence, StackEffect},
     parser::Offset,
-    types::{self, BlockType},
+    types::BlockType,
     wat::{self, Writer},
 };
 
@@ -14,25 +14,58 @@ fn write_block_type(block_type: BlockType, w: &mut Writer) {
     }
 }
 
-fn write_non_zero_index<I: Copy + Into<u32>>(idx: I, w: &mut Writer) {
-    if idx.into() != 0 {
+fn write_non_zero_index<I: wat::Named>(idx: I, w: &mut Writer) {
+    if Into::<u32>::into(idx) != 0 {
         w.write_char(' ');
-        wat::write_index(false, idx, w);
+        wat::write_id(idx, w);
     }
 }
 
-fn write_mem_arg(arg: &instruction_set::MemArg, w: &mut Writer) {
+/// Writes both indices unless they are both zero, since the text format does not allow only one
+/// of them to be omitted.
+fn write_index_pair<I: wat::Named>(x: I, y: I, w: &mut Writer) {
+    if Into::<u32>::into(x) != 0 || Into::<u32>::into(y) != 0 {
+        w.write_char(' ');
+        wat::write_id(x, w);
+        w.write_char(' ');
+        wat::write_id(y, w);
+    }
+}
+
+fn write_mem_arg(
+    arg: &instruction_set::MemArg,
+    natural_alignment: Option<instruction_set::Align>,
+    w: &mut Writer,
+) {
     write_non_zero_index(arg.memory(), w);
 
     if arg.offset() != 0 {
         write!(w, " offset={}", arg.offset());
     }
 
-    if arg.align() != instruction_set::Align::None {
+    // Like wasm2wat, alignment is omitted when it is equal to the natural alignment
+    if Some(arg.align()) != natural_alignment {
         write!(w, " align={}", arg.align());
     }
 }
 
+/// Writes a floating-point constant in decimal, or a NaN with its sign and `payload`, without any
+/// comments.
+fn write_float<F: core::fmt::Display>(
+    value: F,
+    is_nan: bool,
+    negative: bool,
+    payload: u64,
+    w: &mut Writer,
+) {
+    if is_nan {
+        let sign = if negative { "-" } else { "" };
+        write!(w, " {sign}nan:{payload:#X}");
+    } else {
+        write!(w, " {value}");
+    }
+}
+
 fn instruction<I: Input>(
     instr: &mut Instr<'_, I>,
     indentation: Option<u32>,
@@ -44,34 +77,66 @@ fn instruction<I: Input>(
     }
 
     if let Some(mut level) = indentation {
-        if matches!(
-            instr,
-            Instr::Else | Instr::Catch(_) | Instr::CatchAll | Instr::Delegate(_) | Instr::End
-        ) {
+        let ends_block = match instr {
+            Instr::Else | Instr::End => true,
+            #[cfg(feature = "exceptions")]
+            Instr::Catch(_) | Instr::CatchAll | Instr::Delegate(_) => true,
+            _ => false,
+        };
+
+        if ends_block {
             level = level.saturating_sub(1);
         };
 
         // InstructionSequence has nesting >= 1, so function bodies will always have indentation
-        for _ in 0..level {
-            w.write_str(wat::INDENTATION);
-        }
+        w.write_indentation(level);
     }
 
     w.write_str(instr.name());
 
+    let natural_alignment = instr.natural_alignment();
+    let comments = w.config().comments();
     match instr {
-        Instr::Block(ty) | Instr::Loop(ty) | Instr::If(ty) | Instr::Try(ty) => {
+        Instr::Block(ty) | Instr::Loop(ty) | Instr::If(ty) => {
+            w.write_char(' ');
+            write_block_type(*ty, w);
+        }
+        #[cfg(feature = "exceptions")]
+        Instr::Try(ty) => {
             w.write_char(' ');
             write_block_type(*ty, w);
         }
+        #[cfg(feature = "exceptions")]
+        Instr::TryTable(ty, clauses) => {
+            if !matches!(ty, BlockType::Empty) {
+                w.write_char(' ');
+                write_block_type(*ty, w);
+            }
+
+            for result in clauses.iter()? {
+                let clause = result?;
+                write!(w, " ({}", clause.name());
+                if let Some(tag) = clause.tag() {
+                    w.write_char(' ');
+                    wat::write_id(tag, w);
+                }
+                write!(w, " {})", clause.label().to_u32());
+            }
+        }
+        #[cfg(feature = "exceptions")]
         Instr::Catch(idx) | Instr::Throw(idx) => {
             w.write_char(' ');
-            wat::write_index(false, *idx, w)
+            wat::write_id(*idx, w)
+        }
+        Instr::Br(target) | Instr::BrIf(target) => {
+            write!(w, " {}", target.to_u32())
+        }
+        #[cfg(feature = "exceptions")]
+        Instr::Delegate(target) | Instr::Rethrow(target) => {
+            write!(w, " {}", target.to_u32())
         }
-        Instr::Br(target)
-        | Instr::BrIf(target)
-        | Instr::Delegate(target)
-        | Instr::Rethrow(target) => {
+        #[cfg(feature = "function-references")]
+        Instr::BrOnNull(target) | Instr::BrOnNonNull(target) => {
             write!(w, " {}", target.to_u32())
         }
         Instr::BrTable(entries) => {
@@ -79,13 +144,29 @@ fn instruction<I: Input>(
                 write!(w, " {}", u32::from(target?));
             }
         }
-        Instr::Call(idx) | Instr::RefFunc(idx) | Instr::ReturnCall(idx) => {
+        Instr::Call(idx) | Instr::RefFunc(idx) => {
+            w.write_char(' ');
+            wat::write_id(*idx, w)
+        }
+        #[cfg(feature = "tail-call")]
+        Instr::ReturnCall(idx) => {
+            w.write_char(' ');
+            wat::write_id(*idx, w)
+        }
+        #[cfg(feature = "function-references")]
+        Instr::CallRef(signature) | Instr::ReturnCallRef(signature) => {
+            w.write_char(' ');
+            wat::write_id(*signature, w)
+        }
+        Instr::CallIndirect(signature, table) => {
             w.write_char(' ');
-            wat::write_index(false, *idx, w)
+            wat::write_id(*table, w);
+            wat::write_type_use(*signature, w);
         }
-        Instr::CallIndirect(signature, table) | Instr::ReturnCallIndirect(signature, table) => {
+        #[cfg(feature = "tail-call")]
+        Instr::ReturnCallIndirect(signature, table) => {
             w.write_char(' ');
-            wat::write_index(false, *table, w);
+            wat::write_id(*table, w);
             wat::write_type_use(*signature, w);
         }
         Instr::Select(types) => {
@@ -97,11 +178,11 @@ fn instruction<I: Input>(
         }
         Instr::LocalGet(idx) | Instr::LocalSet(idx) | Instr::LocalTee(idx) => {
             w.write_char(' ');
-            wat::write_index(false, *idx, w);
+            wat::write_local_id(*idx, w);
         }
         Instr::GlobalGet(idx) | Instr::GlobalSet(idx) => {
             w.write_char(' ');
-            wat::write_index(false, *idx, w);
+            wat::write_id(*idx, w);
         }
         Instr::I32Load(arg)
         | Instr::I64Load(arg)
@@ -125,8 +206,11 @@ fn instruction<I: Input>(
         | Instr::I32Store16(arg)
         | Instr::I64Store8(arg)
         | Instr::I64Store16(arg)
-        | Instr::I64Store32(arg)
-        | Instr::V128Load(arg)
+        | Instr::I64Store32(arg) => {
+            write_mem_arg(arg, natural_alignment, w);
+        }
+        #[cfg(feature = "simd")]
+        Instr::V128Load(arg)
         | Instr::V128Load8x8S(arg)
         | Instr::V128Load8x8U(arg)
         | Instr::V128Load16x4S(arg)
@@ -139,8 +223,11 @@ fn instruction<I: Input>(
         | Instr::V128Load64Splat(arg)
         | Instr::V128Load32Zero(arg)
         | Instr::V128Load64Zero(arg)
-        | Instr::V128Store(arg)
-        | Instr::MemoryAtomicNotify(arg)
+        | Instr::V128Store(arg) => {
+            write_mem_arg(arg, natural_alignment, w);
+        }
+        #[cfg(feature = "threads")]
+        Instr::MemoryAtomicNotify(arg)
         | Instr::MemoryAtomicWait32(arg)
         | Instr::MemoryAtomicWait64(arg)
         | Instr::I32AtomicLoad(arg)
@@ -206,59 +293,67 @@ fn instruction<I: Input>(
         | Instr::I64AtomicRmw8CmpxchgU(arg)
         | Instr::I64AtomicRmw16CmpxchgU(arg)
         | Instr::I64AtomicRmw32CmpxchgU(arg) => {
-            write_mem_arg(arg, w);
+            write_mem_arg(arg, natural_alignment, w);
         }
         Instr::MemorySize(idx) | Instr::MemoryGrow(idx) | Instr::MemoryFill(idx) => {
             write_non_zero_index(*idx, w)
         }
+        Instr::I32Const(i) if !comments => write!(w, " {i}"),
+        Instr::I64Const(i) if !comments => write!(w, " {i}"),
+        Instr::F32Const(f) if !comments => write_float(
+            *f,
+            f.is_nan(),
+            f.is_sign_negative(),
+            u64::from(f.to_bits() & 0x7F_FFFF),
+            w,
+        ),
+        Instr::F64Const(f) if !comments => write_float(
+            *f,
+            f.is_nan(),
+            f.is_sign_negative(),
+            f.to_bits() & 0xF_FFFF_FFFF_FFFF,
+            w,
+        ),
         Instr::I32Const(i) => write!(w, " {i:#010X} (; {i} signed, {} unsigned ;)", *i as u32),
         Instr::I64Const(i) => write!(w, " {i:#018X} (; {i} signed, {} unsigned ;)", *i as u64),
         Instr::F32Const(f) => write!(w, " {:#010X} (; {f} ;)", f.to_bits()),
         Instr::F64Const(f) => write!(w, " {:#018X} (; {f} ;)", f.to_bits()),
-        Instr::RefNull(rt) => w.write_str(match rt {
-            types::RefType::Extern => " extern",
-            types::RefType::Func => " func",
-        }),
+        Instr::RefNull(rt) => write!(w, " {}", rt.heap_type()),
         Instr::TableGet(idx)
         | Instr::TableSet(idx)
         | Instr::TableSize(idx)
         | Instr::TableGrow(idx)
         | Instr::TableFill(idx) => {
             w.write_char(' ');
-            wat::write_index(false, *idx, w)
+            wat::write_id(*idx, w)
         }
         Instr::MemoryCopy {
             destination: x,
             source: y,
-        } => {
-            write_non_zero_index(*x, w);
-            write_non_zero_index(*y, w);
-        }
+        } => write_index_pair(*x, *y, w),
         Instr::TableCopy {
             destination: x,
             source: y,
-        } => {
-            write_non_zero_index(*x, w);
-            write_non_zero_index(*y, w);
-        }
+        } => write_index_pair(*x, *y, w),
         Instr::MemoryInit(data, mem) => {
             write_non_zero_index(*mem, w);
             w.write_char(' ');
-            wat::write_index(false, *data, w);
+            wat::write_id(*data, w);
         }
         Instr::TableInit(elem, table) => {
             write_non_zero_index(*table, w);
             w.write_char(' ');
-            wat::write_index(false, *elem, w);
+            wat::write_id(*elem, w);
         }
         Instr::DataDrop(data) => {
             w.write_char(' ');
-            wat::write_index(false, *data, w);
+            wat::write_id(*data, w);
         }
         Instr::ElemDrop(elem) => {
             w.write_char(' ');
-            wat::write_index(false, *elem, w);
+            wat::write_id(*elem, w);
         }
+        #[cfg(feature = "simd")]
         Instr::V128Load8Lane(mem, lane)
         | Instr::V128Load16Lane(mem, lane)
         | Instr::V128Load32Lane(mem, lane)
@@ -267,20 +362,23 @@ fn instruction<I: Input>(
         | Instr::V128Store16Lane(mem, lane)
         | Instr::V128Store32Lane(mem, lane)
         | Instr::V128Store64Lane(mem, lane) => {
-            write_mem_arg(mem, w);
+            write_mem_arg(mem, natural_alignment, w);
             write!(w, " {lane}");
         }
+        #[cfg(feature = "simd")]
         Instr::V128Const(vec) => {
             w.write_str(" i8x16");
             for b in vec.to_le_bytes() {
                 write!(w, " {b:#04X}");
             }
         }
+        #[cfg(feature = "simd")]
         Instr::I8x16Shuffle(lanes) => {
             for index in lanes {
                 write!(w, " {index}");
             }
         }
+        #[cfg(feature = "simd")]
         Instr::I8x16ExtractLaneS(lane)
         | Instr::I8x16ExtractLaneU(lane)
         | Instr::I8x16ReplaceLane(lane)
@@ -309,9 +407,15 @@ impl<I: Input> wat::Wat for Instr<'_, I> {
     }
 }
 
+impl<I: Input> wat::Wat for &mut Instr<'_, I> {
+    fn write(self, writer: &mut Writer) -> wat::Parsed<()> {
+        instruction(self, None, false, writer)
+    }
+}
+
 impl<O: Offset, I: Input> wat::Wat for InstructionSequence<O, I> {
     fn write(mut self, writer: &mut Writer) -> crate::parser::Parsed<()> {
-        expression_indented(&mut self, false, writer)
+        expression_indented(&mut self, false, None::<&mut wat::NoRelocations>, writer)
     }
 }
 
@@ -322,7 +426,10 @@ pub(super) fn expression_linear(
     loop {
         let last = expr.nesting_level() <= 1;
         let printer = |instr: &mut Instr<_>| {
-            w.write_char(' ');
+            // Instructions are preceded by a space, except for the final end which is omitted
+            if !matches!(instr, Instr::End if last) {
+                w.write_char(' ');
+            }
             instruction(instr, None, last, w)?;
             Ok(())
         };
@@ -335,14 +442,460 @@ pub(super) fn expression_linear(
     }
 }
 
+/// The maximum nesting level of blocks that are written in the folded form.
+const MAX_FOLDED_DEPTH: u32 = u128::BITS;
+
+/// Tracks the blocks that are currently open when writing structured control instructions in the
+/// [folded form](https://webassembly.github.io/spec/core/text/instructions.html#folded-instructions).
+#[derive(Default)]
+struct FoldedBlocks {
+    /// Stack containing a set bit for each open block, such as an `if` or a `try`, whose contents
+    /// are enclosed in an additional clause like `(then ...)`.
+    clauses: u128,
+    depth: u32,
+}
+
+impl FoldedBlocks {
+    /// Returns `true` if the innermost open block was written in the folded form.
+    fn is_innermost_folded(&self) -> bool {
+        self.depth > 0 && self.depth <= MAX_FOLDED_DEPTH
+    }
+
+    /// Returns `true` if the next block can be written in the folded form.
+    fn can_fold(&self) -> bool {
+        self.depth < MAX_FOLDED_DEPTH
+    }
+
+    /// Each clause is written on its own line, adding an extra level of indentation.
+    fn extra_indentation(&self) -> u32 {
+        self.clauses.count_ones()
+    }
+
+    fn push(&mut self, has_clauses: bool) {
+        if self.can_fold() {
+            self.clauses = (self.clauses << 1) | u128::from(has_clauses);
+        }
+        self.depth += 1;
+    }
+
+    /// Returns `Some` if the block being closed was written in the folded form, indicating if it
+    /// had a clause that needs to be closed.
+    fn pop(&mut self) -> Option<bool> {
+        let folded = self.is_innermost_folded();
+        self.depth = self.depth.saturating_sub(1);
+        if folded {
+            let has_clauses = self.clauses & 1 == 1;
+            self.clauses >>= 1;
+            Some(has_clauses)
+        } else {
+            None
+        }
+    }
+}
+
+/// Writes the start of a structured control instruction in the folded form, up to its block type.
+fn start_folded_block(name: &str, block_type: BlockType, indentation: u32, w: &mut Writer) {
+    w.write_indentation(indentation);
+    w.open_paren();
+    w.write_str(name);
+    if !matches!(block_type, BlockType::Empty) {
+        w.write_char(' ');
+        write_block_type(block_type, w);
+    }
+}
+
+/// Writes the start of the first `clause` of a folded block on its own line, if the block has
+/// clauses, such as the `then` of an `if`.
+fn open_first_clause(
+    clause: Option<&str>,
+    indentation: u32,
+    blocks: &mut FoldedBlocks,
+    w: &mut Writer,
+) {
+    if let Some(clause) = clause {
+        writeln!(w);
+        w.write_indentation(indentation.saturating_add(1));
+        w.open_paren();
+        w.write_str(clause);
+    }
+
+    blocks.push(clause.is_some());
+}
+
+fn folded_instruction<I: Input>(
+    instr: &mut Instr<'_, I>,
+    level: u32,
+    last: bool,
+    blocks: &mut FoldedBlocks,
+    w: &mut Writer,
+) -> wat::Parsed<()> {
+    let indentation = level.saturating_add(blocks.extra_indentation());
+    let instr_name = instr.name();
+
+    let open_block =
+        |block_type: BlockType, clause: Option<&str>, blocks: &mut FoldedBlocks, w: &mut Writer| {
+            start_folded_block(instr_name, block_type, indentation, w);
+            open_first_clause(clause, indentation, blocks, w);
+        };
+
+    // Clauses are written at the same level as the enclosing block's first clause
+    let next_clause = |clause: &str, w: &mut Writer| {
+        w.close_paren();
+        writeln!(w);
+        w.write_indentation(indentation.saturating_sub(1));
+        w.open_paren();
+        w.write_str(clause);
+    };
+
+    match instr {
+        Instr::Block(ty) | Instr::Loop(ty) if blocks.can_fold() => open_block(*ty, None, blocks, w),
+        Instr::If(ty) if blocks.can_fold() => open_block(*ty, Some("then"), blocks, w),
+        #[cfg(feature = "exceptions")]
+        Instr::Try(ty) if blocks.can_fold() => open_block(*ty, Some("do"), blocks, w),
+        #[cfg(feature = "exceptions")]
+        Instr::TryTable(..) if blocks.can_fold() => {
+            w.write_indentation(indentation);
+            w.open_paren();
+            instruction(instr, None, last, w)?;
+            blocks.push(false);
+        }
+        Instr::Else if blocks.is_innermost_folded() => next_clause("else", w),
+        #[cfg(feature = "exceptions")]
+        Instr::Catch(tag) if blocks.is_innermost_folded() => {
+            next_clause("catch ", w);
+            wat::write_id(*tag, w);
+        }
+        #[cfg(feature = "exceptions")]
+        Instr::CatchAll if blocks.is_innermost_folded() => next_clause("catch_all", w),
+        #[cfg(feature = "exceptions")]
+        Instr::Delegate(target) if blocks.is_innermost_folded() => {
+            next_clause("delegate", w);
+            write!(w, " {}", target.to_u32());
+            blocks.pop();
+            w.close_paren(); // delegate
+            w.close_paren(); // try
+        }
+        Instr::End if !last && blocks.is_innermost_folded() => {
+            if blocks.pop() == Some(true) {
+                w.close_paren();
+            }
+            w.close_paren();
+        }
+        _ => {
+            match instr {
+                Instr::Block(_) | Instr::Loop(_) | Instr::If(_) => blocks.push(false),
+                #[cfg(feature = "exceptions")]
+                Instr::Try(_) | Instr::TryTable(..) => blocks.push(false),
+                #[cfg(feature = "exceptions")]
+                Instr::Delegate(_) => {
+                    blocks.pop();
+                }
+                Instr::End if !last => {
+                    blocks.pop();
+                }
+                _ => (),
+            }
+
+            instruction(instr, Some(indentation), last, w)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// The maximum number of instructions that are kept before being written, while waiting for an
+/// instruction that consumes them as operands.
+const MAX_OPERANDS: usize = 16;
+
+/// The maximum number of folded instructions that are nested within each other.
+const MAX_OPERAND_DEPTH: u8 = 32;
+
+/// A sequence of instructions that is written as a single folded instruction, such as
+/// `(i32.add (local.get 0) (i32.const 1))`.
+#[derive(Clone, Copy, Default)]
+struct Operand {
+    start: u64,
+    end: u64,
+    pushes: u8,
+    depth: u8,
+}
+
+/// Stack of instructions that have not yet been written, since a later instruction might consume
+/// them as operands.
+///
+/// Only the offsets of each instruction are stored, so they are parsed again when written.
+#[derive(Default)]
+struct Operands {
+    entries: [Operand; MAX_OPERANDS],
+    len: usize,
+}
+
+impl Operands {
+    fn as_slice(&self) -> &[Operand] {
+        &self.entries[..self.len]
+    }
+
+    /// Adds the instruction from `start` to `end`, folding the operands it pops from the top of
+    /// the stack.
+    ///
+    /// Returns `false` if the instruction cannot be added until the existing entries are written,
+    /// such as when the operands it pops were not all pushed by the entries.
+    fn try_push(&mut self, start: u64, end: u64, effect: StackEffect) -> bool {
+        let pops = usize::from(effect.pops());
+        if pops > self.len || (pops == 0 && self.len == MAX_OPERANDS) {
+            return false;
+        }
+
+        let first = self.len - pops;
+        let operands = &self.entries[first..self.len];
+        if operands.iter().any(|operand| operand.pushes != 1) {
+            return false;
+        }
+
+        let depth = match operands.iter().map(|operand| operand.depth).max() {
+            Some(depth) if depth >= MAX_OPERAND_DEPTH => return false,
+            Some(depth) => depth + 1,
+            None => 0,
+        };
+
+        self.entries[first] = Operand {
+            start: operands.first().map_or(start, |operand| operand.start),
+            end,
+            pushes: effect.pushes(),
+            depth,
+        };
+        self.len = first + 1;
+        true
+    }
+
+    /// Adds the instruction from `start` to `end` without folding any operands, after the
+    /// existing entries were written.
+    fn push_unfolded(&mut self, start: u64, end: u64, effect: StackEffect) {
+        debug_assert_eq!(self.len, 0);
+        self.entries[0] = Operand {
+            start,
+            end,
+            pushes: effect.pushes(),
+            depth: 0,
+        };
+        self.len = 1;
+    }
+
+    /// Removes the entry at the top of the stack if it pushes a single value, which is used as the
+    /// condition of an **if** instruction.
+    fn pop_condition(&mut self) -> Option<Operand> {
+        let top = *self.as_slice().last()?;
+        if top.pushes == 1 {
+            self.len -= 1;
+            Some(top)
+        } else {
+            None
+        }
+    }
+
+    /// Writes each entry on its own line.
+    fn flush<I: Input>(
+        &mut self,
+        input: &I,
+        indentation: u32,
+        first: &mut bool,
+        w: &mut Writer,
+    ) -> wat::Parsed<()> {
+        for operand in self.as_slice() {
+            if !*first {
+                writeln!(w);
+            }
+
+            *first = false;
+            w.write_indentation(indentation);
+            write_operand(*operand, input, w)?;
+        }
+
+        self.len = 0;
+        Ok(())
+    }
+}
+
+/// Gets the [`StackEffect`] of instructions that can be written in the folded form along with
+/// their operands, which excludes any instructions that begin or end a block.
+fn operand_effect<I: Input>(instr: &Instr<'_, I>) -> Option<StackEffect> {
+    match instr {
+        #[cfg(feature = "exceptions")]
+        Instr::CatchAll => None,
+        _ => instr.stack_effect(),
+    }
+}
+
+/// Describes the next instruction to write in the folded form.
+enum Folding {
+    Operand(StackEffect),
+    /// An **if** instruction, whose condition can be written within the folded block.
+    If(BlockType),
+    Other,
+}
+
+impl Folding {
+    fn classify<I: Input>(instr: &Instr<'_, I>) -> Self {
+        match instr {
+            Instr::If(block_type) => Self::If(*block_type),
+            _ => operand_effect(instr).map_or(Self::Other, Self::Operand),
+        }
+    }
+}
+
+/// Writes the folded instruction, parsing the instructions within the `operand` again to
+/// determine its operands.
+fn write_operand<I: Input>(operand: Operand, input: &I, w: &mut Writer) -> wat::Parsed<()> {
+    let mut operands = Operands::default();
+    let mut expr = InstructionSequence::new(operand.start, input);
+    loop {
+        let start = expr.offset();
+        let effect = expr
+            .next(|instr| wat::Parsed::Ok(operand_effect(instr)))
+            .transpose()?
+            .flatten();
+
+        let end = expr.offset();
+        let effect = match effect.filter(|_| end < operand.end) {
+            Some(effect) => effect,
+            None => break,
+        };
+
+        // The same entries are built as when the operand was originally added
+        if !operands.try_push(start, end, effect) {
+            operands.len = 0;
+            operands.push_unfolded(start, end, effect);
+        }
+    }
+
+    // The last instruction is the one that consumes the operands
+    let root_start = operands
+        .as_slice()
+        .last()
+        .map_or(operand.start, |last| last.end);
+
+    w.open_paren();
+    InstructionSequence::new(root_start, input)
+        .next(|instr| instruction(instr, None, false, w))
+        .transpose()?;
+
+    for nested in operands.as_slice() {
+        w.write_char(' ');
+        write_operand(*nested, input, w)?;
+    }
+
+    w.close_paren();
+    Ok(())
+}
+
+/// Writes the instructions in the folded form, where instructions are written along with the
+/// instructions that produce their operands, and structured control instructions enclose the
+/// instructions within their blocks.
+fn expression_folded<I: Input>(
+    expr: &mut InstructionSequence<impl Offset, I>,
+    is_function: bool,
+    mut relocations: Option<&mut wat::RelocationAnnotations<impl Input>>,
+    w: &mut Writer,
+) -> wat::Parsed<()> {
+    let mut first = true;
+    let mut blocks = FoldedBlocks::default();
+    let mut operands = Operands::default();
+
+    // Relocations are written following each instruction, which requires instructions to be
+    // written in order
+    let fold_operands = relocations.is_none();
+
+    loop {
+        let start = expr.offset();
+        let indent = expr.nesting_level().saturating_sub(u32::from(!is_function));
+        let last = expr.nesting_level() <= 1;
+        let indentation = indent.saturating_add(blocks.extra_indentation());
+
+        let folding = if fold_operands {
+            expr.borrow_input()
+                .next(|instr| wat::Parsed::Ok(Folding::classify(instr)))
+                .transpose()?
+                .unwrap_or(Folding::Other)
+        } else {
+            Folding::Other
+        };
+
+        match folding {
+            Folding::Operand(effect) => {
+                expr.next(|_| wat::Parsed::Ok(())).transpose()?;
+                let end = expr.offset();
+                if !operands.try_push(start, end, effect) {
+                    operands.flush(expr.input(), indentation, &mut first, w)?;
+                    operands.push_unfolded(start, end, effect);
+                }
+                continue;
+            }
+            Folding::If(block_type) if blocks.can_fold() => {
+                if let Some(condition) = operands.pop_condition() {
+                    operands.flush(expr.input(), indentation, &mut first, w)?;
+                    if !first {
+                        writeln!(w);
+                    }
+
+                    first = false;
+                    start_folded_block("if", block_type, indentation, w);
+                    w.write_char(' ');
+                    write_operand(condition, expr.input(), w)?;
+                    open_first_clause(Some("then"), indentation, &mut blocks, w);
+                    expr.next(|_| wat::Parsed::Ok(())).transpose()?;
+                    continue;
+                }
+            }
+            _ => (),
+        }
+
+        operands.flush(expr.input(), indentation, &mut first, w)?;
+
+        let printer = |instr: &mut Instr<_>| {
+            // Folded blocks and clauses are closed on the same line as the last instruction
+            let closes_folded = blocks.is_innermost_folded()
+                && match instr {
+                    Instr::End => !last,
+                    Instr::Else => true,
+                    #[cfg(feature = "exceptions")]
+                    Instr::Catch(_) | Instr::CatchAll | Instr::Delegate(_) => true,
+                    _ => false,
+                };
+
+            if !first && !closes_folded {
+                writeln!(w);
+            }
+
+            first = false;
+            folded_instruction(instr, indent, last, &mut blocks, w)
+        };
+
+        match expr.next(printer) {
+            Some(Ok(())) => {
+                if let Some(relocations) = relocations.as_mut() {
+                    relocations.write(start, expr.offset(), w)?;
+                }
+            }
+            None => return Ok(()),
+            Some(Err(e)) => return Err(e),
+        }
+    }
+}
+
 pub(super) fn expression_indented(
     expr: &mut instruction_set::InstructionSequence<impl Offset, impl Input>,
     is_function: bool,
+    mut relocations: Option<&mut wat::RelocationAnnotations<impl Input>>,
     w: &mut Writer,
 ) -> wat::Parsed<()> {
+    if w.config().folded_instructions() {
+        return expression_folded(expr, is_function, relocations, w);
+    }
+
     let mut first = true;
 
     loop {
+        let start = expr.offset();
         let indent = expr.nesting_level().saturating_sub(u32::from(!is_function));
         let last = expr.nesting_level() <= 1;
         let printer = |instr: &mut Instr<_>| {
@@ -357,7 +910,11 @@ pub(super) fn expression_indented(
         };
 
         match expr.next(printer) {
-            Some(Ok(())) => continue,
+            Some(Ok(())) => {
+                if let Some(relocations) = relocations.as_mut() {
+                    relocations.write(start, expr.offset(), w)?;
+                }
+            }
             None => return Ok(()),
             Some(Err(e)) => return Err(e),
         }
@@ -1,16 +1,30 @@
-use crate::wat;
+use crate::{component::MemsComponent, input::Input, wat};
 
-impl<B: crate::input::Input> wat::Wat for crate::component::MemsComponent<B> {
-    fn write(self, w: &mut wat::Writer) -> crate::parser::Parsed<()> {
-        for result in self {
-            let mem = result?;
-            w.open_paren();
-            w.write_str("memory ");
-            wat::write_mem_type(&mem, w);
-            w.close_paren();
-            writeln!(w);
-        }
+/// Writes the memories in the *memory section*, where `first` is the index of the first defined
+/// memory, which is the number of imported memories.
+pub(super) fn write_mems<B: Input>(
+    mut mems: MemsComponent<B>,
+    first: u32,
+    w: &mut wat::Writer,
+) -> wat::Parsed<()> {
+    let max_items = crate::int::u32_to_usize(w.config().max_items());
+    for (i, result) in (first..).zip(mems.by_ref().take(max_items)) {
+        let mem = result?;
+        w.open_paren();
+        w.write_str("memory");
+        wat::write_declaration_id(crate::index::MemIdx::from(i), w);
+        w.write_char(' ');
+        wat::write_mem_type(&mem, w);
+        w.close_paren();
+        writeln!(w);
+    }
+
+    wat::write_omitted_items(mems.remaining_count(), "memories", w);
+    Ok(())
+}
 
-        Ok(())
+impl<B: Input> wat::Wat for MemsComponent<B> {
+    fn write(self, w: &mut wat::Writer) -> wat::Parsed<()> {
+        write_mems(self, 0, w)
     }
 }
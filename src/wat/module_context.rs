@@ -0,0 +1,168 @@
+use crate::{
+    component::{Tag, TypesComponent},
+    index::TypeIdx,
+    input::Input,
+    wat,
+};
+
+/// Information gathered from other sections of a module, used to resolve the signatures of
+/// definitions and the indices of those that are defined after imports.
+pub(super) struct ModuleContext<T: Input> {
+    types: Option<TypesComponent<T>>,
+    /// The *type section* positioned at each of its entries, so that signatures can be looked up
+    /// without reading the preceding types.
+    #[cfg(feature = "alloc")]
+    type_entries: alloc::vec::Vec<TypesComponent<T>>,
+    /// The number of tags that have been defined so far, including imports.
+    pub(super) tag_count: u32,
+    /// The number of imported functions, used to write the names of defined functions.
+    pub(super) function_count: u32,
+    /// The number of imported tables, used to write the indices of defined tables.
+    pub(super) table_count: u32,
+    /// The number of imported memories, used to write the indices of defined memories.
+    pub(super) memory_count: u32,
+    /// The number of imported globals, used to write the names of defined globals.
+    pub(super) global_count: u32,
+}
+
+/// Used when printing sections in isolation, where no other sections are available.
+pub(super) type NoContext = ModuleContext<&'static [u8]>;
+
+impl<T: Clone + Input> ModuleContext<T> {
+    pub(super) fn new() -> Self {
+        Self {
+            types: None,
+            #[cfg(feature = "alloc")]
+            type_entries: alloc::vec::Vec::new(),
+            tag_count: 0,
+            function_count: 0,
+            table_count: 0,
+            memory_count: 0,
+            global_count: 0,
+        }
+    }
+
+    /// Sets the *type section* used to write signatures.
+    ///
+    /// When the `alloc` feature is enabled, the position of each type is recorded so that
+    /// signatures can be looked up directly. Otherwise, the types preceding a signature are read
+    /// each time it is written.
+    pub(super) fn set_types(&mut self, types: TypesComponent<T>) {
+        #[cfg(feature = "alloc")]
+        {
+            // Types after one that could not be parsed are not recorded, and are written without
+            // their parameter and result types
+            self.type_entries.clear();
+            let mut entries = types.clone();
+            loop {
+                self.type_entries.push(entries.clone());
+                if !matches!(entries.parse(|_| Ok(()), |(), _| Ok(())), Ok(Some(()))) {
+                    self.type_entries.pop();
+                    break;
+                }
+            }
+        }
+
+        self.types = Some(types);
+    }
+
+    /// Gets the *type section* positioned at the type with the given `index`.
+    fn type_entry(&self, index: TypeIdx) -> wat::Parsed<Option<TypesComponent<T>>> {
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "alloc")] {
+                Ok(self.type_entries.get(crate::int::u32_to_usize(index.to_u32())).cloned())
+            } else {
+                let mut types = if let Some(types) = &self.types {
+                    types.clone()
+                } else {
+                    return Ok(None);
+                };
+
+                for _ in 0..index.to_u32() {
+                    if types.parse(|_| Ok(()), |(), _| Ok(()))?.is_none() {
+                        return Ok(None);
+                    }
+                }
+
+                Ok(Some(types))
+            }
+        }
+    }
+
+    /// Writes the type use of a tag, followed by its parameter types if the *type section* is
+    /// available.
+    #[inline]
+    pub(super) fn write_tag_type(
+        &self,
+        Tag::Exception(index): Tag,
+        w: &mut wat::Writer,
+    ) -> wat::Parsed<()> {
+        self.write_signature(index, w)?;
+        Ok(())
+    }
+
+    /// Writes a type use, followed by its parameter and result types if the *type section* is
+    /// available, returning the number of parameters if they were written.
+    ///
+    /// Parameters are written with the names of the locals of the [`Writer`](wat::Writer)'s
+    /// current function, if it has any.
+    pub(super) fn write_signature(
+        &self,
+        index: TypeIdx,
+        w: &mut wat::Writer,
+    ) -> wat::Parsed<Option<u32>> {
+        wat::write_type_use(index, w);
+
+        let mut types = if let Some(types) = self.type_entry(index)? {
+            types
+        } else {
+            return Ok(None);
+        };
+
+        types.parse(
+            |parameters| {
+                // Unnamed parameters are grouped together, while named ones are written separately
+                let mut count = 0u32;
+                let mut is_group_open = false;
+                for result in parameters {
+                    let parameter_type = result?;
+                    let is_named = w.has_local_name(count);
+                    if is_named || !is_group_open {
+                        if is_group_open {
+                            w.close_paren();
+                        }
+
+                        w.write_char(' ');
+                        w.open_paren();
+                        w.write_str("param");
+                        is_group_open = !is_named;
+                    }
+
+                    if is_named {
+                        w.write_local_name(count, " ");
+                        write!(w, " {parameter_type}");
+                        w.close_paren();
+                    } else {
+                        write!(w, " {parameter_type}");
+                    }
+
+                    count += 1;
+                }
+
+                if is_group_open {
+                    w.close_paren();
+                }
+
+                Ok((&mut *w, count))
+            },
+            |(w, count), results| {
+                if results.remaining_count() > 0 {
+                    w.write_str(" (result");
+                    wat::write_types(results, w)?;
+                    w.write_char(')');
+                }
+                Ok(count)
+            },
+        )
+    }
+}
@@ -1,51 +1,204 @@
 use crate::{
-    component::KnownSection,
-    input::BorrowInput,
+    component::{ImportKind, KnownSection},
+    custom::{
+        linking::{LinkingSection, LinkingSubsection, SymbolTable},
+        reloc::RelocationSection,
+        CustomSection,
+    },
+    input::{BorrowInput, Input, Window},
+    sections::{id as section_id, SectionSequence},
+    trace,
     wat::{self, Wat},
 };
 
-impl<B: crate::input::Input> Wat for crate::sections::DisplayModule<'_, B> {
+/// Looks for the symbol table in the `linking` section.
+///
+/// Errors are ignored, since custom sections are not required to be well-formed.
+fn find_symbol_table<B: Input>(
+    sections: &SectionSequence<B>,
+) -> Option<SymbolTable<Window<Window<&B>>>> {
+    for result in sections.borrow_input() {
+        let custom = match result.map(CustomSection::try_from_section) {
+            Ok(Ok(Ok(custom))) => custom,
+            _ => continue,
+        };
+
+        if let Ok(Ok(linking)) = LinkingSection::interpret(custom) {
+            for subsection in linking.into_subsections() {
+                if let Ok(Ok(Ok(LinkingSubsection::SymbolTable(symbols)))) =
+                    subsection.map(LinkingSubsection::interpret)
+                {
+                    return Some(symbols);
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Looks for the relocation section that applies to the *code section*.
+///
+/// Like with [`find_symbol_table`], errors are ignored.
+fn find_code_relocations<B: Input>(
+    sections: &SectionSequence<B>,
+) -> Option<RelocationSection<Window<&B>>> {
+    let mut code_index = None;
+    for (index, result) in (0u32..).zip(sections.borrow_input()) {
+        let section = result.ok()?;
+        if section.id() == section_id::CODE {
+            code_index = Some(index);
+            continue;
+        }
+
+        if let Ok(Ok(custom)) = CustomSection::try_from_section(section) {
+            if let Ok(Ok(relocations)) = RelocationSection::interpret(custom) {
+                if Some(relocations.section_index()) == code_index {
+                    return Some(relocations);
+                }
+            }
+        }
+    }
+
+    None
+}
+
+impl<B: Input> Wat for crate::sections::DisplayModule<'_, B> {
     fn write(self, w: &mut wat::Writer) -> wat::Parsed<()> {
+        let _span = trace::span!("write_module", offset = self.as_sections().offset());
+
+        if self.custom_sections() && w.config().names() {
+            w.names = wat::NameTable::read(self.as_sections());
+        }
+
         w.open_paren();
         w.write_str("module");
+        w.write_module_name();
 
+        let function_bodies = self.function_bodies();
         let mut function_types = None;
+        let mut context = wat::ModuleContext::new();
+
+        let mut code_relocations = Some(self)
+            .filter(|module| module.custom_sections() && module.relocation_annotations())
+            .and_then(|module| find_code_relocations(module.as_sections()));
+
+        for (index, result) in self.as_sections().borrow_input().enumerate() {
+            let section = result?;
+            let included = self.includes_section(index, section.id());
 
-        for result in self.as_sections().borrow_input() {
-            writeln!(w);
-            match KnownSection::interpret(result?) {
+            // Sections that are not written are still needed to write other sections
+            let is_context = matches!(
+                section.id(),
+                section_id::TYPE | section_id::IMPORT | section_id::FUNC
+            );
+
+            if !included && !is_context {
+                continue;
+            } else if included {
+                writeln!(w);
+            }
+
+            let contents_base = section.contents().base();
+            let _span = trace::span!(
+                "write_section",
+                id = section.id(),
+                offset = contents_base,
+                length = section.length(),
+                included
+            );
+
+            match KnownSection::interpret(section) {
                 Ok(known) => match known? {
-                    KnownSection::Type(types) => Wat::write(types, w)?,
-                    KnownSection::Import(imports) => Wat::write(imports, w)?,
+                    KnownSection::Type(types) => {
+                        context.set_types(types);
+                        if included {
+                            Wat::write(types, w)?
+                        }
+                    }
+                    KnownSection::Import(imports) if included => wat::imports_text::write_imports(
+                        imports,
+                        &mut context,
+                        self.inline_imports(),
+                        w,
+                    )?,
+                    KnownSection::Import(imports) => {
+                        for result in imports {
+                            match result?.kind() {
+                                ImportKind::Function(_) => context.function_count += 1,
+                                ImportKind::Table(_) => context.table_count += 1,
+                                ImportKind::Memory(_) => context.memory_count += 1,
+                                ImportKind::Global(_) => context.global_count += 1,
+                                ImportKind::Tag(_) => context.tag_count += 1,
+                            }
+                        }
+                    }
                     KnownSection::Function(functions) => {
-                        write!(
-                            w,
-                            ";; function section count = {}",
-                            functions.remaining_count()
-                        );
+                        if included && w.config().comments() {
+                            write!(
+                                w,
+                                ";; function section count = {}",
+                                functions.remaining_count()
+                            );
+                        }
                         function_types = Some(functions);
                     }
-                    KnownSection::Table(tables) => Wat::write(tables, w)?,
-                    KnownSection::Memory(mems) => Wat::write(mems, w)?,
-                    KnownSection::Global(globals) => Wat::write(globals, w)?,
-                    KnownSection::Export(exports) => Wat::write(exports, w)?,
+                    KnownSection::Table(tables) => {
+                        wat::tables_text::write_tables(tables, context.table_count, w)?
+                    }
+                    KnownSection::Memory(mems) => {
+                        wat::mems_text::write_mems(mems, context.memory_count, w)?
+                    }
+                    KnownSection::Global(globals) => {
+                        wat::globals_text::write_globals(globals, context.global_count, w)?
+                    }
+                    KnownSection::Export(exports) => wat::exports_text::write_exports(exports, w)?,
                     KnownSection::Start(start) => {
                         w.write_str("(start ");
-                        wat::write_index(false, start, w);
+                        wat::write_id(start, w);
                         w.write_char(')');
                     }
                     KnownSection::Element(elems) => Wat::write(elems, w)?,
                     KnownSection::Code(code) => {
-                        if let Some(types) = function_types.take() {
-                            Wat::write(crate::component::FuncsComponent::new(types, code)?, w)?;
-                        } else {
+                        if let Some(types) = function_types.take().filter(|_| function_bodies) {
+                            let mut relocations = code_relocations.take().map(|relocations| {
+                                wat::RelocationAnnotations::new(
+                                    relocations,
+                                    find_symbol_table(self.as_sections()),
+                                    contents_base,
+                                )
+                            });
+
+                            wat::funcs_text::write_funcs(
+                                crate::component::FuncsComponent::new(types, code)?,
+                                &context,
+                                context.function_count,
+                                self.function_range(),
+                                relocations.as_mut(),
+                                w,
+                            )?;
+                        } else if w.config().comments() {
                             write!(w, ";; code section count = {}", code.remaining_count());
                         }
                     }
                     KnownSection::Data(data) => Wat::write(data, w)?,
-                    KnownSection::DataCount(count) => write!(w, ";; data count = {count}"),
-                    KnownSection::Tag(tags) => Wat::write(tags, w)?,
+                    KnownSection::DataCount(count) => {
+                        if w.config().comments() {
+                            write!(w, ";; data count = {count}");
+                        }
+                    }
+                    KnownSection::Tag(tags) => wat::tags_text::write_tags(tags, &mut context, w)?,
                 },
+                Err(section) if section.id() == section_id::CUSTOM && !self.custom_sections() => {
+                    let contents = section.contents();
+                    write!(
+                        w,
+                        "(; CUSTOM @ {:#X} to {:#X} ;)",
+                        contents.base(),
+                        contents.base() + contents.length() - 1,
+                    );
+                    writeln!(w);
+                }
                 Err(section) => {
                     let id = section.id();
                     let contents = section.into_contents();
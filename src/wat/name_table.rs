@@ -0,0 +1,308 @@
+use crate::{
+    index,
+    input::{BorrowInput as _, Input},
+    parser::name::Name,
+    sections::SectionSequence,
+    wat,
+};
+
+/// An index space containing definitions that can be given names by the *name section*.
+#[derive(Clone, Copy)]
+pub(super) enum Space {
+    Function,
+    Table,
+    Memory,
+    Global,
+    Type,
+    Tag,
+    Element,
+    Data,
+}
+
+const SPACE_COUNT: usize = 8;
+
+/// Trait for indices of definitions that can be referred to by their names.
+pub(super) trait Named: index::Index {
+    const SPACE: Space;
+}
+
+macro_rules! named {
+    ($($index:ident => $space:ident,)*) => {$(
+        impl Named for index::$index {
+            const SPACE: Space = Space::$space;
+        }
+    )*};
+}
+
+named! {
+    FuncIdx => Function,
+    TableIdx => Table,
+    MemIdx => Memory,
+    GlobalIdx => Global,
+    TypeIdx => Type,
+    TagIdx => Tag,
+    ElemIdx => Element,
+    DataIdx => Data,
+}
+
+fn is_id_char(c: char) -> bool {
+    matches!(
+        c,
+        '0'..='9'
+            | 'A'..='Z'
+            | 'a'..='z'
+            | '!'
+            | '#'
+            | '$'
+            | '%'
+            | '&'
+            | '\''
+            | '*'
+            | '+'
+            | '-'
+            | '.'
+            | '/'
+            | ':'
+            | '<'
+            | '='
+            | '>'
+            | '?'
+            | '@'
+            | '\\'
+            | '^'
+            | '_'
+            | '`'
+            | '|'
+            | '~'
+    )
+}
+
+/// Writes the [identifier](https://webassembly.github.io/spec/core/text/values.html#text-id) for
+/// the given `name`, returning `false` if the name cannot be written as one.
+pub(super) fn write_name_id<I: Input>(name: &Name<I>, w: &mut wat::Writer) -> bool {
+    let is_valid = name.length() > 0
+        && name
+            .borrow_input()
+            .chars()
+            .all(|result| matches!(result, Ok(c) if is_id_char(c)));
+
+    if is_valid {
+        write!(w, "${name}");
+    }
+
+    is_valid
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "alloc")] {
+        use crate::{
+            custom::{name::NameSubsection, CustomSection, KnownCustomSection},
+            custom::name::NameMap,
+        };
+        use alloc::{
+            collections::{BTreeMap, BTreeSet},
+            string::String,
+            vec::Vec,
+        };
+
+        /// Returns `true` if the `name` can be written as an
+        /// [identifier](https://webassembly.github.io/spec/core/text/values.html#text-id).
+        fn is_id(name: &str) -> bool {
+            !name.is_empty() && name.chars().all(is_id_char)
+        }
+
+        /// The names of the definitions in a module, read from its *name section*.
+        ///
+        /// Only names that can be written as identifiers are stored, and duplicate names within an
+        /// index space are given suffixes so that each identifier is unique.
+        #[derive(Default)]
+        pub(super) struct NameTable {
+            module: Option<String>,
+            spaces: [BTreeMap<u32, String>; SPACE_COUNT],
+            /// The names of the locals of each function, keyed by the function and local indices.
+            locals: BTreeMap<(u32, u32), String>,
+        }
+
+        fn read_names<N: index::Index, I: Input>(
+            names: &mut NameMap<N, impl crate::parser::Offset, I>,
+            mut insert: impl FnMut(u32, String),
+        ) {
+            // Errors are ignored, since the name section is not required to be well-formed
+            while let Ok(Some(name_assoc)) = names.parse() {
+                match (*name_assoc.name()).try_into_string() {
+                    Ok(name) if is_id(&name) => insert(name_assoc.index().into(), name),
+                    _ => (),
+                }
+            }
+        }
+
+        /// Appends a suffix to each name that was already used by a preceding name, such as `$f_1`
+        /// for the second function named `$f`, so that each identifier refers to one definition.
+        fn deduplicate<'a>(names: impl IntoIterator<Item = &'a mut String>) {
+            let names = names.into_iter().collect::<Vec<_>>();
+            let mut used = names
+                .iter()
+                .map(|name| String::clone(name))
+                .collect::<BTreeSet<_>>();
+            let mut seen = BTreeSet::new();
+
+            for name in names {
+                if seen.insert(name.clone()) {
+                    continue;
+                }
+
+                let unique = (1u32..)
+                    .map(|suffix| alloc::format!("{name}_{suffix}"))
+                    .find(|candidate| !used.contains(candidate))
+                    .unwrap_or_default();
+
+                used.insert(unique.clone());
+                seen.insert(unique.clone());
+                *name = unique;
+            }
+        }
+
+        impl NameTable {
+            pub(super) fn new() -> Self {
+                Self::default()
+            }
+
+            fn insert_into(&mut self, space: Space) -> impl FnMut(u32, String) + '_ {
+                let names = &mut self.spaces[space as usize];
+                move |index, name| {
+                    names.insert(index, name);
+                }
+            }
+
+            /// Reads the names in the first *name section* of a module.
+            ///
+            /// Like with other custom sections, errors are ignored.
+            pub(super) fn read<B: Input>(sections: &SectionSequence<B>) -> Self {
+                let mut table = Self::default();
+                for result in sections.borrow_input() {
+                    let custom = match result.map(CustomSection::try_from_section) {
+                        Ok(Ok(Ok(custom))) => custom,
+                        _ => continue,
+                    };
+
+                    let names = match KnownCustomSection::interpret(custom) {
+                        Ok(KnownCustomSection::Name(names)) => names,
+                        _ => continue,
+                    };
+
+                    for subsection in names {
+                        match subsection {
+                            Ok(Ok(NameSubsection::ModuleName(name))) => {
+                                table.module = name.try_into_string().ok().filter(|name| is_id(name));
+                            }
+                            Ok(Ok(NameSubsection::FunctionName(mut names))) => {
+                                read_names(&mut names, table.insert_into(Space::Function))
+                            }
+                            Ok(Ok(NameSubsection::LocalName(mut locals))) => {
+                                let table_locals = &mut table.locals;
+                                while let Ok(Some(())) = locals.parse(|function, names| {
+                                    let function = function.to_u32();
+                                    read_names(names, |local, name| {
+                                        table_locals.insert((function, local), name);
+                                    });
+                                    Ok(())
+                                }) {}
+                            }
+                            Ok(Ok(NameSubsection::TypeName(mut names))) => {
+                                read_names(&mut names, table.insert_into(Space::Type))
+                            }
+                            Ok(Ok(NameSubsection::TableName(mut names))) => {
+                                read_names(&mut names, table.insert_into(Space::Table))
+                            }
+                            Ok(Ok(NameSubsection::MemoryName(mut names))) => {
+                                read_names(&mut names, table.insert_into(Space::Memory))
+                            }
+                            Ok(Ok(NameSubsection::GlobalName(mut names))) => {
+                                read_names(&mut names, table.insert_into(Space::Global))
+                            }
+                            Ok(Ok(NameSubsection::ElementName(mut names))) => {
+                                read_names(&mut names, table.insert_into(Space::Element))
+                            }
+                            Ok(Ok(NameSubsection::DataName(mut names))) => {
+                                read_names(&mut names, table.insert_into(Space::Data))
+                            }
+                            Ok(Ok(NameSubsection::TagName(mut names))) => {
+                                read_names(&mut names, table.insert_into(Space::Tag))
+                            }
+                            _ => (),
+                        }
+                    }
+
+                    break;
+                }
+
+                for names in table.spaces.iter_mut() {
+                    deduplicate(names.values_mut());
+                }
+
+                let mut locals = table.locals.iter_mut().peekable();
+                while let Some(((function, _), name)) = locals.next() {
+                    let function = *function;
+                    let mut names = alloc::vec![name];
+                    while let Some((_, name)) =
+                        locals.next_if(|((next, _), _)| *next == function)
+                    {
+                        names.push(name);
+                    }
+
+                    deduplicate(names);
+                }
+
+                table
+            }
+
+            /// Gets the name of the module.
+            pub(super) fn module(&self) -> Option<&str> {
+                self.module.as_deref()
+            }
+
+            /// Gets the name of the definition at the given `index` in the index `space`.
+            pub(super) fn get(&self, space: Space, index: u32) -> Option<&str> {
+                self.spaces[space as usize].get(&index).map(String::as_str)
+            }
+
+            /// Gets the name of the `local` in the given `function`.
+            pub(super) fn local(&self, function: u32, local: u32) -> Option<&str> {
+                self.locals.get(&(function, local)).map(String::as_str)
+            }
+        }
+    } else {
+        /// Names cannot be stored without allocating, so no names are written.
+        pub(super) struct NameTable;
+
+        impl NameTable {
+            #[inline]
+            pub(super) fn new() -> Self {
+                Self
+            }
+
+            #[inline]
+            pub(super) fn read<B: Input>(sections: &SectionSequence<B>) -> Self {
+                let _ = sections;
+                Self
+            }
+
+            #[inline]
+            pub(super) fn module(&self) -> Option<&str> {
+                None
+            }
+
+            #[inline]
+            pub(super) fn get(&self, space: Space, index: u32) -> Option<&str> {
+                let _ = (space, index, SPACE_COUNT);
+                None
+            }
+
+            #[inline]
+            pub(super) fn local(&self, function: u32, local: u32) -> Option<&str> {
+                let _ = (function, local);
+                None
+            }
+        }
+    }
+}
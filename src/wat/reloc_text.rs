@@ -0,0 +1,99 @@
+use crate::{
+    custom::{
+        linking::SymbolTable,
+        reloc::{RelocationEntry, RelocationSection, RelocationType},
+    },
+    input::{BorrowInput as _, Input, Window},
+    wat,
+};
+
+/// Writes comments describing the relocations applied to instructions in the *code section*.
+pub(super) struct RelocationAnnotations<I: Input> {
+    relocations: RelocationSection<I>,
+    /// The symbol table in the `linking` section, used to write the names of the symbols that
+    /// relocation entries refer to.
+    symbols: Option<SymbolTable<Window<I>>>,
+    /// The offset to the start of the contents of the *code section*, which the offsets of the
+    /// relocation entries are relative to.
+    base: u64,
+    next: Option<RelocationEntry>,
+}
+
+/// Used when printing function bodies without any relocation information.
+pub(super) type NoRelocations = RelocationAnnotations<&'static [u8]>;
+
+impl<I: Input> RelocationAnnotations<I> {
+    pub(super) fn new(
+        relocations: RelocationSection<I>,
+        symbols: Option<SymbolTable<Window<I>>>,
+        base: u64,
+    ) -> Self {
+        Self {
+            relocations,
+            symbols,
+            base,
+            next: None,
+        }
+    }
+
+    /// Writes the name of the symbol at the given `index`, or the `index` if the symbol does not
+    /// have a name that can be written as an identifier.
+    ///
+    /// Errors in the symbol table are ignored, since it is not needed to write the instructions.
+    fn write_symbol(&self, index: u32, w: &mut wat::Writer) {
+        if let Some(mut symbols) = self.symbols.as_ref().map(|symbols| symbols.borrow_input()) {
+            for _ in 0..index {
+                if !matches!(symbols.parse(), Ok(Some(_))) {
+                    break;
+                }
+            }
+
+            if let Ok(Some(symbol)) = symbols.parse() {
+                if let Some(name) = symbol.name() {
+                    if wat::name_table::write_name_id(name, w) {
+                        return;
+                    }
+                }
+            }
+        }
+
+        write!(w, "sym={index}");
+    }
+
+    /// Writes the relocations located from `start` up to `end`.
+    ///
+    /// Relocation entries are expected to be sorted by their offset, any entries located before
+    /// `start` are skipped.
+    pub(super) fn write(&mut self, start: u64, end: u64, w: &mut wat::Writer) -> wat::Parsed<()> {
+        loop {
+            let entry = match self.next.take() {
+                Some(entry) => entry,
+                None => match self.relocations.parse()? {
+                    Some(entry) => entry,
+                    None => return Ok(()),
+                },
+            };
+
+            let location = self.base + u64::from(entry.offset());
+            if location >= end {
+                self.next = Some(entry);
+                return Ok(());
+            } else if location >= start {
+                write!(w, " (; reloc {} ", entry.relocation_type());
+
+                // The index of a type relocation refers to the type section, not a symbol
+                if entry.relocation_type() == RelocationType::R_WASM_TYPE_INDEX_LEB {
+                    write!(w, "type={}", entry.index());
+                } else {
+                    self.write_symbol(entry.index(), w);
+                }
+
+                if entry.addend() != 0 {
+                    write!(w, "{:+}", entry.addend());
+                }
+
+                w.write_str(" ;)");
+            }
+        }
+    }
+}
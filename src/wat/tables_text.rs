@@ -1,18 +1,30 @@
-use crate::wat;
+use crate::{component::TablesComponent, input::Input, wat};
 
-impl<B: crate::input::Input> wat::Wat for crate::component::TablesComponent<B> {
-    fn write(self, w: &mut wat::Writer) -> crate::parser::Parsed<()> {
-        for (i, result) in (0u32..).flat_map(crate::index::MemIdx::try_from).zip(self) {
-            let table = result?;
-            w.open_paren();
-            w.write_str("table ");
-            wat::write_index(true, i, w);
-            w.write_char(' ');
-            wat::write_table_type(&table, w);
-            w.close_paren();
-            writeln!(w);
-        }
+/// Writes the tables in the *table section*, where `first` is the index of the first defined
+/// table, which is the number of imported tables.
+pub(super) fn write_tables<B: Input>(
+    mut tables: TablesComponent<B>,
+    first: u32,
+    w: &mut wat::Writer,
+) -> wat::Parsed<()> {
+    let max_items = crate::int::u32_to_usize(w.config().max_items());
+    for (i, result) in (first..).zip(tables.by_ref().take(max_items)) {
+        let table = result?;
+        w.open_paren();
+        w.write_str("table");
+        wat::write_declaration_id(crate::index::TableIdx::from(i), w);
+        w.write_char(' ');
+        wat::write_table_type(&table, w);
+        w.close_paren();
+        writeln!(w);
+    }
+
+    wat::write_omitted_items(tables.remaining_count(), "tables", w);
+    Ok(())
+}
 
-        Ok(())
+impl<B: Input> wat::Wat for TablesComponent<B> {
+    fn write(self, w: &mut wat::Writer) -> wat::Parsed<()> {
+        write_tables(self, 0, w)
     }
 }
@@ -1,19 +1,39 @@
-use crate::{component::Tag, input::BorrowInput as _, wat};
+use crate::{
+    component::TagsComponent,
+    index::TagIdx,
+    input::{BorrowInput as _, Input},
+    wat::{self, ModuleContext},
+};
 
-pub(super) fn write_tag(Tag::Exception(tag): Tag, w: &mut wat::Writer) {
-    wat::write_type_use(tag, w);
+pub(super) fn write_tags<B, T>(
+    tags: TagsComponent<B>,
+    context: &mut ModuleContext<T>,
+    w: &mut wat::Writer,
+) -> wat::Parsed<()>
+where
+    B: Input,
+    T: Clone + Input,
+{
+    let mut tags = tags.borrow_input();
+    let max_items = crate::int::u32_to_usize(w.config().max_items());
+    for result in tags.by_ref().take(max_items) {
+        let tag = result?;
+        w.open_paren();
+        w.write_str("tag");
+        wat::write_declaration_id(TagIdx::from(context.tag_count), w);
+        w.write_char(' ');
+        context.write_tag_type(tag, w)?;
+        w.close_paren();
+        writeln!(w);
+        context.tag_count += 1;
+    }
+
+    wat::write_omitted_items(tags.remaining_count(), "tags", w);
+    Ok(())
 }
 
-impl<B: crate::input::Input> wat::Wat for crate::component::TagsComponent<B> {
+impl<B: Input> wat::Wat for TagsComponent<B> {
     fn write(self, w: &mut wat::Writer) -> wat::Parsed<()> {
-        for result in self.borrow_input() {
-            w.open_paren();
-            w.write_str("tag ");
-            write_tag(result?, w);
-            w.close_paren();
-            writeln!(w);
-        }
-
-        Ok(())
+        write_tags(self, &mut wat::NoContext::new(), w)
     }
 }
@@ -0,0 +1,61 @@
+//! Parses modules written in the
+//! [WebAssembly text format](https://webassembly.github.io/spec/core/text/index.html).
+
+use crate::sections::SectionSequence;
+use alloc::{boxed::Box, vec::Vec};
+
+mod cursor;
+mod error;
+mod instructions;
+mod lexer;
+mod module;
+mod numbers;
+
+pub use error::ParseError;
+
+pub(crate) type Result<T> = core::result::Result<T, ParseError>;
+
+/// Parses a module in the
+/// [WebAssembly text format](https://webassembly.github.io/spec/core/text/index.html), returning
+/// its [binary encoding](https://webassembly.github.io/spec/core/binary/index.html).
+///
+/// The `text` can either be a single `(module ...)` or a sequence of module fields. Identifiers
+/// for functions, locals, and tags are written to a `name` custom section.
+///
+/// # Example
+///
+/// ```
+/// let wasm = wasmiter::text::parse_str(r#"(module
+///     (func $add (export "add") (param i32 i32) (result i32)
+///         (i32.add (local.get 0) (local.get 1))))"#)?;
+///
+/// assert!(wasm.starts_with(b"\0asm"));
+/// # Ok::<(), wasmiter::text::ParseError>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the `text` is not a valid module, or refers to an unknown identifier.
+pub fn parse_str(text: &str) -> Result<Vec<u8>> {
+    let items = lexer::parse(text).map_err(|e| e.locate(text))?;
+    module::encode(&items, text.len()).map_err(|e| e.locate(text))
+}
+
+/// Parses a module in the
+/// [WebAssembly text format](https://webassembly.github.io/spec/core/text/index.html), returning
+/// a [`SectionSequence`] over its binary encoding.
+///
+/// # Example
+///
+/// ```
+/// let module = wasmiter::text::parse_module("(module (memory 1))")?;
+/// assert_eq!(module.display_module().to_string().lines().count(), 3);
+/// # Ok::<(), wasmiter::text::ParseError>(())
+/// ```
+///
+/// # Errors
+///
+/// See [`parse_str`] for more information.
+pub fn parse_module(text: &str) -> Result<SectionSequence<Box<[u8]>>> {
+    Ok(SectionSequence::new(8, parse_str(text)?.into_boxed_slice()))
+}
@@ -0,0 +1,229 @@
+use crate::types::{HeapType, RefType, ValType};
+use crate::wat::text_parser::{
+    lexer::{self, Atom, Item, List},
+    numbers, ParseError, Result,
+};
+use alloc::{format, string::String, vec::Vec};
+
+/// Reads the items within a [`List`].
+#[derive(Clone, Copy, Debug)]
+pub(super) struct Cursor<'t, 'a> {
+    items: &'t [Item<'a>],
+    /// Offset used for errors when there are no items remaining.
+    end: usize,
+}
+
+impl<'t, 'a> Cursor<'t, 'a> {
+    pub(super) fn new(items: &'t [Item<'a>], end: usize) -> Self {
+        Self { items, end }
+    }
+
+    /// Reads the items of the `list` that follow its keyword.
+    pub(super) fn after_keyword(list: &'t List<'a>) -> Self {
+        Self::new(list.items.get(1..).unwrap_or_default(), list.end)
+    }
+
+    pub(super) fn offset(&self) -> usize {
+        self.items.first().map_or(self.end, Item::offset)
+    }
+
+    pub(super) fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError::new(self.offset(), message)
+    }
+
+    #[inline]
+    pub(super) fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    #[inline]
+    pub(super) fn peek(&self) -> Option<&'t Item<'a>> {
+        self.items.first()
+    }
+
+    pub(super) fn next(&mut self) -> Option<&'t Item<'a>> {
+        let (first, remaining) = self.items.split_first()?;
+        self.items = remaining;
+        Some(first)
+    }
+
+    pub(super) fn peek_atom(&self) -> Option<Atom<'a>> {
+        match self.peek() {
+            Some(Item::Atom(atom)) => Some(*atom),
+            _ => None,
+        }
+    }
+
+    /// Reads the next item if it is an atom.
+    pub(super) fn atom(&mut self) -> Option<Atom<'a>> {
+        let atom = self.peek_atom()?;
+        self.next();
+        Some(atom)
+    }
+
+    pub(super) fn expect_atom(&mut self, expected: &str) -> Result<Atom<'a>> {
+        self.atom()
+            .ok_or_else(|| self.error(format!("expected {expected}")))
+    }
+
+    /// Reads the next item if it is the given `keyword`.
+    pub(super) fn keyword(&mut self, keyword: &str) -> bool {
+        let matches = self.peek_atom().map_or(false, |atom| atom.text == keyword);
+        if matches {
+            self.next();
+        }
+        matches
+    }
+
+    /// Reads the next item if it is an identifier, such as `$main`.
+    pub(super) fn id(&mut self) -> Option<Atom<'a>> {
+        match self.peek_atom() {
+            Some(atom) if atom.is_id() => {
+                self.next();
+                Some(atom)
+            }
+            _ => None,
+        }
+    }
+
+    /// Reads the next item if it is an unsigned integer or an identifier.
+    pub(super) fn index(&mut self) -> Option<Atom<'a>> {
+        match self.peek_atom() {
+            Some(atom) if atom.is_id() || numbers::u32(atom.text).is_some() => {
+                self.next();
+                Some(atom)
+            }
+            _ => None,
+        }
+    }
+
+    pub(super) fn expect_index(&mut self) -> Result<Atom<'a>> {
+        self.index()
+            .ok_or_else(|| self.error("expected an index or identifier"))
+    }
+
+    /// Gets the keyword of the next item, if it is a list.
+    pub(super) fn peek_list(&self) -> Option<(&'a str, &'t List<'a>)> {
+        match self.peek() {
+            Some(Item::List(list)) => Some((list.keyword()?, list)),
+            _ => None,
+        }
+    }
+
+    /// Reads the next item if it is a list starting with the given `keyword`, returning the
+    /// items following the `keyword`.
+    pub(super) fn list(&mut self, keyword: &str) -> Option<Cursor<'t, 'a>> {
+        match self.peek_list() {
+            Some((actual, list)) if actual == keyword => {
+                self.next();
+                Some(Cursor::after_keyword(list))
+            }
+            _ => None,
+        }
+    }
+
+    pub(super) fn expect_list(&mut self, keyword: &str) -> Result<Cursor<'t, 'a>> {
+        self.list(keyword)
+            .ok_or_else(|| self.error(format!("expected `({keyword} ...)`")))
+    }
+
+    /// Reads the next item if it is a string, decoding any escape sequences.
+    pub(super) fn string(&mut self) -> Option<Result<Vec<u8>>> {
+        match self.peek() {
+            Some(Item::String(string)) => {
+                self.next();
+                Some(lexer::string_bytes(*string))
+            }
+            _ => None,
+        }
+    }
+
+    /// Reads a string that must be valid UTF-8, such as an import or export name.
+    pub(super) fn name(&mut self) -> Result<String> {
+        let offset = self.offset();
+        let bytes = self
+            .string()
+            .unwrap_or_else(|| Err(self.error("expected a string")))?;
+        String::from_utf8(bytes).map_err(|_| ParseError::new(offset, "malformed UTF-8 encoding"))
+    }
+
+    pub(super) fn u64(&mut self) -> Result<u64> {
+        let atom = self.expect_atom("an integer")?;
+        numbers::u64(atom.text).ok_or_else(|| atom.error("expected a 64-bit unsigned integer"))
+    }
+
+    /// Reads a heap type, which is either `func`, `extern`, `exn`, or a type index.
+    ///
+    /// Type identifiers are not supported, since types may refer to types that are defined after
+    /// them.
+    pub(super) fn heap_type(&mut self) -> Result<HeapType> {
+        let atom = self.expect_atom("a heap type")?;
+        match atom.text {
+            "func" => Ok(HeapType::Func),
+            "extern" => Ok(HeapType::Extern),
+            "exn" => Ok(HeapType::Exn),
+            _ if atom.is_id() => {
+                Err(atom.error("type identifiers in heap types are not supported"))
+            }
+            _ => numbers::u32(atom.text)
+                .map(|index| HeapType::Index(index.into()))
+                .ok_or_else(|| atom.error("expected `func`, `extern`, `exn`, or a type index")),
+        }
+    }
+
+    /// Reads a `(ref null? heaptype)` if one is next.
+    fn typed_ref_type(&mut self) -> Option<Result<RefType>> {
+        let mut c = self.list("ref")?;
+        let nullable = c.keyword("null");
+        Some(c.heap_type().and_then(|heap_type| {
+            c.finish()?;
+            Ok(RefType::new(nullable, heap_type))
+        }))
+    }
+
+    pub(super) fn ref_type(&mut self) -> Result<RefType> {
+        if let Some(typed) = self.typed_ref_type() {
+            return typed;
+        }
+
+        match self.atom() {
+            Some(atom) if atom.text == "funcref" => Ok(RefType::Func),
+            Some(atom) if atom.text == "externref" => Ok(RefType::Extern),
+            Some(atom) if atom.text == "exnref" => Ok(RefType::Exn),
+            Some(atom) => Err(atom.error("expected a reference type")),
+            None => Err(self.error("expected a reference type")),
+        }
+    }
+
+    pub(super) fn val_type(&mut self) -> Result<ValType> {
+        if let Some(typed) = self.typed_ref_type() {
+            return typed.map(ValType::from);
+        }
+
+        let atom = self.expect_atom("a value type")?;
+        Ok(match atom.text {
+            "i32" => ValType::I32,
+            "i64" => ValType::I64,
+            "f32" => ValType::F32,
+            "f64" => ValType::F64,
+            "v128" => ValType::V128,
+            "funcref" => ValType::FuncRef,
+            "externref" => ValType::ExternRef,
+            "exnref" => ValType::ExnRef,
+            _ => return Err(atom.error(format!("unknown value type `{}`", atom.text))),
+        })
+    }
+
+    /// Returns an error if any items remain.
+    pub(super) fn finish(&self) -> Result<()> {
+        match self.peek() {
+            None => Ok(()),
+            Some(Item::List(list)) => Err(ParseError::new(
+                list.start,
+                format!("unexpected `({}`", list.keyword().unwrap_or_default()),
+            )),
+            Some(Item::Atom(atom)) => Err(atom.error(format!("unexpected `{}`", atom.text))),
+            Some(Item::String(string)) => Err(string.error("unexpected string")),
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use alloc::string::String;
+use core::fmt::{Display, Formatter};
+
+/// Describes why a module in the
+/// [WebAssembly text format](https://webassembly.github.io/spec/core/text/index.html) could not be
+/// parsed.
+///
+/// Returned by [`parse_str`](crate::text::parse_str) and
+/// [`parse_module`](crate::text::parse_module).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    message: String,
+    offset: usize,
+    line: usize,
+    column: usize,
+}
+
+impl ParseError {
+    pub(super) fn new(offset: usize, message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            offset,
+            line: 0,
+            column: 0,
+        }
+    }
+
+    /// Calculates the line and column of the error within the `text` that was parsed.
+    pub(super) fn locate(mut self, text: &str) -> Self {
+        let offset = self.offset.min(text.len());
+        let before = &text.as_bytes()[..offset];
+        let line_start = before
+            .iter()
+            .rposition(|b| *b == b'\n')
+            .map_or(0, |newline| newline + 1);
+
+        self.line = before.iter().filter(|b| **b == b'\n').count() + 1;
+        self.column = match text.get(line_start..offset) {
+            Some(line) => line.chars().count() + 1,
+            None => offset - line_start + 1,
+        };
+        self
+    }
+
+    /// Gets the offset, in bytes, from the start of the text to where the error occured.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Gets the line where the error occured, starting at `1`.
+    #[inline]
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Gets the column where the error occured, starting at `1`.
+    ///
+    /// Columns are counted in characters rather than bytes.
+    #[inline]
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Gets a message describing the error, without its location.
+    #[inline]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
@@ -0,0 +1,579 @@
+//! Encodes [instructions](https://webassembly.github.io/spec/core/text/instructions.html) written
+//! in the text format, in either their plain or folded forms.
+
+use crate::{
+    encode,
+    index::{MemIdx, TypeIdx},
+    instruction_set::{self, Align, InstructionOpcode, MemArg, Opcode},
+    types::BlockType,
+    wat::text_parser::{
+        cursor::Cursor,
+        lexer::{Atom, Item, List},
+        module::{Module, Space},
+        numbers, ParseError, Result,
+    },
+};
+use alloc::{format, vec::Vec};
+
+fn write_opcode(out: &mut Vec<u8>, opcode: InstructionOpcode) {
+    out.push(opcode.byte());
+    if let Some(sub_opcode) = opcode.sub_opcode() {
+        encode::u32(out, sub_opcode);
+    }
+}
+
+fn opcode(name: Atom<'_>) -> Result<InstructionOpcode> {
+    instruction_set::variant_named(name.text)
+        .and_then(InstructionOpcode::from_variant)
+        .ok_or_else(|| name.error(format!("unknown instruction `{}`", name.text)))
+}
+
+/// Returns `true` for instructions that have a [`MemArg`].
+fn has_memarg(name: &str) -> bool {
+    name.contains(".load") || name.contains(".store") || name.contains(".atomic.")
+}
+
+/// Gets the natural alignment of a memory instruction by decoding it, so that the alignment does
+/// not have to be specified separately for each instruction name.
+fn natural_alignment(opcode: InstructionOpcode, has_lane: bool) -> Option<Align> {
+    let mut probe = Vec::new();
+    write_opcode(&mut probe, opcode);
+    probe.extend_from_slice(&[0, 0]);
+    if has_lane {
+        probe.push(0);
+    }
+    probe.push(Opcode::End as u8);
+
+    let mut expression = instruction_set::parse_expression(0u64, probe.as_slice());
+    expression
+        .next(|instruction| crate::parser::Parsed::Ok(instruction.natural_alignment()))
+        .and_then(core::result::Result::ok)
+        .flatten()
+}
+
+fn lane(c: &mut Cursor<'_, '_>) -> Result<u8> {
+    let atom = c.expect_atom("a lane index")?;
+    numbers::u32(atom.text)
+        .and_then(|lane| u8::try_from(lane).ok())
+        .ok_or_else(|| atom.error("invalid lane index"))
+}
+
+/// Encodes the instructions of a function body or constant expression.
+pub(super) struct Body<'m, 'a> {
+    module: &'m mut Module<'a>,
+    locals: Space<'a>,
+    /// The labels of the blocks that are currently open, with the innermost block last.
+    labels: Vec<Option<&'a str>>,
+}
+
+impl<'m, 'a> Body<'m, 'a> {
+    pub(super) fn new(module: &'m mut Module<'a>, locals: Space<'a>) -> Self {
+        Self {
+            module,
+            locals,
+            labels: Vec::new(),
+        }
+    }
+
+    /// Encodes all of the remaining instructions, followed by an `end` instruction.
+    pub(super) fn expression(&mut self, c: &mut Cursor<'_, 'a>, out: &mut Vec<u8>) -> Result<()> {
+        self.instructions(c, out)?;
+        if !self.labels.is_empty() {
+            return Err(c.error("unclosed block"));
+        }
+
+        out.push(Opcode::End as u8);
+        Ok(())
+    }
+
+    /// Encodes a single folded instruction, followed by an `end` instruction.
+    pub(super) fn folded_expression(&mut self, list: &List<'a>, out: &mut Vec<u8>) -> Result<()> {
+        self.folded(list, out)?;
+        out.push(Opcode::End as u8);
+        Ok(())
+    }
+
+    fn instructions(&mut self, c: &mut Cursor<'_, 'a>, out: &mut Vec<u8>) -> Result<()> {
+        while let Some(item) = c.peek() {
+            match item {
+                Item::List(list) => {
+                    c.next();
+                    self.folded(list, out)?;
+                }
+                Item::Atom(atom) if !atom.is_id() => {
+                    c.next();
+                    self.plain(*atom, c, out)?;
+                }
+                _ => return Err(c.error("expected an instruction")),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn block_type(&mut self, c: &mut Cursor<'_, 'a>) -> Result<BlockType> {
+        let type_use = self.module.type_use(c)?;
+        Ok(if type_use.is_inline_block_type() {
+            match type_use.signature().results().first() {
+                Some(result) => BlockType::Inline(*result),
+                None => BlockType::Empty,
+            }
+        } else {
+            BlockType::Index(TypeIdx::from(self.module.type_index(&type_use)))
+        })
+    }
+
+    /// Writes the start of a `block`, `loop`, `if`, `try`, or `try_table` instruction, opening a
+    /// new label.
+    fn start_block(
+        &mut self,
+        name: Atom<'a>,
+        c: &mut Cursor<'_, 'a>,
+        out: &mut Vec<u8>,
+    ) -> Result<()> {
+        let opcode = opcode(name)?;
+        let label = c.id();
+        let block_type = self.block_type(c)?;
+        write_opcode(out, opcode);
+        encode::block_type(out, block_type);
+        if name.text == "try_table" {
+            // Labels in catch clauses do not refer to the try_table block itself
+            self.catch_clauses(c, out)?;
+        }
+
+        self.labels.push(label.map(|label| label.text));
+        Ok(())
+    }
+
+    /// Encodes the `(catch x l)`, `(catch_ref x l)`, `(catch_all l)`, and `(catch_all_ref l)`
+    /// clauses of a `try_table` instruction.
+    fn catch_clauses(&mut self, c: &mut Cursor<'_, 'a>, out: &mut Vec<u8>) -> Result<()> {
+        let mut count = 0u32;
+        let mut clauses = Vec::new();
+        loop {
+            let (kind, mut clause) = if let Some(clause) = c.list("catch") {
+                (0, clause)
+            } else if let Some(clause) = c.list("catch_ref") {
+                (1, clause)
+            } else if let Some(clause) = c.list("catch_all") {
+                (2, clause)
+            } else if let Some(clause) = c.list("catch_all_ref") {
+                (3, clause)
+            } else {
+                break;
+            };
+
+            clauses.push(kind);
+            if kind < 2 {
+                encode::u32(&mut clauses, self.module.tags.expect_index(&mut clause)?);
+            }
+            encode::u32(&mut clauses, self.label(&mut clause)?);
+            clause.finish()?;
+            count = count.saturating_add(1);
+        }
+
+        encode::u32(out, count);
+        out.extend_from_slice(&clauses);
+        Ok(())
+    }
+
+    /// Checks that an optional label following an `else` or `end` matches the current block.
+    fn check_label(&self, c: &mut Cursor<'_, 'a>) -> Result<()> {
+        match c.id() {
+            Some(id) if self.labels.last() != Some(&Some(id.text)) => {
+                Err(id.error(format!("mismatching label {}", id.text)))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn label(&self, c: &mut Cursor<'_, 'a>) -> Result<u32> {
+        let label = c.expect_index()?;
+        if label.is_id() {
+            self.labels
+                .iter()
+                .rev()
+                .position(|name| *name == Some(label.text))
+                .map(|depth| u32::try_from(depth).unwrap_or(u32::MAX))
+                .ok_or_else(|| label.error(format!("unknown label {}", label.text)))
+        } else {
+            numbers::u32(label.text).ok_or_else(|| label.error("expected a label"))
+        }
+    }
+
+    fn end_block(&mut self, name: Atom<'a>) -> Result<()> {
+        self.labels
+            .pop()
+            .map(|_| ())
+            .ok_or_else(|| name.error(format!("unexpected `{}`", name.text)))
+    }
+
+    /// Encodes an instruction in its plain form, reading its immediates from the cursor.
+    fn plain(&mut self, name: Atom<'a>, c: &mut Cursor<'_, 'a>, out: &mut Vec<u8>) -> Result<()> {
+        match name.text {
+            "block" | "loop" | "if" | "try" | "try_table" => self.start_block(name, c, out),
+            "else" => {
+                if self.labels.is_empty() {
+                    return Err(name.error("unexpected `else`"));
+                }
+
+                self.check_label(c)?;
+                out.push(Opcode::Else as u8);
+                Ok(())
+            }
+            "end" => {
+                if self.labels.is_empty() {
+                    return Err(name.error("unexpected `end`"));
+                }
+
+                self.check_label(c)?;
+                self.end_block(name)?;
+                out.push(Opcode::End as u8);
+                Ok(())
+            }
+            "delegate" => {
+                let opcode = opcode(name)?;
+                self.end_block(name)?;
+                write_opcode(out, opcode);
+                encode::u32(out, self.label(c)?);
+                Ok(())
+            }
+            _ => self.immediates(name, c, out),
+        }
+    }
+
+    fn immediates(
+        &mut self,
+        name: Atom<'a>,
+        c: &mut Cursor<'_, 'a>,
+        out: &mut Vec<u8>,
+    ) -> Result<()> {
+        let opcode = opcode(name)?;
+        let module = &mut *self.module;
+
+        if name.text == "select" {
+            let mut types = Vec::new();
+            while let Some(mut result) = c.list("result") {
+                while !result.is_empty() {
+                    types.push(result.val_type()?);
+                }
+            }
+
+            if types.is_empty() {
+                out.push(Opcode::Select as u8);
+            } else {
+                out.push(Opcode::SelectMany as u8);
+                encode::u32(out, u32::try_from(types.len()).unwrap_or(u32::MAX));
+                for value_type in types {
+                    encode::val_type(out, value_type);
+                }
+            }
+
+            return Ok(());
+        }
+
+        write_opcode(out, opcode);
+        match name.text {
+            "br" | "br_if" | "rethrow" | "br_on_null" | "br_on_non_null" => {
+                encode::u32(out, self.label(c)?);
+            }
+            "br_table" => {
+                let mut labels = Vec::new();
+                while c.peek_atom().map_or(false, |atom| {
+                    atom.is_id() || numbers::u32(atom.text).is_some()
+                }) {
+                    labels.push(self.label(c)?);
+                }
+
+                let default_label = labels.pop().ok_or_else(|| c.error("expected a label"))?;
+                encode::u32(out, u32::try_from(labels.len()).unwrap_or(u32::MAX));
+                for label in labels {
+                    encode::u32(out, label);
+                }
+                encode::u32(out, default_label);
+            }
+            "call" | "return_call" | "ref.func" => {
+                encode::u32(out, module.funcs.expect_index(c)?);
+            }
+            "call_indirect" | "return_call_indirect" => {
+                let table = module.tables.optional_index(c)?;
+                let type_use = module.type_use(c)?;
+                encode::u32(out, module.type_index(&type_use));
+                encode::u32(out, table);
+            }
+            "call_ref" | "return_call_ref" => {
+                encode::u32(out, module.type_names.expect_index(c)?);
+            }
+            "local.get" | "local.set" | "local.tee" => {
+                encode::u32(out, self.locals.expect_index(c)?);
+            }
+            "global.get" | "global.set" => {
+                encode::u32(out, module.globals.expect_index(c)?);
+            }
+            "table.get" | "table.set" | "table.size" | "table.grow" | "table.fill" => {
+                encode::u32(out, module.tables.optional_index(c)?);
+            }
+            "memory.size" | "memory.grow" | "memory.fill" => {
+                encode::u32(out, module.memories.optional_index(c)?);
+            }
+            "table.copy" | "memory.copy" => {
+                let space = if name.text == "table.copy" {
+                    &module.tables
+                } else {
+                    &module.memories
+                };
+
+                let (destination, source) = match c.index() {
+                    Some(destination) => (space.resolve(destination)?, space.expect_index(c)?),
+                    None => (0, 0),
+                };
+
+                encode::u32(out, destination);
+                encode::u32(out, source);
+            }
+            "table.init" | "memory.init" => {
+                let (space, segments) = if name.text == "table.init" {
+                    (&module.tables, &module.elems)
+                } else {
+                    module.uses_data_count = true;
+                    (&module.memories, &module.datas)
+                };
+
+                let first = c.expect_index()?;
+                let (destination, segment) = match c.index() {
+                    Some(segment) => (space.resolve(first)?, segments.resolve(segment)?),
+                    None => (0, segments.resolve(first)?),
+                };
+
+                encode::u32(out, segment);
+                encode::u32(out, destination);
+            }
+            "data.drop" => {
+                module.uses_data_count = true;
+                encode::u32(out, module.datas.expect_index(c)?);
+            }
+            "elem.drop" => encode::u32(out, module.elems.expect_index(c)?),
+            "throw" | "catch" => encode::u32(out, module.tags.expect_index(c)?),
+            "i32.const" => {
+                let value = c.expect_atom("an integer")?;
+                let value = numbers::i32(value.text)
+                    .ok_or_else(|| value.error("expected a 32-bit integer"))?;
+                encode::s64(out, i64::from(value));
+            }
+            "i64.const" => {
+                let value = c.expect_atom("an integer")?;
+                let value = numbers::i64(value.text)
+                    .ok_or_else(|| value.error("expected a 64-bit integer"))?;
+                encode::s64(out, value);
+            }
+            "f32.const" => {
+                let value = c.expect_atom("a float")?;
+                let bits = numbers::f32(value.text)
+                    .ok_or_else(|| value.error("expected a 32-bit float"))?;
+                out.extend_from_slice(&bits.to_le_bytes());
+            }
+            "f64.const" => {
+                let value = c.expect_atom("a float")?;
+                let bits = numbers::f64(value.text)
+                    .ok_or_else(|| value.error("expected a 64-bit float"))?;
+                out.extend_from_slice(&bits.to_le_bytes());
+            }
+            "v128.const" => {
+                let shape = c.expect_atom("a vector shape")?;
+                let (count, bits) = match shape.text {
+                    "i8x16" => (16, 8),
+                    "i16x8" => (8, 16),
+                    "i32x4" | "f32x4" => (4, 32),
+                    "i64x2" | "f64x2" => (2, 64),
+                    _ => return Err(shape.error(format!("unknown vector shape `{}`", shape.text))),
+                };
+
+                for _ in 0..count {
+                    let lane = c.expect_atom("a vector lane")?;
+                    let value = match shape.text {
+                        "f32x4" => numbers::f32(lane.text).map(u64::from),
+                        "f64x2" => numbers::f64(lane.text),
+                        _ => numbers::int(lane.text, bits),
+                    };
+
+                    let value = value.ok_or_else(|| lane.error("invalid vector lane"))?;
+                    out.extend_from_slice(&value.to_le_bytes()[..bits as usize / 8]);
+                }
+            }
+            "ref.null" => encode::heap_type(out, c.heap_type()?),
+            "i8x16.shuffle" => {
+                for _ in 0..16 {
+                    out.push(lane(c)?);
+                }
+            }
+            _ if has_memarg(name.text) => {
+                let has_lane = name.text.starts_with("v128.") && name.text.ends_with("_lane");
+                self.memarg(opcode, has_lane, c, out)?;
+                if has_lane {
+                    out.push(lane(c)?);
+                }
+            }
+            _ if name.text.contains("_lane") => out.push(lane(c)?),
+            _ => (),
+        }
+
+        Ok(())
+    }
+
+    fn memarg(
+        &mut self,
+        opcode: InstructionOpcode,
+        has_lane: bool,
+        c: &mut Cursor<'_, 'a>,
+        out: &mut Vec<u8>,
+    ) -> Result<()> {
+        // For lane instructions, a single integer is the lane rather than the memory
+        let mut lookahead = *c;
+        let has_memory = match lookahead.index() {
+            Some(index) => {
+                index.is_id()
+                    || !has_lane
+                    || lookahead.peek_atom().map_or(false, |next| {
+                        numbers::u32(next.text).is_some()
+                            || next.text.starts_with("offset=")
+                            || next.text.starts_with("align=")
+                    })
+            }
+            None => false,
+        };
+
+        let memory = if has_memory {
+            self.module.memories.expect_index(c)?
+        } else {
+            0
+        };
+
+        let mut offset = 0;
+        if let Some(atom) = c.peek_atom() {
+            if let Some(value) = atom.text.strip_prefix("offset=") {
+                offset = numbers::u64(value).ok_or_else(|| atom.error("invalid offset"))?;
+                c.next();
+            }
+        }
+
+        let mut align = None;
+        if let Some(atom) = c.peek_atom() {
+            if let Some(value) = atom.text.strip_prefix("align=") {
+                align = Some(
+                    numbers::u64(value)
+                        .filter(|value| value.is_power_of_two())
+                        .and_then(|value| u8::try_from(value.trailing_zeros()).ok())
+                        .and_then(Align::new)
+                        .ok_or_else(|| atom.error("alignment must be a power of two"))?,
+                );
+                c.next();
+            }
+        }
+
+        let align = align
+            .or_else(|| natural_alignment(opcode, has_lane))
+            .unwrap_or(Align::None);
+        encode::memarg(out, &MemArg::new(offset, align, MemIdx::from(memory)));
+        Ok(())
+    }
+
+    /// Encodes a folded instruction, such as `(i32.add (local.get 0) (i32.const 1))`, where the
+    /// operands are written before the instruction itself.
+    fn folded(&mut self, list: &List<'a>, out: &mut Vec<u8>) -> Result<()> {
+        let name = match list.items.first() {
+            Some(Item::Atom(atom)) if !atom.is_id() => *atom,
+            _ => return Err(ParseError::new(list.start, "expected an instruction")),
+        };
+
+        let mut c = Cursor::after_keyword(list);
+        match name.text {
+            "block" | "loop" | "try_table" => {
+                self.start_block(name, &mut c, out)?;
+                self.instructions(&mut c, out)?;
+                self.end_block(name)?;
+                out.push(Opcode::End as u8);
+            }
+            "if" => {
+                let mut header = Vec::new();
+                self.start_block(name, &mut c, &mut header)?;
+
+                // The condition is written before the if instruction
+                while let Some((keyword, condition)) = c.peek_list() {
+                    if keyword == "then" {
+                        break;
+                    }
+
+                    c.next();
+                    self.folded(condition, out)?;
+                }
+
+                out.extend_from_slice(&header);
+                self.instructions(&mut c.expect_list("then")?, out)?;
+                if let Some(mut alternative) = c.list("else") {
+                    out.push(Opcode::Else as u8);
+                    self.instructions(&mut alternative, out)?;
+                }
+
+                c.finish()?;
+                self.end_block(name)?;
+                out.push(Opcode::End as u8);
+            }
+            "try" => {
+                self.start_block(name, &mut c, out)?;
+                self.instructions(&mut c.expect_list("do")?, out)?;
+                loop {
+                    if let Some((keyword, handler)) = c.peek_list() {
+                        let handler_name = match handler.items.first() {
+                            Some(Item::Atom(atom)) => *atom,
+                            _ => break,
+                        };
+
+                        match keyword {
+                            "catch" | "catch_all" => {
+                                c.next();
+                                let mut handler = Cursor::after_keyword(handler);
+                                self.immediates(handler_name, &mut handler, out)?;
+                                self.instructions(&mut handler, out)?;
+                                continue;
+                            }
+                            "delegate" => {
+                                c.next();
+                                let mut handler = Cursor::after_keyword(handler);
+                                self.plain(handler_name, &mut handler, out)?;
+                                handler.finish()?;
+                                return c.finish();
+                            }
+                            _ => (),
+                        }
+                    }
+
+                    break;
+                }
+
+                c.finish()?;
+                self.end_block(name)?;
+                out.push(Opcode::End as u8);
+            }
+            "else" | "end" | "delegate" | "then" => {
+                return Err(name.error(format!("unexpected `{}`", name.text)));
+            }
+            _ => {
+                let mut instruction = Vec::new();
+                self.immediates(name, &mut c, &mut instruction)?;
+                while let Some(item) = c.peek() {
+                    match item {
+                        Item::List(operand) => {
+                            c.next();
+                            self.folded(operand, out)?;
+                        }
+                        _ => return c.finish(),
+                    }
+                }
+
+                out.extend_from_slice(&instruction);
+            }
+        }
+
+        Ok(())
+    }
+}
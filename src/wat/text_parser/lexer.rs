@@ -0,0 +1,249 @@
+//! Splits text into [tokens](https://webassembly.github.io/spec/core/text/lexical.html), grouping
+//! them into a tree of parenthesized lists.
+
+use crate::wat::text_parser::{ParseError, Result};
+use alloc::{string::String, vec::Vec};
+
+/// A keyword, number, identifier, or string.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct Atom<'a> {
+    pub(super) text: &'a str,
+    pub(super) offset: usize,
+}
+
+impl Atom<'_> {
+    #[inline]
+    pub(super) fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError::new(self.offset, message)
+    }
+
+    #[inline]
+    pub(super) fn is_id(&self) -> bool {
+        self.text.starts_with('$')
+    }
+}
+
+#[derive(Debug)]
+pub(super) enum Item<'a> {
+    Atom(Atom<'a>),
+    /// A string literal, excluding the quotation marks. Escape sequences are not yet decoded.
+    String(Atom<'a>),
+    List(List<'a>),
+}
+
+impl Item<'_> {
+    pub(super) fn offset(&self) -> usize {
+        match self {
+            Self::Atom(atom) | Self::String(atom) => atom.offset,
+            Self::List(list) => list.start,
+        }
+    }
+}
+
+/// A parenthesized list of items.
+#[derive(Debug)]
+pub(super) struct List<'a> {
+    pub(super) items: Vec<Item<'a>>,
+    /// Offset to the opening parenthesis.
+    pub(super) start: usize,
+    /// Offset to the closing parenthesis.
+    pub(super) end: usize,
+}
+
+impl<'a> List<'a> {
+    /// Gets the keyword at the start of the list, such as `func` in `(func ...)`.
+    pub(super) fn keyword(&self) -> Option<&'a str> {
+        match self.items.first() {
+            Some(Item::Atom(atom)) if !atom.is_id() => Some(atom.text),
+            _ => None,
+        }
+    }
+}
+
+fn is_id_char(b: u8) -> bool {
+    matches!(
+        b,
+        b'0'..=b'9'
+            | b'A'..=b'Z'
+            | b'a'..=b'z'
+            | b'!'
+            | b'#'
+            | b'$'
+            | b'%'
+            | b'&'
+            | b'\''
+            | b'*'
+            | b'+'
+            | b'-'
+            | b'.'
+            | b'/'
+            | b':'
+            | b'<'
+            | b'='
+            | b'>'
+            | b'?'
+            | b'@'
+            | b'\\'
+            | b'^'
+            | b'_'
+            | b'`'
+            | b'|'
+            | b'~'
+    )
+}
+
+/// Splits the `text` into tokens, skipping any whitespace and comments.
+pub(super) fn parse(text: &str) -> Result<Vec<Item<'_>>> {
+    let bytes = text.as_bytes();
+    let mut parents = Vec::<(usize, Vec<Item<'_>>)>::new();
+    let mut items = Vec::new();
+    let mut i = 0;
+
+    while let Some(&b) = bytes.get(i) {
+        match b {
+            b' ' | b'\t' | b'\n' | b'\r' => i += 1,
+            b';' if bytes.get(i + 1) == Some(&b';') => {
+                i = bytes[i..]
+                    .iter()
+                    .position(|b| *b == b'\n')
+                    .map_or(bytes.len(), |newline| i + newline);
+            }
+            b'(' if bytes.get(i + 1) == Some(&b';') => {
+                let start = i;
+                let mut depth = 0u32;
+                loop {
+                    match bytes.get(i..i + 2) {
+                        Some(b"(;") => {
+                            depth += 1;
+                            i += 2;
+                        }
+                        Some(b";)") => {
+                            depth -= 1;
+                            i += 2;
+                            if depth == 0 {
+                                break;
+                            }
+                        }
+                        Some(_) => i += 1,
+                        None => return Err(ParseError::new(start, "unterminated block comment")),
+                    }
+                }
+            }
+            b'(' => {
+                parents.push((i, core::mem::take(&mut items)));
+                i += 1;
+            }
+            b')' => {
+                let (start, parent) = parents
+                    .pop()
+                    .ok_or_else(|| ParseError::new(i, "unexpected `)`"))?;
+
+                let list = List {
+                    items: core::mem::replace(&mut items, parent),
+                    start,
+                    end: i,
+                };
+
+                items.push(Item::List(list));
+                i += 1;
+            }
+            b'"' => {
+                let start = i;
+                i += 1;
+                loop {
+                    match bytes.get(i) {
+                        Some(b'"') => break,
+                        Some(b'\\') => i += 2,
+                        Some(b) if *b < 0x20 || *b == 0x7F => {
+                            return Err(ParseError::new(i, "control character in string"))
+                        }
+                        Some(_) => i += 1,
+                        None => return Err(ParseError::new(start, "unterminated string")),
+                    }
+                }
+
+                items.push(Item::String(Atom {
+                    text: &text[start + 1..i],
+                    offset: start,
+                }));
+                i += 1;
+            }
+            _ if is_id_char(b) => {
+                let start = i;
+                while bytes.get(i).copied().map_or(false, is_id_char) {
+                    i += 1;
+                }
+
+                items.push(Item::Atom(Atom {
+                    text: &text[start..i],
+                    offset: start,
+                }));
+            }
+            _ => return Err(ParseError::new(i, "unexpected character")),
+        }
+    }
+
+    match parents.last() {
+        Some((start, _)) => Err(ParseError::new(*start, "unclosed `(`")),
+        None => Ok(items),
+    }
+}
+
+fn hex_digit(b: u8) -> Option<u8> {
+    char::from(b)
+        .to_digit(16)
+        .and_then(|digit| u8::try_from(digit).ok())
+}
+
+/// Decodes the escape sequences in a string literal.
+pub(super) fn string_bytes(string: Atom<'_>) -> Result<Vec<u8>> {
+    let bytes = string.text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while let Some(&b) = bytes.get(i) {
+        i += 1;
+        if b != b'\\' {
+            decoded.push(b);
+            continue;
+        }
+
+        let escape_offset = string.offset + i;
+        let bad_escape = || ParseError::new(escape_offset, "invalid string escape");
+        match bytes.get(i).copied().ok_or_else(bad_escape)? {
+            b't' => decoded.push(b'\t'),
+            b'n' => decoded.push(b'\n'),
+            b'r' => decoded.push(b'\r'),
+            b'"' => decoded.push(b'"'),
+            b'\'' => decoded.push(b'\''),
+            b'\\' => decoded.push(b'\\'),
+            b'u' => {
+                let rest = &string.text[i + 1..];
+                let end = rest
+                    .strip_prefix('{')
+                    .and_then(|rest| rest.find('}'))
+                    .ok_or_else(bad_escape)?;
+
+                let code_point = super::numbers::hex_u32(&rest[1..end + 1])
+                    .and_then(char::from_u32)
+                    .ok_or_else(bad_escape)?;
+
+                let mut buffer = [0u8; 4];
+                decoded.extend_from_slice(code_point.encode_utf8(&mut buffer).as_bytes());
+                i += end + 2;
+            }
+            high => {
+                let low = bytes.get(i + 1).copied().ok_or_else(bad_escape)?;
+                match (hex_digit(high), hex_digit(low)) {
+                    (Some(high), Some(low)) => decoded.push(high << 4 | low),
+                    _ => return Err(bad_escape()),
+                }
+                i += 1;
+            }
+        }
+
+        i += 1;
+    }
+
+    Ok(decoded)
+}
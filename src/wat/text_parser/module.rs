@@ -0,0 +1,1019 @@
+//! Encodes the [module fields](https://webassembly.github.io/spec/core/text/modules.html) of the
+//! text format into sections.
+
+use crate::{
+    edit::NameSectionBuilder,
+    encode,
+    index::{FuncIdx, LocalIdx, TagIdx},
+    sections::id as section_id,
+    types::{FuncType, GlobalMutability, GlobalType, IdxType, Limits, RefType, Sharing, TableType},
+    wat::text_parser::{
+        cursor::Cursor,
+        instructions::Body,
+        lexer::{Atom, Item, List},
+        numbers, ParseError, Result,
+    },
+};
+use alloc::{collections::BTreeMap, format, vec::Vec};
+
+const DEFAULT_PAGE_SIZE: u64 = 65536;
+
+fn length_u32(length: usize) -> u32 {
+    u32::try_from(length).unwrap_or(u32::MAX)
+}
+
+/// Maps identifiers to indices within one of the index spaces of a module, such as the functions
+/// or the locals of a function.
+#[derive(Debug)]
+pub(super) struct Space<'a> {
+    kind: &'static str,
+    names: BTreeMap<&'a str, u32>,
+    count: u32,
+}
+
+impl<'a> Space<'a> {
+    pub(super) fn new(kind: &'static str) -> Self {
+        Self {
+            kind,
+            names: BTreeMap::new(),
+            count: 0,
+        }
+    }
+
+    /// Defines the next index, associating it with the `id` if one is provided.
+    pub(super) fn define(&mut self, id: Option<Atom<'a>>) -> Result<u32> {
+        let index = self.count;
+        if let Some(id) = id {
+            if self.names.insert(id.text, index).is_some() {
+                return Err(id.error(format!("duplicate {} {}", self.kind, id.text)));
+            }
+        }
+
+        self.count = index
+            .checked_add(1)
+            .ok_or_else(|| ParseError::new(0, format!("too many {}s", self.kind)))?;
+        Ok(index)
+    }
+
+    /// Gets the index referred to by the given integer or identifier.
+    pub(super) fn resolve(&self, index: Atom<'a>) -> Result<u32> {
+        if index.is_id() {
+            self.names
+                .get(index.text)
+                .copied()
+                .ok_or_else(|| index.error(format!("unknown {} {}", self.kind, index.text)))
+        } else {
+            numbers::u32(index.text).ok_or_else(|| index.error("expected an index or identifier"))
+        }
+    }
+
+    pub(super) fn expect_index(&self, c: &mut Cursor<'_, 'a>) -> Result<u32> {
+        self.resolve(c.expect_index()?)
+    }
+
+    /// Reads an optional index, which defaults to `0`.
+    pub(super) fn optional_index(&self, c: &mut Cursor<'_, 'a>) -> Result<u32> {
+        c.index().map_or(Ok(0), |index| self.resolve(index))
+    }
+}
+
+/// The contents of a section containing a vector of entries.
+#[derive(Debug, Default)]
+struct Section {
+    count: u32,
+    contents: Vec<u8>,
+}
+
+impl Section {
+    fn push(&mut self, entry: &[u8]) {
+        self.count += 1;
+        self.contents.extend_from_slice(entry);
+    }
+
+    fn write(&self, out: &mut Vec<u8>, id: u8) {
+        if self.count > 0 {
+            let mut contents = Vec::with_capacity(self.contents.len() + 5);
+            encode::u32(&mut contents, self.count);
+            contents.extend_from_slice(&self.contents);
+            encode::section(out, id, &contents);
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Sections {
+    imports: Section,
+    functions: Section,
+    tables: Section,
+    memories: Section,
+    tags: Section,
+    globals: Section,
+    exports: Section,
+    start: Option<u32>,
+    elements: Section,
+    code: Section,
+    data: Section,
+}
+
+/// The parts of a `func`, `table`, `memory`, `global`, or `tag` field that precede its
+/// definition.
+struct Header<'t, 'a> {
+    id: Option<Atom<'a>>,
+    exports: Vec<Cursor<'t, 'a>>,
+    import: Option<Cursor<'t, 'a>>,
+    rest: Cursor<'t, 'a>,
+}
+
+impl<'t, 'a> Header<'t, 'a> {
+    fn parse(field: &'t List<'a>) -> Self {
+        let mut rest = Cursor::after_keyword(field);
+        let id = rest.id();
+        let mut exports = Vec::new();
+        while let Some(export) = rest.list("export") {
+            exports.push(export);
+        }
+
+        Self {
+            id,
+            exports,
+            import: rest.list("import"),
+            rest,
+        }
+    }
+}
+
+/// A [type use](https://webassembly.github.io/spec/core/text/modules.html#type-uses), which refers
+/// to a function type either by its index or by its parameter and result types.
+pub(super) struct TypeUse<'a> {
+    index: Option<u32>,
+    signature: FuncType,
+    parameter_names: Vec<Option<Atom<'a>>>,
+}
+
+impl TypeUse<'_> {
+    /// Returns `true` if the type use is an inline block type, such as `(result i32)`.
+    pub(super) fn is_inline_block_type(&self) -> bool {
+        self.index.is_none()
+            && self.signature.parameters().is_empty()
+            && self.signature.results().len() <= 1
+    }
+
+    pub(super) fn signature(&self) -> &FuncType {
+        &self.signature
+    }
+}
+
+/// Keeps track of the index spaces and sections of the module being parsed.
+pub(super) struct Module<'a> {
+    types: Vec<FuncType>,
+    pub(super) type_names: Space<'a>,
+    pub(super) funcs: Space<'a>,
+    pub(super) tables: Space<'a>,
+    pub(super) memories: Space<'a>,
+    pub(super) globals: Space<'a>,
+    pub(super) tags: Space<'a>,
+    pub(super) elems: Space<'a>,
+    pub(super) datas: Space<'a>,
+    /// Set when a `memory.init` or `data.drop` instruction is used, which requires a *data count*
+    /// section.
+    pub(super) uses_data_count: bool,
+    sections: Sections,
+    names: NameSectionBuilder,
+}
+
+impl<'a> Module<'a> {
+    fn new() -> Self {
+        Self {
+            types: Vec::new(),
+            type_names: Space::new("type"),
+            funcs: Space::new("function"),
+            tables: Space::new("table"),
+            memories: Space::new("memory"),
+            globals: Space::new("global"),
+            tags: Space::new("tag"),
+            elems: Space::new("elem"),
+            datas: Space::new("data"),
+            uses_data_count: false,
+            sections: Sections::default(),
+            names: NameSectionBuilder::new(),
+        }
+    }
+
+    fn space(&mut self, kind: &str) -> Option<&mut Space<'a>> {
+        Some(match kind {
+            "func" => &mut self.funcs,
+            "table" => &mut self.tables,
+            "memory" => &mut self.memories,
+            "global" => &mut self.globals,
+            "tag" => &mut self.tags,
+            _ => return None,
+        })
+    }
+
+    fn func_type(mut c: Cursor<'_, 'a>) -> Result<FuncType> {
+        let mut parameters = Vec::new();
+        while let Some(mut parameter) = c.list("param") {
+            if parameter.id().is_some() {
+                parameters.push(parameter.val_type()?);
+                parameter.finish()?;
+            } else {
+                while !parameter.is_empty() {
+                    parameters.push(parameter.val_type()?);
+                }
+            }
+        }
+
+        let mut results = Vec::new();
+        while let Some(mut result) = c.list("result") {
+            while !result.is_empty() {
+                results.push(result.val_type()?);
+            }
+        }
+
+        c.finish()?;
+        Ok(FuncType::new(parameters, results))
+    }
+
+    /// Parses a type use, which may be followed by other items.
+    pub(super) fn type_use(&self, c: &mut Cursor<'_, 'a>) -> Result<TypeUse<'a>> {
+        let index = match c.list("type") {
+            Some(mut type_index) => {
+                let index = self.type_names.expect_index(&mut type_index)?;
+                type_index.finish()?;
+                Some(index)
+            }
+            None => None,
+        };
+
+        let mut parameters = Vec::new();
+        let mut parameter_names = Vec::new();
+        while let Some(mut parameter) = c.list("param") {
+            if let Some(id) = parameter.id() {
+                parameters.push(parameter.val_type()?);
+                parameter_names.push(Some(id));
+                parameter.finish()?;
+            } else {
+                while !parameter.is_empty() {
+                    parameters.push(parameter.val_type()?);
+                    parameter_names.push(None);
+                }
+            }
+        }
+
+        let mut results = Vec::new();
+        while let Some(mut result) = c.list("result") {
+            while !result.is_empty() {
+                results.push(result.val_type()?);
+            }
+        }
+
+        Ok(TypeUse {
+            index,
+            signature: FuncType::new(parameters, results),
+            parameter_names,
+        })
+    }
+
+    /// Gets the index of the function type referred to by the type use, adding a new type to the
+    /// end of the *type* section if no matching type exists.
+    pub(super) fn type_index(&mut self, type_use: &TypeUse<'_>) -> u32 {
+        if let Some(index) = type_use.index {
+            return index;
+        }
+
+        match self
+            .types
+            .iter()
+            .position(|existing| *existing == type_use.signature)
+        {
+            Some(index) => length_u32(index),
+            None => {
+                self.types.push(type_use.signature.clone());
+                length_u32(self.types.len() - 1)
+            }
+        }
+    }
+
+    fn limits(c: &mut Cursor<'_, 'a>, share: Sharing, index_type: IdxType) -> Result<Limits> {
+        let minimum = c.u64()?;
+        let maximum = match c.peek_atom() {
+            Some(atom) if numbers::u64(atom.text).is_some() => Some(c.u64()?),
+            _ => None,
+        };
+
+        Limits::new(minimum, maximum, share, index_type)
+            .ok_or_else(|| c.error("size minimum must not be greater than maximum"))
+    }
+
+    fn index_type(c: &mut Cursor<'_, 'a>) -> IdxType {
+        if c.keyword("i64") {
+            IdxType::I64
+        } else {
+            c.keyword("i32");
+            IdxType::I32
+        }
+    }
+
+    fn table_type(c: &mut Cursor<'_, 'a>) -> Result<TableType> {
+        let index_type = Self::index_type(c);
+        let limits = Self::limits(c, Sharing::Unshared, index_type)?;
+        Ok(TableType::new(c.ref_type()?, limits))
+    }
+
+    fn mem_type(c: &mut Cursor<'_, 'a>) -> Result<Limits> {
+        let index_type = Self::index_type(c);
+        let offset = c.offset();
+        let mut limits = Self::limits(c, Sharing::Unshared, index_type)?;
+        if c.keyword("shared") {
+            limits = Limits::new(
+                limits.minimum(),
+                limits.maximum(),
+                Sharing::Shared,
+                index_type,
+            )
+            .ok_or_else(|| ParseError::new(offset, "invalid memory limits"))?;
+        }
+
+        #[cfg(feature = "custom-page-sizes")]
+        if let Some(mut page_size) = c.list("pagesize") {
+            let size = page_size.u64()?;
+            page_size.finish()?;
+            limits = Some(size)
+                .filter(|size| size.is_power_of_two())
+                .and_then(|size| u8::try_from(size.trailing_zeros()).ok())
+                .and_then(|log2| limits.with_page_size_log2(log2))
+                .ok_or_else(|| c.error("page size must be 1 or 65536"))?;
+        }
+
+        Ok(limits)
+    }
+
+    fn global_type(c: &mut Cursor<'_, 'a>) -> Result<GlobalType> {
+        Ok(match c.list("mut") {
+            Some(mut mutable) => {
+                let value_type = mutable.val_type()?;
+                mutable.finish()?;
+                GlobalType::new(GlobalMutability::Variable, value_type)
+            }
+            None => GlobalType::new(GlobalMutability::Constant, c.val_type()?),
+        })
+    }
+
+    fn exports(&mut self, exports: &mut [Cursor<'_, 'a>], kind: u8, index: u32) -> Result<()> {
+        for export in exports {
+            let name = export.name()?;
+            export.finish()?;
+
+            let mut entry = Vec::new();
+            encode::bytes(&mut entry, name.as_bytes());
+            entry.push(kind);
+            encode::u32(&mut entry, index);
+            self.sections.exports.push(&entry);
+        }
+
+        Ok(())
+    }
+
+    /// Encodes the description of an imported definition, such as `(func (type 0))`.
+    fn import_description(&mut self, kind: &str, c: &mut Cursor<'_, 'a>) -> Result<Vec<u8>> {
+        let mut description = Vec::new();
+        match kind {
+            "func" => {
+                let type_use = self.type_use(c)?;
+                description.push(0);
+                encode::u32(&mut description, self.type_index(&type_use));
+            }
+            "table" => {
+                description.push(1);
+                encode::table_type(&mut description, &Self::table_type(c)?);
+            }
+            "memory" => {
+                description.push(2);
+                encode::limits(&mut description, &Self::mem_type(c)?);
+            }
+            "global" => {
+                description.push(3);
+                encode::global_type(&mut description, Self::global_type(c)?);
+            }
+            "tag" => {
+                let type_use = self.type_use(c)?;
+                description.extend_from_slice(&[4, 0]);
+                encode::u32(&mut description, self.type_index(&type_use));
+            }
+            _ => return Err(c.error(format!("unknown import kind `{kind}`"))),
+        }
+
+        c.finish()?;
+        Ok(description)
+    }
+
+    fn import(
+        &mut self,
+        mut names: Cursor<'_, 'a>,
+        kind: &str,
+        description: &mut Cursor<'_, 'a>,
+    ) -> Result<()> {
+        let module = names.name()?;
+        let name = names.name()?;
+
+        let mut entry = Vec::new();
+        encode::bytes(&mut entry, module.as_bytes());
+        encode::bytes(&mut entry, name.as_bytes());
+        entry.extend_from_slice(&self.import_description(kind, description)?);
+        self.sections.imports.push(&entry);
+        Ok(())
+    }
+
+    /// Parses an expression that ends at the end of the cursor.
+    fn expression(&mut self, c: &mut Cursor<'_, 'a>) -> Result<Vec<u8>> {
+        let mut expression = Vec::new();
+        Body::new(self, Space::new("local")).expression(c, &mut expression)?;
+        Ok(expression)
+    }
+
+    /// Parses an `(offset ...)` or `(item ...)` expression, or an expression containing a single
+    /// folded instruction.
+    fn abbreviated_expression(&mut self, c: &mut Cursor<'_, 'a>, keyword: &str) -> Result<Vec<u8>> {
+        if let Some(mut expression) = c.list(keyword) {
+            return self.expression(&mut expression);
+        }
+
+        match c.peek() {
+            Some(Item::List(list)) => {
+                c.next();
+                let mut expression = Vec::new();
+                Body::new(self, Space::new("local")).folded_expression(list, &mut expression)?;
+                Ok(expression)
+            }
+            _ => Err(c.error(format!(
+                "expected `({keyword} ...)` or a folded instruction"
+            ))),
+        }
+    }
+
+    fn func(&mut self, mut header: Header<'_, 'a>, index: u32) -> Result<()> {
+        self.exports(&mut header.exports, 0, index)?;
+        if let Some(id) = header.id {
+            self.names
+                .insert_function_name(FuncIdx::from(index), &id.text[1..]);
+        }
+
+        let mut c = header.rest;
+        if let Some(import) = header.import {
+            return self.import(import, "func", &mut c);
+        }
+
+        let type_use = self.type_use(&mut c)?;
+        let type_index = self.type_index(&type_use);
+        let mut function = Vec::new();
+        encode::u32(&mut function, type_index);
+        self.sections.functions.push(&function);
+
+        let mut parameter_names = type_use.parameter_names;
+        if parameter_names.is_empty() {
+            let signature = self
+                .types
+                .get(usize::try_from(type_index).unwrap_or(usize::MAX))
+                .ok_or_else(|| c.error(format!("unknown type {type_index}")))?;
+            parameter_names.resize(signature.parameters().len(), None);
+        }
+
+        let mut locals = Space::new("local");
+        for name in parameter_names {
+            let local = locals.define(name)?;
+            if let Some(name) = name {
+                self.names.insert_local_name(
+                    FuncIdx::from(index),
+                    LocalIdx::from(local),
+                    &name.text[1..],
+                );
+            }
+        }
+
+        let mut local_groups = Vec::<(u32, _)>::new();
+        while let Some(mut declaration) = c.list("local") {
+            let id = declaration.id();
+            loop {
+                let local_type = declaration.val_type()?;
+                let local = locals.define(id)?;
+                if let Some(id) = id {
+                    self.names.insert_local_name(
+                        FuncIdx::from(index),
+                        LocalIdx::from(local),
+                        &id.text[1..],
+                    );
+                }
+
+                match local_groups.last_mut() {
+                    Some((count, group_type)) if *group_type == local_type => *count += 1,
+                    _ => local_groups.push((1, local_type)),
+                }
+
+                if id.is_some() || declaration.is_empty() {
+                    break;
+                }
+            }
+
+            declaration.finish()?;
+        }
+
+        let mut body = Vec::new();
+        encode::u32(&mut body, length_u32(local_groups.len()));
+        for (count, local_type) in local_groups {
+            encode::u32(&mut body, count);
+            encode::val_type(&mut body, local_type);
+        }
+
+        Body::new(self, locals).expression(&mut c, &mut body)?;
+
+        let mut code = Vec::with_capacity(body.len() + 5);
+        encode::bytes(&mut code, &body);
+        self.sections.code.push(&code);
+        Ok(())
+    }
+
+    /// Parses the elements of an element segment, writing the *elemkind* or reference type if
+    /// `write_type` is set.
+    fn element_list(
+        &mut self,
+        c: &mut Cursor<'_, 'a>,
+        element_type: Option<RefType>,
+        write_type: bool,
+        segment: &mut Vec<u8>,
+    ) -> Result<u32> {
+        let mut count = 0u32;
+        let mut elements = Vec::new();
+        match element_type {
+            None => {
+                if write_type {
+                    segment.push(0);
+                }
+
+                while let Some(function) = c.index() {
+                    encode::u32(&mut elements, self.funcs.resolve(function)?);
+                    count += 1;
+                }
+            }
+            Some(element_type) => {
+                if write_type {
+                    encode::ref_type(segment, element_type);
+                }
+
+                while !c.is_empty() {
+                    let expression = self.abbreviated_expression(c, "item")?;
+                    elements.extend_from_slice(&expression);
+                    count += 1;
+                }
+            }
+        }
+
+        c.finish()?;
+        encode::u32(segment, count);
+        segment.extend_from_slice(&elements);
+        Ok(count)
+    }
+
+    fn table(&mut self, mut header: Header<'_, 'a>, index: u32) -> Result<()> {
+        self.exports(&mut header.exports, 1, index)?;
+        let mut c = header.rest;
+        if let Some(import) = header.import {
+            return self.import(import, "table", &mut c);
+        }
+
+        let mut table = Vec::new();
+        if matches!(c.peek_atom(), Some(atom) if atom.text.ends_with("ref")) {
+            let element_type = c.ref_type()?;
+            let mut elements = c.expect_list("elem")?;
+            c.finish()?;
+
+            let is_expressions = matches!(elements.peek(), Some(Item::List(_)));
+            let mut segment = Vec::new();
+            segment.push(if is_expressions { 6 } else { 2 });
+            encode::u32(&mut segment, index);
+            segment.extend_from_slice(&[0x41, 0, 0x0B]);
+
+            let count = self.element_list(
+                &mut elements,
+                Some(element_type).filter(|_| is_expressions),
+                true,
+                &mut segment,
+            )?;
+
+            let count = u64::from(count);
+            let limits = Limits::new(count, Some(count), Sharing::Unshared, IdxType::I32)
+                .ok_or_else(|| c.error("invalid table limits"))?;
+            encode::table_type(&mut table, &TableType::new(element_type, limits));
+            self.sections.elements.push(&segment);
+        } else {
+            encode::table_type(&mut table, &Self::table_type(&mut c)?);
+            c.finish()?;
+        }
+
+        self.sections.tables.push(&table);
+        Ok(())
+    }
+
+    fn memory(&mut self, mut header: Header<'_, 'a>, index: u32) -> Result<()> {
+        self.exports(&mut header.exports, 2, index)?;
+        let mut c = header.rest;
+        if let Some(import) = header.import {
+            return self.import(import, "memory", &mut c);
+        }
+
+        let mut memory = Vec::new();
+        let mut inline_data = c;
+        let index_type = Self::index_type(&mut inline_data);
+        match inline_data.list("data") {
+            Some(mut strings) => {
+                inline_data.finish()?;
+
+                let mut bytes = Vec::new();
+                while let Some(string) = strings.string() {
+                    bytes.extend_from_slice(&string?);
+                }
+                strings.finish()?;
+
+                let length = bytes.len() as u64;
+                let pages = length / DEFAULT_PAGE_SIZE + u64::from(length % DEFAULT_PAGE_SIZE != 0);
+                let limits = Limits::new(pages, Some(pages), Sharing::Unshared, index_type)
+                    .ok_or_else(|| c.error("invalid memory limits"))?;
+                encode::limits(&mut memory, &limits);
+
+                let mut segment = Vec::new();
+                if index == 0 {
+                    segment.push(0);
+                } else {
+                    segment.push(2);
+                    encode::u32(&mut segment, index);
+                }
+
+                segment.push(match index_type {
+                    IdxType::I32 => 0x41,
+                    IdxType::I64 => 0x42,
+                });
+                segment.extend_from_slice(&[0, 0x0B]);
+                encode::bytes(&mut segment, &bytes);
+                self.sections.data.push(&segment);
+            }
+            None => {
+                encode::limits(&mut memory, &Self::mem_type(&mut c)?);
+                c.finish()?;
+            }
+        }
+
+        self.sections.memories.push(&memory);
+        Ok(())
+    }
+
+    fn global(&mut self, mut header: Header<'_, 'a>, index: u32) -> Result<()> {
+        self.exports(&mut header.exports, 3, index)?;
+        let mut c = header.rest;
+        if let Some(import) = header.import {
+            return self.import(import, "global", &mut c);
+        }
+
+        let mut global = Vec::new();
+        encode::global_type(&mut global, Self::global_type(&mut c)?);
+        global.extend_from_slice(&self.expression(&mut c)?);
+        self.sections.globals.push(&global);
+        Ok(())
+    }
+
+    fn tag(&mut self, mut header: Header<'_, 'a>, index: u32) -> Result<()> {
+        self.exports(&mut header.exports, 4, index)?;
+        if let Some(id) = header.id {
+            self.names
+                .insert_tag_name(TagIdx::from(index), &id.text[1..]);
+        }
+
+        let mut c = header.rest;
+        if let Some(import) = header.import {
+            return self.import(import, "tag", &mut c);
+        }
+
+        let type_use = self.type_use(&mut c)?;
+        c.finish()?;
+
+        let mut tag = alloc::vec![0];
+        encode::u32(&mut tag, self.type_index(&type_use));
+        self.sections.tags.push(&tag);
+        Ok(())
+    }
+
+    fn export(&mut self, mut c: Cursor<'_, 'a>) -> Result<()> {
+        let name = c.name()?;
+        let (kind, list) = c
+            .peek_list()
+            .ok_or_else(|| c.error("expected an export description"))?;
+
+        let mut description = Cursor::after_keyword(list);
+        let (kind, space) = match kind {
+            "func" => (0, &self.funcs),
+            "table" => (1, &self.tables),
+            "memory" => (2, &self.memories),
+            "global" => (3, &self.globals),
+            "tag" => (4, &self.tags),
+            _ => return Err(c.error(format!("unknown export kind `{kind}`"))),
+        };
+
+        let index = space.expect_index(&mut description)?;
+        description.finish()?;
+        c.next();
+        c.finish()?;
+
+        let mut entry = Vec::new();
+        encode::bytes(&mut entry, name.as_bytes());
+        entry.push(kind);
+        encode::u32(&mut entry, index);
+        self.sections.exports.push(&entry);
+        Ok(())
+    }
+
+    fn elem(&mut self, mut c: Cursor<'_, 'a>) -> Result<()> {
+        c.id();
+
+        let mut segment = Vec::new();
+        let mut table = None;
+        let mut offset = None;
+        let mut legacy_indices = false;
+        let mode = if c.keyword("declare") {
+            3
+        } else if let Some(mut table_use) = c.list("table") {
+            table = Some(self.tables.expect_index(&mut table_use)?);
+            table_use.finish()?;
+            offset = Some(self.abbreviated_expression(&mut c, "offset")?);
+            2
+        } else if matches!(c.peek(), Some(Item::List(_))) {
+            offset = Some(self.abbreviated_expression(&mut c, "offset")?);
+            legacy_indices = true;
+            0
+        } else {
+            1
+        };
+
+        let element_type = if c.keyword("func") {
+            None
+        } else if c
+            .peek_atom()
+            .map_or(false, |atom| atom.text.ends_with("ref"))
+        {
+            Some(c.ref_type()?)
+        } else if legacy_indices {
+            // The `func` keyword can be omitted when the table is omitted
+            None
+        } else {
+            return Err(c.error("expected `func` or a reference type"));
+        };
+
+        // Like in the binary format, the table and the type can only be omitted for function
+        // references in table 0
+        let is_expressions = element_type.is_some();
+        let mut flags = mode | if is_expressions { 4 } else { 0 };
+        if mode == 0 && element_type.map_or(false, |ty| ty != RefType::Func) {
+            flags |= 2;
+            table = Some(0);
+        }
+
+        encode::u32(&mut segment, flags);
+        if let Some(table) = table {
+            encode::u32(&mut segment, table);
+        }
+        if let Some(offset) = offset {
+            segment.extend_from_slice(&offset);
+        }
+
+        self.element_list(&mut c, element_type, flags & 3 != 0, &mut segment)?;
+        self.sections.elements.push(&segment);
+        Ok(())
+    }
+
+    fn data(&mut self, mut c: Cursor<'_, 'a>) -> Result<()> {
+        c.id();
+
+        let mut segment = Vec::new();
+        if let Some(mut memory_use) = c.list("memory") {
+            let memory = self.memories.expect_index(&mut memory_use)?;
+            memory_use.finish()?;
+            if memory == 0 {
+                segment.push(0);
+            } else {
+                segment.push(2);
+                encode::u32(&mut segment, memory);
+            }
+            segment.extend_from_slice(&self.abbreviated_expression(&mut c, "offset")?);
+        } else if matches!(c.peek(), Some(Item::List(_))) {
+            segment.push(0);
+            segment.extend_from_slice(&self.abbreviated_expression(&mut c, "offset")?);
+        } else {
+            segment.push(1);
+        }
+
+        let mut bytes = Vec::new();
+        while let Some(string) = c.string() {
+            bytes.extend_from_slice(&string?);
+        }
+
+        c.finish()?;
+        encode::bytes(&mut segment, &bytes);
+        self.sections.data.push(&segment);
+        Ok(())
+    }
+
+    /// Assigns indices to all definitions, so that they can be referred to before they are
+    /// defined.
+    fn declare(&mut self, fields: &[&List<'a>]) -> Result<Vec<u32>> {
+        let mut indices = alloc::vec![0; fields.len()];
+
+        // Imports always come first in their index spaces
+        for (field, field_index) in fields.iter().zip(indices.iter_mut()) {
+            let keyword = field.keyword().unwrap_or_default();
+            match keyword {
+                "type" => {
+                    let mut c = Cursor::after_keyword(field);
+                    let id = c.id();
+                    let signature = Self::func_type(c.expect_list("func")?)?;
+                    c.finish()?;
+                    self.type_names.define(id)?;
+                    self.types.push(signature);
+                }
+                "import" => {
+                    let mut c = Cursor::after_keyword(field);
+                    c.name()?;
+                    c.name()?;
+                    let (kind, list) = c
+                        .peek_list()
+                        .ok_or_else(|| c.error("expected an import description"))?;
+
+                    let id = Cursor::after_keyword(list).id();
+                    self.space(kind)
+                        .ok_or_else(|| c.error(format!("unknown import kind `{kind}`")))?
+                        .define(id)?;
+                }
+                _ => {
+                    let header = Header::parse(field);
+                    if header.import.is_some() {
+                        if let Some(space) = self.space(keyword) {
+                            *field_index = space.define(header.id)?;
+                        }
+                    }
+                }
+            }
+        }
+
+        for (field, field_index) in fields.iter().zip(indices.iter_mut()) {
+            let keyword = field.keyword().unwrap_or_default();
+            let header = Header::parse(field);
+            match keyword {
+                "elem" => {
+                    self.elems.define(header.id)?;
+                }
+                "data" => {
+                    self.datas.define(header.id)?;
+                }
+                _ if header.import.is_none() => {
+                    if let Some(space) = self.space(keyword) {
+                        *field_index = space.define(header.id)?;
+                    }
+
+                    // Inline element and data segments
+                    let mut rest = header.rest;
+                    if keyword == "table" {
+                        rest.atom();
+                        if rest.list("elem").is_some() {
+                            self.elems.define(None)?;
+                        }
+                    } else if keyword == "memory" {
+                        rest.keyword("i64");
+                        if rest.list("data").is_some() {
+                            self.datas.define(None)?;
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        Ok(indices)
+    }
+
+    fn write(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(b"\0asm\x01\0\0\0");
+
+        let mut types = Section::default();
+        for signature in self.types.iter() {
+            let mut entry = alloc::vec![0x60];
+            encode::u32(&mut entry, length_u32(signature.parameters().len()));
+            for parameter in signature.parameters() {
+                encode::val_type(&mut entry, *parameter);
+            }
+            encode::u32(&mut entry, length_u32(signature.results().len()));
+            for result in signature.results() {
+                encode::val_type(&mut entry, *result);
+            }
+            types.push(&entry);
+        }
+
+        let sections = &self.sections;
+        types.write(out, section_id::TYPE);
+        sections.imports.write(out, section_id::IMPORT);
+        sections.functions.write(out, section_id::FUNC);
+        sections.tables.write(out, section_id::TABLE);
+        sections.memories.write(out, section_id::MEMORY);
+        sections.tags.write(out, section_id::TAG);
+        sections.globals.write(out, section_id::GLOBAL);
+        sections.exports.write(out, section_id::EXPORT);
+
+        if let Some(start) = sections.start {
+            let mut contents = Vec::new();
+            encode::u32(&mut contents, start);
+            encode::section(out, section_id::START, &contents);
+        }
+
+        sections.elements.write(out, section_id::ELEMENT);
+
+        if self.uses_data_count {
+            let mut contents = Vec::new();
+            encode::u32(&mut contents, sections.data.count);
+            encode::section(out, section_id::DATA_COUNT, &contents);
+        }
+
+        sections.code.write(out, section_id::CODE);
+        sections.data.write(out, section_id::DATA);
+
+        if !self.names.is_empty() {
+            self.names.write(out);
+        }
+    }
+}
+
+/// Encodes a module from the items of the text format.
+pub(super) fn encode<'a>(items: &[Item<'a>], end: usize) -> Result<Vec<u8>> {
+    let mut module = Module::new();
+    let mut fields = Cursor::new(items, end);
+
+    if let [Item::List(list)] = items {
+        if list.keyword() == Some("module") {
+            fields = Cursor::after_keyword(list);
+            if let Some(id) = fields.id() {
+                module.names.set_module_name(&id.text[1..]);
+            }
+        }
+    }
+
+    let mut lists = Vec::new();
+    while let Some(item) = fields.peek() {
+        match item {
+            Item::List(list) if list.keyword().is_some() => lists.push(list),
+            _ => return Err(fields.error("expected a module field")),
+        }
+        fields.next();
+    }
+
+    let indices = module.declare(&lists)?;
+    for (field, index) in lists.into_iter().zip(indices) {
+        let mut c = Cursor::after_keyword(field);
+        match field.keyword().unwrap_or_default() {
+            "type" => (),
+            "import" => {
+                let names = c;
+                c.name()?;
+                c.name()?;
+                let (kind, list) = c
+                    .peek_list()
+                    .ok_or_else(|| c.error("expected an import description"))?;
+
+                let mut description = Cursor::after_keyword(list);
+                description.id();
+                c.next();
+                c.finish()?;
+                module.import(names, kind, &mut description)?;
+            }
+            "func" => module.func(Header::parse(field), index)?,
+            "table" => module.table(Header::parse(field), index)?,
+            "memory" => module.memory(Header::parse(field), index)?,
+            "global" => module.global(Header::parse(field), index)?,
+            "tag" => module.tag(Header::parse(field), index)?,
+            "export" => module.export(c)?,
+            "start" => {
+                if module.sections.start.is_some() {
+                    return Err(ParseError::new(field.start, "multiple start functions"));
+                }
+
+                module.sections.start = Some(module.funcs.expect_index(&mut c)?);
+                c.finish()?;
+            }
+            "elem" => module.elem(c)?,
+            "data" => module.data(c)?,
+            unknown => {
+                return Err(ParseError::new(
+                    field.start,
+                    format!("unknown module field `{unknown}`"),
+                ))
+            }
+        }
+    }
+
+    let mut binary = Vec::new();
+    module.write(&mut binary);
+    Ok(binary)
+}
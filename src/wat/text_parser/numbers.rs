@@ -0,0 +1,267 @@
+//! Parses the [integer and floating-point literals](https://webassembly.github.io/spec/core/text/values.html)
+//! of the text format.
+
+use alloc::string::String;
+
+/// Returns `true` if the `text` is a non-empty sequence of digits, optionally separated by single
+/// underscores.
+fn is_digits(text: &str, radix: u32) -> bool {
+    !text.is_empty()
+        && !text.starts_with('_')
+        && !text.ends_with('_')
+        && !text.contains("__")
+        && text.chars().all(|c| c == '_' || c.is_digit(radix))
+}
+
+fn digits(text: &str, radix: u32) -> Option<u64> {
+    if !is_digits(text, radix) {
+        return None;
+    }
+
+    text.chars()
+        .filter_map(|c| c.to_digit(radix))
+        .try_fold(0u64, |value, digit| {
+            value
+                .checked_mul(u64::from(radix))?
+                .checked_add(u64::from(digit))
+        })
+}
+
+pub(super) fn u64(text: &str) -> Option<u64> {
+    match text.strip_prefix("0x") {
+        Some(hex) => digits(hex, 16),
+        None => digits(text, 10),
+    }
+}
+
+pub(super) fn u32(text: &str) -> Option<u32> {
+    u32::try_from(u64(text)?).ok()
+}
+
+/// Parses the hexadecimal digits of a `\u{...}` string escape.
+pub(super) fn hex_u32(text: &str) -> Option<u32> {
+    u32::try_from(digits(text, 16)?).ok()
+}
+
+fn split_sign(text: &str) -> (bool, &str) {
+    match text.as_bytes().first() {
+        Some(b'-') => (true, &text[1..]),
+        Some(b'+') => (false, &text[1..]),
+        _ => (false, text),
+    }
+}
+
+/// Parses an integer with the given number of `bits`, which can be written as either a signed or
+/// an unsigned value. The result is the two's complement representation of the integer.
+pub(super) fn int(text: &str, bits: u32) -> Option<u64> {
+    let (negative, magnitude) = split_sign(text);
+    let magnitude = u64(magnitude)?;
+    let max = u64::MAX >> (64 - bits);
+    if !negative {
+        Some(magnitude).filter(|value| *value <= max)
+    } else if magnitude <= 1 << (bits - 1) {
+        Some(magnitude.wrapping_neg() & max)
+    } else {
+        None
+    }
+}
+
+pub(super) fn i32(text: &str) -> Option<i32> {
+    let bits = u32::try_from(int(text, 32)?).ok()?;
+    Some(bits as i32)
+}
+
+pub(super) fn i64(text: &str) -> Option<i64> {
+    Some(int(text, 64)? as i64)
+}
+
+/// Describes the layout of a floating-point type.
+#[derive(Clone, Copy)]
+struct FloatFormat {
+    mantissa_bits: u32,
+    exponent_bits: u32,
+}
+
+struct FloatParts<'a> {
+    integer: &'a str,
+    fraction: &'a str,
+    exponent: Option<&'a str>,
+}
+
+impl<'a> FloatParts<'a> {
+    fn split(text: &'a str, radix: u32, exponent_markers: [char; 2]) -> Option<Self> {
+        let (mantissa, exponent) = match text.find(|c| exponent_markers.contains(&c)) {
+            Some(index) => (&text[..index], Some(&text[index + 1..])),
+            None => (text, None),
+        };
+
+        let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+        let valid = is_digits(integer, radix)
+            && (fraction.is_empty() || is_digits(fraction, radix))
+            && exponent.map_or(true, |exponent| is_digits(split_sign(exponent).1, 10));
+
+        Some(Self {
+            integer,
+            fraction,
+            exponent,
+        })
+        .filter(|_| valid)
+    }
+
+    /// Parses the exponent, saturating to a value large enough that any result would be zero or
+    /// out of range.
+    fn exponent(&self) -> i64 {
+        let (negative, digits) = split_sign(self.exponent.unwrap_or("0"));
+        let magnitude = digits
+            .chars()
+            .filter_map(|c| c.to_digit(10))
+            .fold(0i64, |value, digit| {
+                (value * 10 + i64::from(digit)).min(1 << 24)
+            });
+
+        if negative {
+            -magnitude
+        } else {
+            magnitude
+        }
+    }
+}
+
+/// Rounds a hexadecimal float to the nearest representable value, with ties rounding to even.
+fn hex_float(parts: &FloatParts<'_>, format: FloatFormat) -> Option<u64> {
+    let mut significand = 0u64;
+    let mut exponent = parts.exponent();
+    // Set if any non-zero digits did not fit in the significand
+    let mut sticky = false;
+
+    let integer = parts.integer.chars().filter_map(|c| c.to_digit(16));
+    for digit in integer {
+        if significand >> 60 == 0 {
+            significand = significand << 4 | u64::from(digit);
+        } else {
+            exponent += 4;
+            sticky |= digit != 0;
+        }
+    }
+
+    let fraction = parts.fraction.chars().filter_map(|c| c.to_digit(16));
+    for digit in fraction {
+        if significand >> 60 == 0 {
+            significand = significand << 4 | u64::from(digit);
+            exponent -= 4;
+        } else {
+            sticky |= digit != 0;
+        }
+    }
+
+    if significand == 0 {
+        return Some(0);
+    }
+
+    // The value is significand * 2^exponent, find the exponent of the least significant bit
+    let mantissa_bits = i64::from(format.mantissa_bits);
+    let bias = (1i64 << (format.exponent_bits - 1)) - 1;
+    let msb = 63 - i64::from(significand.leading_zeros());
+    let mut lsb_exponent = (msb + exponent - mantissa_bits).max(1 - bias - mantissa_bits);
+    let shift = lsb_exponent - exponent;
+
+    let mut rounded = if shift <= 0 {
+        significand << -shift
+    } else if shift > 64 {
+        0
+    } else {
+        let value = u128::from(significand);
+        let kept = u64::try_from(value >> shift).ok()?;
+        let remainder = value & ((1 << shift) - 1);
+        let half = 1u128 << (shift - 1);
+        let round_up = remainder > half || (remainder == half && (sticky || kept & 1 == 1));
+        kept + u64::from(round_up)
+    };
+
+    if rounded >> (mantissa_bits + 1) != 0 {
+        rounded >>= 1;
+        lsb_exponent += 1;
+    }
+
+    let biased_exponent = if rounded >> mantissa_bits == 0 {
+        0
+    } else {
+        lsb_exponent + mantissa_bits + bias
+    };
+
+    if biased_exponent >= (1 << format.exponent_bits) - 1 {
+        return None;
+    }
+
+    let mantissa = rounded & ((1 << mantissa_bits) - 1);
+    Some(u64::try_from(biased_exponent).ok()? << mantissa_bits | mantissa)
+}
+
+fn float(
+    text: &str,
+    format: FloatFormat,
+    decimal: impl FnOnce(&str) -> Option<u64>,
+) -> Option<u64> {
+    let (negative, magnitude) = split_sign(text);
+    let sign = u64::from(negative) << (format.mantissa_bits + format.exponent_bits);
+    let infinity = ((1 << format.exponent_bits) - 1) << format.mantissa_bits;
+
+    let bits = if magnitude == "inf" {
+        infinity
+    } else if magnitude == "nan" {
+        infinity | 1 << (format.mantissa_bits - 1)
+    } else if let Some(payload) = magnitude.strip_prefix("nan:0x") {
+        let payload = digits(payload, 16)?;
+        if payload == 0 || payload >> format.mantissa_bits != 0 {
+            return None;
+        }
+        infinity | payload
+    } else if let Some(hex) = magnitude.strip_prefix("0x") {
+        hex_float(&FloatParts::split(hex, 16, ['p', 'P'])?, format)?
+    } else {
+        let parts = FloatParts::split(magnitude, 10, ['e', 'E'])?;
+        let mut normalized = String::with_capacity(magnitude.len() + 4);
+        normalized.extend(parts.integer.chars().filter(|c| *c != '_'));
+        normalized.push('.');
+        normalized.extend(parts.fraction.chars().filter(|c| *c != '_'));
+        normalized.push_str("0e");
+        normalized.extend(parts.exponent.unwrap_or("0").chars().filter(|c| *c != '_'));
+        decimal(&normalized)?
+    };
+
+    Some(sign | bits)
+}
+
+/// Parses a 32-bit float, returning its bit pattern.
+pub(super) fn f32(text: &str) -> Option<u32> {
+    let format = FloatFormat {
+        mantissa_bits: 23,
+        exponent_bits: 8,
+    };
+
+    let bits = float(text, format, |decimal| {
+        let value = decimal
+            .parse::<f32>()
+            .ok()
+            .filter(|value| value.is_finite())?;
+        Some(u64::from(value.to_bits()))
+    })?;
+
+    u32::try_from(bits).ok()
+}
+
+/// Parses a 64-bit float, returning its bit pattern.
+pub(super) fn f64(text: &str) -> Option<u64> {
+    let format = FloatFormat {
+        mantissa_bits: 52,
+        exponent_bits: 11,
+    };
+
+    float(text, format, |decimal| {
+        let value = decimal
+            .parse::<f64>()
+            .ok()
+            .filter(|value| value.is_finite())?;
+        Some(value.to_bits())
+    })
+}
@@ -3,11 +3,16 @@ use crate::wat;
 impl<B: crate::input::Input> wat::Wat for crate::component::TypesComponent<B> {
     fn write(mut self, mut w: &mut wat::Writer) -> crate::parser::Parsed<()> {
         for i in (0u32..).flat_map(crate::index::TypeIdx::try_from) {
+            if i.to_u32() >= w.config().max_items() {
+                wat::write_omitted_items(self.remaining_count(), "types", w);
+                break;
+            }
+
             let result = self.parse(
                 move |params| {
                     w.open_paren();
-                    w.write_str("type ");
-                    wat::write_index(true, i, w);
+                    w.write_str("type");
+                    wat::write_declaration_id(i, w);
                     w.write_char(' ');
                     w.open_paren();
                     w.write_str("func ");
@@ -41,3 +46,24 @@ impl<B: crate::input::Input> wat::Wat for crate::component::TypesComponent<B> {
         Ok(())
     }
 }
+
+impl wat::Wat for crate::types::GlobalType {
+    fn write(self, w: &mut wat::Writer) -> crate::parser::Parsed<()> {
+        wat::write_global_type(self, w);
+        Ok(())
+    }
+}
+
+impl wat::Wat for &crate::types::TableType {
+    fn write(self, w: &mut wat::Writer) -> crate::parser::Parsed<()> {
+        wat::write_table_type(self, w);
+        Ok(())
+    }
+}
+
+impl wat::Wat for &crate::types::MemType {
+    fn write(self, w: &mut wat::Writer) -> crate::parser::Parsed<()> {
+        wat::write_mem_type(self, w);
+        Ok(())
+    }
+}
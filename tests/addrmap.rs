@@ -0,0 +1,67 @@
+#![cfg(feature = "alloc")]
+
+use wasmiter::{addrmap::AddressMap, index::FuncIdx, sections::id};
+
+#[test]
+fn section_and_function_offsets() {
+    let wasm = wat::parse_str(
+        r#"(module
+    (import "env" "a" (func))
+    (import "env" "b" (global i32))
+    (import "env" "c" (func))
+    (func i32.const 1 drop)
+    (func (local i32) nop))"#,
+    )
+    .unwrap();
+
+    let sections = wasmiter::parse_module_sections(wasm.as_slice()).unwrap();
+    let map = AddressMap::new(&sections).unwrap();
+    assert_eq!(map.section_count(), 4);
+    assert_eq!(map.function_count(), 2);
+
+    // Preamble and the id of the first section
+    assert_eq!(map.section(0), None);
+    assert_eq!(map.section(8), None);
+    assert_eq!(map.function(8), None);
+
+    let code = sections
+        .into_iter()
+        .map(Result::unwrap)
+        .find(|section| section.id() == id::CODE)
+        .unwrap();
+    let start = code.contents().base();
+
+    // Entry count
+    let location = map.section(start).unwrap();
+    assert_eq!(location.index(), 3);
+    assert_eq!(location.id(), id::CODE);
+    assert_eq!(location.offset(), 0);
+    assert_eq!(map.function(start), None);
+
+    // Size of the first entry, then its local declarations
+    assert_eq!(map.function(start + 1), None);
+    let first = map.function(start + 2).unwrap();
+    assert_eq!(first.index(), FuncIdx::from(2u32));
+    assert_eq!(first.offset(), 0);
+
+    // Entry count, size, locals, i32.const 1, drop, end, size, then the local declarations
+    let second = map.function(start + 8).unwrap();
+    assert_eq!(second.index(), FuncIdx::from(3u32));
+    assert_eq!(second.offset(), 0);
+    assert_eq!(map.function(start + 11).unwrap().offset(), 3);
+    assert_eq!(map.section(start + 11).unwrap().offset(), 11);
+
+    let end = u64::try_from(wasm.len()).unwrap();
+    assert_eq!(map.section(end - 1).unwrap().id(), id::CODE);
+    assert_eq!(map.function(end), None);
+    assert_eq!(map.section(end), None);
+}
+
+#[test]
+fn huge_code_section_entry_count() {
+    // A code section that claims to contain 2^32 - 1 entries
+    let mut wasm = b"\0asm\x01\0\0\0".to_vec();
+    wasm.extend_from_slice(&[10, 5, 0xFF, 0xFF, 0xFF, 0xFF, 0x0F]);
+    let sections = wasmiter::parse_module_sections(wasm.as_slice()).unwrap();
+    assert!(AddressMap::new(&sections).is_err());
+}
@@ -0,0 +1,538 @@
+use wasmiter::analysis;
+
+#[test]
+fn startup_cost() {
+    let wat = r#"(module
+    (import "env" "memory" (memory 1))
+    (memory 2 4)
+    (table 8 funcref)
+    (global i32 (i32.const 42))
+    (global (mut i64) (i64.const 0))
+    (func $f)
+    (start $f)
+    (elem (i32.const 0) $f $f $f)
+    (elem declare func $f)
+    (data (memory 1) (i32.const 16) "hello")
+    (data "passive"))
+"#;
+    let wasm = wat::parse_str(wat).unwrap();
+    let sections = wasmiter::parse_module_sections(wasm.as_slice()).unwrap();
+    let cost = analysis::startup_cost(&sections).unwrap();
+
+    assert_eq!(cost.active_data_segments(), 1);
+    assert_eq!(cost.active_data_bytes(), 5);
+    assert_eq!(cost.active_element_segments(), 1);
+    assert_eq!(cost.active_element_entries(), 3);
+    assert_eq!(cost.global_initializers(), 2);
+    assert_eq!(cost.memory_pages(), 2);
+    assert_eq!(cost.memory_bytes(), 2 * 65536);
+    assert_eq!(cost.table_elements(), 8);
+    assert_eq!(cost.start_function().map(|f| f.to_u32()), Some(0));
+}
+
+#[cfg(feature = "custom-page-sizes")]
+#[test]
+fn startup_cost_custom_page_size() {
+    let wasm = [
+        0, 0x61, 0x73, 0x6D, 1, 0, 0, 0, // preamble
+        5, 6, // memory section
+        2, // 2 memories
+        0b1000, 3, 0, // 3 pages of 1 byte each
+        0, 1, // 1 page of 64 KiB
+    ];
+    let sections = wasmiter::parse_module_sections(wasm.as_slice()).unwrap();
+    let cost = analysis::startup_cost(&sections).unwrap();
+
+    assert_eq!(cost.memory_pages(), 4);
+    assert_eq!(cost.memory_bytes(), 3 + 65536);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn stack_depth() {
+    let wat = r#"(module
+    (import "env" "f" (func $f (param i32 i32 i32) (result i32)))
+    (func $flat (result i32)
+        i32.const 1
+        i32.const 2
+        i32.const 3
+        call $f
+        i32.const 4
+        i32.add)
+    (func $nested (param i32) (result i32)
+        block (result i32)
+            loop (result i32)
+                local.get 0
+                if (result i32)
+                    i32.const 1
+                    i32.const 2
+                    br 2
+                else
+                    i32.const 3
+                end
+            end
+        end)
+    (func $unreachable
+        unreachable
+        i32.add
+        drop)
+)
+"#;
+    let wasm = wat::parse_str(wat).unwrap();
+    let sections = wasmiter::parse_module_sections(wasm.as_slice()).unwrap();
+    let depths = analysis::stack_depth(&sections).unwrap();
+
+    let summary = depths
+        .iter()
+        .map(|d| (d.function().to_u32(), d.max_value_stack(), d.max_nesting()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(summary, [(1, 3, 0), (2, 2, 3), (3, 1, 0)]);
+}
+
+#[cfg(all(feature = "alloc", feature = "exceptions"))]
+#[test]
+fn stack_depth_catch_all() {
+    let wat = r#"(module
+    (func (result i32)
+        try (result i32)
+            i32.const 1
+        catch_all
+            i32.const 2
+        end)
+)
+"#;
+    let wasm = wat::parse_str(wat).unwrap();
+    let sections = wasmiter::parse_module_sections(wasm.as_slice()).unwrap();
+    let depths = analysis::stack_depth(&sections).unwrap();
+
+    assert_eq!(depths.len(), 1);
+    assert_eq!(depths[0].max_value_stack(), 1);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn constant_globals() {
+    use analysis::ConstantValue;
+    use wasmiter::index::GlobalIdx;
+
+    let wat = r#"(module
+    (import "env" "base" (global $base i32))
+    (import "env" "counter" (global $counter (mut i32)))
+    (global $answer i32 (i32.const 42))
+    (global $offset i32 (global.get $base))
+    (global $mutable (mut i64) (i64.const 1))
+    (global $from_mutable i32 (global.get $counter))
+    (global $pi f64 (f64.const 3.5))
+    (global $callback funcref (ref.func $f))
+    (func $f))
+"#;
+    let wasm = wat::parse_str(wat).unwrap();
+    let sections = wasmiter::parse_module_sections(wasm.as_slice()).unwrap();
+    let constants = analysis::constant_globals(&sections).unwrap();
+
+    assert_eq!(
+        constants.into_iter().collect::<Vec<_>>(),
+        vec![
+            (GlobalIdx::from(2u32), ConstantValue::I32(42)),
+            (
+                GlobalIdx::from(3u32),
+                ConstantValue::Imported(GlobalIdx::from(0u32))
+            ),
+            (GlobalIdx::from(6u32), ConstantValue::F64(3.5)),
+            (
+                GlobalIdx::from(7u32),
+                ConstantValue::RefFunc(wasmiter::index::FuncIdx::from(0u32))
+            ),
+        ]
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn lint_custom_sections() {
+    use analysis::{CustomSectionBudget, CustomSectionLint};
+
+    let wat = r#"(module
+    (@custom ".debug_info" "0123456789abcdef")
+    (@custom "producers" "xy")
+    (@custom ".debug_info" "0123"))
+"#;
+    let wasm = wat::parse_str(wat).unwrap();
+    let sections = wasmiter::parse_module_sections(wasm.as_slice()).unwrap();
+    let budget = CustomSectionBudget::new()
+        .with_max_section_size(16)
+        .with_max_total_size(32);
+    let lints = analysis::lint_custom_sections(&sections, &budget).unwrap();
+
+    assert!(matches!(
+        &lints[..],
+        [
+            CustomSectionLint::SectionTooLarge { name, size: 28, limit: 16, .. },
+            CustomSectionLint::DuplicateName { .. },
+            CustomSectionLint::TotalTooLarge { size: 56, limit: 32 },
+        ] if name == ".debug_info"
+    ));
+}
+
+#[test]
+fn classify_data() {
+    use analysis::DataContents;
+    use wasmiter::input::Window;
+
+    let classify = |bytes: &[u8]| {
+        analysis::classify_data(&Window::with_offset_and_length(
+            bytes,
+            0,
+            bytes.len() as u64,
+        ))
+        .unwrap()
+    };
+
+    let mut text = "a".repeat(255);
+    text.push_str("\u{E9}t\u{E9}\0");
+    let mut state = 0x2545F491u32;
+    let random = (0..4096)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state.to_le_bytes()[0]
+        })
+        .collect::<Vec<u8>>();
+
+    assert_eq!(classify(&[]), DataContents::Empty);
+    assert_eq!(classify(&[0; 300]), DataContents::ZeroFilled);
+    assert_eq!(classify(text.as_bytes()), DataContents::Text);
+    assert_eq!(classify(&random), DataContents::HighEntropy);
+    assert_eq!(classify(&[1, 2, 3, 0, 0, 0]), DataContents::Binary);
+
+    let mut decoded = String::new();
+    let window = Window::with_offset_and_length(text.as_bytes(), 0, text.len() as u64);
+    analysis::decode_utf8(&window, |chunk| {
+        match chunk {
+            analysis::Utf8Chunk::Valid(valid) => decoded.push_str(valid),
+            analysis::Utf8Chunk::Invalid(invalid) => panic!("invalid UTF-8 {invalid:?}"),
+        }
+        Ok(())
+    })
+    .unwrap();
+    assert_eq!(decoded, text);
+}
+
+#[test]
+fn hash_canonical_ignores_custom_sections() {
+    let hash = |wat: &str| {
+        let wasm = wat::parse_str(wat).unwrap();
+        let sections = wasmiter::parse_module_sections(wasm.as_slice()).unwrap();
+        analysis::hash_canonical(&sections).unwrap()
+    };
+
+    let plain = hash("(module (func (export \"f\") (result i32) i32.const 1))");
+    let named = hash("(module $m (func $f (export \"f\") (result i32) i32.const 1))");
+    let different = hash("(module (func (export \"f\") (result i32) i32.const 2))");
+    assert_eq!(plain, named);
+    assert_ne!(plain, different);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn check_imports() {
+    use analysis::{ExternType, HostImports, ImportMismatch, TypeDifference};
+    use wasmiter::types::*;
+
+    let wat = r#"(module
+    (import "env" "call" (func (param i32 i64) (result i32)))
+    (import "env" "memory" (memory 1 2))
+    (import "env" "counter" (global (mut i32)))
+    (import "env" "table" (table 1 funcref)))
+"#;
+    let wasm = wat::parse_str(wat).unwrap();
+    let sections = wasmiter::parse_module_sections(wasm.as_slice()).unwrap();
+
+    let unbounded = Limits::new(1, None, Sharing::Unshared, IdxType::I32).unwrap();
+    let mut host = HostImports::new();
+    host.insert(
+        "env",
+        "call",
+        ExternType::Func(FuncType::new([ValType::I32, ValType::F32], [ValType::I32])),
+    );
+    host.insert("env", "memory", ExternType::Memory(unbounded));
+    host.insert(
+        "env",
+        "counter",
+        ExternType::Global(GlobalType::new(GlobalMutability::Variable, ValType::I32)),
+    );
+    host.insert("env", "unused", ExternType::Memory(unbounded));
+
+    let mismatches = analysis::check_imports(&sections, &host).unwrap();
+    assert_eq!(mismatches.len(), 4);
+
+    assert!(matches!(
+        &mismatches[0],
+        ImportMismatch::Mismatched { name, differences, .. }
+            if name == "call" && differences == &[TypeDifference::Parameter {
+                index: 1,
+                required: ValType::I64,
+                provided: ValType::F32,
+            }]
+    ));
+    assert!(matches!(
+        &mismatches[1],
+        ImportMismatch::Mismatched { name, differences, .. }
+            if name == "memory" && matches!(differences[..], [TypeDifference::Limits { .. }])
+    ));
+    assert!(matches!(
+        &mismatches[2],
+        ImportMismatch::Missing { name, required: ExternType::Table(_), .. } if name == "table"
+    ));
+    assert!(matches!(
+        &mismatches[3],
+        ImportMismatch::Extra { name, .. } if name == "unused"
+    ));
+
+    assert_eq!(
+        mismatches[0].to_string(),
+        "import \"env\" \"call\" requires (func (param i32 i64) (result i32)), but host provides \
+        (func (param i32 f32) (result i32)); expected parameter 1 to be i64, but got f32"
+    );
+}
+
+#[test]
+fn contains_opcode() {
+    use wasmiter::{
+        component::KnownSection,
+        instruction_set::{InstructionOpcode, Opcode},
+    };
+
+    let wat = r#"(module
+    (import "env" "f" (func))
+    (func (local i32 i64)
+        i32.const 1
+        drop)
+    (func
+        nop
+        call 0)
+)
+"#;
+    let wasm = wat::parse_str(wat).unwrap();
+    let sections = wasmiter::parse_module_sections(wasm.as_slice()).unwrap();
+    let code = sections
+        .filter_map(|result| KnownSection::interpret(result.unwrap()).ok())
+        .find_map(|known| match known.unwrap() {
+            KnownSection::Code(code) => Some(code.with_function_indices(1)),
+            _ => None,
+        })
+        .unwrap();
+
+    let (index, offset) = analysis::contains_opcode(code, |opcode| {
+        opcode == InstructionOpcode::Byte(Opcode::Call)
+    })
+    .unwrap()
+    .unwrap();
+
+    assert_eq!(index.to_u32(), 2);
+    assert_eq!(wasm[usize::try_from(offset).unwrap()], Opcode::Call as u8);
+
+    let atomics =
+        analysis::contains_opcode(code, |opcode| matches!(opcode, InstructionOpcode::FE(_)))
+            .unwrap();
+
+    assert_eq!(atomics, None);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn feature_detector() {
+    use analysis::FeatureDetector;
+    use wasmiter::features::Feature;
+
+    let detect = |wat: &str| {
+        let wasm = wat::parse_str(wat).unwrap();
+        let mut detector = FeatureDetector::new();
+        wasmiter::visit::visit_module(wasm.as_slice(), &mut detector).unwrap();
+        detector.features().collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        detect(
+            r#"(module
+    (func (param f32) (result i32)
+        local.get 0
+        i32.trunc_sat_f32_s
+        i32.extend8_s))"#
+        ),
+        [Feature::NonTrappingFloatToInt, Feature::SignExtension]
+    );
+
+    #[cfg(all(feature = "simd", feature = "tail-call"))]
+    assert_eq!(
+        detect(
+            r#"(module
+    (global $g (mut i32) (i32.const 0))
+    (export "g" (global $g))
+    (func (result v128)
+        block (result i32 i32)
+            i32.const 1
+            i32.const 2
+        end
+        drop
+        drop
+        v128.const i64x2 0 0)
+    (func (param i32)
+        local.get 0
+        return_call 1))"#
+        ),
+        [
+            Feature::MutableGlobal,
+            Feature::MultiValue,
+            Feature::Simd,
+            Feature::TailCall
+        ]
+    );
+
+    // A single memory accessed only through memory 0 does not require multi-memory
+    assert_eq!(
+        detect(
+            r#"(module
+    (memory 1)
+    (func (param i32) (result i32)
+        local.get 0
+        i32.load))"#
+        ),
+        []
+    );
+
+    // An imported memory along with a defined memory requires multi-memory
+    assert_eq!(
+        detect(
+            r#"(module
+    (import "env" "memory" (memory 1))
+    (memory 1)
+    (func (param i32)
+        local.get 0
+        i32.const 0
+        i32.store 1))"#
+        ),
+        [Feature::MultiMemory]
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn export_index() {
+    use wasmiter::{
+        component::ExportKind,
+        index::{FuncIdx, GlobalIdx, MemIdx, TableIdx},
+    };
+
+    let wat = r#"(module
+    (import "env" "memory" (memory 1))
+    (import "env" "print" (func $print (param i32)))
+    (import "env" "counter" (global $counter (mut i32)))
+    (table $table 1 funcref)
+    (global $limit i32 (i32.const 10))
+    (func $run)
+    (export "memory" (memory 0))
+    (export "run" (func $run))
+    (export "main" (func $run))
+    (export "print" (func $print))
+    (export "table" (table $table))
+    (export "limit" (global $limit)))
+"#;
+    let wasm = wat::parse_str(wat).unwrap();
+    let sections = wasmiter::parse_module_sections(wasm.as_slice()).unwrap();
+    let exports = analysis::ExportIndex::new(&sections).unwrap();
+
+    assert_eq!(exports.len(), 6);
+    assert_eq!(exports.get("missing"), None);
+    assert_eq!(
+        exports.get("table"),
+        Some(ExportKind::Table(TableIdx::from(0u32)))
+    );
+    assert_eq!(
+        exports.get("limit"),
+        Some(ExportKind::Global(GlobalIdx::from(1u32)))
+    );
+
+    let run = ExportKind::Function(FuncIdx::from(1u32));
+    assert_eq!(exports.get("main"), Some(run));
+    assert_eq!(exports.names_of(run), &["run", "main"]);
+    assert!(exports
+        .names_of(ExportKind::Function(FuncIdx::from(2u32)))
+        .is_empty());
+    assert_eq!(
+        exports.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+        ["limit", "main", "memory", "print", "run", "table"]
+    );
+
+    let imports = exports.imports();
+    assert_eq!(imports.functions(), 1);
+    assert_eq!(imports.memories(), 1);
+    assert_eq!(imports.globals(), 1);
+    assert_eq!(imports.tables(), 0);
+    assert_eq!(imports.tags(), 0);
+    assert!(imports.is_imported(ExportKind::Memory(MemIdx::from(0u32))));
+    assert!(imports.is_imported(exports.get("print").unwrap()));
+    assert!(!imports.is_imported(run));
+    assert!(!imports.is_imported(exports.get("limit").unwrap()));
+    assert!(!imports.is_imported(exports.get("table").unwrap()));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn size_profile() {
+    let wat = r#"(module
+    (import "env" "f" (func $imported))
+    (func $small)
+    (func $large (result i64) i64.const 1 i64.const 2 i64.add)
+    (func (result i32) i32.const 0)
+    (memory 1)
+    (data (i32.const 0) "hello world"))
+"#;
+    let wasm = wat::parse_str(wat).unwrap();
+    let sections = wasmiter::parse_module_sections(wasm.as_slice()).unwrap();
+    let profile = analysis::size_profile(&sections, true).unwrap();
+
+    assert_eq!(profile.total_size(), wasm.len() as u64);
+    assert_eq!(
+        profile.total_size(),
+        8 + profile
+            .sections()
+            .iter()
+            .map(|section| section.size())
+            .sum::<u64>()
+    );
+
+    let sizes = profile
+        .sections()
+        .iter()
+        .map(|s| s.size())
+        .collect::<Vec<_>>();
+    assert!(sizes.windows(2).all(|pair| pair[0] >= pair[1]));
+    assert_eq!(profile.sections()[0].name(), Some("name"));
+
+    let names = profile
+        .sections()
+        .iter()
+        .filter_map(|section| section.name())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["name"]);
+
+    let functions = profile
+        .functions()
+        .iter()
+        .map(|f| (f.index().to_u32(), f.name(), f.size()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        functions,
+        [(2, Some("large"), 7), (3, None, 4), (1, Some("small"), 2)]
+    );
+
+    let text = profile.to_string();
+    assert!(text.contains("  func[2] $large\n"));
+    assert!(text.contains("  Custom \"name\"\n"));
+
+    let unnamed = analysis::size_profile(&sections, false).unwrap();
+    assert!(unnamed.functions().iter().all(|f| f.name().is_none()));
+}
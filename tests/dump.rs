@@ -0,0 +1,86 @@
+use wasmiter::dump;
+
+#[test]
+fn headers_and_details() {
+    let wasm = wat::parse_str(
+        r#"(module
+    (import "env" "log" (func (param i32)))
+    (import "env" "memory" (memory 1 2))
+    (table 2 funcref)
+    (func (export "run") (param i32 i64) (result i32)
+        local.get 0)
+    (func (result i32 i32)
+        i32.const 0
+        i32.const 1)
+    (elem (i32.const 0) 1 2)
+    (data (i32.const 0) "hello")
+    (data "passive")
+    (@custom "notes" "hello"))"#,
+    )
+    .unwrap();
+
+    let sections = wasmiter::parse_module_sections(wasm.as_slice()).unwrap();
+    let headers = dump::headers(&sections).to_string();
+    let lines = headers
+        .lines()
+        .map(|line| {
+            let (name, rest) = line.trim_start().split_once(' ').unwrap();
+            (name, rest.rsplit_once(')').unwrap().1)
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        lines,
+        [
+            ("Type", " count: 3"),
+            ("Import", " count: 2"),
+            ("Function", " count: 2"),
+            ("Table", " count: 1"),
+            ("Export", " count: 1"),
+            ("Elem", " count: 1"),
+            ("Code", " count: 2"),
+            ("Data", " count: 2"),
+            ("Custom", " \"notes\""),
+        ]
+    );
+
+    assert_eq!(
+        dump::details(&sections).to_string(),
+        r#"Type[3]:
+ - type[0] (i32) -> nil
+ - type[1] (i32, i64) -> i32
+ - type[2] () -> (i32, i32)
+Import[2]:
+ - func[0] sig=0 <- env.log
+ - memory[0] pages: initial=1 max=2 <- env.memory
+Function[2]:
+ - func[1] sig=1
+ - func[2] sig=2
+Table[1]:
+ - table[0] type=funcref initial=2
+Export[1]:
+ - func[1] -> "run"
+Elem[1]:
+ - segment[0] active table=0 count=2
+Code[2]:
+ - func[1] size=4
+ - func[2] size=6
+Data[2]:
+ - segment[0] active memory=0 size=5
+ - segment[1] passive size=7
+Custom:
+ - name: "notes"
+"#
+    );
+}
+
+#[test]
+fn dump_reports_errors() {
+    // Type section claiming to contain 5 types, but the section is empty
+    let wasm = b"\0asm\x01\0\0\0\x01\x01\x05";
+    let sections = wasmiter::parse_module_sections(wasm.as_slice()).unwrap();
+    let headers = dump::headers(&sections).to_string();
+    assert!(headers.contains("count: 5"), "{headers}");
+
+    let details = dump::details(&sections).to_string();
+    assert!(details.starts_with("Type[5]:\n\nerror: "), "{details}");
+}
@@ -0,0 +1,441 @@
+#![cfg(feature = "alloc")]
+
+use wasmiter::edit;
+
+#[test]
+fn merge_resolves_imports() {
+    let a = wat::parse_str(
+        r#"(module
+    (import "env" "log" (func $log (param i32)))
+    (import "b" "double" (func $double (param i32) (result i32)))
+    (memory (export "memory") 1)
+    (global $counter (mut i32) (i32.const 0))
+    (func (export "run") (param i32)
+        local.get 0
+        call $double
+        call $log
+        global.get $counter
+        i32.load offset=4
+        drop)
+    (data (i32.const 8) "hi"))
+"#,
+    )
+    .unwrap();
+
+    let b = wat::parse_str(
+        r#"(module
+    (import "env" "log" (func $log (param i32)))
+    (global $factor i32 (i32.const 2))
+    (table 1 funcref)
+    (func $double (export "double") (param i32) (result i32)
+        local.get 0
+        global.get $factor
+        i32.mul)
+    (func $log_twice (param i32)
+        local.get 0
+        call $log
+        local.get 0
+        call $double
+        call $log)
+    (elem (i32.const 0) $log_twice))
+"#,
+    )
+    .unwrap();
+
+    let merged = edit::merge(&[a.as_slice(), b.as_slice()]).unwrap();
+    insta::assert_snapshot!(wasmprinter::print_bytes(merged).unwrap());
+}
+
+#[test]
+fn merge_duplicate_export() {
+    let a = wat::parse_str(r#"(module (func (export "f")))"#).unwrap();
+    let b = wat::parse_str(r#"(module (func (export "f")))"#).unwrap();
+    assert!(edit::merge(&[a.as_slice(), b.as_slice()]).is_err());
+}
+
+#[test]
+fn merge_index_space_overflow() {
+    // Each module declares 2^31 data segments
+    let mut data = b"\0asm\x01\0\0\0".to_vec();
+    data.extend_from_slice(&[11, 5, 0x80, 0x80, 0x80, 0x80, 0x08]);
+    let error = edit::merge(&[data.as_slice(); 3]).unwrap_err();
+    assert_eq!(error.code(), wasmiter::parser::ErrorCode::MergeConflict);
+
+    // Declared counts of other definitions are not trusted
+    let mut tables = b"\0asm\x01\0\0\0".to_vec();
+    tables.extend_from_slice(&[4, 5, 0xFF, 0xFF, 0xFF, 0xFF, 0x0F]);
+    assert!(edit::merge(&[tables.as_slice()]).is_err());
+}
+
+/// Merges a module importing `import` with a module exporting `export`, returning the error
+/// message if the import could not be resolved.
+fn merge_import(import: &str, export: &str) -> Result<(), String> {
+    let a = wat::parse_str(format!(r#"(module (import "b" "x" {import}))"#)).unwrap();
+    let b = wat::parse_str(format!(r#"(module {export})"#)).unwrap();
+    edit::merge(&[a.as_slice(), b.as_slice()])
+        .map(|_| ())
+        .map_err(|error| format!("{error:#}"))
+}
+
+#[test]
+fn merge_global_import_type_mismatch() {
+    let mismatch = Err(String::from(
+        "could not merge modules, global import resolved to global of different type\n",
+    ));
+    let export = r#"(global (export "x") i32 (i32.const 0))"#;
+    assert_eq!(merge_import("(global i32)", export), Ok(()));
+    assert_eq!(merge_import("(global i64)", export), mismatch);
+    assert_eq!(merge_import("(global (mut i32))", export), mismatch);
+}
+
+#[test]
+fn merge_table_import_type_mismatch() {
+    let mismatch = Err(String::from(
+        "could not merge modules, table import resolved to table of different type\n",
+    ));
+    let export = r#"(table (export "x") 2 10 funcref)"#;
+    assert_eq!(merge_import("(table 1 funcref)", export), Ok(()));
+    assert_eq!(merge_import("(table 1 externref)", export), mismatch);
+    assert_eq!(merge_import("(table 3 funcref)", export), mismatch);
+    assert_eq!(merge_import("(table 2 5 funcref)", export), mismatch);
+}
+
+#[test]
+fn merge_memory_import_type_mismatch() {
+    let mismatch = Err(String::from(
+        "could not merge modules, memory import resolved to memory of different type\n",
+    ));
+    let export = r#"(memory (export "x") 2)"#;
+    assert_eq!(merge_import("(memory 1)", export), Ok(()));
+    assert_eq!(merge_import("(memory 3)", export), mismatch);
+    assert_eq!(merge_import("(memory 1 4)", export), mismatch);
+    assert_eq!(merge_import("(memory i64 1)", export), mismatch);
+}
+
+#[test]
+fn merge_tag_import_type_mismatch() {
+    let mismatch = Err(String::from(
+        "could not merge modules, tag import resolved to tag of different type\n",
+    ));
+    let export = r#"(tag (export "x") (param i32))"#;
+    assert_eq!(merge_import("(tag (param i32))", export), Ok(()));
+    assert_eq!(merge_import("(tag (param i64))", export), mismatch);
+}
+
+#[test]
+#[cfg(feature = "function-references")]
+fn merge_remaps_typed_select() {
+    let a = wat::parse_str(r#"(module (type (func (param f32))) (func (type 0)))"#).unwrap();
+    let b = wat::parse_str(
+        r#"(module
+    (type $t (func))
+    (func $f (type $t))
+    (elem declare func $f)
+    (func (param i32)
+        ref.func $f
+        ref.func $f
+        local.get 0
+        select (result (ref $t))
+        drop))
+"#,
+    )
+    .unwrap();
+
+    let merged = edit::merge(&[a.as_slice(), b.as_slice()]).unwrap();
+    let printed = wasmprinter::print_bytes(merged).unwrap();
+    assert!(printed.contains("select (result (ref 1))"), "{printed}");
+}
+
+#[cfg(feature = "exceptions")]
+#[test]
+fn merge_remaps_catch_clause_tags() {
+    let a = wat::parse_str(r#"(module (tag (param i64)))"#).unwrap();
+    let b = wat::parse_str(
+        r#"(module
+    (tag $e (param i32))
+    (func (result i32)
+        block $handler (result i32)
+            try_table (result i32) (catch $e $handler)
+                i32.const 1
+            end
+        end))
+"#,
+    )
+    .unwrap();
+
+    let merged = edit::merge(&[a.as_slice(), b.as_slice()]).unwrap();
+    wasmiter::validate::validate_module(merged.as_slice()).unwrap();
+    let printed = wasmiter::parse_module_sections(merged.as_slice())
+        .unwrap()
+        .display_module()
+        .to_string();
+    assert!(
+        printed.contains("try_table (result i32) (catch 1 0)"),
+        "{printed}"
+    );
+}
+
+#[test]
+fn write_large_custom_section() {
+    let payload = vec![0xAB; 300];
+    let mut module = b"\0asm\x01\0\0\0".to_vec();
+    edit::write_custom_section(&mut module, "debug", &payload);
+    assert_eq!(
+        module.len() as u64,
+        8 + edit::custom_section_size("debug", 300)
+    );
+
+    let sections = wasmiter::parse_module_sections(module.as_slice()).unwrap();
+    let section = sections.into_iter().next().unwrap().unwrap();
+    let custom = wasmiter::custom::CustomSection::try_from_section(section)
+        .unwrap()
+        .unwrap();
+    assert!(custom.name().try_eq_str("debug").unwrap());
+    assert_eq!(custom.contents().length(), 300);
+}
+
+#[test]
+fn name_section_matches_wat() {
+    use wasmiter::input::Input as _;
+
+    let named = wat::parse_str(
+        r#"(module $example
+    (func $first (param $a i32) (local $b i64))
+    (func $second (local $c f32) (local $d f64)))
+"#,
+    )
+    .unwrap();
+
+    let sections = wasmiter::parse_module_sections(named.as_slice()).unwrap();
+    let expected = sections
+        .into_iter()
+        .filter_map(|result| {
+            let section = result.unwrap();
+            let custom = wasmiter::custom::CustomSection::try_from_section(section).ok()?;
+            let custom = custom.unwrap();
+            assert!(custom.name().try_eq_str("name").unwrap());
+            let contents = custom.contents();
+            let mut bytes = vec![0; usize::try_from(contents.length()).unwrap()];
+            contents.read_exact_at(contents.base(), &mut bytes).unwrap();
+            Some(bytes)
+        })
+        .next()
+        .unwrap();
+
+    let mut names = edit::NameSectionBuilder::new();
+    names.insert_local_name(1u32.into(), 1u32.into(), "d");
+    names.insert_function_name(1u32.into(), "second");
+    names.insert_local_name(0u32.into(), 1u32.into(), "b");
+    names.insert_local_name(1u32.into(), 0u32.into(), "c");
+    names.insert_function_name(0u32.into(), "first");
+    names.insert_local_name(0u32.into(), 0u32.into(), "a");
+    names.set_module_name("example");
+
+    let mut actual = Vec::new();
+    names.write_contents(&mut actual);
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn rename_exports_and_imports() {
+    let module = wat::parse_str(
+        r#"(module
+    (import "env" "log" (func (param i32)))
+    (import "wasi" "exit" (func (param i32)))
+    (func (export "run") (param i32) local.get 0 call 0)
+    (global (export "count") i32 (i32.const 1))
+    (@custom "extra" "data"))
+"#,
+    )
+    .unwrap();
+
+    let expected = wat::parse_str(
+        r#"(module
+    (import "host" "log" (func (param i32)))
+    (import "wasi" "exit" (func (param i32)))
+    (func (export "a:main") (param i32) local.get 0 call 0)
+    (global (export "a:count") i32 (i32.const 1))
+    (@custom "extra" "data"))
+"#,
+    )
+    .unwrap();
+
+    let mut renames = edit::RenameMap::new().with_export_prefix("a:");
+    renames.insert_export("run", "main");
+    renames.insert_import_module("env", "host");
+    assert_eq!(edit::rename(module.as_slice(), &renames).unwrap(), expected);
+
+    renames.insert_export("count", "run");
+    renames.insert_export("run", "run");
+    assert!(edit::rename(module.as_slice(), &renames).is_err());
+}
+
+#[test]
+fn name_section_builder_from_section() {
+    use wasmiter::custom::{name::NameSectionBuilder, CustomSection, KnownCustomSection};
+    use wasmiter::input::Input as _;
+
+    fn name_section(wasm: &[u8]) -> Vec<u8> {
+        wasmiter::parse_module_sections(wasm)
+            .unwrap()
+            .into_iter()
+            .find_map(|result| {
+                let custom = CustomSection::try_from_section(result.unwrap()).ok()?;
+                let contents = *custom.unwrap().contents();
+                let mut bytes = vec![0; usize::try_from(contents.length()).unwrap()];
+                contents.read_exact_at(contents.base(), &mut bytes).unwrap();
+                Some(bytes)
+            })
+            .unwrap()
+    }
+
+    let original = wat::parse_str(
+        r#"(module $example
+    (tag $oops)
+    (func $first (param $a i32) (local $b i64))
+    (func $second))"#,
+    )
+    .unwrap();
+
+    // An instrumented version of the module, with a function inserted at the end
+    let expected = wat::parse_str(
+        r#"(module $example
+    (tag $oops)
+    (func $first (param $a i32) (local $b i64))
+    (func $second)
+    (func $injected (param $counter i32)))"#,
+    )
+    .unwrap();
+
+    let sections = wasmiter::parse_module_sections(original.as_slice()).unwrap();
+    let custom = sections
+        .into_iter()
+        .find_map(|result| CustomSection::try_from_section(result.unwrap()).ok())
+        .unwrap()
+        .unwrap();
+    let names = match KnownCustomSection::interpret(custom) {
+        Ok(KnownCustomSection::Name(names)) => names,
+        _ => unreachable!(),
+    };
+
+    let mut names = NameSectionBuilder::from_section(names).unwrap();
+    names.extend_function_names([(2u32.into(), "injected")]);
+    names.extend_local_names(2u32.into(), [(0u32.into(), "counter")]);
+
+    let mut actual = Vec::new();
+    names.write_contents(&mut actual);
+    assert_eq!(actual, name_section(&expected));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn transform_preserves_section_bytes() {
+    use wasmiter::edit::SectionAction;
+
+    let mut module = b"\0asm\x01\0\0\0".to_vec();
+    // Type section with no types, and a size that is not minimally encoded
+    let types = [1u8, 0x81, 0x80, 0x80, 0x80, 0x00, 0x00];
+    module.extend_from_slice(&types);
+    edit::write_custom_section(&mut module, "first", b"abc");
+    // Function section with no functions
+    module.extend_from_slice(&[3, 1, 0]);
+    edit::write_custom_section(&mut module, "second", &[]);
+
+    let mut stripped = Vec::new();
+    let written = edit::strip_custom_sections(module.as_slice(), &mut stripped).unwrap();
+    assert_eq!(written, stripped.len() as u64);
+    let mut expected = b"\0asm\x01\0\0\0".to_vec();
+    expected.extend_from_slice(&types);
+    expected.extend_from_slice(&[3, 1, 0]);
+    assert_eq!(stripped, expected);
+
+    let mut replacement = Vec::new();
+    edit::write_custom_section(&mut replacement, "replaced", b"xyz");
+    let mut ids = Vec::new();
+    let mut replaced = Vec::new();
+    edit::transform(module.as_slice(), &mut replaced, |section| {
+        ids.push(section.id());
+        Ok(match section.id() {
+            3 => SectionAction::Remove,
+            0 if section.length() == 9 => SectionAction::Replace(replacement.clone()),
+            _ => SectionAction::Keep,
+        })
+    })
+    .unwrap();
+    assert_eq!(ids, [1, 0, 3, 0]);
+
+    let mut expected = b"\0asm\x01\0\0\0".to_vec();
+    expected.extend_from_slice(&types);
+    expected.extend_from_slice(&replacement);
+    edit::write_custom_section(&mut expected, "second", &[]);
+    assert_eq!(replaced, expected);
+
+    let mut unchanged = Vec::new();
+    edit::transform(module.as_slice(), &mut unchanged, |_| {
+        Ok(SectionAction::Keep)
+    })
+    .unwrap();
+    assert_eq!(unchanged, module);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn insert_custom_section() {
+    use wasmiter::edit::SectionAction;
+
+    let mut module = b"\0asm\x01\0\0\0".to_vec();
+    module.extend_from_slice(&[1, 1, 0]);
+    module.extend_from_slice(&[3, 1, 0]);
+
+    let mut build_id = Vec::new();
+    edit::write_custom_section(&mut build_id, "build_id", &[7; 200]);
+    // The size of the payload requires a multi-byte LEB128 encoding
+    assert_eq!(&build_id[..3], &[0, 0xD1, 0x01]);
+
+    let with_section_at = |position| {
+        let mut out = Vec::new();
+        let written = edit::insert_custom_section(
+            module.as_slice(),
+            &mut out,
+            position,
+            "build_id",
+            &[7; 200],
+        )
+        .unwrap();
+        assert_eq!(written, out.len() as u64);
+        out
+    };
+
+    let mut expected = module[..8].to_vec();
+    expected.extend_from_slice(&build_id);
+    expected.extend_from_slice(&module[8..]);
+    assert_eq!(with_section_at(0), expected);
+
+    let mut expected = module[..11].to_vec();
+    expected.extend_from_slice(&build_id);
+    expected.extend_from_slice(&module[11..]);
+    assert_eq!(with_section_at(1), expected);
+
+    let mut expected = module.clone();
+    expected.extend_from_slice(&build_id);
+    assert_eq!(with_section_at(2), expected);
+    assert_eq!(with_section_at(usize::MAX), expected);
+
+    let mut after = Vec::new();
+    edit::transform(module.as_slice(), &mut after, |section| {
+        Ok(if section.id() == 1 {
+            SectionAction::InsertAfter(build_id.clone())
+        } else {
+            SectionAction::Keep
+        })
+    })
+    .unwrap();
+    assert_eq!(after, with_section_at(1));
+
+    let sections = wasmiter::parse_module_sections(after.as_slice()).unwrap();
+    let ids = sections
+        .into_iter()
+        .map(|section| section.unwrap().id())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, [1, 0, 3]);
+}
@@ -0,0 +1,49 @@
+//! Separate test binary, since the maximum context length is shared by all errors in a process.
+
+#![cfg(feature = "alloc")]
+
+struct Nothing;
+
+impl wasmiter::visit::ModuleVisitor for Nothing {}
+
+#[test]
+fn context_is_capped() {
+    // Module containing a function type with an invalid parameter type
+    let wasm = [
+        0, 0x61, 0x73, 0x6D, 1, 0, 0, 0, // preamble
+        1, 4, 1, 0x60, 1, 0xFF, // type section
+    ];
+
+    let parse = || {
+        let error = wasmiter::visit::visit_module(wasm.as_slice(), &mut Nothing).unwrap_err();
+        format!("{error:#}")
+    };
+
+    let full = parse();
+    assert!(
+        full.ends_with(concat!(
+            "- could not parse signed 33-bit integer\n",
+            "- block type tag or index\n",
+            "- vector element #0 of 1\n",
+            "- vector element #0 of 1\n",
+        )),
+        "{full}"
+    );
+    assert!(!full.contains('…'));
+    assert_eq!(wasmiter::parser::Error::max_context_length(), 16);
+
+    // The integer parser adds the first context, followed by four more
+    wasmiter::parser::Error::set_max_context_length(2);
+    let capped = parse();
+    assert!(
+        capped.starts_with("input was malformed\n- continuation flag was set in integer"),
+        "{capped}"
+    );
+    assert!(
+        capped.ends_with(concat!(
+            "- could not parse signed 33-bit integer\n",
+            "- … 3 more\n",
+        )),
+        "{capped}"
+    );
+}
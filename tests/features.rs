@@ -0,0 +1,88 @@
+#![cfg(feature = "alloc")]
+
+use wasmiter::features;
+
+#[cfg(all(
+    feature = "simd",
+    feature = "threads",
+    feature = "exceptions",
+    feature = "tail-call"
+))]
+#[test]
+fn detect_proposals() {
+    use features::Feature;
+
+    let wasm = wat::parse_str(
+        r#"(module
+    (import "env" "memory" (memory i64 1 1 shared))
+    (memory 1)
+    (table 1 externref)
+    (tag $error (param i32))
+    (data "passive")
+    (func (param i64 v128) (result i32)
+        local.get 0
+        i32.atomic.load
+        local.get 1
+        i32x4.extract_lane 0
+        i32.add
+        try_table (catch $error 0)
+            i32.const 0
+            throw $error
+        end
+        return_call 1)
+    (func (param i32) (result i32)
+        i32.const 0
+        i32.const 0
+        i32.const 1
+        memory.init 1 0
+        local.get 0))"#,
+    )
+    .unwrap();
+
+    let detected = features::detect(wasm.as_slice()).unwrap();
+    assert_eq!(
+        detected.iter().copied().collect::<Vec<_>>(),
+        [
+            Feature::ReferenceTypes,
+            Feature::BulkMemory,
+            Feature::Simd,
+            Feature::TailCall,
+            Feature::Threads,
+            Feature::Exceptions,
+            Feature::Memory64,
+            Feature::MultiMemory,
+        ]
+    );
+
+    let unsupported = detected
+        .into_iter()
+        .filter(|feature| !feature.is_from_wasm_2_0())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        unsupported,
+        [
+            Feature::TailCall,
+            Feature::Threads,
+            Feature::Exceptions,
+            Feature::Memory64,
+            Feature::MultiMemory,
+        ]
+    );
+}
+
+#[test]
+fn mvp_module_uses_no_features() {
+    let wasm = wat::parse_str(
+        r#"(module
+    (memory 1)
+    (global $g i32 (i32.const 0))
+    (func (export "f") (param i32) (result i32)
+        local.get 0
+        global.get $g
+        i32.add
+        i32.load offset=4))"#,
+    )
+    .unwrap();
+
+    assert!(features::detect(wasm.as_slice()).unwrap().is_empty());
+}
@@ -1,3 +1,5 @@
+#[cfg(feature = "std")]
+use wasmiter::input::{BufferedInput, Input};
 use wasmiter::input::{HexDump, Window};
 
 const DATA: &[u8] =
@@ -10,11 +12,179 @@ fn hex_dump_debug() {
 
 #[test]
 fn hex_dump_display() {
-    insta::assert_display_snapshot!(format_args!("{:#}", HexDump::from(DATA)));
+    insta::assert_snapshot!(format_args!("{:#}", HexDump::from(DATA)));
 }
 
 #[test]
 fn hex_dump_display_at_weird_offset() {
     let window = Window::with_offset_and_length(DATA, 3, 45);
-    insta::assert_display_snapshot!(format_args!("{:#}", HexDump::from(window)));
+    insta::assert_snapshot!(format_args!("{:#}", HexDump::from(window)));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn window_copy_to_in_chunks() {
+    let bytes = (0..10_000u32).map(|i| i as u8).collect::<Vec<_>>();
+    let window = Window::with_offset_and_length(bytes.as_slice(), 3, 9_000);
+    let mut copied = Vec::new();
+    assert_eq!(window.copy_to(&mut copied).unwrap(), 9_000);
+    assert_eq!(copied, bytes[3..9_003]);
+
+    let truncated = Window::with_offset_and_length(bytes.as_slice(), 9_000, 2_000);
+    assert!(truncated.copy_to(&mut Vec::new()).is_err());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn input_error_kinds() {
+    use wasmiter::input::{Error, ErrorKind, Input as _};
+
+    let bytes = [1u8, 2, 3];
+    let error = bytes.as_slice().read_at(8, &mut [0u8; 2]).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::OutOfBounds);
+    assert!(error.kind().is_incomplete());
+
+    let error = bytes
+        .as_slice()
+        .read_exact_at(2, &mut [0u8; 2])
+        .unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+    assert_eq!(
+        std::io::Error::from(error).kind(),
+        std::io::ErrorKind::UnexpectedEof
+    );
+
+    let io = std::io::Error::new(std::io::ErrorKind::PermissionDenied, "denied");
+    let error = Error::from_io_error(io, 4);
+    assert_eq!(error.kind(), ErrorKind::Io);
+    assert!(!error.kind().is_incomplete());
+    assert_eq!(error.offset(), 4);
+    assert_eq!(
+        std::io::Error::from(error).kind(),
+        std::io::ErrorKind::PermissionDenied
+    );
+
+    let parsed = wasmiter::parse_module_sections(bytes.as_slice()).unwrap_err();
+    assert_eq!(
+        parsed.input_error().map(Error::kind),
+        Some(ErrorKind::UnexpectedEof)
+    );
+}
+
+/// Counts the number of calls to [`Input::read_at`].
+#[cfg(feature = "std")]
+struct CountingInput<'a> {
+    bytes: &'a [u8],
+    reads: std::sync::atomic::AtomicUsize,
+}
+
+#[cfg(feature = "std")]
+impl Input for CountingInput<'_> {
+    fn read_at<'b>(
+        &self,
+        offset: u64,
+        buffer: &'b mut [u8],
+    ) -> wasmiter::input::Result<&'b mut [u8]> {
+        self.reads
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        self.bytes.read_at(offset, buffer)
+    }
+
+    fn length_at(&self, offset: u64) -> wasmiter::input::Result<u64> {
+        self.bytes.length_at(offset)
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn buffered_input_caches_reads() {
+    let wasm = wat::parse_str(
+        r#"(module
+            (func $add (export "add") (param i32 i32) (result i32)
+                local.get 0
+                local.get 1
+                i32.add)
+            (memory 1)
+            (data (i32.const 0) "hello"))"#,
+    )
+    .unwrap();
+
+    let expected = wasmiter::parse_module_sections(wasm.as_slice())
+        .unwrap()
+        .display_module()
+        .to_string();
+
+    let input = BufferedInput::with_block_size_and_count(
+        CountingInput {
+            bytes: &wasm,
+            reads: Default::default(),
+        },
+        16,
+        4,
+    );
+
+    let sections = wasmiter::parse_module_sections(&input).unwrap();
+    assert_eq!(sections.display_module().to_string(), expected);
+
+    let inner_reads = input
+        .inner()
+        .reads
+        .load(std::sync::atomic::Ordering::Relaxed);
+    let unbuffered = CountingInput {
+        bytes: &wasm,
+        reads: Default::default(),
+    };
+    let sections = wasmiter::parse_module_sections(&unbuffered).unwrap();
+    let _ = sections.display_module().to_string();
+    assert!(inner_reads < unbuffered.reads.into_inner());
+
+    // Errors for offsets past the end are the same as the inner input
+    let mut buffer = [0u8; 4];
+    assert_eq!(input.read_at(wasm.len() as u64, &mut buffer).unwrap(), b"");
+    assert_eq!(
+        input
+            .read_at(wasm.len() as u64 + 1, &mut buffer)
+            .unwrap_err()
+            .kind(),
+        wasm.as_slice()
+            .read_at(wasm.len() as u64 + 1, &mut buffer)
+            .unwrap_err()
+            .kind()
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn cursor_ignores_position() {
+    let mut cursor = std::io::Cursor::new(DATA.to_vec());
+    cursor.set_position(20);
+    let mut buffer = [0u8; 7];
+    assert_eq!(cursor.read_at(8, &mut buffer).unwrap(), b"man wen");
+    assert_eq!(cursor.length_at(0).unwrap(), DATA.len() as u64);
+    assert!(cursor.try_eq_at(4, b"the").unwrap());
+}
+
+#[cfg(feature = "mmap")]
+#[test]
+fn shared_mmap_across_threads() {
+    let path = std::env::temp_dir().join(format!("wasmiter-shared-{}.wasm", std::process::id()));
+    std::fs::write(&path, wat::parse_str("(module (func) (memory 1))").unwrap()).unwrap();
+    let binary = wasmiter::input::SharedMmap::open(&path).unwrap();
+
+    let counts = (0..4)
+        .map(|_| {
+            let binary = binary.clone();
+            std::thread::spawn(move || {
+                let sections = wasmiter::parse_module_sections(binary).unwrap();
+                wasmiter::input::BorrowInput::borrow_input(&sections).count()
+            })
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .map(|handle| handle.join().unwrap())
+        .collect::<Vec<_>>();
+
+    drop(binary);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(counts, [4; 4]);
 }
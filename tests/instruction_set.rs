@@ -0,0 +1,361 @@
+use wasmiter::instruction_set::{self, BlockTerminator};
+
+#[test]
+fn skip_nested_if_else() {
+    // if, block, nop, end, else, nop, end, end
+    let bytes = [0x04u8, 0x40, 0x02, 0x40, 0x01, 0x0B, 0x05, 0x01, 0x0B, 0x0B];
+    let mut expression = instruction_set::parse_expression(0, bytes.as_slice());
+    expression
+        .next(|_| wasmiter::parser::Parsed::Ok(()))
+        .transpose()
+        .unwrap();
+
+    assert_eq!(
+        expression.skip_block().transpose().unwrap(),
+        Some(BlockTerminator::Else)
+    );
+    assert_eq!(expression.nesting_level(), 2);
+    assert_eq!(
+        expression.skip_block().transpose().unwrap(),
+        Some(BlockTerminator::End)
+    );
+    assert_eq!(expression.nesting_level(), 1);
+    assert_eq!(
+        expression.skip_block().transpose().unwrap(),
+        Some(BlockTerminator::End)
+    );
+    assert!(expression.skip_block().is_none());
+}
+
+#[test]
+fn if_arms_without_else() {
+    // nop, if, if, nop, else, end, end, end
+    let bytes = [0x01u8, 0x04, 0x40, 0x04, 0x40, 0x01, 0x05, 0x0B, 0x0B, 0x0B];
+    let arms = instruction_set::if_arms(1, bytes.as_slice()).unwrap();
+    assert_eq!(arms.then_arm(), 3..8);
+    assert_eq!(arms.else_arm(), None);
+    assert_eq!(arms.end(), 9);
+    assert!(instruction_set::if_arms(0, bytes.as_slice()).is_err());
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn memarg_error_names_instruction() {
+    // i32.load with an alignment of 2^32
+    let bytes = [0x28u8, 0x20, 0x00, 0x0B];
+    let error = instruction_set::parse_expression(0, bytes.as_slice())
+        .next(|_| wasmiter::parser::Parsed::Ok(()))
+        .unwrap()
+        .unwrap_err();
+
+    let message = format!("{error:#}");
+    assert!(
+        message.contains("32 is too large to be a valid alignment power"),
+        "{message}"
+    );
+    assert!(
+        message.contains("memory argument of I32Load instruction (0x28)"),
+        "{message}"
+    );
+}
+
+#[test]
+fn multi_memory_memarg_order() {
+    use wasmiter::index::MemIdx;
+
+    // i32.load with a memory index of 1, an alignment of 2^2, and an offset of 8, then
+    // i64.store with an offset of 300 to memory 0
+    let bytes = [0x28u8, 0x42, 0x01, 0x08, 0x37, 0x03, 0xAC, 0x02, 0x0B];
+    let mut expression = instruction_set::parse_expression(0, bytes.as_slice());
+    let mut memargs = Vec::new();
+    while let Some(result) =
+        expression.next(|instruction| wasmiter::parser::Parsed::Ok(instruction.memarg()))
+    {
+        memargs.extend(result.unwrap());
+    }
+
+    let memargs = memargs
+        .into_iter()
+        .map(|memarg| (memarg.memory(), memarg.align().to_power(), memarg.offset()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        memargs,
+        [(MemIdx::from(1u32), 2, 8), (MemIdx::from(0u32), 3, 300)]
+    );
+}
+
+#[test]
+#[cfg(target_pointer_width = "64")]
+fn instruction_size() {
+    use wasmiter::instruction_set::Instruction;
+
+    // The u128 immediate of v128.const raises the alignment to 16
+    assert!(core::mem::align_of::<Instruction<&[u8]>>() <= 16);
+    assert!(core::mem::size_of::<Instruction<&[u8]>>() <= 48);
+}
+
+#[test]
+fn instruction_trace_into_fixed_buffer() {
+    use wasmiter::text;
+
+    // local.get 0, local.get 1, i32.add, end
+    let bytes = [0x20u8, 0x00, 0x20, 0x01, 0x6A, 0x0B];
+    let mut expression = instruction_set::parse_expression(0, bytes.as_slice());
+    let mut buffer = [0u8; 16];
+    let mut trace = Vec::new();
+    while let Some(result) = expression.next(|instr| {
+        let mut writer = text::SliceWriter::new(&mut buffer);
+        text::write_to(instr, &mut writer).unwrap();
+        trace.push(writer.as_str().to_string());
+        wasmiter::parser::Parsed::Ok(())
+    }) {
+        result.unwrap();
+    }
+
+    assert_eq!(trace, ["local.get 0", "local.get 1", "i32.add", "end"]);
+
+    let mut small = [0u8; 5];
+    let mut writer = text::SliceWriter::new(&mut small);
+    let mut expression = instruction_set::parse_expression(0, bytes.as_slice());
+    let mut written = Ok(());
+    expression
+        .next(|instr| {
+            written = text::write_to(instr, &mut writer);
+            wasmiter::parser::Parsed::Ok(())
+        })
+        .unwrap()
+        .unwrap();
+    assert!(written.is_err());
+    assert_eq!(writer.as_str(), "local");
+    assert!(writer.is_truncated());
+}
+
+#[test]
+#[cfg(all(feature = "alloc", feature = "function-references"))]
+fn function_references_instructions() {
+    use wasmiter::text;
+
+    // ref.as_non_null, br_on_null 0, br_on_non_null 0, call_ref 0, return_call_ref 1, end
+    let bytes = [0xD4u8, 0xD5, 0x00, 0xD6, 0x00, 0x14, 0x00, 0x15, 0x01, 0x0B];
+    let mut expression = instruction_set::parse_expression(0, bytes.as_slice());
+    let mut trace = Vec::new();
+    while let Some(result) = expression.next(|instr| {
+        assert_eq!(
+            instr.is_from_function_references(),
+            !matches!(instr, instruction_set::Instruction::End)
+        );
+        trace.push(text::to_string(instr));
+        wasmiter::parser::Parsed::Ok(())
+    }) {
+        result.unwrap();
+    }
+
+    assert_eq!(
+        trace,
+        [
+            "ref.as_non_null",
+            "br_on_null 0",
+            "br_on_non_null 0",
+            "call_ref 0",
+            "return_call_ref 1",
+            "end"
+        ]
+    );
+}
+
+#[test]
+#[cfg(feature = "simd")]
+fn instruction_opcodes() {
+    use instruction_set::{FCPrefixedOpcode, InstructionOpcode, Opcode, VectorOpcode};
+
+    // select, select (result i32), memory.copy 0 0, v128.not, end
+    let bytes = [
+        0x1Bu8, 0x1C, 0x01, 0x7F, 0xFC, 0x0A, 0x00, 0x00, 0xFD, 0x4D, 0x0B,
+    ];
+    let mut expression = instruction_set::parse_expression(0, bytes.as_slice());
+    let mut opcodes = Vec::new();
+    while let Some(result) = expression.next(|instr| {
+        let opcode = instr.opcode();
+        opcodes.push((opcode, opcode.byte(), opcode.sub_opcode()));
+        wasmiter::parser::Parsed::Ok(())
+    }) {
+        result.unwrap();
+    }
+
+    assert_eq!(
+        opcodes,
+        [
+            (InstructionOpcode::Byte(Opcode::Select), 0x1B, None),
+            (InstructionOpcode::Byte(Opcode::SelectMany), 0x1C, None),
+            (
+                InstructionOpcode::FC(FCPrefixedOpcode::MemoryCopy),
+                0xFC,
+                Some(10)
+            ),
+            (InstructionOpcode::V128(VectorOpcode::Not), 0xFD, Some(0x4D)),
+            (InstructionOpcode::Byte(Opcode::End), 0x0B, None),
+        ]
+    );
+}
+
+#[test]
+#[cfg(feature = "tail-call")]
+fn versioned_instruction_sets() {
+    use instruction_set::InstructionSet;
+
+    // i32.const 0, i32.extend8_s, memory.fill 0, return_call 0, end
+    let bytes = [
+        0x41u8, 0, 0xC0, 0x41, 0, 0x41, 0, 0xFC, 11, 0, 0x12, 0, 0x0B,
+    ];
+    let mut expression = instruction_set::parse_expression(0, bytes.as_slice());
+    let mut sets = Vec::new();
+    while let Some(result) = expression.next(|instruction| {
+        wasmiter::parser::Parsed::Ok([
+            InstructionSet::MVP.contains(instruction),
+            InstructionSet::WASM_2_0.contains(instruction),
+            InstructionSet::WASM_2_0
+                .with(InstructionSet::TAIL_CALL)
+                .contains(instruction),
+        ])
+    }) {
+        sets.push(result.unwrap());
+    }
+
+    assert_eq!(
+        sets,
+        [
+            [true, true, true],
+            [false, true, true],
+            [true, true, true],
+            [true, true, true],
+            [false, true, true],
+            [false, false, true],
+            [true, true, true],
+        ]
+    );
+    assert!(InstructionSet::ALL.includes(InstructionSet::WASM_2_0));
+    assert!(!InstructionSet::WASM_2_0
+        .without(InstructionSet::FIXED_WIDTH_SIMD)
+        .includes(InstructionSet::FIXED_WIDTH_SIMD));
+}
+
+#[test]
+fn visit_instructions() {
+    use wasmiter::{
+        component::IndexVector,
+        index::{LabelIdx, MemIdx},
+        input::Input,
+        instruction_set::VisitInstruction,
+    };
+
+    #[derive(Default)]
+    struct Recorder {
+        visited: Vec<String>,
+    }
+
+    impl<'a, I: Input> VisitInstruction<'a, I> for Recorder {
+        type Output = ();
+
+        fn visit_other(&mut self, name: &'static str) {
+            self.visited.push(name.to_string());
+        }
+
+        fn visit_local_get(&mut self, a: wasmiter::index::LocalIdx) {
+            self.visited.push(format!("get {}", a.to_u32()));
+        }
+
+        fn visit_br_table(&mut self, targets: &mut IndexVector<LabelIdx, &'a mut u64, I>) {
+            // Only the first label is read, the rest are skipped afterwards
+            let first = targets.next().unwrap().unwrap();
+            self.visited.push(format!("br_table {}", first.to_u32()));
+        }
+
+        fn visit_memory_copy(&mut self, destination: MemIdx, source: MemIdx) {
+            self.visited
+                .push(format!("copy {} {}", destination.to_u32(), source.to_u32()));
+        }
+    }
+
+    // block, local.get 0, br_table 0 0 0, end, i32.const 0 (3 times), memory.copy 0 0, end
+    let bytes = [
+        0x02u8, 0x40, 0x20, 0x00, 0x0E, 0x02, 0x00, 0x00, 0x00, 0x0B, 0x41, 0x00, 0x41, 0x00, 0x41,
+        0x00, 0xFC, 0x0A, 0x00, 0x00, 0x0B,
+    ];
+    let mut expression = instruction_set::parse_expression(0, bytes.as_slice());
+    let mut recorder = Recorder::default();
+    while let Some(result) = expression.visit(&mut recorder) {
+        result.unwrap();
+    }
+
+    assert_eq!(
+        recorder.visited,
+        [
+            "block",
+            "get 0",
+            "br_table 0",
+            "end",
+            "i32.const",
+            "i32.const",
+            "i32.const",
+            "copy 0 0",
+            "end"
+        ]
+    );
+}
+
+#[test]
+#[cfg(feature = "exceptions")]
+fn owned_instructions() {
+    use wasmiter::{
+        index::{LabelIdx, MemIdx},
+        instruction_set::{BlockType, CatchClause, OwnedInstruction},
+        types::ValType,
+    };
+
+    // block, i32.const 0, br_table 0 0, end, select (result i32), try_table catch_all 0, end,
+    // memory.copy 0 0, end
+    let bytes = [
+        0x02u8, 0x40, 0x41, 0x00, 0x0E, 0x01, 0x00, 0x00, 0x0B, 0x1C, 0x01, 0x7F, 0x1F, 0x40, 0x01,
+        0x02, 0x00, 0x0B, 0xFC, 0x0A, 0x00, 0x00, 0x0B,
+    ];
+    let instructions = instruction_set::parse_expression(0, bytes.as_slice())
+        .into_iter_owned()
+        .collect::<wasmiter::parser::Parsed<Vec<_>>>()
+        .unwrap();
+
+    let label = LabelIdx::from(0u32);
+    let memory = MemIdx::from(0u32);
+    assert_eq!(
+        instructions,
+        [
+            OwnedInstruction::Block(BlockType::Empty),
+            OwnedInstruction::I32Const(0),
+            OwnedInstruction::BrTable(vec![label, label]),
+            OwnedInstruction::End,
+            OwnedInstruction::Select(vec![ValType::I32]),
+            OwnedInstruction::TryTable(BlockType::Empty, vec![CatchClause::CatchAll(label)]),
+            OwnedInstruction::End,
+            OwnedInstruction::MemoryCopy {
+                destination: memory,
+                source: memory
+            },
+            OwnedInstruction::End,
+        ]
+    );
+    assert_eq!(instructions[2].name(), "br_table");
+
+    let mut expression = instruction_set::parse_expression(0, bytes.as_slice());
+    let block = expression
+        .next(|instruction| OwnedInstruction::try_from(instruction))
+        .unwrap()
+        .unwrap();
+    assert_eq!(block, instructions[0]);
+
+    // br_table with a truncated label vector
+    let truncated = [0x0Eu8, 0x02, 0x00];
+    assert!(instruction_set::parse_expression(0, truncated.as_slice())
+        .into_iter_owned()
+        .next()
+        .unwrap()
+        .is_err());
+}
@@ -68,3 +68,26 @@ fn examples_s64() {
     assert_eq_decoded!(-1, &[0x7F]);
     assert_eq_decoded!(-17, [0x6F]);
 }
+
+#[test]
+fn examples_s33() {
+    macro_rules! assert_eq_decoded {
+        ($expected:expr, $actual:expr) => {{
+            assert_eq!(
+                $expected,
+                leb128::s33(&mut 0, $actual.as_slice()).unwrap().get()
+            );
+        }};
+    }
+
+    assert_eq_decoded!(0, &[0]);
+    assert_eq_decoded!(-64, &[0x40]);
+    assert_eq_decoded!(-1, &[0x7F]);
+    assert_eq_decoded!(0xFFFF_FFFF, &[0xFF, 0xFF, 0xFF, 0xFF, 0x0F]);
+    assert_eq_decoded!(-0x1_0000_0000, &[0x80, 0x80, 0x80, 0x80, 0x70]);
+    assert_eq!(leb128::S33::MAX.get(), 0xFFFF_FFFF);
+
+    // Too large, or encoded in too many bytes
+    assert!(leb128::s33(&mut 0, [0x80u8, 0x80, 0x80, 0x80, 0x10].as_slice()).is_err());
+    assert!(leb128::s33(&mut 0, [0x80u8, 0x80, 0x80, 0x80, 0x80, 0].as_slice()).is_err());
+}
@@ -0,0 +1,124 @@
+#![cfg(feature = "alloc")]
+
+use wasmiter::{component::LocalTypes, index::LocalIdx, types::ValType};
+
+#[test]
+fn local_types_lookup() {
+    let wat = r#"(module
+    (func (param i32 i32 f64) (local i64 i64) (local f64) (local i32))
+)
+"#;
+    let wasm = wat::parse_str(wat).unwrap();
+    let sections = wasmiter::parse_module_sections(wasm.as_slice()).unwrap();
+    let mut types = None;
+    let mut code = None;
+    for result in wasmiter::input::BorrowInput::borrow_input(&sections) {
+        match wasmiter::component::KnownSection::interpret(result.unwrap()) {
+            Ok(Ok(wasmiter::component::KnownSection::Type(t))) => types = Some(t),
+            Ok(Ok(wasmiter::component::KnownSection::Code(c))) => code = Some(c),
+            _ => (),
+        }
+    }
+
+    let mut types = types.unwrap();
+    let parameters = types
+        .parse(
+            |parameters| parameters.collect::<Result<Vec<_>, _>>(),
+            |parameters, _| Ok(parameters),
+        )
+        .unwrap()
+        .unwrap();
+
+    let mut code = code.unwrap();
+    let entry = code.parse().unwrap().unwrap();
+    let locals = entry
+        .read(
+            |locals| LocalTypes::new(parameters.into_iter().map(Ok), locals),
+            |locals, _| Ok(locals),
+        )
+        .unwrap();
+
+    assert_eq!(locals.parameter_count(), 3);
+    assert_eq!(locals.len(), 7);
+    assert!(!locals.is_empty());
+
+    let expected = [
+        (ValType::I32, true),
+        (ValType::I32, true),
+        (ValType::F64, true),
+        (ValType::I64, false),
+        (ValType::I64, false),
+        (ValType::F64, false),
+        (ValType::I32, false),
+    ];
+
+    for (i, (ty, is_parameter)) in expected.into_iter().enumerate() {
+        let index = LocalIdx::from(i as u32);
+        assert_eq!(locals.get(index), Some(ty), "local {i}");
+        assert_eq!(locals.is_parameter(index), is_parameter, "local {i}");
+    }
+
+    assert_eq!(locals.get(LocalIdx::from(7u32)), None);
+}
+
+#[test]
+fn local_types_empty() {
+    // Code section containing one function with no locals
+    let mut code = wasmiter::component::CodeSection::new(0, [1u8, 2, 0, 0x0B].as_slice()).unwrap();
+    let locals = code
+        .parse()
+        .unwrap()
+        .unwrap()
+        .read(
+            |locals| LocalTypes::new(core::iter::empty(), locals),
+            |locals, _| Ok(locals),
+        )
+        .unwrap();
+
+    assert!(locals.is_empty());
+    assert_eq!(locals.len(), 0);
+    assert_eq!(locals.get(LocalIdx::from(0u32)), None);
+}
+
+#[test]
+fn local_count_overflow_and_limit() {
+    use wasmiter::component::CodeSection;
+
+    let read_locals = |code: &mut CodeSection<&[u8]>| {
+        let entry = code.parse().unwrap().unwrap();
+        entry
+            .read(
+                |locals| {
+                    let mut total = 0;
+                    while let Some((count, _)) = locals.next_group()? {
+                        total += u64::from(count.get());
+                        assert_eq!(u64::from(locals.total_count()), total);
+                    }
+                    Ok(total)
+                },
+                |total, _| wasmiter::parser::Parsed::Ok(total),
+            )
+            .map_err(|e| e.to_string())
+    };
+
+    // (local i32 * 0xFFFFFFFF) (local i64 * 0xFFFFFFFF)
+    let overflowing = [
+        1u8, 14, 2, 0xFF, 0xFF, 0xFF, 0xFF, 0x0F, 0x7F, 0xFF, 0xFF, 0xFF, 0xFF, 0x0F, 0x7E, 0x0B,
+    ];
+    let mut code = CodeSection::new(0, overflowing.as_slice()).unwrap();
+    assert!(read_locals(&mut code)
+        .unwrap_err()
+        .contains("function has too many locals"));
+
+    // (local i32 * 5) (local i64 * 20)
+    let bytes = [1u8, 6, 2, 5, 0x7F, 20, 0x7E, 0x0B];
+    let mut code = CodeSection::new(0, bytes.as_slice()).unwrap();
+    assert_eq!(read_locals(&mut code), Ok(25));
+
+    let mut code = CodeSection::new(0, bytes.as_slice())
+        .unwrap()
+        .with_max_locals(16);
+    assert!(read_locals(&mut code)
+        .unwrap_err()
+        .contains("more than the maximum of 16 locals"));
+}
@@ -0,0 +1,182 @@
+#![cfg(feature = "alloc")]
+
+use wasmiter::types::ValType;
+
+#[cfg(feature = "simd")]
+#[test]
+fn summarizes_interface() {
+    use wasmiter::{features::Feature, manifest::Signature, types::GlobalMutability};
+
+    let wasm = wat::parse_str(
+        r#"(module $registry
+    (@producers
+        (language "Rust" "1.70.0")
+        (processed-by "rustc" "1.70.0")
+        (processed-by "wasm-opt" ""))
+    (import "env" "counter" (global $counter (mut i32)))
+    (import "env" "memory" (memory 1 2 shared))
+    (import "env" "log" (func $log (param i32)))
+    (tag $oops (export "oops") (param i32))
+    (table $callbacks (export "callbacks") 1 externref)
+    (memory (export "heap") 4)
+    (global (export "vector") v128 (v128.const i64x2 0 0))
+    (func (export "pair") (param i64) (result i64 i64)
+        local.get 0
+        local.get 0)
+    (data (memory 1) (i32.const 0) "hello"))"#,
+    )
+    .unwrap();
+
+    let manifest = wasmiter::manifest(wasm.as_slice()).unwrap();
+    assert_eq!(manifest.name(), Some("registry"));
+
+    let imports = manifest
+        .imports()
+        .iter()
+        .map(|import| (import.module(), import.name()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        imports,
+        [("env", "counter"), ("env", "memory"), ("env", "log")]
+    );
+    match manifest.imports()[0].signature() {
+        Signature::Global(global) => {
+            assert_eq!(global.mutability(), GlobalMutability::Variable);
+            assert_eq!(global.value_type(), ValType::I32);
+        }
+        other => panic!("expected a global, but got {other:?}"),
+    }
+
+    let exports = manifest
+        .exports()
+        .iter()
+        .map(|export| export.name())
+        .collect::<Vec<_>>();
+    assert_eq!(exports, ["oops", "callbacks", "heap", "vector", "pair"]);
+    match manifest.exports()[4].signature() {
+        Signature::Function(signature) => {
+            assert_eq!(signature.parameters(), &[ValType::I64]);
+            assert_eq!(signature.results(), &[ValType::I64, ValType::I64]);
+        }
+        other => panic!("expected a function, but got {other:?}"),
+    }
+    assert!(matches!(
+        manifest.exports()[0].signature(),
+        Signature::Tag(signature) if signature.parameters() == [ValType::I32]
+    ));
+
+    assert_eq!(manifest.tables().len(), 1);
+    let memories = manifest
+        .memories()
+        .iter()
+        .map(|memory| (memory.minimum(), memory.maximum()))
+        .collect::<Vec<_>>();
+    assert_eq!(memories, [(1, Some(2)), (4, None)]);
+
+    assert_eq!(
+        manifest.features(),
+        [
+            Feature::MutableGlobal,
+            Feature::MultiValue,
+            Feature::ReferenceTypes,
+            Feature::Simd,
+            Feature::Threads,
+            Feature::Exceptions,
+            Feature::MultiMemory,
+        ]
+    );
+
+    let producers = manifest
+        .producers()
+        .iter()
+        .map(|producer| (producer.field(), producer.name(), producer.version()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        producers,
+        [
+            ("language", "Rust", "1.70.0"),
+            ("processed-by", "rustc", "1.70.0"),
+            ("processed-by", "wasm-opt", ""),
+        ]
+    );
+}
+
+#[test]
+fn skips_function_bodies() {
+    let mut wasm = wat::parse_str(
+        r#"(module
+    (func (export "f") (result i32)
+        i32.const 1))"#,
+    )
+    .unwrap();
+
+    // Corrupt the contents of the code section
+    let code = wasmiter::parse_module_sections(wasm.as_slice())
+        .unwrap()
+        .into_iter()
+        .map(Result::unwrap)
+        .find(|section| section.id() == wasmiter::sections::id::CODE)
+        .unwrap();
+    let start = usize::try_from(code.contents().base()).unwrap();
+    let end = start + usize::try_from(code.length()).unwrap();
+    wasm[start..end].fill(0xFF);
+
+    assert!(wasmiter::validate::validate_module(wasm.as_slice()).is_err());
+
+    let manifest = wasmiter::manifest(wasm.as_slice()).unwrap();
+    assert_eq!(manifest.exports()[0].name(), "f");
+    assert!(manifest.features().is_empty());
+}
+
+#[test]
+fn sections_and_function_signatures() {
+    use wasmiter::sections::id;
+
+    let wasm = wat::parse_str(
+        r#"(module
+    (import "env" "log" (func (param i32)))
+    (func (export "run") (param i32) (result i32)
+        local.get 0)
+    (@custom "notes" "hello"))"#,
+    )
+    .unwrap();
+
+    let manifest = wasmiter::manifest(wasm.as_slice()).unwrap();
+    let sections = manifest
+        .sections()
+        .iter()
+        .map(|section| (section.id(), section.custom_name()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        sections,
+        [
+            (id::TYPE, None),
+            (id::IMPORT, None),
+            (id::FUNC, None),
+            (id::EXPORT, None),
+            (id::CODE, None),
+            (id::CUSTOM, Some("notes")),
+        ]
+    );
+
+    let code = &manifest.sections()[4];
+    let start = usize::try_from(code.offset()).unwrap();
+    // Entry count, body size, no locals, local.get 0, end
+    assert_eq!(
+        &wasm[start..start + usize::try_from(code.length()).unwrap()],
+        [1, 4, 0, 0x20, 0, 0x0B]
+    );
+
+    let functions = manifest
+        .functions()
+        .iter()
+        .map(|signature| (signature.parameters(), signature.results()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        functions,
+        [
+            (&[ValType::I32][..], &[][..]),
+            (&[ValType::I32][..], &[ValType::I32][..]),
+        ]
+    );
+}
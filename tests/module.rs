@@ -0,0 +1,149 @@
+use wasmiter::{
+    component::ImportKind,
+    custom::name::NameSubsection,
+    index::{FuncIdx, TypeIdx},
+    parser::ErrorCode,
+    sections::id,
+    Module,
+};
+
+#[cfg(feature = "alloc")]
+use wasmiter::{component::SignatureResolver, types::ValType};
+
+#[test]
+fn locates_sections_and_resolves_signatures() {
+    let wasm = wat::parse_str(
+        r#"(module
+    (type (func (param i32)))
+    (type (func (result f64)))
+    (import "env" "log" (func $log (type 0)))
+    (import "env" "memory" (memory 1))
+    (import "env" "clock" (func $clock (type 1)))
+    (func $first (type 1) f64.const 0)
+    (func $second (type 0))
+    (start $second)
+    (data "hello"))"#,
+    )
+    .unwrap();
+
+    let module = Module::parse(wasm.as_slice()).unwrap();
+    assert_eq!(module.imported_function_count(), 2);
+    assert_eq!(module.start().unwrap(), Some(FuncIdx::from(3u32)));
+    assert!(module.tables().unwrap().is_none());
+    assert!(module.section(id::TABLE).is_none());
+    assert_eq!(module.section(id::DATA).unwrap().id(), id::DATA);
+    assert_eq!(module.types().unwrap().unwrap().remaining_count(), 2);
+    assert_eq!(module.code().unwrap().unwrap().remaining_count(), 2);
+    assert_eq!(module.datas().unwrap().unwrap().remaining_count(), 1);
+
+    let mut imports = module.imports().unwrap().unwrap();
+    let import = imports.parse().unwrap().unwrap();
+    assert!(matches!(import.kind(), ImportKind::Function(_)));
+
+    let type_indices = (0..5u32)
+        .map(|index| module.func_type_index(index.into()).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        type_indices,
+        [
+            Some(TypeIdx::from(0u32)),
+            Some(TypeIdx::from(1u32)),
+            Some(TypeIdx::from(1u32)),
+            Some(TypeIdx::from(0u32)),
+            None,
+        ]
+    );
+
+    #[cfg(feature = "alloc")]
+    {
+        let clock = module.func_type(1u32.into()).unwrap().unwrap();
+        assert_eq!(clock.parameters(), &[]);
+        assert_eq!(clock.results(), &[ValType::F64]);
+        let second = module.func_type(3u32.into()).unwrap().unwrap();
+        assert_eq!(second.parameters(), &[ValType::I32]);
+        assert!(module.func_type(4u32.into()).unwrap().is_none());
+    }
+
+    let mut names = module.names().unwrap();
+    let function_names = names
+        .find_map(|subsection| match subsection.unwrap().unwrap() {
+            NameSubsection::FunctionName(names) => Some(names),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(function_names.declared_count(), 4);
+}
+
+#[test]
+fn module_without_sections() {
+    let module = Module::parse(b"\0asm\x01\0\0\0".as_slice()).unwrap();
+    assert_eq!(module.imported_function_count(), 0);
+    assert!(module.types().unwrap().is_none());
+    assert!(module.names().is_none());
+    assert!(module.func_type_index(0u32.into()).unwrap().is_none());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn signature_resolver_matches_module() {
+    let wasm = wat::parse_str(
+        r#"(module
+    (type $unary (func (param i64) (result i64)))
+    (import "env" "abort" (func $abort))
+    (func $double (type $unary) local.get 0 i64.const 2 i64.mul)
+    (func $call (result i64) i64.const 21 call $double))"#,
+    )
+    .unwrap();
+
+    let module = Module::parse(wasm.as_slice()).unwrap();
+    let resolver = module.signature_resolver().unwrap();
+    let from_sections = SignatureResolver::new(module.sections()).unwrap();
+    assert_eq!(resolver.imported_function_count(), 1);
+    assert_eq!(resolver.function_count(), 3);
+
+    for index in 0..4u32 {
+        let index = FuncIdx::from(index);
+        let expected = module.func_type(index).unwrap();
+        assert_eq!(resolver.function_signature(index), expected.as_ref());
+        assert_eq!(from_sections.function_signature(index), expected.as_ref());
+    }
+
+    let abort = resolver.function_signature(0u32.into()).unwrap();
+    assert!(abort.parameters().is_empty() && abort.results().is_empty());
+    let double = resolver.function_signature(1u32.into()).unwrap();
+    assert_eq!(double.parameters(), &[ValType::I64]);
+    assert!(resolver.function_signature(3u32.into()).is_none());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn signature_resolver_huge_entry_count() {
+    // Type and function sections that claim to contain 2^32 - 1 entries
+    for id in [1u8, 3] {
+        let mut wasm = b"\0asm\x01\0\0\0".to_vec();
+        wasm.extend_from_slice(&[id, 5, 0xFF, 0xFF, 0xFF, 0xFF, 0x0F]);
+        let sections = wasmiter::parse_module_sections(wasm.as_slice()).unwrap();
+        assert!(SignatureResolver::new(&sections).is_err());
+    }
+}
+
+#[test]
+fn duplicate_sections_are_rejected() {
+    // Two start sections, then two function sections
+    for wasm in [
+        b"\0asm\x01\0\0\0\x08\x01\x00\x08\x01\x01".as_slice(),
+        b"\0asm\x01\0\0\0\x03\x01\x00\x03\x01\x00".as_slice(),
+    ] {
+        let sections = wasmiter::parse_module_sections(wasm).unwrap();
+        let error = Module::new(sections).unwrap_err();
+        assert_eq!(error.code(), ErrorCode::DuplicateSection);
+    }
+
+    #[cfg(feature = "alloc")]
+    {
+        let wasm = b"\0asm\x01\0\0\0\x03\x01\x00\x03\x01\x00";
+        let sections = wasmiter::parse_module_sections(wasm.as_slice()).unwrap();
+        let error = SignatureResolver::new(&sections).unwrap_err();
+        assert_eq!(error.code(), ErrorCode::DuplicateSection);
+    }
+}
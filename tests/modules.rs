@@ -7,11 +7,127 @@ fn basic_module() {
         i32.add))
 "#;
     let wasm = wat::parse_str(wat).unwrap();
-    insta::assert_display_snapshot!(wasmiter::parse_module_sections(wasm.as_slice())
+    insta::assert_snapshot!(wasmiter::parse_module_sections(wasm.as_slice())
         .unwrap()
         .display_module());
 }
 
+#[test]
+fn split_function_bodies() {
+    let wat = r#"(module
+    (func (export "add_five") (param i32) (result i32)
+        local.get 0
+        i32.const 5
+        i32.add))
+"#;
+    let wasm = wat::parse_str(wat).unwrap();
+    let module = wasmiter::parse_module_sections(wasm.as_slice()).unwrap();
+    let mut funcs = None;
+    let mut types = None;
+    for result in module {
+        match wasmiter::component::KnownSection::interpret(result.unwrap()) {
+            Ok(Ok(wasmiter::component::KnownSection::Function(f))) => types = Some(f),
+            Ok(Ok(wasmiter::component::KnownSection::Code(code))) => {
+                funcs = Some(
+                    wasmiter::component::FuncsComponent::new(types.take().unwrap(), code).unwrap(),
+                );
+            }
+            _ => (),
+        }
+    }
+
+    let func = funcs.unwrap().next().unwrap().unwrap();
+    insta::assert_snapshot!(format!(
+        "{}\n{func}",
+        module.display_module().without_function_bodies()
+    ));
+}
+
+#[test]
+fn memarg_alignment() {
+    let wat = r#"(module
+    (memory 1)
+    (func (param i32)
+        local.get 0
+        i32.load
+        local.get 0
+        i64.load offset=8 align=4
+        local.get 0
+        i32.load8_u align=1
+        drop
+        drop
+        i32.store16 align=1))
+"#;
+    let wasm = wat::parse_str(wat).unwrap();
+    insta::assert_snapshot!(wasmiter::parse_module_sections(wasm.as_slice())
+        .unwrap()
+        .display_module());
+}
+
+#[test]
+fn element_segment_encodings() {
+    // Each segment uses a different one of the 8 element segment encodings, in order
+    let wat = r#"(module
+    (table 1 funcref)
+    (table 1 funcref)
+    (func)
+    (elem (i32.const 0) func 0)
+    (elem func 0)
+    (elem (table 1) (i32.const 0) func 0)
+    (elem declare func 0)
+    (elem (i32.const 0) funcref (ref.func 0) (ref.null func))
+    (elem funcref (ref.func 0))
+    (elem (table 1) (i32.const 0) funcref (ref.func 0))
+    (elem declare funcref (ref.func 0)))
+"#;
+    let wasm = wat::parse_str(wat).unwrap();
+    let text = wasmiter::parse_module_sections(wasm.as_slice())
+        .unwrap()
+        .display_module()
+        .to_string();
+
+    let reparsed = wat::parse_str(&text).unwrap();
+    assert_eq!(
+        wasmiter::parse_module_sections(reparsed.as_slice())
+            .unwrap()
+            .display_module()
+            .to_string(),
+        text
+    );
+
+    insta::assert_snapshot!(text);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn without_custom_sections() {
+    let wasm = wat::parse_str(include_str!("modules/name_custom_section.wat")).unwrap();
+    let sections = wasmiter::parse_module_sections(wasm.as_slice()).unwrap();
+    let decoded = sections.display_module().to_string();
+    let unnamed = sections
+        .display_module_with(wasmiter::text::Config::new().with_names(false))
+        .to_string();
+    let opaque = sections
+        .display_module()
+        .without_custom_sections()
+        .to_string();
+
+    assert!(decoded.contains("UNRECOGNIZED (0)"));
+    assert!(!opaque.contains("UNRECOGNIZED"));
+    assert!(opaque.contains("(; CUSTOM @ "));
+
+    let strip_custom = |text: &str| {
+        text.lines()
+            .take_while(|line| {
+                !line.starts_with("(; UNRECOGNIZED") && !line.starts_with("(; CUSTOM")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    assert!(decoded.contains("(tag $my_language_eh"));
+    assert_eq!(strip_custom(&opaque), strip_custom(&unnamed));
+}
+
 #[test]
 fn name_custom_section() {
     let wasm = wat::parse_str(include_str!("modules/name_custom_section.wat")).unwrap();
@@ -20,21 +136,265 @@ fn name_custom_section() {
         .debug_module());
 }
 
+#[cfg(feature = "alloc")]
+#[test]
+fn duplicate_names_are_unique() {
+    let mut wasm = wat::parse_str(
+        "(module (func (param i32) (local i32) call 1) (func call 2) (func call 0))",
+    )
+    .unwrap();
+
+    // Name section with duplicate function and local names
+    wasm.extend_from_slice(&[0, 32, 4]);
+    wasm.extend_from_slice(b"name");
+    wasm.extend_from_slice(&[1, 12, 3]);
+    wasm.extend_from_slice(&[0, 1, b'f', 1, 1, b'f', 2, 3, b'f', b'_', b'1']);
+    wasm.extend_from_slice(&[2, 11, 1, 0, 2]);
+    wasm.extend_from_slice(&[0, 2, b'x', b'y', 1, 2, b'x', b'y']);
+
+    let text = wasmiter::parse_module_sections(wasm.as_slice())
+        .unwrap()
+        .display_module()
+        .to_string();
+
+    assert!(text.contains("(func $f (type 0) (param $xy i32)"), "{text}");
+    assert!(text.contains("(local $xy_1 i32)"), "{text}");
+    assert!(text.contains("(func $f_2 (type 1)"), "{text}");
+    assert!(text.contains("(func $f_1 (type 1)"), "{text}");
+    assert!(text.contains("call $f_2\n"), "{text}");
+    assert!(text.contains("call $f_1\n"), "{text}");
+    assert!(text.contains("call $f\n"), "{text}");
+}
+
+#[test]
+fn relocation_annotations() {
+    let mut wasm = wat::parse_str(
+        "(module (memory 1) (func (result i32) i32.const 0 i32.load offset=0) (func call 0 drop))",
+    )
+    .unwrap();
+
+    // Linking section containing the symbol table
+    wasm.extend_from_slice(&[0, 30, 7]);
+    wasm.extend_from_slice(b"linking");
+    wasm.extend_from_slice(&[2, 8, 19, 3]);
+    wasm.extend_from_slice(&[1, 0, 1, b'a', 0, 0, 4]); // data symbol defined in segment 0
+    wasm.extend_from_slice(&[1, 0x10, 1, b'b']); // undefined data symbol
+    wasm.extend_from_slice(&[0, 0, 0, 3, b'g', b'e', b't']); // function symbol
+
+    // Relocation section for the code section, which is the fourth section in the module
+    wasm.extend_from_slice(&[0, 24, 10]);
+    wasm.extend_from_slice(b"reloc.CODE");
+    wasm.extend_from_slice(&[3, 3]);
+    wasm.extend_from_slice(&[4, 4, 0, 8]); // R_WASM_MEMORY_ADDR_SLEB in i32.const
+    wasm.extend_from_slice(&[3, 7, 1, 0]); // R_WASM_MEMORY_ADDR_LEB in i32.load
+    wasm.extend_from_slice(&[0, 12, 2]); // R_WASM_FUNCTION_INDEX_LEB in call
+
+    let module = wasmiter::parse_module_sections(wasm.as_slice()).unwrap();
+    insta::assert_snapshot!(module.display_module().with_relocation_annotations());
+}
+
+#[test]
+fn relocation_annotation_type_index() {
+    let mut wasm =
+        wat::parse_str("(module (table 1 funcref) (func i32.const 0 call_indirect (type 0)))")
+            .unwrap();
+
+    wasm.extend_from_slice(&[0, 16, 10]);
+    wasm.extend_from_slice(b"reloc.CODE");
+    wasm.extend_from_slice(&[3, 1]);
+    wasm.extend_from_slice(&[6, 6, 0]); // R_WASM_TYPE_INDEX_LEB in call_indirect
+
+    let module = wasmiter::parse_module_sections(wasm.as_slice()).unwrap();
+    let text = module
+        .display_module()
+        .with_relocation_annotations()
+        .to_string();
+    assert!(
+        text.contains("(; reloc R_WASM_TYPE_INDEX_LEB type=0 ;)"),
+        "{text}"
+    );
+}
+
+#[test]
+fn inline_imports() {
+    let wat = r#"(module
+    (import "env" "f" (func (param i32)))
+    (import "env" "t" (table 1 funcref))
+    (import "env" "m" (memory 1 2))
+    (import "env" "g" (global (mut i64)))
+    (import "env" "e" (tag (param i32)))
+    (func call 0))
+"#;
+    let wasm = wat::parse_str(wat).unwrap();
+    let module = wasmiter::parse_module_sections(wasm.as_slice()).unwrap();
+    let printed = module.display_module().with_inline_imports().to_string();
+    #[cfg(feature = "alloc")]
+    assert_eq!(wasmiter::text::parse_str(&printed).unwrap(), wasm);
+    insta::assert_snapshot!(printed);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn display_selected_sections() {
+    use wasmiter::sections::id;
+
+    let wasm = wat::parse_str(include_str!("modules/tag_imports_exports.wat")).unwrap();
+    let module = wasmiter::parse_module_sections(wasm.as_slice()).unwrap();
+
+    // Tags are still numbered after the imported tag, even though imports are not written
+    insta::assert_snapshot!(
+        module.display_sections(|section| matches!(section, id::TAG | id::EXPORT))
+    );
+}
+
 macro_rules! check_module_display {
-    ($($name:ident,)*) => {$(
+    ($($(#[$attribute:meta])* $name:ident,)*) => {$(
+        $(#[$attribute])*
         #[test]
         fn $name() {
             const WAT: &str = include_str!(concat!("modules/", stringify!($name), ".wat"));
             let wasm = wat::parse_str(WAT).unwrap();
             let module = wasmiter::parse_module_sections(wasm.as_slice()).unwrap();
-            insta::assert_display_snapshot!(module.display_module());
+            insta::assert_snapshot!(module.display_module());
         }
     )*};
 }
 
+// Names are only written when the `alloc` feature is enabled
 check_module_display! {
+    #[cfg(feature = "alloc")]
     all_the_things,
     // Case found with libFuzzer
     lots_of_br_table,
+    #[cfg(all(feature = "alloc", feature = "exceptions"))]
     exception_handling,
+    #[cfg(feature = "alloc")]
+    names,
+    #[cfg(feature = "alloc")]
+    tag_imports_exports,
+}
+
+#[cfg(feature = "mmap")]
+#[test]
+fn batch_process_isolates_errors() {
+    let directory = std::env::temp_dir().join(format!("wasmiter-batch-{}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+
+    let modules = [
+        ("a.wasm", wat::parse_str("(module (func) (func))").unwrap()),
+        ("b.wasm", b"\0asm\x02\0\0\0".to_vec()),
+        ("c.wasm", wat::parse_str("(module (func))").unwrap()),
+        ("ignored.txt", Vec::new()),
+    ];
+
+    for (name, contents) in modules.iter() {
+        std::fs::write(directory.join(name), contents).unwrap();
+    }
+
+    let paths = wasmiter::batch::wasm_files_in(&directory).unwrap();
+    let report = wasmiter::batch::process_with_jobs(paths, 2, |sections| {
+        Ok(wasmiter::input::BorrowInput::borrow_input(&sections).count())
+    });
+    std::fs::remove_dir_all(&directory).unwrap();
+
+    let names = report
+        .results()
+        .iter()
+        .map(|module| module.path().file_name().unwrap().to_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["a.wasm", "b.wasm", "c.wasm"]);
+    assert_eq!(report.success_count(), 2);
+    assert_eq!(report.failure_count(), 1);
+    assert!(report.results()[1].result().is_err());
+    assert_eq!(*report.results()[2].result().as_ref().unwrap(), 3);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn sections_parsed_on_other_threads() {
+    let wasm = wat::parse_str(include_str!("modules/all_the_things.wat")).unwrap();
+    let shared = std::sync::Arc::<[u8]>::from(wasm.as_slice());
+    let sections = wasmiter::parse_module_sections(shared).unwrap();
+
+    let expected = sections.display_module().to_string();
+    let printed = std::thread::scope(|scope| {
+        let handles = (0..2)
+            .map(|_| {
+                let sections = sections.clone();
+                scope.spawn(move || sections.display_module().to_string())
+            })
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect::<Vec<_>>()
+    });
+
+    assert!(printed.iter().all(|text| *text == expected));
+
+    let error = std::thread::spawn(|| wasmiter::parse_module_sections([0u8; 4].as_slice()).err())
+        .join()
+        .unwrap();
+    assert!(error.is_some());
+}
+
+/// Writes every module in the `modules` directory with each configuration.
+fn display_all_modules() -> Vec<String> {
+    const MODULES: &[&str] = &[
+        include_str!("modules/all_the_things.wat"),
+        #[cfg(feature = "exceptions")]
+        include_str!("modules/exception_handling.wat"),
+        include_str!("modules/lots_of_br_table.wat"),
+        include_str!("modules/name_custom_section.wat"),
+        include_str!("modules/names.wat"),
+        include_str!("modules/tag_imports_exports.wat"),
+    ];
+
+    let mut outputs = Vec::new();
+    for text in MODULES {
+        let wasm = wat::parse_str(text).unwrap();
+        let module = wasmiter::parse_module_sections(wasm.as_slice()).unwrap();
+        outputs.push(module.display_module().to_string());
+        outputs.push(
+            module
+                .display_module()
+                .without_function_bodies()
+                .to_string(),
+        );
+        outputs.push(module.display_module().with_inline_imports().to_string());
+        outputs.push(
+            module
+                .display_module()
+                .with_relocation_annotations()
+                .to_string(),
+        );
+    }
+    outputs
+}
+
+#[test]
+fn display_is_deterministic() {
+    let expected = display_all_modules();
+    for _ in 0..3 {
+        assert_eq!(display_all_modules(), expected);
+    }
+}
+
+#[cfg(all(feature = "alloc", feature = "exceptions"))]
+#[test]
+fn display_matches_format_version() {
+    // FNV-1a, since the hash must be stable across Rust versions
+    let hash = display_all_modules()
+        .iter()
+        .flat_map(|text| text.bytes())
+        .fold(0xCBF2_9CE4_8422_2325u64, |hash, b| {
+            (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01B3)
+        });
+
+    assert_eq!(
+        (wasmiter::text::FORMAT_VERSION, hash),
+        ("5", 0x7050_C4E2_D3DF_FAF7),
+        "text output changed, update the hash and consider changing FORMAT_VERSION"
+    );
 }
@@ -0,0 +1,35 @@
+(module $names
+    (type $binop (func (param i32 i32) (result i32)))
+    (import "env" "log" (func $log (param i32)))
+    (import "env" "counter" (global $counter (mut i32)))
+    (table $callbacks 1 funcref)
+    (memory $heap 1)
+    (global $total (mut i32) i32.const 0)
+    (tag $oops (param i32))
+    (func $add (type $binop) (param $lhs i32) (param $rhs i32) (result i32)
+        (local $sum i32)
+        local.get $lhs
+        local.get $rhs
+        i32.add
+        local.tee $sum
+        global.get $counter
+        i32.add
+        global.set $total
+        local.get $sum
+    )
+    (func $run (param i32)
+        i32.const 1
+        i32.const 2
+        call $add
+        call $log
+        local.get 0
+        i32.load $heap
+        drop
+        data.drop $greeting
+        elem.drop $handlers
+    )
+    (elem $handlers declare func $run)
+    (elem $init (table $callbacks) (i32.const 0) func $add)
+    (data $greeting (memory $heap) (i32.const 0) "hello")
+    (export "add" (func $add))
+)
@@ -0,0 +1,9 @@
+(module
+    (type (func (param i32 i64)))
+    (import "env" "error" (tag $error (param i32)))
+    (tag $fatal (type 0))
+    (tag (param f32))
+    (export "error" (tag $error))
+    (export "fatal" (tag $fatal))
+    (export "anonymous" (tag 2))
+)
@@ -20,3 +20,113 @@ fn simple_names() {
         .map(|bytes| Name::try_from(*bytes))
         .collect::<Box<[_]>>());
 }
+
+#[test]
+fn name_subsection_trailing_bytes() {
+    use wasmiter::custom::{name::NameSection, Strictness};
+
+    // Module name subsection with two trailing bytes, followed by a function name subsection
+    let bytes = [0u8, 4, 1, b'm', 0xFF, 0xFF, 1, 4, 1, 0, 1, b'f'];
+    let sections = wasmiter::sections::SectionSequence::new(0, bytes.as_slice());
+
+    let mut lenient = NameSection::new(sections);
+    let module_name = lenient.parse().unwrap().unwrap().unwrap();
+    assert_eq!(module_name.remnant_length().unwrap(), 2);
+    let function_names = lenient.parse().unwrap().unwrap().unwrap();
+    assert_eq!(function_names.remnant_length().unwrap(), 0);
+
+    let mut strict = NameSection::new(sections).with_strictness(Strictness::Strict);
+    assert!(strict.parse().unwrap().unwrap().is_err());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn extended_name_subsections() {
+    use wasmiter::{
+        custom::{
+            name::{NameMap, NameSubsection},
+            CustomSection, KnownCustomSection,
+        },
+        index::Index,
+        input::{BorrowInput as _, Input},
+    };
+
+    fn names<N: Index, I: Clone + Input>(map: NameMap<N, u64, I>) -> Vec<(u32, String)> {
+        map.map(|result| {
+            let assoc = result.unwrap();
+            let name = assoc.name().borrow_input().try_into_string().unwrap();
+            (assoc.index().into(), name)
+        })
+        .collect()
+    }
+
+    let wasm = wat::parse_str(
+        r#"(module
+    (type $t (func))
+    (table $tab 1 funcref)
+    (memory $mem 1)
+    (global $g i32 (i32.const 0))
+    (elem $e func)
+    (data $d "")
+    (func (type $t)
+        block $outer
+            block $inner
+            end
+        end))"#,
+    )
+    .unwrap();
+
+    let custom = wasmiter::parse_module_sections(wasm.as_slice())
+        .unwrap()
+        .into_iter()
+        .find_map(|result| CustomSection::try_from_section(result.unwrap()).ok())
+        .unwrap()
+        .unwrap();
+    let section = match KnownCustomSection::interpret(custom) {
+        Ok(KnownCustomSection::Name(section)) => section,
+        _ => unreachable!(),
+    };
+
+    let mut subsections = Vec::new();
+    for result in section {
+        let subsection = result.unwrap().unwrap();
+        let id = subsection.id();
+        let names = match subsection {
+            NameSubsection::TypeName(map) => names(map),
+            NameSubsection::TableName(map) => names(map),
+            NameSubsection::MemoryName(map) => names(map),
+            NameSubsection::GlobalName(map) => names(map),
+            NameSubsection::ElementName(map) => names(map),
+            NameSubsection::DataName(map) => names(map),
+            NameSubsection::LabelName(mut functions) => {
+                let mut labels = Vec::new();
+                while let Some(mut function_labels) = functions
+                    .parse(|function, map| {
+                        assert_eq!(u32::from(function), 0);
+                        Ok(names(map.borrow_input()))
+                    })
+                    .unwrap()
+                {
+                    labels.append(&mut function_labels);
+                }
+                labels
+            }
+            other => panic!("unexpected subsection {other:?}"),
+        };
+        subsections.push((id, names));
+    }
+
+    let named = |name: &str| vec![(0, name.to_string())];
+    assert_eq!(
+        subsections,
+        [
+            (3, vec![(0, "outer".to_string()), (1, "inner".to_string())]),
+            (4, named("t")),
+            (5, named("tab")),
+            (6, named("mem")),
+            (7, named("g")),
+            (8, named("e")),
+            (9, named("d")),
+        ]
+    );
+}
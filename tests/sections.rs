@@ -21,7 +21,7 @@ fn type_section() {
         0x7E, // i64
     ];
 
-    insta::assert_display_snapshot!(component::TypesComponent::new(0, bytes.as_slice()).unwrap());
+    insta::assert_snapshot!(component::TypesComponent::new(0, bytes.as_slice()).unwrap());
 }
 
 #[test]
@@ -38,7 +38,7 @@ fn export_section() {
         ],
     };
 
-    insta::assert_display_snapshot!(component::ExportsComponent::new(0, bytes.as_slice()).unwrap());
+    insta::assert_snapshot!(component::ExportsComponent::new(0, bytes.as_slice()).unwrap());
 }
 
 #[test]
@@ -84,5 +84,936 @@ fn import_section() {
         ],
     };
 
-    insta::assert_display_snapshot!(component::ImportsComponent::new(0, bytes.as_slice()).unwrap());
+    insta::assert_snapshot!(component::ImportsComponent::new(0, bytes.as_slice()).unwrap());
+}
+
+#[cfg(feature = "custom-page-sizes")]
+#[test]
+fn memory_with_custom_page_size() {
+    let bytes = [
+        0b1001u8, // has maximum and page size
+        1,        // minimum
+        2,        // maximum
+        0,        // page size of 2^0
+    ];
+
+    let mut offset = 0;
+    let memory = component::mem_type(&mut offset, &bytes.as_slice()).unwrap();
+    assert_eq!(offset, 4);
+    assert_eq!(memory.maximum(), Some(2));
+    assert_eq!(memory.page_size_log2(), Some(0));
+    assert_eq!(memory.page_size(), 1);
+}
+
+#[test]
+fn unsupported_custom_page_sizes() {
+    // Only 2^0 and 2^16 are allowed
+    let memory = [0b1000u8, 1, 8];
+    assert!(component::mem_type(&mut 0, &memory.as_slice()).is_err());
+
+    // Tables do not have pages
+    let table = [0x70u8, 0b1000, 1, 0];
+    assert!(component::table_type(&mut 0, &table.as_slice()).is_err());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn bind_code_before_function_section() {
+    use wasmiter::component::{CodeSection, FunctionSection, FunctionSignatures};
+    use wasmiter::sections::id;
+
+    let wasm = wat::parse_str(
+        "(module (type (func)) (type (func (param i32))) (func (type 1)) (func (type 0)))",
+    )
+    .unwrap();
+    let sections = wasmiter::parse_module_sections(wasm.as_slice()).unwrap();
+    let mut function_section = None;
+    let mut code_section = None;
+    for result in sections {
+        let section = result.unwrap();
+        let contents = *section.contents();
+        match section.id() {
+            id::FUNC => function_section = Some(FunctionSection::new(contents.base(), contents)),
+            id::CODE => code_section = Some(CodeSection::new(contents.base(), contents)),
+            _ => (),
+        }
+    }
+
+    // Code entries are read before their types are known
+    let codes = code_section
+        .unwrap()
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let signatures = FunctionSignatures::new(function_section.unwrap().unwrap()).unwrap();
+
+    let types = codes
+        .into_iter()
+        .rev()
+        .map(|code| signatures.bind(code).unwrap().signature().to_u32())
+        .collect::<Vec<_>>();
+    assert_eq!(types, [0, 1]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn function_signatures_huge_entry_count() {
+    // Claims to contain 2^32 - 1 entries
+    let bytes = [0xFFu8, 0xFF, 0xFF, 0xFF, 0x0F];
+    let section = component::FunctionSection::new(0, bytes.as_slice()).unwrap();
+    assert!(component::FunctionSignatures::new(section).is_err());
+}
+
+#[test]
+fn code_section_function_indices() {
+    let wasm = wat::parse_str(
+        r#"(module (import "env" "a" (func)) (import "env" "b" (func)) (func) (func))"#,
+    )
+    .unwrap();
+    let sections = wasmiter::parse_module_sections(wasm.as_slice()).unwrap();
+    for result in sections {
+        let section = result.unwrap();
+        if section.id() == wasmiter::sections::id::CODE {
+            let contents = *section.contents();
+            let indices = component::CodeSection::new(contents.base(), contents)
+                .unwrap()
+                .with_function_indices(2)
+                .map(|result| result.map(|(index, _)| index.to_u32()))
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            assert_eq!(indices, [2, 3]);
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn code_section_index_random_access() {
+    let wasm = wat::parse_str(
+        r#"(module
+    (import "env" "a" (func))
+    (func i32.const 1 drop)
+    (func (local i64 f32) nop nop)
+    (func unreachable))"#,
+    )
+    .unwrap();
+    let sections = wasmiter::parse_module_sections(wasm.as_slice()).unwrap();
+    for result in sections {
+        let section = result.unwrap();
+        if section.id() == wasmiter::sections::id::CODE {
+            let contents = *section.contents();
+            let code = component::CodeSection::new(contents.base(), contents).unwrap();
+            let expected = code
+                .map(|result| *result.unwrap().content())
+                .collect::<Vec<_>>();
+
+            let index = component::CodeSectionIndex::new(code, 1).unwrap();
+            assert_eq!(index.len(), 3);
+            for function in [3u32, 1, 2] {
+                let entry = index.get(function.into()).unwrap().unwrap();
+                let expected = &expected[function as usize - 1];
+                assert_eq!(entry.index(), function - 1);
+                assert_eq!(entry.content().base(), expected.base());
+                assert_eq!(entry.content().length(), expected.length());
+            }
+
+            assert!(index.get(0u32.into()).unwrap().is_none());
+            assert!(index.get(4u32.into()).unwrap().is_none());
+
+            // Offsets saved from a previous index can be reused
+            let reused =
+                component::CodeSectionIndex::from_offsets(code, 1, index.offsets().to_vec());
+            assert_eq!(
+                reused.get(2u32.into()).unwrap().unwrap().content().base(),
+                expected[1].base()
+            );
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn code_section_index_huge_entry_count() {
+    // Claims to contain 2^32 - 1 entries
+    let bytes = [0xFFu8, 0xFF, 0xFF, 0xFF, 0x0F];
+    let code = component::CodeSection::new(0, bytes.as_slice()).unwrap();
+    assert!(component::CodeSectionIndex::new(code, 0).is_err());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vector_progress_after_error() {
+    use wasmiter::parser::Vector;
+
+    // Declares 3 elements, but only 2 are present
+    let bytes = [3u8, 0x2A, 0x2B];
+    let mut vector = Vector::parse(0u64, bytes.as_slice()).unwrap();
+    let mut next = || {
+        vector
+            .advance(|offset, input| {
+                let mut byte = [0u8];
+                wasmiter::input::Input::read_exact_at(input, *offset, &mut byte)?;
+                *offset += 1;
+                wasmiter::parser::Parsed::Ok(byte[0])
+            })
+            .map(|result| result.map_err(|error| format!("{error:#}")))
+    };
+
+    assert_eq!(next(), Some(Ok(0x2A)));
+    assert_eq!(next(), Some(Ok(0x2B)));
+    let error = next().unwrap().unwrap_err();
+    assert!(error.contains("vector element #2 of 3"), "{error}");
+    assert_eq!(next(), None);
+    assert_eq!(vector.consumed_count(), 2);
+    assert_eq!(vector.declared_count(), 3);
+    assert_eq!(vector.remaining_count(), 0);
+}
+
+#[test]
+fn vector_resumed_from_position() {
+    let bytes = [3u8, 10, 20, 30];
+    let mut vector = wasmiter::parser::Vector::parse(0u64, bytes.as_slice()).unwrap();
+    let next = |vector: &mut wasmiter::parser::Vector<u64, &[u8]>| {
+        vector
+            .advance(|offset, input| {
+                let mut byte = [0u8];
+                wasmiter::input::Input::read_exact(input, offset, &mut byte)?;
+                wasmiter::parser::Parsed::Ok(byte[0])
+            })
+            .map(Result::unwrap)
+    };
+
+    assert_eq!(next(&mut vector), Some(10));
+    let position = vector.current_position().unwrap();
+    assert_eq!(position.offset(), 2);
+
+    let mut resumed = wasmiter::parser::Vector::from_position(&position, bytes.as_slice()).unwrap();
+    assert_eq!(resumed.consumed_count(), 1);
+    assert_eq!(next(&mut resumed), Some(20));
+    assert_eq!(next(&mut resumed), Some(30));
+    assert_eq!(next(&mut resumed), None);
+
+    let other = [3u8, 10, 21, 30];
+    assert!(wasmiter::parser::Vector::from_position(&position, other.as_slice()).is_err());
+
+    let sections = wasmiter::sections::SectionSequence::new(2, bytes.as_slice());
+    let not_a_vector = sections.current_position().unwrap();
+    assert!(wasmiter::parser::Vector::from_position(&not_a_vector, bytes.as_slice()).is_err());
+}
+
+#[test]
+fn section_sequence_fused_after_error() {
+    // Size of the second section is truncated
+    let bytes = [0u8, 1, 0x2A, 1, 0x80];
+    let mut sections = wasmiter::sections::SectionSequence::new(0, bytes.as_slice());
+
+    assert!(matches!(sections.next(), Some(Ok(_))));
+    assert!(matches!(sections.next(), Some(Err(_))));
+    assert!(sections.next().is_none());
+    assert!(sections.next().is_none());
+}
+
+#[test]
+fn dwarf_custom_sections() {
+    use wasmiter::custom::{dwarf::DwarfSectionKind, CustomSection, KnownCustomSection};
+    use wasmiter::input::Input as _;
+
+    let wasm = wat::parse_str(
+        r#"(module
+    (@custom ".debug_line" "\01\02\03")
+    (@custom ".debug_str_offsets" "")
+    (@custom "external_debug_info" "\13http://example.com/")
+    (@custom ".debug_unknown" "?")
+    (func))"#,
+    )
+    .unwrap();
+
+    let module = wasmiter::parse_module_sections(wasm.as_slice()).unwrap();
+    let mut customs = module.into_iter().filter_map(|section| {
+        let custom = CustomSection::try_from_section(section.unwrap()).ok()?;
+        Some(KnownCustomSection::interpret(custom.unwrap()))
+    });
+
+    match customs.next() {
+        Some(Ok(KnownCustomSection::Dwarf(line))) => {
+            assert_eq!(line.kind(), DwarfSectionKind::Line);
+            assert_eq!(line.contents().length(), 3);
+            let mut contents = [0u8; 3];
+            let window = line.contents();
+            window.read_exact_at(window.base(), &mut contents).unwrap();
+            assert_eq!(contents, [1, 2, 3]);
+        }
+        other => panic!("expected .debug_line section, but got {other:?}"),
+    }
+
+    assert!(matches!(
+        customs.next(),
+        Some(Ok(KnownCustomSection::Dwarf(strs))) if strs.kind() == DwarfSectionKind::StrOffsets
+    ));
+
+    match customs.next() {
+        Some(Ok(KnownCustomSection::ExternalDebugInfo(info))) => {
+            let url = info.url().unwrap().chars_lossy().collect::<String>();
+            assert_eq!(url, "http://example.com/");
+        }
+        other => panic!("expected external_debug_info section, but got {other:?}"),
+    }
+
+    assert!(matches!(customs.next(), Some(Err(_))));
+    assert!(customs.next().is_none());
+}
+
+#[test]
+fn module_info_queries() {
+    use wasmiter::index::FuncIdx;
+
+    let wasm = wat::parse_str(
+        r#"(module
+    (import "env" "log" (func (param i32)))
+    (import "wasi" "thread-spawn" (func (param i32) (result i32)))
+    (import "env" "memory" (memory 2 16 shared))
+    (memory 1)
+    (func $init)
+    (start $init))"#,
+    )
+    .unwrap();
+
+    let module = wasmiter::parse_module_sections(wasm.as_slice()).unwrap();
+    assert!(module.has_wasi_imports().unwrap());
+    assert_eq!(module.initial_memory_pages().unwrap(), Some(2));
+    assert!(module.uses_shared_memory().unwrap());
+    assert_eq!(module.start_function().unwrap(), Some(FuncIdx::from(2u32)));
+
+    let wasm = wat::parse_str(r#"(module (import "wasip1" "f" (func)) (func))"#).unwrap();
+    let module = wasmiter::parse_module_sections(wasm.as_slice()).unwrap();
+    assert!(!module.has_wasi_imports().unwrap());
+    assert_eq!(module.initial_memory_pages().unwrap(), None);
+    assert!(!module.uses_shared_memory().unwrap());
+    assert_eq!(module.start_function().unwrap(), None);
+}
+
+#[test]
+fn segment_display_limits() {
+    use wasmiter::component::{KnownSection, SegmentLimits};
+
+    let mut wat = String::from("(module (memory 1)");
+    for i in 0..70 {
+        wat.push_str(&format!(" (data (i32.const {i}) \"0123456789\")"));
+    }
+    wat.push(')');
+
+    let wasm = wat::parse_str(&wat).unwrap();
+    let datas = wasmiter::parse_module_sections(wasm.as_slice())
+        .unwrap()
+        .find_map(|section| match KnownSection::interpret(section.unwrap()) {
+            Ok(Ok(KnownSection::Data(datas))) => Some(datas),
+            _ => None,
+        })
+        .unwrap();
+
+    let limits = SegmentLimits::new()
+        .with_max_segments(2)
+        .with_max_data_bytes(4);
+    let text = datas.display_with_limits(limits).to_string();
+    assert_eq!(text.matches("\"0123\" (; 6 more bytes ;))").count(), 2);
+    assert!(text.ends_with(")\n(; 68 more data segments ;)\n"), "{text}");
+
+    let debug = format!("{datas:?}");
+    assert!(debug.ends_with(", ... 6 more]"), "{debug}");
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn into_owned_components() {
+    use wasmiter::input::{BorrowInput as _, IntoOwned as _};
+
+    let wasm = std::rc::Rc::<[u8]>::from(
+        wat::parse_str(r#"(module (func (export "a")) (func (export "b")))"#).unwrap(),
+    );
+
+    let mut exports = None;
+    for result in wasmiter::parse_module_sections(wasm).unwrap() {
+        if let Ok(known) = component::KnownSection::interpret(result.unwrap()) {
+            if let component::KnownSection::Export(owned) = known.unwrap() {
+                exports = Some(owned);
+            }
+        }
+    }
+
+    let exports = exports.unwrap();
+    let borrowed = exports.borrow_input();
+    let names = borrowed
+        .into_owned()
+        .map(|export| export.unwrap().name().to_string())
+        .collect::<Vec<_>>();
+
+    assert_eq!(names, ["a", "b"]);
+    assert_eq!(exports.count(), 2);
+}
+
+#[test]
+fn linking_section() {
+    use wasmiter::custom::linking::{
+        ComdatSymbolKind, LinkingSection, LinkingSubsection, SegmentFlags, SymbolFlags, SymbolKind,
+    };
+
+    let symbol_table = bytes! {
+        [3u8],
+        [0, 0, 0, 1, b'f'],
+        [1, 0, 1, b'd', 0, 4, 8],
+        [0, 0x10, 1],
+    };
+    let segment_info = bytes! {
+        [1u8, 5],
+        b".data",
+        [2, 1],
+    };
+    let init_funcs = [1u8, 0xFF, 0xFF, 0x03, 0];
+    let comdat_info = [1u8, 1, b'c', 0, 2, 1, 0, 0, 0];
+
+    let bytes = bytes! {
+        [2u8],
+        [8, symbol_table.len() as u8],
+        symbol_table,
+        [5, segment_info.len() as u8],
+        segment_info,
+        [6, init_funcs.len() as u8],
+        init_funcs,
+        [7, comdat_info.len() as u8],
+        comdat_info,
+    };
+
+    let mut linking = LinkingSection::new(0, bytes.as_slice()).unwrap();
+    assert_eq!(linking.version(), 2);
+
+    let symbols = match linking.parse().unwrap() {
+        Ok(Ok(LinkingSubsection::SymbolTable(symbols))) => symbols,
+        _ => panic!("expected symbol table"),
+    };
+
+    let symbols = symbols.map(Result::unwrap).collect::<Vec<_>>();
+    assert_eq!(symbols.len(), 3);
+    assert_eq!(symbols[0].kind(), &SymbolKind::Function(0u32.into()));
+    assert_eq!(symbols[0].name().unwrap().to_string(), "f");
+    match symbols[1].kind() {
+        SymbolKind::Data(Some(definition)) => {
+            assert_eq!(definition.segment().to_u32(), 0);
+            assert_eq!(definition.offset(), 4);
+            assert_eq!(definition.size(), 8);
+        }
+        other => panic!("expected defined data symbol, got {other:?}"),
+    }
+    assert!(symbols[2].flags().contains(SymbolFlags::UNDEFINED));
+    assert!(symbols[2].name().is_none());
+
+    let mut segments = match linking.parse().unwrap() {
+        Ok(Ok(LinkingSubsection::SegmentInfo(segments))) => segments,
+        _ => panic!("expected segment info"),
+    };
+
+    let segment = segments.next().unwrap().unwrap();
+    assert_eq!(segment.name().to_string(), ".data");
+    assert_eq!(segment.alignment_power(), 2);
+    assert_eq!(segment.flags(), SegmentFlags::STRINGS);
+
+    let mut init_funcs = match linking.parse().unwrap() {
+        Ok(Ok(LinkingSubsection::InitFuncs(init_funcs))) => init_funcs,
+        _ => panic!("expected init functions"),
+    };
+
+    let init = init_funcs.next().unwrap().unwrap();
+    assert_eq!((init.priority(), init.symbol_index()), (65535, 0));
+
+    let mut comdats = match linking.parse().unwrap() {
+        Ok(Ok(LinkingSubsection::ComdatInfo(comdats))) => comdats,
+        _ => panic!("expected COMDAT info"),
+    };
+
+    let comdat = comdats.next().unwrap().unwrap();
+    assert_eq!(comdat.name().to_string(), "c");
+    let kinds = comdat
+        .symbols()
+        .map(|symbol| symbol.unwrap().kind())
+        .collect::<Vec<_>>();
+    assert_eq!(kinds, [ComdatSymbolKind::Function, ComdatSymbolKind::Data]);
+
+    assert!(linking.parse().is_none());
+}
+
+#[test]
+fn linking_subsection_trailing_bytes() {
+    use wasmiter::{
+        custom::{linking::LinkingSubsection, CustomSection, KnownCustomSection, Strictness},
+        parser::ErrorCode,
+    };
+
+    // Custom section containing an empty init functions subsection with one trailing byte
+    let bytes = bytes! {
+        [0u8, 13, 7],
+        b"linking",
+        [2, 6, 2, 0, 0xFF],
+    };
+    let mut sections = wasmiter::sections::SectionSequence::new(0, bytes.as_slice());
+    let section = sections.next().unwrap().unwrap();
+    let custom = || CustomSection::try_from_section(section).unwrap().unwrap();
+
+    let mut lenient = match KnownCustomSection::interpret(custom()) {
+        Ok(KnownCustomSection::Linking(linking)) => linking,
+        _ => panic!("expected linking section"),
+    };
+    let init_funcs = lenient.parse().unwrap().unwrap().unwrap();
+    assert!(matches!(init_funcs, LinkingSubsection::InitFuncs(_)));
+    assert_eq!(init_funcs.remnant_length().unwrap(), 1);
+    assert!(lenient.parse().is_none());
+
+    let mut strict = match KnownCustomSection::interpret_with(custom(), Strictness::Strict) {
+        Ok(KnownCustomSection::Linking(linking)) => linking,
+        _ => panic!("expected linking section"),
+    };
+    let error = strict.parse().unwrap().unwrap().unwrap_err();
+    assert_eq!(error.code(), ErrorCode::TrailingBytes);
+
+    // Unsupported versions are not recognized
+    let mut unsupported = bytes.clone();
+    unsupported[10] = 1;
+    let mut sections = wasmiter::sections::SectionSequence::new(0, unsupported.as_slice());
+    let section = sections.next().unwrap().unwrap();
+    let custom = CustomSection::try_from_section(section).unwrap().unwrap();
+    assert!(KnownCustomSection::interpret(custom).is_err());
+}
+
+#[test]
+fn producers_and_relocation_trailing_bytes() {
+    use wasmiter::{
+        custom::{
+            producers::ProducersSection, reloc::RelocationSection, CustomSection, Strictness,
+        },
+        parser::ErrorCode,
+    };
+
+    // Custom sections containing no fields or entries, followed by one trailing byte
+    let bytes = bytes! {
+        [0u8, 12, 9],
+        b"producers",
+        [0, 0xFF],
+        [0u8, 14, 10],
+        b"reloc.CODE",
+        [0, 0, 0xFF],
+    };
+    let sections = wasmiter::sections::SectionSequence::new(0, bytes.as_slice());
+    let mut customs = sections.map(|section| {
+        CustomSection::try_from_section(section.unwrap())
+            .unwrap()
+            .unwrap()
+    });
+    let (producers, relocations) = (customs.next().unwrap(), customs.next().unwrap());
+
+    let mut lenient = ProducersSection::interpret(producers).unwrap().unwrap();
+    assert_eq!(lenient.remnant_length().unwrap(), 1);
+    assert!(lenient.parse().unwrap().is_none());
+    let error = ProducersSection::interpret_with(producers, Strictness::Strict)
+        .unwrap()
+        .unwrap_err();
+    assert_eq!(error.code(), ErrorCode::TrailingBytes);
+
+    let mut lenient = RelocationSection::interpret(relocations).unwrap().unwrap();
+    assert_eq!(lenient.remnant_length().unwrap(), 1);
+    assert!(lenient.parse().unwrap().is_none());
+    let error = RelocationSection::interpret_with(relocations, Strictness::Strict)
+        .unwrap()
+        .unwrap_err();
+    assert_eq!(error.code(), ErrorCode::TrailingBytes);
+}
+
+#[test]
+fn component_model_sections() {
+    use wasmiter::component_model::{self, id, KnownComponentSection};
+
+    let preamble = *b"\0asm\x0D\0\x01\0";
+    let nested = bytes! {
+        preamble,
+        [id::START, 0],
+    };
+    let component = bytes! {
+        preamble,
+        [id::COMPONENT, nested.len() as u8],
+        nested,
+        [id::EXPORT, 1, 0],
+    };
+
+    let mut sections = component_model::parse_component_sections(component.as_slice()).unwrap();
+    let first = sections.next().unwrap().unwrap();
+    match KnownComponentSection::interpret(first).unwrap() {
+        Ok(KnownComponentSection::Component(mut inner)) => {
+            let start = inner.next().unwrap().unwrap();
+            assert_eq!(start.id(), id::START);
+            assert!(inner.next().is_none());
+        }
+        other => panic!("expected nested component, got {other:?}"),
+    }
+
+    let export = sections.next().unwrap().unwrap();
+    assert_eq!(id::name(export.id()), Some("EXPORT"));
+    assert!(KnownComponentSection::interpret(export).unwrap().is_err());
+    assert!(sections.next().is_none());
+
+    let module = wat::parse_str("(module)").unwrap();
+    assert!(component_model::parse_component_sections(module.as_slice()).is_err());
+}
+
+#[test]
+fn preamble_options() {
+    use wasmiter::{component_model::BinaryKind, PreambleOptions};
+
+    let module = bytes! {
+        *b"\0asm\x02\0\0\0",
+        [wasmiter::sections::id::START, 1, 0],
+    };
+    let component = *b"\0asm\x0A\0\x01\0";
+
+    assert!(wasmiter::parse_module_sections(module.as_slice()).is_err());
+    assert!(
+        wasmiter::parse_module_sections_with_options(module.as_slice(), &Default::default())
+            .is_err()
+    );
+
+    let options = PreambleOptions::new()
+        .with_module_versions(&[1, 2])
+        .with_component_versions(&[0xA]);
+    let (kind, mut sections) =
+        wasmiter::parse_module_sections_with_options(module.as_slice(), &options).unwrap();
+    assert_eq!(kind, BinaryKind::Module(2));
+    assert_eq!(
+        sections.next().unwrap().unwrap().id(),
+        wasmiter::sections::id::START
+    );
+    assert!(sections.next().is_none());
+
+    let (kind, mut sections) =
+        wasmiter::parse_module_sections_with_options(component.as_slice(), &options).unwrap();
+    assert_eq!(kind, BinaryKind::Component(0xA));
+    assert!(sections.next().is_none());
+
+    let unsupported = wasmiter::parse_module_sections_with_options(
+        component.as_slice(),
+        &options.with_component_versions(&[0xD]),
+    )
+    .unwrap_err()
+    .to_string();
+    assert!(unsupported.contains("unsupported WebAssembly version"));
+
+    let unexpected = wasmiter::parse_module_sections_with_options(
+        component.as_slice(),
+        &options.with_component_versions(&[]),
+    )
+    .unwrap_err()
+    .to_string();
+    assert!(unexpected.contains("got a component with version 0x000A"));
+}
+
+#[test]
+fn error_codes() {
+    use wasmiter::parser::ErrorCode;
+
+    let bad_magic = wasmiter::parse_module_sections(b"\0wsm\x01\0\0\0".as_slice()).unwrap_err();
+    assert_eq!(bad_magic.code(), ErrorCode::BadWasmMagic);
+    #[cfg(feature = "alloc")]
+    assert_eq!(bad_magic.offset(), Some(0));
+
+    let version = wasmiter::parse_module_sections(b"\0asm\x03\0\0\0".as_slice()).unwrap_err();
+    assert_eq!(version.code(), ErrorCode::UnsupportedWasmVersion);
+    #[cfg(feature = "alloc")]
+    assert_eq!(version.offset(), Some(0));
+
+    let truncated = wasmiter::parse_module_sections(b"\0asm\x01".as_slice()).unwrap_err();
+    assert_eq!(truncated.code(), ErrorCode::BadInput);
+    assert!(truncated.input_error().is_some());
+
+    // Second section declares 16 bytes of contents, but only 2 are present
+    let bytes = [0u8, 1, 0x2A, 1, 16, 0xAB, 0xCD];
+    let mut sections = wasmiter::sections::SectionSequence::new(0, bytes.as_slice());
+    sections.next().unwrap().unwrap();
+    let incomplete = sections.next().unwrap().unwrap_err();
+    assert_eq!(incomplete.code(), ErrorCode::BadInput);
+    #[cfg(feature = "alloc")]
+    assert!(incomplete.offset().is_some());
+    assert!(sections.next().is_none());
+
+    // Discriminants are stable
+    assert_eq!(ErrorCode::BadWasmMagic as u16, 5);
+    assert_eq!(ErrorCode::InvalidOpcode as u16, 8);
+}
+
+#[test]
+fn spanned_items() {
+    use wasmiter::{
+        component::KnownSection,
+        instruction_set::Instruction,
+        parser::{Parsed, Span, SpannedItems as _},
+    };
+
+    let wasm = wat::parse_str(
+        r#"(module
+    (import "env" "log" (func (param i32)))
+    (func (export "run") (param i32)
+        local.get 0
+        call 0))"#,
+    )
+    .unwrap();
+
+    let bytes_of =
+        |span: Span| &wasm[usize::try_from(span.start()).unwrap()..][..span.len() as usize];
+
+    let mut code_entries = Vec::new();
+    for result in wasmiter::parse_module_sections(wasm.as_slice()).unwrap() {
+        match KnownSection::interpret(result.unwrap()).unwrap().unwrap() {
+            KnownSection::Import(imports) => {
+                let import = imports.spanned().next().unwrap().unwrap();
+                assert_eq!(import.module().to_string(), "env");
+                assert_eq!(bytes_of(import.span()), b"\x03env\x03log\x00\x00");
+            }
+            KnownSection::Function(functions) => {
+                let spans = functions
+                    .spanned()
+                    .map(|index| Ok(index?.span()))
+                    .collect::<Parsed<Vec<_>>>()
+                    .unwrap();
+                assert_eq!(spans.len(), 1);
+                assert_eq!(bytes_of(spans[0]), [0]);
+            }
+            KnownSection::Export(exports) => {
+                let export = exports.spanned().next().unwrap().unwrap();
+                assert_eq!(bytes_of(export.span()), b"\x03run\x00\x01");
+            }
+            KnownSection::Code(code) => {
+                code_entries = code.spanned().collect::<Parsed<Vec<_>>>().unwrap();
+            }
+            _ => (),
+        }
+    }
+
+    assert_eq!(code_entries.len(), 1);
+    let entry = &code_entries[0];
+    // Body size, no locals, local.get 0, call 0, end
+    assert_eq!(bytes_of(entry.span()), [6, 0, 0x20, 0, 0x10, 0, 0x0B]);
+
+    let instructions = entry
+        .read(
+            |_| Ok(()),
+            |_, body| {
+                let mut spans = Vec::new();
+                while let Some(result) = body.next_spanned(|i| {
+                    Ok::<_, wasmiter::parser::Error>(matches!(i, Instruction::Call(_)))
+                }) {
+                    spans.push(result?);
+                }
+                Ok::<_, wasmiter::parser::Error>(spans)
+            },
+        )
+        .unwrap();
+
+    let spans = instructions
+        .iter()
+        .map(|instruction| bytes_of(instruction.span()))
+        .collect::<Vec<_>>();
+    assert_eq!(spans, [&[0x20, 0][..], &[0x10, 0], &[0x0B]]);
+    assert!(*instructions[1].item());
+}
+
+#[test]
+fn data_segment_windows() {
+    use wasmiter::{
+        component::{DataMode, KnownSection},
+        input::Input as _,
+    };
+
+    let wasm = wat::parse_str(
+        r#"(module
+    (memory 2)
+    (data "first")
+    (data (memory 0) (i32.const 8) "second")
+    (data "third"))"#,
+    )
+    .unwrap();
+
+    let datas = wasmiter::parse_module_sections(wasm.as_slice())
+        .unwrap()
+        .find_map(|section| match KnownSection::interpret(section.unwrap()) {
+            Ok(Ok(KnownSection::Data(datas))) => Some(datas),
+            _ => None,
+        })
+        .unwrap();
+
+    let read = |segment: &component::DataSegment<&wasmiter::input::Window<&[u8]>>| {
+        let data = segment.data();
+        let mut buffer = vec![0u8; data.length() as usize];
+        data.read_exact_at(data.base(), &mut buffer).unwrap();
+        buffer
+    };
+
+    let second = datas.find_segment(1u32.into()).unwrap().unwrap();
+    assert_eq!(read(&second), b"second");
+    match second.mode() {
+        DataMode::Active(memory, offset) => {
+            assert_eq!(memory.to_u32(), 0);
+            assert!(!offset.is_finished());
+        }
+        DataMode::Passive => panic!("expected active data segment"),
+    }
+
+    let third = datas.find_segment(2u32.into()).unwrap().unwrap();
+    assert_eq!(read(&third), b"third");
+    assert!(datas.find_segment(3u32.into()).unwrap().is_none());
+
+    // Searching does not advance the component
+    let mut remaining = datas;
+    assert_eq!(remaining.remaining_count(), 3);
+    let mut contents = Vec::new();
+    while let Some(segment) = remaining.parse_segment().unwrap() {
+        contents.push(read(&segment));
+    }
+    assert_eq!(contents, [&b"first"[..], b"second", b"third"]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn eval_const_expr() {
+    use wasmiter::{
+        component::{ConstValue, DataMode, KnownSection},
+        index::GlobalIdx,
+        types::RefType,
+    };
+
+    let wasm = wat::parse_str(
+        r#"(module
+    (import "env" "base" (global i64))
+    (memory 1)
+    (global i32 (i32.const -7))
+    (global f64 (f64.const 1.5))
+    (global funcref (ref.null func))
+    (global i64 (i64.mul (global.get 0) (i64.sub (i64.const 10) (i64.const 2))))
+    (global i32 (global.get 1))
+    (data (i32.add (i32.const 100) (i32.const 28)) "hi"))"#,
+    )
+    .unwrap();
+
+    let mut globals = None;
+    let mut datas = None;
+    for section in wasmiter::parse_module_sections(wasm.as_slice()).unwrap() {
+        match KnownSection::interpret(section.unwrap()) {
+            Ok(Ok(KnownSection::Global(section))) => globals = Some(section),
+            Ok(Ok(KnownSection::Data(section))) => datas = Some(section),
+            _ => (),
+        }
+    }
+
+    let imported = |global: GlobalIdx| (global.to_u32() == 0).then_some(ConstValue::I64(5));
+    // Stops at the first error, since the rest of the initializer is not parsed
+    let evaluate = |extended| {
+        let mut globals = globals.unwrap();
+        let mut values = Vec::new();
+        while let Some(result) = globals
+            .parse(|_, initializer| Ok(component::eval_const_expr(initializer, extended, imported)))
+            .unwrap()
+        {
+            let is_err = result.is_err();
+            values.push(result);
+            if is_err {
+                break;
+            }
+        }
+        values
+    };
+
+    let values = evaluate(true);
+    assert_eq!(values[0].as_ref().unwrap(), &ConstValue::I32(-7));
+    assert_eq!(values[1].as_ref().unwrap(), &ConstValue::F64(1.5));
+    assert_eq!(
+        values[2].as_ref().unwrap(),
+        &ConstValue::RefNull(RefType::Func)
+    );
+    assert_eq!(values[3].as_ref().unwrap(), &ConstValue::I64(40));
+    // Only the imported global can be read
+    let error = values[4].as_ref().unwrap_err().to_string();
+    assert!(error.contains("global 1 cannot be read"), "{error}");
+
+    let values = evaluate(false);
+    assert!(values[0].is_ok());
+    let error = values[3].as_ref().unwrap_err().to_string();
+    assert!(error.contains("i64.sub is not allowed"), "{error}");
+
+    let mut datas = datas.unwrap();
+    let offset = datas
+        .parse(
+            |mode| match mode {
+                DataMode::Active(_, offset) => {
+                    component::eval_const_expr(offset, true, |_| None).map(Some)
+                }
+                DataMode::Passive => Ok(None),
+            },
+            |offset, _| Ok(offset),
+        )
+        .unwrap()
+        .unwrap();
+    assert_eq!(offset, Some(ConstValue::I32(128)));
+
+    let mut expression = wasmiter::instruction_set::InstructionSequence::new(
+        0,
+        [0x41u8, 1, 0x41, 2, 0x0B].as_slice(),
+    );
+    let error = component::eval_const_expr(&mut expression, true, |_| None)
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("but 2 values were produced"), "{error}");
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn imports_grouped_by_module() {
+    use wasmiter::component::KnownSection;
+
+    let wasm = wat::parse_str(
+        r#"(module
+            (import "env" "a" (func))
+            (import "wasi" "b" (func))
+            (import "env" "c" (global i32))
+            (import "js" "d" (memory 1))
+            (import "wasi" "e" (func))
+            (import "env" "f" (func)))"#,
+    )
+    .unwrap();
+
+    let sections = wasmiter::parse_module_sections(wasm.as_slice()).unwrap();
+    let imports = sections
+        .into_iter()
+        .find_map(|section| match KnownSection::interpret(section.unwrap()) {
+            Ok(Ok(KnownSection::Import(imports))) => Some(imports),
+            _ => None,
+        })
+        .unwrap();
+
+    let modules = imports.group_by_module().unwrap();
+    assert_eq!(modules.len(), 3);
+
+    let grouped = modules
+        .map(|module| {
+            let name = module.module().to_string();
+            let imports = module
+                .map(|import| import.unwrap().name().to_string())
+                .collect::<Vec<_>>();
+            (name, imports)
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        grouped,
+        [
+            (
+                "env".to_string(),
+                ["a", "c", "f"].map(String::from).to_vec()
+            ),
+            ("wasi".to_string(), ["b", "e"].map(String::from).to_vec()),
+            ("js".to_string(), ["d"].map(String::from).to_vec()),
+        ]
+    );
 }
@@ -0,0 +1,552 @@
+#[cfg(feature = "alloc")]
+use wasmiter::input::Input as _;
+
+/// Gets the contents of all of the non-custom sections, since the name section produced by `wat`
+/// contains more information than the one produced by `wasmiter`.
+#[cfg(feature = "alloc")]
+fn known_sections(wasm: &[u8]) -> Vec<(u8, Vec<u8>)> {
+    wasmiter::parse_module_sections(wasm)
+        .unwrap()
+        .into_iter()
+        .map(Result::unwrap)
+        .filter(|section| section.id() != wasmiter::sections::id::CUSTOM)
+        .map(|section| {
+            let contents = section.contents();
+            let mut bytes = vec![0; usize::try_from(contents.length()).unwrap()];
+            contents.read_exact_at(contents.base(), &mut bytes).unwrap();
+            (section.id(), bytes)
+        })
+        .collect()
+}
+
+#[cfg(feature = "alloc")]
+fn assert_matches_wat(text: &str) {
+    let expected = wat::parse_str(text).unwrap();
+    let actual = wasmiter::text::parse_str(text).unwrap();
+    assert_eq!(known_sections(&actual), known_sections(&expected));
+}
+
+#[cfg(feature = "alloc")]
+macro_rules! check_modules {
+    ($($(#[$attribute:meta])* $name:ident,)*) => {$(
+        $(#[$attribute])*
+        #[test]
+        fn $name() {
+            assert_matches_wat(include_str!(concat!("modules/", stringify!($name), ".wat")));
+        }
+    )*};
+}
+
+#[cfg(feature = "alloc")]
+check_modules! {
+    all_the_things,
+    lots_of_br_table,
+    #[cfg(feature = "exceptions")]
+    exception_handling,
+    tag_imports_exports,
+    name_custom_section,
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn folded_instructions() {
+    assert_matches_wat(
+        r#"(module
+    (func $fib (export "fib") (param $n i64) (result i64)
+        (if (result i64) (i64.lt_u (local.get $n) (i64.const 2))
+            (then (local.get $n))
+            (else
+                (i64.add
+                    (call $fib (i64.sub (local.get $n) (i64.const 1)))
+                    (call $fib (i64.sub (local.get $n) (i64.const 2)))))))
+    (func (param i32) (result i32)
+        (block $outer (result i32)
+            (loop $inner
+                (br_if $inner (local.get 0))
+                (br_table $inner $outer 0 (i32.const 7) (local.get 0)))
+            (select (i32.const 1) (i32.const 2) (local.get 0)))))"#,
+    );
+}
+
+#[cfg(all(feature = "alloc", feature = "simd"))]
+#[test]
+fn plain_instructions() {
+    assert_matches_wat(
+        r#"(module
+    (type $binary (func (param i32 i32) (result i32)))
+    (table 2 funcref)
+    (memory $mem 1 2)
+    (memory $other i64 1)
+    (global $counter (mut i64) (i64.const -1))
+    (func $add (type $binary)
+        local.get 0
+        local.get 1
+        i32.add)
+    (func (param $x i32) (result i32) (local $y i64) (local f32 f32)
+        block $exit (param i32) (result i32)
+            i32.const 2
+            call_indirect (type $binary)
+            br $exit
+        end $exit
+        i32.load16_u offset=4
+        i64.load8_s $other offset=16 align=1
+        drop
+        global.get $counter
+        i64.const 0xFFFF_FFFF
+        i64.add
+        global.set $counter
+        f64.const -0x1.8p3
+        f32.const 1.5e-2
+        drop
+        drop
+        i32.const 0
+        memory.size
+        memory.grow
+        select (result i32)
+        i32.const 0
+        i32.const 0
+        i32.const 0
+        memory.copy $other $mem
+        v128.const i16x8 1 -2 3 -4 5 6 7 0x8000
+        i8x16.extract_lane_s 15
+        drop
+        i32.const 0
+        v128.const f32x4 1 2 3 inf
+        v128.load32_lane 1
+        drop))"#,
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn segments() {
+    assert_matches_wat(
+        r#"(module
+    (table $t 4 funcref)
+    (table $refs 2 externref)
+    (table $inline funcref (elem $f $g))
+    (memory 1)
+    (memory $inline_mem (data "hello" "\00\ff\u{263A}"))
+    (func $f)
+    (func $g (param i32)
+        i32.const 0
+        i32.const 0
+        local.get 0
+        memory.init $passive
+        data.drop $passive
+        elem.drop $declared)
+    (elem (i32.const 0) $f $g)
+    (elem (table $t) (offset (i32.const 2)) func $g)
+    (elem $declared declare func $f)
+    (elem $exprs funcref (ref.func $f) (item ref.null func))
+    (elem (table $refs) (i32.const 0) externref (ref.null extern))
+    (data (i32.const 8) "abc")
+    (data $passive "passive")
+    (data (memory $inline_mem) (offset i32.const 1) "x"))"#,
+    );
+}
+
+#[cfg(all(feature = "alloc", feature = "exceptions"))]
+#[test]
+fn imports_and_exports() {
+    assert_matches_wat(
+        r#"(module
+    (func $imported (export "reexported") (import "env" "f") (param i32))
+    (import "env" "g" (global $g i32))
+    (global (export "h") (import "env" "h") f64)
+    (memory (export "memory") 1)
+    (tag $e (export "tag") (param i64))
+    (func (export "a") (export "b") (param i32)
+        local.get 0
+        call $imported
+        global.get $g
+        i64.extend_i32_u
+        throw $e)
+    (export "table" (table $table))
+    (table $table 0 funcref))"#,
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn bare_module_fields() {
+    let with_module = wasmiter::text::parse_str("(module (memory 1) (func))").unwrap();
+    let without_module = wasmiter::text::parse_str("(memory 1) (func)").unwrap();
+    assert_eq!(with_module, without_module);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn error_location() {
+    let error =
+        wasmiter::text::parse_str("(module\n    (func\n        local.get $missing))").unwrap_err();
+
+    assert_eq!(error.line(), 3);
+    assert_eq!(error.column(), 19);
+    assert_eq!(error.to_string(), "3:19: unknown local $missing");
+
+    let error = wasmiter::text::parse_str("(module (func i32.nope))").unwrap_err();
+    assert_eq!(error.to_string(), "1:15: unknown instruction `i32.nope`");
+
+    let error = wasmiter::text::parse_str("(module (func)").unwrap_err();
+    assert_eq!(error.to_string(), "1:1: unclosed `(`");
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn round_trip_display_module() {
+    let wasm = wat::parse_str(
+        r#"(module
+    (import "env" "print" (func (param i32 i64)))
+    (table 1 funcref)
+    (memory 1)
+    (global (mut i32) (i32.const -1))
+    (tag (param i32))
+    (func (param i32) (result i32) (local i64 i64)
+        block (result i32)
+            local.get 0
+            i32.const 0xFFFF
+            i32.lt_u
+            br_if 0
+            local.get 0
+            i32.load8_u offset=2
+            i32.const 0
+            call_indirect (type 1)
+            select (result i32)
+        end)
+    (elem (i32.const 0) 0 1)
+    (data (i32.const 16) "\01\02\03 text\ff"))"#,
+    )
+    .unwrap();
+
+    let module = wasmiter::parse_module_sections(wasm.as_slice()).unwrap();
+    let printed = module.display_module().to_string();
+    let parsed = wasmiter::text::parse_module(&printed).unwrap();
+    assert_eq!(parsed.display_module().to_string(), printed);
+}
+
+#[cfg(all(feature = "alloc", feature = "function-references"))]
+#[test]
+fn typed_function_references() {
+    let text = r#"(module
+    (type (func (param i32) (result i32)))
+    (table 1 (ref null 0))
+    (global (ref null 0) (ref.null 0))
+    (func (param (ref null 0)) (result i32)
+        i32.const 1
+        local.get 0
+        call_ref 0)
+    (func (param (ref 0)) (result (ref 0))
+        local.get 0))"#;
+
+    assert_matches_wat(text);
+
+    let wasm = wat::parse_str(text).unwrap();
+    let module = wasmiter::parse_module_sections(wasm.as_slice()).unwrap();
+    let printed = module.display_module().to_string();
+    assert!(
+        printed.contains("(table (; 0 ;) 1 (ref null 0))"),
+        "{printed}"
+    );
+    assert!(
+        printed.contains("(param (ref 0)) (result (ref 0))"),
+        "{printed}"
+    );
+
+    let parsed = wasmiter::text::parse_module(&printed).unwrap();
+    assert_eq!(parsed.display_module().to_string(), printed);
+}
+
+#[cfg(all(feature = "alloc", feature = "exceptions"))]
+#[test]
+fn try_table() {
+    let text = r#"(module
+    (tag (param i32))
+    (func (param exnref) (result i32 exnref)
+        block (result i32 exnref)
+            try_table (catch_ref 0 0) (catch_all 1)
+                i32.const 1
+                throw 0
+            end
+            local.get 0
+            throw_ref
+        end)
+    (func (result i32)
+        block (result i32)
+            try_table (result i32) (catch 0 0) (catch_all_ref 1)
+                i32.const 2
+            end
+        end))"#;
+
+    assert_matches_wat(text);
+
+    let wasm = wat::parse_str(text).unwrap();
+    let module = wasmiter::parse_module_sections(wasm.as_slice()).unwrap();
+    let printed = module.display_module().to_string();
+    assert!(
+        printed.contains("try_table (catch_ref 0 0) (catch_all 1)"),
+        "{printed}"
+    );
+    assert!(
+        printed.contains("try_table (result i32) (catch 0 0) (catch_all_ref 1)"),
+        "{printed}"
+    );
+    assert!(printed.contains("(param exnref)"), "{printed}");
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn memory64() {
+    let text = r#"(module
+    (import "env" "memory" (memory i64 1))
+    (memory i64 1 0x1_0000_0000)
+    (table i64 1 funcref)
+    (func (param i64) (result i64)
+        local.get 0
+        i64.load offset=0x1_0000_0000
+        memory.grow 1)
+    (func (param i64) (result funcref)
+        local.get 0
+        table.get 0)
+    (data (memory 1) (i64.const 16) "hi"))"#;
+
+    assert_matches_wat(text);
+
+    let wasm = wat::parse_str(text).unwrap();
+    wasmiter::validate::validate_module(wasm.as_slice()).unwrap();
+
+    let module = wasmiter::parse_module_sections(wasm.as_slice()).unwrap();
+    let printed = module.display_module().to_string();
+    assert!(printed.contains("(memory (; 0 ;) i64 1)"), "{printed}");
+    assert!(
+        printed.contains("(memory (; 1 ;) i64 1 4294967296)"),
+        "{printed}"
+    );
+    assert!(
+        printed.contains("(table (; 0 ;) i64 1 funcref)"),
+        "{printed}"
+    );
+
+    let parsed = wasmiter::text::parse_module(&printed).unwrap();
+    assert_eq!(parsed.display_module().to_string(), printed);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn multi_memory() {
+    let text = r#"(module
+    (import "env" "memory" (memory 1))
+    (memory 2)
+    (memory 3 4)
+    (func (param i32) (result i32)
+        local.get 0
+        local.get 0
+        i32.load 2 offset=4
+        i32.store 1
+        i32.const 0
+        i32.const 0
+        i32.const 8
+        memory.copy 1 0
+        i32.const 0
+        i32.const 0
+        i32.const 8
+        memory.copy 0 0
+        i32.const 0
+        i32.const 0
+        i32.const 8
+        memory.init 2 0
+        memory.size 2)
+    (data (memory 1) (i32.const 0) "hi"))"#;
+
+    assert_matches_wat(text);
+
+    let wasm = wat::parse_str(text).unwrap();
+    wasmiter::validate::validate_module(wasm.as_slice()).unwrap();
+
+    let module = wasmiter::parse_module_sections(wasm.as_slice()).unwrap();
+    let printed = module.display_module().to_string();
+    assert!(printed.contains("(memory (; 0 ;) 1)"), "{printed}");
+    assert!(printed.contains("(memory (; 1 ;) 2)"), "{printed}");
+    assert!(printed.contains("(memory (; 2 ;) 3 4)"), "{printed}");
+    assert!(printed.contains("i32.load 2 offset=4"), "{printed}");
+    assert!(printed.contains("i32.store 1"), "{printed}");
+    assert!(printed.contains("memory.copy 1 0\n"), "{printed}");
+    assert!(printed.contains("memory.copy\n"), "{printed}");
+    assert!(printed.contains("memory.init 2 0"), "{printed}");
+    assert!(printed.contains("memory.size 2"), "{printed}");
+
+    // The printed text is accepted by other tools
+    assert_eq!(wat::parse_str(&printed).unwrap(), wasm);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn display_single_function() {
+    use wasmiter::index::FuncIdx;
+
+    let wasm = wat::parse_str(
+        r#"(module
+    (import "env" "log" (func $log (param i32)))
+    (func $first i32.const 1 call $log)
+    (func (result i32) i32.const 2)
+    (func $third (param i64) local.get 0 drop))"#,
+    )
+    .unwrap();
+
+    let module = wasmiter::parse_module_sections(wasm.as_slice()).unwrap();
+    assert_eq!(module.function_count().unwrap(), 4);
+    assert_eq!(
+        module.find_function_by_name("third").unwrap(),
+        Some(FuncIdx::from(3u32))
+    );
+
+    let display = |index: u32| module.display_function(FuncIdx::from(index)).to_string();
+    assert_eq!(display(0), "(func $log (import \"env\" \"log\") (type 0))");
+
+    let third = display(3);
+    assert!(third.starts_with("(func $third (type 3)"), "{third}");
+    assert!(third.contains("local.get 0\n"), "{third}");
+    assert!(!third.contains("i32.const"), "{third}");
+
+    // Functions without names are written without an identifier
+    let second = display(2);
+    assert!(second.starts_with("(func (type 2)"), "{second}");
+    assert!(second.contains("i32.const 0x00000002"), "{second}");
+
+    assert_eq!(display(4), "(; function 4 is not defined ;)");
+}
+
+#[test]
+fn config_without_comments() {
+    use wasmiter::text::Config;
+
+    let wasm = wat::parse_str(
+        r#"(module
+    (import "env" "counter" (global (mut i64)))
+    (memory 1)
+    (global f32 (f32.const -nan:0x200))
+    (global f64 (f64.const 0.1))
+    (func (param i32) (result i32) (local f64)
+        i64.const -5
+        drop
+        f32.const -0
+        drop
+        f64.const inf
+        drop
+        local.get 0
+        if (result i32)
+            i32.const -1
+        else
+            block
+                loop
+                    br 1
+                end
+            end
+            i32.const 2
+        end)
+    (data (i32.const 0) "hello"))"#,
+    )
+    .unwrap();
+
+    let module = wasmiter::parse_module_sections(wasm.as_slice()).unwrap();
+    for folded in [false, true] {
+        let config = Config::new()
+            .with_comments(false)
+            .with_folded_instructions(folded)
+            .with_indentation(4);
+        let printed = module.display_module_with(config).to_string();
+
+        assert!(!printed.contains("(;"), "{printed}");
+        assert!(printed.contains("\n    (local f64)"), "{printed}");
+        assert!(printed.contains("i32.const -1"), "{printed}");
+        assert_eq!(printed.contains("(then"), folded, "{printed}");
+
+        // Constants are written without hexadecimal, so other tools can parse them
+        assert_eq!(wat::parse_str(&printed).unwrap(), wasm, "{printed}");
+    }
+}
+
+#[test]
+fn config_max_items() {
+    use wasmiter::text::Config;
+
+    let wasm = wat::parse_str(
+        r#"(module
+    (import "env" "a" (memory 1))
+    (import "env" "b" (memory 1))
+    (memory 1)
+    (func)
+    (func)
+    (func))"#,
+    )
+    .unwrap();
+
+    let module = wasmiter::parse_module_sections(wasm.as_slice()).unwrap();
+    let printed = module
+        .display_module_with(Config::new().with_max_items(1))
+        .to_string();
+
+    assert!(printed.contains("(; 1 more imports ;)"), "{printed}");
+    assert!(printed.contains("(; 2 more functions ;)"), "{printed}");
+
+    // Omitted imports are still counted when writing indices
+    assert!(printed.contains("(memory (; 2 ;) 1)"), "{printed}");
+}
+
+#[test]
+fn config_folded_instructions() {
+    use wasmiter::text::Config;
+
+    let wasm = wat::parse_str(
+        r#"(module
+    (memory 1)
+    (func (param i32 i32) (result i32)
+        local.get 0
+        local.get 1
+        i32.add
+        i32.const 1
+        i32.add
+        i32.eqz
+        if (result i32)
+            i32.const 4
+            i32.load offset=4
+        else
+            local.get 1
+            local.get 0
+            call 0
+        end
+        i32.const 0
+        i32.const 1
+        i32.store))"#,
+    )
+    .unwrap();
+
+    let module = wasmiter::parse_module_sections(wasm.as_slice()).unwrap();
+    let config = Config::new()
+        .with_comments(false)
+        .with_folded_instructions(true);
+    let printed = module.display_module_with(config).to_string();
+
+    assert!(
+        printed.contains(
+            "(if (result i32) (i32.eqz (i32.add (i32.add (local.get 0) (local.get 1)) (i32.const 1)))"
+        ),
+        "{printed}"
+    );
+    assert!(
+        printed.contains("(then\n      (i32.load offset=4 (i32.const 4)))"),
+        "{printed}"
+    );
+
+    // Operands of instructions whose stack effect is not known are written separately
+    assert!(
+        printed.contains("(local.get 1)\n      (local.get 0)\n      call 0))"),
+        "{printed}"
+    );
+    assert!(
+        printed.contains("(i32.store (i32.const 0) (i32.const 1))"),
+        "{printed}"
+    );
+
+    assert_eq!(wat::parse_str(&printed).unwrap(), wasm, "{printed}");
+}
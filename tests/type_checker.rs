@@ -0,0 +1,82 @@
+#![cfg(feature = "alloc")]
+
+use wasmiter::component::LocalTypes;
+use wasmiter::index::GlobalIdx;
+use wasmiter::instruction_set::{self, TypeChecker, TypeContext};
+use wasmiter::types::{FuncType, GlobalMutability, GlobalType, ValType};
+use wasmiter::validate::ValidationError;
+
+struct Globals(Vec<GlobalType>);
+
+impl TypeContext for Globals {
+    fn global(&self, index: GlobalIdx) -> Option<GlobalType> {
+        self.0.get(usize::from(index)).copied()
+    }
+}
+
+fn check_body<C: TypeContext>(
+    context: &C,
+    signature: &FuncType,
+    body: &[u8],
+) -> Result<Vec<Vec<Option<ValType>>>, ValidationError> {
+    let mut checker = TypeChecker::new(context, signature, LocalTypes::default());
+    let mut instructions = instruction_set::parse_expression(0, body);
+    let mut stacks = Vec::new();
+
+    loop {
+        let offset = instructions.offset();
+        let result = instructions.next(|instruction| checker.instruction(offset, instruction));
+        match result {
+            Some(result) => result?,
+            None => break,
+        }
+
+        stacks.push(checker.operands().to_vec());
+    }
+
+    checker.finish(instructions.offset())?;
+    Ok(stacks)
+}
+
+#[test]
+fn operand_stack_is_tracked() {
+    let context = Globals(vec![GlobalType::new(
+        GlobalMutability::Variable,
+        ValType::F32,
+    )]);
+    let signature = FuncType::new([], [ValType::F32]);
+
+    // global.get 0, f32.neg, end
+    let stacks = check_body(&context, &signature, &[0x23, 0, 0x8C, 0x0B]).unwrap();
+    assert_eq!(stacks, vec![vec![Some(ValType::F32)]; 3]);
+}
+
+#[test]
+fn type_mismatch_has_offset_and_types() {
+    let context = Globals(vec![GlobalType::new(
+        GlobalMutability::Constant,
+        ValType::I64,
+    )]);
+    let signature = FuncType::new([], []);
+
+    // global.get 0, i32.eqz, drop, end
+    let error = check_body(&context, &signature, &[0x23, 0, 0x45, 0x1A, 0x0B]).unwrap_err();
+    assert_eq!(error.offset(), Some(2));
+    assert_eq!(error.expected_type(), Some(ValType::I32));
+    assert_eq!(error.actual_type(), Some(ValType::I64));
+}
+
+#[test]
+fn missing_definitions_are_unknown() {
+    let context = Globals(Vec::new());
+    let signature = FuncType::new([], []);
+
+    // call_indirect 0 0, end
+    let error = check_body(&context, &signature, &[0x11, 0, 0, 0x0B]).unwrap_err();
+    assert!(error.to_string().contains("unknown table 0"), "{error}");
+    assert_eq!(error.expected_type(), None);
+
+    // The function type of a block is looked up in the context
+    let error = check_body(&context, &signature, &[0x02, 0, 0x0B, 0x0B]).unwrap_err();
+    assert!(error.to_string().contains("unknown type 0"), "{error}");
+}
@@ -0,0 +1,309 @@
+#![cfg(feature = "alloc")]
+
+use arbitrary::{Arbitrary as _, Unstructured};
+use rand::{RngCore as _, SeedableRng as _};
+use wasmiter::validate::validate_module;
+
+fn validate_wat(wat: &str) -> Result<(), String> {
+    let wasm = wat::parse_str(wat).unwrap();
+    validate_module(wasm.as_slice()).map_err(|e| e.to_string())
+}
+
+#[test]
+fn valid_test_modules() {
+    for entry in std::fs::read_dir("tests/modules").unwrap() {
+        let path = entry.unwrap().path();
+
+        // Exception handling instructions are only parsed when the `exceptions` feature is enabled
+        if cfg!(not(feature = "exceptions"))
+            && path.file_stem() == Some("exception_handling".as_ref())
+        {
+            continue;
+        }
+
+        let wasm = wat::parse_file(&path).unwrap();
+        if let Err(e) = validate_module(wasm.as_slice()) {
+            panic!("{} should be valid: {e}", path.display());
+        }
+    }
+}
+
+#[test]
+fn validate_parsed_sections() {
+    let wasm = wat::parse_str("(module (func (result i32) i64.const 1))").unwrap();
+    let sections = wasmiter::parse_module_sections(wasm.as_slice()).unwrap();
+    let error = wasmiter::validate::validate_module_sections(&sections).unwrap_err();
+    assert!(error.to_string().contains("expected i32 but got i64"));
+    assert!(error.offset().is_some());
+}
+
+#[test]
+fn valid_control_flow() {
+    let wat = r#"(module
+    (type $pair (func (param i32 i32) (result i32)))
+    (memory 1)
+    (table 2 funcref)
+    (global $g (mut i64) (i64.const 0))
+    (elem declare func $f)
+    (func $f (param i32) (result i32)
+        (local f32)
+        block (result i32)
+            local.get 0
+            local.get 0
+            br_if 0
+            drop
+            i32.const 1
+            i32.const 2
+            block (type $pair)
+                i32.add
+            end
+        end
+        if (result i32)
+            i32.const 0
+            i32.load offset=4
+        else
+            unreachable
+        end
+        loop (param i32) (result i32)
+            i32.const 1
+            br_table 0 1 1
+        end)
+    (func (result funcref)
+        ref.func $f)
+    (func
+        global.get $g
+        i64.const 1
+        i64.add
+        global.set $g
+        i32.const 0
+        i32.const 1
+        i32.const 0
+        call_indirect (type $pair)
+        drop
+        return))
+"#;
+
+    assert_eq!(validate_wat(wat), Ok(()));
+}
+
+#[test]
+fn type_mismatch() {
+    let error = validate_wat("(module (func (param i64) (result i32) local.get 0))").unwrap_err();
+    assert!(error.contains("expected i32 but got i64"), "{error}");
+
+    let error = validate_wat("(module (func i32.const 1 i32.add drop))").unwrap_err();
+    assert!(error.contains("operand stack was empty"), "{error}");
+
+    let error = validate_wat("(module (func i32.const 1))").unwrap_err();
+    assert!(error.contains("1 operands remained"), "{error}");
+}
+
+#[test]
+fn unknown_indices() {
+    // (func call 1)
+    let wasm = [
+        0u8, 0x61, 0x73, 0x6D, 1, 0, 0, 0, // preamble
+        1, 4, 1, 0x60, 0, 0, // type section
+        3, 2, 1, 0, // function section
+        10, 6, 1, 4, 0, 0x10, 1, 0x0B, // code section
+    ];
+    let error = validate_module(wasm.as_slice()).unwrap_err();
+    assert!(error.to_string().contains("unknown function 1"), "{error}");
+    assert_eq!(error.offset(), Some(23));
+}
+
+#[test]
+fn immutable_global() {
+    let error = validate_wat("(module (global i32 (i32.const 0)) (func i32.const 1 global.set 0))")
+        .unwrap_err();
+    assert!(error.contains("global 0 is immutable"), "{error}");
+}
+
+#[test]
+fn undeclared_function_reference() {
+    // (func (result funcref) ref.func 0)
+    let wasm = [
+        0u8, 0x61, 0x73, 0x6D, 1, 0, 0, 0, // preamble
+        1, 5, 1, 0x60, 0, 1, 0x70, // type section
+        3, 2, 1, 0, // function section
+        10, 6, 1, 4, 0, 0xD2, 0, 0x0B, // code section
+    ];
+    let error = validate_module(wasm.as_slice()).unwrap_err();
+    assert!(error.to_string().contains("undeclared function reference"));
+}
+
+#[test]
+fn data_count_mismatch() {
+    let wasm = [
+        0u8, 0x61, 0x73, 0x6D, 1, 0, 0, 0, // preamble
+        12, 1, 2, // data count section
+        11, 4, 1, 1, 1, 42, // data section
+    ];
+    let error = validate_module(wasm.as_slice()).unwrap_err();
+    assert!(error.to_string().contains("specifies 2 data segments"));
+}
+
+#[test]
+fn data_count_required() {
+    let wasm = [
+        0u8, 0x61, 0x73, 0x6D, 1, 0, 0, 0, // preamble
+        1, 4, 1, 0x60, 0, 0, // type section
+        3, 2, 1, 0, // function section
+        5, 3, 1, 0, 1, // memory section
+        10, 7, 1, 5, 0, 0xFC, 9, 0, 0x0B, // code section
+        11, 4, 1, 1, 1, 42, // data section
+    ];
+    let error = validate_module(wasm.as_slice()).unwrap_err();
+    assert!(error.to_string().contains("data count section is required"));
+}
+
+#[test]
+fn function_count_mismatch() {
+    let wasm = [
+        0u8, 0x61, 0x73, 0x6D, 1, 0, 0, 0, // preamble
+        1, 4, 1, 0x60, 0, 0, // type section
+        3, 3, 2, 0, 0, // function section
+        10, 4, 1, 2, 0, 0x0B, // code section
+    ];
+    let error = validate_module(wasm.as_slice()).unwrap_err();
+    assert!(error
+        .to_string()
+        .contains("2 functions were declared but 1"));
+}
+
+#[test]
+fn section_order() {
+    let wasm = [
+        0u8, 0x61, 0x73, 0x6D, 1, 0, 0, 0, // preamble
+        3, 1, 0, // function section
+        1, 1, 0, // type section
+    ];
+    let error = validate_module(wasm.as_slice()).unwrap_err();
+    assert!(error.to_string().contains("out of order"));
+}
+
+#[test]
+fn element_segment_type_mismatch() {
+    let error = validate_wat("(module (table 1 externref) (func $f) (elem (i32.const 0) func $f))")
+        .unwrap_err();
+    assert!(
+        error.contains("expected externref but got funcref"),
+        "{error}"
+    );
+}
+
+#[cfg(feature = "function-references")]
+#[test]
+fn typed_function_references() {
+    let wat = r#"(module
+    (type $t (func (param i32) (result i32)))
+    (table 1 (ref null $t))
+    (global (ref null $t) (ref.null $t))
+    (elem declare func $f)
+    (func $f (type $t) local.get 0)
+    (func (param (ref null $t)) (result i32)
+        i32.const 1
+        local.get 0
+        call_ref $t)
+    (func (result (ref $t))
+        ref.func $f)
+    (func (param (ref null $t)) (result funcref)
+        local.get 0
+        ref.as_non_null)
+    (func (param (ref null $t)) (result (ref $t))
+        block (result (ref $t))
+            local.get 0
+            br_on_non_null 0
+            unreachable
+        end)
+    (func (param (ref null $t)) (result i32)
+        i32.const 2
+        local.get 0
+        br_on_null 0
+        call_ref $t))"#;
+    validate_wat(wat).unwrap();
+
+    let error = validate_wat(
+        r#"(module
+    (type $t (func))
+    (func (param funcref) local.get 0 call_ref $t))"#,
+    )
+    .unwrap_err();
+    assert!(
+        error.contains("expected (ref null 0) but got funcref"),
+        "{error}"
+    );
+
+    let error = validate_wat(
+        "(module (type (func)) (func (param (ref 0)) (result (ref null 5)) unreachable))",
+    )
+    .unwrap_err();
+    assert!(error.contains("unknown type 5"), "{error}");
+}
+
+#[cfg(feature = "exceptions")]
+#[test]
+fn try_table() {
+    let wat = r#"(module
+    (tag $e (param i32))
+    (func (param exnref) (result i32 exnref)
+        block $handler (result i32 exnref)
+            try_table (catch_ref $e $handler)
+                i32.const 1
+                throw $e
+            end
+            unreachable
+        end)
+    (func (result i32)
+        block $handler (result i32)
+            try_table (result i32) (catch $e $handler)
+                i32.const 2
+            end
+        end)
+    (func
+        block $handler (result exnref)
+            try_table (catch_all_ref $handler)
+            end
+            return
+        end
+        throw_ref))"#;
+    validate_wat(wat).unwrap();
+
+    let error = validate_wat(
+        r#"(module
+    (tag $e (param i32))
+    (func
+        block $handler
+            try_table (catch $e $handler)
+            end
+        end))"#,
+    )
+    .unwrap_err();
+    assert!(
+        error.contains("catch clause values do not match the types of its label"),
+        "{error}"
+    );
+}
+
+#[test]
+fn duplicate_export() {
+    let error =
+        validate_wat(r#"(module (func (export "a")) (memory (export "a") 1))"#).unwrap_err();
+    assert!(error.contains("duplicate export name"), "{error}");
+}
+
+#[test]
+fn random_modules_are_valid() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(0x5EED);
+    let mut unstructured = vec![0u8; 4096];
+    for _ in 0..200 {
+        rng.fill_bytes(&mut unstructured);
+        let wasm = wasm_smith::Module::arbitrary_take_rest(Unstructured::new(&unstructured))
+            .unwrap()
+            .to_bytes();
+
+        if let Err(e) = validate_module(wasm.as_slice()) {
+            panic!("{e}\n{}", wasmprinter::print_bytes(&wasm).unwrap());
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use wasmiter::{
+    component::{DataMode, ElementMode, Export, Import},
+    index::{FuncIdx, TypeIdx},
+    input::{Input, Window},
+    instruction_set::Instruction,
+    parser::{Offset, Parsed},
+    types::{GlobalType, ValType},
+    visit::{self, ModuleVisitor},
+};
+
+#[derive(Default)]
+struct Counts {
+    types: usize,
+    imports: usize,
+    functions: usize,
+    globals: usize,
+    exports: usize,
+    element_segments: usize,
+    element_functions: usize,
+    codes: usize,
+    locals: u32,
+    data_segments: usize,
+    data_bytes: u64,
+    instructions: usize,
+}
+
+impl ModuleVisitor for Counts {
+    fn visit_type<I: Input>(
+        &mut self,
+        _: wasmiter::component::ResultType<u64, I>,
+        _: wasmiter::component::ResultType<u64, I>,
+    ) -> Parsed<()> {
+        self.types += 1;
+        Ok(())
+    }
+
+    fn visit_import<I: Input>(&mut self, _: &Import<I>) -> Parsed<()> {
+        self.imports += 1;
+        Ok(())
+    }
+
+    fn visit_function(&mut self, _: TypeIdx) -> Parsed<()> {
+        self.functions += 1;
+        Ok(())
+    }
+
+    fn visit_global(&mut self, _: GlobalType) -> Parsed<()> {
+        self.globals += 1;
+        Ok(())
+    }
+
+    fn visit_export<I: Input>(&mut self, _: &Export<I>) -> Parsed<()> {
+        self.exports += 1;
+        Ok(())
+    }
+
+    fn visit_element_segment<O: Offset, I: Input>(&mut self, _: &ElementMode<O, I>) -> Parsed<()> {
+        self.element_segments += 1;
+        Ok(())
+    }
+
+    fn visit_element_function(&mut self, _: FuncIdx) -> Parsed<()> {
+        self.element_functions += 1;
+        Ok(())
+    }
+
+    fn visit_code<I: Input>(&mut self, _: &wasmiter::component::Code<I>) -> Parsed<()> {
+        self.codes += 1;
+        Ok(())
+    }
+
+    fn visit_local_group(&mut self, count: u32, _: ValType) -> Parsed<()> {
+        self.locals += count;
+        Ok(())
+    }
+
+    fn visit_data_segment<O: Offset, I: Input>(&mut self, _: &DataMode<O, I>) -> Parsed<()> {
+        self.data_segments += 1;
+        Ok(())
+    }
+
+    fn visit_data<I: Input>(&mut self, data: &Window<I>) -> Parsed<()> {
+        self.data_bytes += data.length();
+        Ok(())
+    }
+
+    fn visit_instruction<I: Input>(&mut self, _: &mut Instruction<'_, I>) -> Parsed<()> {
+        self.instructions += 1;
+        Ok(())
+    }
+}
+
+#[test]
+fn counts_entries_and_instructions() {
+    let wat = r#"(module
+    (import "env" "f" (func $imported (param i32)))
+    (memory 1)
+    (table 2 funcref)
+    (global i32 (i32.const 1))
+    (func $add (export "add") (param i32 i32) (result i32) (local i64 i64 f32)
+        local.get 0
+        local.get 1
+        i32.add)
+    (elem (i32.const 0) $add $imported)
+    (data (i32.const 8) "abc"))
+"#;
+    let wasm = wat::parse_str(wat).unwrap();
+    let mut counts = Counts::default();
+    visit::visit_module(wasm.as_slice(), &mut counts).unwrap();
+
+    assert_eq!(counts.types, 2);
+    assert_eq!(counts.imports, 1);
+    assert_eq!(counts.functions, 1);
+    assert_eq!(counts.globals, 1);
+    assert_eq!(counts.exports, 1);
+    assert_eq!(counts.element_segments, 1);
+    assert_eq!(counts.element_functions, 2);
+    assert_eq!(counts.codes, 1);
+    assert_eq!(counts.locals, 3);
+    assert_eq!(counts.data_segments, 1);
+    assert_eq!(counts.data_bytes, 3);
+    // global (2) + elem offset (2) + function body (4) + data offset (2)
+    assert_eq!(counts.instructions, 10);
+}
+
+#[test]
+fn duplicate_sections() {
+    let mut wasm = b"\0asm\x01\0\0\0".to_vec();
+    // global section with one global
+    wasm.extend_from_slice(&[6, 6, 1, 0x7F, 0, 0x41, 1, 0x0B]);
+    // global section with two globals
+    wasm.extend_from_slice(&[6, 11, 2, 0x7F, 0, 0x41, 1, 0x0B, 0x7E, 0, 0x42, 2, 0x0B]);
+    let sections = wasmiter::parse_module_sections(wasm.as_slice()).unwrap();
+
+    let error = visit::visit_module_sections(&sections, &mut Counts::default()).unwrap_err();
+    assert!(error.to_string().contains("duplicate section with id 6"));
+
+    let mut counts = Counts::default();
+    visit::visit_module_sections_with(&sections, visit::DuplicateSections::KeepFirst, &mut counts)
+        .unwrap();
+    assert_eq!(counts.globals, 1);
+
+    let mut counts = Counts::default();
+    visit::visit_module_sections_with(&sections, visit::DuplicateSections::KeepLast, &mut counts)
+        .unwrap();
+    assert_eq!(counts.globals, 2);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn errors_from_visitor() {
+    struct Rejecting;
+
+    impl ModuleVisitor for Rejecting {
+        fn visit_global(&mut self, global: GlobalType) -> Parsed<()> {
+            if global.value_type() == ValType::I64 {
+                Err(wasmiter::parser::Error::bad_format(
+                    "64-bit globals are not allowed",
+                ))
+            } else {
+                Err(wasmiter::parser::Error::custom(format!(
+                    "unsupported global of type {}",
+                    global.value_type()
+                )))
+            }
+        }
+    }
+
+    let wasm = wat::parse_str("(module (global i64 (i64.const 0)))").unwrap();
+    let error = visit::visit_module(wasm.as_slice(), &mut Rejecting).unwrap_err();
+    let message = format!("{error:#}");
+    assert!(
+        message.starts_with("input was malformed\n- 64-bit globals are not allowed\n- "),
+        "{message}"
+    );
+
+    let wasm = wat::parse_str("(module (global f32 (f32.const 0)))").unwrap();
+    let error = visit::visit_module(wasm.as_slice(), &mut Rejecting).unwrap_err();
+    let message = format!("{error:#}");
+    assert!(
+        message.starts_with("unsupported global of type f32\n- "),
+        "{message}"
+    );
+}